@@ -4,6 +4,7 @@ use near_primitives::errors::{ChunkAccessError, EpochError, StorageError};
 use near_primitives::shard_layout::ShardLayoutError;
 use near_primitives::sharding::{BadHeaderForProtocolVersionError, ChunkHash, ShardChunkHeader};
 use near_primitives::types::{BlockHeight, EpochId, ShardId};
+use near_primitives::validator_signer::ValidatorSignerError;
 use near_time::Utc;
 use std::io;
 
@@ -55,6 +56,16 @@ pub enum QueryError {
     },
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum SimulateTransactionError {
+    #[error("Transaction is invalid: {0:?}")]
+    InvalidTransaction(near_primitives::errors::InvalidTxError),
+    #[error("Shard {0} is not tracked by this node")]
+    UnavailableShard(ShardId),
+    #[error("Internal error occurred: {0}")]
+    InternalError(String),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// The block is already known
@@ -214,6 +225,9 @@ pub enum Error {
     /// Validator error.
     #[error("Validator Error: {0}")]
     ValidatorError(String),
+    /// Our validator signer didn't produce a signature.
+    #[error("Signer Error: {0}")]
+    SignerError(#[from] ValidatorSignerError),
     /// Epoch out of bounds. Usually if received block is too far in the future or alternative fork.
     #[error("Epoch Out Of Bounds: {:?}", _0)]
     EpochOutOfBounds(EpochId),
@@ -276,6 +290,7 @@ impl Error {
             | Error::IOErr(_)
             | Error::Other(_)
             | Error::ValidatorError(_)
+            | Error::SignerError(_)
             | Error::EpochOutOfBounds(_)
             | Error::ChallengedBlockOnChain
             | Error::CannotBeFinalized
@@ -357,6 +372,7 @@ impl Error {
             Error::IOErr(_) => "io_err",
             Error::Other(_) => "other",
             Error::ValidatorError(_) => "validator_error",
+            Error::SignerError(_) => "signer_error",
             Error::EpochOutOfBounds(_) => "epoch_out_of_bounds",
             Error::ChallengedBlockOnChain => "challenged_block_on_chain",
             Error::CannotBeFinalized => "cannot_be_finalized",
@@ -60,7 +60,7 @@ use near_primitives::epoch_block_info::BlockInfo;
 use near_primitives::errors::EpochError;
 use near_primitives::hash::{hash, CryptoHash};
 use near_primitives::merkle::{merklize, verify_path, PartialMerkleTree};
-use near_primitives::receipt::Receipt;
+use near_primitives::receipt::{Receipt, ReceiptEnum};
 use near_primitives::sandbox::state_patch::SandboxStatePatch;
 use near_primitives::shard_layout::{account_id_to_shard_id, ShardLayout, ShardUId};
 use near_primitives::sharding::{
@@ -431,6 +431,7 @@ impl Chain {
             chain_genesis.height,
             chain_config.save_trie_changes,
         );
+        chain_store.save_latest_witnesses = chain_config.save_latest_witnesses;
         let mut store_update = chain_store.store_update();
         let (block_head, header_head) = match store_update.head() {
             Ok(block_head) => {
@@ -2390,6 +2391,11 @@ impl Chain {
             // For the first block of the epoch we check if we need to start download states for
             // shards that we will care about in the next epoch. If there is no state to be downloaded,
             // we consider that we are caught up, otherwise not
+            if let Err(err) =
+                self.shard_tracker.update_tracking_transition_metrics(me.as_ref(), &prev_hash)
+            {
+                warn!(target: "chain", ?err, "failed to update shard tracking metrics");
+            }
             let state_sync_info = self.get_state_sync_info(me, block)?;
             Ok((state_sync_info.is_none(), state_sync_info))
         } else {
@@ -3437,8 +3443,7 @@ impl Chain {
     }
 
     /// Returns execution status based on the list of currently existing outcomes
-    fn get_execution_status(
-        &self,
+    pub(crate) fn get_execution_status(
         outcomes: &[ExecutionOutcomeWithIdView],
         transaction_hash: &CryptoHash,
     ) -> FinalExecutionStatus {
@@ -3503,7 +3508,7 @@ impl Chain {
     ) -> Result<FinalExecutionOutcomeView, Error> {
         let mut outcomes = Vec::new();
         self.get_recursive_transaction_results(&mut outcomes, transaction_hash, true)?;
-        let status = self.get_execution_status(&outcomes, transaction_hash);
+        let status = Self::get_execution_status(&outcomes, transaction_hash);
         let receipts_outcome = outcomes.split_off(1);
         let transaction = self.chain_store.get_transaction(transaction_hash)?.ok_or_else(|| {
             Error::DBNotFoundErr(format!("Transaction {} is not found", transaction_hash))
@@ -3535,7 +3540,7 @@ impl Chain {
             )));
         }
 
-        let status = self.get_execution_status(&outcomes, transaction_hash);
+        let status = Self::get_execution_status(&outcomes, transaction_hash);
         let receipts_outcome = outcomes.split_off(1);
         let transaction_outcome = outcomes.pop().unwrap();
         Ok(FinalExecutionOutcomeView { status, transaction, transaction_outcome, receipts_outcome })
@@ -4506,7 +4511,7 @@ impl Chain {
     ) -> HashMap<ShardId, Vec<Receipt>> {
         let mut result = HashMap::new();
         for receipt in receipts {
-            let shard_id = account_id_to_shard_id(receipt.receiver_id(), shard_layout);
+            let shard_id = receipt.receiver_shard_id(shard_layout);
             let entry = result.entry(shard_id).or_insert_with(Vec::new);
             entry.push(receipt)
         }
@@ -4526,9 +4531,16 @@ impl Chain {
             shard_layout.shard_ids().map(|shard_id| (shard_id, vec![])).collect();
         let mut cache = HashMap::new();
         for receipt in receipts {
-            let &mut shard_id = cache
-                .entry(receipt.receiver_id())
-                .or_insert_with(|| account_id_to_shard_id(receipt.receiver_id(), shard_layout));
+            // Global contract distribution receipts target a shard rather than the shard of
+            // their receiver, so they can't share the per-account cache.
+            let shard_id = match receipt.receipt() {
+                ReceiptEnum::GlobalContractDistribution(_) => {
+                    receipt.receiver_shard_id(shard_layout)
+                }
+                _ => *cache
+                    .entry(receipt.receiver_id())
+                    .or_insert_with(|| account_id_to_shard_id(receipt.receiver_id(), shard_layout)),
+            };
             // This unwrap should be safe as we pre-populated the map with all
             // valid shard ids.
             result.get_mut(&shard_id).unwrap().push(receipt);
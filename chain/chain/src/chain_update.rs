@@ -156,6 +156,7 @@ impl<'a> ChainUpdate<'a> {
                         *block_hash,
                         shard_id,
                         apply_result.proof,
+                        apply_result.storage_proof_size_attribution,
                         apply_result.applied_receipts_hash,
                         apply_result.contract_updates,
                     );
@@ -186,6 +187,7 @@ impl<'a> ChainUpdate<'a> {
                         *block_hash,
                         shard_uid.shard_id(),
                         apply_result.proof,
+                        apply_result.storage_proof_size_attribution,
                         apply_result.applied_receipts_hash,
                         apply_result.contract_updates,
                     );
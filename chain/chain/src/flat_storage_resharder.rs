@@ -21,7 +21,8 @@ use near_primitives::trie_key::col::{self, ALL_COLUMNS_WITH_NAMES};
 use near_primitives::trie_key::trie_key_parsers::{
     parse_account_id_from_access_key_key, parse_account_id_from_account_key,
     parse_account_id_from_contract_code_key, parse_account_id_from_contract_data_key,
-    parse_account_id_from_received_data_key, parse_account_id_from_trie_key_with_separator,
+    parse_account_id_from_global_contract_owner_key, parse_account_id_from_received_data_key,
+    parse_account_id_from_trie_key_with_separator,
 };
 use near_primitives::types::AccountId;
 use near_store::adapter::flat_store::{FlatStoreAdapter, FlatStoreUpdateAdapter};
@@ -553,6 +554,21 @@ fn shard_split_handle_key_value(
         | col::PROMISE_YIELD_INDICES
         | col::PROMISE_YIELD_TIMEOUT
         | col::PROMISE_YIELD_RECEIPT => copy_kv_to_all_children(&status, key, value, store_update),
+        // The global contract registry is replicated to every shard, while the storage
+        // charged for publishing to it belongs to the shard of the owner.
+        col::EXTENDED_COLUMN
+            if key.starts_with(&col::GLOBAL_CONTRACT_CODE)
+                || key.starts_with(&col::GLOBAL_CONTRACT_PUBLISHER) =>
+        {
+            copy_kv_to_all_children(&status, key, value, store_update)
+        }
+        col::EXTENDED_COLUMN if key.starts_with(&col::GLOBAL_CONTRACT_OWNER) => copy_kv_to_child(
+            &status,
+            key,
+            value,
+            store_update,
+            parse_account_id_from_global_contract_owner_key,
+        )?,
         col::BUFFERED_RECEIPT_INDICES | col::BUFFERED_RECEIPT => {
             copy_kv_to_left_child(&status, key, value, store_update)
         }
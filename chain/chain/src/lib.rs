@@ -40,6 +40,7 @@ pub mod validate;
 
 pub mod rayon_spawner;
 pub mod sharding;
+pub mod signing_ledger;
 #[cfg(test)]
 mod tests;
 pub mod update_shard;
@@ -225,6 +225,7 @@ impl ReshardingManager {
                 *block_hash,
                 new_shard_uid.shard_id(),
                 Some(partial_storage),
+                None,
                 CryptoHash::default(),
                 // No contract code is accessed or deployed during resharding.
                 // TODO(#11099): Confirm if sending no contracts is ok here.
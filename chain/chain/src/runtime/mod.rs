@@ -1,9 +1,10 @@
+use crate::near_chain_primitives::error::SimulateTransactionError;
 use crate::types::{
-    ApplyChunkBlockContext, ApplyChunkResult, ApplyChunkShardContext,
+    ApplyChunkBlockContext, ApplyChunkResult, ApplyChunkShardContext, BlockHeader,
     PrepareTransactionsBlockContext, PrepareTransactionsChunkContext, PrepareTransactionsLimit,
     PreparedTransactions, RuntimeAdapter, RuntimeStorageConfig, StorageDataSource, Tip,
 };
-use crate::Error;
+use crate::{Chain, Error};
 use borsh::BorshDeserialize;
 use errors::FromStateViewerErrors;
 use near_async::time::{Duration, Instant};
@@ -16,6 +17,7 @@ use near_parameters::{ActionCosts, ExtCosts, RuntimeConfig, RuntimeConfigStore};
 use near_pool::types::TransactionGroupIterator;
 use near_primitives::account::{AccessKey, Account};
 use near_primitives::apply::ApplyChunkReason;
+use near_primitives::bandwidth_scheduler::BlockBandwidthRequests;
 use near_primitives::congestion_info::{
     CongestionControl, ExtendedCongestionInfo, RejectTransactionReason, ShardAcceptsTransactions,
 };
@@ -30,12 +32,15 @@ use near_primitives::transaction::SignedTransaction;
 use near_primitives::trie_key::TrieKey;
 use near_primitives::types::{
     AccountId, Balance, BlockHeight, EpochHeight, EpochId, EpochInfoProvider, Gas, MerkleHash,
-    ShardId, StateChangeCause, StateRoot, StateRootNode,
+    ShardId, StateChangeCause, StateChanges, StateChangesExt, StateRoot, StateRootNode,
 };
 use near_primitives::version::{ProtocolFeature, ProtocolVersion};
+#[cfg(feature = "sandbox")]
+use near_primitives::views::ReceiptExecutionTraceView;
 use near_primitives::views::{
-    AccessKeyInfoView, CallResult, ContractCodeView, QueryRequest, QueryResponse,
-    QueryResponseKind, ViewStateResult,
+    AccessKeyInfoView, CallResult, ContractCodeView, ExecutionOutcomeWithIdView,
+    FinalExecutionOutcomeView, QueryRequest, QueryResponse, QueryResponseKind,
+    SimulatedTransactionView, ViewStateResult,
 };
 use near_store::adapter::{StoreAdapter, StoreUpdateAdapter};
 use near_store::config::StateSnapshotType;
@@ -48,6 +53,7 @@ use near_store::{
 use near_vm_runner::ContractCode;
 use near_vm_runner::{precompile_contract, ContractRuntimeCache, FilesystemContractRuntimeCache};
 use node_runtime::adapter::ViewRuntimeAdapter;
+use node_runtime::simulation::{SimulationError, TransactionSimulation};
 use node_runtime::state_viewer::{TrieViewer, ViewApplyState};
 use node_runtime::{
     validate_transaction, verify_and_charge_transaction, ApplyState, Runtime,
@@ -64,6 +70,10 @@ pub mod migrations;
 #[cfg(test)]
 mod tests;
 
+/// Number of receipt traces kept in memory by sandbox nodes.
+#[cfg(feature = "sandbox")]
+const SANDBOX_RECEIPT_TRACES_CACHE_SIZE: usize = 1000;
+
 /// Defines Nightshade state transition and validator rotation.
 /// TODO: this possibly should be merged with the runtime cargo or at least reconciled on the interfaces.
 pub struct NightshadeRuntime {
@@ -78,6 +88,9 @@ pub struct NightshadeRuntime {
     epoch_manager: Arc<EpochManagerHandle>,
     migration_data: Arc<MigrationData>,
     gc_num_epochs_to_keep: u64,
+    /// Traces of the latest applied receipts, served by the `sandbox_trace_receipt` RPC method.
+    #[cfg(feature = "sandbox")]
+    receipt_traces: std::sync::Mutex<lru::LruCache<CryptoHash, ReceiptExecutionTraceView>>,
 }
 
 impl NightshadeRuntime {
@@ -97,6 +110,10 @@ impl NightshadeRuntime {
             Some(store) => store,
             None => RuntimeConfigStore::for_chain_id(&genesis_config.chain_id),
         };
+        // Sandbox nodes trace every receipt, so that a failed cross-contract call can be
+        // inspected after the fact.
+        #[cfg(feature = "sandbox")]
+        let runtime_config_store = runtime_config_store.with_execution_tracing();
 
         let runtime = Runtime::new();
         let trie_viewer = TrieViewer::new(trie_viewer_state_size_limit, max_gas_burnt_view);
@@ -134,6 +151,10 @@ impl NightshadeRuntime {
             epoch_manager,
             migration_data,
             gc_num_epochs_to_keep: gc_num_epochs_to_keep.max(MIN_GC_NUM_EPOCHS_TO_KEEP),
+            #[cfg(feature = "sandbox")]
+            receipt_traces: std::sync::Mutex::new(lru::LruCache::new(
+                std::num::NonZeroUsize::new(SANDBOX_RECEIPT_TRACES_CACHE_SIZE).unwrap(),
+            )),
         })
     }
 
@@ -448,6 +469,14 @@ impl NightshadeRuntime {
 
         let shard_uid = self.get_shard_uid_from_prev_hash(shard_id, prev_block_hash)?;
 
+        #[cfg(feature = "sandbox")]
+        {
+            let mut receipt_traces = self.receipt_traces.lock().unwrap();
+            for trace in &apply_result.receipt_traces {
+                receipt_traces.put(trace.receipt_id, trace.clone());
+            }
+        }
+
         let result = ApplyChunkResult {
             trie_changes: WrappedTrieChanges::new(
                 self.get_tries(),
@@ -464,6 +493,7 @@ impl NightshadeRuntime {
             total_gas_burnt,
             total_balance_burnt,
             proof: apply_result.proof,
+            storage_proof_size_attribution: apply_result.storage_proof_size_attribution,
             processed_delayed_receipts: apply_result.processed_delayed_receipts,
             processed_yield_timeouts: apply_result.processed_yield_timeouts,
             applied_receipts_hash: hash(&borsh::to_vec(receipts).unwrap()),
@@ -471,6 +501,7 @@ impl NightshadeRuntime {
             bandwidth_requests: apply_result.bandwidth_requests,
             bandwidth_scheduler_state_hash: apply_result.bandwidth_scheduler_state_hash,
             contract_updates: apply_result.contract_updates,
+            receipt_traces: apply_result.receipt_traces,
         };
 
         Ok(result)
@@ -1030,8 +1061,9 @@ impl RuntimeAdapter for NightshadeRuntime {
                     block_hash: *block_hash,
                 })
             }
-            QueryRequest::CallFunction { account_id, method_name, args } => {
+            QueryRequest::CallFunction { account_id, method_name, args, function_gas_profile } => {
                 let mut logs = vec![];
+                let mut profile = function_gas_profile.then(String::new);
                 let (epoch_height, current_protocol_version) = {
                     let epoch_manager = self.epoch_manager.read();
                     let epoch_info = epoch_manager.get_epoch_info(epoch_id).map_err(|err| {
@@ -1058,6 +1090,7 @@ impl RuntimeAdapter for NightshadeRuntime {
                         method_name,
                         args.as_ref(),
                         &mut logs,
+                        profile.as_mut(),
                         self.epoch_manager.as_ref(),
                         current_protocol_version,
                     )
@@ -1072,6 +1105,7 @@ impl RuntimeAdapter for NightshadeRuntime {
                     kind: QueryResponseKind::CallResult(CallResult {
                         result: call_function_result,
                         logs,
+                        function_gas_profile: profile,
                     }),
                     block_height,
                     block_hash: *block_hash,
@@ -1141,6 +1175,108 @@ impl RuntimeAdapter for NightshadeRuntime {
         }
     }
 
+    fn simulate_transaction(
+        &self,
+        block: &BlockHeader,
+        state_roots: HashMap<ShardId, StateRoot>,
+        transaction: &SignedTransaction,
+        verify_signature: bool,
+        check_nonce: bool,
+    ) -> Result<SimulatedTransactionView, SimulateTransactionError> {
+        let internal_error =
+            |err: &dyn std::fmt::Display| SimulateTransactionError::InternalError(err.to_string());
+        let epoch_id = *block.epoch_id();
+        let epoch_info =
+            self.epoch_manager.get_epoch_info(&epoch_id).map_err(|err| internal_error(&err))?;
+        let current_protocol_version = epoch_info.protocol_version();
+        let shard_layout =
+            self.epoch_manager.get_shard_layout(&epoch_id).map_err(|err| internal_error(&err))?;
+        let shard_id = account_id_to_shard_id(transaction.transaction.signer_id(), &shard_layout);
+        let tries = state_roots
+            .into_iter()
+            .map(|(shard_id, state_root)| {
+                let trie = self.get_view_trie_for_shard(shard_id, block.hash(), state_root)?;
+                Ok((shard_id, trie))
+            })
+            .collect::<Result<HashMap<_, _>, Error>>()
+            .map_err(|err| internal_error(&err))?;
+        // The transaction is applied as if it was included in the chunk following `block`. The
+        // hash of that block isn't known yet, so the hash of `block` stands in for it.
+        let apply_state = ApplyState {
+            apply_reason: None,
+            block_height: block.height() + 1,
+            prev_block_hash: *block.hash(),
+            block_hash: *block.hash(),
+            shard_id,
+            epoch_id,
+            epoch_height: epoch_info.epoch_height(),
+            gas_price: block.next_gas_price(),
+            block_timestamp: block.raw_timestamp(),
+            gas_limit: None,
+            random_seed: *block.random_value(),
+            current_protocol_version,
+            config: self.runtime_config_store.get_config(current_protocol_version).clone(),
+            cache: Some(self.compiled_contract_cache.handle()),
+            is_new_chunk: true,
+            migration_data: Arc::new(MigrationData::default()),
+            migration_flags: MigrationFlags::default(),
+            congestion_info: Default::default(),
+            bandwidth_requests: BlockBandwidthRequests::empty(),
+        };
+        let TransactionSimulation { transaction_outcome, receipt_outcomes, state_changes } = self
+            .runtime
+            .simulate_transaction(
+                tries,
+                &apply_state,
+                transaction,
+                verify_signature,
+                check_nonce,
+                self.epoch_manager.as_ref(),
+            )
+            .map_err(|err| match err {
+                SimulationError::InvalidTransaction(err) => {
+                    SimulateTransactionError::InvalidTransaction(err)
+                }
+                SimulationError::UnavailableShard(shard_id) => {
+                    SimulateTransactionError::UnavailableShard(shard_id)
+                }
+                SimulationError::RuntimeError(err) => internal_error(&err),
+            })?;
+
+        let outcomes = std::iter::once(transaction_outcome)
+            .chain(receipt_outcomes)
+            .map(|outcome_with_id| ExecutionOutcomeWithIdView {
+                proof: vec![],
+                block_hash: *block.hash(),
+                id: outcome_with_id.id,
+                outcome: outcome_with_id.outcome.into(),
+            })
+            .collect::<Vec<_>>();
+        let status = Chain::get_execution_status(&outcomes, &transaction.get_hash());
+        let gas_burnt = outcomes.iter().map(|outcome| outcome.outcome.gas_burnt).sum();
+        let tokens_burnt = outcomes.iter().map(|outcome| outcome.outcome.tokens_burnt).sum();
+        let state_changes = StateChanges::from_changes(state_changes.into_iter().map(Ok))
+            .map_err(|err| internal_error(&err))?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let mut outcomes = outcomes.into_iter();
+        let transaction_outcome = outcomes.next().expect("transaction outcome is always present");
+        Ok(SimulatedTransactionView {
+            block_hash: *block.hash(),
+            block_height: block.height(),
+            final_execution_outcome: FinalExecutionOutcomeView {
+                status,
+                transaction: transaction.clone().into(),
+                transaction_outcome,
+                receipts_outcome: outcomes.collect(),
+            },
+            gas_burnt,
+            tokens_burnt,
+            state_changes,
+        })
+    }
+
     // Wrapper to get the metrics.
     fn obtain_state_part(
         &self,
@@ -1349,6 +1485,11 @@ impl RuntimeAdapter for NightshadeRuntime {
         });
         Ok(())
     }
+
+    #[cfg(feature = "sandbox")]
+    fn get_receipt_trace(&self, receipt_id: &CryptoHash) -> Option<ReceiptExecutionTraceView> {
+        self.receipt_traces.lock().unwrap().get(receipt_id).cloned()
+    }
 }
 
 /// Get the limit on the number of new receipts imposed by the local congestion control.
@@ -1497,6 +1638,7 @@ impl node_runtime::adapter::ViewRuntimeAdapter for NightshadeRuntime {
         method_name: &str,
         args: &[u8],
         logs: &mut Vec<String>,
+        function_gas_profile: Option<&mut String>,
         epoch_info_provider: &dyn EpochInfoProvider,
         current_protocol_version: ProtocolVersion,
     ) -> Result<Vec<u8>, node_runtime::state_viewer::errors::CallFunctionError> {
@@ -1519,6 +1661,7 @@ impl node_runtime::adapter::ViewRuntimeAdapter for NightshadeRuntime {
             method_name,
             args,
             logs,
+            function_gas_profile,
             epoch_info_provider,
         )
     }
@@ -0,0 +1,452 @@
+//! Protection against the validator signing conflicting messages.
+//!
+//! Every approval, block header, chunk header and chunk endorsement signed by this node is
+//! recorded in [`DBCol::SigningLedger`] before the signature is released. A different message for
+//! a height (and shard) that already has a recorded signature is refused. This keeps a validator
+//! from equivocating when the same key is mistakenly used by two hosts, provided that the ledger
+//! is moved together with the key (see `neard database signing-ledger`). Records further than
+//! [`SIGNING_WINDOW`] below the highest signed height are dropped, and messages for such heights
+//! refused.
+use crate::metrics;
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_crypto::PublicKey;
+use near_primitives::block::{Approval, BlockHeader};
+use near_primitives::hash::CryptoHash;
+use near_primitives::sharding::ShardChunkHeader;
+use near_primitives::types::{BlockHeight, ShardId};
+use near_primitives::validator_signer::SIGNING_WINDOW;
+use near_store::{DBCol, Store};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    strum::IntoStaticStr,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedMessageKind {
+    Approval,
+    BlockHeader,
+    ChunkHeader,
+    ChunkEndorsement,
+}
+
+/// A message signed by a validator, identified by its position in the chain and the hash of
+/// the signed content.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignedMessage {
+    pub kind: SignedMessageKind,
+    /// Set for chunk headers and endorsements.
+    pub shard_id: Option<ShardId>,
+    /// Target height for approvals, height created for chunks.
+    pub height: BlockHeight,
+    pub hash: CryptoHash,
+}
+
+impl SignedMessage {
+    pub fn approval(approval: &Approval) -> Self {
+        Self {
+            kind: SignedMessageKind::Approval,
+            shard_id: None,
+            height: approval.target_height,
+            hash: CryptoHash::hash_borsh(&approval.inner),
+        }
+    }
+
+    pub fn block_header(header: &BlockHeader) -> Self {
+        Self {
+            kind: SignedMessageKind::BlockHeader,
+            shard_id: None,
+            height: header.height(),
+            hash: *header.hash(),
+        }
+    }
+
+    pub fn chunk_header(header: &ShardChunkHeader) -> Self {
+        Self {
+            kind: SignedMessageKind::ChunkHeader,
+            shard_id: Some(header.shard_id()),
+            height: header.height_created(),
+            hash: header.chunk_hash().0,
+        }
+    }
+
+    pub fn chunk_endorsement(header: &ShardChunkHeader) -> Self {
+        Self {
+            kind: SignedMessageKind::ChunkEndorsement,
+            shard_id: Some(header.shard_id()),
+            height: header.height_created(),
+            hash: header.chunk_hash().0,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SigningLedgerError {
+    #[error(
+        "refusing to sign {kind:?} at height {height} for shard {shard_id:?}: \
+        already signed {existing} instead of {requested}"
+    )]
+    Conflict {
+        kind: SignedMessageKind,
+        shard_id: Option<ShardId>,
+        height: BlockHeight,
+        existing: CryptoHash,
+        requested: CryptoHash,
+    },
+    #[error(
+        "refusing to sign {kind:?} at height {height} for shard {shard_id:?}: \
+        heights below {min_height} are no longer tracked"
+    )]
+    TooOld {
+        kind: SignedMessageKind,
+        shard_id: Option<ShardId>,
+        height: BlockHeight,
+        min_height: BlockHeight,
+    },
+    #[error(
+        "no signing ledger found for validator key {0}; if this key was used on another host, \
+        import its ledger with `neard database signing-ledger import` before starting the node"
+    )]
+    Missing(PublicKey),
+    #[error("the signing ledger is for validator key {actual}, expected {expected}")]
+    WrongKey { expected: Box<PublicKey>, actual: Box<PublicKey> },
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+}
+
+/// Contents of the ledger of one validator key, used to move the ledger between hosts.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct SigningLedgerExport {
+    pub public_key: PublicKey,
+    pub messages: Vec<SignedMessage>,
+}
+
+type MessageSlot = (SignedMessageKind, Option<ShardId>);
+
+/// Signed message hashes by height for every kind of message and shard.
+type KeyRecords = HashMap<MessageSlot, BTreeMap<BlockHeight, CryptoHash>>;
+
+/// Ledger of the messages signed by the validator keys of this node.
+///
+/// Cheap to clone, all clones share the same cache and lock, so checking and recording a
+/// signature is atomic across threads.
+#[derive(Clone)]
+pub struct SigningLedger {
+    store: Store,
+    records: Arc<Mutex<HashMap<PublicKey, KeyRecords>>>,
+}
+
+impl SigningLedger {
+    pub fn new(store: Store) -> Self {
+        Self { store, records: Default::default() }
+    }
+
+    /// Records `message` as signed by `public_key`. Must succeed before the signature is released.
+    ///
+    /// Recording the same message again is a no-op. Fails if a different message was already
+    /// signed for the same kind, shard and height, or if the height is too far below the highest
+    /// signed one to tell.
+    pub fn record(
+        &self,
+        public_key: &PublicKey,
+        message: &SignedMessage,
+    ) -> Result<(), SigningLedgerError> {
+        let mut records = self.records.lock().unwrap();
+        let key_records = match records.entry(public_key.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(self.load_key_records(public_key)?),
+        };
+        let slot = (message.kind, message.shard_id);
+        let heights = key_records.entry(slot).or_default();
+        if let Err(err) = Self::check(heights, message) {
+            metrics::SIGNING_LEDGER_REFUSED_TOTAL.with_label_values(&[message.kind.into()]).inc();
+            return Err(err);
+        }
+        if heights.contains_key(&message.height) {
+            return Ok(());
+        }
+
+        let mut store_update = self.store.store_update();
+        store_update.set_ser(
+            DBCol::SigningLedger,
+            &ledger_key(public_key, &slot, message.height),
+            &message.hash,
+        )?;
+        let max_height = heights.last_key_value().map_or(message.height, |(h, _)| *h);
+        let min_height = max_height.max(message.height).saturating_sub(SIGNING_WINDOW);
+        let pruned = heights.range(..min_height).map(|(h, _)| *h).collect::<Vec<_>>();
+        for height in &pruned {
+            store_update.delete(DBCol::SigningLedger, &ledger_key(public_key, &slot, *height));
+        }
+        store_update.commit_sync()?;
+
+        for height in pruned {
+            heights.remove(&height);
+        }
+        heights.insert(message.height, message.hash);
+        Ok(())
+    }
+
+    fn check(
+        heights: &BTreeMap<BlockHeight, CryptoHash>,
+        message: &SignedMessage,
+    ) -> Result<(), SigningLedgerError> {
+        if let Some(existing) = heights.get(&message.height) {
+            if *existing != message.hash {
+                return Err(SigningLedgerError::Conflict {
+                    kind: message.kind,
+                    shard_id: message.shard_id,
+                    height: message.height,
+                    existing: *existing,
+                    requested: message.hash,
+                });
+            }
+            return Ok(());
+        }
+        if let Some((max_height, _)) = heights.last_key_value() {
+            let min_height = max_height.saturating_sub(SIGNING_WINDOW);
+            if message.height < min_height {
+                return Err(SigningLedgerError::TooOld {
+                    kind: message.kind,
+                    shard_id: message.shard_id,
+                    height: message.height,
+                    min_height,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn load_key_records(&self, public_key: &PublicKey) -> std::io::Result<KeyRecords> {
+        let mut key_records = KeyRecords::new();
+        for message in self.load_messages(public_key)? {
+            key_records
+                .entry((message.kind, message.shard_id))
+                .or_default()
+                .insert(message.height, message.hash);
+        }
+        Ok(key_records)
+    }
+
+    fn load_messages(&self, public_key: &PublicKey) -> std::io::Result<Vec<SignedMessage>> {
+        let prefix = CryptoHash::hash_borsh(public_key);
+        let mut messages = vec![];
+        for item in self.store.iter_prefix_ser::<CryptoHash>(DBCol::SigningLedger, prefix.as_ref())
+        {
+            let (key, hash) = item?;
+            let (kind, shard_id, height) =
+                <(SignedMessageKind, Option<ShardId>, BlockHeight)>::try_from_slice(
+                    &key[prefix.as_ref().len()..],
+                )?;
+            messages.push(SignedMessage { kind, shard_id, height, hash });
+        }
+        Ok(messages)
+    }
+
+    /// Returns whether anything was ever signed with `public_key` on this node.
+    pub fn has_records(&self, public_key: &PublicKey) -> std::io::Result<bool> {
+        let prefix = CryptoHash::hash_borsh(public_key);
+        let mut records = self.store.iter_prefix(DBCol::SigningLedger, prefix.as_ref());
+        Ok(records.next().transpose()?.is_some())
+    }
+
+    /// Checks that the ledger of `public_key` is present before the node starts signing.
+    ///
+    /// A node that never signed anything with the key, e.g. a new validator, has no ledger
+    /// either, so a missing ledger is only an error if `require` is set.
+    pub fn check_on_startup(
+        &self,
+        public_key: &PublicKey,
+        require: bool,
+    ) -> Result<(), SigningLedgerError> {
+        if self.has_records(public_key)? {
+            return Ok(());
+        }
+        let err = SigningLedgerError::Missing(public_key.clone());
+        if require {
+            return Err(err);
+        }
+        tracing::warn!(target: "client", %err, "Starting without a signing ledger");
+        Ok(())
+    }
+
+    pub fn export(&self, public_key: &PublicKey) -> std::io::Result<SigningLedgerExport> {
+        let mut messages = self.load_messages(public_key)?;
+        messages.sort_by_key(|message| message.height);
+        Ok(SigningLedgerExport { public_key: public_key.clone(), messages })
+    }
+
+    /// Merges an exported ledger of `public_key` into this one and returns the number of newly
+    /// recorded messages. Fails without importing the rest if an exported message conflicts with
+    /// a local one, which means that both hosts signed with the key.
+    pub fn import(
+        &self,
+        public_key: &PublicKey,
+        export: &SigningLedgerExport,
+    ) -> Result<usize, SigningLedgerError> {
+        if &export.public_key != public_key {
+            return Err(SigningLedgerError::WrongKey {
+                expected: Box::new(public_key.clone()),
+                actual: Box::new(export.public_key.clone()),
+            });
+        }
+        let mut messages = export.messages.iter().collect::<Vec<_>>();
+        messages.sort_by_key(|message| message.height);
+        let mut imported = 0;
+        for message in messages {
+            let before = self.has_message(public_key, message)?;
+            match self.record(public_key, message) {
+                Ok(()) if !before => imported += 1,
+                Ok(()) => {}
+                // Covered by the higher heights already recorded.
+                Err(SigningLedgerError::TooOld { .. }) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(imported)
+    }
+
+    fn has_message(
+        &self,
+        public_key: &PublicKey,
+        message: &SignedMessage,
+    ) -> std::io::Result<bool> {
+        let key = ledger_key(public_key, &(message.kind, message.shard_id), message.height);
+        Ok(self.store.get(DBCol::SigningLedger, &key)?.is_some())
+    }
+}
+
+fn ledger_key(public_key: &PublicKey, slot: &MessageSlot, height: BlockHeight) -> Vec<u8> {
+    let mut key = CryptoHash::hash_borsh(public_key).as_ref().to_vec();
+    key.extend(borsh::to_vec(&(slot.0, slot.1, height)).unwrap());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_crypto::KeyType;
+    use near_primitives::block::ApprovalInner;
+    use near_primitives::hash::hash;
+    use near_store::test_utils::create_test_store;
+
+    fn approval(target_height: BlockHeight, inner: ApprovalInner) -> SignedMessage {
+        SignedMessage {
+            kind: SignedMessageKind::Approval,
+            shard_id: None,
+            height: target_height,
+            hash: CryptoHash::hash_borsh(&inner),
+        }
+    }
+
+    fn endorsement(shard_id: u64, height: BlockHeight, chunk: &[u8]) -> SignedMessage {
+        SignedMessage {
+            kind: SignedMessageKind::ChunkEndorsement,
+            shard_id: Some(ShardId::new(shard_id)),
+            height,
+            hash: hash(chunk),
+        }
+    }
+
+    #[test]
+    fn test_signing_ledger_refuses_conflicts() {
+        let public_key = PublicKey::from_seed(KeyType::ED25519, "test1");
+        let store = create_test_store();
+        let ledger = SigningLedger::new(store.clone());
+
+        let skip = approval(10, ApprovalInner::Skip(8));
+        ledger.record(&public_key, &skip).unwrap();
+        // Re-sending the same approval is fine.
+        ledger.record(&public_key, &skip).unwrap();
+        let endorse = approval(10, ApprovalInner::Endorsement(hash(&[9])));
+        assert!(matches!(
+            ledger.record(&public_key, &endorse),
+            Err(SigningLedgerError::Conflict { height: 10, .. })
+        ));
+
+        // The same height on different shards does not conflict.
+        ledger.record(&public_key, &endorsement(0, 10, b"a")).unwrap();
+        ledger.record(&public_key, &endorsement(1, 10, b"b")).unwrap();
+        assert!(ledger.record(&public_key, &endorsement(0, 10, b"b")).is_err());
+
+        // Another key has its own ledger.
+        let other_key = PublicKey::from_seed(KeyType::ED25519, "test2");
+        ledger.record(&other_key, &endorse).unwrap();
+
+        // The ledger survives a restart.
+        let ledger = SigningLedger::new(store);
+        assert!(ledger.record(&public_key, &endorse).is_err());
+        ledger.record(&public_key, &endorsement(0, 11, b"c")).unwrap();
+    }
+
+    #[test]
+    fn test_signing_ledger_window() {
+        let public_key = PublicKey::from_seed(KeyType::ED25519, "test1");
+        let store = create_test_store();
+        let ledger = SigningLedger::new(store.clone());
+
+        let top = 1000;
+        ledger.record(&public_key, &endorsement(0, top, b"top")).unwrap();
+        // Out of order signatures within the window are accepted.
+        ledger.record(&public_key, &endorsement(0, top - SIGNING_WINDOW, b"low")).unwrap();
+        assert!(matches!(
+            ledger.record(&public_key, &endorsement(0, top - SIGNING_WINDOW - 1, b"old")),
+            Err(SigningLedgerError::TooOld { .. })
+        ));
+
+        // Records that fall out of the window are pruned from the database.
+        ledger.record(&public_key, &endorsement(0, top + 1, b"next")).unwrap();
+        let export = SigningLedger::new(store).export(&public_key).unwrap();
+        let heights = export.messages.iter().map(|m| m.height).collect::<Vec<_>>();
+        assert_eq!(heights, vec![top, top + 1]);
+    }
+
+    #[test]
+    fn test_signing_ledger_export_import() {
+        let public_key = PublicKey::from_seed(KeyType::ED25519, "test1");
+        let source = SigningLedger::new(create_test_store());
+        assert!(!source.has_records(&public_key).unwrap());
+        assert!(source.check_on_startup(&public_key, false).is_ok());
+        assert!(matches!(
+            source.check_on_startup(&public_key, true),
+            Err(SigningLedgerError::Missing(_))
+        ));
+        source.record(&public_key, &approval(5, ApprovalInner::Skip(3))).unwrap();
+        source.record(&public_key, &endorsement(0, 5, b"a")).unwrap();
+
+        let export = source.export(&public_key).unwrap();
+        let export: SigningLedgerExport =
+            serde_json::from_str(&serde_json::to_string(&export).unwrap()).unwrap();
+
+        let target = SigningLedger::new(create_test_store());
+        assert_eq!(target.import(&public_key, &export).unwrap(), 2);
+        assert_eq!(target.import(&public_key, &export).unwrap(), 0);
+        target.check_on_startup(&public_key, true).unwrap();
+        assert!(target.record(&public_key, &endorsement(0, 5, b"b")).is_err());
+
+        // A conflicting local signature fails the import.
+        let conflicting = SigningLedger::new(create_test_store());
+        conflicting.record(&public_key, &endorsement(0, 5, b"b")).unwrap();
+        assert!(matches!(
+            conflicting.import(&public_key, &export),
+            Err(SigningLedgerError::Conflict { .. })
+        ));
+
+        let other_key = PublicKey::from_seed(KeyType::ED25519, "test2");
+        assert!(matches!(
+            target.import(&other_key, &export),
+            Err(SigningLedgerError::WrongKey { .. })
+        ));
+    }
+}
@@ -5,6 +5,9 @@ use crate::chain::{
 use crate::rayon_spawner::RayonAsyncComputationSpawner;
 use crate::sharding::shuffle_receipt_proofs;
 use crate::stateless_validation::processing_tracker::ProcessingDoneTracker;
+use crate::stateless_validation::shadow_validation_report::{
+    ShadowValidationRecord, ShadowValidationReport, ShadowValidationStatus,
+};
 use crate::types::{
     ApplyChunkBlockContext, ApplyChunkResult, PreparedTransactions, RuntimeAdapter,
     RuntimeStorageConfig, StorageDataSource,
@@ -28,7 +31,7 @@ use near_primitives::stateless_validation::state_witness::{
 };
 use near_primitives::transaction::SignedTransaction;
 use near_primitives::types::chunk_extra::ChunkExtra;
-use near_primitives::types::{ProtocolVersion, ShardId, ShardIndex};
+use near_primitives::types::{ProtocolVersion, ShardId, ShardIndex, StateRoot};
 use near_primitives::utils::compression::CompressedData;
 use near_store::PartialStorage;
 use std::collections::HashMap;
@@ -451,6 +454,43 @@ fn validate_receipt_proof(
     Ok(())
 }
 
+/// Check of a recomputed state root that failed, see [`PostStateRootMismatch`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateRootCheck {
+    /// The main transition, against its post state root in the witness.
+    MainTransition,
+    /// The implicit transition at the given block, against its post state root in the witness.
+    ImplicitTransition(CryptoHash),
+    /// The state after all transitions, against the prev state root in the chunk header.
+    ChunkHeader,
+}
+
+/// Recomputed state root which differs from the one committed to by the witness or the chunk.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PostStateRootMismatch {
+    pub check: StateRootCheck,
+    /// State root that the witness or the chunk header commits to.
+    pub expected: StateRoot,
+    /// State root computed by applying the transitions.
+    pub actual: StateRoot,
+}
+
+impl From<PostStateRootMismatch> for Error {
+    fn from(mismatch: PostStateRootMismatch) -> Self {
+        let PostStateRootMismatch { check, expected, actual } = mismatch;
+        match check {
+            StateRootCheck::MainTransition => Error::InvalidChunkStateWitness(format!(
+                "Post state root {actual:?} for main transition does not match expected post state root {expected:?}",
+            )),
+            StateRootCheck::ImplicitTransition(block_hash) => Error::InvalidChunkStateWitness(format!(
+                "Post state root {actual:?} for implicit transition at block {block_hash:?}, does not match expected state root {expected:?}",
+            )),
+            StateRootCheck::ChunkHeader => Error::InvalidStateRoot,
+        }
+    }
+}
+
 pub fn validate_chunk_state_witness(
     state_witness: ChunkStateWitness,
     pre_validation_output: PreValidationOutput,
@@ -458,6 +498,25 @@ pub fn validate_chunk_state_witness(
     runtime_adapter: &dyn RuntimeAdapter,
     main_state_transition_cache: &MainStateTransitionCache,
 ) -> Result<(), Error> {
+    validate_chunk_state_witness_impl(
+        state_witness,
+        pre_validation_output,
+        epoch_manager,
+        runtime_adapter,
+        main_state_transition_cache,
+    )?
+    .map_err(Error::from)
+}
+
+/// Same as [`validate_chunk_state_witness`], but returns a mismatch of the post state roots
+/// separately from the other errors, so that it can be reported in detail.
+fn validate_chunk_state_witness_impl(
+    state_witness: ChunkStateWitness,
+    pre_validation_output: PreValidationOutput,
+    epoch_manager: &dyn EpochManagerAdapter,
+    runtime_adapter: &dyn RuntimeAdapter,
+    main_state_transition_cache: &MainStateTransitionCache,
+) -> Result<Result<(), PostStateRootMismatch>, Error> {
     let _timer = crate::stateless_validation::metrics::CHUNK_STATE_WITNESS_VALIDATION_TIME
         .with_label_values(&[&state_witness.chunk_header.shard_id().to_string()])
         .start_timer();
@@ -503,11 +562,11 @@ pub fn validate_chunk_state_witness(
         // This is an early check, it's not for correctness, only for better
         // error reporting in case of an invalid state witness due to a bug.
         // Only the final state root check against the chunk header is required.
-        return Err(Error::InvalidChunkStateWitness(format!(
-            "Post state root {:?} for main transition does not match expected post state root {:?}",
-            chunk_extra.state_root(),
-            state_witness.main_state_transition.post_state_root,
-        )));
+        return Ok(Err(PostStateRootMismatch {
+            check: StateRootCheck::MainTransition,
+            expected: state_witness.main_state_transition.post_state_root,
+            actual: *chunk_extra.state_root(),
+        }));
     }
 
     // Compute receipt hashes here to avoid copying receipts
@@ -565,14 +624,22 @@ pub fn validate_chunk_state_witness(
             // This is an early check, it's not for correctness, only for better
             // error reporting in case of an invalid state witness due to a bug.
             // Only the final state root check against the chunk header is required.
-            return Err(Error::InvalidChunkStateWitness(format!(
-                "Post state root {:?} for implicit transition at block {:?}, does not match expected state root {:?}",
-                chunk_extra.state_root(), block_hash, transition.post_state_root
-            )));
+            return Ok(Err(PostStateRootMismatch {
+                check: StateRootCheck::ImplicitTransition(block_hash),
+                expected: transition.post_state_root,
+                actual: *chunk_extra.state_root(),
+            }));
         }
     }
 
     // Finally, verify that the newly proposed chunk matches everything we have computed.
+    if *chunk_extra.state_root() != state_witness.chunk_header.prev_state_root() {
+        return Ok(Err(PostStateRootMismatch {
+            check: StateRootCheck::ChunkHeader,
+            expected: state_witness.chunk_header.prev_state_root(),
+            actual: *chunk_extra.state_root(),
+        }));
+    }
     let (outgoing_receipts_root, _) = merklize(&outgoing_receipts_hashes);
     validate_chunk_with_chunk_extra_and_receipts_root(
         &chunk_extra,
@@ -580,7 +647,7 @@ pub fn validate_chunk_state_witness(
         &outgoing_receipts_root,
     )?;
 
-    Ok(())
+    Ok(Ok(()))
 }
 
 pub fn apply_result_to_chunk_extra(
@@ -602,49 +669,76 @@ pub fn apply_result_to_chunk_extra(
     )
 }
 
+/// Encodes the witness as it would be sent over the network and decodes it back, recording the
+/// time taken and the witness size in the metrics. Returns the encoded witness and its raw size.
+fn encode_and_decode_witness(
+    witness: &ChunkStateWitness,
+) -> Result<(EncodedChunkStateWitness, usize), Error> {
+    let shard_id_label = witness.chunk_header.shard_id().to_string();
+    let encode_timer = crate::stateless_validation::metrics::CHUNK_STATE_WITNESS_ENCODE_TIME
+        .with_label_values(&[shard_id_label.as_str()])
+        .start_timer();
+    let (encoded_witness, raw_witness_size) = EncodedChunkStateWitness::encode(witness)?;
+    encode_timer.observe_duration();
+    crate::stateless_validation::metrics::record_witness_size_metrics(
+        raw_witness_size,
+        encoded_witness.size_bytes(),
+        witness,
+    );
+    let decode_timer = crate::stateless_validation::metrics::CHUNK_STATE_WITNESS_DECODE_TIME
+        .with_label_values(&[shard_id_label.as_str()])
+        .start_timer();
+    encoded_witness.decode()?;
+    decode_timer.observe_duration();
+    Ok((encoded_witness, raw_witness_size))
+}
+
 impl Chain {
+    /// Validates the state witness of a chunk in the background, without affecting the chain.
+    /// The outcome is exported as metrics, and appended to `report` if given.
     pub fn shadow_validate_state_witness(
         &self,
         witness: ChunkStateWitness,
         epoch_manager: &dyn EpochManagerAdapter,
         runtime_adapter: &dyn RuntimeAdapter,
         processing_done_tracker: Option<ProcessingDoneTracker>,
+        report: Option<Arc<ShadowValidationReport>>,
     ) -> Result<(), Error> {
         let shard_id = witness.chunk_header.shard_id();
         let height_created = witness.chunk_header.height_created();
         let chunk_hash = witness.chunk_header.chunk_hash();
         let parent_span = tracing::debug_span!(
             target: "chain", "shadow_validate", ?shard_id, height_created);
-        let (encoded_witness, raw_witness_size) = {
-            let shard_id_label = shard_id.to_string();
-            let encode_timer =
-                crate::stateless_validation::metrics::CHUNK_STATE_WITNESS_ENCODE_TIME
-                    .with_label_values(&[shard_id_label.as_str()])
-                    .start_timer();
-            let (encoded_witness, raw_witness_size) = EncodedChunkStateWitness::encode(&witness)?;
-            encode_timer.observe_duration();
-            crate::stateless_validation::metrics::record_witness_size_metrics(
-                raw_witness_size,
-                encoded_witness.size_bytes(),
-                &witness,
-            );
-            let decode_timer =
-                crate::stateless_validation::metrics::CHUNK_STATE_WITNESS_DECODE_TIME
-                    .with_label_values(&[shard_id_label.as_str()])
-                    .start_timer();
-            encoded_witness.decode()?;
-            decode_timer.observe_duration();
-            (encoded_witness, raw_witness_size)
-        };
+        let mut record = ShadowValidationRecord::new(&witness.chunk_header);
+        match encode_and_decode_witness(&witness) {
+            Ok((encoded_witness, raw_witness_size)) => {
+                record.witness_size = encoded_witness.size_bytes();
+                record.raw_witness_size = raw_witness_size;
+            }
+            Err(err) => {
+                record.status = ShadowValidationStatus::failed(&err);
+                record.finish(report.as_deref());
+                return Err(err);
+            }
+        }
         let pre_validation_start = Instant::now();
         let pre_validation_result =
-            pre_validate_chunk_state_witness(&witness, &self, epoch_manager, runtime_adapter)?;
+            pre_validate_chunk_state_witness(&witness, &self, epoch_manager, runtime_adapter);
+        record.pre_validation_secs = pre_validation_start.elapsed().as_secs_f64();
+        let pre_validation_result = match pre_validation_result {
+            Ok(pre_validation_result) => pre_validation_result,
+            Err(err) => {
+                record.status = ShadowValidationStatus::failed(&err);
+                record.finish(report.as_deref());
+                return Err(err);
+            }
+        };
         tracing::debug!(
             parent: &parent_span,
             ?shard_id,
             ?chunk_hash,
-            witness_size = encoded_witness.size_bytes(),
-            raw_witness_size,
+            witness_size = record.witness_size,
+            raw_witness_size = record.raw_witness_size,
             pre_validation_elapsed = ?pre_validation_start.elapsed(),
             "completed shadow chunk pre-validation"
         );
@@ -657,13 +751,24 @@ impl Chain {
 
             let validation_start = Instant::now();
 
-            match validate_chunk_state_witness(
+            let result = validate_chunk_state_witness_impl(
                 witness,
                 pre_validation_result,
                 epoch_manager.as_ref(),
                 runtime_adapter.as_ref(),
                 &MainStateTransitionCache::default(),
-            ) {
+            );
+            record.validation_secs = Some(validation_start.elapsed().as_secs_f64());
+            record.status = match &result {
+                Ok(Ok(())) => ShadowValidationStatus::Ok,
+                Ok(Err(mismatch)) => {
+                    ShadowValidationStatus::PostStateRootMismatch(mismatch.clone())
+                }
+                Err(err) => ShadowValidationStatus::failed(err),
+            };
+            record.finish(report.as_deref());
+
+            match result.and_then(|result| result.map_err(Error::from)) {
                 Ok(()) => {
                     tracing::debug!(
                         parent: &parent_span,
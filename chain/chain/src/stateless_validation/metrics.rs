@@ -60,6 +60,25 @@ pub static SHADOW_CHUNK_VALIDATION_FAILED_TOTAL: LazyLock<IntCounter> = LazyLock
     .unwrap()
 });
 
+pub static SHADOW_CHUNK_VALIDATION_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "near_shadow_chunk_validation_total",
+        "Shadow validated chunks by status: ok, post_state_root_mismatch or failed",
+        &["shard_id", "status"],
+    )
+    .unwrap()
+});
+
+pub(crate) static SHADOW_CHUNK_VALIDATION_TIME: LazyLock<HistogramVec> = LazyLock::new(|| {
+    try_create_histogram_vec(
+        "near_shadow_chunk_validation_time",
+        "Time taken to pre-validate and validate a shadow validated chunk state witness in seconds",
+        &["shard_id"],
+        Some(exponential_buckets(0.01, 2.0, 12).unwrap()),
+    )
+    .unwrap()
+});
+
 pub static CHUNK_WITNESS_VALIDATION_FAILED_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
     try_create_int_counter_vec(
         "near_chunk_witness_validation_failed_total",
@@ -2,4 +2,5 @@ pub mod chunk_endorsement;
 pub mod chunk_validation;
 pub mod metrics;
 pub mod processing_tracker;
+pub mod shadow_validation_report;
 pub(crate) mod state_transition_data;
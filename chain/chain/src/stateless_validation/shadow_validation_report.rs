@@ -0,0 +1,156 @@
+use crate::stateless_validation::chunk_validation::PostStateRootMismatch;
+use near_chain_primitives::Error;
+use near_primitives::hash::CryptoHash;
+use near_primitives::sharding::{ChunkHash, ShardChunkHeader};
+use near_primitives::types::{BlockHeight, ShardId};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+#[derive(serde::Serialize, Clone, Debug)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ShadowValidationStatus {
+    Ok,
+    /// The state transitions in the witness result in a different state root than the one the
+    /// witness or the chunk header commits to.
+    PostStateRootMismatch(PostStateRootMismatch),
+    /// The witness could not be validated, or the result doesn't match the chunk header in some
+    /// other way.
+    Failed {
+        error_kind: &'static str,
+        error: String,
+    },
+}
+
+impl ShadowValidationStatus {
+    pub fn failed(err: &Error) -> Self {
+        Self::Failed { error_kind: err.prometheus_label_value(), error: err.to_string() }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::PostStateRootMismatch(_) => "post_state_root_mismatch",
+            Self::Failed { .. } => "failed",
+        }
+    }
+}
+
+/// Outcome of the shadow validation of the state witness of one chunk.
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct ShadowValidationRecord {
+    pub shard_id: ShardId,
+    pub height_created: BlockHeight,
+    pub chunk_hash: ChunkHash,
+    pub prev_block_hash: CryptoHash,
+    /// Size of the compressed witness, as it would be sent over the network. The witness sizes
+    /// are 0 if the witness couldn't be produced.
+    pub witness_size: usize,
+    pub raw_witness_size: usize,
+    pub pre_validation_secs: f64,
+    /// `None` when the pre-validation failed.
+    pub validation_secs: Option<f64>,
+    #[serde(flatten)]
+    pub status: ShadowValidationStatus,
+}
+
+impl ShadowValidationRecord {
+    /// Record of a successful validation of the chunk, to be filled in as the validation goes.
+    pub fn new(chunk_header: &ShardChunkHeader) -> Self {
+        Self {
+            shard_id: chunk_header.shard_id(),
+            height_created: chunk_header.height_created(),
+            chunk_hash: chunk_header.chunk_hash(),
+            prev_block_hash: *chunk_header.prev_block_hash(),
+            witness_size: 0,
+            raw_witness_size: 0,
+            pre_validation_secs: 0.0,
+            validation_secs: None,
+            status: ShadowValidationStatus::Ok,
+        }
+    }
+
+    /// Exports the record as metrics, and appends it to the report if there is one.
+    pub fn finish(&self, report: Option<&ShadowValidationReport>) {
+        let shard_id = self.shard_id.to_string();
+        crate::stateless_validation::metrics::SHADOW_CHUNK_VALIDATION_TOTAL
+            .with_label_values(&[&shard_id, self.status.label()])
+            .inc();
+        crate::stateless_validation::metrics::SHADOW_CHUNK_VALIDATION_TIME
+            .with_label_values(&[&shard_id])
+            .observe(self.pre_validation_secs + self.validation_secs.unwrap_or_default());
+        if let Some(report) = report {
+            report.write(self);
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ReportLine<'a> {
+    neard_version: &'a str,
+    #[serde(flatten)]
+    record: &'a ShadowValidationRecord,
+}
+
+/// Report of the shadow validation, one JSON line per validated chunk, appended to a file.
+/// Each line carries the version of the binary, so that reports of different versions run against
+/// the same blocks can be compared.
+pub struct ShadowValidationReport {
+    neard_version: String,
+    file: Mutex<File>,
+}
+
+impl ShadowValidationReport {
+    pub fn open(path: &Path, neard_version: String) -> std::io::Result<Self> {
+        let file = File::options().create(true).append(true).open(path)?;
+        Ok(Self { neard_version, file: Mutex::new(file) })
+    }
+
+    fn write(&self, record: &ShadowValidationRecord) {
+        let line = ReportLine { neard_version: &self.neard_version, record };
+        let mut line = serde_json::to_vec(&line).expect("record must serialize to JSON");
+        line.push(b'\n');
+        if let Err(err) = self.file.lock().unwrap().write_all(&line) {
+            tracing::error!(target: "chain", ?err, "failed to write shadow validation report");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReportLine, ShadowValidationRecord, ShadowValidationStatus};
+    use crate::stateless_validation::chunk_validation::{PostStateRootMismatch, StateRootCheck};
+    use near_primitives::hash::{hash, CryptoHash};
+    use near_primitives::types::ShardId;
+
+    #[test]
+    fn test_report_line() {
+        let expected = hash(b"expected");
+        let actual = hash(b"actual");
+        let record = ShadowValidationRecord {
+            shard_id: ShardId::new(3),
+            height_created: 10,
+            chunk_hash: Default::default(),
+            prev_block_hash: CryptoHash::default(),
+            witness_size: 100,
+            raw_witness_size: 200,
+            pre_validation_secs: 0.5,
+            validation_secs: Some(1.5),
+            status: ShadowValidationStatus::PostStateRootMismatch(PostStateRootMismatch {
+                check: StateRootCheck::ChunkHeader,
+                expected,
+                actual,
+            }),
+        };
+        let line = ReportLine { neard_version: "trunk", record: &record };
+        let line = serde_json::to_value(&line).unwrap();
+        assert_eq!(line["neard_version"], "trunk");
+        assert_eq!(line["shard_id"], 3);
+        assert_eq!(line["witness_size"], 100);
+        assert_eq!(line["status"], "post_state_root_mismatch");
+        assert_eq!(line["expected"], expected.to_string());
+        assert_eq!(line["actual"], actual.to_string());
+        assert_eq!(line["check"], "chunk_header");
+    }
+}
@@ -9,6 +9,9 @@ use std::io::ErrorKind;
 
 use near_primitives::hash::CryptoHash;
 use near_primitives::stateless_validation::state_witness::ChunkStateWitness;
+use near_primitives::stateless_validation::witness_size_attribution::{
+    StorageProofSizeAttribution, WitnessSizeBreakdown,
+};
 use near_primitives::types::EpochId;
 use near_store::DBCol;
 
@@ -101,12 +104,15 @@ impl LatestWitnessesInfo {
 
 impl ChainStore {
     /// Saves an observed `ChunkStateWitness` to the database for later analysis and debugging.
-    /// The witness is stored in `DBCol::LatestChunkStateWitnesses`.
+    /// The witness is stored in `DBCol::LatestChunkStateWitnesses`, and the sizes of its parts in
+    /// `DBCol::LatestWitnessesSizeBreakdown`. `main_storage_proof` is known only for the witnesses
+    /// produced by this node.
     /// This function does a read-before-write. Don't call it in parallel on the same database,
     /// or there will be race conditions.
     pub fn save_latest_chunk_state_witness(
         &mut self,
         witness: &ChunkStateWitness,
+        main_storage_proof: Option<StorageProofSizeAttribution>,
     ) -> Result<(), std::io::Error> {
         let start_time = std::time::Instant::now();
         let _span = tracing::info_span!(
@@ -162,6 +168,7 @@ impl ChainStore {
             let key_deser = LatestWitnessesKey::deserialize(&key_to_delete)?;
 
             store_update.delete(DBCol::LatestChunkStateWitnesses, &key_to_delete);
+            store_update.delete(DBCol::LatestWitnessesSizeBreakdown, &key_to_delete);
             store_update.delete(DBCol::LatestWitnessesByIndex, &info.lowest_index.to_be_bytes());
             info.lowest_index += 1;
             info.count -= 1;
@@ -179,6 +186,12 @@ impl ChainStore {
             random_uuid,
         };
         store_update.set(DBCol::LatestChunkStateWitnesses, &key.serialized(), &serialized_witness);
+        let size_breakdown = WitnessSizeBreakdown::new(witness, main_storage_proof);
+        store_update.set_ser(
+            DBCol::LatestWitnessesSizeBreakdown,
+            &key.serialized(),
+            &size_breakdown,
+        )?;
         store_update.set(
             DBCol::LatestWitnessesByIndex,
             &new_witness_index.to_be_bytes(),
@@ -224,6 +237,28 @@ impl ChainStore {
         shard_id: Option<u64>,
         epoch_id: Option<EpochId>,
     ) -> Result<Vec<ChunkStateWitness>, std::io::Error> {
+        self.get_latest_from_column(DBCol::LatestChunkStateWitnesses, height, shard_id, epoch_id)
+            .map(|witnesses| witnesses.into_iter().map(|(_key, witness)| witness).collect())
+    }
+
+    /// Fetch the size breakdowns of the observed witnesses matching the criteria, in the same way
+    /// as `get_latest_witnesses`. Witnesses saved before the breakdowns were introduced have none.
+    pub fn get_latest_witnesses_size_breakdown(
+        &self,
+        height: Option<u64>,
+        shard_id: Option<u64>,
+        epoch_id: Option<EpochId>,
+    ) -> Result<Vec<(LatestWitnessesKey, WitnessSizeBreakdown)>, std::io::Error> {
+        self.get_latest_from_column(DBCol::LatestWitnessesSizeBreakdown, height, shard_id, epoch_id)
+    }
+
+    fn get_latest_from_column<T: BorshDeserialize>(
+        &self,
+        col: DBCol,
+        height: Option<u64>,
+        shard_id: Option<u64>,
+        epoch_id: Option<EpochId>,
+    ) -> Result<Vec<(LatestWitnessesKey, T)>, std::io::Error> {
         let mut key_prefix: Vec<u8> = Vec::new();
         if let Some(h) = height {
             key_prefix.extend_from_slice(&h.to_be_bytes());
@@ -233,13 +268,10 @@ impl ChainStore {
             }
         }
 
-        let mut result: Vec<ChunkStateWitness> = Vec::new();
+        let mut result = Vec::new();
 
-        for read_result in self
-            .store()
-            .iter_prefix_ser::<ChunkStateWitness>(DBCol::LatestChunkStateWitnesses, &key_prefix)
-        {
-            let (key_bytes, witness) = read_result?;
+        for read_result in self.store().iter_prefix_ser::<T>(col, &key_prefix) {
+            let (key_bytes, value) = read_result?;
 
             let key = LatestWitnessesKey::deserialize(&key_bytes)?;
             if let Some(h) = height {
@@ -257,7 +289,7 @@ impl ChainStore {
                     continue;
                 }
             }
-            result.push(witness);
+            result.push((key, value));
         }
 
         Ok(result)
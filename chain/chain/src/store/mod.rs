@@ -14,7 +14,6 @@ use near_primitives::errors::InvalidTxError;
 use near_primitives::hash::CryptoHash;
 use near_primitives::merkle::{MerklePath, PartialMerkleTree};
 use near_primitives::receipt::Receipt;
-use near_primitives::shard_layout::account_id_to_shard_id;
 use near_primitives::shard_layout::{get_block_shard_uid, ShardLayout, ShardUId};
 use near_primitives::sharding::{
     ChunkHash, EncodedShardChunk, PartialEncodedChunk, ReceiptProof, ShardChunk, ShardChunkHeader,
@@ -26,7 +25,9 @@ use near_primitives::state_sync::{
 use near_primitives::stateless_validation::contract_distribution::ContractUpdates;
 use near_primitives::stateless_validation::stored_chunk_state_transition_data::{
     StoredChunkStateTransitionData, StoredChunkStateTransitionDataV3,
+    StoredChunkStateTransitionDataV4,
 };
+use near_primitives::stateless_validation::witness_size_attribution::StorageProofSizeAttribution;
 use near_primitives::transaction::{
     ExecutionOutcomeWithId, ExecutionOutcomeWithIdAndProof, ExecutionOutcomeWithProof,
     SignedTransaction,
@@ -384,8 +385,7 @@ fn filter_incoming_receipts_for_shard(
         let mut filtered_receipts = vec![];
         let ReceiptProof(receipts, shard_proof) = receipt_proof.clone();
         for receipt in receipts {
-            let receiver_shard_id =
-                account_id_to_shard_id(receipt.receiver_id(), target_shard_layout);
+            let receiver_shard_id = receipt.receiver_shard_id(target_shard_layout);
             if receiver_shard_id == target_shard_id {
                 tracing::trace!(target: "chain", receipt_id=?receipt.receipt_id(), "including receipt");
                 filtered_receipts.push(receipt);
@@ -456,6 +456,9 @@ pub struct ChainStore {
     /// - archive is true, cold_store is configured and migration to split_storage is finished - node
     /// working in split storage mode needs trie changes in order to do garbage collection on hot.
     save_trie_changes: bool,
+    /// Whether the latest state witnesses are saved for debugging. Only then the storage proof
+    /// size attribution is kept with the state transition data.
+    pub(crate) save_latest_witnesses: bool,
 }
 
 fn option_to_not_found<T, F>(res: io::Result<Option<T>>, field_name: F) -> Result<T, Error>
@@ -497,6 +500,7 @@ impl ChainStore {
             block_ordinal_to_hash: CellLruCache::new(CACHE_SIZE),
             processed_block_heights: CellLruCache::new(CACHE_SIZE),
             save_trie_changes,
+            save_latest_witnesses: false,
         }
     }
 
@@ -2009,20 +2013,35 @@ impl<'a> ChainStoreUpdate<'a> {
         block_hash: CryptoHash,
         shard_id: ShardId,
         partial_storage: Option<PartialStorage>,
+        size_attribution: Option<StorageProofSizeAttribution>,
         applied_receipts_hash: CryptoHash,
         contract_updates: ContractUpdates,
     ) {
         if let Some(partial_storage) = partial_storage {
             let ContractUpdates { contract_accesses, contract_deploys } = contract_updates;
-            self.state_transition_data.insert(
-                (block_hash, shard_id),
-                StoredChunkStateTransitionData::V3(StoredChunkStateTransitionDataV3 {
-                    base_state: partial_storage.nodes,
+            let base_state = partial_storage.nodes;
+            let contract_accesses = contract_accesses.into_iter().collect();
+            let contract_deploys = contract_deploys.into_iter().map(|c| c.into()).collect();
+            // The attribution is only shown next to the saved latest witnesses, don't spend
+            // the space on it otherwise.
+            let data = match size_attribution {
+                Some(size_attribution) if self.chain_store.save_latest_witnesses => {
+                    StoredChunkStateTransitionData::V4(StoredChunkStateTransitionDataV4 {
+                        base_state,
+                        receipts_hash: applied_receipts_hash,
+                        contract_accesses,
+                        contract_deploys,
+                        size_attribution,
+                    })
+                }
+                _ => StoredChunkStateTransitionData::V3(StoredChunkStateTransitionDataV3 {
+                    base_state,
                     receipts_hash: applied_receipts_hash,
-                    contract_accesses: contract_accesses.into_iter().collect(),
-                    contract_deploys: contract_deploys.into_iter().map(|c| c.into()).collect(),
+                    contract_accesses,
+                    contract_deploys,
                 }),
-            );
+            };
+            self.state_transition_data.insert((block_hash, shard_id), data);
         }
     }
 
@@ -50,7 +50,8 @@ use near_primitives::types::{
 use near_primitives::version::{ProtocolFeature, ProtocolVersion, PROTOCOL_VERSION};
 use near_primitives::views::{
     AccessKeyInfoView, AccessKeyList, CallResult, ContractCodeView, EpochValidatorInfo,
-    QueryRequest, QueryResponse, QueryResponseKind, ViewStateResult,
+    EpochValidatorPerformanceView, QueryRequest, QueryResponse, QueryResponseKind,
+    SimulatedTransactionView, ViewStateResult,
 };
 use near_store::test_utils::TestTriesBuilder;
 use near_store::{
@@ -850,6 +851,20 @@ impl EpochManagerAdapter for MockEpochManager {
         })
     }
 
+    fn get_validator_performance(
+        &self,
+        block_hash: &CryptoHash,
+    ) -> Result<EpochValidatorPerformanceView, EpochError> {
+        Ok(EpochValidatorPerformanceView {
+            epoch_id: self.get_epoch_id(block_hash)?,
+            epoch_height: 1,
+            epoch_start_height: 0,
+            block_height: 0,
+            epoch_length: self.epoch_length,
+            validators: vec![],
+        })
+    }
+
     fn add_validator_proposals(
         &self,
         _block_info: BlockInfo,
@@ -1382,6 +1397,7 @@ impl RuntimeAdapter for KeyValueRuntime {
             total_gas_burnt: 0,
             total_balance_burnt: 0,
             proof: storage_proof,
+            storage_proof_size_attribution: None,
             processed_delayed_receipts: vec![],
             processed_yield_timeouts: vec![],
             applied_receipts_hash: hash(&borsh::to_vec(receipts).unwrap()),
@@ -1389,6 +1405,7 @@ impl RuntimeAdapter for KeyValueRuntime {
             bandwidth_requests: BandwidthRequests::default_for_protocol_version(PROTOCOL_VERSION),
             bandwidth_scheduler_state_hash: CryptoHash::default(),
             contract_updates: Default::default(),
+            receipt_traces: vec![],
         })
     }
 
@@ -1457,6 +1474,7 @@ impl RuntimeAdapter for KeyValueRuntime {
                 kind: QueryResponseKind::CallResult(CallResult {
                     result: Default::default(),
                     logs: Default::default(),
+                    function_gas_profile: None,
                 }),
                 block_height,
                 block_hash: *block_hash,
@@ -1464,6 +1482,20 @@ impl RuntimeAdapter for KeyValueRuntime {
         }
     }
 
+    fn simulate_transaction(
+        &self,
+        _block: &BlockHeader,
+        _state_roots: HashMap<ShardId, StateRoot>,
+        _transaction: &SignedTransaction,
+        _verify_signature: bool,
+        _check_nonce: bool,
+    ) -> Result<SimulatedTransactionView, near_chain_primitives::error::SimulateTransactionError>
+    {
+        Err(near_chain_primitives::error::SimulateTransactionError::InternalError(
+            "transaction simulation is not supported by KeyValueRuntime".to_string(),
+        ))
+    }
+
     fn obtain_state_part(
         &self,
         _shard_id: ShardId,
@@ -91,7 +91,8 @@ fn build_chain_with_orphans() {
         CryptoHash::default(),
         clock,
         None,
-    );
+    )
+    .unwrap();
     assert_matches!(chain.process_block_test(&None, block).unwrap_err(), Error::Orphan);
     assert_matches!(
         chain.process_block_test(&None, blocks.pop().unwrap()).unwrap_err(),
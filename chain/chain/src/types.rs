@@ -25,6 +25,7 @@ use near_primitives::sandbox::state_patch::SandboxStatePatch;
 use near_primitives::shard_layout::ShardUId;
 use near_primitives::state_part::PartId;
 use near_primitives::stateless_validation::contract_distribution::ContractUpdates;
+use near_primitives::stateless_validation::witness_size_attribution::StorageProofSizeAttribution;
 use near_primitives::transaction::{ExecutionOutcomeWithId, SignedTransaction};
 use near_primitives::types::validator_stake::{ValidatorStake, ValidatorStakeIter};
 use near_primitives::types::{
@@ -36,13 +37,16 @@ use near_primitives::version::{
     ProtocolVersion, MIN_GAS_PRICE_NEP_92, MIN_GAS_PRICE_NEP_92_FIX, MIN_PROTOCOL_VERSION_NEP_92,
     MIN_PROTOCOL_VERSION_NEP_92_FIX,
 };
-use near_primitives::views::{QueryRequest, QueryResponse};
+use near_primitives::views::{
+    QueryRequest, QueryResponse, ReceiptExecutionTraceView, SimulatedTransactionView,
+};
 use near_schema_checker_lib::ProtocolSchema;
 use near_store::flat::FlatStorageManager;
 use near_store::{PartialStorage, ShardTries, Store, Trie, WrappedTrieChanges};
 use near_vm_runner::ContractCode;
 use near_vm_runner::ContractRuntimeCache;
 use num_rational::Rational32;
+use std::collections::HashMap;
 use tracing::instrument;
 
 #[derive(Eq, PartialEq, Debug, Clone)]
@@ -95,6 +99,8 @@ pub struct ApplyChunkResult {
     pub total_gas_burnt: Gas,
     pub total_balance_burnt: Balance,
     pub proof: Option<PartialStorage>,
+    /// Which transactions and receipts recorded the `proof`, present whenever the `proof` is.
+    pub storage_proof_size_attribution: Option<StorageProofSizeAttribution>,
     pub processed_delayed_receipts: Vec<Receipt>,
     pub processed_yield_timeouts: Vec<PromiseYieldTimeout>,
     /// Hash of Vec<Receipt> which were applied in a chunk, later used for
@@ -113,6 +119,9 @@ pub struct ApplyChunkResult {
     pub bandwidth_scheduler_state_hash: CryptoHash,
     /// Contracts accessed and deployed while applying the chunk.
     pub contract_updates: ContractUpdates,
+    /// Step-level traces of the executed action receipts. Empty unless `execution_tracing` is
+    /// enabled in the runtime config.
+    pub receipt_traces: Vec<ReceiptExecutionTraceView>,
 }
 
 impl ApplyChunkResult {
@@ -220,6 +229,8 @@ pub struct ChainConfig {
     pub background_migration_threads: usize,
     /// The resharding configuration.
     pub resharding_config: MutableConfigValue<ReshardingConfig>,
+    /// Whether the latest state witnesses are saved for debugging, see `ClientConfig`.
+    pub save_latest_witnesses: bool,
 }
 
 impl ChainConfig {
@@ -231,6 +242,7 @@ impl ChainConfig {
                 ReshardingConfig::default(),
                 "resharding_config",
             ),
+            save_latest_witnesses: false,
         }
     }
 }
@@ -488,6 +500,19 @@ pub trait RuntimeAdapter: Send + Sync {
         request: &QueryRequest,
     ) -> Result<QueryResponse, near_chain_primitives::error::QueryError>;
 
+    /// Dry runs the transaction and all the receipts it produces on top of the post state of
+    /// `block`, without persisting anything.
+    /// `state_roots` are the post state roots of the shards tracked by this node; the simulation
+    /// fails if any other shard is touched.
+    fn simulate_transaction(
+        &self,
+        block: &BlockHeader,
+        state_roots: HashMap<ShardId, StateRoot>,
+        transaction: &SignedTransaction,
+        verify_signature: bool,
+        check_nonce: bool,
+    ) -> Result<SimulatedTransactionView, near_chain_primitives::error::SimulateTransactionError>;
+
     /// Get part of the state corresponding to the given state root.
     /// `prev_hash` is a block whose post state root is `state_root`.
     /// Returns error when storage is inconsistent.
@@ -544,6 +569,12 @@ pub trait RuntimeAdapter: Send + Sync {
         epoch_id: &EpochId,
         contract_codes: Vec<ContractCode>,
     ) -> Result<(), Error>;
+
+    /// Returns the step-level trace of a recently applied receipt, if it is still kept.
+    #[cfg(feature = "sandbox")]
+    fn get_receipt_trace(&self, _receipt_id: &CryptoHash) -> Option<ReceiptExecutionTraceView> {
+        None
+    }
 }
 
 /// The last known / checked height and time when we have processed it.
@@ -595,7 +626,7 @@ mod tests {
         assert!(b1.header().verify_block_producer(&signer.public_key()));
         let other_signer = create_test_signer("other2");
         let approvals =
-            vec![Some(Box::new(Approval::new(*b1.hash(), 1, 2, &other_signer).signature))];
+            vec![Some(Box::new(Approval::new(*b1.hash(), 1, 2, &other_signer).unwrap().signature))];
         let b2 =
             TestBlockBuilder::new(Clock::real(), &b1, signer.clone()).approvals(approvals).build();
         b2.header().verify_block_producer(&signer.public_key());
@@ -2,6 +2,9 @@
 //! without backwards compatibility of JSON encoding.
 use crate::types::StatusError;
 use near_primitives::congestion_info::CongestionInfo;
+use near_primitives::stateless_validation::witness_size_attribution::{
+    StorageProofSizeSummary, WitnessSizeBreakdown,
+};
 use near_primitives::types::{EpochId, ShardId};
 use near_primitives::views::{
     CatchupStatusView, ChainProcessingInfo, EpochValidatorInfo, RequestedStatePartsView,
@@ -193,6 +196,8 @@ pub enum DebugStatus {
     ChainProcessingStatus,
     // The state parts already requested.
     RequestedStateParts,
+    // Sizes of the latest stored state witnesses.
+    WitnessSizes,
 }
 
 impl actix::Message for DebugStatus {
@@ -214,4 +219,17 @@ pub enum DebugStatusResponse {
     ChainProcessingStatus(ChainProcessingInfo),
     // The state parts already requested.
     RequestedStateParts(Vec<RequestedStatePartsView>),
+    // Sizes of the latest stored state witnesses, the highest first.
+    WitnessSizes(Vec<WitnessSizeView>),
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct WitnessSizeView {
+    pub height: BlockHeight,
+    pub shard_id: ShardId,
+    pub epoch_id: EpochId,
+    /// Sizes of the parts of the witness, without the storage proof attribution which is
+    /// summarized in `main_storage_proof`.
+    pub parts: WitnessSizeBreakdown,
+    pub main_storage_proof: Option<StorageProofSizeSummary>,
 }
@@ -4,16 +4,18 @@ use near_primitives::hash::CryptoHash;
 use near_primitives::merkle::{MerklePath, PartialMerkleTree};
 use near_primitives::network::PeerId;
 use near_primitives::sharding::{ChunkHash, ShardChunk};
+use near_primitives::transaction::SignedTransaction;
 use near_primitives::types::{
     AccountId, BlockHeight, BlockReference, EpochId, EpochReference, MaybeBlockId, ShardId,
     TransactionOrReceiptId,
 };
 use near_primitives::views::validator_stake_view::ValidatorStakeView;
 use near_primitives::views::{
-    BlockView, ChunkView, EpochValidatorInfo, ExecutionOutcomeWithIdView, GasPriceView,
-    LightClientBlockLiteView, LightClientBlockView, MaintenanceWindowsView, QueryRequest,
-    QueryResponse, ReceiptView, SplitStorageInfoView, StateChangesKindsView,
-    StateChangesRequestView, StateChangesView, StateSyncStatusView, SyncStatusView, TxStatusView,
+    BlockView, ChunkView, EpochValidatorInfo, EpochValidatorPerformanceView,
+    ExecutionOutcomeWithIdView, GasPriceView, LightClientBlockLiteView, LightClientBlockView,
+    MaintenanceWindowsView, QueryRequest, QueryResponse, ReceiptView, SimulatedTransactionView,
+    SplitStorageInfoView, StateChangesKindsView, StateChangesRequestView, StateChangesView,
+    StateSyncStatusView, SyncStatusView, TxStatusView,
 };
 pub use near_primitives::views::{StatusResponse, StatusSyncInfo};
 use near_time::Duration;
@@ -628,6 +630,17 @@ impl Message for GetValidatorInfo {
     type Result = Result<EpochValidatorInfo, GetValidatorInfoError>;
 }
 
+/// Requests the performance of the validators in the current epoch up to `block_id`, or up to
+/// the header head if not set.
+#[derive(Debug)]
+pub struct GetValidatorPerformance {
+    pub block_id: MaybeBlockId,
+}
+
+impl Message for GetValidatorPerformance {
+    type Result = Result<EpochValidatorPerformanceView, GetValidatorInfoError>;
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum GetValidatorInfoError {
     #[error("IO Error: {0}")]
@@ -851,6 +864,53 @@ impl Message for GetBlockProof {
     type Result = Result<GetBlockProofResponse, GetBlockProofError>;
 }
 
+/// Requests a proof of the value of `key` in the storage of `account_id` at the beginning of
+/// the block `block_hash`, anchored in the block merkle tree of `head_block_hash`.
+#[derive(Debug)]
+pub struct GetStateProof {
+    pub account_id: AccountId,
+    pub key: Vec<u8>,
+    pub block_hash: CryptoHash,
+    pub head_block_hash: CryptoHash,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GetStateProofError {
+    #[error("Block either has never been observed on the node or has been garbage collected: {error_message}")]
+    UnknownBlock { error_message: String },
+    #[error("Node doesn't track the shard {shard_id} of the requested account")]
+    UnavailableShard { shard_id: ShardId },
+    #[error("Internal error: {error_message}")]
+    InternalError { error_message: String },
+    // NOTE: Currently, the underlying errors are too broad, and while we tried to handle
+    // expected cases, we cannot statically guarantee that no other errors will be returned
+    // in the future.
+    // TODO #3851: Remove this variant once we can exhaustively match all the underlying errors
+    #[error("It is a bug if you receive this error type, please, report this incident: https://github.com/near/nearcore/issues/new/choose. Details: {error_message}")]
+    Unreachable { error_message: String },
+}
+
+impl From<near_chain_primitives::error::Error> for GetStateProofError {
+    fn from(error: near_chain_primitives::error::Error) -> Self {
+        match error {
+            near_chain_primitives::error::Error::DBNotFoundErr(error_message) => {
+                Self::UnknownBlock { error_message }
+            }
+            near_chain_primitives::error::Error::Other(error_message) => {
+                Self::InternalError { error_message }
+            }
+            near_chain_primitives::error::Error::StorageError(err) => {
+                Self::InternalError { error_message: err.to_string() }
+            }
+            err => Self::Unreachable { error_message: err.to_string() },
+        }
+    }
+}
+
+impl Message for GetStateProof {
+    type Result = Result<near_primitives::state_proof::ContractStateProof, GetStateProofError>;
+}
+
 #[derive(Debug)]
 pub struct GetReceipt {
     pub receipt_id: CryptoHash,
@@ -883,6 +943,52 @@ impl Message for GetReceipt {
     type Result = Result<Option<ReceiptView>, GetReceiptError>;
 }
 
+/// Dry runs the transaction and all its receipts on top of the latest final block.
+#[derive(Debug)]
+pub struct SimulateTransaction {
+    pub signed_transaction: SignedTransaction,
+    pub verify_signature: bool,
+    pub check_nonce: bool,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SimulateTransactionError {
+    #[error("Transaction is invalid: {0:?}")]
+    InvalidTransaction(near_primitives::errors::InvalidTxError),
+    #[error("Node doesn't track shard {0}")]
+    UnavailableShard(ShardId),
+    #[error("There are no fully synchronized blocks yet")]
+    NoSyncedBlocks,
+    #[error("Internal error: {0}")]
+    InternalError(String),
+}
+
+impl From<near_chain_primitives::error::SimulateTransactionError> for SimulateTransactionError {
+    fn from(error: near_chain_primitives::error::SimulateTransactionError) -> Self {
+        match error {
+            near_chain_primitives::error::SimulateTransactionError::InvalidTransaction(error) => {
+                Self::InvalidTransaction(error)
+            }
+            near_chain_primitives::error::SimulateTransactionError::UnavailableShard(shard_id) => {
+                Self::UnavailableShard(shard_id)
+            }
+            near_chain_primitives::error::SimulateTransactionError::InternalError(error) => {
+                Self::InternalError(error)
+            }
+        }
+    }
+}
+
+impl From<near_chain_primitives::Error> for SimulateTransactionError {
+    fn from(error: near_chain_primitives::Error) -> Self {
+        Self::InternalError(error.to_string())
+    }
+}
+
+impl Message for SimulateTransaction {
+    type Result = Result<SimulatedTransactionView, SimulateTransactionError>;
+}
+
 #[derive(Debug)]
 pub struct GetProtocolConfig(pub BlockReference);
 
@@ -1009,6 +1115,7 @@ pub enum SandboxMessage {
     SandboxPatchStateStatus,
     SandboxFastForward(near_primitives::types::BlockHeightDelta),
     SandboxFastForwardStatus,
+    SandboxTraceReceipt(CryptoHash),
 }
 
 #[cfg(feature = "sandbox")]
@@ -1017,6 +1124,7 @@ pub enum SandboxResponse {
     SandboxPatchStateFinished(bool),
     SandboxFastForwardFinished(bool),
     SandboxFastForwardFailed(String),
+    SandboxReceiptTrace(Option<Box<near_primitives::views::ReceiptExecutionTraceView>>),
     SandboxNoResponse,
 }
 #[cfg(feature = "sandbox")]
@@ -1,12 +1,16 @@
 //! Helper functions for obtaining chunks from the chunk_distribution_network.
 
+use crate::metrics;
+use anyhow::Context;
 use borsh::BorshDeserialize;
+use futures::stream::{FuturesUnordered, StreamExt};
 use near_async::messaging::Sender;
+use near_async::time::Duration;
 use near_chain::{
     blocks_delay_tracker::BlocksDelayTracker, chain::BlockMissingChunks,
     orphan::OrphanMissingChunks,
 };
-use near_chain_configs::{ChunkDistributionNetworkConfig, ClientConfig};
+use near_chain_configs::{ChunkDistributionBackendConfig, ChunkDistributionUris, ClientConfig};
 use near_chunks::adapter::ShardsManagerRequestFromClient;
 use near_primitives::{
     hash::CryptoHash,
@@ -14,6 +18,7 @@ use near_primitives::{
     types::{EpochId, ShardId},
 };
 use std::fmt;
+use std::path::PathBuf;
 use tracing::{debug, error};
 
 /// Basic interface for the chunk distribution network.
@@ -38,22 +43,63 @@ pub trait ChunkDistributionClient {
 }
 
 /// Helper struct for the Chunk Distribution Network Feature.
+/// Dispatches to the configured backend and records its latency and hit rate.
 #[derive(Debug, Clone)]
 pub struct ChunkDistributionNetwork {
-    client: reqwest::Client,
-    config: ChunkDistributionNetworkConfig,
+    backend: ChunkDistributionBackend,
+    enabled: bool,
+    publish_only: bool,
+}
+
+#[derive(Debug, Clone)]
+enum ChunkDistributionBackend {
+    Http(HttpChunkDistributionClient),
+    Filesystem(FilesystemChunkDistributionClient),
+    MultiEndpoint(MultiEndpointChunkDistributionClient<HttpChunkDistributionClient>),
+}
+
+impl ChunkDistributionBackend {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Http(_) => "http",
+            Self::Filesystem(_) => "filesystem",
+            Self::MultiEndpoint(_) => "multi_endpoint",
+        }
+    }
 }
 
 impl ChunkDistributionNetwork {
     pub fn from_config(config: &ClientConfig) -> Option<Self> {
-        config
-            .chunk_distribution_network
-            .as_ref()
-            .map(|c| Self { client: reqwest::Client::new(), config: c.clone() })
+        config.chunk_distribution_network.as_ref().map(|c| {
+            let backend = match &c.backend {
+                None => {
+                    ChunkDistributionBackend::Http(HttpChunkDistributionClient::new(c.uris.clone()))
+                }
+                Some(ChunkDistributionBackendConfig::Filesystem { path, retention }) => {
+                    ChunkDistributionBackend::Filesystem(FilesystemChunkDistributionClient::new(
+                        path.clone(),
+                        *retention,
+                    ))
+                }
+                Some(ChunkDistributionBackendConfig::MultiEndpoint { endpoints, hedge_delay }) => {
+                    let endpoints =
+                        endpoints.iter().cloned().map(HttpChunkDistributionClient::new).collect();
+                    ChunkDistributionBackend::MultiEndpoint(
+                        MultiEndpointChunkDistributionClient::new(endpoints, *hedge_delay),
+                    )
+                }
+            };
+            Self { backend, enabled: c.enabled, publish_only: c.publish_only }
+        })
     }
 
     pub fn enabled(&self) -> bool {
-        self.config.enabled
+        self.enabled
+    }
+
+    /// Whether missing chunks are looked up in the network before requesting them over p2p.
+    pub fn lookup_enabled(&self) -> bool {
+        self.enabled && !self.publish_only
     }
 }
 
@@ -173,6 +219,75 @@ fn request_orphan_chunk<C>(
 }
 
 impl ChunkDistributionClient for ChunkDistributionNetwork {
+    type Error = anyhow::Error;
+    type Response = ();
+
+    async fn lookup_chunk(
+        &self,
+        prev_hash: CryptoHash,
+        shard_id: ShardId,
+    ) -> Result<Option<PartialEncodedChunk>, Self::Error> {
+        let backend = self.backend.name();
+        let _timer = metrics::CHUNK_DISTRIBUTION_NETWORK_REQUEST_TIME
+            .with_label_values(&[backend, "lookup"])
+            .start_timer();
+        let result = match &self.backend {
+            ChunkDistributionBackend::Http(client) => {
+                client.lookup_chunk(prev_hash, shard_id).await
+            }
+            ChunkDistributionBackend::Filesystem(client) => {
+                client.lookup_chunk(prev_hash, shard_id).await
+            }
+            ChunkDistributionBackend::MultiEndpoint(client) => {
+                client.lookup_chunk(prev_hash, shard_id).await
+            }
+        };
+        let label = match &result {
+            Ok(Some(_)) => "hit",
+            Ok(None) => "miss",
+            Err(_) => "error",
+        };
+        metrics::CHUNK_DISTRIBUTION_NETWORK_REQUESTS_TOTAL
+            .with_label_values(&[backend, "lookup", label])
+            .inc();
+        result
+    }
+
+    async fn publish_chunk(
+        &mut self,
+        chunk: &PartialEncodedChunk,
+    ) -> Result<Self::Response, Self::Error> {
+        let backend = self.backend.name();
+        let _timer = metrics::CHUNK_DISTRIBUTION_NETWORK_REQUEST_TIME
+            .with_label_values(&[backend, "publish"])
+            .start_timer();
+        let result = match &mut self.backend {
+            ChunkDistributionBackend::Http(client) => client.publish_chunk(chunk).await.map(|_| ()),
+            ChunkDistributionBackend::Filesystem(client) => client.publish_chunk(chunk).await,
+            ChunkDistributionBackend::MultiEndpoint(client) => client.publish_chunk(chunk).await,
+        };
+        let label = if result.is_ok() { "ok" } else { "error" };
+        metrics::CHUNK_DISTRIBUTION_NETWORK_REQUESTS_TOTAL
+            .with_label_values(&[backend, "publish", label])
+            .inc();
+        result
+    }
+}
+
+/// Backend pulling chunks from and pushing chunks to a pair of HTTP URIs.
+#[derive(Debug, Clone)]
+pub struct HttpChunkDistributionClient {
+    client: reqwest::Client,
+    uris: ChunkDistributionUris,
+}
+
+impl HttpChunkDistributionClient {
+    pub fn new(uris: ChunkDistributionUris) -> Self {
+        Self { client: reqwest::Client::new(), uris }
+    }
+}
+
+impl ChunkDistributionClient for HttpChunkDistributionClient {
     type Error = anyhow::Error;
     type Response = reqwest::Response;
 
@@ -181,7 +296,7 @@ impl ChunkDistributionClient for ChunkDistributionNetwork {
         prev_hash: CryptoHash,
         shard_id: ShardId,
     ) -> Result<Option<PartialEncodedChunk>, Self::Error> {
-        let url = &self.config.uris.get;
+        let url = &self.uris.get;
         let request = self
             .client
             .get(url)
@@ -197,7 +312,7 @@ impl ChunkDistributionClient for ChunkDistributionNetwork {
     ) -> Result<Self::Response, Self::Error> {
         let prev_hash = chunk.prev_block();
         let bytes = borsh::to_vec(chunk)?;
-        let url = &self.config.uris.set;
+        let url = &self.uris.set;
         let request = self
             .client
             .post(url)
@@ -211,6 +326,195 @@ impl ChunkDistributionClient for ChunkDistributionNetwork {
     }
 }
 
+/// How often at most the filesystem backend scans its directory for old chunks.
+const FILESYSTEM_REMOVE_OLD_CHUNKS_PERIOD: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Backend keeping every chunk in a file of a directory shared by co-located nodes.
+#[derive(Debug, Clone)]
+pub struct FilesystemChunkDistributionClient {
+    path: PathBuf,
+    retention: Duration,
+    /// When old chunks were last removed, so that the directory isn't scanned on every publish.
+    last_removal: Option<std::time::Instant>,
+}
+
+impl FilesystemChunkDistributionClient {
+    pub fn new(path: PathBuf, retention: Duration) -> Self {
+        Self { path, retention, last_removal: None }
+    }
+
+    fn chunk_file_name(prev_hash: &CryptoHash, shard_id: ShardId) -> String {
+        format!("{prev_hash}_{shard_id}")
+    }
+
+    /// Removes the chunks published longer than `retention` ago, unless that was already done
+    /// less than `FILESYSTEM_REMOVE_OLD_CHUNKS_PERIOD` ago.
+    ///
+    /// Other nodes sharing the directory remove the same files concurrently, so files which
+    /// disappear in the meantime are skipped.
+    async fn remove_old_chunks(&mut self) -> std::io::Result<()> {
+        if self
+            .last_removal
+            .is_some_and(|last| last.elapsed() < FILESYSTEM_REMOVE_OLD_CHUNKS_PERIOD)
+        {
+            return Ok(());
+        }
+        self.last_removal = Some(std::time::Instant::now());
+        let retention = self.retention.unsigned_abs();
+        let mut entries = tokio::fs::read_dir(&self.path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let modified = match entry.metadata().await {
+                Ok(metadata) => metadata.modified()?,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            if modified.elapsed().is_ok_and(|age| age > retention) {
+                match tokio::fs::remove_file(entry.path()).await {
+                    Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ChunkDistributionClient for FilesystemChunkDistributionClient {
+    type Error = anyhow::Error;
+    type Response = ();
+
+    async fn lookup_chunk(
+        &self,
+        prev_hash: CryptoHash,
+        shard_id: ShardId,
+    ) -> Result<Option<PartialEncodedChunk>, Self::Error> {
+        let path = self.path.join(Self::chunk_file_name(&prev_hash, shard_id));
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => {
+                let chunk = PartialEncodedChunk::try_from_slice(&bytes)
+                    .with_context(|| format!("corrupt chunk file {}", path.display()))?;
+                Ok(Some(chunk))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn publish_chunk(
+        &mut self,
+        chunk: &PartialEncodedChunk,
+    ) -> Result<Self::Response, Self::Error> {
+        tokio::fs::create_dir_all(&self.path).await?;
+        if let Err(err) = self.remove_old_chunks().await {
+            debug!(target: "client", ?err, path = ?self.path, "Failed to remove old chunks");
+        }
+        let file_name = Self::chunk_file_name(chunk.prev_block(), chunk.shard_id());
+        // Write to a temporary file first, so that readers never see a partially written chunk.
+        let tmp_path = self.path.join(format!(".{file_name}.{}", std::process::id()));
+        tokio::fs::write(&tmp_path, borsh::to_vec(chunk)?).await?;
+        tokio::fs::rename(&tmp_path, self.path.join(file_name)).await?;
+        Ok(())
+    }
+}
+
+/// Backend spreading requests over several endpoints serving the same chunks.
+#[derive(Debug, Clone)]
+pub struct MultiEndpointChunkDistributionClient<C> {
+    endpoints: Vec<C>,
+    hedge_delay: Option<Duration>,
+}
+
+impl<C> MultiEndpointChunkDistributionClient<C> {
+    pub fn new(endpoints: Vec<C>, hedge_delay: Option<Duration>) -> Self {
+        Self { endpoints, hedge_delay }
+    }
+}
+
+impl<C> ChunkDistributionClient for MultiEndpointChunkDistributionClient<C>
+where
+    C: ChunkDistributionClient,
+    C::Error: fmt::Debug,
+{
+    type Error = C::Error;
+    type Response = ();
+
+    /// Looks the chunk up from the endpoints in order, until one of them has it. With a hedge
+    /// delay, the next endpoint is also asked whenever no answer comes within the delay.
+    /// Returns an error only if all endpoints failed.
+    async fn lookup_chunk(
+        &self,
+        prev_hash: CryptoHash,
+        shard_id: ShardId,
+    ) -> Result<Option<PartialEncodedChunk>, Self::Error> {
+        let mut endpoints = self.endpoints.iter();
+        let mut lookups = FuturesUnordered::new();
+        lookups.extend(endpoints.next().map(|endpoint| endpoint.lookup_chunk(prev_hash, shard_id)));
+        let mut missed = false;
+        let mut last_error = None;
+        loop {
+            let result = match self.hedge_delay {
+                Some(hedge_delay) if endpoints.len() > 0 => {
+                    tokio::select! {
+                        result = lookups.next() => result,
+                        _ = tokio::time::sleep(hedge_delay.unsigned_abs()) => {
+                            lookups.extend(
+                                endpoints.next().map(|endpoint| endpoint.lookup_chunk(prev_hash, shard_id)),
+                            );
+                            continue;
+                        }
+                    }
+                }
+                _ => lookups.next().await,
+            };
+            let Some(result) = result else {
+                break;
+            };
+            match result {
+                Ok(Some(chunk)) => return Ok(Some(chunk)),
+                Ok(None) => missed = true,
+                Err(err) => {
+                    debug!(target: "client", ?err, "Chunk Distribution Network endpoint failed");
+                    last_error = Some(err);
+                }
+            }
+            // Fail over to the next endpoint right away.
+            lookups.extend(
+                endpoints.next().map(|endpoint| endpoint.lookup_chunk(prev_hash, shard_id)),
+            );
+        }
+        match last_error {
+            Some(err) if !missed => Err(err),
+            _ => Ok(None),
+        }
+    }
+
+    /// Publishes the chunk to all endpoints. Succeeds if at least one of them accepted it.
+    async fn publish_chunk(
+        &mut self,
+        chunk: &PartialEncodedChunk,
+    ) -> Result<Self::Response, Self::Error> {
+        let results = futures::future::join_all(
+            self.endpoints.iter_mut().map(|endpoint| endpoint.publish_chunk(chunk)),
+        )
+        .await;
+        let mut last_error = None;
+        let mut published = false;
+        for result in results {
+            match result {
+                Ok(_) => published = true,
+                Err(err) => {
+                    debug!(target: "client", ?err, "Chunk Distribution Network endpoint failed");
+                    last_error = Some(err);
+                }
+            }
+        }
+        match last_error {
+            Some(err) if !published => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -393,6 +697,89 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_filesystem_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut client = FilesystemChunkDistributionClient::new(
+            dir.path().join("chunks"),
+            Duration::minutes(10),
+        );
+        let chunk = mock_shard_chunk(1, ShardId::new(0));
+        let other_chunk = mock_shard_chunk(1, ShardId::new(1));
+
+        let system = actix::System::new();
+        system.block_on(async {
+            // Nothing was published yet, not even the directory exists.
+            let lookup = client.lookup_chunk(*chunk.prev_block(), chunk.shard_id()).await;
+            assert_eq!(lookup.unwrap(), None);
+
+            client.publish_chunk(&chunk).await.unwrap();
+            let lookup = client.lookup_chunk(*chunk.prev_block(), chunk.shard_id()).await;
+            assert_eq!(lookup.unwrap(), Some(chunk.clone()));
+            let lookup =
+                client.lookup_chunk(*other_chunk.prev_block(), other_chunk.shard_id()).await;
+            assert_eq!(lookup.unwrap(), None);
+
+            // A corrupt file is an error rather than a missing chunk.
+            let file_name = FilesystemChunkDistributionClient::chunk_file_name(
+                other_chunk.prev_block(),
+                other_chunk.shard_id(),
+            );
+            std::fs::write(dir.path().join("chunks").join(file_name), b"corrupt").unwrap();
+            let lookup =
+                client.lookup_chunk(*other_chunk.prev_block(), other_chunk.shard_id()).await;
+            assert!(lookup.is_err());
+        });
+    }
+
+    #[test]
+    fn test_multi_endpoint_client() {
+        let chunk = mock_shard_chunk(1, ShardId::new(0));
+        let mut known = MockClient::default();
+        known.publish_chunk(&chunk).now_or_never();
+        let failing = TestEndpoint { client: MockClient::default(), fail: true, delay: None };
+        let missing = TestEndpoint { client: MockClient::default(), fail: false, delay: None };
+        let serving = TestEndpoint { client: known.clone(), fail: false, delay: None };
+        let stuck = TestEndpoint {
+            client: known,
+            fail: false,
+            delay: Some(std::time::Duration::from_secs(3600)),
+        };
+        let lookup = |endpoints: Vec<TestEndpoint>, hedge_delay: Option<Duration>| {
+            let client = MultiEndpointChunkDistributionClient::new(endpoints, hedge_delay);
+            let chunk = chunk.clone();
+            async move {
+                let lookup = client.lookup_chunk(*chunk.prev_block(), chunk.shard_id());
+                tokio::time::timeout(std::time::Duration::from_secs(10), lookup)
+                    .await
+                    .expect("lookup must not wait for the stuck endpoint")
+            }
+        };
+
+        let system = actix::System::new();
+        system.block_on(async {
+            // Endpoints are tried in order until one of them has the chunk.
+            let result = lookup(vec![failing.clone(), missing.clone(), serving.clone()], None);
+            assert_eq!(result.await.unwrap(), Some(chunk.clone()));
+            // A miss is not an error, as long as one endpoint answered.
+            let result = lookup(vec![failing.clone(), missing.clone()], None);
+            assert_eq!(result.await.unwrap(), None);
+            let result = lookup(vec![failing.clone(), failing.clone()], None);
+            assert!(result.await.is_err());
+            // With a hedge delay, a slow endpoint doesn't hold the lookup back.
+            let result =
+                lookup(vec![stuck.clone(), serving.clone()], Some(Duration::milliseconds(10)));
+            assert_eq!(result.await.unwrap(), Some(chunk.clone()));
+
+            // Publishing succeeds if any of the endpoints accepted the chunk.
+            let mut client =
+                MultiEndpointChunkDistributionClient::new(vec![failing.clone(), missing], None);
+            client.publish_chunk(&chunk).await.unwrap();
+            let mut client = MultiEndpointChunkDistributionClient::new(vec![failing], None);
+            assert!(client.publish_chunk(&chunk).await.is_err());
+        });
+    }
+
     fn mock_shard_chunk(height: u64, shard_id: ShardId) -> PartialEncodedChunk {
         let prev_block_hash =
             hash(&[height.to_le_bytes().as_slice(), shard_id.to_le_bytes().as_slice()].concat());
@@ -416,7 +803,7 @@ mod tests {
             congestion_info: CongestionInfo::default(),
             bandwidth_requests: BandwidthRequests::empty(),
         });
-        let header = ShardChunkHeaderV3::from_inner(header_inner, &signer);
+        let header = ShardChunkHeaderV3::from_inner(header_inner, &signer).unwrap();
         PartialEncodedChunk::V2(PartialEncodedChunkV2 {
             header: ShardChunkHeader::V3(header),
             parts: Vec::new(),
@@ -474,6 +861,43 @@ mod tests {
         }
     }
 
+    /// Endpoint of a multi-endpoint client that can be broken or slow.
+    #[derive(Debug, Clone)]
+    struct TestEndpoint {
+        client: MockClient,
+        fail: bool,
+        delay: Option<std::time::Duration>,
+    }
+    impl ChunkDistributionClient for TestEndpoint {
+        type Error = ();
+        type Response = ();
+
+        async fn lookup_chunk(
+            &self,
+            prev_hash: CryptoHash,
+            shard_id: ShardId,
+        ) -> Result<Option<PartialEncodedChunk>, Self::Error> {
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+            if self.fail {
+                return Err(());
+            }
+            Ok(self.client.lookup_chunk(prev_hash, shard_id).await.unwrap())
+        }
+
+        async fn publish_chunk(
+            &mut self,
+            chunk: &PartialEncodedChunk,
+        ) -> Result<Self::Response, Self::Error> {
+            if self.fail {
+                return Err(());
+            }
+            self.client.publish_chunk(chunk).await.unwrap();
+            Ok(())
+        }
+    }
+
     #[derive(Debug)]
     struct MockSender<M> {
         inner: mpsc::UnboundedSender<M>,
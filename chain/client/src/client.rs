@@ -23,7 +23,9 @@ use near_chain::chain::{
 };
 use near_chain::flat_storage_creator::FlatStorageCreator;
 use near_chain::orphan::OrphanMissingChunks;
+use near_chain::signing_ledger::{SignedMessage, SigningLedger};
 use near_chain::state_snapshot_actor::SnapshotCallbacks;
+use near_chain::stateless_validation::shadow_validation_report::ShadowValidationReport;
 use near_chain::test_utils::format_hash;
 use near_chain::types::PrepareTransactionsChunkContext;
 use near_chain::types::{
@@ -193,6 +195,8 @@ pub struct Client {
     /// Helper module for stateless validation functionality like chunk witness production, validation
     /// chunk endorsements tracking etc.
     pub chunk_validator: ChunkValidator,
+    /// Records everything signed by the validator key, so that conflicting signatures are refused.
+    pub signing_ledger: SigningLedger,
     /// Tracks current chunks that are ready to be included in block
     /// Also tracks banned chunk producers and filters out chunks produced by them
     pub chunk_inclusion_tracker: ChunkInclusionTracker,
@@ -202,6 +206,8 @@ pub struct Client {
     pub partial_witness_adapter: PartialWitnessSenderForClient,
     // Optional value used for the Chunk Distribution Network Feature.
     chunk_distribution_network: Option<ChunkDistributionNetwork>,
+    /// Report of the shadow chunk validation, if enabled and configured to be written.
+    pub shadow_validation_report: Option<Arc<ShadowValidationReport>>,
 }
 
 impl AsRef<Client> for Client {
@@ -222,6 +228,7 @@ impl Client {
             .config
             .produce_chunk_add_transactions_time_limit
             .update(update_client_config.produce_chunk_add_transactions_time_limit);
+        is_updated |= self.config.tracked_accounts.update(update_client_config.tracked_accounts);
         is_updated
     }
 
@@ -364,6 +371,7 @@ impl Client {
             save_trie_changes: config.save_trie_changes,
             background_migration_threads: config.client_background_migration_threads,
             resharding_config: config.resharding_config.clone(),
+            save_latest_witnesses: config.save_latest_witnesses,
         };
         let chain = Chain::new(
             clock.clone(),
@@ -444,14 +452,34 @@ impl Client {
             epoch_manager.clone(),
             chain.chain_store().store().clone(),
         );
+        let signing_ledger = SigningLedger::new(chain.chain_store().store().clone());
         let chunk_validator = ChunkValidator::new(
             epoch_manager.clone(),
             network_adapter.clone().into_sender(),
             runtime_adapter.clone(),
             config.orphan_state_witness_pool_size,
             async_computation_spawner,
+            signing_ledger.clone(),
         );
         let chunk_distribution_network = ChunkDistributionNetwork::from_config(&config);
+        let shadow_validation_report = match config
+            .shadow_chunk_validation
+            .as_ref()
+            .and_then(|config| config.report_file.as_ref())
+        {
+            Some(path) => {
+                let version =
+                    format!("{} (build {})", config.version.version, config.version.build);
+                let report = ShadowValidationReport::open(path, version).map_err(|err| {
+                    Error::Other(format!(
+                        "failed to open the shadow validation report {}: {err}",
+                        path.display()
+                    ))
+                })?;
+                Some(Arc::new(report))
+            }
+            None => None,
+        };
         Ok(Self {
             #[cfg(feature = "test_features")]
             adv_produce_blocks: None,
@@ -501,10 +529,12 @@ impl Client {
             flat_storage_creator,
             last_time_sync_block_requested: HashMap::new(),
             chunk_validator,
+            signing_ledger,
             chunk_inclusion_tracker: ChunkInclusionTracker::new(),
             chunk_endorsement_tracker,
             partial_witness_adapter,
             chunk_distribution_network,
+            shadow_validation_report,
         })
     }
 
@@ -905,7 +935,11 @@ impl Client {
             block_merkle_root,
             self.clock.clone(),
             sandbox_delta_time,
-        );
+        )
+        .map_err(|err| Error::BlockProducer(err.to_string()))?;
+        self.signing_ledger
+            .record(&validator_signer.public_key(), &SignedMessage::block_header(block.header()))
+            .map_err(|err| Error::BlockProducer(err.to_string()))?;
 
         // Update latest known even before returning block out, to prevent race conditions.
         self.chain
@@ -1038,6 +1072,12 @@ impl Client {
             &mut self.rs_for_chunk_production,
             protocol_version,
         )?;
+        self.signing_ledger
+            .record(
+                &validator_signer.public_key(),
+                &SignedMessage::chunk_header(&encoded_chunk.cloned_header()),
+            )
+            .map_err(|err| Error::ChunkProducer(err.to_string()))?;
 
         span.record("chunk_hash", tracing::field::debug(encoded_chunk.chunk_hash()));
         debug!(target: "client",
@@ -1184,7 +1224,13 @@ impl Client {
     ) {
         if let Some(validator_signer) = &signer {
             for body in challenges {
-                let challenge = Challenge::produce(body, &**validator_signer);
+                let challenge = match Challenge::produce(body, &**validator_signer) {
+                    Ok(challenge) => challenge,
+                    Err(err) => {
+                        warn!(target: "client", ?err, "Failed to sign challenge");
+                        continue;
+                    }
+                };
                 self.challenges.insert(challenge.hash, challenge.clone());
                 self.network_adapter.send(PeerManagerMessageRequest::NetworkRequests(
                     NetworkRequests::Challenge(challenge),
@@ -1417,24 +1463,25 @@ impl Client {
         // Send out challenge if the block was found to be invalid.
         if let Some(signer) = signer {
             if let Err(e) = &result {
-                match e {
+                let body = match e {
                     near_chain::Error::InvalidChunkProofs(chunk_proofs) => {
-                        self.network_adapter.send(PeerManagerMessageRequest::NetworkRequests(
-                            NetworkRequests::Challenge(Challenge::produce(
-                                ChallengeBody::ChunkProofs(*chunk_proofs.clone()),
-                                &*signer,
-                            )),
-                        ));
+                        Some(ChallengeBody::ChunkProofs(*chunk_proofs.clone()))
                     }
                     near_chain::Error::InvalidChunkState(chunk_state) => {
+                        Some(ChallengeBody::ChunkState(*chunk_state.clone()))
+                    }
+                    _ => None,
+                };
+                match body.map(|body| Challenge::produce(body, &*signer)) {
+                    Some(Ok(challenge)) => {
                         self.network_adapter.send(PeerManagerMessageRequest::NetworkRequests(
-                            NetworkRequests::Challenge(Challenge::produce(
-                                ChallengeBody::ChunkState(*chunk_state.clone()),
-                                &*signer,
-                            )),
+                            NetworkRequests::Challenge(challenge),
                         ));
                     }
-                    _ => {}
+                    Some(Err(err)) => {
+                        warn!(target: "client", ?err, "Failed to sign challenge");
+                    }
+                    None => {}
                 }
             }
         }
@@ -1670,6 +1717,11 @@ impl Client {
         approval: Approval,
         signer: &Option<Arc<ValidatorSigner>>,
     ) -> Result<(), Error> {
+        if let Some(signer) = signer {
+            self.signing_ledger
+                .record(&signer.public_key(), &SignedMessage::approval(&approval))
+                .map_err(|err| Error::Other(err.to_string()))?;
+        }
         let next_epoch_id = self.epoch_manager.get_epoch_id_from_prev_block(parent_hash)?;
         let next_block_producer =
             self.epoch_manager.get_block_producer(&next_epoch_id, approval.target_height)?;
@@ -2031,7 +2083,7 @@ impl Client {
             ?orphans_missing_chunks)
         .entered();
         if let Some(chunk_distribution) = &self.chunk_distribution_network {
-            if chunk_distribution.enabled() {
+            if chunk_distribution.lookup_enabled() {
                 return crate::chunk_distribution_network::request_missing_chunks(
                     blocks_missing_chunks,
                     orphans_missing_chunks,
@@ -687,6 +687,11 @@ impl Handler<near_client_primitives::types::SandboxMessage> for ClientActorInner
                     self.fastforward_delta == 0,
                 )
             }
+            near_client_primitives::types::SandboxMessage::SandboxTraceReceipt(receipt_id) => {
+                near_client_primitives::types::SandboxResponse::SandboxReceiptTrace(
+                    self.client.chain.runtime_adapter.get_receipt_trace(&receipt_id).map(Box::new),
+                )
+            }
         }
     }
 }
@@ -972,8 +977,17 @@ impl ClientActorInner {
             debug!(target: "client", "Sending announce account for {}", signer.validator_id());
             self.last_validator_announce_time = Some(now);
 
-            let signature =
-                signer.sign_account_announce(signer.validator_id(), &self.node_id, &next_epoch_id);
+            let signature = match signer.sign_account_announce(
+                signer.validator_id(),
+                &self.node_id,
+                &next_epoch_id,
+            ) {
+                Ok(signature) => signature,
+                Err(err) => {
+                    warn!(target: "client", ?err, "Failed to sign account announcement");
+                    return;
+                }
+            };
             self.network_adapter.send(PeerManagerMessageRequest::NetworkRequests(
                 NetworkRequests::AnnounceAccount(AnnounceAccount {
                     account_id: signer.validator_id().clone(),
@@ -59,6 +59,14 @@ impl ConfigUpdater {
         update_result
     }
 
+    /// Returns a new receiver of the config updates, which can be used to apply the updates
+    /// of configs not owned by the client.
+    pub fn subscribe(
+        &self,
+    ) -> Receiver<Result<UpdateableConfigs, Arc<UpdateableConfigLoaderError>>> {
+        self.rx_config_update.resubscribe()
+    }
+
     /// Prints an error if it's present.
     pub fn report_status(&self) {
         if let Some(updateable_configs_error) = &self.updateable_configs_error {
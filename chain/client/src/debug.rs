@@ -8,7 +8,7 @@ use near_chain::crypto_hash_timer::CryptoHashTimer;
 use near_chain::{near_chain_primitives, Block, Chain, ChainStoreAccess};
 use near_client_primitives::debug::{
     ApprovalAtHeightStatus, BlockProduction, ChunkCollection, DebugBlockStatusData, DebugStatus,
-    DebugStatusResponse, MissedHeightInfo, ProductionAtHeight, ValidatorStatus,
+    DebugStatusResponse, MissedHeightInfo, ProductionAtHeight, ValidatorStatus, WitnessSizeView,
 };
 use near_client_primitives::types::Error;
 use near_client_primitives::{
@@ -46,6 +46,9 @@ use near_primitives::views::{
 // Constants for debug requests.
 const DEBUG_BLOCKS_TO_FETCH: u32 = 50;
 const DEBUG_EPOCHS_TO_FETCH: u32 = 5;
+const DEBUG_WITNESSES_TO_SHOW: usize = 50;
+// How many of the largest accounts and receipts to show for each witness.
+const DEBUG_WITNESS_LARGEST_CONTRIBUTIONS: usize = 10;
 
 // How many old blocks (before HEAD) should be shown in debug page.
 const DEBUG_PRODUCTION_OLD_BLOCKS_TO_SHOW: u64 = 50;
@@ -182,6 +185,9 @@ impl Handler<DebugStatus> for ClientActorInner {
             DebugStatus::ChainProcessingStatus => Ok(DebugStatusResponse::ChainProcessingStatus(
                 self.client.chain.get_chain_processing_info(),
             )),
+            DebugStatus::WitnessSizes => {
+                Ok(DebugStatusResponse::WitnessSizes(self.get_witness_sizes()?))
+            }
         }
     }
 }
@@ -372,6 +378,33 @@ impl ClientActorInner {
         Ok(TrackedShardsView { shards_tracked_this_epoch, shards_tracked_next_epoch })
     }
 
+    fn get_witness_sizes(&self) -> Result<Vec<WitnessSizeView>, near_chain_primitives::Error> {
+        let mut breakdowns = self
+            .client
+            .chain
+            .chain_store()
+            .get_latest_witnesses_size_breakdown(None, None, None)?;
+        // Keys are ordered by height, then shard id.
+        breakdowns.reverse();
+        breakdowns.truncate(DEBUG_WITNESSES_TO_SHOW);
+        Ok(breakdowns
+            .into_iter()
+            .map(|(key, mut parts)| {
+                let main_storage_proof = parts
+                    .main_storage_proof
+                    .take()
+                    .map(|proof| proof.summary(DEBUG_WITNESS_LARGEST_CONTRIBUTIONS));
+                WitnessSizeView {
+                    height: key.height,
+                    shard_id: ShardId::new(key.shard_id),
+                    epoch_id: key.epoch_id,
+                    parts,
+                    main_storage_proof,
+                }
+            })
+            .collect())
+    }
+
     fn get_recent_epoch_info(
         &mut self,
     ) -> Result<Vec<EpochInfoView>, near_chain_primitives::Error> {
@@ -786,6 +819,11 @@ fn new_peer_info_view(chain: &Chain, connected_peer_info: &ConnectedPeerInfo) ->
             .whole_milliseconds() as u64,
         is_outbound_peer: connected_peer_info.peer_type == PeerType::Outbound,
         nonce: connected_peer_info.nonce,
+        send_queue_bytes: connected_peer_info
+            .send_queue_bytes
+            .iter()
+            .map(|(class, bytes)| (class.to_string(), *bytes))
+            .collect(),
     }
 }
 
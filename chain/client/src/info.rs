@@ -19,7 +19,7 @@ use near_primitives::types::{
     ValidatorInfoIdentifier,
 };
 use near_primitives::unwrap_or_return;
-use near_primitives::validator_signer::ValidatorSigner;
+use near_primitives::validator_signer::{ValidatorSigner, ValidatorSignerError};
 use near_primitives::version::{Version, PROTOCOL_VERSION};
 use near_primitives::views::{
     CatchupStatusView, ChunkProcessingStatus, CurrentEpochValidatorInfo, EpochValidatorInfo,
@@ -481,20 +481,24 @@ impl InfoHelper {
         self.num_chunks_in_blocks_processed = 0;
         self.gas_used = 0;
 
-        let telemetry_event = TelemetryEvent {
-            content: self.telemetry_info(
-                head,
-                sync_status,
-                node_id,
-                network_info,
-                client_config,
-                cpu_usage,
-                memory_usage,
-                is_validator,
-                signer,
-            ),
+        let content = match self.telemetry_info(
+            head,
+            sync_status,
+            node_id,
+            network_info,
+            client_config,
+            cpu_usage,
+            memory_usage,
+            is_validator,
+            signer,
+        ) {
+            Ok(content) => content,
+            Err(err) => {
+                tracing::warn!(target: "telemetry", ?err, "Failed to sign telemetry, not sending it");
+                return;
+            }
         };
-        self.telemetry_sender.send(telemetry_event);
+        self.telemetry_sender.send(TelemetryEvent { content });
     }
 
     /// Updates the prometheus metrics to track the block and chunk production and endorsement by validators.
@@ -575,7 +579,7 @@ impl InfoHelper {
         memory_usage: u64,
         is_validator: bool,
         signer: &Option<Arc<ValidatorSigner>>,
-    ) -> serde_json::Value {
+    ) -> Result<serde_json::Value, ValidatorSignerError> {
         let info = TelemetryInfo {
             agent: TelemetryAgentInfo {
                 name: "near-rs".to_string(),
@@ -616,7 +620,7 @@ impl InfoHelper {
         if let Some(signer) = signer {
             signer.sign_telemetry(&info)
         } else {
-            serde_json::to_value(&info).expect("Telemetry must serialize to json")
+            Ok(serde_json::to_value(&info).expect("Telemetry must serialize to json"))
         }
     }
 
@@ -1021,28 +1025,30 @@ mod tests {
         )
         .unwrap();
 
-        let telemetry = info_helper.telemetry_info(
-            &chain.head().unwrap(),
-            &SyncStatus::AwaitingPeers,
-            &peer_id_from_seed("zxc"),
-            &NetworkInfo {
-                connected_peers: vec![],
-                num_connected_peers: 0,
-                peer_max_count: 0,
-                highest_height_peers: vec![],
-                sent_bytes_per_sec: 0,
-                received_bytes_per_sec: 0,
-                known_producers: vec![],
-                tier1_connections: vec![],
-                tier1_accounts_keys: vec![],
-                tier1_accounts_data: vec![],
-            },
-            &config,
-            0.0,
-            0,
-            false,
-            &validator.get(),
-        );
+        let telemetry = info_helper
+            .telemetry_info(
+                &chain.head().unwrap(),
+                &SyncStatus::AwaitingPeers,
+                &peer_id_from_seed("zxc"),
+                &NetworkInfo {
+                    connected_peers: vec![],
+                    num_connected_peers: 0,
+                    peer_max_count: 0,
+                    highest_height_peers: vec![],
+                    sent_bytes_per_sec: 0,
+                    received_bytes_per_sec: 0,
+                    known_producers: vec![],
+                    tier1_connections: vec![],
+                    tier1_accounts_keys: vec![],
+                    tier1_accounts_data: vec![],
+                },
+                &config,
+                0.0,
+                0,
+                false,
+                &validator.get(),
+            )
+            .unwrap();
         println!("Got telemetry info: {:?}", telemetry);
         assert_matches!(
             telemetry["extra_info"].as_str().unwrap().find("\"max_block_production_delay\":2.34,"),
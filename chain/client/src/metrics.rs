@@ -690,3 +690,24 @@ pub(crate) static DECODE_PARTIAL_WITNESS_ACCESSED_CONTRACTS_STATE_COUNT: LazyLoc
         )
         .unwrap()
     });
+
+pub(crate) static CHUNK_DISTRIBUTION_NETWORK_REQUEST_TIME: LazyLock<HistogramVec> =
+    LazyLock::new(|| {
+        try_create_histogram_vec(
+            "near_chunk_distribution_network_request_time",
+            "Time taken to look up or publish a chunk in the Chunk Distribution Network in seconds",
+            &["backend", "operation"],
+            Some(exponential_buckets(0.001, 2.0, 14).unwrap()),
+        )
+        .unwrap()
+    });
+
+pub(crate) static CHUNK_DISTRIBUTION_NETWORK_REQUESTS_TOTAL: LazyLock<IntCounterVec> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "near_chunk_distribution_network_requests_total",
+            "Requests to the Chunk Distribution Network by operation and result. Lookups result in hit, miss or error, publications in ok or error",
+            &["backend", "operation", "result"],
+        )
+        .unwrap()
+    });
@@ -5,6 +5,7 @@ use crate::Client;
 use itertools::Itertools;
 use near_async::futures::{AsyncComputationSpawner, AsyncComputationSpawnerExt};
 use near_async::messaging::{CanSend, Sender};
+use near_chain::signing_ledger::{SignedMessage, SigningLedger};
 use near_chain::stateless_validation::chunk_validation;
 use near_chain::stateless_validation::processing_tracker::ProcessingDoneTracker;
 use near_chain::types::RuntimeAdapter;
@@ -42,6 +43,7 @@ pub struct ChunkValidator {
     orphan_witness_pool: OrphanStateWitnessPool,
     validation_spawner: Arc<dyn AsyncComputationSpawner>,
     main_state_transition_result_cache: chunk_validation::MainStateTransitionCache,
+    signing_ledger: SigningLedger,
 }
 
 impl ChunkValidator {
@@ -51,6 +53,7 @@ impl ChunkValidator {
         runtime_adapter: Arc<dyn RuntimeAdapter>,
         orphan_witness_pool_size: usize,
         validation_spawner: Arc<dyn AsyncComputationSpawner>,
+        signing_ledger: SigningLedger,
     ) -> Self {
         Self {
             epoch_manager,
@@ -60,6 +63,7 @@ impl ChunkValidator {
             validation_spawner,
             main_state_transition_result_cache: chunk_validation::MainStateTransitionCache::default(
             ),
+            signing_ledger,
         }
     }
 
@@ -95,6 +99,7 @@ impl ChunkValidator {
         let chunk_header = state_witness.chunk_header.clone();
         let network_sender = self.network_sender.clone();
         let epoch_manager = self.epoch_manager.clone();
+        let signing_ledger = self.signing_ledger.clone();
         if matches!(
             pre_validation_result.main_transition_params,
             chunk_validation::MainTransition::ShardLayoutChange
@@ -104,6 +109,7 @@ impl ChunkValidator {
                 epoch_manager.as_ref(),
                 signer,
                 &network_sender,
+                &signing_ledger,
             );
             return Ok(());
         }
@@ -135,6 +141,7 @@ impl ChunkValidator {
                         epoch_manager.as_ref(),
                         signer,
                         &network_sender,
+                        &signing_ledger,
                     );
                     return Ok(());
                 }
@@ -173,6 +180,7 @@ impl ChunkValidator {
                         epoch_manager.as_ref(),
                         signer.as_ref(),
                         &network_sender,
+                        &signing_ledger,
                     );
                 }
                 Err(err) => {
@@ -196,6 +204,7 @@ pub(crate) fn send_chunk_endorsement_to_block_producers(
     epoch_manager: &dyn EpochManagerAdapter,
     signer: &ValidatorSigner,
     network_sender: &Sender<PeerManagerMessageRequest>,
+    signing_ledger: &SigningLedger,
 ) {
     let epoch_id =
         epoch_manager.get_epoch_id_from_prev_block(chunk_header.prev_block_hash()).unwrap();
@@ -221,7 +230,20 @@ pub(crate) fn send_chunk_endorsement_to_block_producers(
     );
 
     let protocol_version = epoch_manager.get_epoch_protocol_version(&epoch_id).unwrap();
-    let endorsement = ChunkEndorsement::new(epoch_id, chunk_header, signer, protocol_version);
+    let endorsement = match ChunkEndorsement::new(epoch_id, chunk_header, signer, protocol_version)
+    {
+        Ok(endorsement) => endorsement,
+        Err(err) => {
+            tracing::error!(target: "client", ?chunk_hash, %err, "Failed to sign chunk endorsement");
+            return;
+        }
+    };
+    if let Err(err) =
+        signing_ledger.record(&signer.public_key(), &SignedMessage::chunk_endorsement(chunk_header))
+    {
+        tracing::error!(target: "client", ?chunk_hash, %err, "Not sending chunk endorsement");
+        return;
+    }
     for block_producer in block_producers {
         network_sender.send(PeerManagerMessageRequest::NetworkRequests(
             NetworkRequests::ChunkEndorsement(block_producer, endorsement.clone()),
@@ -262,7 +284,7 @@ impl Client {
         self.send_state_witness_ack(&witness, &signer);
 
         if self.config.save_latest_witnesses {
-            self.chain.chain_store.save_latest_chunk_state_witness(&witness)?;
+            self.chain.chain_store.save_latest_chunk_state_witness(&witness, None)?;
         }
 
         match self.chain.get_block(witness.chunk_header.prev_block_hash()) {
@@ -235,11 +235,12 @@ impl PartialWitnessActor {
         let encoder = self.witness_encoders.entry(chunk_validators.len());
         let (parts, encoded_length) = encoder.encode(&witness_bytes);
 
-        Ok(chunk_validators
+        // Signing any of the parts fails the whole distribution, so that no part is sent.
+        chunk_validators
             .iter()
             .zip_eq(parts)
             .enumerate()
-            .map(|(part_ord, (chunk_validator, part))| {
+            .map(|(part_ord, (chunk_validator, part))| -> Result<_, Error> {
                 // It's fine to unwrap part here as we just constructed the parts above and we expect
                 // all of them to be present.
                 let partial_witness = PartialEncodedStateWitness::new(
@@ -249,10 +250,10 @@ impl PartialWitnessActor {
                     part.unwrap().to_vec(),
                     encoded_length,
                     signer,
-                );
-                (chunk_validator.clone(), partial_witness)
+                )?;
+                Ok((chunk_validator.clone(), partial_witness))
             })
-            .collect_vec())
+            .collect()
     }
 
     fn generate_contract_deploys_parts(
@@ -265,11 +266,11 @@ impl PartialWitnessActor {
         let (parts, encoded_length) = encoder.encode(&deploys);
         let signer = self.my_validator_signer()?;
 
-        Ok(validators
+        validators
             .into_iter()
             .zip_eq(parts)
             .enumerate()
-            .map(|(part_ord, (validator, part))| {
+            .map(|(part_ord, (validator, part))| -> Result<_, Error> {
                 let partial_deploys = PartialEncodedContractDeploys::new(
                     key.clone(),
                     PartialEncodedContractDeploysPart {
@@ -278,10 +279,10 @@ impl PartialWitnessActor {
                         encoded_length,
                     },
                     &signer,
-                );
-                (validator, partial_deploys)
+                )?;
+                Ok((validator, partial_deploys))
             })
-            .collect_vec())
+            .collect()
     }
 
     // Break the state witness into parts and send each part to the corresponding chunk validator owner.
@@ -518,7 +519,7 @@ impl PartialWitnessActor {
             .store_accessed_contract_hashes(key.clone(), missing_contract_hashes.clone())?;
         let random_chunk_producer =
             self.epoch_manager.get_random_chunk_producer_for_shard(&key.epoch_id, key.shard_id)?;
-        let request = ContractCodeRequest::new(key.clone(), missing_contract_hashes, &signer);
+        let request = ContractCodeRequest::new(key.clone(), missing_contract_hashes, &signer)?;
         self.network_adapter.send(PeerManagerMessageRequest::NetworkRequests(
             NetworkRequests::ContractCodeRequest(random_chunk_producer, request),
         ));
@@ -577,7 +578,7 @@ impl PartialWitnessActor {
                 Err(err) => return Err(err.into()),
             }
         }
-        let response = ContractCodeResponse::new(key.clone(), &contracts, &signer);
+        let response = ContractCodeResponse::new(key.clone(), &contracts, &signer)?;
         self.network_adapter.send(PeerManagerMessageRequest::NetworkRequests(
             NetworkRequests::ContractCodeResponse(request.requester().clone(), response),
         ));
@@ -1,41 +1,56 @@
 use crate::stateless_validation::state_witness_producer::CreateWitnessResult;
 use near_chain::stateless_validation::chunk_validation::validate_prepared_transactions;
+use near_chain::stateless_validation::shadow_validation_report::{
+    ShadowValidationRecord, ShadowValidationStatus,
+};
 use near_chain::types::{RuntimeStorageConfig, StorageDataSource};
 use near_chain::{Block, BlockHeader};
 use near_chain_primitives::Error;
-use near_primitives::sharding::{ShardChunk, ShardChunkHeader};
+use near_primitives::sharding::ShardChunkHeader;
+use near_primitives::stateless_validation::state_witness::ChunkStateWitness;
 
 use crate::Client;
 
 impl Client {
-    // Temporary feature to make node produce state witness for every chunk in every processed block
-    // and then self-validate it.
+    // Makes the node produce a state witness for every new chunk of the tracked shards in every
+    // processed block, and then self-validate it. Enabled by the `shadow_chunk_validation` config
+    // or cargo feature.
     pub(crate) fn shadow_validate_block_chunks(&mut self, block: &Block) -> Result<(), Error> {
-        if !cfg!(feature = "shadow_chunk_validation") {
+        if !cfg!(feature = "shadow_chunk_validation")
+            && self.config.shadow_chunk_validation.is_none()
+        {
             return Ok(());
         }
         let block_hash = block.hash();
         tracing::debug!(target: "client", ?block_hash, "shadow validation for block chunks");
         let prev_block = self.chain.get_block(block.header().prev_hash())?;
         let prev_block_chunks = prev_block.chunks();
-        for (shard_index, chunk) in block
+        let me = self.validator_signer.get().map(|signer| signer.validator_id().clone());
+        for (shard_index, chunk_header) in block
             .chunks()
             .iter_deprecated()
             .enumerate()
             .filter(|(_, chunk)| chunk.is_new_chunk(block.header().height()))
         {
-            let chunk = self.chain.get_chunk_clone_from_header(chunk)?;
+            if !self.shard_tracker.care_about_shard(
+                me.as_ref(),
+                block.header().prev_hash(),
+                chunk_header.shard_id(),
+                true,
+            ) {
+                continue;
+            }
             // TODO(resharding) This doesn't work if shard layout changes.
             let prev_chunk_header = prev_block_chunks.get(shard_index).unwrap();
             if let Err(err) =
-                self.shadow_validate_chunk(prev_block.header(), prev_chunk_header, &chunk)
+                self.shadow_validate_chunk(prev_block.header(), prev_chunk_header, chunk_header)
             {
                 near_chain::stateless_validation::metrics::SHADOW_CHUNK_VALIDATION_FAILED_TOTAL
                     .inc();
                 tracing::error!(
                     target: "client",
                     ?err,
-                    shard_id = ?chunk.shard_id(),
+                    shard_id = ?chunk_header.shard_id(),
                     ?block_hash,
                     "shadow chunk validation failed"
                 );
@@ -48,9 +63,39 @@ impl Client {
         &mut self,
         prev_block_header: &BlockHeader,
         prev_chunk_header: &ShardChunkHeader,
-        chunk: &ShardChunk,
+        chunk_header: &ShardChunkHeader,
     ) -> Result<(), Error> {
-        let chunk_header = chunk.cloned_header();
+        let state_witness = match self.create_shadow_state_witness(
+            prev_block_header,
+            prev_chunk_header,
+            chunk_header,
+        ) {
+            Ok(state_witness) => state_witness,
+            Err(err) => {
+                // The chain reports the outcome of the validation itself, but not a witness which
+                // couldn't be produced in the first place.
+                let mut record = ShadowValidationRecord::new(chunk_header);
+                record.status = ShadowValidationStatus::failed(&err);
+                record.finish(self.shadow_validation_report.as_deref());
+                return Err(err);
+            }
+        };
+        self.chain.shadow_validate_state_witness(
+            state_witness,
+            self.epoch_manager.as_ref(),
+            self.runtime_adapter.as_ref(),
+            None,
+            self.shadow_validation_report.clone(),
+        )
+    }
+
+    fn create_shadow_state_witness(
+        &mut self,
+        prev_block_header: &BlockHeader,
+        prev_chunk_header: &ShardChunkHeader,
+        chunk_header: &ShardChunkHeader,
+    ) -> Result<ChunkStateWitness, Error> {
+        let chunk = self.chain.get_chunk_clone_from_header(chunk_header)?;
         let last_chunk = self.chain.get_chunk(&prev_chunk_header.chunk_hash())?;
 
         let transactions_validation_storage_config = RuntimeStorageConfig {
@@ -65,7 +110,7 @@ impl Client {
         let Ok(validated_transactions) = validate_prepared_transactions(
             &self.chain,
             self.runtime_adapter.as_ref(),
-            &chunk_header,
+            chunk_header,
             transactions_validation_storage_config,
             chunk.transactions(),
             last_chunk.transactions(),
@@ -75,23 +120,20 @@ impl Client {
             ));
         };
 
-        let CreateWitnessResult { state_witness, .. } = self.create_state_witness(
-            // Setting arbitrary chunk producer is OK for shadow validation
-            "alice.near".parse().unwrap(),
-            prev_block_header,
-            prev_chunk_header,
-            chunk,
-            validated_transactions.storage_proof,
-        )?;
+        let CreateWitnessResult { state_witness, main_storage_proof, .. } = self
+            .create_state_witness(
+                // Setting arbitrary chunk producer is OK for shadow validation
+                "alice.near".parse().unwrap(),
+                prev_block_header,
+                prev_chunk_header,
+                &chunk,
+                validated_transactions.storage_proof,
+            )?;
         if self.config.save_latest_witnesses {
-            self.chain.chain_store.save_latest_chunk_state_witness(&state_witness)?;
+            self.chain
+                .chain_store
+                .save_latest_chunk_state_witness(&state_witness, main_storage_proof)?;
         }
-        self.chain.shadow_validate_state_witness(
-            state_witness,
-            self.epoch_manager.as_ref(),
-            self.runtime_adapter.as_ref(),
-            None,
-        )?;
-        Ok(())
+        Ok(state_witness)
     }
 }
@@ -20,7 +20,9 @@ use near_primitives::stateless_validation::state_witness::{
 use near_primitives::stateless_validation::stored_chunk_state_transition_data::{
     StoredChunkStateTransitionData, StoredChunkStateTransitionDataV1,
     StoredChunkStateTransitionDataV2, StoredChunkStateTransitionDataV3,
+    StoredChunkStateTransitionDataV4,
 };
+use near_primitives::stateless_validation::witness_size_attribution::StorageProofSizeAttribution;
 use near_primitives::stateless_validation::ChunkProductionKey;
 use near_primitives::types::{AccountId, EpochId, ShardId};
 use near_primitives::validator_signer::ValidatorSigner;
@@ -38,6 +40,7 @@ struct StateTransitionData {
     implicit_transitions: Vec<ChunkStateTransition>,
     applied_receipts_hash: CryptoHash,
     contract_updates: ContractUpdates,
+    main_storage_proof: Option<StorageProofSizeAttribution>,
 }
 
 /// Result of creating witness.
@@ -49,6 +52,9 @@ pub(crate) struct CreateWitnessResult {
     pub(crate) state_witness: ChunkStateWitness,
     /// Contracts accessed and deployed while applying the chunk.
     pub(crate) contract_updates: ContractUpdates,
+    /// Which transactions and receipts recorded the storage proof of the main state transition,
+    /// unknown for chunks applied before it was stored.
+    pub(crate) main_storage_proof: Option<StorageProofSizeAttribution>,
 }
 
 impl Client {
@@ -73,16 +79,19 @@ impl Client {
 
         let my_signer =
             validator_signer.as_ref().ok_or(Error::NotAValidator(format!("send state witness")))?;
-        let CreateWitnessResult { state_witness, contract_updates } = self.create_state_witness(
-            my_signer.validator_id().clone(),
-            prev_block_header,
-            prev_chunk_header,
-            chunk,
-            transactions_storage_proof,
-        )?;
+        let CreateWitnessResult { state_witness, contract_updates, main_storage_proof } = self
+            .create_state_witness(
+                my_signer.validator_id().clone(),
+                prev_block_header,
+                prev_chunk_header,
+                chunk,
+                transactions_storage_proof,
+            )?;
 
         if self.config.save_latest_witnesses {
-            self.chain.chain_store.save_latest_chunk_state_witness(&state_witness)?;
+            self.chain
+                .chain_store
+                .save_latest_chunk_state_witness(&state_witness, main_storage_proof)?;
         }
 
         let height = chunk_header.height_created();
@@ -98,6 +107,7 @@ impl Client {
                 self.epoch_manager.as_ref(),
                 my_signer.as_ref(),
                 &self.network_adapter.clone().into_sender(),
+                &self.signing_ledger,
             );
         }
 
@@ -146,6 +156,7 @@ impl Client {
             implicit_transitions,
             applied_receipts_hash,
             contract_updates,
+            main_storage_proof,
         } = self.collect_state_transition_data(&chunk_header, prev_chunk_header)?;
 
         let new_transactions = chunk.transactions().to_vec();
@@ -179,7 +190,7 @@ impl Client {
             new_transactions,
             new_transactions_validation_state,
         );
-        Ok(CreateWitnessResult { state_witness, contract_updates })
+        Ok(CreateWitnessResult { state_witness, contract_updates, main_storage_proof })
     }
 
     /// Collect state transition data necessary to produce state witness for
@@ -220,7 +231,7 @@ impl Client {
             if current_shard_id != next_shard_id {
                 // If shard id changes, we need to get implicit state
                 // transition from current shard id to the next shard id.
-                let (chunk_state_transition, _, _) =
+                let (chunk_state_transition, _, _, _) =
                     self.get_state_transition(&current_block_hash, &next_epoch_id, next_shard_id)?;
                 implicit_transitions.push(chunk_state_transition);
             }
@@ -232,7 +243,7 @@ impl Client {
             }
 
             // Add implicit state transition.
-            let (chunk_state_transition, _, _) = self.get_state_transition(
+            let (chunk_state_transition, _, _, _) = self.get_state_transition(
                 &current_block_hash,
                 &current_epoch_id,
                 current_shard_id,
@@ -248,17 +259,19 @@ impl Client {
         implicit_transitions.reverse();
 
         // Get the main state transition.
-        let (main_transition, receipts_hash, contract_updates) = if prev_chunk_header.is_genesis() {
-            self.get_genesis_state_transition(&main_block, &epoch_id, shard_id)?
-        } else {
-            self.get_state_transition(&main_block, &epoch_id, shard_id)?
-        };
+        let (main_transition, receipts_hash, contract_updates, main_storage_proof) =
+            if prev_chunk_header.is_genesis() {
+                self.get_genesis_state_transition(&main_block, &epoch_id, shard_id)?
+            } else {
+                self.get_state_transition(&main_block, &epoch_id, shard_id)?
+            };
 
         Ok(StateTransitionData {
             main_transition,
             implicit_transitions,
             applied_receipts_hash: receipts_hash,
             contract_updates,
+            main_storage_proof,
         })
     }
 
@@ -268,7 +281,10 @@ impl Client {
         block_hash: &CryptoHash,
         epoch_id: &EpochId,
         shard_id: ShardId,
-    ) -> Result<(ChunkStateTransition, CryptoHash, ContractUpdates), Error> {
+    ) -> Result<
+        (ChunkStateTransition, CryptoHash, ContractUpdates, Option<StorageProofSizeAttribution>),
+        Error,
+    > {
         let shard_uid = self.chain.epoch_manager.shard_id_to_uid(shard_id, epoch_id)?;
         let stored_chunk_state_transition_data = self
             .chain
@@ -287,7 +303,7 @@ impl Client {
                 }
                 Error::Other(message)
             })?;
-        let (base_state, receipts_hash, contract_accesses, contract_deploys) =
+        let (base_state, receipts_hash, contract_accesses, contract_deploys, size_attribution) =
             match stored_chunk_state_transition_data {
                 StoredChunkStateTransitionData::V1(StoredChunkStateTransitionDataV1 {
                     base_state,
@@ -299,13 +315,26 @@ impl Client {
                     receipts_hash,
                     contract_accesses,
                     ..
-                }) => (base_state, receipts_hash, contract_accesses, Default::default()),
+                }) => (base_state, receipts_hash, contract_accesses, Default::default(), None),
                 StoredChunkStateTransitionData::V3(StoredChunkStateTransitionDataV3 {
                     base_state,
                     receipts_hash,
                     contract_accesses,
                     contract_deploys,
-                }) => (base_state, receipts_hash, contract_accesses, contract_deploys),
+                }) => (base_state, receipts_hash, contract_accesses, contract_deploys, None),
+                StoredChunkStateTransitionData::V4(StoredChunkStateTransitionDataV4 {
+                    base_state,
+                    receipts_hash,
+                    contract_accesses,
+                    contract_deploys,
+                    size_attribution,
+                }) => (
+                    base_state,
+                    receipts_hash,
+                    contract_accesses,
+                    contract_deploys,
+                    Some(size_attribution),
+                ),
             };
         let contract_updates = ContractUpdates {
             contract_accesses: contract_accesses.into_iter().collect(),
@@ -319,6 +348,7 @@ impl Client {
             },
             receipts_hash,
             contract_updates,
+            size_attribution,
         ))
     }
 
@@ -327,7 +357,10 @@ impl Client {
         block_hash: &CryptoHash,
         epoch_id: &EpochId,
         shard_id: ShardId,
-    ) -> Result<(ChunkStateTransition, CryptoHash, ContractUpdates), Error> {
+    ) -> Result<
+        (ChunkStateTransition, CryptoHash, ContractUpdates, Option<StorageProofSizeAttribution>),
+        Error,
+    > {
         let shard_uid = self.epoch_manager.shard_id_to_uid(shard_id, &epoch_id)?;
         Ok((
             ChunkStateTransition {
@@ -337,6 +370,7 @@ impl Client {
             },
             hash(&borsh::to_vec::<[Receipt]>(&[]).unwrap()),
             Default::default(),
+            None,
         ))
     }
 
@@ -452,11 +486,15 @@ impl Client {
         // Exclude chunk producers that track the same shard from the target list, since they track the state that contains the respective code.
         let target_chunk_validators =
             chunk_validators.difference(&chunk_producers).cloned().collect();
+        let accesses = match ChunkContractAccesses::new(key, contract_accesses, my_signer) {
+            Ok(accesses) => accesses,
+            Err(err) => {
+                tracing::error!(target: "client", ?err, "Failed to sign chunk contract accesses");
+                return;
+            }
+        };
         self.network_adapter.send(PeerManagerMessageRequest::NetworkRequests(
-            NetworkRequests::ChunkContractAccesses(
-                target_chunk_validators,
-                ChunkContractAccesses::new(key, contract_accesses, my_signer),
-            ),
+            NetworkRequests::ChunkContractAccesses(target_chunk_validators, accesses),
         ));
     }
 }
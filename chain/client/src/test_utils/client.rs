@@ -248,7 +248,8 @@ pub fn create_chunk(
     let mut block_merkle_tree = PartialMerkleTree::clone(&block_merkle_tree);
 
     let signer = client.validator_signer.get().unwrap();
-    let endorsement = ChunkEndorsementV1::new(chunk.cloned_header().chunk_hash(), signer.as_ref());
+    let endorsement =
+        ChunkEndorsementV1::new(chunk.cloned_header().chunk_hash(), signer.as_ref()).unwrap();
     block_merkle_tree.insert(*last_block.hash());
     let block = Block::produce(
         PROTOCOL_VERSION,
@@ -273,7 +274,8 @@ pub fn create_chunk(
         block_merkle_tree.root(),
         client.clock.clone(),
         None,
-    );
+    )
+    .unwrap();
     (
         ProduceChunkResult {
             chunk,
@@ -268,6 +268,7 @@ pub fn setup_only_view(
                 ReshardingConfig::default(),
                 "resharding_config",
             ),
+            save_latest_witnesses: false,
         },
         None,
         Arc::new(RayonAsyncComputationSpawner),
@@ -464,6 +465,7 @@ fn process_peer_manager_message_default(
                 connection_established_time: near_async::time::Instant::now(),
                 peer_type: PeerType::Outbound,
                 nonce: 3,
+                send_queue_bytes: Default::default(),
             })
             .collect();
         let peers2 = peers.iter().filter_map(|it| it.full_peer_info.clone().into()).collect();
@@ -1122,6 +1124,7 @@ pub fn setup_synchronous_shards_manager(
                 ReshardingConfig::default(),
                 "resharding_config",
             ),
+            save_latest_witnesses: false,
         }, // irrelevant
         None,
         Arc::new(RayonAsyncComputationSpawner),
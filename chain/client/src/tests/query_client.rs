@@ -96,7 +96,8 @@ fn query_status_not_crash() {
                 block_merkle_tree.root(),
                 Clock::real(),
                 None,
-            );
+            )
+            .unwrap();
             let timestamp = next_block.header().timestamp();
             next_block
                 .mut_header()
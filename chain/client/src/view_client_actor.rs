@@ -4,6 +4,7 @@
 use crate::{
     metrics, sync, GetChunk, GetExecutionOutcomeResponse, GetNextLightClientBlock, GetShardChunk,
     GetStateChanges, GetStateChangesInBlock, GetValidatorInfo, GetValidatorOrdered,
+    GetValidatorPerformance,
 };
 use actix::{Addr, SyncArbiter};
 use near_async::actix_wrapper::SyncActixWrapper;
@@ -24,7 +25,8 @@ use near_client_primitives::types::{
     GetMaintenanceWindowsError, GetNextLightClientBlockError, GetProtocolConfig,
     GetProtocolConfigError, GetReceipt, GetReceiptError, GetSplitStorageInfo,
     GetSplitStorageInfoError, GetStateChangesError, GetStateChangesWithCauseInBlock,
-    GetStateChangesWithCauseInBlockForTrackedShards, GetValidatorInfoError, Query, QueryError,
+    GetStateChangesWithCauseInBlockForTrackedShards, GetStateProof, GetStateProofError,
+    GetValidatorInfoError, Query, QueryError, SimulateTransaction, SimulateTransactionError,
     TxStatus, TxStatusError,
 };
 use near_epoch_manager::shard_tracker::ShardTracker;
@@ -39,16 +41,19 @@ use near_network::types::{
 };
 use near_performance_metrics_macros::perf;
 use near_primitives::block::{Block, BlockHeader};
+use near_primitives::challenge::PartialState;
 use near_primitives::epoch_info::EpochInfo;
 use near_primitives::hash::CryptoHash;
 use near_primitives::merkle::{merklize, PartialMerkleTree};
 use near_primitives::network::AnnounceAccount;
 use near_primitives::receipt::Receipt;
 use near_primitives::sharding::ShardChunk;
+use near_primitives::state_proof::ContractStateProof;
 use near_primitives::state_sync::{
     ShardStateSyncResponse, ShardStateSyncResponseHeader, ShardStateSyncResponseV3,
 };
 use near_primitives::transaction::SignedTransaction;
+use near_primitives::trie_key::TrieKey;
 use near_primitives::types::{
     AccountId, BlockHeight, BlockId, BlockReference, EpochReference, Finality, MaybeBlockId,
     ShardId, SyncCheckpoint, TransactionOrReceiptId, ValidatorInfoIdentifier,
@@ -56,10 +61,11 @@ use near_primitives::types::{
 use near_primitives::validator_signer::ValidatorSigner;
 use near_primitives::views::validator_stake_view::ValidatorStakeView;
 use near_primitives::views::{
-    BlockView, ChunkView, EpochValidatorInfo, ExecutionOutcomeWithIdView, ExecutionStatusView,
-    FinalExecutionOutcomeView, FinalExecutionOutcomeViewEnum, FinalExecutionStatus, GasPriceView,
-    LightClientBlockView, MaintenanceWindowsView, QueryRequest, QueryResponse, ReceiptView,
-    SignedTransactionView, SplitStorageInfoView, StateChangesKindsView, StateChangesView,
+    BlockView, ChunkView, EpochValidatorInfo, EpochValidatorPerformanceView,
+    ExecutionOutcomeWithIdView, ExecutionStatusView, FinalExecutionOutcomeView,
+    FinalExecutionOutcomeViewEnum, FinalExecutionStatus, GasPriceView, LightClientBlockView,
+    MaintenanceWindowsView, QueryRequest, QueryResponse, ReceiptView, SignedTransactionView,
+    SimulatedTransactionView, SplitStorageInfoView, StateChangesKindsView, StateChangesView,
     TxExecutionStatus, TxStatusView,
 };
 use near_store::flat::{FlatStorageReadyStatus, FlatStorageStatus};
@@ -914,6 +920,25 @@ impl Handler<GetValidatorOrdered> for ViewClientActorInner {
         })?)
     }
 }
+impl Handler<GetValidatorPerformance> for ViewClientActorInner {
+    #[perf]
+    fn handle(
+        &mut self,
+        msg: GetValidatorPerformance,
+    ) -> Result<EpochValidatorPerformanceView, GetValidatorInfoError> {
+        tracing::debug!(target: "client", ?msg);
+        let _timer = metrics::VIEW_CLIENT_MESSAGE_TIME
+            .with_label_values(&["GetValidatorPerformance"])
+            .start_timer();
+        let block_hash = match msg.block_id {
+            Some(block_id) => *self.maybe_block_id_to_block_header(Some(block_id))?.hash(),
+            // use header head because this is latest from the perspective of epoch manager
+            None => self.chain.header_head()?.last_block_hash,
+        };
+        Ok(self.epoch_manager.get_validator_performance(&block_hash).into_chain_error()?)
+    }
+}
+
 /// Returns a list of change kinds per account in a store for a given block.
 impl Handler<GetStateChangesInBlock> for ViewClientActorInner {
     #[perf]
@@ -1186,6 +1211,44 @@ impl Handler<GetReceipt> for ViewClientActorInner {
     }
 }
 
+impl Handler<SimulateTransaction> for ViewClientActorInner {
+    #[perf]
+    fn handle(
+        &mut self,
+        msg: SimulateTransaction,
+    ) -> Result<SimulatedTransactionView, SimulateTransactionError> {
+        tracing::debug!(target: "client", ?msg);
+        let _timer = metrics::VIEW_CLIENT_MESSAGE_TIME
+            .with_label_values(&["SimulateTransaction"])
+            .start_timer();
+        let header = self
+            .get_block_header_by_reference(&BlockReference::Finality(Finality::Final))?
+            .ok_or(SimulateTransactionError::NoSyncedBlocks)?;
+        let shard_layout = self
+            .epoch_manager
+            .get_shard_layout(header.epoch_id())
+            .map_err(|err| SimulateTransactionError::InternalError(err.to_string()))?;
+        // The post state is only available for the shards this node applies chunks for.
+        let mut state_roots = HashMap::new();
+        for shard_uid in shard_layout.shard_uids() {
+            match self.chain.get_chunk_extra(header.hash(), &shard_uid) {
+                Ok(chunk_extra) => {
+                    state_roots.insert(shard_uid.shard_id(), *chunk_extra.state_root());
+                }
+                Err(near_chain::Error::DBNotFoundErr(_)) => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(self.runtime.simulate_transaction(
+            &header,
+            state_roots,
+            &msg.signed_transaction,
+            msg.verify_signature,
+            msg.check_nonce,
+        )?)
+    }
+}
+
 impl Handler<GetBlockProof> for ViewClientActorInner {
     #[perf]
     fn handle(&mut self, msg: GetBlockProof) -> Result<GetBlockProofResponse, GetBlockProofError> {
@@ -1204,6 +1267,68 @@ impl Handler<GetBlockProof> for ViewClientActorInner {
     }
 }
 
+impl Handler<GetStateProof> for ViewClientActorInner {
+    #[perf]
+    fn handle(&mut self, msg: GetStateProof) -> Result<ContractStateProof, GetStateProofError> {
+        tracing::debug!(target: "client", ?msg);
+        let _timer =
+            metrics::VIEW_CLIENT_MESSAGE_TIME.with_label_values(&["GetStateProof"]).start_timer();
+        let block = self.chain.get_block(&msg.block_hash)?;
+        let head_block_header = self.chain.get_block_header(&msg.head_block_hash)?;
+        self.chain
+            .check_blocks_final_and_canonical(&[block.header().clone(), head_block_header])?;
+
+        let epoch_id = block.header().epoch_id();
+        let shard_id = self
+            .epoch_manager
+            .account_id_to_shard_id(&msg.account_id, epoch_id)
+            .into_chain_error()?;
+        let shard_index =
+            self.epoch_manager.shard_id_to_index(shard_id, epoch_id).into_chain_error()?;
+        let prev_hash = block.header().prev_hash();
+        if !self.shard_tracker.care_about_shard(
+            self.validator.get().map(|v| v.validator_id().clone()).as_ref(),
+            prev_hash,
+            shard_id,
+            true,
+        ) {
+            return Err(GetStateProofError::UnavailableShard { shard_id });
+        }
+
+        // The chunk headers of the block hold the state roots at the beginning of the block.
+        let state_roots: Vec<CryptoHash> =
+            block.chunks().iter_raw().map(|chunk| chunk.prev_state_root()).collect();
+        let (_, state_root_proofs) = merklize(&state_roots);
+        let state_root = state_roots[shard_index];
+
+        let trie = self
+            .runtime
+            .get_trie_for_shard(shard_id, prev_hash, state_root, false)?
+            .recording_reads();
+        let key =
+            TrieKey::ContractData { account_id: msg.account_id.clone(), key: msg.key.clone() };
+        let value = trie
+            .get(&key.to_vec())
+            .map_err(|err| GetStateProofError::InternalError { error_message: err.to_string() })?;
+        let PartialState::TrieValues(trie_proof) = trie.recorded_storage().unwrap().nodes;
+
+        let block_proof = self.chain.compute_past_block_proof_in_merkle_tree_of_later_block(
+            &msg.block_hash,
+            &msg.head_block_hash,
+        )?;
+        Ok(ContractStateProof {
+            account_id: msg.account_id,
+            key: msg.key,
+            value,
+            trie_proof,
+            state_root,
+            state_root_proof: state_root_proofs[shard_index].clone(),
+            block_header_lite: block.header().clone().into(),
+            block_proof,
+        })
+    }
+}
+
 impl Handler<GetProtocolConfig> for ViewClientActorInner {
     #[perf]
     fn handle(
@@ -23,7 +23,7 @@ use near_primitives::types::{
     ValidatorInfoIdentifier,
 };
 use near_primitives::version::ProtocolVersion;
-use near_primitives::views::EpochValidatorInfo;
+use near_primitives::views::{EpochValidatorInfo, EpochValidatorPerformanceView};
 use near_store::{ShardUId, StoreUpdate};
 use std::cmp::Ordering;
 use std::sync::Arc;
@@ -280,6 +280,13 @@ pub trait EpochManagerAdapter: Send + Sync {
         epoch_id: ValidatorInfoIdentifier,
     ) -> Result<EpochValidatorInfo, EpochError>;
 
+    /// Performance of the validators in the epoch of `block_hash` up to the block, with their
+    /// projected kickouts and rewards.
+    fn get_validator_performance(
+        &self,
+        block_hash: &CryptoHash,
+    ) -> Result<EpochValidatorPerformanceView, EpochError>;
+
     fn add_validator_proposals(
         &self,
         block_info: BlockInfo,
@@ -891,6 +898,14 @@ impl EpochManagerAdapter for EpochManagerHandle {
         epoch_manager.get_validator_info(epoch_id)
     }
 
+    fn get_validator_performance(
+        &self,
+        block_hash: &CryptoHash,
+    ) -> Result<EpochValidatorPerformanceView, EpochError> {
+        let epoch_manager = self.read();
+        epoch_manager.get_validator_performance(block_hash)
+    }
+
     fn add_validator_proposals(
         &self,
         block_info: BlockInfo,
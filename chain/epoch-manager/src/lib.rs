@@ -23,7 +23,8 @@ use near_primitives::version::{
     ProtocolFeature, ProtocolVersion, UPGRADABILITY_FIX_PROTOCOL_VERSION,
 };
 use near_primitives::views::{
-    CurrentEpochValidatorInfo, EpochValidatorInfo, NextEpochValidatorInfo, ValidatorKickoutView,
+    CurrentEpochValidatorInfo, EpochValidatorInfo, EpochValidatorPerformanceView,
+    NextEpochValidatorInfo, ValidatorKickoutView, ValidatorPerformanceView,
 };
 use near_store::{DBCol, Store, StoreUpdate, HEADER_HEAD_KEY};
 use num_rational::BigRational;
@@ -132,6 +133,10 @@ impl EpochInfoProvider for EpochManagerHandle {
     ) -> Result<ShardId, EpochError> {
         EpochManagerAdapter::account_id_to_shard_id(self, account_id, epoch_id)
     }
+
+    fn shard_layout(&self, epoch_id: &EpochId) -> Result<ShardLayout, EpochError> {
+        EpochManagerAdapter::get_shard_layout(self, epoch_id)
+    }
 }
 
 /// Tracks epoch information across different forks, such as validators.
@@ -794,6 +799,35 @@ impl EpochManager {
         })
     }
 
+    /// Whether the validator doesn't get a reward for the epoch because of the kickout.
+    fn is_kickout_for_performance(reason: &ValidatorKickoutReason) -> bool {
+        matches!(
+            reason,
+            ValidatorKickoutReason::NotEnoughBlocks { .. }
+                | ValidatorKickoutReason::NotEnoughChunks { .. }
+                | ValidatorKickoutReason::NotEnoughChunkEndorsements { .. }
+        )
+    }
+
+    fn online_thresholds(
+        epoch_config: &EpochConfig,
+        protocol_version: ProtocolVersion,
+    ) -> ValidatorOnlineThresholds {
+        // If ChunkEndorsementsInBlockHeader feature is enabled, we use the chunk validator kickout threshold
+        // as the cutoff threshold for the endorsement ratio to remap the ratio to 0 or 1.
+        ValidatorOnlineThresholds {
+            online_min_threshold: epoch_config.online_min_threshold,
+            online_max_threshold: epoch_config.online_max_threshold,
+            endorsement_cutoff_threshold: if ProtocolFeature::ChunkEndorsementsInBlockHeader
+                .enabled(protocol_version)
+            {
+                Some(epoch_config.chunk_validator_only_kickout_threshold)
+            } else {
+                None
+            },
+        }
+    }
+
     /// Finalizes epoch (T), where given last block hash is given, and returns next next epoch id (T + 2).
     fn finalize_epoch(
         &mut self,
@@ -827,29 +861,11 @@ impl EpochManager {
             let epoch_duration =
                 block_info.timestamp_nanosec() - last_block_in_last_epoch.timestamp_nanosec();
             for (account_id, reason) in validator_kickout.iter() {
-                if matches!(
-                    reason,
-                    ValidatorKickoutReason::NotEnoughBlocks { .. }
-                        | ValidatorKickoutReason::NotEnoughChunks { .. }
-                        | ValidatorKickoutReason::NotEnoughChunkEndorsements { .. }
-                ) {
+                if Self::is_kickout_for_performance(reason) {
                     validator_block_chunk_stats.remove(account_id);
                 }
             }
             let epoch_config = self.get_epoch_config(block_info.epoch_id())?;
-            // If ChunkEndorsementsInBlockHeader feature is enabled, we use the chunk validator kickout threshold
-            // as the cutoff threshold for the endorsement ratio to remap the ratio to 0 or 1.
-            let online_thresholds = ValidatorOnlineThresholds {
-                online_min_threshold: epoch_config.online_min_threshold,
-                online_max_threshold: epoch_config.online_max_threshold,
-                endorsement_cutoff_threshold: if ProtocolFeature::ChunkEndorsementsInBlockHeader
-                    .enabled(epoch_protocol_version)
-                {
-                    Some(epoch_config.chunk_validator_only_kickout_threshold)
-                } else {
-                    None
-                },
-            };
             self.reward_calculator.calculate_reward(
                 validator_block_chunk_stats,
                 &validator_stake,
@@ -857,7 +873,7 @@ impl EpochManager {
                 epoch_protocol_version,
                 self.genesis_protocol_version,
                 epoch_duration,
-                online_thresholds,
+                Self::online_thresholds(&epoch_config, epoch_protocol_version),
             )
         };
         let next_next_epoch_config = self.config.for_protocol_version(next_next_epoch_version);
@@ -1737,6 +1753,97 @@ impl EpochManager {
         })
     }
 
+    /// Computes the performance of the validators of the epoch of `block_hash`, from the start of
+    /// the epoch up to and including the block. Kickouts and rewards are computed the same way as
+    /// at the end of the epoch, with the duration of the epoch extrapolated from the blocks so far.
+    pub fn get_validator_performance(
+        &self,
+        block_hash: &CryptoHash,
+    ) -> Result<EpochValidatorPerformanceView, EpochError> {
+        let block_info = self.get_block_info(block_hash)?;
+        let epoch_id = *block_info.epoch_id();
+        let epoch_info = self.get_epoch_info(&epoch_id)?;
+        let protocol_version = epoch_info.protocol_version();
+        let next_epoch_info = self.get_epoch_info(&self.get_next_epoch_id(block_hash)?)?;
+        let aggregator = self.get_epoch_info_aggregator_upto_last(block_hash)?;
+
+        let config = self.config.for_protocol_version(protocol_version);
+        let (mut validator_block_chunk_stats, validator_kickout) =
+            Self::compute_validators_to_reward_and_kickout(
+                &config,
+                &epoch_info,
+                &aggregator.block_tracker,
+                &aggregator.shard_tracker,
+                block_info.slashed(),
+                next_epoch_info.validator_kickout(),
+            );
+
+        let epoch_first_block_info = self.get_block_info(block_info.epoch_first_block())?;
+        let last_block_in_last_epoch = self.get_block_info(epoch_first_block_info.prev_hash())?;
+        let elapsed_time = block_info
+            .timestamp_nanosec()
+            .saturating_sub(*last_block_in_last_epoch.timestamp_nanosec());
+        let elapsed_heights =
+            block_info.height().saturating_sub(last_block_in_last_epoch.height()).max(1);
+        let epoch_duration = if elapsed_heights < config.epoch_length {
+            (elapsed_time as u128 * config.epoch_length as u128 / elapsed_heights as u128) as u64
+        } else {
+            elapsed_time
+        };
+
+        let mut validators: Vec<ValidatorPerformanceView> = epoch_info
+            .validators_iter()
+            .map(|info| {
+                let (account_id, _, stake) = info.destructure();
+                let stats = validator_block_chunk_stats.get(&account_id);
+                let block_stats = stats.map(|s| s.block_stats.clone()).unwrap_or_default();
+                let chunk_stats = stats.map(|s| s.chunk_stats.clone()).unwrap_or_default();
+                ValidatorPerformanceView {
+                    stake,
+                    num_produced_blocks: block_stats.produced,
+                    num_expected_blocks: block_stats.expected,
+                    num_produced_chunks: chunk_stats.produced(),
+                    num_expected_chunks: chunk_stats.expected(),
+                    num_produced_endorsements: chunk_stats.endorsement_stats().produced,
+                    num_expected_endorsements: chunk_stats.endorsement_stats().expected,
+                    projected_kickout: validator_kickout.get(&account_id).cloned(),
+                    projected_reward: 0,
+                    account_id,
+                }
+            })
+            .collect();
+
+        for (account_id, reason) in validator_kickout.iter() {
+            if Self::is_kickout_for_performance(reason) {
+                validator_block_chunk_stats.remove(account_id);
+            }
+        }
+        let validator_stake =
+            epoch_info.validators_iter().map(|r| r.account_and_stake()).collect::<HashMap<_, _>>();
+        let epoch_config = self.get_epoch_config(&epoch_id)?;
+        let (validator_reward, _) = self.reward_calculator.calculate_reward(
+            validator_block_chunk_stats,
+            &validator_stake,
+            *block_info.total_supply(),
+            protocol_version,
+            self.genesis_protocol_version,
+            epoch_duration,
+            Self::online_thresholds(&epoch_config, protocol_version),
+        );
+        for validator in &mut validators {
+            validator.projected_reward =
+                validator_reward.get(&validator.account_id).copied().unwrap_or(0);
+        }
+        Ok(EpochValidatorPerformanceView {
+            epoch_id,
+            epoch_height: epoch_info.epoch_height(),
+            epoch_start_height: epoch_first_block_info.height(),
+            block_height: block_info.height(),
+            epoch_length: config.epoch_length,
+            validators,
+        })
+    }
+
     pub fn add_validator_proposals(
         &mut self,
         block_info: BlockInfo,
@@ -14,3 +14,12 @@ pub(crate) static PROTOCOL_VERSION_NEXT: LazyLock<IntGauge> = LazyLock::new(|| {
     try_create_int_gauge("near_protocol_version_next", "The protocol version for the next epoch.")
         .unwrap()
 });
+
+pub(crate) static SHARD_TRACKING_TRANSITION: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "near_shard_tracking_transition",
+        "Whether the node starts (1) or stops (-1) tracking the shard in the next epoch, 0 otherwise",
+        &["shard_id"],
+    )
+    .unwrap()
+});
@@ -1,8 +1,8 @@
-use std::sync::Arc;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
 
 use crate::EpochManagerAdapter;
-use near_cache::SyncLruCache;
-use near_chain_configs::ClientConfig;
+use near_chain_configs::{ClientConfig, MutableConfigValue};
 use near_primitives::errors::EpochError;
 use near_primitives::hash::CryptoHash;
 use near_primitives::shard_layout::account_id_to_shard_id;
@@ -11,7 +11,12 @@ use near_primitives::types::{AccountId, EpochId, ShardId};
 #[derive(Clone)]
 pub enum TrackedConfig {
     /// Tracks shards that contain one of the given account.
-    Accounts(Vec<AccountId>),
+    ///
+    /// The accounts can be updated while the node is running. The shards to track in an epoch are
+    /// decided the first time the epoch is looked at, and don't change afterwards. The new accounts
+    /// hence apply from the first epoch that wasn't looked at yet, usually the epoch after the
+    /// next one, so that the node has time to download the state of the new shards.
+    Accounts(MutableConfigValue<Vec<AccountId>>),
     /// Tracks shards that are assigned to given validator account.
     ShadowValidator(AccountId),
     /// Tracks all shards.
@@ -22,7 +27,11 @@ pub enum TrackedConfig {
 
 impl TrackedConfig {
     pub fn new_empty() -> Self {
-        TrackedConfig::Accounts(vec![])
+        TrackedConfig::new_accounts(vec![])
+    }
+
+    pub fn new_accounts(tracked_accounts: Vec<AccountId>) -> Self {
+        TrackedConfig::Accounts(MutableConfigValue::new(tracked_accounts, "tracked_accounts"))
     }
 
     pub fn from_config(config: &ClientConfig) -> Self {
@@ -47,8 +56,10 @@ type BitMask = Vec<bool>;
 #[derive(Clone)]
 pub struct ShardTracker {
     tracked_config: TrackedConfig,
-    /// Stores shard tracking information by epoch, only useful if TrackedState == Accounts
-    tracking_shards_cache: Arc<SyncLruCache<EpochId, BitMask>>,
+    /// Stores shard tracking information by epoch, only useful if TrackedState == Accounts.
+    /// The entries are never evicted: the tracked accounts can change while the node is running,
+    /// so computing the entry of an epoch again could change the shards tracked in that epoch.
+    tracking_shards_cache: Arc<Mutex<HashMap<EpochId, BitMask>>>,
     epoch_manager: Arc<dyn EpochManagerAdapter>,
 }
 
@@ -56,10 +67,8 @@ impl ShardTracker {
     pub fn new(tracked_config: TrackedConfig, epoch_manager: Arc<dyn EpochManagerAdapter>) -> Self {
         ShardTracker {
             tracked_config,
-            // 1024 epochs on mainnet is about 512 days which is more than enough,
-            // and this is a cache anyway. The data size is pretty small as well,
-            // only one bit per shard per epoch.
-            tracking_shards_cache: Arc::new(SyncLruCache::new(1024)),
+            // The data size is pretty small, only one bit per shard per epoch the node looked at.
+            tracking_shards_cache: Arc::new(Mutex::new(HashMap::new())),
             epoch_manager,
         }
     }
@@ -76,10 +85,11 @@ impl ShardTracker {
         match &self.tracked_config {
             TrackedConfig::Accounts(tracked_accounts) => {
                 let shard_layout = self.epoch_manager.get_shard_layout(epoch_id)?;
-                let tracking_mask = self.tracking_shards_cache.get_or_put(*epoch_id, |_| {
+                let mut tracking_shards_cache = self.tracking_shards_cache.lock().unwrap();
+                let tracking_mask = tracking_shards_cache.entry(*epoch_id).or_insert_with(|| {
                     let mut tracking_mask: Vec<_> =
                         shard_layout.shard_ids().map(|_| false).collect();
-                    for account_id in tracked_accounts {
+                    for account_id in &tracked_accounts.get() {
                         let shard_id = account_id_to_shard_id(account_id, &shard_layout);
                         let shard_index = shard_layout.get_shard_index(shard_id);
                         tracking_mask[shard_index] = true;
@@ -115,7 +125,20 @@ impl ShardTracker {
         prev_hash: &CryptoHash,
     ) -> Result<bool, EpochError> {
         let epoch_id = self.epoch_manager.get_next_epoch_id_from_prev_block(prev_hash)?;
-        self.tracks_shard_at_epoch(shard_id, &epoch_id)
+        let shard_layout = self.epoch_manager.get_shard_layout(&epoch_id)?;
+        if shard_layout.shard_ids().any(|id| id == shard_id) {
+            return self.tracks_shard_at_epoch(shard_id, &epoch_id);
+        }
+        // The shard doesn't exist anymore in the next epoch, follow the shards it is split into.
+        let Some(children) = shard_layout.get_children_shards_ids(shard_id) else {
+            return Ok(false);
+        };
+        for child_shard_id in children {
+            if self.tracks_shard_at_epoch(child_shard_id, &epoch_id)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
     }
 
     /// Whether the client cares about some shard right now.
@@ -203,6 +226,66 @@ impl ShardTracker {
             }
         }
     }
+
+    /// Exports which shards of the current and the next epoch the node starts or stops tracking
+    /// in the next epoch. Meant to be called on the first block of an epoch, when the node decides
+    /// which shards to download the state of.
+    ///
+    /// A shard that only exists in the next epoch is considered tracked in the current epoch if
+    /// the shard it is split from is.
+    pub fn update_tracking_transition_metrics(
+        &self,
+        account_id: Option<&AccountId>,
+        parent_hash: &CryptoHash,
+    ) -> Result<(), EpochError> {
+        let epoch_id = self.epoch_manager.get_epoch_id_from_prev_block(parent_hash)?;
+        let shard_layout = self.epoch_manager.get_shard_layout(&epoch_id)?;
+        let next_epoch_id = self.epoch_manager.get_next_epoch_id_from_prev_block(parent_hash)?;
+        let next_shard_layout = self.epoch_manager.get_shard_layout(&next_epoch_id)?;
+        let shard_ids: BTreeSet<ShardId> =
+            shard_layout.shard_ids().chain(next_shard_layout.shard_ids()).collect();
+        crate::metrics::SHARD_TRACKING_TRANSITION.reset();
+        for shard_id in shard_ids {
+            let (cares, will_care) = if shard_layout.shard_ids().any(|id| id == shard_id) {
+                (
+                    self.care_about_shard(account_id, parent_hash, shard_id, true),
+                    self.will_care_about_shard(account_id, parent_hash, shard_id, true),
+                )
+            } else {
+                let parent_shard_id = shard_layout.shard_ids().find(|&parent_shard_id| {
+                    next_shard_layout
+                        .get_children_shards_ids(parent_shard_id)
+                        .is_some_and(|children| children.contains(&shard_id))
+                });
+                let cares = parent_shard_id.is_some_and(|parent_shard_id| {
+                    self.care_about_shard(account_id, parent_hash, parent_shard_id, true)
+                });
+                let will_care = account_id.is_some_and(|account_id| {
+                    self.epoch_manager
+                        .cares_about_shard_in_epoch(next_epoch_id, account_id, shard_id)
+                        .unwrap_or(false)
+                }) || self.tracks_shard_at_epoch(shard_id, &next_epoch_id)?;
+                (cares, will_care)
+            };
+            let transition = match (cares, will_care) {
+                (false, true) => 1,
+                (true, false) => -1,
+                _ => 0,
+            };
+            if transition != 0 {
+                tracing::info!(
+                    target: "shard_tracker",
+                    ?shard_id,
+                    will_care,
+                    "shard tracking changes in the next epoch"
+                );
+            }
+            crate::metrics::SHARD_TRACKING_TRANSITION
+                .with_label_values(&[&shard_id.to_string()])
+                .set(transition);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -212,9 +295,10 @@ mod tests {
     use crate::test_utils::hash_range;
     use crate::{EpochManager, EpochManagerAdapter, EpochManagerHandle, RewardCalculator};
     use itertools::Itertools;
+    use near_chain_configs::MutableConfigValue;
     use near_crypto::{KeyType, PublicKey};
     use near_primitives::epoch_block_info::BlockInfo;
-    use near_primitives::epoch_manager::{AllEpochConfig, EpochConfig};
+    use near_primitives::epoch_manager::{AllEpochConfig, EpochConfig, EpochConfigStore};
     use near_primitives::hash::CryptoHash;
     use near_primitives::shard_layout::ShardLayout;
     use near_primitives::types::validator_stake::ValidatorStake;
@@ -223,7 +307,7 @@ mod tests {
     use near_primitives::version::PROTOCOL_VERSION;
     use near_store::test_utils::create_test_store;
     use num_rational::Ratio;
-    use std::collections::HashSet;
+    use std::collections::{BTreeMap, HashSet};
     use std::sync::Arc;
 
     const DEFAULT_TOTAL_SUPPLY: u128 = 1_000_000_000_000;
@@ -233,8 +317,33 @@ mod tests {
         num_shards: NumShards,
         use_production_config: bool,
     ) -> EpochManagerHandle {
-        let store = create_test_store();
-        let initial_epoch_config = EpochConfig {
+        let initial_epoch_config = get_epoch_config(ShardLayout::v0(num_shards, 0));
+        let all_epoch_config = AllEpochConfig::new(
+            use_production_config,
+            genesis_protocol_version,
+            initial_epoch_config,
+            "test-chain",
+        );
+        new_epoch_manager(all_epoch_config, genesis_protocol_version)
+    }
+
+    /// The shard layout changes from the genesis protocol version to the next one.
+    fn get_epoch_manager_with_shard_layout_change(
+        genesis_protocol_version: ProtocolVersion,
+        shard_layout: ShardLayout,
+        next_shard_layout: ShardLayout,
+    ) -> EpochManagerHandle {
+        let epoch_config_store = EpochConfigStore::test(BTreeMap::from([
+            (genesis_protocol_version, Arc::new(get_epoch_config(shard_layout))),
+            (genesis_protocol_version + 1, Arc::new(get_epoch_config(next_shard_layout))),
+        ]));
+        let all_epoch_config =
+            AllEpochConfig::from_epoch_config_store("test-chain", 1, epoch_config_store);
+        new_epoch_manager(all_epoch_config, genesis_protocol_version)
+    }
+
+    fn get_epoch_config(shard_layout: ShardLayout) -> EpochConfig {
+        EpochConfig {
             epoch_length: 1,
             num_block_producer_seats: 1,
             num_block_producer_seats_per_shard: vec![1],
@@ -248,10 +357,17 @@ mod tests {
             online_min_threshold: Ratio::new(90, 100),
             minimum_stake_divisor: 1,
             protocol_upgrade_stake_threshold: Ratio::new(80, 100),
-            shard_layout: ShardLayout::v0(num_shards, 0),
+            shard_layout,
             validator_selection_config: Default::default(),
             validator_max_kickout_stake_perc: 100,
-        };
+        }
+    }
+
+    fn new_epoch_manager(
+        all_epoch_config: AllEpochConfig,
+        genesis_protocol_version: ProtocolVersion,
+    ) -> EpochManagerHandle {
+        let store = create_test_store();
         let reward_calculator = RewardCalculator {
             max_inflation_rate: Ratio::from_integer(0),
             num_blocks_per_year: 1000000,
@@ -262,12 +378,7 @@ mod tests {
         };
         EpochManager::new(
             store,
-            AllEpochConfig::new(
-                use_production_config,
-                genesis_protocol_version,
-                initial_epoch_config,
-                "test-chain",
-            ),
+            all_epoch_config,
             genesis_protocol_version,
             reward_calculator,
             vec![ValidatorStake::new(
@@ -342,8 +453,10 @@ mod tests {
             get_epoch_manager(PROTOCOL_VERSION, shard_ids.len() as NumShards, false);
         let shard_layout = epoch_manager.read().get_shard_layout(&EpochId::default()).unwrap();
         let tracked_accounts = vec!["test1".parse().unwrap(), "test2".parse().unwrap()];
-        let tracker =
-            ShardTracker::new(TrackedConfig::Accounts(tracked_accounts), Arc::new(epoch_manager));
+        let tracker = ShardTracker::new(
+            TrackedConfig::new_accounts(tracked_accounts),
+            Arc::new(epoch_manager),
+        );
         let mut total_tracked_shards = HashSet::new();
         total_tracked_shards
             .insert(account_id_to_shard_id(&"test1".parse().unwrap(), &shard_layout));
@@ -426,6 +539,48 @@ mod tests {
         assert_eq!(get_all_shards_will_care_about(&tracker, &shard_ids, &h[7]), subset3);
     }
 
+    #[test]
+    fn test_track_accounts_update() {
+        let shard_ids = (0..4).map(ShardId::new).collect_vec();
+        let epoch_manager =
+            Arc::new(get_epoch_manager(PROTOCOL_VERSION, shard_ids.len() as NumShards, false));
+        let shard_layout = epoch_manager.read().get_shard_layout(&EpochId::default()).unwrap();
+        let tracked_accounts = MutableConfigValue::new(vec!["test1".parse().unwrap()], "test");
+        let tracker = ShardTracker::new(
+            TrackedConfig::Accounts(tracked_accounts.clone()),
+            epoch_manager.clone(),
+        );
+
+        let h = hash_range(8);
+        {
+            let mut epoch_manager = epoch_manager.write();
+            for i in 0..8 {
+                record_block(
+                    &mut epoch_manager,
+                    if i > 0 { h[i - 1] } else { CryptoHash::default() },
+                    h[i],
+                    i as u64,
+                    vec![],
+                    PROTOCOL_VERSION,
+                );
+            }
+        }
+
+        let old_shards =
+            HashSet::from([account_id_to_shard_id(&"test1".parse().unwrap(), &shard_layout)]);
+        let new_shards =
+            HashSet::from([account_id_to_shard_id(&"test2".parse().unwrap(), &shard_layout)]);
+        assert_eq!(get_all_shards_care_about(&tracker, &shard_ids, &h[4]), old_shards);
+        assert_eq!(get_all_shards_will_care_about(&tracker, &shard_ids, &h[4]), old_shards);
+
+        tracked_accounts.update(vec!["test2".parse().unwrap()]);
+        // The epochs that were already looked at keep their tracked shards.
+        assert_eq!(get_all_shards_care_about(&tracker, &shard_ids, &h[4]), old_shards);
+        assert_eq!(get_all_shards_will_care_about(&tracker, &shard_ids, &h[4]), old_shards);
+        assert_eq!(get_all_shards_care_about(&tracker, &shard_ids, &h[5]), old_shards);
+        assert_eq!(get_all_shards_will_care_about(&tracker, &shard_ids, &h[5]), new_shards);
+    }
+
     #[test]
     fn test_track_shards_shard_layout_change() {
         let simple_nightshade_version = SimpleNightshade.protocol_version();
@@ -433,7 +588,7 @@ mod tests {
         let tracked_accounts =
             vec!["a.near".parse().unwrap(), "near".parse().unwrap(), "zoo".parse().unwrap()];
         let tracker = ShardTracker::new(
-            TrackedConfig::Accounts(tracked_accounts.clone()),
+            TrackedConfig::new_accounts(tracked_accounts.clone()),
             Arc::new(epoch_manager.clone()),
         );
 
@@ -504,4 +659,85 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_track_accounts_in_split_shards() {
+        let shard_layout = ShardLayout::v2(
+            vec!["test1".parse().unwrap()],
+            vec![ShardId::new(0), ShardId::new(1)],
+            None,
+        );
+        // Shard 1 is split into shards 2 and 3, which don't exist in the first layout.
+        let next_shard_layout = ShardLayout::v2(
+            vec!["test1".parse().unwrap(), "test3".parse().unwrap()],
+            vec![ShardId::new(0), ShardId::new(2), ShardId::new(3)],
+            Some(BTreeMap::from([
+                (ShardId::new(0), vec![ShardId::new(0)]),
+                (ShardId::new(1), vec![ShardId::new(2), ShardId::new(3)]),
+            ])),
+        );
+        let epoch_manager = get_epoch_manager_with_shard_layout_change(
+            PROTOCOL_VERSION - 1,
+            shard_layout.clone(),
+            next_shard_layout.clone(),
+        );
+        let tracker = ShardTracker::new(
+            TrackedConfig::new_accounts(vec!["test4".parse().unwrap()]),
+            Arc::new(epoch_manager.clone()),
+        );
+
+        let h = hash_range(8);
+        {
+            let mut epoch_manager = epoch_manager.write();
+            for i in 0..8 {
+                record_block(
+                    &mut epoch_manager,
+                    if i > 0 { h[i - 1] } else { CryptoHash::default() },
+                    h[i],
+                    i as u64,
+                    vec![],
+                    PROTOCOL_VERSION,
+                );
+            }
+        }
+
+        // Find the last block before the shard layout changes.
+        let parent_hash = h
+            .iter()
+            .find(|hash| {
+                let next_epoch_id = epoch_manager.get_next_epoch_id_from_prev_block(hash).unwrap();
+                epoch_manager.get_shard_layout(&next_epoch_id).unwrap() == next_shard_layout
+            })
+            .unwrap();
+        let epoch_id = epoch_manager.get_epoch_id_from_prev_block(parent_hash).unwrap();
+        assert_eq!(epoch_manager.get_shard_layout(&epoch_id).unwrap(), shard_layout);
+
+        // The tracked account moves from shard 1 to its child shard 3.
+        let shard_ids = shard_layout.shard_ids().collect_vec();
+        assert_eq!(
+            get_all_shards_care_about(&tracker, &shard_ids, parent_hash),
+            HashSet::from([ShardId::new(1)])
+        );
+        assert_eq!(
+            get_all_shards_will_care_about(&tracker, &shard_ids, parent_hash),
+            HashSet::from([ShardId::new(1)])
+        );
+        let next_shard_ids = next_shard_layout.shard_ids().collect_vec();
+        assert_eq!(
+            get_all_shards_will_care_about(&tracker, &next_shard_ids, parent_hash),
+            HashSet::from([ShardId::new(3)])
+        );
+
+        // The shards of both epochs are reported, the child shards relative to their parent.
+        tracker.update_tracking_transition_metrics(None, parent_hash).unwrap();
+        let transition = |shard_id: u64| {
+            crate::metrics::SHARD_TRACKING_TRANSITION
+                .with_label_values(&[&shard_id.to_string()])
+                .get()
+        };
+        assert_eq!(transition(0), 0);
+        assert_eq!(transition(1), 0);
+        assert_eq!(transition(2), -1);
+        assert_eq!(transition(3), 0);
+    }
 }
@@ -1282,6 +1282,78 @@ fn test_rewards_with_kickouts() {
     }
 }
 
+/// The performance forecast at the last block of an epoch must match the rewards and kickouts
+/// that are actually computed when the epoch is finalized.
+#[test]
+fn test_validator_performance_forecast() {
+    let stake_amount = 1_000_000;
+    let validators = vec![
+        ("test1".parse().unwrap(), stake_amount),
+        ("test2".parse().unwrap(), stake_amount),
+        ("test3".parse().unwrap(), stake_amount),
+    ];
+    let epoch_length = 10;
+    let reward_calculator = RewardCalculator {
+        max_inflation_rate: Ratio::new(5, 100),
+        num_blocks_per_year: 1,
+        epoch_length,
+        protocol_reward_rate: Ratio::new(1, 10),
+        protocol_treasury_account: "near".parse().unwrap(),
+        num_seconds_per_year: NUM_SECONDS_IN_A_YEAR,
+    };
+    let mut em = setup_epoch_manager(validators, epoch_length, 1, 3, 10, 10, 0, reward_calculator);
+
+    let mut height: BlockHeight = 0;
+    let genesis_hash = hash(height.to_le_bytes().as_ref());
+    record_block(&mut em, Default::default(), genesis_hash, height, vec![]);
+
+    let mut prev_hash = genesis_hash;
+    let last_block_hash = loop {
+        height += 1;
+        let block_hash = hash(height.to_le_bytes().as_ref());
+        let epoch_id = em.get_epoch_id_from_prev_block(&prev_hash).unwrap();
+        let epoch_info = em.get_epoch_info(&epoch_id).unwrap();
+        let validator_id = EpochManager::block_producer_from_info(&epoch_info, height);
+        // don't produce blocks for test2 so that it is forecast to be kicked out
+        if epoch_info.validator_account_id(validator_id).as_str() == "test2" {
+            continue;
+        }
+        record_block(&mut em, prev_hash, block_hash, height, vec![]);
+        prev_hash = block_hash;
+        if em.is_next_block_epoch_start(&block_hash).unwrap() {
+            break block_hash;
+        }
+    };
+
+    let performance = em.get_validator_performance(&last_block_hash).unwrap();
+    assert_eq!(performance.block_height, height);
+    assert_eq!(performance.epoch_length, epoch_length);
+    assert_eq!(performance.validators.len(), 3);
+
+    // The epoch info keyed by the last block hash of an epoch holds its rewards and kickouts.
+    let finalized = em.get_epoch_info(&EpochId(last_block_hash)).unwrap();
+    for validator in &performance.validators {
+        assert_eq!(
+            validator.projected_reward,
+            finalized.validator_reward().get(&validator.account_id).copied().unwrap_or(0),
+            "{}",
+            validator.account_id
+        );
+        assert_eq!(
+            validator.projected_kickout.as_ref(),
+            finalized.validator_kickout().get(&validator.account_id),
+            "{}",
+            validator.account_id
+        );
+    }
+    let test2 = performance.validators.iter().find(|v| v.account_id.as_str() == "test2").unwrap();
+    assert_eq!(test2.num_produced_blocks, 0);
+    assert!(test2.num_expected_blocks > 0);
+    assert!(matches!(test2.projected_kickout, Some(NotEnoughBlocks { produced: 0, .. })));
+    let test1 = performance.validators.iter().find(|v| v.account_id.as_str() == "test1").unwrap();
+    assert!(test1.projected_reward > 0);
+}
+
 #[test]
 fn test_epoch_info_aggregator() {
     let stake_amount = 1_000_000;
@@ -3305,25 +3377,28 @@ fn test_chunk_header(h: &[CryptoHash], signer: &ValidatorSigner) -> ShardChunkHe
     let congestion_info = ProtocolFeature::CongestionControl
         .enabled(PROTOCOL_VERSION)
         .then_some(CongestionInfo::default());
-    ShardChunkHeader::V3(ShardChunkHeaderV3::new(
-        PROTOCOL_VERSION,
-        h[0],
-        h[2],
-        h[2],
-        h[2],
-        0,
-        1,
-        ShardId::new(0),
-        0,
-        0,
-        0,
-        h[2],
-        h[2],
-        vec![],
-        congestion_info,
-        BandwidthRequests::default_for_protocol_version(PROTOCOL_VERSION),
-        signer,
-    ))
+    ShardChunkHeader::V3(
+        ShardChunkHeaderV3::new(
+            PROTOCOL_VERSION,
+            h[0],
+            h[2],
+            h[2],
+            h[2],
+            0,
+            1,
+            ShardId::new(0),
+            0,
+            0,
+            0,
+            h[2],
+            h[2],
+            vec![],
+            congestion_info,
+            BandwidthRequests::default_for_protocol_version(PROTOCOL_VERSION),
+            signer,
+        )
+        .unwrap(),
+    )
 }
 
 #[test]
@@ -3362,7 +3437,8 @@ fn test_verify_chunk_endorsements() {
     let chunk_header = test_chunk_header(&h, signer.as_ref());
 
     // check chunk endorsement validity
-    let mut chunk_endorsement = ChunkEndorsementV1::new(chunk_header.chunk_hash(), signer.as_ref());
+    let mut chunk_endorsement =
+        ChunkEndorsementV1::new(chunk_header.chunk_hash(), signer.as_ref()).unwrap();
     assert!(epoch_manager.verify_chunk_endorsement(&chunk_header, &chunk_endorsement).unwrap());
 
     // check invalid chunk endorsement signature
@@ -3370,7 +3446,7 @@ fn test_verify_chunk_endorsements() {
     assert!(!epoch_manager.verify_chunk_endorsement(&chunk_header, &chunk_endorsement).unwrap());
 
     // check chunk endorsement invalidity when chunk header and chunk endorsement don't match
-    let chunk_endorsement = ChunkEndorsementV1::new(h[3].into(), signer.as_ref());
+    let chunk_endorsement = ChunkEndorsementV1::new(h[3].into(), signer.as_ref()).unwrap();
     let err =
         epoch_manager.verify_chunk_endorsement(&chunk_header, &chunk_endorsement).unwrap_err();
     match err {
@@ -3380,7 +3456,8 @@ fn test_verify_chunk_endorsements() {
 
     // check chunk endorsement invalidity when signer is not chunk validator
     let bad_signer = Arc::new(create_test_signer("test2"));
-    let chunk_endorsement = ChunkEndorsementV1::new(chunk_header.chunk_hash(), bad_signer.as_ref());
+    let chunk_endorsement =
+        ChunkEndorsementV1::new(chunk_header.chunk_hash(), bad_signer.as_ref()).unwrap();
     let err =
         epoch_manager.verify_chunk_endorsement(&chunk_header, &chunk_endorsement).unwrap_err();
     match err {
@@ -3423,7 +3500,8 @@ fn test_verify_partial_witness_signature() {
         "witness".bytes().collect(),
         7,
         signer.as_ref(),
-    );
+    )
+    .unwrap();
     assert!(epoch_manager.verify_partial_witness_signature(&partial_witness).unwrap());
 
     // Check invalid chunk state witness signature.
@@ -3439,7 +3517,8 @@ fn test_verify_partial_witness_signature() {
         "witness".bytes().collect(),
         7,
         bad_signer.as_ref(),
-    );
+    )
+    .unwrap();
     assert!(!epoch_manager.verify_partial_witness_signature(&bad_partial_witness).unwrap());
 }
 
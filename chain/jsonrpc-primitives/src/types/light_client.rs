@@ -19,6 +19,16 @@ pub struct RpcLightClientBlockProofRequest {
     pub light_client_head: near_primitives::hash::CryptoHash,
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcLightClientStateProofRequest {
+    pub account_id: near_primitives::types::AccountId,
+    #[serde(rename = "key_base64")]
+    pub key: near_primitives::types::StoreKey,
+    /// The state proven is the state at the beginning of this block.
+    pub block_hash: near_primitives::hash::CryptoHash,
+    pub light_client_head: near_primitives::hash::CryptoHash,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct RpcLightClientExecutionProofResponse {
     pub outcome_proof: near_primitives::views::ExecutionOutcomeWithIdView,
@@ -27,7 +37,7 @@ pub struct RpcLightClientExecutionProofResponse {
     pub block_proof: near_primitives::merkle::MerklePath,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct RpcLightClientNextBlockResponse {
     #[serde(flatten)]
     pub light_client_block: Option<Arc<near_primitives::views::LightClientBlockView>>,
@@ -39,6 +49,12 @@ pub struct RpcLightClientBlockProofResponse {
     pub block_proof: near_primitives::merkle::MerklePath,
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcLightClientStateProofResponse {
+    #[serde(flatten)]
+    pub proof: near_primitives::state_proof::ContractStateProof,
+}
+
 #[derive(thiserror::Error, Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "name", content = "info", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RpcLightClientProofError {
@@ -79,6 +95,20 @@ pub enum RpcLightClientNextBlockError {
     EpochOutOfBounds { epoch_id: near_primitives::types::EpochId },
 }
 
+#[derive(thiserror::Error, Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "name", content = "info", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RpcLightClientStateProofError {
+    #[error("Block either has never been observed on the node or has been garbage collected: {error_message}")]
+    UnknownBlock {
+        #[serde(skip_serializing)]
+        error_message: String,
+    },
+    #[error("Node doesn't track the shard {shard_id} of the requested account")]
+    UnavailableShard { shard_id: near_primitives::types::ShardId },
+    #[error("Internal error: {error_message}")]
+    InternalError { error_message: String },
+}
+
 impl From<RpcLightClientProofError> for crate::errors::RpcError {
     fn from(error: RpcLightClientProofError) -> Self {
         let error_data = match &error {
@@ -116,3 +146,26 @@ impl From<RpcLightClientNextBlockError> for crate::errors::RpcError {
         Self::new_internal_or_handler_error(Some(error_data.clone()), error_data)
     }
 }
+
+impl From<RpcLightClientStateProofError> for crate::errors::RpcError {
+    fn from(error: RpcLightClientStateProofError) -> Self {
+        let error_data = match &error {
+            RpcLightClientStateProofError::UnknownBlock { error_message } => {
+                Some(Value::String(format!("DB Not Found Error: {}", error_message)))
+            }
+            _ => Some(Value::String(error.to_string())),
+        };
+
+        let error_data_value = match serde_json::to_value(error) {
+            Ok(value) => value,
+            Err(err) => {
+                return Self::new_internal_error(
+                    None,
+                    format!("Failed to serialize RpcLightClientStateProofError: {:?}", err),
+                )
+            }
+        };
+
+        Self::new_internal_or_handler_error(error_data, error_data_value)
+    }
+}
@@ -1,5 +1,7 @@
+use near_primitives::hash::CryptoHash;
 use near_primitives::state_record::StateRecord;
 use near_primitives::types::BlockHeightDelta;
+use near_primitives::views::ReceiptExecutionTraceView;
 
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
 pub struct RpcSandboxPatchStateRequest {
@@ -60,3 +62,38 @@ impl From<RpcSandboxFastForwardError> for crate::errors::RpcError {
         Self::new_internal_or_handler_error(Some(error_data.clone()), error_data)
     }
 }
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+pub struct RpcSandboxTraceReceiptRequest {
+    pub receipt_id: CryptoHash,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+pub struct RpcSandboxTraceReceiptResponse {
+    #[serde(flatten)]
+    pub trace: ReceiptExecutionTraceView,
+}
+
+#[derive(thiserror::Error, Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "name", content = "info", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RpcSandboxTraceReceiptError {
+    #[error("Trace of receipt {receipt_id} is not available, it was not executed recently")]
+    UnknownReceiptTrace { receipt_id: CryptoHash },
+    #[error("The node reached its limits. Try again later. More details: {error_message}")]
+    InternalError { error_message: String },
+}
+
+impl From<RpcSandboxTraceReceiptError> for crate::errors::RpcError {
+    fn from(error: RpcSandboxTraceReceiptError) -> Self {
+        let error_data = match serde_json::to_value(error) {
+            Ok(value) => value,
+            Err(err) => {
+                return Self::new_internal_error(
+                    None,
+                    format!("Failed to serialize RpcSandboxTraceReceiptError: {:?}", err),
+                )
+            }
+        };
+        Self::new_internal_or_handler_error(Some(error_data.clone()), error_data)
+    }
+}
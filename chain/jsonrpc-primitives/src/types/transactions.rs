@@ -59,6 +59,35 @@ pub struct RpcTransactionResponse {
     pub final_execution_status: near_primitives::views::TxExecutionStatus,
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RpcSimulateTransactionRequest {
+    /// Transaction to simulate, signed with an empty signature if it was passed unsigned.
+    #[serde(rename = "signed_tx_base64")]
+    pub signed_transaction: near_primitives::transaction::SignedTransaction,
+    /// Always unset for unsigned transactions.
+    pub verify_signature: bool,
+    pub check_nonce: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct RpcSimulateTransactionResponse {
+    #[serde(flatten)]
+    pub simulation: near_primitives::views::SimulatedTransactionView,
+}
+
+#[derive(thiserror::Error, Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "name", content = "info", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RpcSimulateTransactionError {
+    #[error("Transaction is invalid: {context:?}")]
+    InvalidTransaction { context: near_primitives::errors::InvalidTxError },
+    #[error("Node doesn't track shard {requested_shard_id}, which the transaction touches")]
+    UnavailableShard { requested_shard_id: near_primitives::types::ShardId },
+    #[error("There are no fully synchronized blocks on the node yet")]
+    NoSyncedBlocks,
+    #[error("The node reached its limits. Try again later. More details: {error_message}")]
+    InternalError { error_message: String },
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct RpcBroadcastTxSyncResponse {
     pub transaction_hash: near_primitives::hash::CryptoHash,
@@ -137,3 +166,18 @@ impl From<RpcTransactionError> for crate::errors::RpcError {
         Self::new_internal_or_handler_error(Some(error_data), error_data_value)
     }
 }
+
+impl From<RpcSimulateTransactionError> for crate::errors::RpcError {
+    fn from(error: RpcSimulateTransactionError) -> Self {
+        let error_data = match serde_json::to_value(error) {
+            Ok(value) => value,
+            Err(err) => {
+                return Self::new_internal_error(
+                    None,
+                    format!("Failed to serialize RpcSimulateTransactionError: {:?}", err),
+                )
+            }
+        };
+        Self::new_internal_or_handler_error(Some(error_data.clone()), error_data)
+    }
+}
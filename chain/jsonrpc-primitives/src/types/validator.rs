@@ -25,12 +25,23 @@ pub struct RpcValidatorsOrderedRequest {
     pub block_id: near_primitives::types::MaybeBlockId,
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct RpcValidatorPerformanceRequest {
+    pub block_id: near_primitives::types::MaybeBlockId,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct RpcValidatorResponse {
     #[serde(flatten)]
     pub validator_info: near_primitives::views::EpochValidatorInfo,
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct RpcValidatorPerformanceResponse {
+    #[serde(flatten)]
+    pub performance: near_primitives::views::EpochValidatorPerformanceView,
+}
+
 impl From<RpcValidatorError> for crate::errors::RpcError {
     fn from(error: RpcValidatorError) -> Self {
         let error_data = match &error {
@@ -242,6 +242,61 @@ impl JsonRpcClient {
         call_method(&self.client, &self.server_addr, "EXPERIMENTAL_validators_ordered", request)
     }
 
+    #[allow(non_snake_case)]
+    pub fn EXPERIMENTAL_validator_performance(
+        &self,
+        request: near_jsonrpc_primitives::types::validator::RpcValidatorPerformanceRequest,
+    ) -> RpcRequest<near_jsonrpc_primitives::types::validator::RpcValidatorPerformanceResponse>
+    {
+        call_method(&self.client, &self.server_addr, "EXPERIMENTAL_validator_performance", request)
+    }
+
+    pub fn next_light_client_block(
+        &self,
+        request: near_jsonrpc_primitives::types::light_client::RpcLightClientNextBlockRequest,
+    ) -> RpcRequest<near_jsonrpc_primitives::types::light_client::RpcLightClientNextBlockResponse>
+    {
+        call_method(&self.client, &self.server_addr, "next_light_client_block", request)
+    }
+
+    #[allow(non_snake_case)]
+    pub fn EXPERIMENTAL_light_client_proof(
+        &self,
+        request: near_jsonrpc_primitives::types::light_client::RpcLightClientExecutionProofRequest,
+    ) -> RpcRequest<
+        near_jsonrpc_primitives::types::light_client::RpcLightClientExecutionProofResponse,
+    > {
+        call_method(&self.client, &self.server_addr, "EXPERIMENTAL_light_client_proof", request)
+    }
+
+    #[allow(non_snake_case)]
+    pub fn EXPERIMENTAL_light_client_block_proof(
+        &self,
+        request: near_jsonrpc_primitives::types::light_client::RpcLightClientBlockProofRequest,
+    ) -> RpcRequest<near_jsonrpc_primitives::types::light_client::RpcLightClientBlockProofResponse>
+    {
+        call_method(
+            &self.client,
+            &self.server_addr,
+            "EXPERIMENTAL_light_client_block_proof",
+            request,
+        )
+    }
+
+    #[allow(non_snake_case)]
+    pub fn EXPERIMENTAL_light_client_state_proof(
+        &self,
+        request: near_jsonrpc_primitives::types::light_client::RpcLightClientStateProofRequest,
+    ) -> RpcRequest<near_jsonrpc_primitives::types::light_client::RpcLightClientStateProofResponse>
+    {
+        call_method(
+            &self.client,
+            &self.server_addr,
+            "EXPERIMENTAL_light_client_state_proof",
+            request,
+        )
+    }
+
     #[allow(non_snake_case)]
     pub fn EXPERIMENTAL_receipt(
         &self,
@@ -328,6 +328,7 @@ fn test_query_call_function() {
                     account_id: "test".parse().unwrap(),
                     method_name: "method".to_string(),
                     args: vec![].into(),
+                    function_gas_profile: false,
                 },
             })
             .await
@@ -34,10 +34,31 @@ function convertBps(bytes_per_second) {
     return `${megabytes_per_second.toFixed(1)} Mbps`
 }
 
+function convertBytes(bytes) {
+    if (bytes < 3000) {
+        return `${bytes} B`
+    }
+    let kilobytes = bytes / 1024;
+    if (kilobytes < 3000) {
+        return `${kilobytes.toFixed(1)} KB`
+    }
+    return `${(kilobytes / 1024).toFixed(1)} MB`
+}
+
 function computeTraffic(bytes_received, bytes_sent) {
     return "⬇ " + convertBps(bytes_received) + "<br>⬆ " + convertBps(bytes_sent);
 }
 
+function computeSendQueues(send_queue_bytes) {
+    if (!send_queue_bytes) {
+        return "";
+    }
+    return Object.entries(send_queue_bytes)
+        .filter(([_, bytes]) => bytes > 0)
+        .map(([class_name, bytes]) => class_name + ": " + convertBytes(bytes))
+        .join("<br>");
+}
+
 function add_debug_port_link(peer_network_addr) {
     // Each node running in a machine is assigned ports 24567 + peer_num and 3030 + peer_num, whereby peer_num is a whole number
     // peer_rpc_address is not shared between peer nodes. Hence, it cannot be programmatically fetched.
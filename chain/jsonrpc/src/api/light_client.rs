@@ -4,13 +4,13 @@ use near_async::messaging::AsyncSendError;
 use serde_json::Value;
 
 use near_client_primitives::types::{
-    GetBlockProofError, GetExecutionOutcomeError, GetNextLightClientBlockError,
+    GetBlockProofError, GetExecutionOutcomeError, GetNextLightClientBlockError, GetStateProofError,
 };
 use near_jsonrpc_primitives::errors::RpcParseError;
 use near_jsonrpc_primitives::types::light_client::{
     RpcLightClientBlockProofRequest, RpcLightClientExecutionProofRequest,
     RpcLightClientNextBlockError, RpcLightClientNextBlockRequest, RpcLightClientNextBlockResponse,
-    RpcLightClientProofError,
+    RpcLightClientProofError, RpcLightClientStateProofError, RpcLightClientStateProofRequest,
 };
 use near_primitives::views::LightClientBlockView;
 
@@ -36,6 +36,12 @@ impl RpcRequest for RpcLightClientBlockProofRequest {
     }
 }
 
+impl RpcRequest for RpcLightClientStateProofRequest {
+    fn parse(value: Value) -> Result<Self, RpcParseError> {
+        Params::parse(value)
+    }
+}
+
 impl RpcFrom<Option<Arc<LightClientBlockView>>> for RpcLightClientNextBlockResponse {
     fn rpc_from(light_client_block: Option<Arc<LightClientBlockView>>) -> Self {
         Self { light_client_block }
@@ -129,3 +135,32 @@ impl RpcFrom<GetNextLightClientBlockError> for RpcLightClientNextBlockError {
         }
     }
 }
+
+impl RpcFrom<AsyncSendError> for RpcLightClientStateProofError {
+    fn rpc_from(error: AsyncSendError) -> Self {
+        Self::InternalError { error_message: error.to_string() }
+    }
+}
+
+impl RpcFrom<GetStateProofError> for RpcLightClientStateProofError {
+    fn rpc_from(error: GetStateProofError) -> Self {
+        match error {
+            GetStateProofError::UnknownBlock { error_message } => {
+                Self::UnknownBlock { error_message }
+            }
+            GetStateProofError::UnavailableShard { shard_id } => {
+                Self::UnavailableShard { shard_id }
+            }
+            GetStateProofError::InternalError { error_message } => {
+                Self::InternalError { error_message }
+            }
+            GetStateProofError::Unreachable { ref error_message } => {
+                tracing::warn!(target: "jsonrpc", "Unreachable error occurred: {}", error_message);
+                crate::metrics::RPC_UNREACHABLE_ERROR_COUNT
+                    .with_label_values(&["RpcLightClientStateProofError"])
+                    .inc();
+                Self::InternalError { error_message: error.to_string() }
+            }
+        }
+    }
+}
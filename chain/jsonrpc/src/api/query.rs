@@ -78,6 +78,7 @@ fn parse_path_data(path: String, data: String) -> Result<RpcQueryRequest, RpcPar
                 account_id,
                 method_name: method_name.to_string(),
                 args: parse_data()?.into(),
+                function_gas_profile: false,
             },
             None => return Err(RpcParseError("Method name is missing".to_string())),
         },
@@ -50,6 +50,9 @@ impl RpcFrom<near_client_primitives::debug::DebugStatusResponse>
                     x,
                 )
             }
+            near_client_primitives::debug::DebugStatusResponse::WitnessSizes(x) => {
+                near_jsonrpc_primitives::types::status::DebugStatusResponse::WitnessSizes(x)
+            }
         }
     }
 }
@@ -74,6 +77,9 @@ impl RpcFrom<near_network::debug::DebugStatus>
             near_network::debug::DebugStatus::SnapshotHosts(x) => {
                 near_jsonrpc_primitives::types::status::DebugStatusResponse::SnapshotHosts(x)
             }
+            near_network::debug::DebugStatus::NetworkTopology(x) => {
+                near_jsonrpc_primitives::types::status::DebugStatusResponse::NetworkTopology(x)
+            }
         }
     }
 }
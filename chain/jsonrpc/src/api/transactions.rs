@@ -1,13 +1,15 @@
 use near_async::messaging::AsyncSendError;
 use serde_json::Value;
 
-use near_client_primitives::types::TxStatusError;
+use near_client_primitives::types::{SimulateTransactionError, TxStatusError};
+use near_crypto::{KeyType, Signature};
 use near_jsonrpc_primitives::errors::RpcParseError;
 use near_jsonrpc_primitives::types::transactions::{
-    RpcSendTransactionRequest, RpcTransactionError, RpcTransactionStatusRequest, TransactionInfo,
+    RpcSendTransactionRequest, RpcSimulateTransactionError, RpcSimulateTransactionRequest,
+    RpcTransactionError, RpcTransactionStatusRequest, TransactionInfo,
 };
 use near_primitives::borsh::BorshDeserialize;
-use near_primitives::transaction::SignedTransaction;
+use near_primitives::transaction::{SignedTransaction, Transaction};
 
 use super::{Params, RpcFrom, RpcRequest};
 
@@ -53,6 +55,71 @@ impl RpcRequest for RpcTransactionStatusRequest {
     }
 }
 
+impl RpcRequest for RpcSimulateTransactionRequest {
+    fn parse(value: Value) -> Result<Self, RpcParseError> {
+        #[derive(serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct SimulateTransactionParams {
+            signed_tx_base64: Option<String>,
+            tx_base64: Option<String>,
+            verify_signature: Option<bool>,
+            check_nonce: Option<bool>,
+        }
+
+        let params: SimulateTransactionParams = Params::parse(value)?;
+        let check_nonce = params.check_nonce.unwrap_or(true);
+        match (params.signed_tx_base64, params.tx_base64) {
+            (Some(signed_tx), None) => Ok(Self {
+                signed_transaction: decode_signed_transaction(signed_tx)?,
+                verify_signature: params.verify_signature.unwrap_or(true),
+                check_nonce,
+            }),
+            (None, Some(tx)) => {
+                if params.verify_signature == Some(true) {
+                    return Err(RpcParseError(
+                        "Cannot verify the signature of an unsigned transaction".to_string(),
+                    ));
+                }
+                let transaction = decode_transaction(tx)?;
+                Ok(Self {
+                    signed_transaction: SignedTransaction::new(
+                        Signature::empty(KeyType::ED25519),
+                        transaction,
+                    ),
+                    verify_signature: false,
+                    check_nonce,
+                })
+            }
+            _ => Err(RpcParseError(
+                "Exactly one of `signed_tx_base64` and `tx_base64` must be passed".to_string(),
+            )),
+        }
+    }
+}
+
+impl RpcFrom<AsyncSendError> for RpcSimulateTransactionError {
+    fn rpc_from(error: AsyncSendError) -> Self {
+        Self::InternalError { error_message: error.to_string() }
+    }
+}
+
+impl RpcFrom<SimulateTransactionError> for RpcSimulateTransactionError {
+    fn rpc_from(error: SimulateTransactionError) -> Self {
+        match error {
+            SimulateTransactionError::InvalidTransaction(context) => {
+                Self::InvalidTransaction { context }
+            }
+            SimulateTransactionError::UnavailableShard(requested_shard_id) => {
+                Self::UnavailableShard { requested_shard_id }
+            }
+            SimulateTransactionError::NoSyncedBlocks => Self::NoSyncedBlocks,
+            SimulateTransactionError::InternalError(error_message) => {
+                Self::InternalError { error_message }
+            }
+        }
+    }
+}
+
 impl RpcFrom<AsyncSendError> for RpcTransactionError {
     fn rpc_from(error: AsyncSendError) -> Self {
         Self::InternalError { debug_info: error.to_string() }
@@ -81,11 +148,18 @@ fn decode_signed_transaction(value: String) -> Result<SignedTransaction, RpcPars
         .map_err(|err| RpcParseError(format!("Failed to decode transaction: {}", err)))
 }
 
+fn decode_transaction(value: String) -> Result<Transaction, RpcParseError> {
+    let bytes = near_primitives::serialize::from_base64(&value)
+        .map_err(|err| RpcParseError(format!("Failed to decode transaction: {}", err)))?;
+    Transaction::try_from_slice(&bytes)
+        .map_err(|err| RpcParseError(format!("Failed to decode transaction: {}", err)))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::api::RpcRequest;
     use near_jsonrpc_primitives::types::transactions::{
-        RpcSendTransactionRequest, RpcTransactionStatusRequest,
+        RpcSendTransactionRequest, RpcSimulateTransactionRequest, RpcTransactionStatusRequest,
     };
     use near_primitives::borsh;
     use near_primitives::hash::CryptoHash;
@@ -198,4 +272,38 @@ mod tests {
         let params = serde_json::json!([str_tx, wait_until]);
         assert!(RpcSendTransactionRequest::parse(params).is_err());
     }
+
+    #[test]
+    fn test_serialize_simulate_tx_params_with_signed_tx() {
+        let tx = SignedTransaction::empty(CryptoHash::new());
+        let str_tx = to_base64(&borsh::to_vec(&tx).unwrap());
+        let params = serde_json::json!({"signed_tx_base64": str_tx, "check_nonce": false});
+        let request = RpcSimulateTransactionRequest::parse(params).unwrap();
+        assert!(request.verify_signature);
+        assert!(!request.check_nonce);
+    }
+
+    #[test]
+    fn test_serialize_simulate_tx_params_with_unsigned_tx() {
+        let tx = SignedTransaction::empty(CryptoHash::new()).transaction;
+        let str_tx = to_base64(&borsh::to_vec(&tx).unwrap());
+        let request =
+            RpcSimulateTransactionRequest::parse(serde_json::json!({"tx_base64": str_tx})).unwrap();
+        assert!(!request.verify_signature);
+        assert!(request.check_nonce);
+
+        let params = serde_json::json!({"tx_base64": str_tx, "verify_signature": true});
+        assert!(RpcSimulateTransactionRequest::parse(params).is_err());
+    }
+
+    // The params are invalid because exactly one transaction must be passed
+    #[test]
+    fn test_serialize_invalid_simulate_tx_params() {
+        let tx = SignedTransaction::empty(CryptoHash::new());
+        let str_signed_tx = to_base64(&borsh::to_vec(&tx).unwrap());
+        let str_tx = to_base64(&borsh::to_vec(&tx.transaction).unwrap());
+        let params = serde_json::json!({"signed_tx_base64": str_signed_tx, "tx_base64": str_tx});
+        assert!(RpcSimulateTransactionRequest::parse(params).is_err());
+        assert!(RpcSimulateTransactionRequest::parse(serde_json::json!({})).is_err());
+    }
 }
@@ -13,10 +13,11 @@ use near_chain_configs::GenesisConfig;
 use near_client::{
     DebugStatus, GetBlock, GetBlockProof, GetChunk, GetClientConfig, GetExecutionOutcome,
     GetGasPrice, GetMaintenanceWindows, GetNetworkInfo, GetNextLightClientBlock, GetProtocolConfig,
-    GetReceipt, GetStateChanges, GetStateChangesInBlock, GetValidatorInfo, GetValidatorOrdered,
-    ProcessTxRequest, ProcessTxResponse, Query, Status, TxStatus,
+    GetReceipt, GetStateChanges, GetStateChangesInBlock, GetStateProof, GetValidatorInfo,
+    GetValidatorOrdered, GetValidatorPerformance, ProcessTxRequest, ProcessTxResponse, Query,
+    Status, TxStatus,
 };
-use near_client_primitives::types::GetSplitStorageInfo;
+use near_client_primitives::types::{GetSplitStorageInfo, SimulateTransaction};
 pub use near_jsonrpc_client as client;
 pub use near_jsonrpc_primitives as primitives;
 use near_jsonrpc_primitives::errors::{RpcError, RpcErrorKind};
@@ -32,6 +33,7 @@ use near_jsonrpc_primitives::types::transactions::{
 };
 use near_network::debug::GetDebugStatus;
 use near_network::tcp::{self, ListenerAddr};
+use near_network::topology::TopologyFormat;
 use near_o11y::metrics::{prometheus, Encoder, TextEncoder};
 use near_primitives::hash::CryptoHash;
 use near_primitives::transaction::SignedTransaction;
@@ -255,9 +257,12 @@ pub struct ViewClientSenderForRpc(
     AsyncSender<GetSplitStorageInfo, ActixResult<GetSplitStorageInfo>>,
     AsyncSender<GetStateChanges, ActixResult<GetStateChanges>>,
     AsyncSender<GetStateChangesInBlock, ActixResult<GetStateChangesInBlock>>,
+    AsyncSender<GetStateProof, ActixResult<GetStateProof>>,
     AsyncSender<GetValidatorInfo, ActixResult<GetValidatorInfo>>,
     AsyncSender<GetValidatorOrdered, ActixResult<GetValidatorOrdered>>,
+    AsyncSender<GetValidatorPerformance, ActixResult<GetValidatorPerformance>>,
     AsyncSender<Query, ActixResult<Query>>,
+    AsyncSender<SimulateTransaction, ActixResult<SimulateTransaction>>,
     AsyncSender<TxStatus, ActixResult<TxStatus>>,
     #[cfg(feature = "test_features")] Sender<near_client::NetworkAdversarialMessage>,
 );
@@ -427,18 +432,27 @@ impl JsonRpcHandler {
             "EXPERIMENTAL_light_client_block_proof" => {
                 process_method_call(request, |params| self.light_client_block_proof(params)).await
             }
+            "EXPERIMENTAL_light_client_state_proof" => {
+                process_method_call(request, |params| self.light_client_state_proof(params)).await
+            }
             "EXPERIMENTAL_protocol_config" => {
                 process_method_call(request, |params| self.protocol_config(params)).await
             }
             "EXPERIMENTAL_receipt" => {
                 process_method_call(request, |params| self.receipt(params)).await
             }
+            "EXPERIMENTAL_simulate_tx" => {
+                process_method_call(request, |params| self.simulate_tx(params)).await
+            }
             "EXPERIMENTAL_tx_status" => {
                 process_method_call(request, |params| self.tx_status_common(params, true)).await
             }
             "EXPERIMENTAL_validators_ordered" => {
                 process_method_call(request, |params| self.validators_ordered(params)).await
             }
+            "EXPERIMENTAL_validator_performance" => {
+                process_method_call(request, |params| self.validator_performance(params)).await
+            }
             "EXPERIMENTAL_maintenance_windows" => {
                 process_method_call(request, |params| self.maintenance_windows(params)).await
             }
@@ -453,6 +467,10 @@ impl JsonRpcHandler {
             "sandbox_fast_forward" => {
                 process_method_call(request, |params| self.sandbox_fast_forward(params)).await
             }
+            #[cfg(feature = "sandbox")]
+            "sandbox_trace_receipt" => {
+                process_method_call(request, |params| self.sandbox_trace_receipt(params)).await
+            }
             _ => return Err(request),
         })
     }
@@ -799,6 +817,9 @@ impl JsonRpcHandler {
                     "/debug/api/requested_state_parts" => {
                         self.client_send(DebugStatus::RequestedStateParts).await?.rpc_into()
                     }
+                    "/debug/api/witness_sizes" => {
+                        self.client_send(DebugStatus::WitnessSizes).await?.rpc_into()
+                    }
                     "/debug/api/peer_store" => self
                         .peer_manager_send(near_network::debug::GetDebugStatus::PeerStore)
                         .await?
@@ -821,6 +842,10 @@ impl JsonRpcHandler {
                         .peer_manager_send(near_network::debug::GetDebugStatus::SnapshotHosts)
                         .await?
                         .rpc_into(),
+                    "/debug/api/network_topology" => self
+                        .peer_manager_send(near_network::debug::GetDebugStatus::NetworkTopology)
+                        .await?
+                        .rpc_into(),
                     "/debug/api/split_store_info" => {
                         let split_storage_info: RpcSplitStorageInfoResponse = self
                             .split_storage_info(RpcSplitStorageInfoRequest {})
@@ -856,6 +881,28 @@ impl JsonRpcHandler {
         }
     }
 
+    pub async fn debug_network_topology(
+        &self,
+    ) -> Result<
+        Option<near_primitives::views::NetworkTopologyView>,
+        near_jsonrpc_primitives::types::status::RpcStatusError,
+    > {
+        if self.enable_debug_rpc {
+            match self
+                .peer_manager_send(near_network::debug::GetDebugStatus::NetworkTopology)
+                .await?
+            {
+                near_network::debug::DebugStatus::NetworkTopology(view) => Ok(Some(view)),
+                _ => Err(near_jsonrpc_primitives::types::status::RpcStatusError::InternalError {
+                    error_message: "unexpected response to GetDebugStatus::NetworkTopology"
+                        .to_string(),
+                }),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
     pub async fn protocol_config(
         &self,
         request_data: near_jsonrpc_primitives::types::config::RpcProtocolConfigRequest,
@@ -975,6 +1022,25 @@ impl JsonRpcHandler {
         }
     }
 
+    async fn simulate_tx(
+        &self,
+        request_data: near_jsonrpc_primitives::types::transactions::RpcSimulateTransactionRequest,
+    ) -> Result<
+        near_jsonrpc_primitives::types::transactions::RpcSimulateTransactionResponse,
+        near_jsonrpc_primitives::types::transactions::RpcSimulateTransactionError,
+    > {
+        let simulation = self
+            .view_client_send(SimulateTransaction {
+                signed_transaction: request_data.signed_transaction,
+                verify_signature: request_data.verify_signature,
+                check_nonce: request_data.check_nonce,
+            })
+            .await?;
+        Ok(near_jsonrpc_primitives::types::transactions::RpcSimulateTransactionResponse {
+            simulation,
+        })
+    }
+
     async fn changes_in_block(
         &self,
         request: near_jsonrpc_primitives::types::changes::RpcStateChangesInBlockRequest,
@@ -1083,6 +1149,32 @@ impl JsonRpcHandler {
         })
     }
 
+    async fn light_client_state_proof(
+        &self,
+        request: near_jsonrpc_primitives::types::light_client::RpcLightClientStateProofRequest,
+    ) -> Result<
+        near_jsonrpc_primitives::types::light_client::RpcLightClientStateProofResponse,
+        near_jsonrpc_primitives::types::light_client::RpcLightClientStateProofError,
+    > {
+        let near_jsonrpc_primitives::types::light_client::RpcLightClientStateProofRequest {
+            account_id,
+            key,
+            block_hash,
+            light_client_head,
+        } = request;
+
+        let proof = self
+            .view_client_send(GetStateProof {
+                account_id,
+                key: key.into(),
+                block_hash,
+                head_block_hash: light_client_head,
+            })
+            .await?;
+
+        Ok(near_jsonrpc_primitives::types::light_client::RpcLightClientStateProofResponse { proof })
+    }
+
     async fn network_info(
         &self,
     ) -> Result<
@@ -1134,6 +1226,23 @@ impl JsonRpcHandler {
         Ok(validators)
     }
 
+    /// Returns how each validator of the current epoch has performed so far, together with the
+    /// kickout and reward it would get if the epoch ended with the same performance.
+    async fn validator_performance(
+        &self,
+        request: near_jsonrpc_primitives::types::validator::RpcValidatorPerformanceRequest,
+    ) -> Result<
+        near_jsonrpc_primitives::types::validator::RpcValidatorPerformanceResponse,
+        near_jsonrpc_primitives::types::validator::RpcValidatorError,
+    > {
+        let near_jsonrpc_primitives::types::validator::RpcValidatorPerformanceRequest { block_id } =
+            request;
+        let performance = self.view_client_send(GetValidatorPerformance { block_id }).await?;
+        Ok(near_jsonrpc_primitives::types::validator::RpcValidatorPerformanceResponse {
+            performance,
+        })
+    }
+
     /// If experimental_debug_pages_src_path config is set, reads the html file from that
     /// directory. Otherwise, returns None.
     fn read_html_file_override(&self, html_file: &'static str) -> Option<String> {
@@ -1275,6 +1384,40 @@ impl JsonRpcHandler {
 
         Ok(near_jsonrpc_primitives::types::sandbox::RpcSandboxFastForwardResponse {})
     }
+
+    async fn sandbox_trace_receipt(
+        &self,
+        request: near_jsonrpc_primitives::types::sandbox::RpcSandboxTraceReceiptRequest,
+    ) -> Result<
+        near_jsonrpc_primitives::types::sandbox::RpcSandboxTraceReceiptResponse,
+        near_jsonrpc_primitives::types::sandbox::RpcSandboxTraceReceiptError,
+    > {
+        use near_client_primitives::types::SandboxResponse;
+        use near_jsonrpc_primitives::types::sandbox::RpcSandboxTraceReceiptError;
+
+        let response = self
+            .client_sender
+            .send_async(near_client_primitives::types::SandboxMessage::SandboxTraceReceipt(
+                request.receipt_id,
+            ))
+            .await
+            .map_err(RpcFrom::rpc_from)?;
+        match response {
+            SandboxResponse::SandboxReceiptTrace(Some(trace)) => {
+                Ok(near_jsonrpc_primitives::types::sandbox::RpcSandboxTraceReceiptResponse {
+                    trace: *trace,
+                })
+            }
+            SandboxResponse::SandboxReceiptTrace(None) => {
+                Err(RpcSandboxTraceReceiptError::UnknownReceiptTrace {
+                    receipt_id: request.receipt_id,
+                })
+            }
+            response => Err(RpcSandboxTraceReceiptError::InternalError {
+                error_message: format!("unexpected sandbox response: {:?}", response),
+            }),
+        }
+    }
 }
 
 #[cfg(feature = "test_features")]
@@ -1408,6 +1551,26 @@ async fn debug_handler(
     }
 }
 
+async fn debug_network_topology_handler(
+    path: web::Path<String>,
+    handler: web::Data<JsonRpcHandler>,
+) -> Result<HttpResponse, HttpError> {
+    let format = match path.parse::<TopologyFormat>() {
+        Ok(format) => format,
+        Err(_) => {
+            return Ok(HttpResponse::BadRequest()
+                .body(format!("unknown format {:?}, use one of: json, dot, graphml", *path)))
+        }
+    };
+    match handler.debug_network_topology().await {
+        Ok(Some(view)) => Ok(HttpResponse::Ok()
+            .content_type(format.content_type())
+            .body(near_network::topology::render(&view, format))),
+        Ok(None) => Ok(HttpResponse::MethodNotAllowed().finish()),
+        Err(_) => Ok(HttpResponse::ServiceUnavailable().finish()),
+    }
+}
+
 async fn handle_entity_debug(
     req: web::Json<EntityQueryWithParams>,
     handler: web::Data<JsonRpcHandler>,
@@ -1532,6 +1695,7 @@ async fn display_debug_html(
         "congestion_control" => Some(debug_page_string!("congestion_control.html", handler)),
         "congestion_control.css" => Some(debug_page_string!("congestion_control.css", handler)),
         "congestion_control.js" => Some(debug_page_string!("congestion_control.js", handler)),
+        "witness_sizes" => Some(debug_page_string!("witness_sizes.html", handler)),
         _ => None,
     };
 
@@ -1608,6 +1772,10 @@ pub fn start_http(
             .service(web::resource("/metrics").route(web::get().to(prometheus_handler)))
             .service(web::resource("/debug/api/entity").route(web::post().to(handle_entity_debug)))
             .service(web::resource("/debug/api/{api}").route(web::get().to(debug_handler)))
+            .service(
+                web::resource("/debug/api/network_topology/{format}")
+                    .route(web::get().to(debug_network_topology_handler)),
+            )
             .service(
                 web::resource("/debug/api/block_status/{starting_height}")
                     .route(web::get().to(debug_block_status_handler)),
@@ -0,0 +1,174 @@
+use crate::state::LightClientState;
+use crate::verifier::{light_client_block_hash, verify_execution_proof};
+use crate::Error;
+use near_jsonrpc_client::JsonRpcClient;
+use near_jsonrpc_primitives::errors::RpcError;
+use near_jsonrpc_primitives::types::light_client::{
+    RpcLightClientBlockProofRequest, RpcLightClientExecutionProofRequest,
+    RpcLightClientNextBlockRequest, RpcLightClientStateProofRequest,
+};
+use near_jsonrpc_primitives::types::validator::RpcValidatorsOrderedRequest;
+use near_primitives::hash::CryptoHash;
+use near_primitives::state_proof::verify_contract_state_proof;
+use near_primitives::types::{AccountId, BlockId, TransactionOrReceiptId};
+use near_primitives::views::{ExecutionOutcomeWithIdView, LightClientBlockLiteView};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn rpc_error(err: RpcError) -> Error {
+    Error::Rpc(format!("{:?}", err))
+}
+
+/// Follows the chain through an RPC node, persisting the verified head in `state_path`.
+pub struct LightClient {
+    rpc: JsonRpcClient,
+    state: LightClientState,
+    state_path: PathBuf,
+}
+
+impl LightClient {
+    /// Initializes the light client from the hash of a final block that is trusted by the caller.
+    ///
+    /// The block producers of the epoch of the trusted block are taken from the RPC node and are
+    /// trusted together with the block. The producers of all later epochs are verified.
+    pub async fn bootstrap(
+        rpc: JsonRpcClient,
+        trusted_block_hash: CryptoHash,
+        state_path: PathBuf,
+    ) -> Result<Self, Error> {
+        let response = rpc
+            .EXPERIMENTAL_light_client_block_proof(RpcLightClientBlockProofRequest {
+                block_hash: trusted_block_hash,
+                light_client_head: trusted_block_hash,
+            })
+            .await
+            .map_err(rpc_error)?;
+        let head = response.block_header_lite;
+        let head_hash = head.hash();
+        if head_hash != trusted_block_hash {
+            return Err(Error::UntrustedBlock { expected: trusted_block_hash, actual: head_hash });
+        }
+        let block_producers = rpc
+            .EXPERIMENTAL_validators_ordered(RpcValidatorsOrderedRequest {
+                block_id: Some(BlockId::Hash(trusted_block_hash)),
+            })
+            .await
+            .map_err(rpc_error)?;
+        let state = LightClientState::new(head, block_producers);
+        state.save(&state_path)?;
+        Ok(Self { rpc, state, state_path })
+    }
+
+    /// Opens a light client that was previously bootstrapped into `state_path`.
+    pub fn open(rpc: JsonRpcClient, state_path: PathBuf) -> Result<Self, Error> {
+        let state = LightClientState::load(&state_path)?;
+        Ok(Self { rpc, state, state_path })
+    }
+
+    pub fn head(&self) -> &LightClientBlockLiteView {
+        &self.state.head
+    }
+
+    /// Fetches and verifies the next light client block, if there is one. Returns whether the
+    /// head was advanced.
+    pub async fn sync_step(&mut self) -> Result<bool, Error> {
+        let response = self
+            .rpc
+            .next_light_client_block(RpcLightClientNextBlockRequest {
+                last_block_hash: self.state.head.hash(),
+            })
+            .await
+            .map_err(rpc_error)?;
+        let Some(block) = response.light_client_block else {
+            return Ok(false);
+        };
+        let block = Arc::unwrap_or_clone(block);
+
+        let epoch_id = block.inner_lite.epoch_id;
+        if self.state.block_producers(&epoch_id).is_none()
+            && epoch_id == self.state.head.inner_lite.next_epoch_id
+        {
+            // Only the trusted block the client was bootstrapped from comes without the block
+            // producers of the next epoch. They are still verified against its `next_bp_hash`.
+            let block_producers = self
+                .rpc
+                .EXPERIMENTAL_validators_ordered(RpcValidatorsOrderedRequest {
+                    block_id: Some(BlockId::Hash(light_client_block_hash(&block))),
+                })
+                .await
+                .map_err(rpc_error)?;
+            self.state.set_next_block_producers(block_producers)?;
+        }
+
+        self.state.apply_block(block)?;
+        self.state.save(&self.state_path)?;
+        tracing::debug!(
+            target: "light_client",
+            height = self.state.head.inner_lite.height,
+            hash = %self.state.head.hash(),
+            "advanced head"
+        );
+        Ok(true)
+    }
+
+    /// Advances the head as far as the RPC node allows.
+    pub async fn sync(&mut self) -> Result<(), Error> {
+        while self.sync_step().await? {}
+        Ok(())
+    }
+
+    /// Fetches the execution outcome of a transaction or a receipt and verifies its proof against
+    /// the current head. The outcome has to be in a block before the head.
+    pub async fn verify_execution(
+        &self,
+        id: TransactionOrReceiptId,
+    ) -> Result<ExecutionOutcomeWithIdView, Error> {
+        let proof = self
+            .rpc
+            .EXPERIMENTAL_light_client_proof(RpcLightClientExecutionProofRequest {
+                id,
+                light_client_head: self.state.head.hash(),
+            })
+            .await
+            .map_err(rpc_error)?;
+        verify_execution_proof(&self.state.head, &proof)?;
+        Ok(proof.outcome_proof)
+    }
+
+    /// Fetches the value of `key` in the storage of `account_id` at the beginning of the block
+    /// `block_hash` and verifies its proof against the current head. The block has to be final
+    /// and not after the head. Returns `None` if the key is proven to be absent.
+    pub async fn verify_contract_state(
+        &self,
+        account_id: AccountId,
+        key: Vec<u8>,
+        block_hash: CryptoHash,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let response = self
+            .rpc
+            .EXPERIMENTAL_light_client_state_proof(RpcLightClientStateProofRequest {
+                account_id: account_id.clone(),
+                key: key.clone().into(),
+                block_hash,
+                light_client_head: self.state.head.hash(),
+            })
+            .await
+            .map_err(rpc_error)?;
+        let proof = response.proof;
+        if proof.account_id != account_id || proof.key != key {
+            return Err(Error::UnexpectedProof {
+                expected: format!("{}/{:?}", account_id, key),
+                actual: format!("{}/{:?}", proof.account_id, proof.key),
+            });
+        }
+        let proof_block_hash = proof.block_header_lite.hash();
+        if proof_block_hash != block_hash {
+            return Err(Error::UnexpectedProof {
+                expected: block_hash.to_string(),
+                actual: proof_block_hash.to_string(),
+            });
+        }
+        verify_contract_state_proof(&self.state.head, &proof)?;
+        Ok(proof.value)
+    }
+}
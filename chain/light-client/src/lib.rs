@@ -0,0 +1,64 @@
+//! A light client for NEAR that follows the chain over JSON RPC.
+//!
+//! The client bootstraps from a trusted block hash, then follows the `next_light_client_block`
+//! RPC, verifying each block against the stakes of the block producers of its epoch. The verified
+//! head is persisted locally, and is used to verify the execution proofs of transactions and
+//! receipts returned by the `EXPERIMENTAL_light_client_proof` RPC, and the contract state proofs
+//! returned by the `EXPERIMENTAL_light_client_state_proof` RPC.
+//!
+//! The stateless checks live in [`verifier`] and can be used without an RPC connection.
+
+mod client;
+mod state;
+pub mod verifier;
+
+pub use client::LightClient;
+pub use state::LightClientState;
+
+use near_primitives::hash::CryptoHash;
+use near_primitives::state_proof::StateProofError;
+use near_primitives::types::AccountId;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("block at height {new_height} is not ahead of the head at height {head_height}")]
+    BlockNotAhead { head_height: u64, new_height: u64 },
+    #[error(
+        "block is in epoch {epoch_id} which is neither the epoch of the head nor the next one"
+    )]
+    UnexpectedEpoch { epoch_id: CryptoHash },
+    #[error("block starts a new epoch but does not include the next block producers")]
+    MissingNextBlockProducers,
+    #[error(
+        "block has {approvals} approvals but there are only {block_producers} block producers"
+    )]
+    TooManyApprovals { approvals: usize, block_producers: usize },
+    #[error("invalid approval signature of {account_id}")]
+    InvalidApprovalSignature { account_id: AccountId },
+    #[error(
+        "approved stake {approved_stake} is not more than 2/3 of the total stake {total_stake}"
+    )]
+    NotEnoughApprovals { approved_stake: u128, total_stake: u128 },
+    #[error("block producers hash to {actual}, expected {expected}")]
+    InvalidBlockProducersHash { expected: CryptoHash, actual: CryptoHash },
+    #[error("block producers of epoch {epoch_id} are not known")]
+    UnknownBlockProducers { epoch_id: CryptoHash },
+    #[error("block hash {actual} does not match the trusted block hash {expected}")]
+    UntrustedBlock { expected: CryptoHash, actual: CryptoHash },
+    #[error("invalid outcome root proof")]
+    InvalidOutcomeRootProof,
+    #[error("invalid block hash proof")]
+    InvalidBlockHashProof,
+    #[error("block is not included in the block merkle tree of the head")]
+    InvalidBlockMerkleProof,
+    #[error("invalid state proof: {0}")]
+    InvalidStateProof(#[from] StateProofError),
+    #[error("proof is for {actual} while {expected} was requested")]
+    UnexpectedProof { expected: String, actual: String },
+    #[error("RPC error: {0}")]
+    Rpc(String),
+    #[error("failed to access the light client state: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse the light client state: {0}")]
+    Serde(#[from] serde_json::Error),
+}
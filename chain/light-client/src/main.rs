@@ -0,0 +1,112 @@
+use anyhow::Context;
+use near_light_client::LightClient;
+use near_primitives::hash::CryptoHash;
+use near_primitives::types::{AccountId, TransactionOrReceiptId};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(clap::Parser)]
+#[clap(about = "Follows the NEAR chain over RPC and verifies transaction and receipt proofs")]
+struct Cli {
+    /// Address of the JSON RPC server to follow.
+    #[clap(long, default_value = "http://127.0.0.1:3030")]
+    rpc_url: String,
+    /// File in which the verified head of the light client is persisted.
+    #[clap(long, default_value = "light_client_state.json")]
+    state_file: PathBuf,
+    #[clap(subcommand)]
+    cmd: SubCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum SubCommand {
+    /// Initializes the state file from a trusted final block.
+    Init {
+        #[clap(long)]
+        trusted_block_hash: CryptoHash,
+    },
+    /// Keeps following the chain, verifying every light client block.
+    Run {
+        /// How often to poll the RPC node for new light client blocks, in seconds.
+        #[clap(long, default_value = "10")]
+        poll_interval: u64,
+    },
+    /// Advances the head as far as possible and exits.
+    Sync,
+    /// Verifies the execution outcome of a transaction against the current head.
+    VerifyTx {
+        #[clap(long)]
+        tx_hash: CryptoHash,
+        #[clap(long)]
+        sender_id: AccountId,
+    },
+    /// Verifies the execution outcome of a receipt against the current head.
+    VerifyReceipt {
+        #[clap(long)]
+        receipt_id: CryptoHash,
+        #[clap(long)]
+        receiver_id: AccountId,
+    },
+    /// Verifies a value in the storage of a contract against the current head.
+    VerifyState {
+        #[clap(long)]
+        account_id: AccountId,
+        /// Storage key, encoded as base64.
+        #[clap(long)]
+        key_base64: String,
+        /// Block at the beginning of which the state is read. Defaults to the head.
+        #[clap(long)]
+        block_hash: Option<CryptoHash>,
+    },
+}
+
+async fn run(cli: Cli) -> anyhow::Result<()> {
+    let rpc = near_jsonrpc_client::new_client(&cli.rpc_url);
+    if let SubCommand::Init { trusted_block_hash } = cli.cmd {
+        let light_client = LightClient::bootstrap(rpc, trusted_block_hash, cli.state_file).await?;
+        println!("{}", serde_json::to_string_pretty(light_client.head())?);
+        return Ok(());
+    }
+    let mut light_client = LightClient::open(rpc, cli.state_file.clone()).with_context(|| {
+        format!("failed to open light client state at {}", cli.state_file.display())
+    })?;
+    match cli.cmd {
+        SubCommand::Init { .. } => unreachable!(),
+        SubCommand::Run { poll_interval } => loop {
+            if let Err(err) = light_client.sync().await {
+                tracing::error!(target: "light_client", ?err, "failed to advance head");
+            }
+            tokio::time::sleep(Duration::from_secs(poll_interval)).await;
+        },
+        SubCommand::Sync => {
+            light_client.sync().await?;
+            println!("{}", serde_json::to_string_pretty(light_client.head())?);
+        }
+        SubCommand::VerifyTx { tx_hash, sender_id } => {
+            let id = TransactionOrReceiptId::Transaction { transaction_hash: tx_hash, sender_id };
+            let outcome = light_client.verify_execution(id).await?;
+            println!("{}", serde_json::to_string_pretty(&outcome)?);
+        }
+        SubCommand::VerifyReceipt { receipt_id, receiver_id } => {
+            let id = TransactionOrReceiptId::Receipt { receipt_id, receiver_id };
+            let outcome = light_client.verify_execution(id).await?;
+            println!("{}", serde_json::to_string_pretty(&outcome)?);
+        }
+        SubCommand::VerifyState { account_id, key_base64, block_hash } => {
+            let key = near_primitives::serialize::from_base64(&key_base64)
+                .context("invalid base64 key")?;
+            let block_hash = block_hash.unwrap_or_else(|| light_client.head().hash());
+            let value = light_client.verify_contract_state(account_id, key, block_hash).await?;
+            let value = value.map(|value| near_primitives::serialize::to_base64(&value));
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let env_filter = near_o11y::EnvFilterBuilder::from_env().finish().unwrap();
+    let _subscriber = near_o11y::default_subscriber(env_filter, &Default::default()).global();
+    let cli = <Cli as clap::Parser>::parse();
+    actix::System::new().block_on(run(cli))
+}
@@ -0,0 +1,125 @@
+use crate::verifier::{validate_block_producers, validate_light_client_block};
+use crate::Error;
+use near_primitives::hash::CryptoHash;
+use near_primitives::views::validator_stake_view::ValidatorStakeView;
+use near_primitives::views::{LightClientBlockLiteView, LightClientBlockView};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The verified state of the light client: the head and the block producers of the epochs in
+/// which the next block can be.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct LightClientState {
+    pub head: LightClientBlockLiteView,
+    /// Ordered block producers, keyed by epoch id.
+    pub epoch_block_producers: HashMap<CryptoHash, Vec<ValidatorStakeView>>,
+}
+
+impl LightClientState {
+    pub fn new(head: LightClientBlockLiteView, block_producers: Vec<ValidatorStakeView>) -> Self {
+        let epoch_block_producers = HashMap::from([(head.inner_lite.epoch_id, block_producers)]);
+        Self { head, epoch_block_producers }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let contents = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&contents)?)
+    }
+
+    /// Writes the state to a temporary file first, so that a crash never leaves a partially
+    /// written state behind.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    pub fn block_producers(&self, epoch_id: &CryptoHash) -> Option<&Vec<ValidatorStakeView>> {
+        self.epoch_block_producers.get(epoch_id)
+    }
+
+    /// Records the block producers of the epoch following the epoch of the head, after checking
+    /// them against the `next_bp_hash` of the head.
+    pub fn set_next_block_producers(
+        &mut self,
+        block_producers: Vec<ValidatorStakeView>,
+    ) -> Result<(), Error> {
+        validate_block_producers(&block_producers, &self.head.inner_lite.next_bp_hash)?;
+        self.epoch_block_producers.insert(self.head.inner_lite.next_epoch_id, block_producers);
+        Ok(())
+    }
+
+    /// Validates `new_block` and makes it the new head.
+    pub fn apply_block(&mut self, new_block: LightClientBlockView) -> Result<(), Error> {
+        let epoch_id = new_block.inner_lite.epoch_id;
+        let block_producers =
+            self.block_producers(&epoch_id).ok_or(Error::UnknownBlockProducers { epoch_id })?;
+        validate_light_client_block(&self.head, &new_block, block_producers)?;
+
+        self.head = LightClientBlockLiteView {
+            prev_block_hash: new_block.prev_block_hash,
+            inner_rest_hash: new_block.inner_rest_hash,
+            inner_lite: new_block.inner_lite,
+        };
+        if let Some(next_bps) = new_block.next_bps {
+            self.epoch_block_producers.insert(self.head.inner_lite.next_epoch_id, next_bps);
+        }
+        let inner_lite = &self.head.inner_lite;
+        self.epoch_block_producers.retain(|epoch_id, _| {
+            epoch_id == &inner_lite.epoch_id || epoch_id == &inner_lite.next_epoch_id
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_primitives::hash::hash;
+    use near_primitives::views::BlockHeaderInnerLiteView;
+
+    fn test_head() -> LightClientBlockLiteView {
+        LightClientBlockLiteView {
+            prev_block_hash: hash(b"prev"),
+            inner_rest_hash: hash(b"rest"),
+            inner_lite: BlockHeaderInnerLiteView {
+                height: 10,
+                epoch_id: hash(b"epoch"),
+                next_epoch_id: hash(b"next_epoch"),
+                prev_state_root: CryptoHash::default(),
+                outcome_root: CryptoHash::default(),
+                timestamp: 0,
+                timestamp_nanosec: 0,
+                next_bp_hash: CryptoHash::hash_borsh_iter(Vec::<u8>::new()),
+                block_merkle_root: CryptoHash::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let state = LightClientState::new(test_head(), vec![]);
+        state.save(&path).unwrap();
+        let loaded = LightClientState::load(&path).unwrap();
+        assert_eq!(loaded.head.hash(), state.head.hash());
+        assert_eq!(loaded.epoch_block_producers, state.epoch_block_producers);
+    }
+
+    #[test]
+    fn test_next_block_producers() {
+        let mut state = LightClientState::new(test_head(), vec![]);
+        let next_epoch_id = state.head.inner_lite.next_epoch_id;
+        assert!(state.block_producers(&next_epoch_id).is_none());
+        state.set_next_block_producers(vec![]).unwrap();
+        assert_eq!(state.block_producers(&next_epoch_id), Some(&vec![]));
+
+        state.head.inner_lite.next_bp_hash = hash(b"other");
+        assert!(matches!(
+            state.set_next_block_producers(vec![]),
+            Err(Error::InvalidBlockProducersHash { .. })
+        ));
+    }
+}
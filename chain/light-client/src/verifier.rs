@@ -0,0 +1,312 @@
+//! Stateless verification of light client blocks and execution proofs.
+//!
+//! Follows the light client spec: a new block is accepted if it is ahead of the current head,
+//! belongs to the epoch of the head or to the next one, and is endorsed by more than 2/3 of the
+//! stake of the block producers of its epoch.
+
+use crate::Error;
+use near_jsonrpc_primitives::types::light_client::RpcLightClientExecutionProofResponse;
+use near_primitives::block_header::{Approval, ApprovalInner};
+use near_primitives::hash::CryptoHash;
+use near_primitives::merkle::{combine_hash, compute_root_from_path};
+use near_primitives::types::validator_stake::ValidatorStake;
+use near_primitives::views::validator_stake_view::ValidatorStakeView;
+use near_primitives::views::{LightClientBlockLiteView, LightClientBlockView};
+
+/// Returns the hash of the block described by the light client block.
+pub fn light_client_block_hash(block: &LightClientBlockView) -> CryptoHash {
+    LightClientBlockLiteView {
+        prev_block_hash: block.prev_block_hash,
+        inner_rest_hash: block.inner_rest_hash,
+        inner_lite: block.inner_lite.clone(),
+    }
+    .hash()
+}
+
+/// Checks that `block_producers` hash to `expected_hash`, which comes from the `next_bp_hash`
+/// field of a block of the previous epoch.
+///
+/// Both the versioned and the legacy serialization of the producers are accepted, since older
+/// protocol versions hashed the unversioned stakes.
+pub fn validate_block_producers(
+    block_producers: &[ValidatorStakeView],
+    expected_hash: &CryptoHash,
+) -> Result<(), Error> {
+    let stakes: Vec<ValidatorStake> =
+        block_producers.iter().cloned().map(ValidatorStakeView::into_validator_stake).collect();
+    let versioned_hash = CryptoHash::hash_borsh_iter(&stakes);
+    let legacy_hash = CryptoHash::hash_borsh_iter(stakes.into_iter().map(|s| s.into_v1()));
+    if &versioned_hash != expected_hash && &legacy_hash != expected_hash {
+        return Err(Error::InvalidBlockProducersHash {
+            expected: *expected_hash,
+            actual: versioned_hash,
+        });
+    }
+    Ok(())
+}
+
+/// Validates `new_block` against the current `head`.
+///
+/// `block_producers` must be the ordered block producers of the epoch of `new_block`, already
+/// validated against the `next_bp_hash` of a block of the previous epoch.
+pub fn validate_light_client_block(
+    head: &LightClientBlockLiteView,
+    new_block: &LightClientBlockView,
+    block_producers: &[ValidatorStakeView],
+) -> Result<(), Error> {
+    let new_height = new_block.inner_lite.height;
+    if new_height <= head.inner_lite.height {
+        return Err(Error::BlockNotAhead { head_height: head.inner_lite.height, new_height });
+    }
+    let epoch_id = new_block.inner_lite.epoch_id;
+    if epoch_id != head.inner_lite.epoch_id && epoch_id != head.inner_lite.next_epoch_id {
+        return Err(Error::UnexpectedEpoch { epoch_id });
+    }
+    if epoch_id == head.inner_lite.next_epoch_id && new_block.next_bps.is_none() {
+        return Err(Error::MissingNextBlockProducers);
+    }
+    if new_block.approvals_after_next.len() > block_producers.len() {
+        return Err(Error::TooManyApprovals {
+            approvals: new_block.approvals_after_next.len(),
+            block_producers: block_producers.len(),
+        });
+    }
+
+    // The approvals are the endorsements of the block after next, which are signed over the hash
+    // of the next block and the height of the block after next.
+    let current_block_hash = light_client_block_hash(new_block);
+    let next_block_hash = combine_hash(&new_block.next_block_inner_hash, &current_block_hash);
+    let approval_message =
+        Approval::get_data_for_sig(&ApprovalInner::Endorsement(next_block_hash), new_height + 2);
+
+    let mut total_stake = 0u128;
+    let mut approved_stake = 0u128;
+    for (index, block_producer) in block_producers.iter().enumerate() {
+        let block_producer = block_producer.clone().into_validator_stake();
+        total_stake += block_producer.stake();
+        let Some(Some(signature)) = new_block.approvals_after_next.get(index) else {
+            continue;
+        };
+        if !signature.verify(&approval_message, block_producer.public_key()) {
+            return Err(Error::InvalidApprovalSignature {
+                account_id: block_producer.account_id().clone(),
+            });
+        }
+        approved_stake += block_producer.stake();
+    }
+    let threshold = total_stake * 2 / 3;
+    if approved_stake <= threshold {
+        return Err(Error::NotEnoughApprovals { approved_stake, total_stake });
+    }
+
+    if let Some(next_bps) = &new_block.next_bps {
+        validate_block_producers(next_bps, &new_block.inner_lite.next_bp_hash)?;
+    }
+    Ok(())
+}
+
+/// Verifies the response of the `EXPERIMENTAL_light_client_proof` RPC against `head`, the
+/// block that was passed as `light_client_head` in the request.
+pub fn verify_execution_proof(
+    head: &LightClientBlockLiteView,
+    proof: &RpcLightClientExecutionProofResponse,
+) -> Result<(), Error> {
+    let outcome_hash = CryptoHash::hash_borsh(proof.outcome_proof.to_hashes());
+    let shard_outcome_root = compute_root_from_path(&proof.outcome_proof.proof, outcome_hash);
+    let block_outcome_root = compute_root_from_path(
+        &proof.outcome_root_proof,
+        CryptoHash::hash_borsh(shard_outcome_root),
+    );
+    if block_outcome_root != proof.block_header_lite.inner_lite.outcome_root {
+        return Err(Error::InvalidOutcomeRootProof);
+    }
+
+    let block_hash = proof.block_header_lite.hash();
+    if block_hash != proof.outcome_proof.block_hash {
+        return Err(Error::InvalidBlockHashProof);
+    }
+    verify_block_proof(head, &block_hash, &proof.block_proof)
+}
+
+/// Verifies that `block_hash` is included in the block merkle tree of `head`.
+pub fn verify_block_proof(
+    head: &LightClientBlockLiteView,
+    block_hash: &CryptoHash,
+    block_proof: &near_primitives::merkle::MerklePath,
+) -> Result<(), Error> {
+    if compute_root_from_path(block_proof, *block_hash) != head.inner_lite.block_merkle_root {
+        return Err(Error::InvalidBlockMerkleProof);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_crypto::{InMemorySigner, KeyType, Signature};
+    use near_primitives::hash::hash;
+    use near_primitives::views::BlockHeaderInnerLiteView;
+
+    struct TestEpoch {
+        signers: Vec<InMemorySigner>,
+        block_producers: Vec<ValidatorStakeView>,
+    }
+
+    impl TestEpoch {
+        fn new(seed: &str, stakes: &[u128]) -> Self {
+            let signers: Vec<_> = (0..stakes.len())
+                .map(|i| {
+                    InMemorySigner::from_seed(
+                        format!("{seed}{i}").parse().unwrap(),
+                        KeyType::ED25519,
+                        &format!("{seed}{i}"),
+                    )
+                })
+                .collect();
+            let block_producers = signers
+                .iter()
+                .zip(stakes)
+                .map(|(signer, stake)| {
+                    ValidatorStake::new(signer.account_id.clone(), signer.public_key(), *stake)
+                        .into()
+                })
+                .collect();
+            Self { signers, block_producers }
+        }
+
+        fn bp_hash(&self) -> CryptoHash {
+            let stakes: Vec<ValidatorStake> = self
+                .block_producers
+                .iter()
+                .cloned()
+                .map(ValidatorStakeView::into_validator_stake)
+                .collect();
+            CryptoHash::hash_borsh_iter(stakes)
+        }
+    }
+
+    fn head(height: u64, epoch: &str, next_epoch: &str) -> LightClientBlockLiteView {
+        LightClientBlockLiteView {
+            prev_block_hash: hash(b"prev"),
+            inner_rest_hash: hash(b"rest"),
+            inner_lite: BlockHeaderInnerLiteView {
+                height,
+                epoch_id: hash(epoch.as_bytes()),
+                next_epoch_id: hash(next_epoch.as_bytes()),
+                prev_state_root: CryptoHash::default(),
+                outcome_root: CryptoHash::default(),
+                timestamp: 0,
+                timestamp_nanosec: 0,
+                next_bp_hash: CryptoHash::default(),
+                block_merkle_root: CryptoHash::default(),
+            },
+        }
+    }
+
+    /// Creates a block at `height` in `epoch`, approved by the producers of `epoch` at the
+    /// positions set in `approved`.
+    fn new_block(
+        height: u64,
+        epoch: &str,
+        next_epoch: &str,
+        producers: &TestEpoch,
+        next_producers: &TestEpoch,
+        approved: &[bool],
+    ) -> LightClientBlockView {
+        let mut block = LightClientBlockView {
+            prev_block_hash: hash(b"prev"),
+            next_block_inner_hash: hash(b"next"),
+            inner_lite: head(height, epoch, next_epoch).inner_lite,
+            inner_rest_hash: hash(b"rest"),
+            next_bps: Some(next_producers.block_producers.clone()),
+            approvals_after_next: vec![],
+        };
+        block.inner_lite.next_bp_hash = next_producers.bp_hash();
+        let next_block_hash =
+            combine_hash(&block.next_block_inner_hash, &light_client_block_hash(&block));
+        let message =
+            Approval::get_data_for_sig(&ApprovalInner::Endorsement(next_block_hash), height + 2);
+        block.approvals_after_next = producers
+            .signers
+            .iter()
+            .zip(approved)
+            .map(|(signer, approved)| approved.then(|| Box::new(signer.sign(&message))))
+            .collect();
+        block
+    }
+
+    #[test]
+    fn test_validate_block_in_same_and_next_epoch() {
+        let producers = TestEpoch::new("a", &[10, 10, 10]);
+        let next_producers = TestEpoch::new("b", &[5, 5]);
+        let head = head(10, "e1", "e2");
+
+        let block = new_block(20, "e1", "e2", &producers, &next_producers, &[true, true, true]);
+        validate_light_client_block(&head, &block, &producers.block_producers).unwrap();
+
+        let block = new_block(30, "e2", "e3", &producers, &next_producers, &[true, true, true]);
+        validate_light_client_block(&head, &block, &producers.block_producers).unwrap();
+    }
+
+    #[test]
+    fn test_reject_insufficient_stake() {
+        let producers = TestEpoch::new("a", &[10, 10, 10]);
+        let next_producers = TestEpoch::new("b", &[5, 5]);
+        let head = head(10, "e1", "e2");
+
+        // Exactly 2/3 of the stake is not enough.
+        let block = new_block(20, "e1", "e2", &producers, &next_producers, &[true, true, false]);
+        assert!(matches!(
+            validate_light_client_block(&head, &block, &producers.block_producers),
+            Err(Error::NotEnoughApprovals { approved_stake: 20, total_stake: 30 })
+        ));
+    }
+
+    #[test]
+    fn test_reject_invalid_blocks() {
+        let producers = TestEpoch::new("a", &[10, 10, 10]);
+        let next_producers = TestEpoch::new("b", &[5, 5]);
+        let all = [true, true, true];
+        let head = head(10, "e1", "e2");
+
+        let block = new_block(10, "e1", "e2", &producers, &next_producers, &all);
+        assert!(matches!(
+            validate_light_client_block(&head, &block, &producers.block_producers),
+            Err(Error::BlockNotAhead { .. })
+        ));
+
+        let block = new_block(20, "e3", "e4", &producers, &next_producers, &all);
+        assert!(matches!(
+            validate_light_client_block(&head, &block, &producers.block_producers),
+            Err(Error::UnexpectedEpoch { .. })
+        ));
+
+        let mut block = new_block(20, "e2", "e3", &producers, &next_producers, &all);
+        block.next_bps = None;
+        assert!(matches!(
+            validate_light_client_block(&head, &block, &producers.block_producers),
+            Err(Error::MissingNextBlockProducers)
+        ));
+
+        // Approvals signed by the wrong block producers.
+        let block = new_block(20, "e1", "e2", &next_producers, &next_producers, &[true, true]);
+        assert!(matches!(
+            validate_light_client_block(&head, &block, &producers.block_producers),
+            Err(Error::InvalidApprovalSignature { .. })
+        ));
+
+        let mut block = new_block(20, "e1", "e2", &producers, &next_producers, &all);
+        block.approvals_after_next[0] = Some(Box::new(Signature::empty(KeyType::ED25519)));
+        assert!(matches!(
+            validate_light_client_block(&head, &block, &producers.block_producers),
+            Err(Error::InvalidApprovalSignature { .. })
+        ));
+
+        let mut block = new_block(20, "e1", "e2", &producers, &next_producers, &all);
+        block.next_bps = Some(producers.block_producers.clone());
+        assert!(matches!(
+            validate_light_client_block(&head, &block, &producers.block_producers),
+            Err(Error::InvalidBlockProducersHash { .. })
+        ));
+    }
+}
@@ -1,34 +1,28 @@
 use std::collections::HashSet;
 use std::net;
 
-/// Only IPv6 addresses are stored.  IPv4 addresses are mapped to IPv6 before being added.
+/// IP addresses are stored in their canonical form, see `IpAddr::to_canonical()`, so IPv4-mapped
+/// IPv6 addresses are stored as IPv4.
 ///
-/// Without the mapping, we could blacklist an IPv4 and still interact with that address if
-/// it is presented as IPv6.
-/// TODO: alternatively we could use IpAddr::to_canonical(), but then the variants of
-/// the Entry enum would have to be private.
+/// Without the canonicalization, we could blacklist an IPv4 and still interact with that address
+/// if it is presented as IPv6. The variants are private, so that an entry with an address that
+/// is not canonical can't be constructed.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
-pub enum Entry {
-    Ip(net::Ipv6Addr),
-    IpPort(net::Ipv6Addr, u16),
+pub struct Entry(EntryKind);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+enum EntryKind {
+    Ip(net::IpAddr),
+    IpPort(net::IpAddr, u16),
 }
 
 impl Entry {
     pub fn from_ip(ip: net::IpAddr) -> Entry {
-        Entry::Ip(match ip {
-            net::IpAddr::V4(ip) => ip.to_ipv6_mapped(),
-            net::IpAddr::V6(ip) => ip,
-        })
+        Entry(EntryKind::Ip(ip.to_canonical()))
     }
 
     pub fn from_addr(addr: net::SocketAddr) -> Entry {
-        Entry::IpPort(
-            match addr.ip() {
-                net::IpAddr::V4(ip) => ip.to_ipv6_mapped(),
-                net::IpAddr::V6(ip) => ip,
-            },
-            addr.port(),
-        )
+        Entry(EntryKind::IpPort(addr.ip().to_canonical(), addr.port()))
     }
 }
 
@@ -59,7 +53,9 @@ impl FromIterator<Entry> for Blacklist {
 impl Blacklist {
     /// Returns whether given address is on the blacklist.
     pub fn contains(&self, addr: net::SocketAddr) -> bool {
-        self.0.contains(&Entry::from_ip(addr.ip())) || self.0.contains(&Entry::from_addr(addr))
+        let ip = addr.ip().to_canonical();
+        self.0.contains(&Entry(EntryKind::Ip(ip)))
+            || self.0.contains(&Entry(EntryKind::IpPort(ip, addr.port())))
     }
 }
 
@@ -124,4 +120,35 @@ mod test {
         assert!(blacklist.contains(SocketAddr::new(mapped_ip, 42)));
         assert!(!blacklist.contains(SocketAddr::new(mapped_ip, 8080)));
     }
+
+    #[test]
+    fn test_blacklist_ipv4_mapped() {
+        use std::net::*;
+
+        let ip: IpAddr = Ipv4Addr::new(192, 0, 2, 4).into();
+        let mapped_ip = IpAddr::V6("::ffff:192.0.2.4".parse().unwrap());
+
+        // Entries created from IPv4-mapped addresses match the plain IPv4 ones and vice versa.
+        assert_eq!(Entry::from_ip(mapped_ip), Entry::from_ip(ip));
+        assert_eq!(
+            Entry::from_addr(SocketAddr::new(mapped_ip, 42)),
+            Entry::from_addr(SocketAddr::new(ip, 42))
+        );
+
+        let blacklist: Blacklist = [Entry::from_ip(mapped_ip)].into_iter().collect();
+        assert!(blacklist.contains(SocketAddr::new(ip, 42)));
+        assert!(blacklist.contains(SocketAddr::new(mapped_ip, 42)));
+
+        let blacklist: Blacklist =
+            [Entry::from_addr(SocketAddr::new(mapped_ip, 42))].into_iter().collect();
+        assert!(blacklist.contains(SocketAddr::new(ip, 42)));
+        assert!(blacklist.contains(SocketAddr::new(mapped_ip, 42)));
+        assert!(!blacklist.contains(SocketAddr::new(ip, 8080)));
+        assert!(!blacklist.contains(SocketAddr::new(mapped_ip, 8080)));
+
+        // Addresses which are not IPv4-mapped are not treated as IPv4.
+        let compatible_ip = IpAddr::V6("::192.0.2.4".parse().unwrap());
+        assert_ne!(Entry::from_ip(compatible_ip), Entry::from_ip(ip));
+        assert!(!blacklist.contains(SocketAddr::new(compatible_ip, 42)));
+    }
 }
@@ -1,8 +1,10 @@
 use crate::blacklist;
 use crate::concurrency::rate;
+use crate::discovery;
 use crate::network_protocol::PeerAddr;
 use crate::network_protocol::PeerInfo;
 use crate::peer_manager::peer_store;
+use crate::rate_limits::bandwidth;
 use crate::rate_limits::messages_limits;
 use crate::snapshot_hosts;
 use crate::stun;
@@ -103,6 +105,54 @@ pub struct Tier1 {
     pub enable_outbound: bool,
 }
 
+/// Family of IP addresses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum IpFamily {
+    V4,
+    V6,
+}
+
+impl IpFamily {
+    /// Returns the family of `addr`. IPv4-mapped IPv6 addresses are considered IPv4.
+    pub fn of(addr: &std::net::SocketAddr) -> Self {
+        match addr.ip().to_canonical() {
+            std::net::IpAddr::V4(_) => Self::V4,
+            std::net::IpAddr::V6(_) => Self::V6,
+        }
+    }
+
+    /// Returns the `items` with addresses of this family. In case there are none,
+    /// returns all the `items`, so that peers supporting only the other family stay reachable.
+    pub fn prefer<T>(self, items: Vec<T>, addr: impl Fn(&T) -> std::net::SocketAddr) -> Vec<T> {
+        let (preferred, other): (Vec<_>, Vec<_>) =
+            items.into_iter().partition(|item| Self::of(&addr(item)) == self);
+        if preferred.is_empty() {
+            other
+        } else {
+            preferred
+        }
+    }
+}
+
+/// Checks whether `ip` is not reachable from the public internet.
+// TODO(gprusak): use !ip.is_global() instead, once it is stable.
+fn is_private_ip(ip: std::net::IpAddr) -> bool {
+    match ip.to_canonical() {
+        std::net::IpAddr::V4(ip) => {
+            ip.is_loopback() || ip.is_unspecified() || ip.is_private() || ip.is_link_local()
+        }
+        std::net::IpAddr::V6(ip) => {
+            let first_segment = ip.segments()[0];
+            ip.is_loopback()
+                || ip.is_unspecified()
+                // Unique local addresses (fc00::/7).
+                || first_segment & 0xfe00 == 0xfc00
+                // Unicast link local addresses (fe80::/10).
+                || first_segment & 0xffc0 == 0xfe80
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SocketOptions {
     pub recv_buffer_size: Option<u32>,
@@ -119,6 +169,11 @@ impl SocketOptions {
 #[derive(Clone)]
 pub struct NetworkConfig {
     pub node_addr: Option<tcp::ListenerAddr>,
+    /// Addresses to listen on in addition to `node_addr`, with the same port.
+    /// Their IP families are advertised only in AccountData, see `tier1_advertise_proxies`.
+    pub additional_node_addrs: Vec<tcp::ListenerAddr>,
+    /// IP family to use when connecting to TIER1 proxies which advertise addresses of both families.
+    pub preferred_ip_family: IpFamily,
     pub node_key: SecretKey,
     pub validator: ValidatorConfig,
 
@@ -192,9 +247,18 @@ pub struct NetworkConfig {
     //   * not broadcasting deleted edges
     //   * ignoring received deleted edges as well
     pub skip_tombstones: Option<time::Duration>,
+    /// How often to ping the directly connected peers, to measure the latency of the
+    /// connections. If None, the peers are not pinged.
+    pub ping_latency_probe_interval: Option<time::Duration>,
+    /// Sources of peers to add to the peer store, in addition to the peers
+    /// learned from the connected peers.
+    pub peer_discovery: discovery::Config,
 
     /// Configuration of rate limits for incoming messages.
-    pub received_messages_rate_limits: messages_limits::Config,
+    /// It can be updated while the node is running, see [UpdateableNetworkConfig].
+    pub received_messages_rate_limits: MutableConfigValue<messages_limits::Config>,
+    /// Outbound bandwidth budgets per message class, shared by all the connections.
+    pub outbound_bandwidth: bandwidth::Config,
 
     #[cfg(test)]
     pub(crate) event_sink:
@@ -241,9 +305,8 @@ impl NetworkConfig {
         ) {
             self.routing_table_update_rate_limit = rate::Limit { qps, burst }
         }
-        if let Some(rate_limits) = overrides.received_messages_rate_limits {
-            self.received_messages_rate_limits.apply_overrides(rate_limits);
-        }
+        // `received_messages_rate_limits` overrides are applied by `UpdateableNetworkConfig::new`,
+        // because they can also be changed while the node is running.
     }
 
     pub fn new(
@@ -267,22 +330,30 @@ impl NetworkConfig {
             let ip = proxy.addr.ip();
             if cfg.allow_private_ip_in_public_addrs {
                 if ip.is_unspecified() {
-                    anyhow::bail!("public_addrs: {ip} is not a valid IP. If you wanted to specify a loopback IP, use 127.0.0.1 instead.");
-                }
-            } else {
-                // TODO(gprusak): use !ip.is_global() instead, once it is stable.
-                if ip.is_loopback()
-                    || ip.is_unspecified()
-                    || match ip {
-                        std::net::IpAddr::V4(ip) => ip.is_private(),
-                        // TODO(gprusak): use ip.is_unique_local() once stable.
-                        std::net::IpAddr::V6(_) => false,
-                    }
-                {
-                    anyhow::bail!("public_addrs: {ip} is not a public IP.");
+                    anyhow::bail!("public_addrs: {ip} is not a valid IP. If you wanted to specify a loopback IP, use 127.0.0.1 or ::1 instead.");
                 }
+            } else if is_private_ip(ip) {
+                anyhow::bail!("public_addrs: {ip} is not a public IP.");
             }
         }
+        let node_addr = match cfg.addr.as_str() {
+            "" => None,
+            addr => {
+                Some(tcp::ListenerAddr::new(addr.parse().context("Failed to parse SocketAddr")?))
+            }
+        };
+        let additional_node_addrs = cfg
+            .additional_addrs
+            .iter()
+            .map(|addr| anyhow::Ok(tcp::ListenerAddr::new(addr.parse()?)))
+            .collect::<anyhow::Result<_>>()
+            .context("Failed to parse additional_addrs")?;
+        let preferred_ip_family = cfg
+            .preferred_ip_family
+            .or_else(|| node_addr.as_ref().map(|addr| IpFamily::of(addr)))
+            .unwrap_or(IpFamily::V4);
+        let updateable_config =
+            UpdateableNetworkConfig::new(&cfg.experimental.network_config_overrides);
         let mut this = Self {
             node_key,
             validator: ValidatorConfig {
@@ -293,12 +364,9 @@ impl NetworkConfig {
                     ValidatorProxies::Dynamic(cfg.trusted_stun_servers)
                 },
             },
-            node_addr: match cfg.addr.as_str() {
-                "" => None,
-                addr => Some(tcp::ListenerAddr::new(
-                    addr.parse().context("Failed to parse SocketAddr")?,
-                )),
-            },
+            node_addr,
+            additional_node_addrs,
+            preferred_ip_family,
             peer_store: peer_store::Config {
                 boot_nodes: if cfg.boot_nodes.is_empty() {
                     vec![]
@@ -378,8 +446,30 @@ impl NetworkConfig {
             } else {
                 None
             },
-            // Use a preset to configure rate limits and override entries with user defined values later.
-            received_messages_rate_limits: messages_limits::Config::standard_preset(),
+            ping_latency_probe_interval: if cfg.experimental.ping_latency_probe_interval
+                > time::Duration::ZERO
+            {
+                Some(cfg.experimental.ping_latency_probe_interval)
+            } else {
+                None
+            },
+            peer_discovery: discovery::Config {
+                peer_records_file: cfg.peer_records_file,
+                dns_seeds: cfg.dns_seeds,
+                dns_resolver: cfg
+                    .dns_resolver
+                    .map(|addr| addr.parse())
+                    .transpose()
+                    .context("failed to parse dns_resolver")?,
+                interval: cfg.peer_discovery_interval,
+            },
+            received_messages_rate_limits: MutableConfigValue::new(
+                updateable_config.received_messages_rate_limits,
+                "received_messages_rate_limits",
+            ),
+            outbound_bandwidth: bandwidth::Config {
+                budgets: cfg.experimental.outbound_bandwidth_budgets,
+            },
             #[cfg(test)]
             event_sink: near_async::messaging::IntoSender::into_sender(
                 near_async::messaging::noop(),
@@ -393,6 +483,11 @@ impl NetworkConfig {
         PeerId::new(self.node_key.public_key())
     }
 
+    /// All the addresses to listen on for incoming connections.
+    pub fn listen_addrs(&self) -> impl Iterator<Item = &tcp::ListenerAddr> {
+        self.node_addr.iter().chain(self.additional_node_addrs.iter())
+    }
+
     /// TEST-ONLY: Returns network config with given seed used for peer id.
     pub fn from_seed(seed: &str, node_addr: tcp::ListenerAddr) -> Self {
         let node_key = SecretKey::from_seed(KeyType::ED25519, seed);
@@ -408,6 +503,8 @@ impl NetworkConfig {
         };
         NetworkConfig {
             node_addr: Some(node_addr),
+            additional_node_addrs: vec![],
+            preferred_ip_family: IpFamily::of(&node_addr),
             node_key,
             validator,
             peer_store: peer_store::Config {
@@ -457,7 +554,13 @@ impl NetworkConfig {
                 enable_outbound: true,
             }),
             skip_tombstones: None,
-            received_messages_rate_limits: messages_limits::Config::default(),
+            ping_latency_probe_interval: None,
+            peer_discovery: discovery::Config::default(),
+            received_messages_rate_limits: MutableConfigValue::new(
+                messages_limits::Config::default(),
+                "received_messages_rate_limits",
+            ),
+            outbound_bandwidth: bandwidth::Config::default(),
             #[cfg(test)]
             event_sink: near_async::messaging::IntoSender::into_sender(
                 near_async::messaging::noop(),
@@ -466,6 +569,32 @@ impl NetworkConfig {
     }
 
     pub fn verify(self) -> anyhow::Result<VerifiedConfig> {
+        if let Some(node_addr) = &self.node_addr {
+            let mut listen_addrs = HashSet::new();
+            for addr in self.listen_addrs() {
+                if !listen_addrs.insert(**addr) {
+                    anyhow::bail!("listen address {addr} is specified multiple times");
+                }
+                if addr.port() != node_addr.port() {
+                    anyhow::bail!(
+                        "additional listen address {addr} has to use the same port as {node_addr}"
+                    );
+                }
+            }
+            // On most systems a listener on [::] also accepts IPv4 connections, so an IPv4
+            // listener on the same port would fail to bind.
+            let dual_stack =
+                self.listen_addrs().any(|addr| addr.is_ipv6() && addr.ip().is_unspecified());
+            if dual_stack && self.listen_addrs().any(|addr| addr.is_ipv4()) {
+                anyhow::bail!(
+                    "{node_addr}: IPv4 listen addresses are redundant with an unspecified IPv6 listen address"
+                );
+            }
+        } else if !self.additional_node_addrs.is_empty() {
+            anyhow::bail!(
+                "additional listen addresses require the primary listen address to be set"
+            );
+        }
         if !(self.ideal_connections_lo <= self.ideal_connections_hi) {
             anyhow::bail!(
                 "Invalid ideal_connections values. lo({}) > hi({}).",
@@ -511,14 +640,42 @@ impl NetworkConfig {
             .validate()
             .context("routing_table_update_rate_limit")?;
 
-        if let Err(err) = self.received_messages_rate_limits.validate() {
+        if let Err(err) = self.received_messages_rate_limits.get().validate() {
             anyhow::bail!("One or more invalid rate limits: {err:?}");
         }
+        if let Err(err) = self.outbound_bandwidth.validate() {
+            anyhow::bail!("One or more invalid outbound bandwidth budgets: {err:?}");
+        }
 
         Ok(VerifiedConfig { node_id: self.node_id(), inner: self })
     }
 }
 
+/// A subset of `NetworkConfig` that can be updated while the node is running.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateableNetworkConfig {
+    /// See `NetworkConfig::received_messages_rate_limits`.
+    pub received_messages_rate_limits: messages_limits::Config,
+}
+
+impl UpdateableNetworkConfig {
+    pub fn new(overrides: &crate::config_json::NetworkConfigOverrides) -> Self {
+        // Use a preset to configure rate limits and override entries with user defined values.
+        let mut received_messages_rate_limits = messages_limits::Config::standard_preset();
+        if let Some(rate_limits) = &overrides.received_messages_rate_limits {
+            received_messages_rate_limits.apply_overrides(rate_limits.clone());
+        }
+        Self { received_messages_rate_limits }
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Err(err) = self.received_messages_rate_limits.validate() {
+            anyhow::bail!("One or more invalid rate limits: {err:?}");
+        }
+        Ok(())
+    }
+}
+
 /// On every message from peer don't update `last_time_received_message`
 /// but wait some "small" timeout between updates to avoid a lot of messages between
 /// Peer and PeerManager.
@@ -553,12 +710,14 @@ mod test {
     use crate::network_protocol;
     use crate::network_protocol::testonly as data;
     use crate::network_protocol::{AccountData, VersionedAccountData};
+    use crate::rate_limits::messages_limits;
     use crate::rate_limits::messages_limits::{
         RateLimitedPeerMessageKey::BlockHeaders, SingleMessageConfig,
     };
     use crate::tcp;
     use crate::testonly::make_rng;
     use near_async::time;
+    use std::net::SocketAddr;
 
     #[test]
     fn test_network_config() {
@@ -582,6 +741,75 @@ mod test {
         assert!(nc.verify().is_err());
     }
 
+    #[test]
+    fn test_network_config_listen_addrs() {
+        let with_additional = |addr: &str| {
+            let mut nc =
+                config::NetworkConfig::from_seed("123", tcp::ListenerAddr::reserve_for_test());
+            let port = nc.node_addr.as_ref().unwrap().port();
+            let addr = SocketAddr::new(addr.parse().unwrap(), port);
+            nc.additional_node_addrs = vec![tcp::ListenerAddr::new(addr)];
+            nc
+        };
+        assert!(with_additional("127.0.0.1").verify().is_ok());
+        // Duplicate of node_addr.
+        assert!(with_additional("::1").verify().is_err());
+
+        // Different port than node_addr.
+        let mut nc = with_additional("127.0.0.1");
+        let addr = *nc.additional_node_addrs[0];
+        nc.additional_node_addrs =
+            vec![tcp::ListenerAddr::new(SocketAddr::new(addr.ip(), addr.port() + 1))];
+        assert!(nc.verify().is_err());
+
+        // IPv4 listener next to a dual stack listener.
+        let mut nc = with_additional("127.0.0.1");
+        let port = nc.node_addr.as_ref().unwrap().port();
+        nc.node_addr = Some(tcp::ListenerAddr::new(SocketAddr::new("::".parse().unwrap(), port)));
+        assert!(nc.verify().is_err());
+
+        // Additional listener without node_addr.
+        let mut nc = with_additional("127.0.0.1");
+        nc.node_addr = None;
+        assert!(nc.verify().is_err());
+    }
+
+    #[test]
+    fn ip_family_prefer() {
+        let v4: SocketAddr = "1.2.3.4:24567".parse().unwrap();
+        let v4_mapped: SocketAddr = "[::ffff:1.2.3.5]:24567".parse().unwrap();
+        let v6: SocketAddr = "[2001:db8::1]:24567".parse().unwrap();
+        assert_eq!(config::IpFamily::V4, config::IpFamily::of(&v4_mapped));
+        assert_eq!(vec![v6], config::IpFamily::V6.prefer(vec![v4, v6, v4_mapped], |a| *a));
+        assert_eq!(
+            vec![v4, v4_mapped],
+            config::IpFamily::V4.prefer(vec![v4, v6, v4_mapped], |a| *a)
+        );
+        // Fall back to the other family if there is no address of the preferred one.
+        assert_eq!(vec![v4], config::IpFamily::V6.prefer(vec![v4], |a| *a));
+    }
+
+    #[test]
+    fn private_ips() {
+        for ip in [
+            "127.0.0.1",
+            "0.0.0.0",
+            "10.1.2.3",
+            "192.168.0.1",
+            "169.254.0.1",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(config::is_private_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in ["1.2.3.4", "2001:db8::1", "2a01:4f8::1"] {
+            assert!(!config::is_private_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
     #[test]
     fn test_network_config_override() {
         fn check_override_field<T: std::cmp::PartialEq>(
@@ -697,16 +925,37 @@ mod test {
 
     #[test]
     fn received_messages_rate_limits_error() {
-        let mut nc = config::NetworkConfig::from_seed("123", tcp::ListenerAddr::reserve_for_test());
-        nc.received_messages_rate_limits
-            .rate_limits
-            .insert(BlockHeaders, SingleMessageConfig::new(1, -4.0, None));
+        let nc = config::NetworkConfig::from_seed("123", tcp::ListenerAddr::reserve_for_test());
+        let mut rate_limits = nc.received_messages_rate_limits.get();
+        rate_limits.rate_limits.insert(BlockHeaders, SingleMessageConfig::new(1, -4.0, None));
+        nc.received_messages_rate_limits.update(rate_limits);
         assert!(nc.verify().is_err());
 
-        let mut nc = config::NetworkConfig::from_seed("123", tcp::ListenerAddr::reserve_for_test());
-        nc.received_messages_rate_limits
-            .rate_limits
-            .insert(BlockHeaders, SingleMessageConfig::new(1, 4.0, None));
+        let nc = config::NetworkConfig::from_seed("123", tcp::ListenerAddr::reserve_for_test());
+        let mut rate_limits = nc.received_messages_rate_limits.get();
+        rate_limits.rate_limits.insert(BlockHeaders, SingleMessageConfig::new(1, 4.0, None));
+        nc.received_messages_rate_limits.update(rate_limits);
         assert!(nc.verify().is_ok());
     }
+
+    #[test]
+    fn updateable_network_config() {
+        let mut overrides = NetworkConfigOverrides::default();
+        let config = config::UpdateableNetworkConfig::new(&overrides);
+        assert_eq!(
+            config.received_messages_rate_limits,
+            messages_limits::Config::standard_preset()
+        );
+        assert!(config.validate().is_ok());
+
+        let mut rate_limits = messages_limits::OverrideConfig::default();
+        rate_limits
+            .peer_class_rate_limits
+            .entry(messages_limits::PeerClass::UnknownInbound)
+            .or_default()
+            .insert(BlockHeaders, Some(SingleMessageConfig::new(1, -4.0, None)));
+        overrides.received_messages_rate_limits = Some(rate_limits);
+        let config = config::UpdateableNetworkConfig::new(&overrides);
+        assert!(config.validate().is_err());
+    }
 }
@@ -1,7 +1,10 @@
+use crate::config;
 use crate::network_protocol::PeerAddr;
+use crate::rate_limits::bandwidth;
 use crate::rate_limits::messages_limits;
 use crate::stun;
 use near_async::time::Duration;
+use std::collections::HashMap;
 
 /// Time to persist Accounts Id in the router without removing them in seconds.
 pub const TTL_ACCOUNT_ID_ROUTER: i64 = 60 * 60;
@@ -68,6 +71,11 @@ fn default_peer_expiration_duration() -> Duration {
     Duration::seconds(7 * 24 * 60 * 60)
 }
 
+/// How often to query the peer discovery sources.
+fn default_peer_discovery_interval() -> Duration {
+    Duration::seconds(60)
+}
+
 /// This is a list of public STUN servers provided by Google,
 /// which are known to have good availability. To avoid trusting
 /// a centralized entity (and DNS used for domain resolution),
@@ -86,7 +94,20 @@ pub(crate) fn default_trusted_stun_servers() -> Vec<stun::ServerAddr> {
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct Config {
     /// Local address to listen for incoming connections.
+    /// On most systems `[::]:<port>` accepts connections over both IPv4 and IPv6.
     pub addr: String,
+    /// Additional local addresses to listen for incoming connections, for example an IPv6
+    /// address next to an IPv4 `addr`. They have to use the same port as `addr`, because
+    /// peers learn the listening port from the handshake, independently of the IP family.
+    /// The addresses of both families are advertised only in the TIER1 AccountData, when the
+    /// public IPs are discovered via STUN (i.e. `public_addrs` is empty). Peer exchange
+    /// advertises a single address per peer: the one other peers have connected to.
+    #[serde(default)]
+    pub additional_addrs: Vec<String>,
+    /// IP family to use when connecting to TIER1 proxies which advertise addresses of both
+    /// families. Defaults to the family of `addr`.
+    #[serde(default)]
+    pub preferred_ip_family: Option<config::IpFamily>,
     /// Comma separated list of nodes to connect to.
     /// Examples:
     ///   ed25519:86EtEy7epneKyrcJwSWP7zsisTkfDRH5CFVszt4qiQYw@31.192.22.209:24567
@@ -209,6 +230,23 @@ pub struct Config {
     /// such a case.
     #[serde(default = "default_trusted_stun_servers")]
     pub trusted_stun_servers: Vec<stun::ServerAddr>,
+    /// File with peer records signed by the peers themselves, in the format described in
+    /// `near_network::discovery::file`. Relative paths are resolved against the home directory.
+    /// The file is re-read when it changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_records_file: Option<std::path::PathBuf>,
+    /// Domains to discover the peers at, see `near_network::discovery::dns`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dns_seeds: Vec<String>,
+    /// DNS server (`<ip>:<port>`) to query the `dns_seeds` at.
+    /// Defaults to the first nameserver from /etc/resolv.conf.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_resolver: Option<String>,
+    /// How often to query the peer discovery sources: boot nodes, `peer_records_file`
+    /// and `dns_seeds`.
+    #[serde(default = "default_peer_discovery_interval")]
+    #[serde(with = "near_async::time::serde_duration_as_std")]
+    pub peer_discovery_interval: Duration,
     // Experimental part of the JSON config. Regular users/validators should not have to set any values there.
     // Field names in here can change/disappear at any moment without warning.
     #[serde(default)]
@@ -231,6 +269,10 @@ fn default_tier1_new_connections_per_attempt() -> u64 {
     50
 }
 
+fn default_ping_latency_probe_interval() -> Duration {
+    Duration::ZERO
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct ExperimentalConfig {
     // If true - don't allow any inbound connections.
@@ -265,6 +307,21 @@ pub struct ExperimentalConfig {
     #[serde(default = "default_tier1_new_connections_per_attempt")]
     pub tier1_new_connections_per_attempt: u64,
 
+    /// How often to ping the directly connected peers, to measure the latency of the
+    /// connections, as reported by the network topology debug page. Zero, the default, disables
+    /// pinging.
+    #[serde(default = "default_ping_latency_probe_interval")]
+    #[serde(with = "near_async::time::serde_duration_as_std")]
+    pub ping_latency_probe_interval: Duration,
+
+    /// Outbound bandwidth budgets per message class, shared by all the connections.
+    /// `maximum_size` is the burst in bytes and `refill_rate` is in bytes per second.
+    /// Classes without a budget are not limited. Example:
+    /// `{"Sync": {"maximum_size": 10000000, "refill_rate": 5000000}}`.
+    #[serde(default)]
+    pub outbound_bandwidth_budgets:
+        HashMap<bandwidth::MessageClass, messages_limits::SingleMessageConfig>,
+
     /// See `NetworkConfig`.
     /// Fields set here will override the NetworkConfig fields.
     #[serde(default)]
@@ -299,6 +356,8 @@ impl Default for ExperimentalConfig {
             tier1_enable_outbound: default_tier1_enable_outbound(),
             tier1_connect_interval: default_tier1_connect_interval(),
             tier1_new_connections_per_attempt: default_tier1_new_connections_per_attempt(),
+            ping_latency_probe_interval: default_ping_latency_probe_interval(),
+            outbound_bandwidth_budgets: HashMap::new(),
             network_config_overrides: Default::default(),
         }
     }
@@ -308,6 +367,8 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             addr: "0.0.0.0:24567".to_string(),
+            additional_addrs: vec![],
+            preferred_ip_family: None,
             boot_nodes: "".to_string(),
             whitelist_nodes: "".to_string(),
             max_num_peers: default_max_num_peers(),
@@ -332,6 +393,10 @@ impl Default for Config {
             public_addrs: vec![],
             allow_private_ip_in_public_addrs: false,
             trusted_stun_servers: default_trusted_stun_servers(),
+            peer_records_file: None,
+            dns_seeds: vec![],
+            dns_resolver: None,
+            peer_discovery_interval: default_peer_discovery_interval(),
             experimental: Default::default(),
         }
     }
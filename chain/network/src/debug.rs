@@ -1,7 +1,7 @@
 use ::actix::Message;
 use near_primitives::views::{
-    NetworkGraphView, NetworkRoutesView, PeerStoreView, RecentOutboundConnectionsView,
-    SnapshotHostsView,
+    NetworkGraphView, NetworkRoutesView, NetworkTopologyView, PeerStoreView,
+    RecentOutboundConnectionsView, SnapshotHostsView,
 };
 
 // Different debug requests that can be sent by HTML pages, via GET.
@@ -11,6 +11,7 @@ pub enum GetDebugStatus {
     RecentOutboundConnections,
     Routes,
     SnapshotHosts,
+    NetworkTopology,
 }
 
 #[derive(actix::MessageResponse, Debug)]
@@ -20,6 +21,7 @@ pub enum DebugStatus {
     RecentOutboundConnections(RecentOutboundConnectionsView),
     Routes(NetworkRoutesView),
     SnapshotHosts(SnapshotHostsView),
+    NetworkTopology(NetworkTopologyView),
 }
 
 impl Message for GetDebugStatus {
@@ -0,0 +1,339 @@
+//! Discovery of peers from DNS seeds.
+//!
+//! For every seed domain the following records are looked up:
+//! * TXT records of the seed itself, each containing a PeerInfo in the
+//!   `id@addr[@account_id]` format (records which don't parse are ignored).
+//! * SRV records of `_near._tcp.<seed>`. For every SRV target, the peer id is taken
+//!   from the TXT record of the target, and the addresses from its A/AAAA records,
+//!   combined with the port from the SRV record.
+//!
+//! The records are not authenticated in any way, so the DNS seeds (and the resolver)
+//! have to be trusted by the node operator.
+//!
+//! This is a minimal DNS client (RFC 1035), which sends the queries over UDP to the
+//! configured resolver and doesn't retry. It is good enough for a periodic discovery.
+use super::PeerDiscovery;
+use crate::network_protocol::PeerInfo;
+use anyhow::Context as _;
+use near_async::time;
+use near_crypto::PublicKey;
+use near_primitives::network::PeerId;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+const QUERY_TIMEOUT: time::Duration = time::Duration::seconds(5);
+/// UDP payload size advertised to the resolver (via EDNS0) and the size of the receive buffer.
+const MAX_MESSAGE_SIZE: usize = 4096;
+/// Service name prefix of the SRV records.
+pub const SRV_SERVICE: &str = "_near._tcp";
+const DNS_PORT: u16 = 53;
+/// Limit on the number of compression pointers followed while reading a single name,
+/// to protect against pointer loops.
+const MAX_NAME_POINTERS: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum RecordType {
+    A = 1,
+    TXT = 16,
+    AAAA = 28,
+    SRV = 33,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Record {
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+    /// Character strings of a TXT record, concatenated.
+    Txt(String),
+    Srv {
+        port: u16,
+        target: String,
+    },
+}
+
+/// Returns the first nameserver from /etc/resolv.conf.
+pub(crate) fn system_resolver() -> anyhow::Result<SocketAddr> {
+    let path = "/etc/resolv.conf";
+    let conf = std::fs::read_to_string(path).with_context(|| path)?;
+    for line in conf.lines() {
+        let mut words = line.split_whitespace();
+        if words.next() != Some("nameserver") {
+            continue;
+        }
+        if let Some(Ok(ip)) = words.next().map(|w| w.parse::<IpAddr>()) {
+            return Ok(SocketAddr::new(ip, DNS_PORT));
+        }
+    }
+    anyhow::bail!("no nameserver in {path}, set network.dns_resolver explicitly")
+}
+
+pub(crate) fn encode_name(buf: &mut Vec<u8>, name: &str) -> anyhow::Result<()> {
+    for label in name.trim_end_matches('.').split('.') {
+        anyhow::ensure!(!label.is_empty() && label.len() <= 63, "invalid domain name {name:?}");
+        buf.push(label.len() as u8);
+        buf.extend_from_slice(label.as_bytes());
+    }
+    buf.push(0);
+    Ok(())
+}
+
+fn encode_query(id: u16, name: &str, rtype: RecordType) -> anyhow::Result<Vec<u8>> {
+    let mut buf = vec![];
+    buf.extend_from_slice(&id.to_be_bytes());
+    // Standard query with recursion desired.
+    buf.extend_from_slice(&0x0100u16.to_be_bytes());
+    // QDCOUNT=1, ANCOUNT=0, NSCOUNT=0, ARCOUNT=1 (EDNS0 OPT record).
+    for count in [1u16, 0, 0, 1] {
+        buf.extend_from_slice(&count.to_be_bytes());
+    }
+    encode_name(&mut buf, name)?;
+    buf.extend_from_slice(&(rtype as u16).to_be_bytes());
+    // Class IN.
+    buf.extend_from_slice(&1u16.to_be_bytes());
+    // OPT record: root name, TYPE=41, CLASS=UDP payload size, TTL=0, RDLEN=0.
+    buf.push(0);
+    buf.extend_from_slice(&41u16.to_be_bytes());
+    buf.extend_from_slice(&(MAX_MESSAGE_SIZE as u16).to_be_bytes());
+    buf.extend_from_slice(&[0; 6]);
+    Ok(buf)
+}
+
+/// Cursor over a DNS message.
+pub(crate) struct Reader<'a> {
+    msg: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(msg: &'a [u8]) -> Self {
+        Self { msg, pos: 0 }
+    }
+
+    pub fn bytes(&mut self, n: usize) -> anyhow::Result<&'a [u8]> {
+        let end = self.pos.checked_add(n).filter(|end| *end <= self.msg.len());
+        let end = end.context("unexpected end of message")?;
+        let res = &self.msg[self.pos..end];
+        self.pos = end;
+        Ok(res)
+    }
+
+    pub fn u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub fn u16(&mut self) -> anyhow::Result<u16> {
+        Ok(u16::from_be_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    pub fn u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    /// Reads a (possibly compressed) domain name.
+    pub fn name(&mut self) -> anyhow::Result<String> {
+        let mut labels: Vec<String> = vec![];
+        // Position right after the name, once the first compression pointer is followed.
+        let mut end = None;
+        let mut pointers = 0;
+        loop {
+            let len = self.u8()?;
+            if len & 0xC0 == 0xC0 {
+                anyhow::ensure!(pointers < MAX_NAME_POINTERS, "too many compression pointers");
+                pointers += 1;
+                let offset = (((len & 0x3F) as usize) << 8) | self.u8()? as usize;
+                end.get_or_insert(self.pos);
+                self.pos = offset;
+                continue;
+            }
+            if len == 0 {
+                break;
+            }
+            let label = self.bytes(len as usize)?;
+            labels.push(String::from_utf8_lossy(label).into_owned());
+        }
+        if let Some(end) = end {
+            self.pos = end;
+        }
+        Ok(labels.join("."))
+    }
+}
+
+/// Parses the response to a query with the given `id` and returns its answers of type `rtype`.
+fn parse_response(id: u16, rtype: RecordType, msg: &[u8]) -> anyhow::Result<Vec<Record>> {
+    let mut r = Reader::new(msg);
+    anyhow::ensure!(r.u16()? == id, "response id mismatch");
+    let flags = r.u16()?;
+    anyhow::ensure!(flags & 0x8000 != 0, "not a response");
+    anyhow::ensure!(flags & 0x0200 == 0, "truncated response");
+    match flags & 0x000F {
+        0 => {}
+        // NXDOMAIN
+        3 => return Ok(vec![]),
+        rcode => anyhow::bail!("DNS error, rcode = {rcode}"),
+    }
+    let qdcount = r.u16()?;
+    let ancount = r.u16()?;
+    // NSCOUNT and ARCOUNT, the corresponding sections are not needed.
+    r.u32()?;
+    for _ in 0..qdcount {
+        r.name()?;
+        r.u32()?;
+    }
+    let mut records = vec![];
+    for _ in 0..ancount {
+        r.name()?;
+        let typ = r.u16()?;
+        let _class = r.u16()?;
+        let _ttl = r.u32()?;
+        let rdlen = r.u16()? as usize;
+        let rdata_pos = r.pos;
+        let rdata = r.bytes(rdlen)?;
+        // Answers may contain other types, i.e. CNAME records leading to the requested ones.
+        if typ != rtype as u16 {
+            continue;
+        }
+        records.push(match rtype {
+            RecordType::A => Record::A(<[u8; 4]>::try_from(rdata).context("invalid A")?.into()),
+            RecordType::AAAA => {
+                Record::Aaaa(<[u8; 16]>::try_from(rdata).context("invalid AAAA")?.into())
+            }
+            RecordType::TXT => {
+                let mut txt = Reader::new(rdata);
+                let mut data = vec![];
+                while txt.pos < rdata.len() {
+                    let len = txt.u8()? as usize;
+                    data.extend_from_slice(txt.bytes(len)?);
+                }
+                Record::Txt(String::from_utf8(data).context("invalid TXT")?)
+            }
+            RecordType::SRV => {
+                // The target may be compressed, so it has to be read from the whole message.
+                let mut srv = Reader { msg, pos: rdata_pos };
+                let _priority = srv.u16()?;
+                let _weight = srv.u16()?;
+                let port = srv.u16()?;
+                Record::Srv { port, target: srv.name()? }
+            }
+        });
+    }
+    Ok(records)
+}
+
+/// Sends a single query to the `resolver` and waits for the response.
+pub(crate) async fn query(
+    clock: &time::Clock,
+    resolver: SocketAddr,
+    name: &str,
+    rtype: RecordType,
+) -> anyhow::Result<Vec<Record>> {
+    let bind_addr = if resolver.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = tokio::net::UdpSocket::bind(bind_addr).await?;
+    socket.connect(resolver).await?;
+    let id: u16 = rand::random();
+    socket.send(&encode_query(id, name, rtype)?).await?;
+    let mut buf = vec![0; MAX_MESSAGE_SIZE];
+    // Note that both clock.sleep() and socket.recv() are cancellable,
+    // so it is safe to use them in tokio::select!.
+    let n = tokio::select! {
+        _ = clock.sleep(QUERY_TIMEOUT) => anyhow::bail!("{rtype:?} {name}: timeout"),
+        n = socket.recv(&mut buf) => n?,
+    };
+    parse_response(id, rtype, &buf[..n]).with_context(|| format!("{rtype:?} {name}"))
+}
+
+pub struct DnsDiscovery {
+    resolver: SocketAddr,
+    seeds: Vec<String>,
+}
+
+impl DnsDiscovery {
+    pub fn new(resolver: SocketAddr, seeds: Vec<String>) -> Self {
+        Self { resolver, seeds }
+    }
+
+    async fn discover_seed(
+        &self,
+        clock: &time::Clock,
+        seed: &str,
+    ) -> anyhow::Result<Vec<PeerInfo>> {
+        let mut peers = vec![];
+        for record in query(clock, self.resolver, seed, RecordType::TXT).await? {
+            let Record::Txt(txt) = record else { continue };
+            match txt.parse::<PeerInfo>() {
+                Ok(peer_info) if peer_info.addr.is_some() => peers.push(peer_info),
+                // TXT records are commonly used for other purposes as well.
+                _ => tracing::debug!(target: "network", seed, txt, "ignoring TXT record"),
+            }
+        }
+        let srv_name = format!("{SRV_SERVICE}.{seed}");
+        for record in query(clock, self.resolver, &srv_name, RecordType::SRV).await? {
+            let Record::Srv { port, target } = record else { continue };
+            match self.resolve_srv_target(clock, &target, port).await {
+                Ok(target_peers) => peers.extend(target_peers),
+                Err(err) => {
+                    tracing::warn!(target: "network", seed, target, ?err, "failed to resolve SRV target")
+                }
+            }
+        }
+        Ok(peers)
+    }
+
+    async fn resolve_srv_target(
+        &self,
+        clock: &time::Clock,
+        target: &str,
+        port: u16,
+    ) -> anyhow::Result<Vec<PeerInfo>> {
+        let txts = query(clock, self.resolver, target, RecordType::TXT).await?;
+        let peer_id = txts
+            .into_iter()
+            .find_map(|record| match record {
+                Record::Txt(txt) => txt.parse::<PublicKey>().ok().map(PeerId::new),
+                _ => None,
+            })
+            .context("missing TXT record with the peer id")?;
+        let mut ips = vec![];
+        for rtype in [RecordType::A, RecordType::AAAA] {
+            for record in query(clock, self.resolver, target, rtype).await? {
+                match record {
+                    Record::A(ip) => ips.push(IpAddr::V4(ip)),
+                    Record::Aaaa(ip) => ips.push(IpAddr::V6(ip)),
+                    _ => {}
+                }
+            }
+        }
+        Ok(ips
+            .into_iter()
+            .map(|ip| PeerInfo::new(peer_id.clone(), SocketAddr::new(ip, port)))
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl PeerDiscovery for DnsDiscovery {
+    fn name(&self) -> &'static str {
+        "dns"
+    }
+
+    /// Fails only if all the seeds have failed.
+    async fn discover(&self, clock: &time::Clock) -> anyhow::Result<Vec<PeerInfo>> {
+        let mut peers = vec![];
+        let mut last_err = None;
+        let mut any_ok = false;
+        for seed in &self.seeds {
+            match self.discover_seed(clock, seed).await {
+                Ok(seed_peers) => {
+                    any_ok = true;
+                    peers.extend(seed_peers);
+                }
+                Err(err) => {
+                    tracing::warn!(target: "network", seed, ?err, "DNS seed lookup failed");
+                    last_err = Some(err);
+                }
+            }
+        }
+        match last_err {
+            Some(err) if !any_ok => Err(err),
+            _ => Ok(peers),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Discovery of peers from a local file of signed peer records.
+//!
+//! The file contains a JSON array of `SignedPeerRecord`s, for example:
+//! ```json
+//! [
+//!   {
+//!     "peer_info": "ed25519:C6HLP37VJN1Wj2irxxZPsVsSya92Rnx12tqK3us5erKV@1.2.3.4:24567",
+//!     "signature": "ed25519:..."
+//!   }
+//! ]
+//! ```
+//! Every record is signed by the key of the peer it describes, so that the file
+//! can be distributed by an untrusted party (i.e. a peer can only advertise itself).
+//! The file is re-read whenever its modification time changes.
+use super::PeerDiscovery;
+use crate::network_protocol::PeerInfo;
+use anyhow::Context as _;
+use near_async::time;
+use near_crypto::{SecretKey, Signature};
+use parking_lot::Mutex;
+use std::path::PathBuf;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignedPeerRecord {
+    /// PeerInfo in the `id@addr[@account_id]` format. The address is required.
+    pub peer_info: String,
+    /// Signature of `peer_info` bytes, made with the secret key of the peer.
+    pub signature: Signature,
+}
+
+impl SignedPeerRecord {
+    pub fn new(peer_info: &PeerInfo, secret_key: &SecretKey) -> Self {
+        let peer_info = peer_info.to_string();
+        let signature = secret_key.sign(peer_info.as_bytes());
+        Self { peer_info, signature }
+    }
+
+    /// Parses the PeerInfo and verifies that it has been signed by the peer.
+    pub fn verify(&self) -> anyhow::Result<PeerInfo> {
+        let peer_info: PeerInfo = self.peer_info.parse()?;
+        anyhow::ensure!(peer_info.addr.is_some(), "missing address");
+        anyhow::ensure!(
+            self.signature.verify(self.peer_info.as_bytes(), peer_info.id.public_key()),
+            "invalid signature"
+        );
+        Ok(peer_info)
+    }
+}
+
+struct Cache {
+    modified: Option<std::time::SystemTime>,
+    peers: Vec<PeerInfo>,
+}
+
+pub struct StaticFileDiscovery {
+    path: PathBuf,
+    cache: Mutex<Cache>,
+}
+
+impl StaticFileDiscovery {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, cache: Mutex::new(Cache { modified: None, peers: vec![] }) }
+    }
+
+    /// Loads the records from the file. Records with invalid signatures are skipped,
+    /// so that a single bad record doesn't invalidate the whole file.
+    fn load(&self) -> anyhow::Result<Vec<PeerInfo>> {
+        let data = std::fs::read(&self.path)?;
+        let records: Vec<SignedPeerRecord> = serde_json::from_slice(&data)?;
+        let mut peers = vec![];
+        for record in records {
+            match record.verify() {
+                Ok(peer_info) => peers.push(peer_info),
+                Err(err) => {
+                    tracing::warn!(target: "network", path = %self.path.display(), record = record.peer_info, ?err, "ignoring peer record")
+                }
+            }
+        }
+        Ok(peers)
+    }
+}
+
+#[async_trait::async_trait]
+impl PeerDiscovery for StaticFileDiscovery {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn discover(&self, _clock: &time::Clock) -> anyhow::Result<Vec<PeerInfo>> {
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .with_context(|| format!("{}", self.path.display()))?;
+        let mut cache = self.cache.lock();
+        if cache.modified != Some(modified) {
+            cache.peers = self.load().with_context(|| format!("{}", self.path.display()))?;
+            cache.modified = Some(modified);
+            tracing::info!(target: "network", path = %self.path.display(), count = cache.peers.len(), "loaded peer records");
+        }
+        Ok(cache.peers.clone())
+    }
+}
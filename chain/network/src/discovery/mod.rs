@@ -0,0 +1,126 @@
+//! Sources of peers to bootstrap the PeerStore from, in addition to the peer exchange
+//! with the connected peers.
+//!
+//! Every source implements the `PeerDiscovery` trait and is queried periodically.
+//! The discovered peers are added to the PeerStore with `TrustLevel::Discovered`:
+//! the sources are configured by the node operator, so they are trusted more than the
+//! peers learned from other peers, but less than the peers we have actually connected to.
+use crate::network_protocol::PeerInfo;
+use crate::peer_manager::peer_store::PeerStore;
+use crate::stats::metrics;
+use near_async::time;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub mod dns;
+pub mod file;
+
+#[cfg(test)]
+mod testonly;
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// File with the signed peer records, see `file::StaticFileDiscovery`.
+    pub peer_records_file: Option<PathBuf>,
+    /// Domains to look up the peers at, see `dns::DnsDiscovery`.
+    pub dns_seeds: Vec<String>,
+    /// DNS server to send the `dns_seeds` queries to.
+    /// Defaults to the first nameserver from /etc/resolv.conf.
+    pub dns_resolver: Option<SocketAddr>,
+    /// How often to query the discovery sources.
+    pub interval: time::Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            peer_records_file: None,
+            dns_seeds: vec![],
+            dns_resolver: None,
+            interval: time::Duration::seconds(60),
+        }
+    }
+}
+
+/// Source of peers to connect to.
+#[async_trait::async_trait]
+pub trait PeerDiscovery: Send + Sync {
+    /// Name of the source, used in logs and metrics.
+    fn name(&self) -> &'static str;
+    /// Returns the peers currently known to the source.
+    /// It is called periodically, so it is fine to return the same peers over and over.
+    async fn discover(&self, clock: &time::Clock) -> anyhow::Result<Vec<PeerInfo>>;
+}
+
+/// The boot nodes from the config. They are added to the PeerStore on startup anyway,
+/// but rediscovering them periodically makes them come back after they expire from
+/// the PeerStore.
+pub struct BootNodesDiscovery {
+    boot_nodes: Vec<PeerInfo>,
+}
+
+impl BootNodesDiscovery {
+    pub fn new(boot_nodes: Vec<PeerInfo>) -> Self {
+        Self { boot_nodes }
+    }
+}
+
+#[async_trait::async_trait]
+impl PeerDiscovery for BootNodesDiscovery {
+    fn name(&self) -> &'static str {
+        "boot_nodes"
+    }
+
+    async fn discover(&self, _clock: &time::Clock) -> anyhow::Result<Vec<PeerInfo>> {
+        Ok(self.boot_nodes.clone())
+    }
+}
+
+/// Constructs the discovery sources enabled in the config.
+pub(crate) fn sources(
+    config: &Config,
+    boot_nodes: &[PeerInfo],
+) -> anyhow::Result<Vec<Arc<dyn PeerDiscovery>>> {
+    let mut sources: Vec<Arc<dyn PeerDiscovery>> = vec![];
+    if !boot_nodes.is_empty() {
+        sources.push(Arc::new(BootNodesDiscovery::new(boot_nodes.to_vec())));
+    }
+    if let Some(path) = &config.peer_records_file {
+        sources.push(Arc::new(file::StaticFileDiscovery::new(path.clone())));
+    }
+    if !config.dns_seeds.is_empty() {
+        let resolver = match config.dns_resolver {
+            Some(resolver) => resolver,
+            None => dns::system_resolver()?,
+        };
+        sources.push(Arc::new(dns::DnsDiscovery::new(resolver, config.dns_seeds.clone())));
+    }
+    Ok(sources)
+}
+
+/// Queries all the `sources` and adds the discovered peers to the `peer_store`.
+/// Failure of one source doesn't affect the other ones.
+pub(crate) async fn discover_peers(
+    clock: &time::Clock,
+    sources: &[Arc<dyn PeerDiscovery>],
+    peer_store: &PeerStore,
+) {
+    for source in sources {
+        match source.discover(clock).await {
+            Ok(peers) => {
+                tracing::debug!(target: "network", source = source.name(), count = peers.len(), "discovered peers");
+                metrics::PEER_DISCOVERY_PEERS
+                    .with_label_values(&[source.name()])
+                    .set(peers.len() as i64);
+                peer_store.add_discovered_peers(clock, peers.into_iter());
+            }
+            Err(err) => {
+                tracing::warn!(target: "network", source = source.name(), ?err, "peer discovery failed");
+                metrics::PEER_DISCOVERY_FAILURES_TOTAL.with_label_values(&[source.name()]).inc();
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use super::dns::{encode_name, Reader, Record, RecordType};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+type Records = HashMap<(String, RecordType), Vec<Record>>;
+
+/// DNS server listening on localhost, answering the queries with the preconfigured records.
+/// Names without records are answered with an empty response.
+pub(crate) struct StubResolver {
+    addr: SocketAddr,
+    records: Arc<parking_lot::Mutex<Records>>,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for StubResolver {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+impl StubResolver {
+    pub async fn new() -> Self {
+        let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        let records = Arc::new(parking_lot::Mutex::new(Records::new()));
+        let handle = tokio::spawn({
+            let records = records.clone();
+            async move {
+                let mut buf = vec![0; 4096];
+                loop {
+                    let (n, peer) = socket.recv_from(&mut buf).await.unwrap();
+                    let resp = respond(&records.lock(), &buf[..n]);
+                    socket.send_to(&resp, peer).await.unwrap();
+                }
+            }
+        });
+        Self { addr, records, handle }
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn add(&self, name: &str, record: Record) {
+        self.records
+            .lock()
+            .entry((name.to_string(), record_type(&record)))
+            .or_default()
+            .push(record);
+    }
+}
+
+fn record_type(record: &Record) -> RecordType {
+    match record {
+        Record::A(_) => RecordType::A,
+        Record::Aaaa(_) => RecordType::AAAA,
+        Record::Txt(_) => RecordType::TXT,
+        Record::Srv { .. } => RecordType::SRV,
+    }
+}
+
+fn rtype(typ: u16) -> Option<RecordType> {
+    [RecordType::A, RecordType::TXT, RecordType::AAAA, RecordType::SRV]
+        .into_iter()
+        .find(|t| *t as u16 == typ)
+}
+
+fn respond(records: &Records, query: &[u8]) -> Vec<u8> {
+    let mut r = Reader::new(query);
+    let id = r.u16().unwrap();
+    let _flags = r.u16().unwrap();
+    let _counts = r.bytes(8).unwrap();
+    let name = r.name().unwrap();
+    let typ = r.u16().unwrap();
+    let answers = rtype(typ)
+        .and_then(|t| records.get(&(name.clone(), t)))
+        .map(|a| a.as_slice())
+        .unwrap_or_default();
+
+    let mut buf = vec![];
+    buf.extend_from_slice(&id.to_be_bytes());
+    // Response, recursion desired, recursion available.
+    buf.extend_from_slice(&0x8180u16.to_be_bytes());
+    for count in [1u16, answers.len() as u16, 0, 0] {
+        buf.extend_from_slice(&count.to_be_bytes());
+    }
+    encode_name(&mut buf, &name).unwrap();
+    buf.extend_from_slice(&typ.to_be_bytes());
+    buf.extend_from_slice(&1u16.to_be_bytes());
+    for answer in answers {
+        // Compression pointer to the name in the question.
+        buf.extend_from_slice(&0xC00Cu16.to_be_bytes());
+        buf.extend_from_slice(&typ.to_be_bytes());
+        buf.extend_from_slice(&1u16.to_be_bytes());
+        buf.extend_from_slice(&60u32.to_be_bytes());
+        let mut rdata = vec![];
+        match answer {
+            Record::A(ip) => rdata.extend_from_slice(&ip.octets()),
+            Record::Aaaa(ip) => rdata.extend_from_slice(&ip.octets()),
+            Record::Txt(txt) => {
+                for chunk in txt.as_bytes().chunks(255) {
+                    rdata.push(chunk.len() as u8);
+                    rdata.extend_from_slice(chunk);
+                }
+            }
+            Record::Srv { port, target } => {
+                rdata.extend_from_slice(&[0, 10, 0, 5]);
+                rdata.extend_from_slice(&port.to_be_bytes());
+                encode_name(&mut rdata, target).unwrap();
+            }
+        }
+        buf.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        buf.extend_from_slice(&rdata);
+    }
+    buf
+}
@@ -0,0 +1,106 @@
+use crate::discovery::dns::{DnsDiscovery, Record, SRV_SERVICE};
+use crate::discovery::file::{SignedPeerRecord, StaticFileDiscovery};
+use crate::discovery::testonly::StubResolver;
+use crate::discovery::PeerDiscovery;
+use crate::network_protocol::testonly as data;
+use crate::network_protocol::PeerInfo;
+use crate::testonly::make_rng;
+use near_async::time;
+use near_crypto::SecretKey;
+use near_o11y::testonly::init_test_logger;
+use near_primitives::network::PeerId;
+use rand::Rng;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+
+fn make_peer(rng: &mut impl Rng) -> (SecretKey, PeerInfo) {
+    let key = data::make_secret_key(rng);
+    let peer_info = PeerInfo::new(PeerId::new(key.public_key()), data::make_addr(rng));
+    (key, peer_info)
+}
+
+fn write_records(path: &std::path::Path, records: &[SignedPeerRecord], modified: u64) {
+    std::fs::write(path, serde_json::to_vec(records).unwrap()).unwrap();
+    // Set the modification time explicitly, the file system granularity might be too coarse
+    // to notice the change otherwise.
+    let modified = std::time::UNIX_EPOCH + std::time::Duration::from_secs(modified);
+    std::fs::File::options().write(true).open(path).unwrap().set_modified(modified).unwrap();
+}
+
+#[tokio::test]
+async fn file_discovery() {
+    init_test_logger();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let clock = time::FakeClock::default();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("peer_records.json");
+
+    let (key0, peer0) = make_peer(rng);
+    let (_, peer1) = make_peer(rng);
+    let (key2, peer2) = make_peer(rng);
+    let mut records = vec![
+        SignedPeerRecord::new(&peer0, &key0),
+        // Signed by a key of a different peer.
+        SignedPeerRecord::new(&peer1, &key0),
+        // Missing address.
+        SignedPeerRecord::new(&PeerInfo { addr: None, ..peer2.clone() }, &key2),
+    ];
+    write_records(&path, &records, 1000);
+    let discovery = StaticFileDiscovery::new(path.clone());
+    assert_eq!(vec![peer0.clone()], discovery.discover(&clock.clock()).await.unwrap());
+
+    tracing::info!(target:"test", "file modifications are picked up");
+    records.push(SignedPeerRecord::new(&peer2, &key2));
+    write_records(&path, &records, 2000);
+    assert_eq!(vec![peer0, peer2], discovery.discover(&clock.clock()).await.unwrap());
+
+    tracing::info!(target:"test", "malformed file is reported as an error");
+    std::fs::write(&path, "not a json").unwrap();
+    assert!(discovery.discover(&clock.clock()).await.is_err());
+}
+
+#[tokio::test]
+async fn dns_discovery() {
+    init_test_logger();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let clock = time::FakeClock::default();
+    let resolver = StubResolver::new().await;
+
+    let seed = "seed.near.test";
+    let (_, peer0) = make_peer(rng);
+    resolver.add(seed, Record::Txt(peer0.to_string()));
+    resolver.add(seed, Record::Txt("v=spf1 -all".to_string()));
+
+    let peer1_id = data::make_peer_id(rng);
+    let target = "node1.near.test";
+    resolver.add(
+        &format!("{SRV_SERVICE}.{seed}"),
+        Record::Srv { port: 24567, target: target.to_string() },
+    );
+    resolver.add(target, Record::Txt(peer1_id.public_key().to_string()));
+    resolver.add(target, Record::A("1.2.3.4".parse().unwrap()));
+    resolver.add(target, Record::Aaaa("2001:db8::1".parse().unwrap()));
+    // SRV target without the peer id is skipped.
+    resolver.add(
+        &format!("{SRV_SERVICE}.{seed}"),
+        Record::Srv { port: 24567, target: "node2.near.test".to_string() },
+    );
+    resolver.add("node2.near.test", Record::A("1.2.3.5".parse().unwrap()));
+
+    let discovery = DnsDiscovery::new(resolver.addr(), vec![seed.to_string()]);
+    let got: HashSet<_> = discovery.discover(&clock.clock()).await.unwrap().into_iter().collect();
+    let want: HashSet<_> = [
+        peer0,
+        PeerInfo::new(peer1_id.clone(), "1.2.3.4:24567".parse().unwrap()),
+        PeerInfo::new(peer1_id, "[2001:db8::1]:24567".parse::<SocketAddr>().unwrap()),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(want, got);
+
+    tracing::info!(target:"test", "unknown seed yields no peers");
+    let discovery = DnsDiscovery::new(resolver.addr(), vec!["unknown.near.test".to_string()]);
+    assert_eq!(Vec::<PeerInfo>::new(), discovery.discover(&clock.clock()).await.unwrap());
+}
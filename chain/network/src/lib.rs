@@ -1,6 +1,8 @@
 #![cfg_attr(enable_const_type_id, feature(const_type_id))]
 
 pub use crate::peer_manager::peer_manager_actor::{Event, PeerManagerActor};
+pub use crate::rate_limits::bandwidth::MessageClass;
+pub use crate::rate_limits::messages_limits::Config as MessagesLimitsConfig;
 pub use crate::rate_limits::messages_limits::OverrideConfig as MessagesLimitsOverrideConfig;
 
 mod accounts_data;
@@ -22,6 +24,7 @@ pub mod concurrency;
 pub mod config;
 pub mod config_json;
 pub mod debug;
+pub mod discovery;
 pub mod raw;
 pub mod routing;
 pub mod shards_manager;
@@ -30,6 +33,7 @@ pub mod state_witness;
 pub mod tcp;
 pub mod test_loop;
 pub mod test_utils;
+pub mod topology;
 pub mod types;
 
 #[cfg(test)]
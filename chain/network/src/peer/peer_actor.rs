@@ -21,6 +21,7 @@ use crate::peer_manager::network_state::{NetworkState, PRUNE_EDGES_AFTER};
 use crate::peer_manager::peer_manager_actor::Event;
 use crate::peer_manager::peer_manager_actor::MAX_TIER2_PEERS;
 use crate::private_actix::{RegisterPeerError, SendMessage};
+use crate::rate_limits::bandwidth::MessageClass;
 use crate::rate_limits::messages_limits;
 use crate::routing::edge::verify_nonce;
 use crate::routing::NetworkTopologyChange;
@@ -75,6 +76,8 @@ pub(crate) const DROP_DUPLICATED_MESSAGES_PERIOD: time::Duration = time::Duratio
 const SYNC_LATEST_BLOCK_INTERVAL: time::Duration = time::Duration::seconds(60);
 /// How often to perform a full sync of AccountsData with the peer.
 const ACCOUNTS_DATA_FULL_SYNC_INTERVAL: time::Duration = time::Duration::minutes(10);
+/// How often to check whether the received messages rate limits config was updated.
+const RATE_LIMITS_REFRESH_INTERVAL: time::Duration = time::Duration::seconds(10);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ConnectionClosedEvent {
@@ -188,6 +191,12 @@ pub(crate) struct PeerActor {
 
     /// Per-message rate limits for incoming messages.
     received_messages_rate_limits: messages_limits::RateLimits,
+    /// Class of the peer used to select its rate limits. Known only after the handshake.
+    peer_class: Option<messages_limits::PeerClass>,
+    /// Config from which `received_messages_rate_limits` were built.
+    received_messages_rate_limits_config: messages_limits::Config,
+    /// Last time `received_messages_rate_limits_config` was compared with the current config.
+    received_messages_rate_limits_checked_at: time::Instant,
 }
 
 impl Debug for PeerActor {
@@ -318,15 +327,20 @@ impl PeerActor {
         };
         let my_node_info = PeerInfo {
             id: network_state.config.node_id(),
+            // PeerInfo holds a single address, so `additional_node_addrs` are not advertised
+            // in peer exchange. Peers learn the address they have connected to instead.
             addr: network_state.config.node_addr.as_ref().map(|a| **a),
             // TODO(validator-key-hot-swap) Consider using mutable validator signer instead of PeerInfo.account_id ?
             // That likely requires bigger changes and account_id here is later used for debug / logging purposes only.
             account_id: network_state.config.validator.account_id(),
         };
+        let received_messages_rate_limits_config =
+            network_state.config.received_messages_rate_limits.get();
         let received_messages_rate_limits = messages_limits::RateLimits::from_config(
-            &network_state.config.received_messages_rate_limits,
+            &received_messages_rate_limits_config,
             clock.now(),
         );
+        let received_messages_rate_limits_checked_at = clock.now();
         // recv is the HandshakeSignal returned by this spawn_inner() call.
         let (send, recv): (HandshakeSignalSender, HandshakeSignal) =
             tokio::sync::oneshot::channel();
@@ -337,7 +351,13 @@ impl PeerActor {
                 let peer_addr = stream.peer_addr;
                 let stream_type = stream.type_.clone();
                 let stats = Arc::new(connection::Stats::default());
-                let framed = stream::FramedStream::spawn(ctx, stream, stats.clone());
+                let framed = stream::FramedStream::spawn(
+                    ctx,
+                    stream,
+                    stats.clone(),
+                    clock.clone(),
+                    network_state.outbound_bandwidth_budgets.clone(),
+                );
                 Self {
                     closing_reason: None,
                     clock,
@@ -368,12 +388,61 @@ impl PeerActor {
                     .into(),
                     network_state,
                     received_messages_rate_limits,
+                    peer_class: None,
+                    received_messages_rate_limits_config,
+                    received_messages_rate_limits_checked_at,
                 }
             }),
             recv,
         ))
     }
 
+    /// Returns the class of a peer which completed the handshake, used to select its rate limits.
+    fn classify_peer(
+        &self,
+        tier: tcp::Tier,
+        handshake: &Handshake,
+    ) -> Option<messages_limits::PeerClass> {
+        if tier == tcp::Tier::T1 {
+            Some(messages_limits::PeerClass::Tier1Validator)
+        } else if handshake.sender_chain_info.archival {
+            Some(messages_limits::PeerClass::Archival)
+        } else if self.peer_type == PeerType::Inbound
+            && self.network_state.peer_store.get_peer_state(&handshake.sender_peer_id).is_none()
+        {
+            Some(messages_limits::PeerClass::UnknownInbound)
+        } else {
+            None
+        }
+    }
+
+    /// Rebuilds the received messages rate limits for the given peer class and config.
+    /// Note that all the buckets start again from their initial size.
+    fn reset_received_messages_rate_limits(
+        &mut self,
+        peer_class: Option<messages_limits::PeerClass>,
+        config: messages_limits::Config,
+        now: time::Instant,
+    ) {
+        self.received_messages_rate_limits =
+            messages_limits::RateLimits::from_config_for_peer_class(&config, peer_class, now);
+        self.peer_class = peer_class;
+        self.received_messages_rate_limits_config = config;
+    }
+
+    /// Picks up changes of the received messages rate limits config made while the node is running.
+    fn maybe_refresh_received_messages_rate_limits(&mut self, now: time::Instant) {
+        if now < self.received_messages_rate_limits_checked_at + RATE_LIMITS_REFRESH_INTERVAL {
+            return;
+        }
+        self.received_messages_rate_limits_checked_at = now;
+        let config = self.network_state.config.received_messages_rate_limits.get();
+        if config != self.received_messages_rate_limits_config {
+            tracing::debug!(target: "network", peer_info = %self.peer_info, "Received messages rate limits config changed");
+            self.reset_received_messages_rate_limits(self.peer_class, config, now);
+        }
+    }
+
     // Determines the encoding to use for communication with the peer.
     // It can be None while Handshake with the peer has not been finished yet.
     // In case it is None, both encodings are attempted for parsing, and each message
@@ -407,14 +476,25 @@ impl PeerActor {
     }
 
     fn send_message(&self, msg: &PeerMessage) {
+        self.send_message_with_class(msg, MessageClass::of(msg));
+    }
+
+    /// Sends a response to a request received from the peer.
+    /// Responses may be sent with a lower priority than the same messages
+    /// sent on this node's own initiative, see `MessageClass::of_response`.
+    fn send_response(&self, msg: &PeerMessage) {
+        self.send_message_with_class(msg, MessageClass::of_response(msg));
+    }
+
+    fn send_message_with_class(&self, msg: &PeerMessage, class: MessageClass) {
         if let (PeerStatus::Ready(conn), PeerMessage::PeersRequest(_)) = (&self.peer_status, msg) {
             conn.last_time_peer_requested.store(Some(self.clock.now()));
         }
         if let Some(enc) = self.encoding() {
-            return self.send_message_with_encoding(msg, enc);
+            return self.send_message_with_encoding(msg, enc, class);
         }
-        self.send_message_with_encoding(msg, Encoding::Proto);
-        self.send_message_with_encoding(msg, Encoding::Borsh);
+        self.send_message_with_encoding(msg, Encoding::Proto, class);
+        self.send_message_with_encoding(msg, Encoding::Borsh, class);
     }
 
     #[tracing::instrument(
@@ -424,7 +504,7 @@ impl PeerActor {
         skip_all,
         fields(msg_type = msg.msg_variant())
     )]
-    fn send_message_with_encoding(&self, msg: &PeerMessage, enc: Encoding) {
+    fn send_message_with_encoding(&self, msg: &PeerMessage, enc: Encoding, class: MessageClass) {
         // Skip sending block and headers if we received it or header from this peer.
         // Record block requests in tracker.
         match msg {
@@ -453,7 +533,7 @@ impl PeerActor {
         self.tracker.lock().increment_sent(&self.clock, bytes.len() as u64);
         let bytes_len = bytes.len();
         tracing::trace!(target: "network", msg_len = bytes_len);
-        self.framed.send(stream::Frame(bytes));
+        self.framed.send(class, stream::Frame(bytes));
         metrics::PEER_DATA_SENT_BYTES.inc_by(bytes_len as u64);
         let msg_type = msg.msg_variant();
         metrics::PEER_MESSAGE_SENT_BY_TYPE_TOTAL.with_label_values(&[msg_type]).inc();
@@ -483,13 +563,17 @@ impl PeerActor {
                 archival: self.network_state.config.archive,
             },
             partial_edge_info: spec.partial_edge_info,
-            owned_account: self.network_state.config.validator.signer.get().map(|signer| {
+            owned_account: self.network_state.config.validator.signer.get().and_then(|signer| {
                 OwnedAccount {
                     account_key: signer.public_key(),
                     peer_id: self.network_state.config.node_id(),
                     timestamp: self.clock.now_utc(),
                 }
                 .sign(&signer)
+                .inspect_err(|err| {
+                    tracing::warn!(target: "network", ?err, "Failed to sign the owned account, sending the handshake without it");
+                })
+                .ok()
             }),
         };
         let msg = match spec.tier {
@@ -662,6 +746,8 @@ impl PeerActor {
             account_id: None,
         };
 
+        let peer_class = self.classify_peer(tier, &handshake);
+
         let now = self.clock.now();
         let conn = Arc::new(connection::Connection {
             tier,
@@ -731,6 +817,8 @@ impl PeerActor {
                     Ok(()) => {
                         act.peer_info = Some(peer_info).into();
                         act.peer_status = PeerStatus::Ready(conn.clone());
+                        let rate_limits_config = act.network_state.config.received_messages_rate_limits.get();
+                        act.reset_received_messages_rate_limits(peer_class, rate_limits_config, act.clock.now());
                         // Respond to handshake if it's inbound and connection was consolidated.
                         if act.peer_type == PeerType::Inbound {
                             act.send_handshake(HandshakeSpec{
@@ -1131,7 +1219,7 @@ impl PeerActor {
             |res, act: &mut PeerActor, ctx| {
                 match res {
                     // TODO(gprusak): make sure that for routed messages we drop routeback info correctly.
-                    Ok(Some(resp)) => act.send_message_or_log(&resp),
+                    Ok(Some(resp)) => act.send_response(&resp),
                     Ok(None) => {}
                     Err(ban_reason) => act.stop(ctx, ClosingReason::Ban(ban_reason)),
                 }
@@ -1439,9 +1527,10 @@ impl PeerActor {
                             #[cfg(test)]
                             message_processed_event();
                         }
-                        RoutedMessageBody::Pong(_pong) => {
+                        RoutedMessageBody::Pong(pong) => {
+                            self.network_state.receive_pong(&self.clock, pong);
                             #[cfg(test)]
-                            self.network_state.config.event_sink.send(Event::Pong(_pong.clone()));
+                            self.network_state.config.event_sink.send(Event::Pong(pong.clone()));
                             #[cfg(test)]
                             message_processed_event();
                         }
@@ -1701,9 +1790,14 @@ impl actix::Handler<stream::Frame> for PeerActor {
             metrics::PEER_MESSAGE_RECEIVED_BY_TYPE_BYTES
                 .with_label_values(&labels)
                 .inc_by(msg.len() as u64);
-            if !self.received_messages_rate_limits.is_allowed(&peer_msg, now) {
+            self.maybe_refresh_received_messages_rate_limits(now);
+            if let Err(key) = self.received_messages_rate_limits.check(&peer_msg, now) {
                 metrics::PEER_MESSAGE_RATE_LIMITED_BY_TYPE_TOTAL.with_label_values(&labels).inc();
-                tracing::debug!(target: "network", "Peer {} is being rate limited for message {}", self.peer_info, peer_msg.msg_variant());
+                let peer_class = self.peer_class.map_or("Default", |class| class.into());
+                metrics::PEER_MESSAGE_RATE_LIMITED_BY_PEER_CLASS_TOTAL
+                    .with_label_values(&[&key.to_string(), peer_class])
+                    .inc();
+                tracing::debug!(target: "network", peer_id = ?self.other_peer_id(), %key, peer_class, "Peer is being rate limited for message {}", peer_msg.msg_variant());
                 return;
             }
         }
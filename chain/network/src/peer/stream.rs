@@ -1,9 +1,11 @@
 use crate::peer_manager::connection;
+use crate::rate_limits::bandwidth::{Budgets, MessageClass, SendQueue};
 use crate::stats::metrics;
 use crate::tcp;
 use actix::fut::future::wrap_future;
 use actix::AsyncContext as _;
 use bytesize::{GIB, MIB};
+use near_async::time;
 use std::io;
 use std::net::SocketAddr;
 use std::sync::atomic::Ordering;
@@ -16,6 +18,9 @@ use tokio::io::AsyncWriteExt as _;
 const NETWORK_MESSAGE_MAX_SIZE_BYTES: usize = 512 * MIB as usize;
 /// Maximum capacity of write buffer in bytes.
 const MAX_WRITE_BUFFER_CAPACITY_BYTES: usize = GIB as usize;
+/// How often to check whether the exhausted bandwidth budgets have been refilled,
+/// while there are messages waiting for them.
+const BUDGET_REFILL_CHECK_INTERVAL: time::Duration = time::Duration::milliseconds(10);
 
 type ReadHalf = tokio::io::ReadHalf<tokio::net::TcpStream>;
 type WriteHalf = tokio::io::WriteHalf<tokio::net::TcpStream>;
@@ -57,7 +62,7 @@ pub(crate) enum Error {
 }
 
 pub(crate) struct FramedStream<Actor: actix::Actor> {
-    queue_send: tokio::sync::mpsc::UnboundedSender<Frame>,
+    queue_send: tokio::sync::mpsc::UnboundedSender<(MessageClass, Frame)>,
     stats: Arc<connection::Stats>,
     send_buf_size_metric: Arc<metrics::IntGaugeGuard>,
     addr: actix::Addr<Actor>,
@@ -73,6 +78,8 @@ where
         ctx: &mut actix::Context<Actor>,
         stream: tcp::Stream,
         stats: Arc<connection::Stats>,
+        clock: time::Clock,
+        budgets: Arc<Budgets>,
     ) -> Self {
         let (tcp_recv, tcp_send) = tokio::io::split(stream.stream);
         let (queue_send, queue_recv) = tokio::sync::mpsc::unbounded_channel();
@@ -85,7 +92,9 @@ where
             let stats = stats.clone();
             let m = send_buf_size_metric.clone();
             async move {
-                if let Err(err) = Self::run_send_loop(tcp_send, queue_recv, stats, m).await {
+                if let Err(err) =
+                    Self::run_send_loop(clock, tcp_send, queue_recv, budgets, stats, m).await
+                {
                     addr.do_send(Error::Send(SendError::IO(err)));
                 }
             }
@@ -104,16 +113,17 @@ where
         Self { queue_send, stats, send_buf_size_metric, addr: ctx.address() }
     }
 
-    /// Pushes `msg` to the send queue.
+    /// Pushes `msg` to the send queue of the given `class`.
     /// Silently drops message if the connection has been closed.
     /// If the message is too large, it will be silently dropped inside run_send_loop.
     /// Emits a critical error to Actor if send queue is full.
-    pub fn send(&self, frame: Frame) {
+    pub fn send(&self, class: MessageClass, frame: Frame) {
         let msg = &frame.0;
         let mut buf_size =
             self.stats.bytes_to_send.fetch_add(msg.len() as u64, Ordering::Acquire) as usize;
         buf_size += msg.len();
         self.stats.messages_to_send.fetch_add(1, Ordering::Acquire);
+        self.stats.bytes_to_send_by_class[class].fetch_add(msg.len() as u64, Ordering::Acquire);
         self.send_buf_size_metric.add(msg.len() as i64);
         // Exceeding buffer capacity is a critical error and Actor should call ctx.stop()
         // when receiving one. It is not like we do any extra allocations, so we can affort
@@ -125,7 +135,7 @@ where
                 want_max_bytes: MAX_WRITE_BUFFER_CAPACITY_BYTES,
             }));
         }
-        let _ = self.queue_send.send(frame);
+        let _ = self.queue_send.send((class, frame));
     }
 
     /// Event loop receiving and processing messages.
@@ -175,17 +185,25 @@ where
             }
         }
     }
+    /// Sends the queued messages, highest priority class first.
+    /// A class which has exhausted its bandwidth budget is skipped until the budget refills.
     async fn run_send_loop(
+        clock: time::Clock,
         tcp_send: WriteHalf,
-        mut queue_recv: tokio::sync::mpsc::UnboundedReceiver<Frame>,
+        mut queue_recv: tokio::sync::mpsc::UnboundedReceiver<(MessageClass, Frame)>,
+        budgets: Arc<Budgets>,
         stats: Arc<connection::Stats>,
         buf_size_metric: Arc<metrics::IntGaugeGuard>,
     ) -> io::Result<()> {
         const WRITE_BUFFER_CAPACITY: usize = 8 * 1024;
         let mut writer = tokio::io::BufWriter::with_capacity(WRITE_BUFFER_CAPACITY, tcp_send);
-        while let Some(Frame(mut msg)) = queue_recv.recv().await {
-            // Try writing a batch of messages and flush once at the end.
-            loop {
+        let mut queue = SendQueue::default();
+        loop {
+            while let Ok((class, Frame(msg))) = queue_recv.try_recv() {
+                queue.push(class, msg);
+            }
+            // Try writing a batch of messages and flush once nothing more can be sent.
+            if let Some((class, msg)) = queue.pop(&budgets, clock.now()) {
                 // TODO(gprusak): sending a too large message should probably be treated as a bug,
                 // since dropping messages may lead to hard-to-debug high-level issues.
                 if msg.len() > NETWORK_MESSAGE_MAX_SIZE_BYTES {
@@ -196,11 +214,9 @@ where
                 }
                 stats.messages_to_send.fetch_sub(1, Ordering::Release);
                 stats.bytes_to_send.fetch_sub(msg.len() as u64, Ordering::Release);
+                stats.bytes_to_send_by_class[class].fetch_sub(msg.len() as u64, Ordering::Release);
                 buf_size_metric.sub(msg.len() as i64);
-                msg = match queue_recv.try_recv() {
-                    Ok(Frame(it)) => it,
-                    Err(_) => break,
-                };
+                continue;
             }
             // This is an unconditional flush, which means that even if new messages
             // will be added to the queue in the meantime, we will wait for the buffer
@@ -209,7 +225,21 @@ where
             // we would need to put writer.flush() and queue_recv.recv() into a tokio::select
             // and make sure that both are cancellation-safe.
             writer.flush().await?;
+            let next = if queue.is_empty() {
+                queue_recv.recv().await
+            } else {
+                // All the queued messages are waiting for the bandwidth budgets to refill.
+                // Note that both clock.sleep() and queue_recv.recv() are cancellable,
+                // so it is safe to use them in tokio::select!.
+                tokio::select! {
+                    _ = clock.sleep(BUDGET_REFILL_CHECK_INTERVAL) => continue,
+                    next = queue_recv.recv() => next,
+                }
+            };
+            match next {
+                Some((class, Frame(msg))) => queue.push(class, msg),
+                None => return Ok(()),
+            }
         }
-        Ok(())
     }
 }
@@ -25,7 +25,7 @@ async fn test_message_rate_limits() -> anyhow::Result<()> {
 
     let mut clock = FakeClock::default();
     let mut rng = make_rng(89028037453);
-    let (outbound, inbound) = setup_test_peers(&mut clock, &mut rng).await;
+    let (outbound, inbound) = setup_test_peers(&mut clock, &mut rng, |_| {}).await;
 
     const MESSAGES: u32 = 7;
     // Let's gather all events received from now on. We'll check them later, after producing messages.
@@ -55,7 +55,7 @@ async fn test_message_rate_limits_over_time() -> anyhow::Result<()> {
 
     let mut clock = FakeClock::default();
     let mut rng = make_rng(89028037453);
-    let (outbound, inbound) = setup_test_peers(&mut clock, &mut rng).await;
+    let (outbound, inbound) = setup_test_peers(&mut clock, &mut rng, |_| {}).await;
 
     const MESSAGES: u32 = 4;
     const INTERVAL: Duration = Duration::from_secs(2);
@@ -86,6 +86,44 @@ async fn test_message_rate_limits_over_time() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+// Verifies that rate limits overrides for a peer class apply to peers of that class.
+async fn test_message_rate_limits_per_peer_class() -> anyhow::Result<()> {
+    init_test_logger();
+    tracing::info!("test_message_rate_limits_per_peer_class");
+
+    let mut clock = FakeClock::default();
+    let mut rng = make_rng(89028037453);
+    // The outbound peer is not present in the peer store of the inbound peer,
+    // so it is classified as an unknown inbound peer.
+    let (outbound, inbound) = setup_test_peers(&mut clock, &mut rng, |config| {
+        use messages_limits::RateLimitedPeerMessageKey::*;
+        let overrides =
+            config.peer_class_rate_limits.entry(messages_limits::PeerClass::UnknownInbound);
+        let overrides = overrides.or_default();
+        overrides
+            .insert(Transaction, Some(messages_limits::SingleMessageConfig::new(5, 0.0, None)));
+        overrides.insert(BlockRequest, None);
+    })
+    .await;
+
+    const MESSAGES: u32 = 7;
+    let mut events = inbound.events.from_now();
+    let messages_samples = send_messages(&inbound, &outbound, &mut rng, MESSAGES).await;
+
+    let messages_received =
+        wait_for_similar_messages(&messages_samples, &mut events, Duration::from_secs(3)).await;
+    tracing::debug!(target:"test","received {messages_received:?} messages");
+    // BlockRequest rate limit is disabled for unknown inbound peers.
+    assert_eq!(messages_received[0], MESSAGES);
+    // PartialEncodedChunkRequest uses the default rate limit (7 sent vs 5 bucket_start).
+    assert!(messages_received[1] < MESSAGES);
+    // Transaction gets rate limited (7 sent vs 5 bucket_start for unknown inbound peers).
+    assert!(messages_received[2] < MESSAGES);
+
+    Ok(())
+}
+
 /// Waits up to `duration` and then checks how many events equal (in type only) to each one of `samples`
 /// have been received.
 ///
@@ -117,12 +155,19 @@ async fn wait_for_similar_messages(
 /// Rate limits configuration:
 /// - `BlockRequest`, `PartialEncodedChunkRequest`: bucket_start = 5, bucket_max = 10, refill_rate = 2.5/s
 /// - `Transaction`: bucket_start = bucket_max = 50, refill_rate = 5/s
-async fn setup_test_peers(clock: &mut FakeClock, mut rng: &mut Rng) -> (PeerHandle, PeerHandle) {
+///
+/// `customize_rate_limits` can further modify the rate limits of both peers.
+async fn setup_test_peers(
+    clock: &mut FakeClock,
+    mut rng: &mut Rng,
+    customize_rate_limits: impl Fn(&mut messages_limits::Config),
+) -> (PeerHandle, PeerHandle) {
     let chain = Arc::new(data::Chain::make(clock, &mut rng, 12));
 
     // Customize the network configuration to set some arbitrary rate limits.
-    let add_rate_limits = |mut network_config: NetworkConfig| {
-        let rate_limits = &mut network_config.received_messages_rate_limits.rate_limits;
+    let add_rate_limits = |network_config: NetworkConfig| {
+        let mut config = network_config.received_messages_rate_limits.get();
+        let rate_limits = &mut config.rate_limits;
         use messages_limits::RateLimitedPeerMessageKey::*;
         rate_limits
             .insert(BlockRequest, messages_limits::SingleMessageConfig::new(10, 2.5, Some(5)));
@@ -131,6 +176,8 @@ async fn setup_test_peers(clock: &mut FakeClock, mut rng: &mut Rng) -> (PeerHand
             messages_limits::SingleMessageConfig::new(10, 2.5, Some(5)),
         );
         rate_limits.insert(Transaction, messages_limits::SingleMessageConfig::new(50, 5.0, None));
+        customize_rate_limits(&mut config);
+        network_config.received_messages_rate_limits.update(config);
         network_config
     };
 
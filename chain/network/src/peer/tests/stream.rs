@@ -1,11 +1,15 @@
 use crate::actix::ActixSystem;
 use crate::network_protocol::testonly as data;
 use crate::peer::stream;
+use crate::rate_limits::bandwidth::{Budgets, Config, MessageClass};
+use crate::rate_limits::messages_limits::SingleMessageConfig;
 use crate::tcp;
 use crate::testonly::make_rng;
 use actix::Actor as _;
 use actix::ActorContext as _;
+use near_async::time;
 use rand::Rng as _;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
@@ -20,12 +24,12 @@ impl actix::Actor for Actor {
 
 #[derive(actix::Message)]
 #[rtype("()")]
-struct SendFrame(stream::Frame);
+struct SendFrame(MessageClass, stream::Frame);
 
 impl actix::Handler<SendFrame> for Actor {
     type Result = ();
-    fn handle(&mut self, SendFrame(frame): SendFrame, _ctx: &mut Self::Context) {
-        self.stream.send(frame);
+    fn handle(&mut self, SendFrame(class, frame): SendFrame, _ctx: &mut Self::Context) {
+        self.stream.send(class, frame);
     }
 }
 
@@ -50,12 +54,21 @@ struct Handler {
 
 impl Actor {
     async fn spawn(s: tcp::Stream) -> Handler {
+        Self::spawn_with_budgets(s, time::Clock::real(), Arc::default()).await
+    }
+
+    async fn spawn_with_budgets(
+        s: tcp::Stream,
+        clock: time::Clock,
+        budgets: Arc<Budgets>,
+    ) -> Handler {
         let (queue_send, queue_recv) = mpsc::unbounded_channel();
         Handler {
             queue_recv,
             system: ActixSystem::spawn(|| {
                 Actor::create(|ctx| {
-                    let stream = stream::FramedStream::spawn(ctx, s, Arc::default());
+                    let stream =
+                        stream::FramedStream::spawn(ctx, s, Arc::default(), clock, budgets);
                     Self { stream, queue_send }
                 })
             })
@@ -82,7 +95,7 @@ async fn send_recv() {
             })
             .collect();
         for msg in &msgs {
-            a1.system.addr.send(SendFrame(msg.clone())).await.unwrap();
+            a1.system.addr.send(SendFrame(MessageClass::Default, msg.clone())).await.unwrap();
         }
         for want in &msgs {
             let got = a2.queue_recv.recv().await.unwrap();
@@ -90,3 +103,46 @@ async fn send_recv() {
         }
     }
 }
+
+#[tokio::test]
+async fn sync_messages_deprioritized() {
+    let mut rng = make_rng(98324532);
+    let (s1, s2) = tcp::Stream::loopback(data::make_peer_id(&mut rng), tcp::Tier::T2).await;
+    // Budget allows sending a single sync message at once, and then one every 100ms.
+    let config = Config {
+        budgets: HashMap::from([(
+            MessageClass::Sync,
+            SingleMessageConfig::new(1000, 10000., None),
+        )]),
+    };
+    let clock = time::FakeClock::default();
+    let budgets = Arc::new(Budgets::from_config(&config, clock.now()));
+    let a1 = Actor::spawn_with_budgets(s1, clock.clock(), budgets).await;
+    let mut a2 = Actor::spawn(s2).await;
+
+    let frame = |i: u8| stream::Frame(vec![i; 1000]);
+    for i in 0..3 {
+        a1.system.addr.send(SendFrame(MessageClass::Sync, frame(i))).await.unwrap();
+    }
+    assert_eq!(frame(0), a2.queue_recv.recv().await.unwrap());
+    // The consensus message overtakes the sync messages waiting for the budget.
+    a1.system.addr.send(SendFrame(MessageClass::Consensus, frame(3))).await.unwrap();
+    assert_eq!(frame(3), a2.queue_recv.recv().await.unwrap());
+    // The remaining sync messages are sent one at a time, as the budget refills.
+    // The sender polls the budget periodically, so the clock is advanced in small steps
+    // until the message arrives.
+    let start = clock.now();
+    for want in [1, 2] {
+        assert!(a2.queue_recv.try_recv().is_err());
+        let got = loop {
+            clock.advance(time::Duration::milliseconds(10));
+            let recv = a2.queue_recv.recv();
+            if let Ok(got) = tokio::time::timeout(std::time::Duration::from_millis(10), recv).await
+            {
+                break got.unwrap();
+            }
+        };
+        assert_eq!(frame(want), got);
+        assert!(clock.now() - start >= time::Duration::milliseconds(100 * i64::from(want)));
+    }
+}
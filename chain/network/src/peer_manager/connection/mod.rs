@@ -8,10 +8,12 @@ use crate::network_protocol::{
 use crate::peer::peer_actor;
 use crate::peer::peer_actor::PeerActor;
 use crate::private_actix::SendMessage;
+use crate::rate_limits::bandwidth::MessageClass;
 use crate::stats::metrics;
 use crate::tcp;
 use crate::types::{BlockInfo, FullPeerInfo, PeerChainInfo, PeerType, ReasonForBan};
 use arc_swap::ArcSwap;
+use enum_map::EnumMap;
 use near_async::time;
 use near_crypto::PublicKey;
 use near_o11y::WithSpanContextExt;
@@ -77,6 +79,8 @@ pub(crate) struct Stats {
     pub messages_to_send: AtomicU64,
     /// Number of bytes (sum of message sizes) in the buffer to send.
     pub bytes_to_send: AtomicU64,
+    /// Number of bytes in the buffer to send, by message class.
+    pub bytes_to_send_by_class: EnumMap<MessageClass, AtomicU64>,
 }
 
 /// Contains information relevant to a connected peer.
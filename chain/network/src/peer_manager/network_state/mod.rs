@@ -17,6 +17,7 @@ use crate::peer_manager::connection;
 use crate::peer_manager::connection_store;
 use crate::peer_manager::peer_store;
 use crate::private_actix::RegisterPeerError;
+use crate::rate_limits::bandwidth;
 use crate::routing::route_back_cache::RouteBackCache;
 use crate::routing::NetworkTopologyChange;
 use crate::shards_manager::ShardsManagerRequestFromNetwork;
@@ -50,6 +51,7 @@ use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 use tracing::Instrument as _;
 
+mod ping;
 mod routing;
 mod tier1;
 
@@ -154,6 +156,12 @@ pub(crate) struct NetworkState {
     /// TODO(gprusak): consider removing it altogether.
     pub tier1_route_back: Mutex<RouteBackCache>,
 
+    /// Latencies to other peers, measured with Ping/Pong messages.
+    pub ping_latencies: Mutex<ping::PingLatencies>,
+
+    /// Outbound bandwidth budgets per message class, shared by all the connections.
+    pub outbound_bandwidth_budgets: Arc<bandwidth::Budgets>,
+
     /// Queue of received requests to which a response should be made over TIER3.
     pub tier3_requests: Mutex<VecDeque<Tier3Request>>,
 
@@ -220,6 +228,11 @@ impl NetworkState {
             tier2_route_back: Mutex::new(RouteBackCache::default()),
             tier1_route_back: Mutex::new(RouteBackCache::default()),
             tier3_requests: Mutex::new(VecDeque::<Tier3Request>::new()),
+            ping_latencies: Mutex::new(ping::PingLatencies::new()),
+            outbound_bandwidth_budgets: Arc::new(bandwidth::Budgets::from_config(
+                &config.outbound_bandwidth,
+                clock.now(),
+            )),
             recent_routed_messages: Mutex::new(lru::LruCache::new(
                 NonZeroUsize::new(RECENT_ROUTED_MESSAGES_CACHE_SIZE).unwrap(),
             )),
@@ -504,16 +517,6 @@ impl NetworkState {
         }
     }
 
-    #[cfg(test)]
-    pub fn send_ping(&self, clock: &time::Clock, tier: tcp::Tier, nonce: u64, target: PeerId) {
-        let body = RoutedMessageBody::Ping(crate::network_protocol::Ping {
-            nonce,
-            source: self.config.node_id(),
-        });
-        let msg = RawRoutedMessage { target: PeerIdOrHash::PeerId(target), body };
-        self.send_message_to_peer(clock, tier, self.sign_message(clock, msg));
-    }
-
     pub fn send_pong(&self, clock: &time::Clock, tier: tcp::Tier, nonce: u64, target: CryptoHash) {
         let body = RoutedMessageBody::Pong(crate::network_protocol::Pong {
             nonce,
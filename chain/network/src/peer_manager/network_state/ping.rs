@@ -0,0 +1,129 @@
+use super::NetworkState;
+use crate::network_protocol::{PeerIdOrHash, Ping, Pong, RawRoutedMessage, RoutedMessageBody};
+use crate::tcp;
+use ::time::ext::InstantExt as _;
+use near_async::time;
+use near_primitives::network::PeerId;
+use std::num::NonZeroUsize;
+
+/// Max number of pings awaiting a Pong. Unanswered pings are evicted
+/// once the limit is reached.
+const PENDING_PINGS_CACHE_SIZE: usize = 1000;
+/// Max number of peers to keep the latency measurements for.
+const LATENCIES_CACHE_SIZE: usize = 1000;
+
+/// Round trip time measured with a Ping/Pong exchange.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct PingLatency {
+    pub latency: time::Duration,
+    pub measured_at: time::Utc,
+}
+
+/// Latencies to other peers, measured by sending them routed Ping messages
+/// and waiting for the corresponding Pong.
+pub(crate) struct PingLatencies {
+    /// Pings sent by this node, which haven't been answered yet, by nonce.
+    pending: lru::LruCache<u64, (PeerId, time::Instant)>,
+    /// The latest measurement for each peer.
+    latencies: lru::LruCache<PeerId, PingLatency>,
+}
+
+impl PingLatencies {
+    pub fn new() -> Self {
+        Self {
+            pending: lru::LruCache::new(NonZeroUsize::new(PENDING_PINGS_CACHE_SIZE).unwrap()),
+            latencies: lru::LruCache::new(NonZeroUsize::new(LATENCIES_CACHE_SIZE).unwrap()),
+        }
+    }
+
+    fn ping_sent(&mut self, clock: &time::Clock, nonce: u64, target: PeerId) {
+        self.pending.put(nonce, (target, clock.now()));
+    }
+
+    /// Records the latency, if `pong` answers a pending ping.
+    /// Returns the measured latency.
+    fn pong_received(&mut self, clock: &time::Clock, pong: &Pong) -> Option<time::Duration> {
+        match self.pending.peek(&pong.nonce) {
+            // Pongs from other peers with a colliding nonce are ignored.
+            Some((target, _)) if target == &pong.source => {}
+            _ => return None,
+        }
+        let (target, sent_at) = self.pending.pop(&pong.nonce).unwrap();
+        let latency = clock.now().signed_duration_since(sent_at);
+        self.latencies.put(target, PingLatency { latency, measured_at: clock.now_utc() });
+        Some(latency)
+    }
+
+    /// Latest latency measurements, in no particular order.
+    pub fn get_all(&self) -> Vec<(PeerId, PingLatency)> {
+        self.latencies.iter().map(|(peer_id, latency)| (peer_id.clone(), *latency)).collect()
+    }
+}
+
+impl NetworkState {
+    pub fn send_ping(&self, clock: &time::Clock, tier: tcp::Tier, nonce: u64, target: PeerId) {
+        self.ping_latencies.lock().ping_sent(clock, nonce, target.clone());
+        let body = RoutedMessageBody::Ping(Ping { nonce, source: self.config.node_id() });
+        let msg = RawRoutedMessage { target: PeerIdOrHash::PeerId(target), body };
+        self.send_message_to_peer(clock, tier, self.sign_message(clock, msg));
+    }
+
+    pub fn receive_pong(&self, clock: &time::Clock, pong: &Pong) {
+        if let Some(latency) = self.ping_latencies.lock().pong_received(clock, pong) {
+            tracing::trace!(target: "network", peer_id = %pong.source, ?latency, "measured ping latency");
+        }
+    }
+
+    /// Pings all the directly connected TIER2 peers, to measure the latency of the connections.
+    pub fn probe_latencies(&self, clock: &time::Clock) {
+        for peer_id in self.tier2.load().ready.keys() {
+            self.send_ping(clock, tcp::Tier::T2, rand::random(), peer_id.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PingLatencies;
+    use crate::network_protocol::testonly as data;
+    use crate::network_protocol::Pong;
+    use crate::testonly::make_rng;
+    use near_async::time;
+
+    #[test]
+    fn pong_received() {
+        let mut rng = make_rng(921853233);
+        let clock = time::FakeClock::default();
+        let peer0 = data::make_peer_id(&mut rng);
+        let peer1 = data::make_peer_id(&mut rng);
+        let mut latencies = PingLatencies::new();
+
+        latencies.ping_sent(&clock.clock(), 7, peer0.clone());
+        clock.advance(time::Duration::milliseconds(150));
+        // Pong with the right nonce, but from a different peer.
+        assert_eq!(
+            None,
+            latencies.pong_received(&clock.clock(), &Pong { nonce: 7, source: peer1 })
+        );
+        // Pong with an unknown nonce.
+        assert_eq!(
+            None,
+            latencies.pong_received(&clock.clock(), &Pong { nonce: 8, source: peer0.clone() })
+        );
+        assert!(latencies.get_all().is_empty());
+
+        let got =
+            latencies.pong_received(&clock.clock(), &Pong { nonce: 7, source: peer0.clone() });
+        assert_eq!(Some(time::Duration::milliseconds(150)), got);
+        let all = latencies.get_all();
+        assert_eq!(1, all.len());
+        assert_eq!(peer0, all[0].0);
+        assert_eq!(time::Duration::milliseconds(150), all[0].1.latency);
+        // A duplicated Pong is ignored.
+        assert_eq!(
+            None,
+            latencies.pong_received(&clock.clock(), &Pong { nonce: 7, source: peer0 })
+        );
+        assert_eq!(1, latencies.get_all().len());
+    }
+}
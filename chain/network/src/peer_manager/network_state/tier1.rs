@@ -37,6 +37,62 @@ impl super::NetworkState {
         Some(FrozenValidatorConfig { signer, proxies: &self.config.validator.proxies })
     }
 
+    /// Returns the IP families this node is listening on, the preferred one first.
+    fn listen_ip_families(&self) -> Vec<config::IpFamily> {
+        let mut families = vec![];
+        for addr in self.config.listen_addrs() {
+            // On most systems a listener on [::] accepts IPv4 connections as well.
+            if addr.is_ipv6() && addr.ip().is_unspecified() {
+                families.extend([config::IpFamily::V6, config::IpFamily::V4]);
+            } else {
+                families.push(config::IpFamily::of(addr));
+            }
+        }
+        families.sort_by_key(|family| *family != self.config.preferred_ip_family);
+        families.dedup();
+        families
+    }
+
+    /// Discovers the public IP of this node in the given IP family, by querying the STUN
+    /// servers in parallel. We do not require all STUN servers to be available, but
+    /// we require the received responses to be consistent.
+    async fn stun_discover_ip(
+        self: &Arc<Self>,
+        clock: &time::Clock,
+        stun_servers: &[stun::ServerAddr],
+        family: config::IpFamily,
+    ) -> Option<std::net::IpAddr> {
+        // Query all the STUN servers in parallel.
+        let queries = stun_servers.iter().map(|addr| {
+            let clock = clock.clone();
+            let want_ipv4 = family == config::IpFamily::V4;
+            let addr = addr.clone();
+            self.spawn(async move {
+                let addr = stun::lookup_host(&addr, want_ipv4).await?;
+                match stun::query(&clock, &addr).await {
+                    Ok(ip) => Some(ip),
+                    Err(err) => {
+                        tracing::warn!(target:"network", "STUN lookup failed for {addr}: {err}");
+                        None
+                    }
+                }
+            })
+        });
+        let mut node_ips = vec![];
+        for q in queries {
+            node_ips.extend(q.await.unwrap());
+        }
+        // Check that we have received non-zero responses and that they are consistent.
+        if node_ips.is_empty() {
+            None
+        } else if !node_ips.iter().all(|ip| ip == &node_ips[0]) {
+            tracing::warn!(target:"network", ?family, "received inconsistent responses from the STUN servers");
+            None
+        } else {
+            Some(node_ips[0])
+        }
+    }
+
     async fn tier1_connect_to_my_proxies(
         self: &Arc<Self>,
         clock: &time::Clock,
@@ -106,45 +162,28 @@ impl super::NetworkState {
             (_, config::ValidatorProxies::Static(peer_addrs)) => peer_addrs.clone(),
             // If Dynamic are specified,
             // it means that this node is its own proxy.
-            // Discover the public IP of this node using those STUN servers.
-            // We do not require all stun servers to be available, but
-            // we require the received responses to be consistent.
+            // Discover the public IP of this node using those STUN servers,
+            // separately for every IP family this node is listening on.
             (Some(node_addr), config::ValidatorProxies::Dynamic(stun_servers)) => {
-                // Query all the STUN servers in parallel.
-                let queries = stun_servers.iter().map(|addr| {
-                    let clock = clock.clone();
-                    let want_ipv4 = node_addr.is_ipv4();
-                    let addr = addr.clone();
-                    self.spawn(async move {
-                        let addr = stun::lookup_host(&addr, want_ipv4).await?;
-                        match stun::query(&clock, &addr).await {
-                            Ok(ip) => Some(ip),
-                            Err(err) => {
-                                tracing::warn!(target:"network", "STUN lookup failed for {addr}: {err}");
-                                None
-                            }
-                        }
-                    })
-                });
-                let mut node_ips = vec![];
-                for q in queries {
-                    node_ips.extend(q.await.unwrap());
-                }
-                // Check that we have received non-zero responses and that they are consistent.
-                if node_ips.is_empty() {
-                    vec![]
-                } else if !node_ips.iter().all(|ip| ip == &node_ips[0]) {
-                    tracing::warn!(target:"network", "received inconsistent responses from the STUN servers");
-                    vec![]
-                } else {
-                    vec![PeerAddr {
-                        peer_id: self.config.node_id(),
-                        addr: std::net::SocketAddr::new(node_ips[0], node_addr.port()),
-                    }]
+                let mut proxies = vec![];
+                for family in self.listen_ip_families() {
+                    if let Some(ip) = self.stun_discover_ip(clock, stun_servers, family).await {
+                        proxies.push(PeerAddr {
+                            peer_id: self.config.node_id(),
+                            addr: std::net::SocketAddr::new(ip, node_addr.port()),
+                        });
+                    }
                 }
+                proxies
             }
         };
-        self.tier1_connect_to_my_proxies(clock, &proxies).await;
+        // In case of dynamic configuration all the proxies point to this node, so a single
+        // loop connection (over the preferred IP family) confirms the reachability of this node.
+        let connect_to = match &vc.proxies {
+            config::ValidatorProxies::Dynamic(_) => &proxies[..proxies.len().min(1)],
+            config::ValidatorProxies::Static(_) => &proxies[..],
+        };
+        self.tier1_connect_to_my_proxies(clock, connect_to).await;
 
         // Snapshot tier1 connections again before broadcasting.
         let tier1 = self.tier1.load();
@@ -152,12 +191,25 @@ impl super::NetworkState {
         let my_proxies = match &vc.proxies {
             // In case of dynamic configuration, only the node itself can be its proxy,
             // so we look for a loop connection which would prove our node's address.
+            // Addresses of the other IP families discovered via STUN are advertised as well,
+            // as long as the node is confirmed to be reachable.
             config::ValidatorProxies::Dynamic(_) => match tier1.ready.get(&self.config.node_id()) {
                 Some(conn) => {
                     log_assert!(PeerType::Outbound == conn.peer_type);
                     log_assert!(conn.peer_info.addr.is_some());
                     match conn.peer_info.addr {
-                        Some(addr) => vec![PeerAddr { peer_id: self.config.node_id(), addr }],
+                        Some(addr) => {
+                            let family = config::IpFamily::of(&addr);
+                            let mut my_proxies =
+                                vec![PeerAddr { peer_id: self.config.node_id(), addr }];
+                            my_proxies.extend(
+                                proxies
+                                    .iter()
+                                    .filter(|proxy| config::IpFamily::of(&proxy.addr) != family)
+                                    .cloned(),
+                            );
+                            my_proxies
+                        }
                         None => vec![],
                     }
                 }
@@ -322,9 +374,10 @@ impl super::NetworkState {
                 if safe.contains_key(account_key) {
                     continue;
                 }
-                // Find addresses of proxies of account_key.
+                // Find addresses of proxies of account_key, preferably of the preferred IP family.
                 let proxies: Vec<&PeerAddr> =
                     proxies_by_account.get(account_key).into_iter().flatten().map(|x| *x).collect();
+                let proxies = self.config.preferred_ip_family.prefer(proxies, |proxy| proxy.addr);
                 // Select a random proxy of the account_key and try to connect to it.
                 let proxy = proxies.iter().choose(&mut rand::thread_rng());
                 if let Some(proxy) = proxy {
@@ -1,6 +1,7 @@
 use crate::client::{ClientSenderForNetwork, SetNetworkInfo, StateRequestPart};
 use crate::config;
 use crate::debug::{DebugStatus, GetDebugStatus};
+use crate::discovery;
 use crate::network_protocol;
 use crate::network_protocol::SyncSnapshotHosts;
 use crate::network_protocol::{
@@ -25,6 +26,7 @@ use ::time::ext::InstantExt as _;
 use actix::fut::future::wrap_future;
 use actix::{Actor as _, AsyncContext as _};
 use anyhow::Context as _;
+use enum_map::enum_map;
 use near_async::messaging::{SendAsync, Sender};
 use near_async::time;
 use near_o11y::{handler_debug_span, handler_trace_span, WithSpanContext};
@@ -33,8 +35,10 @@ use near_primitives::block::GenesisId;
 use near_primitives::network::{AnnounceAccount, PeerId};
 use near_primitives::stateless_validation::chunk_endorsement::ChunkEndorsement;
 use near_primitives::views::{
-    ConnectionInfoView, EdgeView, KnownPeerStateView, NetworkGraphView, PeerStoreView,
-    RecentOutboundConnectionsView, SnapshotHostInfoView, SnapshotHostsView,
+    ConnectionInfoView, EdgeView, KnownPeerStateView, NetworkGraphView, NetworkTopologyView,
+    PeerLatencyView, PeerStoreView, RecentOutboundConnectionsView, SnapshotHostInfoView,
+    SnapshotHostsView, TopologyAccountView, TopologyEdgeView, TopologyProxyView,
+    TopologyTier1ProxiesView,
 };
 use network_protocol::MAX_SHARDS_PER_SNAPSHOT_HOST_INFO;
 use rand::seq::{IteratorRandom, SliceRandom};
@@ -237,6 +241,9 @@ impl PeerManagerActor {
             }
             v
         };
+        let discovery_sources =
+            discovery::sources(&config.peer_discovery, &config.peer_store.boot_nodes)
+                .context("peer discovery")?;
         let my_peer_id = config.node_id();
         let arbiter = actix::Arbiter::new().handle();
         let clock = clock;
@@ -256,17 +263,22 @@ impl PeerManagerActor {
             let state = state.clone();
             let clock = clock.clone();
             async move {
-                // Start server if address provided.
-                if let Some(server_addr) = &state.config.node_addr {
+                // Start servers on all the configured addresses.
+                let mut listeners = vec![];
+                for server_addr in state.config.listen_addrs() {
                     tracing::debug!(target: "network", at = ?server_addr, "starting public server");
-                    let mut listener = match server_addr.listener() {
+                    listeners.push(match server_addr.listener() {
                         Ok(it) => it,
                         Err(e) => {
                             panic!("failed to start listening on server_addr={server_addr:?} e={e:?}")
                         }
-                    };
-                    #[cfg(test)]
+                    });
+                }
+                #[cfg(test)]
+                if !listeners.is_empty() {
                     state.config.event_sink.send(Event::ServerStarted);
+                }
+                for mut listener in listeners {
                     arbiter.spawn({
                         let clock = clock.clone();
                         let state = state.clone();
@@ -345,6 +357,35 @@ impl PeerManagerActor {
                         }
                     }
                 });
+                // Periodically measure the latency of the connections.
+                if let Some(interval) = state.config.ping_latency_probe_interval {
+                    arbiter.spawn({
+                        let clock = clock.clone();
+                        let state = state.clone();
+                        let mut interval = time::Interval::new(clock.now(), interval);
+                        async move {
+                            loop {
+                                interval.tick(&clock).await;
+                                state.probe_latencies(&clock);
+                            }
+                        }
+                    });
+                }
+                // Periodically add the peers from the discovery sources to the peer store.
+                if !discovery_sources.is_empty() {
+                    arbiter.spawn({
+                        let clock = clock.clone();
+                        let state = state.clone();
+                        let mut interval =
+                            time::Interval::new(clock.now(), state.config.peer_discovery.interval);
+                        async move {
+                            loop {
+                                interval.tick(&clock).await;
+                                discovery::discover_peers(&clock, &discovery_sources, &state.peer_store).await;
+                            }
+                        }
+                    });
+                }
                 // Periodically process pending Tier3 requests.
                 arbiter.spawn({
                     let clock = clock.clone();
@@ -672,7 +713,7 @@ impl PeerManagerActor {
                 |peer_state| {
                     // Ignore connecting to ourself
                     self.my_peer_id == peer_state.peer_info.id
-                    || self.state.config.node_addr.as_ref().map(|a|**a) == peer_state.peer_info.addr
+                    || self.state.config.listen_addrs().any(|a| Some(**a) == peer_state.peer_info.addr)
                     // Or to peers we are currently trying to connect to
                     || tier2.outbound_handshakes.contains(&peer_state.peer_info.id)
                 },
@@ -763,6 +804,9 @@ impl PeerManagerActor {
                 Some(e) => e.nonce(),
                 None => 0,
             },
+            send_queue_bytes: enum_map! {
+                class => cp.stats.bytes_to_send_by_class[class].load(Ordering::Relaxed),
+            },
         };
         NetworkInfo {
             connected_peers: tier2.ready.values().map(connected_peer).collect(),
@@ -798,6 +842,77 @@ impl PeerManagerActor {
         }
     }
 
+    /// Snapshot of the network topology known to this node, for the debug page.
+    pub(crate) fn network_topology_view(&self) -> NetworkTopologyView {
+        let latencies = self.state.ping_latencies.lock().get_all();
+        let latency_micros = |latency: time::Duration| latency.whole_microseconds() as u64;
+        let edge_latency = |peer0: &PeerId, peer1: &PeerId| {
+            let other = match (peer0 == &self.my_peer_id, peer1 == &self.my_peer_id) {
+                (true, false) => peer1,
+                (false, true) => peer0,
+                _ => return None,
+            };
+            let (_, latency) = latencies.iter().find(|(peer_id, _)| peer_id == other)?;
+            Some(latency_micros(latency.latency))
+        };
+        NetworkTopologyView {
+            my_peer_id: self.my_peer_id.clone(),
+            edges: self
+                .state
+                .graph
+                .load()
+                .edges
+                .values()
+                .map(|edge| {
+                    let (peer0, peer1) = edge.key();
+                    TopologyEdgeView {
+                        peer0: peer0.clone(),
+                        peer1: peer1.clone(),
+                        nonce: edge.nonce(),
+                        latency_micros: edge_latency(peer0, peer1),
+                    }
+                })
+                .collect(),
+            accounts: self
+                .state
+                .account_announcements
+                .get_announcements()
+                .into_iter()
+                .map(|announce_account| TopologyAccountView {
+                    account_id: announce_account.account_id,
+                    peer_id: announce_account.peer_id,
+                })
+                .collect(),
+            tier1_proxies: self
+                .state
+                .accounts_data
+                .load()
+                .data
+                .values()
+                .map(|data| TopologyTier1ProxiesView {
+                    account_key: data.account_key.clone(),
+                    peer_id: data.peer_id.clone(),
+                    proxies: data
+                        .proxies
+                        .iter()
+                        .map(|proxy| TopologyProxyView {
+                            peer_id: proxy.peer_id.clone(),
+                            addr: proxy.addr.to_string(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            latencies: latencies
+                .iter()
+                .map(|(peer_id, latency)| PeerLatencyView {
+                    peer_id: peer_id.clone(),
+                    latency_micros: latency_micros(latency.latency),
+                    measured_at: latency.measured_at.unix_timestamp(),
+                })
+                .collect(),
+        }
+    }
+
     fn push_network_info_trigger(&self, ctx: &mut actix::Context<Self>, interval: time::Duration) {
         let _span = tracing::trace_span!(target: "network", "push_network_info_trigger").entered();
         let network_info = self.get_network_info();
@@ -1403,6 +1518,9 @@ impl actix::Handler<GetDebugStatus> for PeerManagerActor {
                     })
                     .collect::<Vec<_>>(),
             }),
+            GetDebugStatus::NetworkTopology => {
+                DebugStatus::NetworkTopology(self.network_topology_view())
+            }
         }
     }
 }
@@ -29,10 +29,14 @@ mod tests;
 /// the PeerStore is initialized from the boot nodes in its config.
 
 /// Level of trust we have about a new (PeerId, Addr) pair.
-#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+/// Variants are ordered from the least to the most trusted.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
 enum TrustLevel {
     /// We learn about it from other peers.
     Indirect,
+    /// We learn about it from a discovery source configured by the node operator
+    /// (see `crate::discovery`).
+    Discovered,
     /// Responding node at addr claims to possess PeerId.
     Direct,
     /// Responding peer proved to have SecretKey associated with this PeerID.
@@ -98,6 +102,31 @@ impl Inner {
         self.add_peer(clock, peer_info, TrustLevel::Signed)
     }
 
+    /// Adds unverified peers into the store with given trust level, skipping the blacklisted ones.
+    fn add_peers(
+        &mut self,
+        clock: &time::Clock,
+        peers: impl Iterator<Item = PeerInfo>,
+        trust_level: TrustLevel,
+    ) {
+        let mut total: usize = 0;
+        let mut blacklisted: usize = 0;
+        for peer_info in peers {
+            total += 1;
+            let is_blacklisted =
+                peer_info.addr.is_some_and(|addr| self.config.blacklist.contains(addr));
+            if is_blacklisted {
+                blacklisted += 1;
+            } else {
+                self.add_peer(clock, peer_info, trust_level);
+            }
+        }
+        if blacklisted != 0 {
+            tracing::info!(target: "network", "Ignored {} blacklisted peers out of {} {:?} peer(s)",
+                  blacklisted, total, trust_level);
+        }
+    }
+
     /// Adds a peer into the store with given trust level.
     fn add_peer(&mut self, clock: &time::Clock, peer_info: PeerInfo, trust_level: TrustLevel) {
         if let Some(peer_addr) = peer_info.addr {
@@ -120,6 +149,21 @@ impl Inner {
                     }
                     self.update_peer_info(clock, peer_info, peer_addr, TrustLevel::Direct);
                 }
+                TrustLevel::Discovered => {
+                    // Discovery sources are configured by the operator, so they may override
+                    // what we have learned from other peers, but not what we have verified
+                    // by connecting to the peer.
+                    let peer_trust_level = (|| {
+                        let state = self.peer_states.peek(&peer_info.id)?;
+                        let addr = state.peer_info.addr?;
+                        Some(self.addr_peers.get(&addr)?.trust_level)
+                    })();
+                    let addr_trust_level = self.addr_peers.get(&peer_addr).map(|v| v.trust_level);
+                    if peer_trust_level.max(addr_trust_level) >= Some(TrustLevel::Direct) {
+                        return;
+                    }
+                    self.update_peer_info(clock, peer_info, peer_addr, TrustLevel::Discovered);
+                }
                 TrustLevel::Indirect => {
                     // We should only update an Indirect connection if we don't know anything about the peer
                     // or about the address.
@@ -361,7 +405,6 @@ impl PeerStore {
         self.0.lock().update(clock)
     }
 
-    #[allow(dead_code)]
     /// Returns the state of the current peer in memory.
     pub fn get_peer_state(&self, peer_id: &PeerId) -> Option<KnownPeerState> {
         self.0.lock().peer_states.get(peer_id).cloned()
@@ -484,23 +527,18 @@ impl PeerStore {
     ///
     /// See also [`Self::add_direct_peer`] and [`Self::add_signed_peer`].
     pub fn add_indirect_peers(&self, clock: &time::Clock, peers: impl Iterator<Item = PeerInfo>) {
-        let mut inner = self.0.lock();
-        let mut total: usize = 0;
-        let mut blacklisted: usize = 0;
-        for peer_info in peers {
-            total += 1;
-            let is_blacklisted =
-                peer_info.addr.is_some_and(|addr| inner.config.blacklist.contains(addr));
-            if is_blacklisted {
-                blacklisted += 1;
-            } else {
-                inner.add_peer(clock, peer_info, TrustLevel::Indirect);
-            }
-        }
-        if blacklisted != 0 {
-            tracing::info!(target: "network", "Ignored {} blacklisted peers out of {} indirect peer(s)",
-                  blacklisted, total);
-        }
+        self.0.lock().add_peers(clock, peers, TrustLevel::Indirect)
+    }
+
+    /// Adds peers returned by the discovery sources (see `crate::discovery`).
+    ///
+    /// As with indirect peers, we haven't verified their identities, but the sources
+    /// are configured by the node operator, so the discovered peers take precedence
+    /// over the indirect ones. They never override peers we have connected to.
+    ///
+    /// See also [`Self::add_indirect_peers`] and [`Self::add_direct_peer`].
+    pub fn add_discovered_peers(&self, clock: &time::Clock, peers: impl Iterator<Item = PeerInfo>) {
+        self.0.lock().add_peers(clock, peers, TrustLevel::Discovered)
     }
 
     /// Adds a peer we’ve connected to but haven’t verified ID yet.
@@ -352,6 +352,56 @@ fn check_add_peers_overriding() {
     assert!(check_integrity(&peer_store));
 }
 
+/// Discovered peers override the indirect ones, but not the ones we have connected to.
+#[test]
+fn check_add_discovered_peers_overriding() {
+    let clock = time::FakeClock::default();
+    let peer_store =
+        PeerStore::new(&clock.clock(), make_config(&[], Default::default(), false)).unwrap();
+
+    let peers_id = (0..4).map(|ix| get_peer_id(format!("node{}", ix))).collect::<Vec<_>>();
+    let addrs = (0..4).map(get_addr).collect::<Vec<_>>();
+
+    // Indirect A - #A is overridden by discovered A - #B.
+    let peer_00 = get_peer_info(peers_id[0].clone(), Some(addrs[0]));
+    peer_store.add_indirect_peers(&clock.clock(), [peer_00].into_iter());
+    let peer_01 = get_peer_info(peers_id[0].clone(), Some(addrs[1]));
+    peer_store.add_discovered_peers(&clock.clock(), [peer_01].into_iter());
+    assert!(check_exist(&peer_store, &peers_id[0], Some((addrs[1], TrustLevel::Discovered))));
+    assert!(check_integrity(&peer_store));
+
+    // Indirect peers don't override discovered A - #B.
+    let peer_02 = get_peer_info(peers_id[0].clone(), Some(addrs[2]));
+    peer_store.add_indirect_peers(&clock.clock(), [peer_02].into_iter());
+    let peer_11 = get_peer_info(peers_id[1].clone(), Some(addrs[1]));
+    peer_store.add_indirect_peers(&clock.clock(), [peer_11].into_iter());
+    assert!(check_exist(&peer_store, &peers_id[0], Some((addrs[1], TrustLevel::Discovered))));
+    assert!(check_integrity(&peer_store));
+
+    // Direct B - #B overrides discovered A - #B.
+    let peer_11 = get_peer_info(peers_id[1].clone(), Some(addrs[1]));
+    peer_store.add_direct_peer(&clock.clock(), peer_11);
+    assert!(check_exist(&peer_store, &peers_id[1], Some((addrs[1], TrustLevel::Direct))));
+    assert!(check_exist(&peer_store, &peers_id[0], None));
+    assert!(check_integrity(&peer_store));
+
+    // Discovered peers don't override direct B - #B, neither by id nor by address.
+    let peer_13 = get_peer_info(peers_id[1].clone(), Some(addrs[3]));
+    let peer_21 = get_peer_info(peers_id[2].clone(), Some(addrs[1]));
+    peer_store.add_discovered_peers(&clock.clock(), [peer_13, peer_21].into_iter());
+    assert!(check_exist(&peer_store, &peers_id[1], Some((addrs[1], TrustLevel::Direct))));
+    assert!(!check_exist(&peer_store, &peers_id[2], Some((addrs[1], TrustLevel::Discovered))));
+    assert!(check_integrity(&peer_store));
+
+    // Signed peers override discovered ones.
+    let peer_33 = get_peer_info(peers_id[3].clone(), Some(addrs[3]));
+    peer_store.add_discovered_peers(&clock.clock(), [peer_33.clone()].into_iter());
+    assert!(check_exist(&peer_store, &peers_id[3], Some((addrs[3], TrustLevel::Discovered))));
+    peer_store.peer_connected(&clock.clock(), &peer_33);
+    assert!(check_exist(&peer_store, &peers_id[3], Some((addrs[3], TrustLevel::Signed))));
+    assert!(check_integrity(&peer_store));
+}
+
 #[test]
 fn check_ignore_blacklisted_peers() {
     let clock = time::FakeClock::default();
@@ -238,14 +238,26 @@ impl ActorHandler {
         &self,
         chain: Arc<data::Chain>,
         network_cfg: config::NetworkConfig,
+    ) -> RawConnection {
+        self.start_inbound_at(chain, network_cfg, self.peer_info().addr.unwrap()).await
+    }
+
+    /// Same as `start_inbound`, but connects to the given listen address of the peer manager,
+    /// which may be any of `NetworkConfig::listen_addrs()`.
+    pub async fn start_inbound_at(
+        &self,
+        chain: Arc<data::Chain>,
+        network_cfg: config::NetworkConfig,
+        addr: std::net::SocketAddr,
     ) -> RawConnection {
         // To avoid race condition:
         // 1. reserve a TCP port
         // 2. snapshot event stream
         // 3. establish connection.
-        let socket = tcp::Socket::bind();
+        let socket = if addr.is_ipv4() { tcp::Socket::bind_v4() } else { tcp::Socket::bind() };
         let events = self.events.from_now();
-        let stream = socket.connect(&self.peer_info(), tcp::Tier::T2).await;
+        let peer_info = PeerInfo { addr: Some(addr), ..self.peer_info() };
+        let stream = socket.connect(&peer_info, tcp::Tier::T2).await;
         let stream_id = stream.id();
         let conn = RawConnection {
             events,
@@ -16,6 +16,7 @@ use crate::types::Edge;
 use near_async::time;
 use near_o11y::testonly::init_test_logger;
 use near_primitives::version::PROTOCOL_VERSION;
+use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::Arc;
 
 #[tokio::test]
@@ -175,7 +176,8 @@ async fn owned_account_mismatch() {
                     peer_id: data::make_peer_id(rng),
                     timestamp: clock.now_utc(),
                 }
-                .sign(&signer),
+                .sign(&signer)
+                .unwrap(),
             ),
         }))
         .await;
@@ -297,7 +299,8 @@ async fn invalid_edge() {
                         peer_id: cfg.node_id(),
                         timestamp: clock.now_utc(),
                     }
-                    .sign(&signer),
+                    .sign(&signer)
+                    .unwrap(),
                 ),
             };
             let handshake = match tier {
@@ -326,3 +329,35 @@ async fn invalid_edge() {
         }
     }
 }
+
+#[tokio::test]
+async fn accept_on_additional_listen_addr() {
+    init_test_logger();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let mut clock = time::FakeClock::default();
+    let chain = Arc::new(data::Chain::make(&mut clock, rng, 10));
+
+    let mut cfg = chain.make_config(rng);
+    // Listen on IPv6 loopback (node_addr) and on IPv4 loopback, on the same port.
+    let node_addr = **cfg.node_addr.as_ref().unwrap();
+    let ipv4_addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), node_addr.port());
+    cfg.additional_node_addrs = vec![tcp::ListenerAddr::new(ipv4_addr)];
+    let pm = peer_manager::testonly::start(
+        clock.clock(),
+        near_store::db::TestDB::new(),
+        cfg,
+        chain.clone(),
+    )
+    .await;
+
+    tracing::info!(target:"test", "connect over IPv6");
+    let conn = pm.start_inbound(chain.clone(), chain.make_config(rng)).await;
+    let v6_peer = conn.handshake(&clock.clock()).await;
+    tracing::info!(target:"test", "connect over IPv4");
+    let conn = pm.start_inbound_at(chain.clone(), chain.make_config(rng), ipv4_addr).await;
+    let v4_peer = conn.handshake(&clock.clock()).await;
+
+    pm.wait_for_direct_connection(v6_peer.cfg.id()).await;
+    pm.wait_for_direct_connection(v4_peer.cfg.id()).await;
+}
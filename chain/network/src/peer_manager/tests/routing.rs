@@ -1,6 +1,7 @@
 use crate::blacklist;
 use crate::broadcast;
 use crate::config::{NetworkConfig, SocketOptions};
+use crate::debug::{DebugStatus, GetDebugStatus};
 use crate::network_protocol::testonly as data;
 use crate::network_protocol::{Encoding, Ping, Pong, RoutedMessageBody, RoutingTableUpdate};
 use crate::peer;
@@ -361,6 +362,52 @@ async fn ping_simple() {
     drop(pm1);
 }
 
+// test that the ping latencies end up in the network topology
+#[tokio::test]
+async fn ping_latency_in_network_topology() {
+    abort_on_panic();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let mut clock = time::FakeClock::default();
+    let chain = Arc::new(data::Chain::make(&mut clock, rng, 10));
+
+    tracing::info!(target:"test", "start two nodes");
+    let pm0 = start_pm(clock.clock(), TestDB::new(), chain.make_config(rng), chain.clone()).await;
+    let pm1 = start_pm(clock.clock(), TestDB::new(), chain.make_config(rng), chain.clone()).await;
+
+    let id0 = pm0.cfg.node_id();
+    let id1 = pm1.cfg.node_id();
+
+    pm0.connect_to(&pm1.peer_info(), tcp::Tier::T2).await;
+    pm0.wait_for_routing_table(&[(id1.clone(), vec![id1.clone()])]).await;
+
+    tracing::info!(target:"test", "probe latencies of {id0} connections");
+    let mut pm0_ev = pm0.events.from_now();
+    let clock0 = clock.clock();
+    pm0.with_state(move |s| async move { s.probe_latencies(&clock0) }).await;
+    pm0_ev
+        .recv_until(|ev| match ev {
+            Event::PeerManager(PME::Pong(pong)) if pong.source == id1 => Some(()),
+            _ => None,
+        })
+        .await;
+
+    let topology = match pm0.actix.addr.send(GetDebugStatus::NetworkTopology).await.unwrap() {
+        DebugStatus::NetworkTopology(topology) => topology,
+        _ => panic!("unexpected response"),
+    };
+    assert_eq!(id0, topology.my_peer_id);
+    assert_eq!(
+        vec![id1.clone()],
+        topology.latencies.iter().map(|l| l.peer_id.clone()).collect::<Vec<_>>()
+    );
+    let edge = topology.edges.iter().find(|e| e.peer0 == id1 || e.peer1 == id1).unwrap();
+    assert!(edge.latency_micros.is_some());
+
+    drop(pm0);
+    drop(pm1);
+}
+
 // test ping without a direct connection
 #[tokio::test]
 async fn ping_jump() {
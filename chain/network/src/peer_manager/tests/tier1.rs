@@ -15,6 +15,7 @@ use near_primitives::validator_signer::ValidatorSigner;
 use near_store::db::TestDB;
 use rand::Rng as _;
 use std::collections::HashSet;
+use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::Arc;
 
 /// Constructs a random TIER1 message.
@@ -22,7 +23,7 @@ fn make_block_approval(rng: &mut Rng, signer: &ValidatorSigner) -> Approval {
     let inner = ApprovalInner::Endorsement(data::make_hash(rng));
     let target_height = rng.gen_range(0..100000);
     Approval {
-        signature: signer.sign_approval(&inner, target_height),
+        signature: signer.sign_approval(&inner, target_height).unwrap(),
         account_id: signer.validator_id().clone(),
         target_height,
         inner,
@@ -418,3 +419,40 @@ async fn stun_self_discovery() {
     stun_server1.close().await;
     stun_server2.close().await;
 }
+
+#[tokio::test]
+async fn stun_self_discovery_dual_stack() {
+    init_test_logger();
+    let mut rng = make_rng(921853233);
+    let rng = &mut rng;
+    let mut clock = time::FakeClock::default();
+    let chain = Arc::new(data::Chain::make(&mut clock, rng, 10));
+
+    tracing::info!(target:"test", "configure a dual stack node with an IPv6 and an IPv4 STUN server");
+    let stun_server_v6 = stun::testonly::Server::new().await;
+    let stun_server_v4 = stun::testonly::Server::new_ipv4().await;
+    let mut cfg = chain.make_config(rng);
+    let node_addr = **cfg.node_addr.as_ref().unwrap();
+    let ipv4_addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), node_addr.port());
+    cfg.additional_node_addrs = vec![tcp::ListenerAddr::new(ipv4_addr)];
+    cfg.validator.proxies = config::ValidatorProxies::Dynamic(vec![
+        stun_server_v6.addr().to_string(),
+        stun_server_v4.addr().to_string(),
+    ]);
+
+    tracing::info!(target:"test", "spawn a node and advertize AccountData with both addresses.");
+    let pm = start_pm(clock.clock(), TestDB::new(), cfg, chain.clone()).await;
+    let chain_info = peer_manager::testonly::make_chain_info(&chain, &[&pm.cfg]);
+    pm.set_chain_info(chain_info).await;
+    let got = pm.tier1_advertise_proxies(&clock.clock()).await.unwrap();
+    let want = vec![
+        PeerAddr { peer_id: pm.cfg.node_id(), addr: node_addr },
+        PeerAddr { peer_id: pm.cfg.node_id(), addr: ipv4_addr },
+    ];
+    assert_eq!(want, got.proxies);
+
+    tracing::info!(target:"test", "close the stun servers");
+    drop(pm);
+    stun_server_v6.close().await;
+    stun_server_v4.close().await;
+}
@@ -0,0 +1,275 @@
+//! Shaping of the outbound traffic.
+//!
+//! Every outgoing message is assigned a [MessageClass]. Each connection keeps a separate
+//! send queue per class and always sends the message of the highest priority class first.
+//! Additionally every class may have a bandwidth budget, shared by all the connections
+//! of the node: a class which has exhausted its budget is skipped until the budget refills.
+//! This way bulky sync traffic cannot saturate the uplink of a node, starving
+//! the consensus-critical messages (including the TIER1 traffic).
+
+use std::collections::{HashMap, VecDeque};
+
+use enum_map::{enum_map, EnumMap};
+use near_async::time::Instant;
+use parking_lot::Mutex;
+
+use crate::network_protocol::{PeerMessage, RoutedMessageBody};
+use crate::stats::metrics;
+
+use super::messages_limits::SingleMessageConfig;
+use super::token_bucket::TokenBucket;
+
+/// Class of an outgoing message, which determines its send priority.
+/// Variants are listed from the highest to the lowest priority.
+#[derive(
+    Clone,
+    Copy,
+    enum_map::Enum,
+    strum::Display,
+    strum::IntoStaticStr,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum MessageClass {
+    /// Messages needed for the block and chunk production: approvals, chunk endorsements,
+    /// state witnesses, chunk parts, new blocks. Also the small connection control messages.
+    Consensus,
+    /// Everything which doesn't fit in the other classes.
+    Default,
+    /// Responses to the sync requests of other nodes: blocks, headers, state parts, epoch
+    /// sync proofs. They are large and their delivery is not time critical.
+    Sync,
+}
+
+impl MessageClass {
+    /// Class of a message sent by this node on its own initiative.
+    pub(crate) fn of(msg: &PeerMessage) -> Self {
+        match msg {
+            PeerMessage::Tier1Handshake(_)
+            | PeerMessage::Tier2Handshake(_)
+            | PeerMessage::Tier3Handshake(_)
+            | PeerMessage::HandshakeFailure(..)
+            | PeerMessage::LastEdge(_)
+            | PeerMessage::Disconnect(_)
+            | PeerMessage::Block(_) => MessageClass::Consensus,
+            PeerMessage::BlockHeaders(_)
+            | PeerMessage::VersionedStateResponse(_)
+            | PeerMessage::EpochSyncResponse(_) => MessageClass::Sync,
+            PeerMessage::Routed(msg) => match &msg.msg.body {
+                RoutedMessageBody::BlockApproval(_)
+                | RoutedMessageBody::ChunkEndorsement(_)
+                | RoutedMessageBody::VersionedChunkEndorsement(_)
+                | RoutedMessageBody::PartialEncodedStateWitness(_)
+                | RoutedMessageBody::PartialEncodedStateWitnessForward(_)
+                | RoutedMessageBody::ChunkStateWitnessAck(_)
+                | RoutedMessageBody::VersionedPartialEncodedChunk(_)
+                | RoutedMessageBody::PartialEncodedChunkForward(_)
+                | RoutedMessageBody::PartialEncodedChunkRequest(_)
+                | RoutedMessageBody::PartialEncodedChunkResponse(_)
+                | RoutedMessageBody::ChunkContractAccesses(_)
+                | RoutedMessageBody::ContractCodeRequest(_)
+                | RoutedMessageBody::ContractCodeResponse(_)
+                | RoutedMessageBody::PartialEncodedContractDeploys(_) => MessageClass::Consensus,
+                _ => MessageClass::Default,
+            },
+            _ => MessageClass::Default,
+        }
+    }
+
+    /// Class of a message sent in response to a request from the peer.
+    /// Blocks sent in response to a BlockRequest are sync traffic,
+    /// unlike the blocks broadcasted by this node.
+    pub(crate) fn of_response(msg: &PeerMessage) -> Self {
+        match msg {
+            PeerMessage::Block(_) => MessageClass::Sync,
+            msg => Self::of(msg),
+        }
+    }
+}
+
+/// Outbound bandwidth configuration. The budgets are expressed in bytes:
+/// `maximum_size` is the burst size and `refill_rate` is the number of bytes per second.
+/// Classes without a budget are not limited.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Config {
+    pub budgets: HashMap<MessageClass, SingleMessageConfig>,
+}
+
+impl Config {
+    /// Validates this configuration. Returns all the errors found.
+    pub fn validate(&self) -> Result<(), Vec<(MessageClass, String)>> {
+        let mut errors = vec![];
+        for (class, config) in &self.budgets {
+            if let Err(err) = TokenBucket::validate_refill_rate(config.refill_rate) {
+                errors.push((*class, err.to_string()));
+            }
+            if config.maximum_size == 0 {
+                errors.push((*class, "maximum_size has to be >0".to_string()));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Bandwidth budgets shared by all the connections of the node.
+#[derive(Default)]
+pub struct Budgets {
+    buckets: EnumMap<MessageClass, Option<Mutex<TokenBucket>>>,
+}
+
+impl Budgets {
+    pub fn from_config(config: &Config, start_time: Instant) -> Self {
+        let mut buckets = enum_map! { _ => None };
+        // Configuration is assumed to be correct. Any failure to build a bucket is ignored.
+        for (class, budget) in &config.budgets {
+            let initial_size = budget.initial_size.unwrap_or(budget.maximum_size);
+            match TokenBucket::new(
+                initial_size,
+                budget.maximum_size,
+                budget.refill_rate,
+                start_time,
+            ) {
+                Ok(bucket) => buckets[*class] = Some(Mutex::new(bucket)),
+                Err(err) => {
+                    tracing::warn!(target: "network", "ignoring bandwidth budget for {class} due to an error ({err})")
+                }
+            }
+        }
+        Self { buckets }
+    }
+
+    /// Consumes the budget of `class` for sending a message of `bytes` bytes.
+    /// Messages larger than the bucket consume the whole bucket, so that they are not
+    /// blocked forever. Returns false if the budget is currently exhausted.
+    pub fn try_acquire(&self, class: MessageClass, bytes: usize, now: Instant) -> bool {
+        let Some(bucket) = &self.buckets[class] else { return true };
+        let mut bucket = bucket.lock();
+        let tokens = u32::try_from(bytes).unwrap_or(u32::MAX).min(bucket.maximum_size());
+        bucket.acquire(tokens, now)
+    }
+}
+
+/// Per-class queues of the messages waiting to be sent over a single connection.
+#[derive(Default)]
+pub(crate) struct SendQueue {
+    queues: EnumMap<MessageClass, VecDeque<Vec<u8>>>,
+}
+
+impl SendQueue {
+    pub fn push(&mut self, class: MessageClass, msg: Vec<u8>) {
+        metrics::PEER_SEND_QUEUE_BYTES.with_label_values(&[class.into()]).add(msg.len() as i64);
+        self.queues[class].push_back(msg);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queues.values().all(|q| q.is_empty())
+    }
+
+    /// Pops the first message of the highest priority class, which fits in its budget.
+    /// Returns None if the queue is empty or all the non-empty classes have exhausted
+    /// their budgets.
+    pub fn pop(&mut self, budgets: &Budgets, now: Instant) -> Option<(MessageClass, Vec<u8>)> {
+        for (class, queue) in &mut self.queues {
+            let Some(msg) = queue.front() else { continue };
+            if budgets.try_acquire(class, msg.len(), now) {
+                let msg = queue.pop_front().unwrap();
+                metrics::PEER_SEND_QUEUE_BYTES
+                    .with_label_values(&[class.into()])
+                    .sub(msg.len() as i64);
+                return Some((class, msg));
+            }
+        }
+        None
+    }
+}
+
+impl Drop for SendQueue {
+    fn drop(&mut self) {
+        for (class, queue) in &self.queues {
+            let bytes: usize = queue.iter().map(|msg| msg.len()).sum();
+            metrics::PEER_SEND_QUEUE_BYTES.with_label_values(&[class.into()]).sub(bytes as i64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_async::time;
+
+    fn msg(len: usize) -> Vec<u8> {
+        vec![0; len]
+    }
+
+    #[test]
+    fn pop_in_priority_order() {
+        let clock = time::FakeClock::default();
+        let budgets = Budgets::default();
+        let mut queue = SendQueue::default();
+        queue.push(MessageClass::Sync, msg(1));
+        queue.push(MessageClass::Default, msg(2));
+        queue.push(MessageClass::Consensus, msg(3));
+        queue.push(MessageClass::Sync, msg(4));
+        queue.push(MessageClass::Consensus, msg(5));
+        let got: Vec<_> = std::iter::from_fn(|| queue.pop(&budgets, clock.now()))
+            .map(|(class, msg)| (class, msg.len()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                (MessageClass::Consensus, 3),
+                (MessageClass::Consensus, 5),
+                (MessageClass::Default, 2),
+                (MessageClass::Sync, 1),
+                (MessageClass::Sync, 4),
+            ]
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn budget_exhausted() {
+        let clock = time::FakeClock::default();
+        let config = Config {
+            budgets: HashMap::from([(
+                MessageClass::Sync,
+                SingleMessageConfig::new(100, 100.0, None),
+            )]),
+        };
+        assert!(config.validate().is_ok());
+        let budgets = Budgets::from_config(&config, clock.now());
+        let mut queue = SendQueue::default();
+        // Messages larger than the bucket consume the whole bucket.
+        queue.push(MessageClass::Sync, msg(1000));
+        queue.push(MessageClass::Sync, msg(50));
+        assert_eq!(queue.pop(&budgets, clock.now()).unwrap(), (MessageClass::Sync, msg(1000)));
+        // Sync budget is exhausted, but Default is not limited.
+        queue.push(MessageClass::Default, msg(1000));
+        assert_eq!(queue.pop(&budgets, clock.now()).unwrap(), (MessageClass::Default, msg(1000)));
+        assert!(queue.pop(&budgets, clock.now()).is_none());
+        assert!(!queue.is_empty());
+
+        clock.advance(time::Duration::milliseconds(500));
+        assert_eq!(queue.pop(&budgets, clock.now()).unwrap(), (MessageClass::Sync, msg(50)));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn invalid_config() {
+        let config = Config {
+            budgets: HashMap::from([
+                (MessageClass::Sync, SingleMessageConfig::new(0, 100.0, None)),
+                (MessageClass::Default, SingleMessageConfig::new(10, -1.0, None)),
+            ]),
+        };
+        assert_eq!(config.validate().unwrap_err().len(), 2);
+    }
+}
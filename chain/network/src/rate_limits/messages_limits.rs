@@ -18,12 +18,22 @@ pub struct RateLimits {
 }
 
 impl RateLimits {
-    /// Creates all buckets as configured in `config`.
+    /// Creates all buckets as configured in `config`, ignoring any per peer class overrides.
     /// See also [TokenBucket::new].
     pub fn from_config(config: &Config, start_time: Instant) -> Self {
+        Self::from_config_for_peer_class(config, None, start_time)
+    }
+
+    /// Creates all buckets as configured in `config` for a peer belonging to `peer_class`.
+    /// Overrides defined for `peer_class` take precedence over the default rate limits.
+    pub fn from_config_for_peer_class(
+        config: &Config,
+        peer_class: Option<PeerClass>,
+        start_time: Instant,
+    ) -> Self {
         let mut buckets = enum_map! { _ => None };
         // Configuration is assumed to be correct. Any failure to build a bucket is ignored.
-        for (key, message_config) in &config.rate_limits {
+        for (key, message_config) in &config.effective_rate_limits(peer_class) {
             let initial_size = message_config.initial_size.unwrap_or(message_config.maximum_size);
             match TokenBucket::new(
                 initial_size,
@@ -47,21 +57,48 @@ impl RateLimits {
     /// * `message` - The network message to be checked
     /// * `now` - Current time
     ///
-    /// Returns `true` if the message should be allowed to continue. Otherwise,
-    /// if it should be rate limited, returns `false`.
-    pub fn is_allowed(&mut self, message: &PeerMessage, now: Instant) -> bool {
+    /// Returns `Ok` if the message should be allowed to continue. Otherwise, if it should be
+    /// rate limited, returns the key of the bucket which ran out of tokens.
+    pub fn check(
+        &mut self,
+        message: &PeerMessage,
+        now: Instant,
+    ) -> Result<(), RateLimitedPeerMessageKey> {
         if let Some((key, cost)) = get_key_and_token_cost(message) {
             if let Some(bucket) = &mut self.buckets[key] {
-                return bucket.acquire(cost, now);
+                if !bucket.acquire(cost, now) {
+                    return Err(key);
+                }
             }
         }
-        true
+        Ok(())
     }
 }
 
+/// Classes of peers which can be assigned rate limits different from the default ones.
+#[derive(
+    Clone,
+    Copy,
+    strum::Display,
+    strum::IntoStaticStr,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum PeerClass {
+    /// Peers connected over a TIER1 connection, i.e. validators and their proxies.
+    Tier1Validator,
+    /// Peers which declared themselves as archival nodes during the handshake.
+    Archival,
+    /// Inbound TIER2 peers which were not present in the peer store before connecting.
+    UnknownInbound,
+}
+
 /// Rate limit configuration for a single network message.
-#[derive(Clone, serde::Serialize, serde::Deserialize, Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Clone, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
 pub struct SingleMessageConfig {
     pub maximum_size: u32,
     pub refill_rate: f32,
@@ -75,17 +112,24 @@ impl SingleMessageConfig {
     }
 }
 
+/// Rate limits overrides, where a `None` value disables the rate limit for the given message.
+pub type RateLimitsOverrides = HashMap<RateLimitedPeerMessageKey, Option<SingleMessageConfig>>;
+
 /// Network messages rate limits configuration.
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Debug, PartialEq)]
 pub struct Config {
     pub rate_limits: HashMap<RateLimitedPeerMessageKey, SingleMessageConfig>,
+    /// Per peer class overrides of `rate_limits`.
+    pub peer_class_rate_limits: HashMap<PeerClass, RateLimitsOverrides>,
 }
 
 /// Struct to manage user defined overrides for [Config]. The key difference with the base struct
 /// is that in this values can be set to `None` to disable preset rate limits.
 #[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug)]
 pub struct OverrideConfig {
-    pub rate_limits: HashMap<RateLimitedPeerMessageKey, Option<SingleMessageConfig>>,
+    pub rate_limits: RateLimitsOverrides,
+    #[serde(default)]
+    pub peer_class_rate_limits: HashMap<PeerClass, RateLimitsOverrides>,
 }
 
 impl Config {
@@ -96,7 +140,12 @@ impl Config {
     /// If at least one error is present, returns the list of all configuration errors.  
     pub fn validate(&self) -> Result<(), Vec<(RateLimitedPeerMessageKey, TokenBucketError)>> {
         let mut errors = Vec::new();
-        for (key, message_config) in &self.rate_limits {
+        let peer_class_configs = self
+            .peer_class_rate_limits
+            .values()
+            .flat_map(|overrides| overrides.iter())
+            .filter_map(|(key, message_config)| message_config.as_ref().map(|c| (key, c)));
+        for (key, message_config) in self.rate_limits.iter().chain(peer_class_configs) {
             if let Err(err) = TokenBucket::validate_refill_rate(message_config.refill_rate) {
                 errors.push((*key, err));
             }
@@ -133,6 +182,29 @@ impl Config {
                 None => self.rate_limits.remove(&key),
             };
         }
+        for (peer_class, class_overrides) in overrides.peer_class_rate_limits {
+            self.peer_class_rate_limits.entry(peer_class).or_default().extend(class_overrides);
+        }
+    }
+
+    /// Returns the rate limits which apply to a peer of the given class: the default
+    /// `rate_limits` merged with the overrides defined for `peer_class`, if any.
+    pub fn effective_rate_limits(
+        &self,
+        peer_class: Option<PeerClass>,
+    ) -> HashMap<RateLimitedPeerMessageKey, SingleMessageConfig> {
+        let mut rate_limits = self.rate_limits.clone();
+        let Some(overrides) = peer_class.and_then(|class| self.peer_class_rate_limits.get(&class))
+        else {
+            return rate_limits;
+        };
+        for (key, message_config) in overrides {
+            match message_config {
+                Some(value) => rate_limits.insert(*key, value.clone()),
+                None => rate_limits.remove(key),
+            };
+        }
+        rate_limits
     }
 }
 
@@ -290,13 +362,13 @@ mod tests {
         // Test message that can't be rate limited.
         {
             let mut limits = RateLimits::default();
-            assert!(limits.is_allowed(&disconnect, now));
+            assert!(limits.check(&disconnect, now).is_ok());
         }
 
         // Test message that might be rate limited, but the system is not configured to do so.
         {
             let mut limits = RateLimits::default();
-            assert!(limits.is_allowed(&block_request, now));
+            assert!(limits.check(&block_request, now).is_ok());
         }
 
         // Test rate limited message with enough tokens.
@@ -304,7 +376,7 @@ mod tests {
             let mut limits = RateLimits::default();
             limits.buckets[RateLimitedPeerMessageKey::BlockRequest] =
                 Some(TokenBucket::new(1, 1, 0.0, now).unwrap());
-            assert!(limits.is_allowed(&block_request, now));
+            assert!(limits.check(&block_request, now).is_ok());
         }
 
         // Test rate limited message without enough tokens.
@@ -312,7 +384,7 @@ mod tests {
             let mut limits = RateLimits::default();
             limits.buckets[RateLimitedPeerMessageKey::BlockRequest] =
                 Some(TokenBucket::new(0, 1, 0.0, now).unwrap());
-            assert!(!limits.is_allowed(&block_request, now));
+            assert!(limits.check(&block_request, now).is_err());
         }
     }
 
@@ -470,16 +542,121 @@ mod tests {
         assert!(serde_json::from_value::<OverrideConfig>(json).is_err());
     }
 
+    #[test]
+    fn peer_class_rate_limits() {
+        use RateLimitedPeerMessageKey::*;
+        let mut config = Config::default();
+        config.rate_limits.insert(Block, SingleMessageConfig::new(5, 1.0, None));
+        config.rate_limits.insert(BlockApproval, SingleMessageConfig::new(5, 1.0, None));
+        let unknown_inbound =
+            config.peer_class_rate_limits.entry(PeerClass::UnknownInbound).or_default();
+        unknown_inbound.insert(Block, Some(SingleMessageConfig::new(1, 1.0, None)));
+        unknown_inbound.insert(Transaction, Some(SingleMessageConfig::new(2, 1.0, None)));
+        config
+            .peer_class_rate_limits
+            .entry(PeerClass::Tier1Validator)
+            .or_default()
+            .insert(BlockApproval, None);
+
+        // Peers without a class, or with a class without overrides, get the default limits.
+        assert_eq!(config.effective_rate_limits(None), config.rate_limits);
+        assert_eq!(config.effective_rate_limits(Some(PeerClass::Archival)), config.rate_limits);
+
+        let limits = config.effective_rate_limits(Some(PeerClass::UnknownInbound));
+        assert_eq!(limits.len(), 3);
+        assert_eq!(limits.get(&Block), Some(&SingleMessageConfig::new(1, 1.0, None)));
+        assert_eq!(limits.get(&BlockApproval), Some(&SingleMessageConfig::new(5, 1.0, None)));
+        assert_eq!(limits.get(&Transaction), Some(&SingleMessageConfig::new(2, 1.0, None)));
+
+        let limits = config.effective_rate_limits(Some(PeerClass::Tier1Validator));
+        assert_eq!(limits.len(), 1);
+        assert_eq!(limits.get(&BlockApproval), None);
+
+        let now = Instant::now();
+        let mut limits =
+            RateLimits::from_config_for_peer_class(&config, Some(PeerClass::UnknownInbound), now);
+        assert!(!limits.buckets[Block].as_mut().unwrap().acquire(2, now));
+        assert!(limits.buckets[Transaction].as_mut().unwrap().acquire(2, now));
+        let limits =
+            RateLimits::from_config_for_peer_class(&config, Some(PeerClass::Tier1Validator), now);
+        assert!(limits.buckets[BlockApproval].is_none());
+    }
+
+    #[test]
+    fn peer_class_rate_limits_validation() {
+        use RateLimitedPeerMessageKey::*;
+        let mut config = Config::default();
+        let overrides = config.peer_class_rate_limits.entry(PeerClass::Archival).or_default();
+        overrides.insert(Block, None);
+        assert!(config.validate().is_ok());
+
+        config
+            .peer_class_rate_limits
+            .entry(PeerClass::Archival)
+            .or_default()
+            .insert(BlockHeaders, Some(SingleMessageConfig::new(1, -3.0, None)));
+        assert_eq!(
+            config.validate(),
+            Err(vec![(BlockHeaders, TokenBucketError::InvalidRefillRate(-3.0))])
+        );
+    }
+
+    #[test]
+    fn check_reports_key() {
+        let block_request = PeerMessage::BlockRequest(CryptoHash::default());
+        let now = Instant::now();
+        let mut limits = RateLimits::default();
+        limits.buckets[RateLimitedPeerMessageKey::BlockRequest] =
+            Some(TokenBucket::new(1, 1, 0.0, now).unwrap());
+        assert_eq!(limits.check(&block_request, now), Ok(()));
+        assert_eq!(limits.check(&block_request, now), Err(RateLimitedPeerMessageKey::BlockRequest));
+    }
+
+    #[test]
+    fn apply_peer_class_overrides() {
+        use RateLimitedPeerMessageKey::*;
+        let mut config = Config::default();
+        config
+            .peer_class_rate_limits
+            .entry(PeerClass::UnknownInbound)
+            .or_default()
+            .insert(Block, Some(SingleMessageConfig::new(1, 1.0, None)));
+
+        let json = serde_json::json!({
+            "rate_limits": {},
+            "peer_class_rate_limits": {
+                "UnknownInbound": {
+                    "Transaction": {
+                        "maximum_size": 2,
+                        "refill_rate": 1.0,
+                    },
+                },
+                "Archival": {
+                    "BlockRequest": null,
+                },
+            },
+        });
+        let overrides: OverrideConfig =
+            serde_json::from_value(json).expect("deserializing OverrideConfig should work");
+        config.apply_overrides(overrides);
+
+        let unknown_inbound = &config.peer_class_rate_limits[&PeerClass::UnknownInbound];
+        assert_eq!(unknown_inbound.len(), 2);
+        assert_eq!(unknown_inbound[&Block], Some(SingleMessageConfig::new(1, 1.0, None)));
+        assert_eq!(unknown_inbound[&Transaction], Some(SingleMessageConfig::new(2, 1.0, None)));
+        assert_eq!(config.peer_class_rate_limits[&PeerClass::Archival][&BlockRequest], None);
+    }
+
     #[test]
     fn test_epoch_sync_rate_limit() {
         let config = Config::standard_preset();
         let clock = FakeClock::default();
         let mut rate_limits = RateLimits::from_config(&config, clock.now());
-        assert!(rate_limits.is_allowed(&PeerMessage::EpochSyncRequest, clock.now()));
-        assert!(!rate_limits.is_allowed(&PeerMessage::EpochSyncRequest, clock.now()));
+        assert!(rate_limits.check(&PeerMessage::EpochSyncRequest, clock.now()).is_ok());
+        assert!(rate_limits.check(&PeerMessage::EpochSyncRequest, clock.now()).is_err());
         clock.advance(Duration::seconds(1));
-        assert!(!rate_limits.is_allowed(&PeerMessage::EpochSyncRequest, clock.now()));
+        assert!(rate_limits.check(&PeerMessage::EpochSyncRequest, clock.now()).is_err());
         clock.advance(Duration::seconds(30));
-        assert!(rate_limits.is_allowed(&PeerMessage::EpochSyncRequest, clock.now()));
+        assert!(rate_limits.check(&PeerMessage::EpochSyncRequest, clock.now()).is_ok());
     }
 }
@@ -1,2 +1,3 @@
+pub mod bandwidth;
 pub mod messages_limits;
 pub mod token_bucket;
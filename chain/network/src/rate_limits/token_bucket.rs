@@ -80,6 +80,11 @@ impl TokenBucket {
         }
     }
 
+    /// Maximum number of tokens the bucket can hold.
+    pub fn maximum_size(&self) -> u32 {
+        self.maximum_size
+    }
+
     /// Refills the bucket with the right number of tokens according to
     /// the `refill_rate` and the new current time `now`.
     ///
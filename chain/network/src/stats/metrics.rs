@@ -160,6 +160,14 @@ pub(crate) static PEER_DATA_WRITE_BUFFER_SIZE: LazyLock<IntGaugeVec> = LazyLock:
     )
     .unwrap()
 });
+pub(crate) static PEER_SEND_QUEUE_BYTES: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "near_peer_send_queue_bytes",
+        "Total size of the messages waiting in the send queues of all connections, by message class",
+        &["class"],
+    )
+    .unwrap()
+});
 pub(crate) static PEER_MESSAGE_RECEIVED_BY_TYPE_BYTES: LazyLock<IntCounterVec> =
     LazyLock::new(|| {
         try_create_int_counter_vec(
@@ -203,6 +211,16 @@ pub(crate) static PEER_MESSAGE_RATE_LIMITED_BY_TYPE_TOTAL: LazyLock<IntCounterVe
         )
         .unwrap()
     });
+
+pub(crate) static PEER_MESSAGE_RATE_LIMITED_BY_PEER_CLASS_TOTAL: LazyLock<IntCounterVec> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "near_peer_message_rate_limited_by_peer_class_total",
+            "Number of messages dropped because rate limited by rate limit key and peer class",
+            &["key", "peer_class"],
+        )
+        .unwrap()
+    });
 pub(crate) static SYNC_ACCOUNTS_DATA: LazyLock<IntCounterVec> = LazyLock::new(|| {
     try_create_int_counter_vec(
         "near_sync_accounts_data",
@@ -312,6 +330,22 @@ pub(crate) static PEER_REACHABLE: LazyLock<IntGauge> = LazyLock::new(|| {
     )
     .unwrap()
 });
+pub(crate) static PEER_DISCOVERY_PEERS: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "near_peer_discovery_peers",
+        "Number of peers returned by the latest query of the peer discovery source",
+        &["source"],
+    )
+    .unwrap()
+});
+pub(crate) static PEER_DISCOVERY_FAILURES_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "near_peer_discovery_failures_total",
+        "Number of failed queries of the peer discovery source",
+        &["source"],
+    )
+    .unwrap()
+});
 static DROPPED_MESSAGE_COUNT: LazyLock<IntCounterVec> = LazyLock::new(|| {
     try_create_int_counter_vec(
         "near_dropped_message_by_type_and_reason_count",
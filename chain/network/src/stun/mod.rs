@@ -30,11 +30,14 @@ const QUERY_TIMEOUT: time::Duration = time::Duration::seconds(5);
 /// Sends a STUN BINDING request to `addr`.
 /// Returns the result of the query: the IP of this machine as perceived by the STUN server.
 /// It should be used to determine the public IP of this machine.
+/// The request is sent from a socket of the same IP family as `addr`, so that the
+/// returned IP belongs to that family as well.
 pub(crate) async fn query(
     clock: &time::Clock,
     addr: &SocketAddr,
 ) -> Result<std::net::IpAddr, Error> {
-    let socket = tokio::net::UdpSocket::bind("[::]:0").await?;
+    let bind_addr = if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = tokio::net::UdpSocket::bind(bind_addr).await?;
     socket.connect(addr).await?;
     let mut client = stun::client::ClientBuilder::new().with_conn(Arc::new(socket)).build()?;
     let mut msg = stun::message::Message::new();
@@ -22,11 +22,20 @@ pub(crate) struct Server {
 }
 
 impl Server {
-    /// Spawns a new STUN server on localhost interface.
+    /// Spawns a new STUN server on the IPv6 localhost interface.
     /// In fact a TURN server is spawned, which implements a superset
     /// of STUN functionality.
     pub async fn new() -> Self {
-        let server_conn = Arc::new(tokio::net::UdpSocket::bind("[::1]:0").await.unwrap());
+        Self::bind("[::1]:0").await
+    }
+
+    /// Spawns a new STUN server on the IPv4 localhost interface.
+    pub async fn new_ipv4() -> Self {
+        Self::bind("127.0.0.1:0").await
+    }
+
+    async fn bind(addr: &str) -> Self {
+        let server_conn = Arc::new(tokio::net::UdpSocket::bind(addr).await.unwrap());
         let server_addr = server_conn.local_addr().unwrap();
 
         Self {
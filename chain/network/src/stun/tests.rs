@@ -13,6 +13,16 @@ async fn test_query() {
     server.close().await;
 }
 
+#[tokio::test]
+async fn test_query_ipv4() {
+    init_test_logger();
+    let clock = time::FakeClock::default();
+    let server = stun::testonly::Server::new_ipv4().await;
+    let ip = stun::query(&clock.clock(), &server.addr()).await.unwrap();
+    assert_eq!(std::net::Ipv4Addr::LOCALHOST, ip);
+    server.close().await;
+}
+
 #[tokio::test]
 async fn test_lookup_host() {
     init_test_logger();
@@ -73,6 +73,12 @@ impl Socket {
         Self(socket)
     }
 
+    pub fn bind_v4() -> Self {
+        let socket = tokio::net::TcpSocket::new_v4().unwrap();
+        socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        Self(socket)
+    }
+
     pub async fn connect(self, peer_info: &PeerInfo, tier: Tier) -> Stream {
         // TODO(gprusak): this could replace Stream::connect,
         // however this means that we will have to replicate everything
@@ -0,0 +1,327 @@
+//! Export of the network topology known to a node (see `GetDebugStatus::NetworkTopology`)
+//! in formats understood by graph analysis tools.
+//!
+//! The exported graph contains a node per known peer and 2 kinds of edges:
+//! * "routing" edges, which are the edges of the routing graph (i.e. TIER2 connections),
+//!   annotated with the Ping/Pong latency wherever it was measured by the exporting node.
+//! * "tier1_proxy" edges, connecting the TIER1 validator nodes with their proxies.
+use near_primitives::network::PeerId;
+use near_primitives::types::AccountId;
+use near_primitives::views::NetworkTopologyView;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum::EnumString, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum TopologyFormat {
+    /// `NetworkTopologyView` serialized as JSON.
+    Json,
+    /// Graphviz DOT.
+    Dot,
+    GraphML,
+}
+
+impl TopologyFormat {
+    /// Content type of the rendered topology, to be used in HTTP responses.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Dot => "text/vnd.graphviz",
+            Self::GraphML => "application/xml",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Node {
+    accounts: Vec<AccountId>,
+    latency_ms: Option<f64>,
+}
+
+struct Edge<'a> {
+    peer0: &'a PeerId,
+    peer1: &'a PeerId,
+    kind: &'static str,
+    nonce: Option<u64>,
+    latency_ms: Option<f64>,
+    addr: Option<&'a str>,
+}
+
+/// Graph extracted from the NetworkTopologyView, with deterministic order of nodes and edges.
+struct Graph<'a> {
+    my_peer_id: &'a PeerId,
+    nodes: BTreeMap<&'a PeerId, Node>,
+    edges: Vec<Edge<'a>>,
+}
+
+fn micros_to_ms(micros: u64) -> f64 {
+    micros as f64 / 1000.
+}
+
+impl<'a> Graph<'a> {
+    fn new(view: &'a NetworkTopologyView) -> Self {
+        let mut nodes = BTreeMap::<_, Node>::new();
+        nodes.entry(&view.my_peer_id).or_default();
+        let mut edges = vec![];
+        for e in &view.edges {
+            nodes.entry(&e.peer0).or_default();
+            nodes.entry(&e.peer1).or_default();
+            edges.push(Edge {
+                peer0: &e.peer0,
+                peer1: &e.peer1,
+                kind: "routing",
+                nonce: Some(e.nonce),
+                latency_ms: e.latency_micros.map(micros_to_ms),
+                addr: None,
+            });
+        }
+        for data in &view.tier1_proxies {
+            nodes.entry(&data.peer_id).or_default();
+            for proxy in &data.proxies {
+                nodes.entry(&proxy.peer_id).or_default();
+                // A validator can be its own proxy, which is not interesting as an edge.
+                if proxy.peer_id == data.peer_id {
+                    continue;
+                }
+                edges.push(Edge {
+                    peer0: &data.peer_id,
+                    peer1: &proxy.peer_id,
+                    kind: "tier1_proxy",
+                    nonce: None,
+                    latency_ms: None,
+                    addr: Some(&proxy.addr),
+                });
+            }
+        }
+        for a in &view.accounts {
+            nodes.entry(&a.peer_id).or_default().accounts.push(a.account_id.clone());
+        }
+        for l in &view.latencies {
+            nodes.entry(&l.peer_id).or_default().latency_ms = Some(micros_to_ms(l.latency_micros));
+        }
+        for node in nodes.values_mut() {
+            node.accounts.sort();
+        }
+        edges.sort_by(|a, b| (a.kind, a.peer0, a.peer1).cmp(&(b.kind, b.peer0, b.peer1)));
+        Self { my_peer_id: &view.my_peer_id, nodes, edges }
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn join_accounts(accounts: &[AccountId]) -> String {
+    accounts.iter().map(|a| a.as_str()).collect::<Vec<_>>().join(",")
+}
+
+fn render_dot(g: &Graph) -> String {
+    let mut out = String::new();
+    writeln!(out, "graph near {{").unwrap();
+    for (peer_id, node) in &g.nodes {
+        let mut label = peer_id.to_string();
+        for account in &node.accounts {
+            write!(label, "\n{account}").unwrap();
+        }
+        if let Some(latency_ms) = node.latency_ms {
+            write!(label, "\nrtt: {latency_ms:.3}ms").unwrap();
+        }
+        let style = if *peer_id == g.my_peer_id { ", style=filled" } else { "" };
+        writeln!(
+            out,
+            "  \"{}\" [label=\"{}\"{style}];",
+            escape_dot(&peer_id.to_string()),
+            escape_dot(&label)
+        )
+        .unwrap();
+    }
+    for e in &g.edges {
+        let mut attrs = vec![format!("kind=\"{}\"", e.kind)];
+        if let Some(nonce) = e.nonce {
+            attrs.push(format!("nonce={nonce}"));
+        }
+        if let Some(latency_ms) = e.latency_ms {
+            attrs.push(format!("latency_ms={latency_ms:.3}"));
+            attrs.push(format!("label=\"{latency_ms:.3}ms\""));
+        }
+        if let Some(addr) = e.addr {
+            attrs.push(format!("addr=\"{}\"", escape_dot(addr)));
+            attrs.push("style=dashed".to_string());
+        }
+        writeln!(
+            out,
+            "  \"{}\" -- \"{}\" [{}];",
+            escape_dot(&e.peer0.to_string()),
+            escape_dot(&e.peer1.to_string()),
+            attrs.join(", ")
+        )
+        .unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+fn render_graphml(g: &Graph) -> String {
+    let mut out = String::new();
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(out, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#).unwrap();
+    for (id, for_, name, ty) in [
+        ("accounts", "node", "accounts", "string"),
+        ("is_me", "node", "is_me", "boolean"),
+        ("rtt_ms", "node", "rtt_ms", "double"),
+        ("kind", "edge", "kind", "string"),
+        ("nonce", "edge", "nonce", "long"),
+        ("latency_ms", "edge", "latency_ms", "double"),
+        ("addr", "edge", "addr", "string"),
+    ] {
+        writeln!(out, r#"  <key id="{id}" for="{for_}" attr.name="{name}" attr.type="{ty}"/>"#)
+            .unwrap();
+    }
+    writeln!(out, r#"  <graph id="near" edgedefault="undirected">"#).unwrap();
+    for (peer_id, node) in &g.nodes {
+        writeln!(out, r#"    <node id="{}">"#, escape_xml(&peer_id.to_string())).unwrap();
+        if !node.accounts.is_empty() {
+            let accounts = escape_xml(&join_accounts(&node.accounts));
+            writeln!(out, r#"      <data key="accounts">{accounts}</data>"#).unwrap();
+        }
+        writeln!(out, r#"      <data key="is_me">{}</data>"#, *peer_id == g.my_peer_id).unwrap();
+        if let Some(latency_ms) = node.latency_ms {
+            writeln!(out, r#"      <data key="rtt_ms">{latency_ms}</data>"#).unwrap();
+        }
+        writeln!(out, "    </node>").unwrap();
+    }
+    for e in &g.edges {
+        writeln!(
+            out,
+            r#"    <edge source="{}" target="{}">"#,
+            escape_xml(&e.peer0.to_string()),
+            escape_xml(&e.peer1.to_string())
+        )
+        .unwrap();
+        writeln!(out, r#"      <data key="kind">{}</data>"#, e.kind).unwrap();
+        if let Some(nonce) = e.nonce {
+            writeln!(out, r#"      <data key="nonce">{nonce}</data>"#).unwrap();
+        }
+        if let Some(latency_ms) = e.latency_ms {
+            writeln!(out, r#"      <data key="latency_ms">{latency_ms}</data>"#).unwrap();
+        }
+        if let Some(addr) = e.addr {
+            writeln!(out, r#"      <data key="addr">{}</data>"#, escape_xml(addr)).unwrap();
+        }
+        writeln!(out, "    </edge>").unwrap();
+    }
+    writeln!(out, "  </graph>").unwrap();
+    writeln!(out, "</graphml>").unwrap();
+    out
+}
+
+/// Renders the topology in the given format.
+pub fn render(view: &NetworkTopologyView, format: TopologyFormat) -> String {
+    match format {
+        TopologyFormat::Json => serde_json::to_string_pretty(view).unwrap(),
+        TopologyFormat::Dot => render_dot(&Graph::new(view)),
+        TopologyFormat::GraphML => render_graphml(&Graph::new(view)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, TopologyFormat};
+    use crate::network_protocol::testonly as data;
+    use crate::testonly::make_rng;
+    use near_primitives::views::{
+        NetworkTopologyView, PeerLatencyView, TopologyAccountView, TopologyEdgeView,
+        TopologyProxyView, TopologyTier1ProxiesView,
+    };
+    use std::str::FromStr as _;
+
+    fn make_view() -> NetworkTopologyView {
+        let mut rng = make_rng(921853233);
+        let rng = &mut rng;
+        let peers: Vec<_> = (0..3).map(|_| data::make_peer_id(rng)).collect();
+        NetworkTopologyView {
+            my_peer_id: peers[0].clone(),
+            edges: vec![
+                TopologyEdgeView {
+                    peer0: peers[0].clone(),
+                    peer1: peers[1].clone(),
+                    nonce: 3,
+                    latency_micros: Some(1500),
+                },
+                TopologyEdgeView {
+                    peer0: peers[1].clone(),
+                    peer1: peers[2].clone(),
+                    nonce: 5,
+                    latency_micros: None,
+                },
+            ],
+            accounts: vec![TopologyAccountView {
+                account_id: "validator.near".parse().unwrap(),
+                peer_id: peers[2].clone(),
+            }],
+            tier1_proxies: vec![TopologyTier1ProxiesView {
+                account_key: data::make_secret_key(rng).public_key(),
+                peer_id: peers[2].clone(),
+                proxies: vec![
+                    TopologyProxyView { peer_id: peers[1].clone(), addr: "1.2.3.4:24567".into() },
+                    TopologyProxyView { peer_id: peers[2].clone(), addr: "[::1]:24567".into() },
+                ],
+            }],
+            latencies: vec![PeerLatencyView {
+                peer_id: peers[2].clone(),
+                latency_micros: 4000,
+                measured_at: 0,
+            }],
+        }
+    }
+
+    #[test]
+    fn parse_format() {
+        for format in [TopologyFormat::Json, TopologyFormat::Dot, TopologyFormat::GraphML] {
+            assert_eq!(format, TopologyFormat::from_str(&format.to_string()).unwrap());
+        }
+        assert_eq!(TopologyFormat::GraphML, TopologyFormat::from_str("graphml").unwrap());
+    }
+
+    #[test]
+    fn render_json() {
+        let view = make_view();
+        let got: NetworkTopologyView =
+            serde_json::from_str(&render(&view, TopologyFormat::Json)).unwrap();
+        assert_eq!(view, got);
+    }
+
+    #[test]
+    fn render_dot() {
+        let view = make_view();
+        let dot = render(&view, TopologyFormat::Dot);
+        assert!(dot.starts_with("graph near {"));
+        // 3 nodes, 2 routing edges and 1 proxy edge (self-proxy is skipped).
+        assert_eq!(3, dot.lines().filter(|l| l.contains("[label=")).count());
+        assert_eq!(3, dot.lines().filter(|l| l.contains(" -- ")).count());
+        assert!(dot.contains("latency_ms=1.500"));
+        assert!(dot.contains("validator.near"));
+        assert!(dot.contains("rtt: 4.000ms"));
+        assert!(dot.contains(r#"kind="tier1_proxy", addr="1.2.3.4:24567""#));
+    }
+
+    #[test]
+    fn render_graphml() {
+        let view = make_view();
+        let xml = render(&view, TopologyFormat::GraphML);
+        assert_eq!(3, xml.matches("<node ").count());
+        assert_eq!(3, xml.matches("<edge ").count());
+        assert_eq!(1, xml.matches(r#"<data key="is_me">true</data>"#).count());
+        assert!(xml.contains(r#"<data key="latency_ms">1.5</data>"#));
+        assert!(xml.contains(r#"<data key="accounts">validator.near</data>"#));
+        assert!(xml.contains(r#"<data key="addr">1.2.3.4:24567</data>"#));
+    }
+}
@@ -9,8 +9,10 @@ pub use crate::network_protocol::{
     PartialEncodedChunkResponseMsg, PeerChainInfoV2, PeerInfo, SnapshotHostInfo, StateResponseInfo,
     StateResponseInfoV1, StateResponseInfoV2,
 };
+use crate::rate_limits::bandwidth::MessageClass;
 use crate::routing::routing_table_view::RoutingTableInfo;
 pub use crate::state_sync::StateSyncResponse;
+use enum_map::EnumMap;
 use near_async::messaging::{AsyncSender, Sender};
 use near_async::time;
 use near_crypto::PublicKey;
@@ -395,6 +397,8 @@ pub struct ConnectedPeerInfo {
     pub peer_type: PeerType,
     /// Nonce used for the connection with the peer.
     pub nonce: u64,
+    /// Number of bytes waiting to be sent to the peer, by message class.
+    pub send_queue_bytes: EnumMap<MessageClass, u64>,
 }
 
 #[derive(Debug, Clone, actix::MessageResponse, PartialEq, Eq)]
@@ -402,6 +402,76 @@ impl From<NearActions> for Vec<crate::models::Operation> {
                     );
                 }
 
+                near_primitives::transaction::Action::DeployContractWithInit(action) => {
+                    let initiate_deploy_contract_with_init_operation_id =
+                        crate::models::OperationIdentifier::new(&operations);
+                    operations.push(
+                        validated_operations::InitiateDeployContractWithInitOperation {
+                            sender_account: sender_account_identifier.clone(),
+                        }
+                        .into_operation(initiate_deploy_contract_with_init_operation_id.clone()),
+                    );
+
+                    let action = *action;
+                    operations.push(
+                        validated_operations::DeployContractWithInitOperation {
+                            account: receiver_account_identifier.clone(),
+                            code: action.code,
+                            method_name: action.method_name,
+                            args: action.args,
+                            attached_gas: action.gas,
+                        }
+                        .into_related_operation(
+                            crate::models::OperationIdentifier::new(&operations),
+                            vec![initiate_deploy_contract_with_init_operation_id],
+                        ),
+                    );
+                }
+
+                near_primitives::transaction::Action::DeployGlobalContract(action) => {
+                    let initiate_deploy_global_contract_operation_id =
+                        crate::models::OperationIdentifier::new(&operations);
+                    operations.push(
+                        validated_operations::InitiateDeployGlobalContractOperation {
+                            sender_account: sender_account_identifier.clone(),
+                        }
+                        .into_operation(initiate_deploy_global_contract_operation_id.clone()),
+                    );
+
+                    operations.push(
+                        validated_operations::DeployGlobalContractOperation {
+                            account: receiver_account_identifier.clone(),
+                            code: action.code,
+                        }
+                        .into_related_operation(
+                            crate::models::OperationIdentifier::new(&operations),
+                            vec![initiate_deploy_global_contract_operation_id],
+                        ),
+                    );
+                }
+
+                near_primitives::transaction::Action::UseGlobalContract(action) => {
+                    let initiate_use_global_contract_operation_id =
+                        crate::models::OperationIdentifier::new(&operations);
+                    operations.push(
+                        validated_operations::InitiateUseGlobalContractOperation {
+                            sender_account: sender_account_identifier.clone(),
+                        }
+                        .into_operation(initiate_use_global_contract_operation_id.clone()),
+                    );
+
+                    operations.push(
+                        validated_operations::UseGlobalContractOperation {
+                            account: receiver_account_identifier.clone(),
+                            code_hash: action.code_hash,
+                        }
+                        .into_related_operation(
+                            crate::models::OperationIdentifier::new(&operations),
+                            vec![initiate_use_global_contract_operation_id],
+                        ),
+                    );
+                }
+
                 near_primitives::transaction::Action::FunctionCall(action) => {
                     let attached_amount = crate::models::Amount::from_yoctonear(action.deposit);
 
@@ -694,6 +764,70 @@ impl TryFrom<Vec<crate::models::Operation>> for NearActions {
                         .into(),
                     )
                 }
+                crate::models::OperationType::DeployContractWithInit => {
+                    let deploy_contract_with_init_operation =
+                        validated_operations::DeployContractWithInitOperation::try_from(
+                            tail_operation,
+                        )?;
+                    receiver_account_id.try_set(&deploy_contract_with_init_operation.account)?;
+
+                    let initiate_deploy_contract_with_init_operation =
+                        validated_operations::InitiateDeployContractWithInitOperation::try_from_option(
+                            operations.next(),
+                        )?;
+                    sender_account_id
+                        .try_set(&initiate_deploy_contract_with_init_operation.sender_account)?;
+
+                    actions.push(
+                        near_primitives::transaction::DeployContractWithInitAction {
+                            code: deploy_contract_with_init_operation.code,
+                            method_name: deploy_contract_with_init_operation.method_name,
+                            args: deploy_contract_with_init_operation.args,
+                            gas: deploy_contract_with_init_operation.attached_gas,
+                        }
+                        .into(),
+                    )
+                }
+                crate::models::OperationType::DeployGlobalContract => {
+                    let deploy_global_contract_operation =
+                        validated_operations::DeployGlobalContractOperation::try_from(
+                            tail_operation,
+                        )?;
+                    receiver_account_id.try_set(&deploy_global_contract_operation.account)?;
+
+                    let initiate_deploy_global_contract_operation =
+                        validated_operations::InitiateDeployGlobalContractOperation::try_from_option(
+                            operations.next(),
+                        )?;
+                    sender_account_id
+                        .try_set(&initiate_deploy_global_contract_operation.sender_account)?;
+
+                    actions.push(
+                        near_primitives::transaction::DeployGlobalContractAction {
+                            code: deploy_global_contract_operation.code,
+                        }
+                        .into(),
+                    )
+                }
+                crate::models::OperationType::UseGlobalContract => {
+                    let use_global_contract_operation =
+                        validated_operations::UseGlobalContractOperation::try_from(tail_operation)?;
+                    receiver_account_id.try_set(&use_global_contract_operation.account)?;
+
+                    let initiate_use_global_contract_operation =
+                        validated_operations::InitiateUseGlobalContractOperation::try_from_option(
+                            operations.next(),
+                        )?;
+                    sender_account_id
+                        .try_set(&initiate_use_global_contract_operation.sender_account)?;
+
+                    actions.push(
+                        near_primitives::transaction::UseGlobalContractAction {
+                            code_hash: use_global_contract_operation.code_hash,
+                        }
+                        .into(),
+                    )
+                }
                 crate::models::OperationType::FunctionCall => {
                     let function_call_operation =
                         validated_operations::FunctionCallOperation::try_from(tail_operation)?;
@@ -793,6 +927,9 @@ impl TryFrom<Vec<crate::models::Operation>> for NearActions {
                 | crate::models::OperationType::InitiateAddKey
                 | crate::models::OperationType::InitiateDeleteKey
                 | crate::models::OperationType::InitiateDeployContract
+                | crate::models::OperationType::InitiateDeployContractWithInit
+                | crate::models::OperationType::InitiateDeployGlobalContract
+                | crate::models::OperationType::InitiateUseGlobalContract
                 | crate::models::OperationType::InitiateFunctionCall
                 | crate::models::OperationType::SignedDelegateAction
                 | crate::models::OperationType::InitiateSignedDelegateAction
@@ -1033,6 +1170,24 @@ mod tests {
             code: b"binary-data".to_vec(),
         }
         .into()];
+        let deploy_contract_with_init_actions =
+            vec![near_primitives::transaction::DeployContractWithInitAction {
+                code: b"binary-data".to_vec(),
+                method_name: "migrate".to_string(),
+                args: b"args".to_vec(),
+                gas: 100500,
+            }
+            .into()];
+        let deploy_global_contract_actions =
+            vec![near_primitives::transaction::DeployGlobalContractAction {
+                code: b"binary-data".to_vec(),
+            }
+            .into()];
+        let use_global_contract_actions =
+            vec![near_primitives::transaction::UseGlobalContractAction {
+                code_hash: near_primitives::hash::hash(b"binary-data"),
+            }
+            .into()];
         let function_call_without_balance_actions =
             vec![near_primitives::transaction::FunctionCallAction {
                 method_name: "method-name".parse().unwrap(),
@@ -1075,6 +1230,9 @@ mod tests {
             delete_key_actions,
             transfer_actions,
             deploy_contract_actions,
+            deploy_contract_with_init_actions,
+            deploy_global_contract_actions,
+            use_global_contract_actions,
             function_call_without_balance_actions,
             function_call_with_balance_actions,
             wallet_style_create_account_actions,
@@ -100,6 +100,7 @@ pub(crate) async fn get_fungible_token_balance_for_account(
         account_id: near_account_id::AccountId::from_str(contract_address)?,
         method_name,
         args: args.into(),
+        function_gas_profile: false,
     };
     let query_response = view_client_addr
         .send(near_client::Query { block_reference, request }.with_span_context())
@@ -4,17 +4,23 @@ pub(crate) use self::delegate_action::DelegateActionOperation;
 pub(crate) use self::delete_account::DeleteAccountOperation;
 pub(crate) use self::delete_key::DeleteKeyOperation;
 pub(crate) use self::deploy_contract::DeployContractOperation;
+pub(crate) use self::deploy_contract_with_init::DeployContractWithInitOperation;
+pub(crate) use self::deploy_global_contract::DeployGlobalContractOperation;
 pub(crate) use self::function_call::FunctionCallOperation;
 pub(crate) use self::initiate_add_key::InitiateAddKeyOperation;
 pub(crate) use self::initiate_create_account::InitiateCreateAccountOperation;
 pub(crate) use self::initiate_delete_account::InitiateDeleteAccountOperation;
 pub(crate) use self::initiate_delete_key::InitiateDeleteKeyOperation;
 pub(crate) use self::initiate_deploy_contract::InitiateDeployContractOperation;
+pub(crate) use self::initiate_deploy_contract_with_init::InitiateDeployContractWithInitOperation;
+pub(crate) use self::initiate_deploy_global_contract::InitiateDeployGlobalContractOperation;
 pub(crate) use self::initiate_function_call::InitiateFunctionCallOperation;
+pub(crate) use self::initiate_use_global_contract::InitiateUseGlobalContractOperation;
 pub(crate) use self::intitiate_signed_delegate_action::InitiateSignedDelegateActionOperation;
 pub(crate) use self::refund_delete_account::RefundDeleteAccountOperation;
 pub(crate) use self::stake::StakeOperation;
 pub(crate) use self::transfer::TransferOperation;
+pub(crate) use self::use_global_contract::UseGlobalContractOperation;
 
 mod add_key;
 mod create_account;
@@ -22,6 +28,8 @@ pub mod delegate_action;
 mod delete_account;
 mod delete_key;
 mod deploy_contract;
+mod deploy_contract_with_init;
+mod deploy_global_contract;
 mod function_call;
 mod initiate_add_key;
 mod initiate_create_account;
@@ -29,12 +37,16 @@ pub mod initiate_delegate_action;
 mod initiate_delete_account;
 mod initiate_delete_key;
 mod initiate_deploy_contract;
+mod initiate_deploy_contract_with_init;
+mod initiate_deploy_global_contract;
 mod initiate_function_call;
+mod initiate_use_global_contract;
 pub mod intitiate_signed_delegate_action;
 mod refund_delete_account;
 pub mod signed_delegate_action;
 mod stake;
 mod transfer;
+mod use_global_contract;
 
 pub(crate) trait ValidatedOperation:
     TryFrom<crate::models::Operation, Error = crate::errors::ErrorKind>
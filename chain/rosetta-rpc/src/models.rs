@@ -743,6 +743,12 @@ pub(crate) enum OperationType {
     InitiateSignedDelegateAction,
     InitiateDelegateAction,
     FunctionCall,
+    InitiateDeployContractWithInit,
+    DeployContractWithInit,
+    InitiateDeployGlobalContract,
+    DeployGlobalContract,
+    InitiateUseGlobalContract,
+    UseGlobalContract,
 }
 
 #[derive(
@@ -819,6 +825,9 @@ pub(crate) struct OperationMetadata {
     /// Has to be specified for SIGNED_DELEGATE_ACTION operation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signature: Option<String>,
+    /// Has to be specified for USE_GLOBAL_CONTRACT operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<String>,
 }
 
 impl OperationMetadata {
@@ -392,7 +392,46 @@ pub fn default_orphan_state_witness_max_size() -> ByteSize {
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct ChunkDistributionNetworkConfig {
     pub enabled: bool,
+    /// URIs of the HTTP backend, used when no other `backend` is configured.
+    #[serde(default)]
     pub uris: ChunkDistributionUris,
+    /// Backend to push chunks to and pull chunks from, instead of the HTTP `uris`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<ChunkDistributionBackendConfig>,
+    /// Only publish the chunks produced by this node. Missing chunks are always requested over
+    /// the peer-to-peer network.
+    #[serde(default)]
+    pub publish_only: bool,
+}
+
+/// Backends of the Chunk Distribution Network other than a single pair of HTTP URIs.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChunkDistributionBackendConfig {
+    /// Chunks are files in a directory shared by co-located nodes. Putting the directory on a
+    /// memory backed filesystem such as `/dev/shm` shares the chunks through memory.
+    Filesystem {
+        path: PathBuf,
+        /// Chunks published longer than this ago are removed when a chunk is published.
+        #[serde(default = "default_chunk_distribution_filesystem_retention")]
+        #[serde(with = "near_time::serde_duration_as_std")]
+        retention: Duration,
+    },
+    /// Several HTTP endpoints serving the same chunks. Chunks are published to all of them, and
+    /// looked up from them in order until one has the chunk.
+    MultiEndpoint {
+        endpoints: Vec<ChunkDistributionUris>,
+        /// If set, a lookup that isn't answered within this delay is sent to the next endpoint
+        /// as well, and the first chunk returned wins. Otherwise the next endpoint is only tried
+        /// once the previous one fails or doesn't have the chunk.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[serde(with = "near_time::serde_opt_duration_as_std")]
+        hedge_delay: Option<Duration>,
+    },
+}
+
+pub fn default_chunk_distribution_filesystem_retention() -> Duration {
+    Duration::minutes(10)
 }
 
 /// URIs for the Chunk Distribution Network feature.
@@ -404,6 +443,14 @@ pub struct ChunkDistributionUris {
     pub set: String,
 }
 
+/// Config for shadow chunk validation.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ShadowChunkValidationConfig {
+    /// File to which a JSON line is appended for every shadow validated chunk, with the witness
+    /// size, the validation time and the validation result.
+    pub report_file: Option<PathBuf>,
+}
+
 /// ClientConfig where some fields can be updated at runtime.
 #[derive(Clone, serde::Serialize)]
 pub struct ClientConfig {
@@ -469,8 +516,9 @@ pub struct ClientConfig {
     pub block_header_fetch_horizon: BlockHeightDelta,
     /// Garbage collection configuration.
     pub gc: GCConfig,
-    /// Accounts that this client tracks.
-    pub tracked_accounts: Vec<AccountId>,
+    /// Accounts that this client tracks. Can be updated while the node is running, see
+    /// `TrackedConfig::Accounts` for when the update takes effect.
+    pub tracked_accounts: MutableConfigValue<Vec<AccountId>>,
     /// Track shards that should be tracked by given validator.
     pub tracked_shadow_validator: Option<AccountId>,
     /// Shards that this client tracks.
@@ -546,6 +594,12 @@ pub struct ClientConfig {
     /// which can cause extra load on the database. This option is not recommended for production use,
     /// as a large number of incoming witnesses could cause denial of service.
     pub save_latest_witnesses: bool,
+    /// Refuse to start a validator node when the signing ledger for its validator key is missing.
+    pub require_signing_ledger: bool,
+    /// If set, the node creates a state witness for every new chunk in the shards it tracks and
+    /// validates it, as a chunk validator would. Used to check a new binary against the chunks
+    /// of a live network. Also enabled by the `shadow_chunk_validation` cargo feature.
+    pub shadow_chunk_validation: Option<ShadowChunkValidationConfig>,
 }
 
 impl ClientConfig {
@@ -601,7 +655,7 @@ impl ClientConfig {
             doosmslug_step_period: Duration::milliseconds(100),
             block_header_fetch_horizon: 50,
             gc: GCConfig { gc_blocks_limit: 100, ..GCConfig::default() },
-            tracked_accounts: vec![],
+            tracked_accounts: MutableConfigValue::new(vec![], "tracked_accounts"),
             tracked_shadow_validator: None,
             tracked_shards: vec![],
             tracked_shard_schedule: vec![],
@@ -634,6 +688,8 @@ impl ClientConfig {
             orphan_state_witness_pool_size: default_orphan_state_witness_pool_size(),
             orphan_state_witness_max_size: default_orphan_state_witness_max_size(),
             save_latest_witnesses: false,
+            require_signing_ledger: false,
+            shadow_chunk_validation: None,
         }
     }
 }
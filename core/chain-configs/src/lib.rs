@@ -19,9 +19,10 @@ pub use client_config::{
     default_sync_height_threshold, default_sync_max_block_requests, default_sync_step_period,
     default_transaction_pool_size_limit, default_trie_viewer_state_size_limit,
     default_tx_routing_height_horizon, default_view_client_threads,
-    default_view_client_throttle_period, ChunkDistributionNetworkConfig, ChunkDistributionUris,
-    ClientConfig, DumpConfig, EpochSyncConfig, ExternalStorageConfig, ExternalStorageLocation,
-    GCConfig, LogSummaryStyle, ReshardingConfig, ReshardingHandle, StateSyncConfig, SyncConfig,
+    default_view_client_throttle_period, ChunkDistributionBackendConfig,
+    ChunkDistributionNetworkConfig, ChunkDistributionUris, ClientConfig, DumpConfig,
+    EpochSyncConfig, ExternalStorageConfig, ExternalStorageLocation, GCConfig, LogSummaryStyle,
+    ReshardingConfig, ReshardingHandle, ShadowChunkValidationConfig, StateSyncConfig, SyncConfig,
     DEFAULT_GC_NUM_EPOCHS_TO_KEEP, DEFAULT_STATE_SYNC_NUM_CONCURRENT_REQUESTS_EXTERNAL,
     DEFAULT_STATE_SYNC_NUM_CONCURRENT_REQUESTS_ON_CATCHUP_EXTERNAL, MIN_GC_NUM_EPOCHS_TO_KEEP,
     TEST_STATE_SYNC_TIMEOUT,
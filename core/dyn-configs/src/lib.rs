@@ -1,6 +1,7 @@
 #![doc = include_str!("../README.md")]
 
 use near_chain_configs::{UpdateableClientConfig, UpdateableValidatorSigner};
+use near_network::config::UpdateableNetworkConfig;
 use near_o11y::log_config::LogConfig;
 use near_time::Clock;
 use std::path::PathBuf;
@@ -16,6 +17,8 @@ pub struct UpdateableConfigs {
     pub log_config: Option<LogConfig>,
     /// Contents of the `config.json` corresponding to the mutable fields of `ClientConfig`.
     pub client_config: Option<UpdateableClientConfig>,
+    /// Contents of the `config.json` corresponding to the mutable fields of `NetworkConfig`.
+    pub network_config: Option<UpdateableNetworkConfig>,
     /// Validator key hot loaded from file.
     /// `None` means that the validator key existence could not be determined.
     /// `Some(None)` means that it was determined that the validator key does not exist.
@@ -38,6 +41,8 @@ pub enum UpdateableConfigLoaderError {
     OpenAndRead { file: PathBuf, err: std::io::Error },
     #[error("Can't open or read the config file {file:?}: {err:?}")]
     ConfigFileError { file: PathBuf, err: anyhow::Error },
+    #[error("Invalid network config in {file:?}: {err:?}")]
+    NetworkConfigError { file: PathBuf, err: anyhow::Error },
     #[error("Can't open or read the validator key file {file:?}: {err:?}")]
     ValidatorKeyFileError { file: PathBuf, err: anyhow::Error },
     #[error("One or multiple dynamic config files reload errors {0:?}")]
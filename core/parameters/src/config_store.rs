@@ -52,6 +52,12 @@ static CONFIG_DIFFS: &[(ProtocolVersion, &str)] = &[
     // Fix wasm_yield_resume_byte and relax congestion control.
     (73, include_config!("73.yaml")),
     (129, include_config!("129.yaml")),
+    // Contract preparation V3 with the bulk memory and multi-value proposals.
+    (147, include_config!("147.yaml")),
+    // Introduce ECDSA and Schnorr signature verification host functions.
+    (148, include_config!("148.yaml")),
+    // Introduce bounded storage iteration.
+    (149, include_config!("149.yaml")),
 ];
 
 /// Testnet parameters for versions <= 29, which (incorrectly) differed from mainnet parameters
@@ -190,6 +196,17 @@ impl RuntimeConfigStore {
         Self::with_one_config(RuntimeConfig::free())
     }
 
+    /// Enables `execution_tracing` in the configs of all protocol versions.
+    ///
+    /// Only meant for debugging tools and sandbox nodes, the traced receipts are applied exactly
+    /// as they are on chain but contracts are compiled and cached separately.
+    pub fn with_execution_tracing(mut self) -> Self {
+        for config in self.store.values_mut() {
+            Arc::make_mut(&mut Arc::make_mut(config).wasm_config).execution_tracing = true;
+        }
+        self
+    }
+
     /// Returns a `RuntimeConfig` for the corresponding protocol version.
     pub fn get_config(&self, protocol_version: ProtocolVersion) -> &Arc<RuntimeConfig> {
         self.store
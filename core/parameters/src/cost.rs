@@ -151,6 +151,12 @@ impl ExtCostsConfig {
             ExtCosts::bls12381_p1_decompress_element => SAFETY_MULTIPLIER * 27_000_000_000,
             ExtCosts::bls12381_p2_decompress_base => SAFETY_MULTIPLIER * 500_000_000,
             ExtCosts::bls12381_p2_decompress_element => SAFETY_MULTIPLIER * 55_000_000_000,
+            ExtCosts::secp256k1_verify_base => SAFETY_MULTIPLIER * 93_000_000_000,
+            ExtCosts::secp256r1_verify_base => SAFETY_MULTIPLIER * 150_000_000_000,
+            ExtCosts::bip340_verify_base => SAFETY_MULTIPLIER * 95_000_000_000,
+            ExtCosts::storage_iter_v2_base => SAFETY_MULTIPLIER * 18_785_615_250,
+            ExtCosts::storage_iter_v2_key => SAFETY_MULTIPLIER * 10_734_637_284,
+            ExtCosts::storage_iter_v2_byte => SAFETY_MULTIPLIER * 10_317_511,
             // TODO(yield/resume): replicate fees here after estimation
             ExtCosts::yield_create_base => 300_000_000_000_000,
             ExtCosts::yield_create_byte => 300_000_000_000_000,
@@ -272,6 +278,12 @@ pub enum ExtCosts {
     bls12381_p2_decompress_element = 82,
     storage_large_read_overhead_base = 83,
     storage_large_read_overhead_byte = 84,
+    secp256k1_verify_base = 85,
+    secp256r1_verify_base = 86,
+    bip340_verify_base = 87,
+    storage_iter_v2_base = 88,
+    storage_iter_v2_key = 89,
+    storage_iter_v2_byte = 90,
 }
 
 // Type of an action, used in fees logic.
@@ -307,6 +319,8 @@ pub enum ActionCosts {
     new_data_receipt_base = 13,
     new_data_receipt_byte = 14,
     delegate = 15,
+    /// Charged on top of the deploy and function call costs of a deploy with init action.
+    deploy_contract_with_init = 16,
 }
 
 impl ExtCosts {
@@ -409,6 +423,12 @@ impl ExtCosts {
             ExtCosts::bls12381_p1_decompress_element => Parameter::WasmBls12381P1DecompressElement,
             ExtCosts::bls12381_p2_decompress_base => Parameter::WasmBls12381P2DecompressBase,
             ExtCosts::bls12381_p2_decompress_element => Parameter::WasmBls12381P2DecompressElement,
+            ExtCosts::secp256k1_verify_base => Parameter::WasmSecp256k1VerifyBase,
+            ExtCosts::secp256r1_verify_base => Parameter::WasmSecp256r1VerifyBase,
+            ExtCosts::bip340_verify_base => Parameter::WasmBip340VerifyBase,
+            ExtCosts::storage_iter_v2_base => Parameter::WasmStorageIterV2Base,
+            ExtCosts::storage_iter_v2_key => Parameter::WasmStorageIterV2Key,
+            ExtCosts::storage_iter_v2_byte => Parameter::WasmStorageIterV2Byte,
         }
     }
 }
@@ -532,6 +552,11 @@ impl RuntimeFeesConfig {
                     send_not_sir: 200_000_000_000,
                     execution: 200_000_000_000,
                 },
+                ActionCosts::deploy_contract_with_init => Fee {
+                    send_sir: 200_000_000_000,
+                    send_not_sir: 200_000_000_000,
+                    execution: 200_000_000_000,
+                },
             },
         }
     }
@@ -70,10 +70,14 @@ pub enum Parameter {
     ActionAddFunctionCallKeyPerByte,
     ActionDeleteKey,
     ActionDelegate,
+    ActionDeployContractWithInit,
 
     // Smart contract dynamic gas costs
     WasmRegularOpCost,
     WasmGrowMemCost,
+    /// Cost per byte of the bulk memory operations (`memory.copy`, `memory.fill`,
+    /// `memory.init`), on top of the regular operation cost.
+    WasmBulkMemoryByteCost,
     /// Base cost for a host function
     WasmBase,
     WasmContractLoadingBase,
@@ -160,6 +164,12 @@ pub enum Parameter {
     WasmBls12381P1DecompressElement,
     WasmBls12381P2DecompressBase,
     WasmBls12381P2DecompressElement,
+    WasmSecp256k1VerifyBase,
+    WasmSecp256r1VerifyBase,
+    WasmBip340VerifyBase,
+    WasmStorageIterV2Base,
+    WasmStorageIterV2Key,
+    WasmStorageIterV2Byte,
 
     // Smart contract limits
     MaxGasBurnt,
@@ -192,6 +202,7 @@ pub enum Parameter {
     AccountIdValidityRulesVersion,
     YieldTimeoutLengthInBlocks,
     MaxYieldPayloadSize,
+    MaxStorageIterPageSize,
 
     // Contract runtime features
     #[strum(serialize = "disable_9393_fix")]
@@ -201,6 +212,8 @@ pub enum Parameter {
     FixContractLoadingCost,
     MathExtension,
     Ed25519Verify,
+    EcdsaSchnorrVerify,
+    StorageIterV2,
     AltBn128,
     FunctionCallWeight,
     VmKind,
@@ -255,6 +268,7 @@ pub enum FeeParameter {
     ActionAddFunctionCallKeyPerByte,
     ActionDeleteKey,
     ActionDelegate,
+    ActionDeployContractWithInit,
 }
 
 impl Parameter {
@@ -291,6 +305,7 @@ impl Parameter {
             Parameter::AccountIdValidityRulesVersion,
             Parameter::YieldTimeoutLengthInBlocks,
             Parameter::MaxYieldPayloadSize,
+            Parameter::MaxStorageIterPageSize,
             Parameter::PerReceiptStorageProofSizeLimit,
         ]
         .iter()
@@ -305,6 +320,7 @@ impl From<ActionCosts> for FeeParameter {
             ActionCosts::create_account => Self::ActionCreateAccount,
             ActionCosts::delete_account => Self::ActionDeleteAccount,
             ActionCosts::delegate => Self::ActionDelegate,
+            ActionCosts::deploy_contract_with_init => Self::ActionDeployContractWithInit,
             ActionCosts::deploy_contract_base => Self::ActionDeployContract,
             ActionCosts::deploy_contract_byte => Self::ActionDeployContractPerByte,
             ActionCosts::function_call_base => Self::ActionFunctionCall,
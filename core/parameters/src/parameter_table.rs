@@ -313,8 +313,11 @@ impl TryFrom<&ParameterTable> for RuntimeConfig {
                 vm_kind: params.get(Parameter::VmKind)?,
                 grow_mem_cost: params.get(Parameter::WasmGrowMemCost)?,
                 regular_op_cost: params.get(Parameter::WasmRegularOpCost)?,
+                bulk_memory_byte_cost: params.get(Parameter::WasmBulkMemoryByteCost)?,
                 disable_9393_fix: params.get(Parameter::Disable9393Fix)?,
                 discard_custom_sections: params.get(Parameter::DiscardCustomSections)?,
+                function_gas_profiling: false,
+                execution_tracing: false,
                 limit_config: serde_yaml::from_value(params.yaml_map(Parameter::vm_limits()))
                     .map_err(InvalidConfigError::InvalidYaml)?,
                 fix_contract_loading_cost: params.get(Parameter::FixContractLoadingCost)?,
@@ -325,6 +328,8 @@ impl TryFrom<&ParameterTable> for RuntimeConfig {
                 implicit_account_creation: params.get(Parameter::ImplicitAccountCreation)?,
                 math_extension: params.get(Parameter::MathExtension)?,
                 ed25519_verify: params.get(Parameter::Ed25519Verify)?,
+                ecdsa_schnorr_verify: params.get(Parameter::EcdsaSchnorrVerify)?,
+                storage_iter_v2: params.get(Parameter::StorageIterV2)?,
                 alt_bn128: params.get(Parameter::AltBn128)?,
                 function_call_weight: params.get(Parameter::FunctionCallWeight)?,
                 eth_implicit_accounts: params.get(Parameter::EthImplicitAccounts)?,
@@ -109,6 +109,11 @@ pub struct ActionCreationConfigView {
     ///
     /// This is on top of the costs for the actions inside the delegate action.
     pub delegate_cost: Fee,
+
+    /// Base cost for processing a deploy with init action.
+    ///
+    /// This is on top of the costs for deploying the contract and calling its init method.
+    pub deploy_contract_with_init_cost: Fee,
 }
 
 /// Describes the cost of creating an access key.
@@ -175,6 +180,10 @@ impl From<crate::RuntimeConfig> for RuntimeConfigView {
                     delete_key_cost: config.fees.fee(ActionCosts::delete_key).clone(),
                     delete_account_cost: config.fees.fee(ActionCosts::delete_account).clone(),
                     delegate_cost: config.fees.fee(ActionCosts::delegate).clone(),
+                    deploy_contract_with_init_cost: config
+                        .fees
+                        .fee(ActionCosts::deploy_contract_with_init)
+                        .clone(),
                 },
                 storage_usage_config: StorageUsageConfigView {
                     num_bytes_account: config.fees.storage_usage_config.num_bytes_account,
@@ -209,6 +218,8 @@ pub struct VMConfigView {
     pub grow_mem_cost: u32,
     /// Gas cost of a regular operation.
     pub regular_op_cost: u32,
+    /// See [VMConfig::bulk_memory_byte_cost](crate::vm::Config::bulk_memory_byte_cost).
+    pub bulk_memory_byte_cost: u32,
 
     /// See [VMConfig::vm_kind](crate::vm::Config::vm_kind).
     pub vm_kind: crate::vm::VMKind,
@@ -227,6 +238,10 @@ pub struct VMConfigView {
     pub math_extension: bool,
     /// See [VMConfig::ed25519_verify](crate::vm::Config::ed25519_verify).
     pub ed25519_verify: bool,
+    /// See [VMConfig::ecdsa_schnorr_verify](crate::vm::Config::ecdsa_schnorr_verify).
+    pub ecdsa_schnorr_verify: bool,
+    /// See [VMConfig::storage_iter_v2](crate::vm::Config::storage_iter_v2).
+    pub storage_iter_v2: bool,
     /// See [VMConfig::alt_bn128](crate::vm::Config::alt_bn128).
     pub alt_bn128: bool,
     /// See [VMConfig::function_call_weight](crate::vm::Config::function_call_weight).
@@ -249,6 +264,7 @@ impl From<crate::vm::Config> for VMConfigView {
             ext_costs: ExtCostsConfigView::from(config.ext_costs),
             grow_mem_cost: config.grow_mem_cost,
             regular_op_cost: config.regular_op_cost,
+            bulk_memory_byte_cost: config.bulk_memory_byte_cost,
             disable_9393_fix: config.disable_9393_fix,
             discard_custom_sections: config.discard_custom_sections,
             limit_config: config.limit_config,
@@ -257,6 +273,8 @@ impl From<crate::vm::Config> for VMConfigView {
             implicit_account_creation: config.implicit_account_creation,
             math_extension: config.math_extension,
             ed25519_verify: config.ed25519_verify,
+            ecdsa_schnorr_verify: config.ecdsa_schnorr_verify,
+            storage_iter_v2: config.storage_iter_v2,
             alt_bn128: config.alt_bn128,
             function_call_weight: config.function_call_weight,
             vm_kind: config.vm_kind,
@@ -272,14 +290,19 @@ impl From<VMConfigView> for crate::vm::Config {
             ext_costs: crate::ExtCostsConfig::from(view.ext_costs),
             grow_mem_cost: view.grow_mem_cost,
             regular_op_cost: view.regular_op_cost,
+            bulk_memory_byte_cost: view.bulk_memory_byte_cost,
             disable_9393_fix: view.disable_9393_fix,
             discard_custom_sections: view.discard_custom_sections,
+            function_gas_profiling: false,
+            execution_tracing: false,
             limit_config: view.limit_config,
             storage_get_mode: view.storage_get_mode,
             fix_contract_loading_cost: view.fix_contract_loading_cost,
             implicit_account_creation: view.implicit_account_creation,
             math_extension: view.math_extension,
             ed25519_verify: view.ed25519_verify,
+            ecdsa_schnorr_verify: view.ecdsa_schnorr_verify,
+            storage_iter_v2: view.storage_iter_v2,
             alt_bn128: view.alt_bn128,
             function_call_weight: view.function_call_weight,
             vm_kind: view.vm_kind,
@@ -487,6 +510,18 @@ pub struct ExtCostsConfigView {
     pub bls12381_p1_decompress_element: Gas,
     pub bls12381_p2_decompress_base: Gas,
     pub bls12381_p2_decompress_element: Gas,
+    /// Base cost of verifying a secp256k1 ECDSA signature.
+    pub secp256k1_verify_base: Gas,
+    /// Base cost of verifying a secp256r1 ECDSA signature.
+    pub secp256r1_verify_base: Gas,
+    /// Base cost of verifying a BIP-340 Schnorr signature.
+    pub bip340_verify_base: Gas,
+    /// Base cost of a `storage_iter_prefix_v2` call.
+    pub storage_iter_v2_base: Gas,
+    /// Cost per key-value pair returned by `storage_iter_prefix_v2`.
+    pub storage_iter_v2_key: Gas,
+    /// Cost per byte of the keys and values returned by `storage_iter_prefix_v2`.
+    pub storage_iter_v2_byte: Gas,
 }
 
 impl From<crate::ExtCostsConfig> for ExtCostsConfigView {
@@ -586,6 +621,12 @@ impl From<crate::ExtCostsConfig> for ExtCostsConfigView {
             bls12381_p2_decompress_base: config.gas_cost(ExtCosts::bls12381_p2_decompress_base),
             bls12381_p2_decompress_element: config
                 .gas_cost(ExtCosts::bls12381_p2_decompress_element),
+            secp256k1_verify_base: config.gas_cost(ExtCosts::secp256k1_verify_base),
+            secp256r1_verify_base: config.gas_cost(ExtCosts::secp256r1_verify_base),
+            bip340_verify_base: config.gas_cost(ExtCosts::bip340_verify_base),
+            storage_iter_v2_base: config.gas_cost(ExtCosts::storage_iter_v2_base),
+            storage_iter_v2_key: config.gas_cost(ExtCosts::storage_iter_v2_key),
+            storage_iter_v2_byte: config.gas_cost(ExtCosts::storage_iter_v2_byte),
             // removed parameters
             contract_compile_base: 0,
             contract_compile_bytes: 0,
@@ -681,6 +722,12 @@ impl From<ExtCostsConfigView> for crate::ExtCostsConfig {
                 ExtCosts::bls12381_p1_decompress_element => view.bls12381_p1_decompress_element,
                 ExtCosts::bls12381_p2_decompress_base => view.bls12381_p2_decompress_base,
                 ExtCosts::bls12381_p2_decompress_element => view.bls12381_p2_decompress_element,
+                ExtCosts::secp256k1_verify_base => view.secp256k1_verify_base,
+                ExtCosts::secp256r1_verify_base => view.secp256r1_verify_base,
+                ExtCosts::bip340_verify_base => view.bip340_verify_base,
+                ExtCosts::storage_iter_v2_base => view.storage_iter_v2_base,
+                ExtCosts::storage_iter_v2_key => view.storage_iter_v2_key,
+                ExtCosts::storage_iter_v2_byte => view.storage_iter_v2_byte,
         }
         .map(|_, value| ParameterCost { gas: value, compute: value });
         Self { costs }
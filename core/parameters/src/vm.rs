@@ -140,6 +140,8 @@ pub struct LimitConfig {
     pub yield_timeout_length_in_blocks: u64,
     /// Maximum number of bytes for payload passed over a yield resume.
     pub max_yield_payload_size: u64,
+    /// Max number of key-value pairs returned by a single `storage_iter_prefix_v2` call.
+    pub max_storage_iter_page_size: u64,
     /// Hard limit on the size of storage proof generated while executing a single receipt.
     pub per_receipt_storage_proof_size_limit: usize,
 }
@@ -162,6 +164,12 @@ pub struct Config {
     /// Gas cost of a regular operation.
     pub regular_op_cost: u32,
 
+    /// Gas cost per byte copied or filled by a bulk memory operation.
+    ///
+    /// Only charged for the contracts prepared with `ContractPrepareVersion::V3` or newer,
+    /// as the older versions do not allow the bulk memory operations.
+    pub bulk_memory_byte_cost: u32,
+
     /// The kind of the VM implementation to use
     pub vm_kind: VMKind,
 
@@ -184,6 +192,12 @@ pub struct Config {
     /// Enable the host functions added by the `Ed25519Verify` protocol feature.
     pub ed25519_verify: bool,
 
+    /// Enable the host functions added by the `EcdsaSchnorrVerify` protocol feature.
+    pub ecdsa_schnorr_verify: bool,
+
+    /// Enable the host functions added by the `StorageIterV2` protocol feature.
+    pub storage_iter_v2: bool,
+
     /// Enable the host functions added by the `AltBn128` protocol feature.
     pub alt_bn128: bool,
 
@@ -199,6 +213,21 @@ pub struct Config {
     /// Whether to discard custom sections.
     pub discard_custom_sections: bool,
 
+    /// Attribute the gas burnt by a contract call to the Wasm functions that burnt it.
+    ///
+    /// This is not a protocol parameter and is never enabled when applying chunks. It is meant
+    /// for view calls and sandbox nodes only, as the injected profiling hooks are metered like
+    /// any other instruction. Only the contracts prepared with `ContractPrepareVersion::V2` or
+    /// newer are instrumented.
+    pub function_gas_profiling: bool,
+
+    /// Record every host function call made by a contract along with the storage accesses and
+    /// promises it made, see `VMOutcome::execution_trace`.
+    ///
+    /// This is not a protocol parameter and is never enabled when applying chunks for the chain.
+    /// It is only meant for the debugging tools replaying receipts.
+    pub execution_tracing: bool,
+
     /// Describes limits for VM and Runtime.
     pub limit_config: LimitConfig,
 }
@@ -220,6 +249,7 @@ impl Config {
         };
         self.grow_mem_cost = 0;
         self.regular_op_cost = 0;
+        self.bulk_memory_byte_cost = 0;
         self.limit_config.max_gas_burnt = u64::MAX;
     }
 
@@ -229,6 +259,8 @@ impl Config {
         self.function_call_weight = true;
         self.alt_bn128 = true;
         self.ed25519_verify = true;
+        self.ecdsa_schnorr_verify = true;
+        self.storage_iter_v2 = true;
         self.math_extension = true;
         self.implicit_account_creation = true;
     }
@@ -267,6 +299,11 @@ pub enum ContractPrepareVersion {
     V1,
     /// finite-wasm 0.3.0 based contract preparation code.
     V2,
+    /// Same as `V2`, but additionally enables the bulk memory and multi-value proposals.
+    ///
+    /// Multi-value is only allowed for the block types: functions still may return at
+    /// most one value.
+    V3,
 }
 
 impl ContractPrepareVersion {
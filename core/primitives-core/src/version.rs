@@ -178,6 +178,20 @@ pub enum ProtocolFeature {
     ExcludeContractCodeFromStateWitness,
     /// A scheduler which limits bandwidth for sending receipts between shards.
     BandwidthScheduler,
+    /// Contract preparation V3, enabling the bulk memory and multi-value Wasm proposals.
+    BulkMemoryMultiValue,
+    /// Host functions verifying secp256k1 and secp256r1 ECDSA signatures and BIP-340 Schnorr
+    /// signatures.
+    EcdsaSchnorrVerify,
+    /// Bounded iteration over the contract storage with the `storage_iter_prefix_v2` host
+    /// function, replacing the deprecated storage iterators.
+    StorageIterV2,
+    /// Action deploying a contract and calling its init method in the same receipt, keeping
+    /// the previous code if the call fails.
+    DeployContractWithInit,
+    /// Actions publishing contract code to a registry addressed by the code hash, and pointing an
+    /// account at a published code without storing a copy of it.
+    GlobalContracts,
     /// Indicates that the "sync_hash" used to identify the point in the chain to sync state to
     /// should no longer be the first block of the epoch, but a couple blocks after that in order
     /// to sync the current epoch's state. This is not strictly a protocol feature, but is included
@@ -262,11 +276,16 @@ impl ProtocolFeature {
             ProtocolFeature::StateSyncHashUpdate => 144,
             ProtocolFeature::SimpleNightshadeV4 => 145,
             ProtocolFeature::BandwidthScheduler => 146,
+            ProtocolFeature::BulkMemoryMultiValue => 147,
+            ProtocolFeature::EcdsaSchnorrVerify => 148,
+            ProtocolFeature::StorageIterV2 => 149,
+            ProtocolFeature::DeployContractWithInit => 150,
+            ProtocolFeature::GlobalContracts => 151,
 
             // Features that are not yet in Nightly.
 
             // TODO(#11099): Move this feature to Nightly.
-            ProtocolFeature::ExcludeContractCodeFromStateWitness => 147,
+            ProtocolFeature::ExcludeContractCodeFromStateWitness => 152,
         }
     }
 
@@ -279,7 +298,7 @@ impl ProtocolFeature {
 const STABLE_PROTOCOL_VERSION: ProtocolVersion = 73;
 
 // On nightly, pick big enough version to support all features.
-const NIGHTLY_PROTOCOL_VERSION: ProtocolVersion = 146;
+const NIGHTLY_PROTOCOL_VERSION: ProtocolVersion = 151;
 
 /// Largest protocol version supported by the current binary.
 pub const PROTOCOL_VERSION: ProtocolVersion = if cfg!(feature = "nightly_protocol") {
@@ -82,6 +82,7 @@ fn create_block() -> Block {
         Clock::real(),
         None,
     )
+    .unwrap()
 }
 
 fn create_account() -> Account {
@@ -4,6 +4,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use near_crypto::PublicKey;
 use near_primitives_core::{
     account::AccessKey,
+    hash::CryptoHash,
     serialize::dec_format,
     types::{AccountId, Balance, Gas},
 };
@@ -106,6 +107,98 @@ impl fmt::Debug for DeployContractAction {
     }
 }
 
+/// Deploys a contract and calls a method of the new code in the same action, e.g. to migrate
+/// the contract state. If the call fails, the whole receipt fails and the previous code is kept.
+#[serde_as]
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    Clone,
+    ProtocolSchema,
+)]
+pub struct DeployContractWithInitAction {
+    /// WebAssembly binary
+    #[serde_as(as = "Base64")]
+    pub code: Vec<u8>,
+    /// Method of the new code called right after the deployment.
+    pub method_name: String,
+    #[serde_as(as = "Base64")]
+    pub args: Vec<u8>,
+    /// Gas attached to the method call.
+    pub gas: Gas,
+}
+
+impl DeployContractWithInitAction {
+    /// The method call part of the action. No deposit is attached to it.
+    pub fn function_call(&self) -> FunctionCallAction {
+        FunctionCallAction {
+            method_name: self.method_name.clone(),
+            args: self.args.clone(),
+            gas: self.gas,
+            deposit: 0,
+        }
+    }
+}
+
+impl fmt::Debug for DeployContractWithInitAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeployContractWithInitAction")
+            .field("code", &format_args!("{}", base64(&self.code)))
+            .field("method_name", &format_args!("{}", &self.method_name))
+            .field("args", &format_args!("{}", base64(&self.args)))
+            .field("gas", &format_args!("{}", &self.gas))
+            .finish()
+    }
+}
+
+/// Publishes a Wasm code to the global contract registry of the receiver's shard, so that
+/// accounts can use it without storing a copy.
+#[serde_as]
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    Clone,
+    ProtocolSchema,
+)]
+pub struct DeployGlobalContractAction {
+    /// WebAssembly binary
+    #[serde_as(as = "Base64")]
+    pub code: Vec<u8>,
+}
+
+impl fmt::Debug for DeployGlobalContractAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeployGlobalContractAction")
+            .field("code", &format_args!("{}", base64(&self.code)))
+            .finish()
+    }
+}
+
+/// Sets the code of the receiver to a Wasm code published to the global contract registry.
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    PartialEq,
+    Eq,
+    Clone,
+    Debug,
+    serde::Serialize,
+    serde::Deserialize,
+    ProtocolSchema,
+)]
+pub struct UseGlobalContractAction {
+    /// Hash of the published code.
+    pub code_hash: CryptoHash,
+}
+
 #[serde_as]
 #[derive(
     BorshSerialize,
@@ -221,6 +314,12 @@ pub enum Action {
     /// Only possible during new account creation.
     /// For implicit account creation, it has to be the only action in the receipt.
     NonrefundableStorageTransfer(NonrefundableStorageTransferAction),
+    /// Sets a Wasm code to a receiver_id and calls a method of it, atomically.
+    DeployContractWithInit(Box<DeployContractWithInitAction>),
+    /// Publishes a Wasm code to the global contract registry.
+    DeployGlobalContract(DeployGlobalContractAction),
+    /// Sets a published Wasm code to a receiver_id.
+    UseGlobalContract(Box<UseGlobalContractAction>),
 }
 
 const _: () = assert!(
@@ -235,6 +334,7 @@ impl Action {
     pub fn get_prepaid_gas(&self) -> Gas {
         match self {
             Action::FunctionCall(a) => a.gas,
+            Action::DeployContractWithInit(a) => a.gas,
             _ => 0,
         }
     }
@@ -261,6 +361,24 @@ impl From<DeployContractAction> for Action {
     }
 }
 
+impl From<DeployContractWithInitAction> for Action {
+    fn from(deploy_contract_with_init_action: DeployContractWithInitAction) -> Self {
+        Self::DeployContractWithInit(Box::new(deploy_contract_with_init_action))
+    }
+}
+
+impl From<DeployGlobalContractAction> for Action {
+    fn from(deploy_global_contract_action: DeployGlobalContractAction) -> Self {
+        Self::DeployGlobalContract(deploy_global_contract_action)
+    }
+}
+
+impl From<UseGlobalContractAction> for Action {
+    fn from(use_global_contract_action: UseGlobalContractAction) -> Self {
+        Self::UseGlobalContract(Box::new(use_global_contract_action))
+    }
+}
+
 impl From<FunctionCallAction> for Action {
     fn from(function_call_action: FunctionCallAction) -> Self {
         Self::FunctionCall(Box::new(function_call_action))
@@ -303,3 +421,96 @@ impl From<DeleteAccountAction> for Action {
         Self::DeleteAccount(delete_account_action)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_crypto::{KeyType, Signature};
+
+    /// The borsh tag of an action is a part of the wire format of transactions and receipts,
+    /// so new variants may only be appended.
+    #[test]
+    fn test_action_borsh_tags() {
+        let public_key = PublicKey::empty(KeyType::ED25519);
+        let delegate_action = delegate::DelegateAction {
+            sender_id: "alice.near".parse().unwrap(),
+            receiver_id: "bob.near".parse().unwrap(),
+            actions: vec![],
+            nonce: 1,
+            max_block_height: 2,
+            public_key: public_key.clone(),
+        };
+        // The tags following the feature gated variant depend on the enabled features.
+        let nep491 = u8::from(cfg!(feature = "protocol_feature_nonrefundable_transfer_nep491"));
+        let actions = vec![
+            (0, Action::CreateAccount(CreateAccountAction {})),
+            (1, Action::DeployContract(DeployContractAction { code: vec![1, 2, 3] })),
+            (
+                2,
+                Action::FunctionCall(Box::new(FunctionCallAction {
+                    method_name: "main".to_string(),
+                    args: vec![4, 5],
+                    gas: 100,
+                    deposit: 200,
+                })),
+            ),
+            (3, Action::Transfer(TransferAction { deposit: 300 })),
+            (
+                4,
+                Action::Stake(Box::new(StakeAction { stake: 400, public_key: public_key.clone() })),
+            ),
+            (
+                5,
+                Action::AddKey(Box::new(AddKeyAction {
+                    public_key: public_key.clone(),
+                    access_key: AccessKey::full_access(),
+                })),
+            ),
+            (6, Action::DeleteKey(Box::new(DeleteKeyAction { public_key }))),
+            (
+                7,
+                Action::DeleteAccount(DeleteAccountAction {
+                    beneficiary_id: "carol.near".parse().unwrap(),
+                }),
+            ),
+            (
+                8,
+                Action::Delegate(Box::new(delegate::SignedDelegateAction {
+                    delegate_action,
+                    signature: Signature::empty(KeyType::ED25519),
+                })),
+            ),
+            #[cfg(feature = "protocol_feature_nonrefundable_transfer_nep491")]
+            (
+                9,
+                Action::NonrefundableStorageTransfer(NonrefundableStorageTransferAction {
+                    deposit: 500,
+                }),
+            ),
+            (
+                9 + nep491,
+                Action::DeployContractWithInit(Box::new(DeployContractWithInitAction {
+                    code: vec![7],
+                    method_name: "init".to_string(),
+                    args: vec![8],
+                    gas: 600,
+                })),
+            ),
+            (
+                10 + nep491,
+                Action::DeployGlobalContract(DeployGlobalContractAction { code: vec![6] }),
+            ),
+            (
+                11 + nep491,
+                Action::UseGlobalContract(Box::new(UseGlobalContractAction {
+                    code_hash: CryptoHash::hash_bytes(&[6]),
+                })),
+            ),
+        ];
+        for (tag, action) in actions {
+            let bytes = borsh::to_vec(&action).unwrap();
+            assert_eq!(bytes[0], tag, "{}", action.as_ref());
+            assert_eq!(Action::try_from_slice(&bytes).unwrap(), action);
+        }
+    }
+}
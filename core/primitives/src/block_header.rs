@@ -5,7 +5,7 @@ use crate::network::PeerId;
 use crate::stateless_validation::chunk_endorsements_bitmap::ChunkEndorsementsBitmap;
 use crate::types::validator_stake::{ValidatorStake, ValidatorStakeIter, ValidatorStakeV1};
 use crate::types::{AccountId, Balance, BlockHeight, EpochId, MerkleHash, NumBlocks};
-use crate::validator_signer::ValidatorSigner;
+use crate::validator_signer::{ValidatorSigner, ValidatorSignerError};
 use crate::version::ProtocolVersion;
 use borsh::{BorshDeserialize, BorshSerialize};
 use near_crypto::{KeyType, PublicKey, Signature};
@@ -345,10 +345,10 @@ impl Approval {
         parent_height: BlockHeight,
         target_height: BlockHeight,
         signer: &ValidatorSigner,
-    ) -> Self {
+    ) -> Result<Self, ValidatorSignerError> {
         let inner = ApprovalInner::new(&parent_hash, parent_height, target_height);
-        let signature = signer.sign_approval(&inner, target_height);
-        Approval { inner, target_height, signature, account_id: signer.validator_id().clone() }
+        let signature = signer.sign_approval(&inner, target_height)?;
+        Ok(Approval { inner, target_height, signature, account_id: signer.validator_id().clone() })
     }
 
     pub fn get_data_for_sig(inner: &ApprovalInner, target_height: BlockHeight) -> Vec<u8> {
@@ -604,7 +604,7 @@ impl BlockHeader {
         prev_height: BlockHeight,
         clock: near_time::Clock,
         chunk_endorsements: Option<ChunkEndorsementsBitmap>,
-    ) -> Self {
+    ) -> Result<Self, ValidatorSignerError> {
         let latest_protocol_version =
             crate::version::get_protocol_version(next_epoch_protocol_version, clock);
         Self::new_impl(
@@ -707,7 +707,8 @@ impl BlockHeader {
             block_merkle_root,
             prev_height,
             chunk_endorsements,
-        );
+        )
+        .expect("header with a given signature is not signed");
         // Note: We do not panic but only log if the hash of the created header does not match the expected hash (From the view)
         // because there are tests that check if we can downgrade a BlockHeader's view a previous version, in which case the hash
         // of the header changes.
@@ -750,7 +751,7 @@ impl BlockHeader {
         block_merkle_root: CryptoHash,
         prev_height: BlockHeight,
         chunk_endorsements: Option<ChunkEndorsementsBitmap>,
-    ) -> Self {
+    ) -> Result<Self, ValidatorSignerError> {
         let inner_lite = BlockHeaderInnerLite {
             height,
             epoch_id,
@@ -788,14 +789,14 @@ impl BlockHeader {
                 chunk_endorsements,
             };
             let (hash, signature) =
-                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest);
-            Self::BlockHeaderV5(Arc::new(BlockHeaderV5 {
+                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest)?;
+            Ok(Self::BlockHeaderV5(Arc::new(BlockHeaderV5 {
                 prev_hash,
                 inner_lite,
                 inner_rest,
                 signature,
                 hash,
-            }))
+            })))
         } else if ProtocolFeature::BlockHeaderV4.enabled(this_epoch_protocol_version) {
             let inner_rest = BlockHeaderInnerRestV4 {
                 block_body_hash,
@@ -818,14 +819,14 @@ impl BlockHeader {
                 latest_protocol_version,
             };
             let (hash, signature) =
-                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest);
-            Self::BlockHeaderV4(Arc::new(BlockHeaderV4 {
+                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest)?;
+            Ok(Self::BlockHeaderV4(Arc::new(BlockHeaderV4 {
                 prev_hash,
                 inner_lite,
                 inner_rest,
                 signature,
                 hash,
-            }))
+            })))
         } else {
             // Build BlockHeaderV1-V3.
             Self::old_impl(
@@ -882,7 +883,7 @@ impl BlockHeader {
         epoch_sync_data_hash: Option<CryptoHash>,
         approvals: Vec<Option<Box<Signature>>>,
         prev_height: BlockHeight,
-    ) -> Self {
+    ) -> Result<Self, ValidatorSignerError> {
         let last_header_v2_version = ProtocolFeature::BlockHeaderV3.protocol_version() - 1;
         // Previously we passed next_epoch_protocol_version here, which is incorrect, but we need
         // to preserve this for archival nodes
@@ -909,14 +910,14 @@ impl BlockHeader {
                 latest_protocol_version,
             };
             let (hash, signature) =
-                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest);
-            Self::BlockHeaderV1(Arc::new(BlockHeaderV1 {
+                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest)?;
+            Ok(Self::BlockHeaderV1(Arc::new(BlockHeaderV1 {
                 prev_hash,
                 inner_lite,
                 inner_rest,
                 signature,
                 hash,
-            }))
+            })))
         } else if this_epoch_protocol_version <= last_header_v2_version {
             let inner_rest = BlockHeaderInnerRestV2 {
                 prev_chunk_outgoing_receipts_root,
@@ -938,14 +939,14 @@ impl BlockHeader {
                 latest_protocol_version,
             };
             let (hash, signature) =
-                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest);
-            Self::BlockHeaderV2(Arc::new(BlockHeaderV2 {
+                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest)?;
+            Ok(Self::BlockHeaderV2(Arc::new(BlockHeaderV2 {
                 prev_hash,
                 inner_lite,
                 inner_rest,
                 signature,
                 hash,
-            }))
+            })))
         } else {
             let inner_rest = BlockHeaderInnerRestV3 {
                 prev_chunk_outgoing_receipts_root,
@@ -967,14 +968,14 @@ impl BlockHeader {
                 latest_protocol_version,
             };
             let (hash, signature) =
-                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest);
-            Self::BlockHeaderV3(Arc::new(BlockHeaderV3 {
+                Self::compute_hash_and_sign(signature_source, prev_hash, &inner_lite, &inner_rest)?;
+            Ok(Self::BlockHeaderV3(Arc::new(BlockHeaderV3 {
                 prev_hash,
                 inner_lite,
                 inner_rest,
                 signature,
                 hash,
-            }))
+            })))
         }
     }
 
@@ -987,7 +988,7 @@ impl BlockHeader {
         prev_hash: CryptoHash,
         inner_lite: &BlockHeaderInnerLite,
         inner_rest: &T,
-    ) -> (CryptoHash, Signature)
+    ) -> Result<(CryptoHash, Signature), ValidatorSignerError>
     where
         T: BorshSerialize + ?Sized,
     {
@@ -1003,7 +1004,7 @@ impl BlockHeader {
                     &borsh::to_vec(&inner_lite).expect("Failed to serialize"),
                     &borsh::to_vec(&inner_rest).expect("Failed to serialize"),
                 );
-                (hash, signature)
+                Ok((hash, signature))
             }
         }
     }
@@ -1057,6 +1058,7 @@ impl BlockHeader {
             0,                     // prev_height
             Some(ChunkEndorsementsBitmap::genesis()),
         )
+        .expect("genesis header with a given signature is not signed")
     }
 
     #[inline]
@@ -593,6 +593,9 @@ pub enum ActionErrorKind {
     DelegateActionNonceTooLarge { delegate_nonce: Nonce, upper_bound: Nonce },
     /// Non-refundable storage transfer to an existing account is not allowed according to NEP-491.
     NonRefundableTransferToExistingAccount { account_id: AccountId },
+    /// The code with the given hash has not been published to the global contract registry of
+    /// the shard.
+    GlobalContractDoesNotExist { code_hash: CryptoHash },
 }
 
 impl From<ActionErrorKind> for ActionError {
@@ -936,6 +939,9 @@ impl Display for ActionErrorKind {
             ActionErrorKind::NonRefundableTransferToExistingAccount { account_id} => {
                 write!(f, "Can't make non-refundable storage transfer to {} because it already exists", account_id)
             }
+            ActionErrorKind::GlobalContractDoesNotExist { code_hash } => {
+                write!(f, "Global contract with code hash {} does not exist", code_hash)
+            }
         }
     }
 }
@@ -28,6 +28,7 @@ pub mod rand;
 pub mod receipt;
 #[cfg(feature = "solomon")]
 pub mod reed_solomon;
+pub mod remote_signer;
 pub mod runtime;
 pub mod sandbox;
 pub mod shard_layout;
@@ -35,6 +36,7 @@ pub mod sharding;
 pub mod signable_message;
 pub mod state;
 pub mod state_part;
+pub mod state_proof;
 pub mod state_record;
 pub mod state_sync;
 pub mod stateless_validation;
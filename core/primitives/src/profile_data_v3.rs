@@ -245,7 +245,7 @@ mod test {
         let pretty_debug_str = format!("{profile_data:#?}");
         expect_test::expect![[r#"
             ------------------------------
-            Action gas: 16120
+            Action gas: 17136
             ------ Host functions --------
             contract_loading_base -> 1 [0% host]
             contract_loading_bytes -> 2 [0% host]
@@ -282,11 +282,11 @@ mod test {
             storage_remove_base -> 33 [0% host]
             storage_remove_key_byte -> 34 [0% host]
             storage_remove_ret_value_byte -> 35 [0% host]
-            storage_has_key_base -> 36 [1% host]
-            storage_has_key_byte -> 37 [1% host]
-            storage_iter_create_prefix_base -> 38 [1% host]
-            storage_iter_create_prefix_byte -> 39 [1% host]
-            storage_iter_create_range_base -> 40 [1% host]
+            storage_has_key_base -> 36 [0% host]
+            storage_has_key_byte -> 37 [0% host]
+            storage_iter_create_prefix_base -> 38 [0% host]
+            storage_iter_create_prefix_byte -> 39 [0% host]
+            storage_iter_create_range_base -> 40 [0% host]
             storage_iter_create_from_byte -> 41 [1% host]
             storage_iter_create_to_byte -> 42 [1% host]
             storage_iter_next_base -> 43 [1% host]
@@ -318,19 +318,25 @@ mod test {
             bls12381_g1_multiexp_base -> 69 [1% host]
             bls12381_g1_multiexp_element -> 70 [1% host]
             bls12381_g2_multiexp_base -> 71 [1% host]
-            bls12381_g2_multiexp_element -> 72 [2% host]
-            bls12381_map_fp_to_g1_base -> 73 [2% host]
-            bls12381_map_fp_to_g1_element -> 74 [2% host]
-            bls12381_map_fp2_to_g2_base -> 75 [2% host]
-            bls12381_map_fp2_to_g2_element -> 76 [2% host]
-            bls12381_pairing_base -> 77 [2% host]
-            bls12381_pairing_element -> 78 [2% host]
-            bls12381_p1_decompress_base -> 79 [2% host]
-            bls12381_p1_decompress_element -> 80 [2% host]
-            bls12381_p2_decompress_base -> 81 [2% host]
+            bls12381_g2_multiexp_element -> 72 [1% host]
+            bls12381_map_fp_to_g1_base -> 73 [1% host]
+            bls12381_map_fp_to_g1_element -> 74 [1% host]
+            bls12381_map_fp2_to_g2_base -> 75 [1% host]
+            bls12381_map_fp2_to_g2_element -> 76 [1% host]
+            bls12381_pairing_base -> 77 [1% host]
+            bls12381_pairing_element -> 78 [1% host]
+            bls12381_p1_decompress_base -> 79 [1% host]
+            bls12381_p1_decompress_element -> 80 [1% host]
+            bls12381_p2_decompress_base -> 81 [1% host]
             bls12381_p2_decompress_element -> 82 [2% host]
             storage_large_read_overhead_base -> 83 [2% host]
             storage_large_read_overhead_byte -> 84 [2% host]
+            secp256k1_verify_base -> 85 [2% host]
+            secp256r1_verify_base -> 86 [2% host]
+            bip340_verify_base -> 87 [2% host]
+            storage_iter_v2_base -> 88 [2% host]
+            storage_iter_v2_key -> 89 [2% host]
+            storage_iter_v2_byte -> 90 [2% host]
             ------ Actions --------
             create_account -> 1000
             delete_account -> 1001
@@ -348,6 +354,7 @@ mod test {
             new_data_receipt_base -> 1013
             new_data_receipt_byte -> 1014
             delegate -> 1015
+            deploy_contract_with_init -> 1016
             ------------------------------
         "#]]
         .assert_eq(&pretty_debug_str)
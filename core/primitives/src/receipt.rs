@@ -1,5 +1,6 @@
 use crate::hash::CryptoHash;
 use crate::serialize::dec_format;
+use crate::shard_layout::{account_id_to_shard_id, ShardLayout};
 use crate::transaction::{Action, TransferAction};
 use crate::types::{AccountId, Balance, BlockHeight, ShardId};
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -498,6 +499,37 @@ impl Receipt {
         }
     }
 
+    /// Generates a receipt without a receipt_id replicating a change of the global contract
+    /// registry made by `account_id` to the given shard.
+    pub fn new_global_contract_distribution(
+        account_id: AccountId,
+        target_shard: ShardId,
+        already_delivered_shards: Vec<ShardId>,
+        change: GlobalContractChange,
+    ) -> Self {
+        Receipt::V0(ReceiptV0 {
+            predecessor_id: account_id.clone(),
+            receiver_id: account_id,
+            receipt_id: CryptoHash::default(),
+            receipt: ReceiptEnum::GlobalContractDistribution(GlobalContractDistributionReceipt {
+                target_shard,
+                already_delivered_shards,
+                change,
+            }),
+        })
+    }
+
+    /// The shard of the given shard layout the receipt should be applied on. That is the shard of
+    /// the receiver, except for the global contract distribution receipts, which target a shard.
+    pub fn receiver_shard_id(&self, shard_layout: &ShardLayout) -> ShardId {
+        match self.receipt() {
+            ReceiptEnum::GlobalContractDistribution(receipt) => {
+                receipt.receiver_shard_id(shard_layout)
+            }
+            _ => account_id_to_shard_id(self.receiver_id(), shard_layout),
+        }
+    }
+
     /// Generates a receipt with a transfer action from system for a given balance without a
     /// receipt_id. It contains `signer_id` and `signer_public_key` to indicate this is a gas
     /// refund. The execution of this receipt will try to refund the allowance of the
@@ -562,6 +594,79 @@ pub enum ReceiptEnum {
     Data(DataReceipt),
     PromiseYield(ActionReceipt),
     PromiseResume(DataReceipt),
+    GlobalContractDistribution(GlobalContractDistributionReceipt),
+}
+
+/// Replicates a change of the global contract registry to a shard. The registry is stored on
+/// every shard, so publishing or unpublishing a code sends one of these to every other shard.
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    serde::Serialize,
+    serde::Deserialize,
+    ProtocolSchema,
+)]
+pub struct GlobalContractDistributionReceipt {
+    /// The shard the change is sent to. If the shard was split since the receipt was sent, the
+    /// receipt is delivered to one of its children, which forwards it to the other ones.
+    pub target_shard: ShardId,
+    /// The shards which already applied the change, so that it isn't forwarded to them again.
+    pub already_delivered_shards: Vec<ShardId>,
+    pub change: GlobalContractChange,
+}
+
+impl GlobalContractDistributionReceipt {
+    /// The shard of the given shard layout the receipt should be applied on.
+    pub fn receiver_shard_id(&self, shard_layout: &ShardLayout) -> ShardId {
+        if shard_layout.shard_ids().any(|shard_id| shard_id == self.target_shard) {
+            return self.target_shard;
+        }
+        shard_layout
+            .get_children_shards_ids(self.target_shard)
+            .and_then(|children| children.first().copied())
+            .unwrap_or(self.target_shard)
+    }
+}
+
+/// A change of the global contract registry made by the predecessor of the receipt.
+#[serde_as]
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    PartialEq,
+    Eq,
+    Clone,
+    serde::Serialize,
+    serde::Deserialize,
+    ProtocolSchema,
+)]
+pub enum GlobalContractChange {
+    /// The account published the code.
+    Publish {
+        #[serde_as(as = "Base64")]
+        code: Vec<u8>,
+    },
+    /// The account no longer publishes the code with the given hash, which is removed from the
+    /// registry if no other account publishes it.
+    Unpublish { code_hash: CryptoHash },
+}
+
+impl fmt::Debug for GlobalContractChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobalContractChange::Publish { code } => f
+                .debug_struct("Publish")
+                .field("code", &format_args!("{}", AbbrBytes(code)))
+                .finish(),
+            GlobalContractChange::Unpublish { code_hash } => {
+                f.debug_struct("Unpublish").field("code_hash", code_hash).finish()
+            }
+        }
+    }
 }
 
 /// ActionReceipt is derived from an Action from `Transaction or from Receipt`
@@ -0,0 +1,287 @@
+//! Signing with a validator key that is held by a separate process.
+//!
+//! Instead of the bytes to sign, the node sends the signer a [`RemoteSignRequest`] describing the
+//! message, so that the signer can check it against its own policy before signing, e.g. refuse to
+//! approve a height lower than one it has already approved. The transport is not implemented here,
+//! see the `near-remote-signer` crate.
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_crypto::{PublicKey, Signature};
+
+use crate::block::{Approval, ApprovalInner, BlockHeader};
+use crate::block_header::BlockHeaderInnerLite;
+use crate::challenge::ChallengeBody;
+use crate::hash::CryptoHash;
+use crate::network::{AnnounceAccount, PeerId};
+use crate::sharding::{
+    ChunkHash, ShardChunkHeaderInner, ShardChunkHeaderInnerV1, ShardChunkHeaderV1,
+    ShardChunkHeaderV2, ShardChunkHeaderV3,
+};
+use crate::stateless_validation::chunk_endorsement::{
+    ChunkEndorsementInner, ChunkEndorsementMetadata,
+};
+use crate::stateless_validation::contract_distribution::{
+    ChunkContractAccessesInner, ContractCodeRequestInner, ContractCodeResponseInner,
+    PartialEncodedContractDeploysInner,
+};
+use crate::stateless_validation::partial_witness::PartialEncodedStateWitnessInner;
+use crate::stateless_validation::state_witness::EncodedChunkStateWitness;
+use crate::types::{AccountId, BlockHeight, EpochId, ShardId};
+use crate::utils::compression::CompressedData;
+use crate::validator_signer::ValidatorSignerError;
+
+/// A message that the node asks the remote signer to sign, one variant per signing method of
+/// [`crate::validator_signer::ValidatorSigner`].
+#[derive(BorshSerialize, BorshDeserialize, Clone, strum::IntoStaticStr)]
+pub enum RemoteSignRequest {
+    /// Asks for the account id and the public key of the validator key. Sent once on connection.
+    ValidatorKey,
+    Telemetry {
+        content: String,
+    },
+    BlockHeader {
+        prev_hash: CryptoHash,
+        inner_lite: Vec<u8>,
+        inner_rest: Vec<u8>,
+    },
+    ChunkHeader(Box<RemoteChunkHeaderInner>),
+    Approval {
+        inner: ApprovalInner,
+        target_height: BlockHeight,
+    },
+    ChunkEndorsement(ChunkEndorsementInner),
+    ChunkEndorsementMetadata(ChunkEndorsementMetadata),
+    ChunkStateWitness(EncodedChunkStateWitness),
+    PartialEncodedStateWitness(PartialEncodedStateWitnessInner),
+    Challenge(Box<ChallengeBody>),
+    AccountAnnounce {
+        account_id: AccountId,
+        peer_id: PeerId,
+        epoch_id: EpochId,
+    },
+    ChunkContractAccesses(ChunkContractAccessesInner),
+    PartialEncodedContractDeploys(PartialEncodedContractDeploysInner),
+    ContractCodeRequest(ContractCodeRequestInner),
+    ContractCodeResponse(ContractCodeResponseInner),
+    AccountKeyPayload(Vec<u8>),
+    Vrf {
+        data: Vec<u8>,
+    },
+}
+
+impl RemoteSignRequest {
+    /// Name of the request, used in logs and metrics.
+    pub fn kind(&self) -> &'static str {
+        self.into()
+    }
+
+    /// Bytes that are signed with the validator key for this request, exactly as
+    /// [`crate::validator_signer::InMemoryValidatorSigner`] signs them.
+    /// Returns `None` for requests that are not answered with a signature.
+    pub fn data_to_sign(&self) -> Option<Vec<u8>> {
+        let data = match self {
+            RemoteSignRequest::ValidatorKey | RemoteSignRequest::Vrf { .. } => return None,
+            RemoteSignRequest::Telemetry { content } => content.as_bytes().to_vec(),
+            RemoteSignRequest::BlockHeader { prev_hash, inner_lite, inner_rest } => {
+                BlockHeader::compute_hash(*prev_hash, inner_lite, inner_rest).as_ref().to_vec()
+            }
+            RemoteSignRequest::ChunkHeader(inner) => inner.chunk_hash().as_ref().to_vec(),
+            RemoteSignRequest::Approval { inner, target_height } => {
+                Approval::get_data_for_sig(inner, *target_height)
+            }
+            RemoteSignRequest::ChunkEndorsement(inner) => borsh::to_vec(inner).unwrap(),
+            RemoteSignRequest::ChunkEndorsementMetadata(inner) => borsh::to_vec(inner).unwrap(),
+            RemoteSignRequest::ChunkStateWitness(witness_bytes) => {
+                witness_bytes.as_slice().to_vec()
+            }
+            RemoteSignRequest::PartialEncodedStateWitness(part) => borsh::to_vec(part).unwrap(),
+            RemoteSignRequest::Challenge(challenge_body) => {
+                CryptoHash::hash_borsh(challenge_body).as_ref().to_vec()
+            }
+            RemoteSignRequest::AccountAnnounce { account_id, peer_id, epoch_id } => {
+                AnnounceAccount::build_header_hash(account_id, peer_id, epoch_id).as_ref().to_vec()
+            }
+            RemoteSignRequest::ChunkContractAccesses(inner) => borsh::to_vec(inner).unwrap(),
+            RemoteSignRequest::PartialEncodedContractDeploys(inner) => {
+                borsh::to_vec(inner).unwrap()
+            }
+            RemoteSignRequest::ContractCodeRequest(inner) => borsh::to_vec(inner).unwrap(),
+            RemoteSignRequest::ContractCodeResponse(inner) => borsh::to_vec(inner).unwrap(),
+            RemoteSignRequest::AccountKeyPayload(proto_bytes) => proto_bytes.clone(),
+        };
+        Some(data)
+    }
+
+    /// Height and shard of the chain position that the signed message commits to, for the
+    /// messages that must never be signed twice for the same position: approvals, block headers,
+    /// chunk headers and chunk endorsements. Returns `None` for all other requests.
+    pub fn signed_position(&self) -> Option<(Option<ShardId>, BlockHeight)> {
+        match self {
+            RemoteSignRequest::Approval { target_height, .. } => Some((None, *target_height)),
+            RemoteSignRequest::BlockHeader { inner_lite, .. } => {
+                // An undecodable header is treated as having the lowest height, so that a signer
+                // enforcing monotonicity refuses it.
+                let height = BlockHeaderInnerLite::try_from_slice(inner_lite)
+                    .map_or(0, |inner_lite| inner_lite.height);
+                Some((None, height))
+            }
+            RemoteSignRequest::ChunkHeader(inner) => {
+                Some((Some(inner.shard_id()), inner.height_created()))
+            }
+            RemoteSignRequest::ChunkEndorsementMetadata(metadata) => {
+                Some((Some(metadata.shard_id()), metadata.height_created()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Inner of a chunk header to sign, tagged with the version of the header it belongs to. The
+/// signer computes the chunk hash from it, so that it knows the height and shard of the chunk.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub enum RemoteChunkHeaderInner {
+    V1(ShardChunkHeaderInnerV1),
+    V2(ShardChunkHeaderInnerV1),
+    V3(ShardChunkHeaderInner),
+}
+
+impl RemoteChunkHeaderInner {
+    pub fn chunk_hash(&self) -> ChunkHash {
+        match self {
+            RemoteChunkHeaderInner::V1(inner) => ShardChunkHeaderV1::compute_hash(inner),
+            RemoteChunkHeaderInner::V2(inner) => ShardChunkHeaderV2::compute_hash(inner),
+            RemoteChunkHeaderInner::V3(inner) => ShardChunkHeaderV3::compute_hash(inner),
+        }
+    }
+
+    pub fn height_created(&self) -> BlockHeight {
+        match self {
+            RemoteChunkHeaderInner::V1(inner) | RemoteChunkHeaderInner::V2(inner) => {
+                inner.height_created
+            }
+            RemoteChunkHeaderInner::V3(inner) => inner.height_created(),
+        }
+    }
+
+    pub fn shard_id(&self) -> ShardId {
+        match self {
+            RemoteChunkHeaderInner::V1(inner) | RemoteChunkHeaderInner::V2(inner) => inner.shard_id,
+            RemoteChunkHeaderInner::V3(inner) => inner.shard_id(),
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub enum RemoteSignResponse {
+    ValidatorKey {
+        account_id: AccountId,
+        public_key: PublicKey,
+    },
+    Signature(Signature),
+    Vrf {
+        value: near_crypto::vrf::Value,
+        proof: near_crypto::vrf::Proof,
+    },
+    /// The signer refused to sign the message, e.g. because it violates the signer's policy.
+    Refused(String),
+}
+
+/// Transport that delivers sign requests to the remote signer.
+pub trait RemoteSignerClient: Send + Sync + Debug {
+    fn send(&self, request: &RemoteSignRequest) -> std::io::Result<RemoteSignResponse>;
+}
+
+/// Validator signer that forwards every sign request to a remote signer.
+///
+/// When the remote signer refuses a request or can't be reached, signing fails with
+/// [`ValidatorSignerError`] and the caller doesn't produce the message.
+#[derive(Clone, Debug)]
+pub struct RemoteValidatorSigner {
+    account_id: AccountId,
+    public_key: PublicKey,
+    client: Arc<dyn RemoteSignerClient>,
+}
+
+impl PartialEq for RemoteValidatorSigner {
+    fn eq(&self, other: &Self) -> bool {
+        self.account_id == other.account_id && self.public_key == other.public_key
+    }
+}
+
+impl RemoteValidatorSigner {
+    /// Asks the remote signer which validator key it holds.
+    pub fn connect(client: Arc<dyn RemoteSignerClient>) -> std::io::Result<Self> {
+        match client.send(&RemoteSignRequest::ValidatorKey)? {
+            RemoteSignResponse::ValidatorKey { account_id, public_key } => {
+                Ok(Self { account_id, public_key, client })
+            }
+            RemoteSignResponse::Refused(reason) => Err(std::io::Error::other(format!(
+                "remote signer refused to share its validator key: {reason}"
+            ))),
+            response => Err(std::io::Error::other(format!(
+                "unexpected response from remote signer: {response:?}"
+            ))),
+        }
+    }
+
+    pub fn validator_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key.clone()
+    }
+
+    /// Sends the request, turning a refusal or a transport error into [`ValidatorSignerError`].
+    fn send(
+        &self,
+        request: &RemoteSignRequest,
+    ) -> Result<RemoteSignResponse, ValidatorSignerError> {
+        let kind = request.kind();
+        match self.client.send(request) {
+            Ok(RemoteSignResponse::Refused(reason)) => {
+                tracing::error!(target: "remote_signer", kind, %reason, "Remote signer refused to sign");
+                Err(ValidatorSignerError::Refused { kind, reason })
+            }
+            Ok(response) => Ok(response),
+            Err(err) => {
+                tracing::error!(target: "remote_signer", kind, ?err, "Failed to reach remote signer");
+                Err(ValidatorSignerError::Unavailable { kind, error: err.to_string() })
+            }
+        }
+    }
+
+    fn unexpected_response(
+        kind: &'static str,
+        response: RemoteSignResponse,
+    ) -> ValidatorSignerError {
+        tracing::error!(target: "remote_signer", kind, ?response, "Unexpected response from remote signer");
+        ValidatorSignerError::Unavailable {
+            kind,
+            error: format!("unexpected response {response:?}"),
+        }
+    }
+
+    pub(crate) fn sign(
+        &self,
+        request: RemoteSignRequest,
+    ) -> Result<Signature, ValidatorSignerError> {
+        match self.send(&request)? {
+            RemoteSignResponse::Signature(signature) => Ok(signature),
+            response => Err(Self::unexpected_response(request.kind(), response)),
+        }
+    }
+
+    pub(crate) fn compute_vrf_with_proof(
+        &self,
+        data: &[u8],
+    ) -> Result<(near_crypto::vrf::Value, near_crypto::vrf::Proof), ValidatorSignerError> {
+        let request = RemoteSignRequest::Vrf { data: data.to_vec() };
+        match self.send(&request)? {
+            RemoteSignResponse::Vrf { value, proof } => Ok((value, proof)),
+            response => Err(Self::unexpected_response(request.kind(), response)),
+        }
+    }
+}
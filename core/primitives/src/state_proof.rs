@@ -0,0 +1,314 @@
+//! Proofs of contract storage values that can be checked by a light client.
+//!
+//! A [`ContractStateProof`] links a value in the storage of a contract to a block the light
+//! client has verified:
+//!
+//! * the trie proof links the value to the state root of the shard of the account,
+//! * the state root proof links the state root to the `prev_state_root` of the block,
+//! * the block proof links the block to the `block_merkle_root` of the light client head.
+//!
+//! The state proven is the state at the beginning of the block, that is the state roots in the
+//! chunk headers of the block.
+
+use crate::hash::{hash, CryptoHash};
+use crate::merkle::{compute_root_from_path, MerklePath};
+use crate::state::ValueRef;
+use crate::trie_key::TrieKey;
+use crate::types::AccountId;
+use crate::views::LightClientBlockLiteView;
+use borsh::BorshDeserialize;
+use serde_with::base64::Base64;
+use serde_with::serde_as;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[serde_as]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct ContractStateProof {
+    pub account_id: AccountId,
+    #[serde_as(as = "Base64")]
+    pub key: Vec<u8>,
+    /// The value stored under the key, `None` if the key does not exist.
+    #[serde_as(as = "Option<Base64>")]
+    pub value: Option<Vec<u8>>,
+    /// Trie nodes on the path to the key, in any order.
+    #[serde_as(as = "Vec<Base64>")]
+    pub trie_proof: Vec<Arc<[u8]>>,
+    /// State root of the shard of the account, as found in the chunk header of the block.
+    pub state_root: CryptoHash,
+    /// Proof of inclusion of `state_root` in the `prev_state_root` of the block.
+    pub state_root_proof: MerklePath,
+    pub block_header_lite: LightClientBlockLiteView,
+    /// Proof of inclusion of the block in the block merkle tree of the light client head.
+    pub block_proof: MerklePath,
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum StateProofError {
+    #[error("trie node {0} is missing from the proof")]
+    MissingTrieNode(CryptoHash),
+    #[error("trie node {0} cannot be decoded")]
+    InvalidTrieNode(CryptoHash),
+    #[error("trie proof does not match the value")]
+    InvalidValue,
+    #[error("state root is not included in the block")]
+    InvalidStateRootProof,
+    #[error("block is not included in the block merkle tree of the head")]
+    InvalidBlockProof,
+}
+
+/// Checks `proof` against `head`, a block header verified by the light client. The head has to
+/// be the `light_client_head` the proof was requested for.
+pub fn verify_contract_state_proof(
+    head: &LightClientBlockLiteView,
+    proof: &ContractStateProof,
+) -> Result<(), StateProofError> {
+    let block_hash = proof.block_header_lite.hash();
+    if compute_root_from_path(&proof.block_proof, block_hash) != head.inner_lite.block_merkle_root {
+        return Err(StateProofError::InvalidBlockProof);
+    }
+    if compute_root_from_path(&proof.state_root_proof, CryptoHash::hash_borsh(proof.state_root))
+        != proof.block_header_lite.inner_lite.prev_state_root
+    {
+        return Err(StateProofError::InvalidStateRootProof);
+    }
+    let trie_key =
+        TrieKey::ContractData { account_id: proof.account_id.clone(), key: proof.key.clone() };
+    verify_trie_proof(
+        &proof.state_root,
+        &trie_key.to_vec(),
+        proof.value.as_deref(),
+        &proof.trie_proof,
+    )
+}
+
+/// Checks that `key` maps to `value` in the trie with root `state_root`, or that it is absent if
+/// `value` is `None`, using only the trie nodes in `nodes`.
+pub fn verify_trie_proof(
+    state_root: &CryptoHash,
+    key: &[u8],
+    value: Option<&[u8]>,
+    nodes: &[Arc<[u8]>],
+) -> Result<(), StateProofError> {
+    let nodes: HashMap<CryptoHash, &[u8]> =
+        nodes.iter().map(|node| (hash(node), node.as_ref())).collect();
+    let key = bytes_to_nibbles(key);
+    let mut remaining = key.as_slice();
+    let mut node_hash = *state_root;
+    let found = loop {
+        if node_hash == CryptoHash::default() {
+            break None;
+        }
+        let bytes = nodes.get(&node_hash).ok_or(StateProofError::MissingTrieNode(node_hash))?;
+        let node = ProofNodeWithSize::try_from_slice(bytes)
+            .map_err(|_| StateProofError::InvalidTrieNode(node_hash))?
+            .node;
+        match node {
+            ProofNode::Leaf(node_key, value_ref) => {
+                let node_key =
+                    decode_nibbles(&node_key).ok_or(StateProofError::InvalidTrieNode(node_hash))?;
+                break (remaining == node_key.as_slice()).then_some(value_ref);
+            }
+            ProofNode::Extension(node_key, child) => {
+                let node_key =
+                    decode_nibbles(&node_key).ok_or(StateProofError::InvalidTrieNode(node_hash))?;
+                let Some(rest) = remaining.strip_prefix(node_key.as_slice()) else {
+                    break None;
+                };
+                remaining = rest;
+                node_hash = child;
+            }
+            ProofNode::BranchNoValue(children) | ProofNode::BranchWithValue(_, children)
+                if !remaining.is_empty() =>
+            {
+                let Some(child) = children.0[usize::from(remaining[0])] else {
+                    break None;
+                };
+                remaining = &remaining[1..];
+                node_hash = child;
+            }
+            ProofNode::BranchNoValue(_) => break None,
+            ProofNode::BranchWithValue(value_ref, _) => break Some(value_ref),
+        }
+    };
+    let matches = match (found, value) {
+        (None, None) => true,
+        (Some(value_ref), Some(value)) => {
+            value_ref.length as usize == value.len() && value_ref.hash == hash(value)
+        }
+        _ => false,
+    };
+    if !matches {
+        return Err(StateProofError::InvalidValue);
+    }
+    Ok(())
+}
+
+fn bytes_to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect()
+}
+
+/// Decodes a hex-prefix encoded key of a leaf or an extension node into nibbles.
+fn decode_nibbles(encoded: &[u8]) -> Option<Vec<u8>> {
+    let (first, rest) = encoded.split_first()?;
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // An odd number of nibbles keeps the first nibble in the flags byte.
+    if first & 0x10 != 0 {
+        nibbles.push(first & 0x0f);
+    }
+    nibbles.extend(bytes_to_nibbles(rest));
+    Some(nibbles)
+}
+
+/// Mirrors the encoding of `RawTrieNodeWithSize` from `near-store`, which stores the trie nodes.
+/// Only decoding is needed to walk a proof.
+#[derive(BorshDeserialize)]
+struct ProofNodeWithSize {
+    node: ProofNode,
+    #[allow(dead_code)]
+    memory_usage: u64,
+}
+
+#[derive(BorshDeserialize)]
+enum ProofNode {
+    Leaf(Vec<u8>, ValueRef),
+    BranchNoValue(ProofChildren),
+    BranchWithValue(ValueRef, ProofChildren),
+    Extension(Vec<u8>, CryptoHash),
+}
+
+/// Children of a branch, encoded as a bitmap of the present children followed by their hashes.
+struct ProofChildren([Option<CryptoHash>; 16]);
+
+impl BorshDeserialize for ProofChildren {
+    fn deserialize_reader<R: std::io::Read>(rd: &mut R) -> std::io::Result<Self> {
+        let mut bitmap = u16::deserialize_reader(rd)?;
+        let mut children = [None; 16];
+        while bitmap != 0 {
+            let idx = bitmap.trailing_zeros() as usize;
+            bitmap &= bitmap - 1;
+            children[idx] = Some(CryptoHash::deserialize_reader(rd)?);
+        }
+        Ok(Self(children))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::{combine_hash, merklize, Direction, MerklePathItem};
+    use crate::views::BlockHeaderInnerLiteView;
+
+    /// Encodes a trie with a single leaf holding `value` under `key`.
+    fn single_leaf_trie(key: &[u8], value: &[u8]) -> (CryptoHash, Arc<[u8]>) {
+        let mut node = vec![0u8];
+        // Hex-prefix encoding of a leaf with an even number of nibbles.
+        let mut encoded_key = vec![0x20];
+        encoded_key.extend_from_slice(key);
+        node.extend(borsh::to_vec(&encoded_key).unwrap());
+        node.extend(borsh::to_vec(&(value.len() as u32)).unwrap());
+        node.extend(hash(value).as_bytes());
+        node.extend(42u64.to_le_bytes());
+        (hash(&node), node.into())
+    }
+
+    fn test_proof(value: &[u8]) -> (LightClientBlockLiteView, ContractStateProof) {
+        let account_id: AccountId = "alice.near".parse().unwrap();
+        let key = b"key".to_vec();
+        let trie_key = TrieKey::ContractData { account_id: account_id.clone(), key: key.clone() };
+        let (state_root, node) = single_leaf_trie(&trie_key.to_vec(), value);
+
+        let state_roots = [hash(b"shard0"), state_root, hash(b"shard2")];
+        let (prev_state_root, state_root_proofs) = merklize(&state_roots);
+        let block_header_lite = LightClientBlockLiteView {
+            prev_block_hash: hash(b"prev"),
+            inner_rest_hash: hash(b"rest"),
+            inner_lite: BlockHeaderInnerLiteView {
+                height: 1,
+                epoch_id: CryptoHash::default(),
+                next_epoch_id: CryptoHash::default(),
+                prev_state_root,
+                outcome_root: CryptoHash::default(),
+                timestamp: 0,
+                timestamp_nanosec: 0,
+                next_bp_hash: CryptoHash::default(),
+                block_merkle_root: CryptoHash::default(),
+            },
+        };
+        // The leaves of the block merkle tree are the block hashes themselves.
+        let other_block_hash = hash(b"block0");
+        let block_proof =
+            vec![MerklePathItem { hash: other_block_hash, direction: Direction::Left }];
+        let mut head = block_header_lite.clone();
+        head.inner_lite.height = 5;
+        head.inner_lite.block_merkle_root =
+            combine_hash(&other_block_hash, &block_header_lite.hash());
+
+        let proof = ContractStateProof {
+            account_id,
+            key,
+            value: Some(value.to_vec()),
+            trie_proof: vec![node],
+            state_root,
+            state_root_proof: state_root_proofs[1].clone(),
+            block_header_lite,
+            block_proof,
+        };
+        (head, proof)
+    }
+
+    #[test]
+    fn test_verify_trie_proof() {
+        let (state_root, node) = single_leaf_trie(b"key", b"value");
+        let nodes = [node];
+        verify_trie_proof(&state_root, b"key", Some(b"value"), &nodes).unwrap();
+        verify_trie_proof(&state_root, b"kez", None, &nodes).unwrap();
+        assert_eq!(
+            verify_trie_proof(&state_root, b"key", Some(b"other"), &nodes),
+            Err(StateProofError::InvalidValue)
+        );
+        assert_eq!(
+            verify_trie_proof(&state_root, b"key", None, &nodes),
+            Err(StateProofError::InvalidValue)
+        );
+        assert_eq!(
+            verify_trie_proof(&state_root, b"key", Some(b"value"), &[]),
+            Err(StateProofError::MissingTrieNode(state_root))
+        );
+        verify_trie_proof(&CryptoHash::default(), b"key", None, &[]).unwrap();
+    }
+
+    #[test]
+    fn test_verify_contract_state_proof() {
+        let (head, proof) = test_proof(b"value");
+        verify_contract_state_proof(&head, &proof).unwrap();
+
+        let mut bad_proof = proof.clone();
+        bad_proof.value = Some(b"other".to_vec());
+        assert_eq!(
+            verify_contract_state_proof(&head, &bad_proof),
+            Err(StateProofError::InvalidValue)
+        );
+
+        let mut bad_proof = proof.clone();
+        bad_proof.state_root_proof.clear();
+        assert_eq!(
+            verify_contract_state_proof(&head, &bad_proof),
+            Err(StateProofError::InvalidStateRootProof)
+        );
+
+        let mut bad_proof = proof.clone();
+        bad_proof.block_header_lite.inner_lite.height = 2;
+        assert_eq!(
+            verify_contract_state_proof(&head, &bad_proof),
+            Err(StateProofError::InvalidBlockProof)
+        );
+
+        let mut bad_proof = proof;
+        bad_proof.account_id = "bob.near".parse().unwrap();
+        assert_eq!(
+            verify_contract_state_proof(&head, &bad_proof),
+            Err(StateProofError::InvalidValue)
+        );
+    }
+}
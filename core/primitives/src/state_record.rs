@@ -4,11 +4,13 @@ use crate::receipt::{Receipt, ReceivedData};
 use crate::trie_key::trie_key_parsers::{
     parse_account_id_from_access_key_key, parse_account_id_from_account_key,
     parse_account_id_from_contract_code_key, parse_account_id_from_contract_data_key,
-    parse_account_id_from_received_data_key, parse_data_id_from_received_data_key,
-    parse_data_key_from_contract_data_key, parse_public_key_from_access_key_key,
+    parse_account_id_from_global_contract_publisher_key, parse_account_id_from_raw_key,
+    parse_account_id_from_received_data_key, parse_code_hash_from_global_contract_key,
+    parse_data_id_from_received_data_key, parse_data_key_from_contract_data_key,
+    parse_public_key_from_access_key_key,
 };
 use crate::trie_key::{col, TrieKey};
-use crate::types::{AccountId, StoreKey, StoreValue};
+use crate::types::{AccountId, StorageUsage, StoreKey, StoreValue};
 use borsh::BorshDeserialize;
 use near_crypto::PublicKey;
 use serde_with::base64::Base64;
@@ -43,6 +45,22 @@ pub enum StateRecord {
     /// Delayed Receipt.
     /// The receipt was delayed because the shard was overwhelmed.
     DelayedReceipt(Box<Receipt>),
+    /// Contract code published to the global contract registry, encoded in base64.
+    /// Replicated to every shard.
+    GlobalContractCode {
+        #[serde_as(as = "Base64")]
+        code: Vec<u8>,
+    },
+    /// An account publishing the code with the given hash to the global contract registry.
+    /// Replicated to every shard.
+    GlobalContractPublisher { code_hash: CryptoHash, account_id: AccountId },
+    /// Storage usage charged to the account for publishing the code with the given hash to the
+    /// global contract registry.
+    GlobalContractOwner {
+        account_id: AccountId,
+        code_hash: CryptoHash,
+        storage_usage: StorageUsage,
+    },
 }
 
 impl StateRecord {
@@ -103,6 +121,29 @@ impl StateRecord {
                 let receipt = Receipt::try_from_slice(&value)?;
                 Some(StateRecord::DelayedReceipt(Box::new(receipt)))
             }
+            col::EXTENDED_COLUMN if key.len() == TrieKey::BandwidthSchedulerState.len() => None,
+            col::EXTENDED_COLUMN if key.starts_with(&col::GLOBAL_CONTRACT_CODE) => {
+                Some(StateRecord::GlobalContractCode { code: value })
+            }
+            col::EXTENDED_COLUMN if key.starts_with(&col::GLOBAL_CONTRACT_PUBLISHER) => {
+                Some(StateRecord::GlobalContractPublisher {
+                    code_hash: parse_code_hash_from_global_contract_key(&key)?,
+                    account_id: parse_account_id_from_global_contract_publisher_key(&key)?,
+                })
+            }
+            col::EXTENDED_COLUMN if key.starts_with(&col::GLOBAL_CONTRACT_OWNER) => {
+                let account_id = parse_account_id_from_raw_key(&key)?.ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "raw key does not have an account id to be TrieKey::GlobalContractOwner",
+                    )
+                })?;
+                Some(StateRecord::GlobalContractOwner {
+                    account_id,
+                    code_hash: parse_code_hash_from_global_contract_key(&key)?,
+                    storage_usage: StorageUsage::try_from_slice(&value)?,
+                })
+            }
             _ => {
                 println!("key[0]: {} is unreachable", key[0]);
                 None
@@ -119,6 +160,9 @@ impl StateRecord {
             StateRecord::PostponedReceipt { .. } => "PostponedReceipt",
             StateRecord::ReceivedData { .. } => "ReceivedData",
             StateRecord::DelayedReceipt { .. } => "DelayedReceipt",
+            StateRecord::GlobalContractCode { .. } => "GlobalContractCode",
+            StateRecord::GlobalContractPublisher { .. } => "GlobalContractPublisher",
+            StateRecord::GlobalContractOwner { .. } => "GlobalContractOwner",
         }
         .to_string()
     }
@@ -152,6 +196,17 @@ impl Display for StateRecord {
             ),
             StateRecord::PostponedReceipt(receipt) => write!(f, "Postponed receipt {:?}", receipt),
             StateRecord::DelayedReceipt(receipt) => write!(f, "Delayed receipt {:?}", receipt),
+            StateRecord::GlobalContractCode { code } => {
+                write!(f, "Global contract code {:?}: ...", hash(code))
+            }
+            StateRecord::GlobalContractPublisher { code_hash, account_id } => {
+                write!(f, "Global contract publisher {:?},{:?}", code_hash, account_id)
+            }
+            StateRecord::GlobalContractOwner { account_id, code_hash, storage_usage } => write!(
+                f,
+                "Global contract owner {:?},{:?}: {:?}",
+                account_id, code_hash, storage_usage
+            ),
         }
     }
 }
@@ -171,20 +226,26 @@ fn to_printable(blob: &[u8]) -> String {
     }
 }
 
-pub fn state_record_to_account_id(state_record: &StateRecord) -> &AccountId {
+/// Returns the account the record belongs to, or `None` for the records of the global contract
+/// registry which are replicated to every shard.
+pub fn state_record_to_account_id(state_record: &StateRecord) -> Option<&AccountId> {
     match state_record {
         StateRecord::Account { account_id, .. }
         | StateRecord::AccessKey { account_id, .. }
         | StateRecord::Contract { account_id, .. }
         | StateRecord::ReceivedData { account_id, .. }
-        | StateRecord::Data { account_id, .. } => account_id,
+        | StateRecord::Data { account_id, .. }
+        | StateRecord::GlobalContractOwner { account_id, .. } => Some(account_id),
         StateRecord::PostponedReceipt(receipt) | StateRecord::DelayedReceipt(receipt) => {
-            receipt.receiver_id()
+            Some(receipt.receiver_id())
+        }
+        StateRecord::GlobalContractCode { .. } | StateRecord::GlobalContractPublisher { .. } => {
+            None
         }
     }
 }
 
 pub fn is_contract_code_key(key: &[u8]) -> bool {
     debug_assert!(!key.is_empty());
-    key[0] == col::CONTRACT_CODE
+    key[0] == col::CONTRACT_CODE || key.starts_with(&col::GLOBAL_CONTRACT_CODE)
 }
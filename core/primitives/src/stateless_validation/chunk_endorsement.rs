@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use crate::sharding::{ChunkHash, ShardChunkHeader};
 use crate::types::EpochId;
-use crate::validator_signer::ValidatorSigner;
+use crate::validator_signer::{ValidatorSigner, ValidatorSignerError};
 use borsh::{BorshDeserialize, BorshSerialize};
 use near_crypto::{PublicKey, Signature};
 use near_primitives_core::types::{AccountId, BlockHeight, ProtocolVersion, ShardId};
@@ -25,12 +25,12 @@ impl ChunkEndorsement {
         chunk_header: &ShardChunkHeader,
         signer: &ValidatorSigner,
         protocol_version: ProtocolVersion,
-    ) -> ChunkEndorsement {
-        if ProtocolFeature::ChunkEndorsementV2.enabled(protocol_version) {
-            ChunkEndorsement::V2(ChunkEndorsementV2::new(epoch_id, chunk_header, signer))
+    ) -> Result<ChunkEndorsement, ValidatorSignerError> {
+        Ok(if ProtocolFeature::ChunkEndorsementV2.enabled(protocol_version) {
+            ChunkEndorsement::V2(ChunkEndorsementV2::new(epoch_id, chunk_header, signer)?)
         } else {
-            ChunkEndorsement::V1(ChunkEndorsementV1::new(chunk_header.chunk_hash(), signer))
-        }
+            ChunkEndorsement::V1(ChunkEndorsementV1::new(chunk_header.chunk_hash(), signer)?)
+        })
     }
 
     pub fn validate_signature(
@@ -67,11 +67,14 @@ pub struct ChunkEndorsementV1 {
 }
 
 impl ChunkEndorsementV1 {
-    pub fn new(chunk_hash: ChunkHash, signer: &ValidatorSigner) -> Self {
+    pub fn new(
+        chunk_hash: ChunkHash,
+        signer: &ValidatorSigner,
+    ) -> Result<Self, ValidatorSignerError> {
         let inner = ChunkEndorsementInner::new(chunk_hash);
         let account_id = signer.validator_id().clone();
-        let signature = signer.sign_chunk_endorsement(&inner);
-        Self { inner, account_id, signature }
+        let signature = signer.sign_chunk_endorsement(&inner)?;
+        Ok(Self { inner, account_id, signature })
     }
 
     pub fn chunk_hash(&self) -> &ChunkHash {
@@ -101,7 +104,7 @@ impl ChunkEndorsementV2 {
         epoch_id: EpochId,
         chunk_header: &ShardChunkHeader,
         signer: &ValidatorSigner,
-    ) -> Self {
+    ) -> Result<Self, ValidatorSignerError> {
         let inner = ChunkEndorsementInner::new(chunk_header.chunk_hash());
         let metadata = ChunkEndorsementMetadata {
             account_id: signer.validator_id().clone(),
@@ -109,9 +112,9 @@ impl ChunkEndorsementV2 {
             epoch_id,
             height_created: chunk_header.height_created(),
         };
-        let signature = signer.sign_chunk_endorsement(&inner);
-        let metadata_signature = signer.sign_chunk_endorsement_metadata(&metadata);
-        Self { inner, signature, metadata, metadata_signature }
+        let signature = signer.sign_chunk_endorsement(&inner)?;
+        let metadata_signature = signer.sign_chunk_endorsement_metadata(&metadata)?;
+        Ok(Self { inner, signature, metadata, metadata_signature })
     }
 
     pub fn chunk_production_key(&self) -> ChunkProductionKey {
@@ -150,6 +153,16 @@ pub struct ChunkEndorsementMetadata {
     height_created: BlockHeight,
 }
 
+impl ChunkEndorsementMetadata {
+    pub fn shard_id(&self) -> ShardId {
+        self.shard_id
+    }
+
+    pub fn height_created(&self) -> BlockHeight {
+        self.height_created
+    }
+}
+
 /// This is the part of the chunk endorsement that is actually being signed.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, ProtocolSchema)]
 pub struct ChunkEndorsementInner {
@@ -11,7 +11,8 @@ use near_schema_checker_lib::ProtocolSchema;
 
 #[cfg(feature = "solomon")]
 use crate::reed_solomon::{ReedSolomonEncoderDeserialize, ReedSolomonEncoderSerialize};
-use crate::{utils::compression::CompressedData, validator_signer::ValidatorSigner};
+use crate::utils::compression::CompressedData;
+use crate::validator_signer::{ValidatorSigner, ValidatorSignerError};
 
 use super::{ChunkProductionKey, SignatureDifferentiator};
 
@@ -30,8 +31,8 @@ impl ChunkContractAccesses {
         next_chunk: ChunkProductionKey,
         contracts: HashSet<CodeHash>,
         signer: &ValidatorSigner,
-    ) -> Self {
-        Self::V1(ChunkContractAccessesV1::new(next_chunk, contracts, signer))
+    ) -> Result<Self, ValidatorSignerError> {
+        Ok(Self::V1(ChunkContractAccessesV1::new(next_chunk, contracts, signer)?))
     }
 
     pub fn contracts(&self) -> &[CodeHash] {
@@ -65,10 +66,10 @@ impl ChunkContractAccessesV1 {
         next_chunk: ChunkProductionKey,
         contracts: HashSet<CodeHash>,
         signer: &ValidatorSigner,
-    ) -> Self {
+    ) -> Result<Self, ValidatorSignerError> {
         let inner = ChunkContractAccessesInner::new(next_chunk, contracts);
-        let signature = signer.sign_chunk_contract_accesses(&inner);
-        Self { inner, signature }
+        let signature = signer.sign_chunk_contract_accesses(&inner)?;
+        Ok(Self { inner, signature })
     }
 
     fn verify_signature(&self, public_key: &PublicKey) -> bool {
@@ -112,8 +113,8 @@ impl ContractCodeRequest {
         next_chunk: ChunkProductionKey,
         contracts: HashSet<CodeHash>,
         signer: &ValidatorSigner,
-    ) -> Self {
-        Self::V1(ContractCodeRequestV1::new(next_chunk, contracts, signer))
+    ) -> Result<Self, ValidatorSignerError> {
+        Ok(Self::V1(ContractCodeRequestV1::new(next_chunk, contracts, signer)?))
     }
 
     pub fn requester(&self) -> &AccountId {
@@ -147,11 +148,11 @@ impl ContractCodeRequestV1 {
         next_chunk: ChunkProductionKey,
         contracts: HashSet<CodeHash>,
         signer: &ValidatorSigner,
-    ) -> Self {
+    ) -> Result<Self, ValidatorSignerError> {
         let inner =
             ContractCodeRequestInner::new(signer.validator_id().clone(), next_chunk, contracts);
-        let signature = signer.sign_contract_code_request(&inner);
-        Self { inner, signature }
+        let signature = signer.sign_contract_code_request(&inner)?;
+        Ok(Self { inner, signature })
     }
 }
 
@@ -197,8 +198,8 @@ impl ContractCodeResponse {
         next_chunk: ChunkProductionKey,
         contracts: &Vec<CodeBytes>,
         signer: &ValidatorSigner,
-    ) -> Self {
-        Self::V1(ContractCodeResponseV1::new(next_chunk, contracts, signer))
+    ) -> Result<Self, ValidatorSignerError> {
+        Ok(Self::V1(ContractCodeResponseV1::new(next_chunk, contracts, signer)?))
     }
 
     pub fn chunk_production_key(&self) -> &ChunkProductionKey {
@@ -227,10 +228,10 @@ impl ContractCodeResponseV1 {
         next_chunk: ChunkProductionKey,
         contracts: &Vec<CodeBytes>,
         signer: &ValidatorSigner,
-    ) -> Self {
+    ) -> Result<Self, ValidatorSignerError> {
         let inner = ContractCodeResponseInner::new(next_chunk, contracts);
-        let signature = signer.sign_contract_code_response(&inner);
-        Self { inner, signature }
+        let signature = signer.sign_contract_code_response(&inner)?;
+        Ok(Self { inner, signature })
     }
 }
 
@@ -375,8 +376,8 @@ impl PartialEncodedContractDeploys {
         key: ChunkProductionKey,
         part: PartialEncodedContractDeploysPart,
         signer: &ValidatorSigner,
-    ) -> Self {
-        Self::V1(PartialEncodedContractDeploysV1::new(key, part, signer))
+    ) -> Result<Self, ValidatorSignerError> {
+        Ok(Self::V1(PartialEncodedContractDeploysV1::new(key, part, signer)?))
     }
 
     pub fn chunk_production_key(&self) -> &ChunkProductionKey {
@@ -415,10 +416,10 @@ impl PartialEncodedContractDeploysV1 {
         key: ChunkProductionKey,
         part: PartialEncodedContractDeploysPart,
         signer: &ValidatorSigner,
-    ) -> Self {
+    ) -> Result<Self, ValidatorSignerError> {
         let inner = PartialEncodedContractDeploysInner::new(key, part);
-        let signature = signer.sign_partial_encoded_contract_deploys(&inner);
-        Self { inner, signature }
+        let signature = signer.sign_partial_encoded_contract_deploys(&inner)?;
+        Ok(Self { inner, signature })
     }
 }
 
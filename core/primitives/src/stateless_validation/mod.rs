@@ -11,6 +11,7 @@ pub mod partial_witness;
 pub mod state_witness;
 pub mod stored_chunk_state_transition_data;
 pub mod validator_assignment;
+pub mod witness_size_attribution;
 
 /// An arbitrary static string to make sure that this struct cannot be
 /// serialized to look identical to another serialized struct. For chunk
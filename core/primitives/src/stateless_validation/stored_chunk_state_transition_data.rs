@@ -4,6 +4,7 @@ use near_primitives_core::hash::CryptoHash;
 use near_schema_checker_lib::ProtocolSchema;
 
 use super::contract_distribution::{CodeBytes, CodeHash};
+use super::witness_size_attribution::StorageProofSizeAttribution;
 
 /// Stored on disk for each chunk, including missing chunks, in order to
 /// produce a chunk state witness when needed.
@@ -14,6 +15,7 @@ pub enum StoredChunkStateTransitionData {
     V1(StoredChunkStateTransitionDataV1),
     V2(StoredChunkStateTransitionDataV2),
     V3(StoredChunkStateTransitionDataV3),
+    V4(StoredChunkStateTransitionDataV4),
 }
 
 #[derive(Debug, BorshSerialize, BorshDeserialize, ProtocolSchema)]
@@ -64,3 +66,23 @@ pub struct StoredChunkStateTransitionDataV3 {
     /// Contracts that are deployed during the state transition.
     pub contract_deploys: Vec<CodeBytes>,
 }
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, ProtocolSchema)]
+pub struct StoredChunkStateTransitionDataV4 {
+    /// The partial state that is needed to apply the state transition,
+    /// whether it is a new chunk state transition or a implicit missing chunk
+    /// state transition.
+    pub base_state: PartialState,
+    /// If this is a new chunk state transition, the hash of the receipts that
+    /// were used to apply the state transition. This is redundant information,
+    /// but is used to validate against `StateChunkWitness::exact_receipts_hash`
+    /// to ease debugging of why a state witness may be incorrect.
+    pub receipts_hash: CryptoHash,
+    /// The code-hashes of the contracts that are accessed (called) during the state transition.
+    pub contract_accesses: Vec<CodeHash>,
+    /// Contracts that are deployed during the state transition.
+    pub contract_deploys: Vec<CodeBytes>,
+    /// Which transactions and receipts recorded `base_state`. Not used for validation, only
+    /// reported for debugging.
+    pub size_attribution: StorageProofSizeAttribution,
+}
@@ -0,0 +1,222 @@
+//! Breakdown of the storage proof of a state transition by the transactions and receipts that
+//! recorded it, to find out what makes a state witness large.
+use std::collections::HashMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_primitives_core::hash::CryptoHash;
+use near_primitives_core::types::AccountId;
+use near_schema_checker_lib::ProtocolSchema;
+
+use super::state_witness::ChunkStateWitness;
+
+/// Transaction or receipt that recorded a part of the storage proof.
+#[derive(
+    BorshSerialize, BorshDeserialize, serde::Serialize, Clone, Debug, PartialEq, Eq, ProtocolSchema,
+)]
+#[serde(tag = "kind", content = "hash", rename_all = "snake_case")]
+pub enum StorageProofSource {
+    /// Conversion of a transaction into a receipt, identified by the transaction hash.
+    Transaction(CryptoHash),
+    /// Application of a receipt, identified by the receipt id.
+    Receipt(CryptoHash),
+}
+
+/// Part of the storage proof recorded while processing a single transaction or receipt.
+///
+/// Trie nodes are recorded only once, so a node read by several receipts is attributed to the
+/// first one of them.
+#[derive(
+    BorshSerialize, BorshDeserialize, serde::Serialize, Clone, Debug, PartialEq, Eq, ProtocolSchema,
+)]
+pub struct StorageProofContribution {
+    pub source: StorageProofSource,
+    /// Signer of the transaction, or receiver of the receipt.
+    pub account_id: AccountId,
+    /// Size of the trie nodes and values recorded.
+    pub recorded_size: u64,
+    /// Increase of the upper bound estimation of the storage proof size, which is what is
+    /// checked against the storage proof size limits. Also covers removals and contract code.
+    pub recorded_size_upper_bound: u64,
+}
+
+/// Storage proof recorded for one account, summed over its transactions and receipts.
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct AccountStorageProofSize {
+    pub account_id: AccountId,
+    pub count: usize,
+    pub recorded_size: u64,
+    pub recorded_size_upper_bound: u64,
+}
+
+/// The largest parts of a storage proof, see [`StorageProofSizeAttribution::summary`].
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct StorageProofSizeSummary {
+    pub total_size: u64,
+    pub total_size_upper_bound: u64,
+    pub size_soft_limit: u64,
+    pub unattributed_size: u64,
+    pub largest_accounts: Vec<AccountStorageProofSize>,
+    pub largest_contributions: Vec<StorageProofContribution>,
+}
+
+/// Breakdown of the storage proof of a state transition.
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    serde::Serialize,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    ProtocolSchema,
+)]
+pub struct StorageProofSizeAttribution {
+    /// In the order in which the transactions and receipts were processed.
+    pub contributions: Vec<StorageProofContribution>,
+    /// Size of the whole storage proof. Includes the parts that are not recorded by any
+    /// transaction or receipt, e.g. reading the delayed receipts queue or updating the validator
+    /// accounts.
+    pub total_size: u64,
+    pub total_size_upper_bound: u64,
+    /// Soft limit of the upper bound, once it's reached no more receipts are processed in the
+    /// chunk.
+    pub size_soft_limit: u64,
+}
+
+impl StorageProofSizeAttribution {
+    /// Size of the storage proof recorded outside of transactions and receipts.
+    pub fn unattributed_size(&self) -> u64 {
+        let attributed: u64 = self.contributions.iter().map(|c| c.recorded_size).sum();
+        self.total_size.saturating_sub(attributed)
+    }
+
+    /// Contributions with the largest upper bound first, at most `limit` of them.
+    pub fn largest_contributions(&self, limit: usize) -> Vec<&StorageProofContribution> {
+        let mut contributions: Vec<_> = self.contributions.iter().collect();
+        contributions.sort_by_key(|c| std::cmp::Reverse(c.recorded_size_upper_bound));
+        contributions.truncate(limit);
+        contributions
+    }
+
+    /// Contributions summed up per account, the largest upper bound first.
+    pub fn by_account(&self) -> Vec<AccountStorageProofSize> {
+        let mut accounts = HashMap::<&AccountId, AccountStorageProofSize>::new();
+        for contribution in &self.contributions {
+            let account = accounts.entry(&contribution.account_id).or_insert_with(|| {
+                AccountStorageProofSize {
+                    account_id: contribution.account_id.clone(),
+                    count: 0,
+                    recorded_size: 0,
+                    recorded_size_upper_bound: 0,
+                }
+            });
+            account.count += 1;
+            account.recorded_size += contribution.recorded_size;
+            account.recorded_size_upper_bound += contribution.recorded_size_upper_bound;
+        }
+        let mut accounts: Vec<_> = accounts.into_values().collect();
+        accounts.sort_by(|a, b| {
+            b.recorded_size_upper_bound
+                .cmp(&a.recorded_size_upper_bound)
+                .then_with(|| a.account_id.cmp(&b.account_id))
+        });
+        accounts
+    }
+
+    /// Totals, with at most `limit` of the largest accounts and contributions.
+    pub fn summary(&self, limit: usize) -> StorageProofSizeSummary {
+        let mut largest_accounts = self.by_account();
+        largest_accounts.truncate(limit);
+        StorageProofSizeSummary {
+            total_size: self.total_size,
+            total_size_upper_bound: self.total_size_upper_bound,
+            size_soft_limit: self.size_soft_limit,
+            unattributed_size: self.unattributed_size(),
+            largest_accounts,
+            largest_contributions: self.largest_contributions(limit).into_iter().cloned().collect(),
+        }
+    }
+}
+
+/// Sizes of the parts of a state witness, as they are serialized.
+#[derive(
+    BorshSerialize, BorshDeserialize, serde::Serialize, Clone, Debug, PartialEq, Eq, ProtocolSchema,
+)]
+pub struct WitnessSizeBreakdown {
+    pub witness_size: u64,
+    pub main_state_transition_size: u64,
+    pub source_receipt_proofs_size: u64,
+    pub transactions_size: u64,
+    pub implicit_transitions_size: u64,
+    pub new_transactions_size: u64,
+    pub new_transactions_validation_state_size: u64,
+    /// Which transactions and receipts recorded the storage proof of the main state transition.
+    /// Only known by the chunk producer that produced the witness.
+    pub main_storage_proof: Option<StorageProofSizeAttribution>,
+}
+
+impl WitnessSizeBreakdown {
+    pub fn new(
+        witness: &ChunkStateWitness,
+        main_storage_proof: Option<StorageProofSizeAttribution>,
+    ) -> Self {
+        fn size<T: BorshSerialize + ?Sized>(value: &T) -> u64 {
+            borsh::object_length(value).unwrap() as u64
+        }
+        Self {
+            witness_size: size(witness),
+            main_state_transition_size: size(&witness.main_state_transition),
+            source_receipt_proofs_size: size(&witness.source_receipt_proofs),
+            transactions_size: size(&witness.transactions),
+            implicit_transitions_size: size(&witness.implicit_transitions),
+            new_transactions_size: size(&witness.new_transactions),
+            new_transactions_validation_state_size: size(
+                &witness.new_transactions_validation_state,
+            ),
+            main_storage_proof,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StorageProofContribution, StorageProofSizeAttribution, StorageProofSource};
+    use near_primitives_core::hash::hash;
+
+    fn contribution(account_id: &str, size: u64) -> StorageProofContribution {
+        StorageProofContribution {
+            source: StorageProofSource::Receipt(hash(account_id.as_bytes())),
+            account_id: account_id.parse().unwrap(),
+            recorded_size: size,
+            recorded_size_upper_bound: size * 2,
+        }
+    }
+
+    #[test]
+    fn test_attribution_summary() {
+        let attribution = StorageProofSizeAttribution {
+            contributions: vec![
+                contribution("alice.near", 100),
+                contribution("bob.near", 300),
+                contribution("alice.near", 250),
+            ],
+            total_size: 1000,
+            total_size_upper_bound: 2000,
+            size_soft_limit: 3000,
+        };
+        assert_eq!(attribution.unattributed_size(), 350);
+
+        let largest = attribution.largest_contributions(2);
+        assert_eq!(largest, vec![&attribution.contributions[1], &attribution.contributions[2]]);
+
+        let by_account = attribution.by_account();
+        assert_eq!(by_account.len(), 2);
+        assert_eq!(by_account[0].account_id.as_str(), "alice.near");
+        assert_eq!(by_account[0].count, 2);
+        assert_eq!(by_account[0].recorded_size, 350);
+        assert_eq!(by_account[0].recorded_size_upper_bound, 700);
+        assert_eq!(by_account[1].account_id.as_str(), "bob.near");
+        assert_eq!(by_account[1].recorded_size, 300);
+    }
+}
@@ -6,6 +6,7 @@ use crate::challenge::Challenges;
 use crate::errors::EpochError;
 use crate::hash::CryptoHash;
 
+use crate::shard_layout::{account_id_to_shard_id, ShardLayout};
 use crate::sharding::{ShardChunkHeader, ShardChunkHeaderV3};
 use crate::stateless_validation::chunk_endorsements_bitmap::ChunkEndorsementsBitmap;
 use crate::transaction::{
@@ -381,11 +382,13 @@ impl BlockHeader {
     }
 
     pub fn resign(&mut self, signer: &ValidatorSigner) {
-        let (hash, signature) = signer.sign_block_header_parts(
-            *self.prev_hash(),
-            &self.inner_lite_bytes(),
-            &self.inner_rest_bytes(),
-        );
+        let (hash, signature) = signer
+            .sign_block_header_parts(
+                *self.prev_hash(),
+                &self.inner_lite_bytes(),
+                &self.inner_rest_bytes(),
+            )
+            .unwrap();
         match self {
             BlockHeader::BlockHeaderV1(header) => {
                 let header = Arc::make_mut(header);
@@ -846,6 +849,7 @@ impl TestBlockBuilder {
             self.clock,
             None,
         )
+        .unwrap()
     }
 }
 
@@ -972,11 +976,18 @@ impl Block {
 #[derive(Default)]
 pub struct MockEpochInfoProvider {
     pub validators: HashMap<AccountId, Balance>,
+    /// The shard layout of every epoch, a single shard layout if not set.
+    pub shard_layout: Option<ShardLayout>,
 }
 
 impl MockEpochInfoProvider {
     pub fn new(validators: impl Iterator<Item = (AccountId, Balance)>) -> Self {
-        MockEpochInfoProvider { validators: validators.collect() }
+        MockEpochInfoProvider { validators: validators.collect(), shard_layout: None }
+    }
+
+    pub fn with_shard_layout(mut self, shard_layout: ShardLayout) -> Self {
+        self.shard_layout = Some(shard_layout);
+        self
     }
 }
 
@@ -1008,10 +1019,14 @@ impl EpochInfoProvider for MockEpochInfoProvider {
 
     fn account_id_to_shard_id(
         &self,
-        _account_id: &AccountId,
-        _epoch_id: &EpochId,
+        account_id: &AccountId,
+        epoch_id: &EpochId,
     ) -> Result<ShardId, EpochError> {
-        Ok(ShardId::new(0))
+        Ok(account_id_to_shard_id(account_id, &self.shard_layout(epoch_id)?))
+    }
+
+    fn shard_layout(&self, _epoch_id: &EpochId) -> Result<ShardLayout, EpochError> {
+        Ok(self.shard_layout.clone().unwrap_or_else(ShardLayout::v0_single_shard))
     }
 }
 
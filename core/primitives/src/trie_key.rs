@@ -57,11 +57,26 @@ pub mod col {
     /// (`primitives::receipt::Receipt`).
     pub const BUFFERED_RECEIPT: u8 = 14;
     pub const BANDWIDTH_SCHEDULER_STATE: u8 = 15;
+    /// The first byte of the columns identified by two bytes, the second byte tells them apart.
+    /// It is shared with `BANDWIDTH_SCHEDULER_STATE`, which is valid because
+    /// `TrieKey::BandwidthSchedulerState` is a single byte while the keys of the two byte
+    /// columns are always longer.
+    pub const EXTENDED_COLUMN: u8 = BANDWIDTH_SCHEDULER_STATE;
+    /// This column id is used when storing contract code published to the global contract
+    /// registry, keyed by the hash of the code. The registry is replicated to every shard.
+    pub const GLOBAL_CONTRACT_CODE: [u8; 2] = [EXTENDED_COLUMN, 0];
+    /// This column id is used when storing the accounts that published a code to the global
+    /// contract registry, keyed by the hash of the code and the account. Replicated to every
+    /// shard, the code is kept for as long as it has at least one publisher.
+    pub const GLOBAL_CONTRACT_PUBLISHER: [u8; 2] = [EXTENDED_COLUMN, 1];
+    /// This column id is used when storing the storage usage charged to an `account_id` for
+    /// publishing a code to the global contract registry. Stored only on the shard of the account.
+    pub const GLOBAL_CONTRACT_OWNER: [u8; 2] = [EXTENDED_COLUMN, 2];
     /// All columns except those used for the delayed receipts queue, the yielded promises
     /// queue, and the outgoing receipts buffer, which are global state for the shard.
 
-    // NOTE: NEW_COLUMN = 15 will be the last unique nibble in the trie!
-    // Consider demultiplexing on 15 and using 2-nibble prefixes.
+    // NOTE: NEW_COLUMN = 15 was the last unique nibble in the trie, new columns are
+    // demultiplexed on `EXTENDED_COLUMN` with 2-byte prefixes.
     pub const COLUMNS_WITH_ACCOUNT_ID_IN_KEY: [(u8, &str); 9] = [
         (ACCOUNT, "Account"),
         (CONTRACT_CODE, "ContractCode"),
@@ -91,6 +106,15 @@ pub mod col {
         (BUFFERED_RECEIPT, "BufferedReceipt"),
         (BANDWIDTH_SCHEDULER_STATE, "BandwidthSchedulerState"),
     ];
+
+    pub const EXTENDED_COLUMNS_WITH_ACCOUNT_ID_IN_KEY: [([u8; 2], &str); 1] =
+        [(GLOBAL_CONTRACT_OWNER, "GlobalContractOwner")];
+
+    pub const ALL_EXTENDED_COLUMNS_WITH_NAMES: [([u8; 2], &str); 3] = [
+        (GLOBAL_CONTRACT_CODE, "GlobalContractCode"),
+        (GLOBAL_CONTRACT_PUBLISHER, "GlobalContractPublisher"),
+        (GLOBAL_CONTRACT_OWNER, "GlobalContractOwner"),
+    ];
 }
 
 /// Describes the key of a specific key-value record in a state trie.
@@ -178,6 +202,24 @@ pub enum TrieKey {
         index: u64,
     },
     BandwidthSchedulerState,
+    /// Used to store `Vec<u8>` contract code published to the global contract registry, for a
+    /// given hash of the code. Accounts using the code do not store a copy of it.
+    GlobalContractCode {
+        code_hash: CryptoHash,
+    },
+    /// Used to mark, with an empty value, that a given `AccountId` published the code with a
+    /// given hash to the global contract registry. The code is removed together with its last
+    /// publisher.
+    GlobalContractPublisher {
+        code_hash: CryptoHash,
+        account_id: AccountId,
+    },
+    /// Used to store the `StorageUsage` charged to a given `AccountId` for publishing the code
+    /// with a given hash to the global contract registry.
+    GlobalContractOwner {
+        account_id: AccountId,
+        code_hash: CryptoHash,
+    },
 }
 
 /// Provides `len` function.
@@ -254,6 +296,18 @@ impl TrieKey {
                     + std::mem::size_of_val(index)
             }
             TrieKey::BandwidthSchedulerState => col::BANDWIDTH_SCHEDULER_STATE.len(),
+            TrieKey::GlobalContractCode { code_hash } => {
+                col::GLOBAL_CONTRACT_CODE.len() + code_hash.as_ref().len()
+            }
+            TrieKey::GlobalContractPublisher { code_hash, account_id } => {
+                col::GLOBAL_CONTRACT_PUBLISHER.len() + code_hash.as_ref().len() + account_id.len()
+            }
+            TrieKey::GlobalContractOwner { account_id, code_hash } => {
+                col::GLOBAL_CONTRACT_OWNER.len()
+                    + account_id.len()
+                    + ACCOUNT_DATA_SEPARATOR.len()
+                    + code_hash.as_ref().len()
+            }
         }
     }
 
@@ -337,6 +391,21 @@ impl TrieKey {
                 buf.extend(&index.to_le_bytes());
             }
             TrieKey::BandwidthSchedulerState => buf.push(col::BANDWIDTH_SCHEDULER_STATE),
+            TrieKey::GlobalContractCode { code_hash } => {
+                buf.extend(&col::GLOBAL_CONTRACT_CODE);
+                buf.extend(code_hash.as_ref());
+            }
+            TrieKey::GlobalContractPublisher { code_hash, account_id } => {
+                buf.extend(&col::GLOBAL_CONTRACT_PUBLISHER);
+                buf.extend(code_hash.as_ref());
+                buf.extend(account_id.as_bytes());
+            }
+            TrieKey::GlobalContractOwner { account_id, code_hash } => {
+                buf.extend(&col::GLOBAL_CONTRACT_OWNER);
+                buf.extend(account_id.as_bytes());
+                buf.push(ACCOUNT_DATA_SEPARATOR);
+                buf.extend(code_hash.as_ref());
+            }
         };
         debug_assert_eq!(expected_len, buf.len() - start_len);
     }
@@ -366,6 +435,9 @@ impl TrieKey {
             TrieKey::BufferedReceiptIndices => None,
             TrieKey::BufferedReceipt { .. } => None,
             TrieKey::BandwidthSchedulerState => None,
+            TrieKey::GlobalContractCode { .. } => None,
+            TrieKey::GlobalContractPublisher { .. } => None,
+            TrieKey::GlobalContractOwner { account_id, .. } => Some(account_id.clone()),
         }
     }
 }
@@ -512,9 +584,85 @@ pub mod trie_key_parsers {
             };
             return Ok(Some(account_id));
         }
+        for (col, col_name) in col::EXTENDED_COLUMNS_WITH_ACCOUNT_ID_IN_KEY {
+            let Some(tail) = raw_key.strip_prefix(&col) else {
+                continue;
+            };
+            let Some(account_id) = next_token(tail, ACCOUNT_DATA_SEPARATOR) else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "raw key does not have ACCOUNT_DATA_SEPARATOR to be TrieKey::{}",
+                        col_name
+                    ),
+                ));
+            };
+            return Ok(Some(parse_account_id_from_slice(account_id, col_name)?));
+        }
         Ok(None)
     }
 
+    /// Parses the code hash from the raw key of `TrieKey::GlobalContractCode`,
+    /// `TrieKey::GlobalContractPublisher` or `TrieKey::GlobalContractOwner`.
+    pub fn parse_code_hash_from_global_contract_key(
+        raw_key: &[u8],
+    ) -> Result<CryptoHash, std::io::Error> {
+        let hash_len = CryptoHash::default().as_ref().len();
+        let hash = if raw_key.starts_with(&col::GLOBAL_CONTRACT_OWNER) {
+            raw_key.len().checked_sub(hash_len).map(|start| &raw_key[start..])
+        } else if raw_key.starts_with(&col::GLOBAL_CONTRACT_CODE)
+            || raw_key.starts_with(&col::GLOBAL_CONTRACT_PUBLISHER)
+        {
+            raw_key.get(col::GLOBAL_CONTRACT_CODE.len()..col::GLOBAL_CONTRACT_CODE.len() + hash_len)
+        } else {
+            None
+        };
+        let Some(hash) = hash else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "raw key is not a global contract registry key",
+            ));
+        };
+        CryptoHash::try_from(hash).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Can't parse CryptoHash for a global contract registry key",
+            )
+        })
+    }
+
+    pub fn parse_account_id_from_global_contract_owner_key(
+        raw_key: &[u8],
+    ) -> Result<AccountId, std::io::Error> {
+        let Some(tail) = raw_key.strip_prefix(&col::GLOBAL_CONTRACT_OWNER) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "raw key is not a TrieKey::GlobalContractOwner",
+            ));
+        };
+        let Some(account_id) = next_token(tail, ACCOUNT_DATA_SEPARATOR) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "raw key does not have ACCOUNT_DATA_SEPARATOR to be TrieKey::GlobalContractOwner",
+            ));
+        };
+        parse_account_id_from_slice(account_id, "GlobalContractOwner")
+    }
+
+    pub fn parse_account_id_from_global_contract_publisher_key(
+        raw_key: &[u8],
+    ) -> Result<AccountId, std::io::Error> {
+        let prefix_len =
+            col::GLOBAL_CONTRACT_PUBLISHER.len() + CryptoHash::default().as_ref().len();
+        if !raw_key.starts_with(&col::GLOBAL_CONTRACT_PUBLISHER) || raw_key.len() < prefix_len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "raw key is too short for TrieKey::GlobalContractPublisher",
+            ));
+        }
+        parse_account_id_from_slice(&raw_key[prefix_len..], "GlobalContractPublisher")
+    }
+
     pub fn parse_account_id_from_trie_key_with_separator(
         col: u8,
         raw_key: &[u8],
@@ -577,6 +725,24 @@ pub mod trie_key_parsers {
         res.extend(prefix);
         res
     }
+
+    pub fn get_raw_prefix_for_global_contract_publishers(code_hash: &CryptoHash) -> Vec<u8> {
+        let mut res =
+            Vec::with_capacity(col::GLOBAL_CONTRACT_PUBLISHER.len() + code_hash.as_ref().len());
+        res.extend(&col::GLOBAL_CONTRACT_PUBLISHER);
+        res.extend(code_hash.as_ref());
+        res
+    }
+
+    pub fn get_raw_prefix_for_global_contract_owners(account_id: &AccountId) -> Vec<u8> {
+        let mut res = Vec::with_capacity(
+            col::GLOBAL_CONTRACT_OWNER.len() + account_id.len() + ACCOUNT_DATA_SEPARATOR.len(),
+        );
+        res.extend(&col::GLOBAL_CONTRACT_OWNER);
+        res.extend(account_id.as_bytes());
+        res.push(ACCOUNT_DATA_SEPARATOR);
+        res
+    }
 }
 
 #[cfg(test)]
@@ -808,6 +974,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_key_for_global_contract_code_consistency() {
+        let key = TrieKey::GlobalContractCode { code_hash: CryptoHash::hash_bytes(b"code") };
+        let raw_key = key.to_vec();
+        assert_eq!(raw_key.len(), key.len());
+        assert_eq!(raw_key[..2], col::GLOBAL_CONTRACT_CODE);
+        assert!(trie_key_parsers::parse_account_id_from_raw_key(&raw_key).unwrap().is_none());
+        assert_eq!(key.get_account_id(), None);
+        assert_eq!(
+            trie_key_parsers::parse_code_hash_from_global_contract_key(&raw_key).unwrap(),
+            CryptoHash::hash_bytes(b"code")
+        );
+        // The extended column shares its first byte with the bandwidth scheduler state.
+        assert!(TrieKey::BandwidthSchedulerState.to_vec().len() < raw_key.len());
+        assert!(raw_key.starts_with(&TrieKey::BandwidthSchedulerState.to_vec()));
+    }
+
+    #[test]
+    fn test_key_for_global_contract_owner_consistency() {
+        let code_hash = CryptoHash::hash_bytes(b"code");
+        for account_id_str in OK_ACCOUNT_IDS {
+            let account_id = account_id_str.parse::<AccountId>().unwrap();
+            let key = TrieKey::GlobalContractOwner { account_id: account_id.clone(), code_hash };
+            let raw_key = key.to_vec();
+            assert_eq!(raw_key.len(), key.len());
+            assert!(raw_key.starts_with(
+                &trie_key_parsers::get_raw_prefix_for_global_contract_owners(&account_id)
+            ));
+            assert_eq!(
+                trie_key_parsers::parse_account_id_from_raw_key(&raw_key).unwrap().unwrap(),
+                account_id
+            );
+            assert_eq!(
+                trie_key_parsers::parse_account_id_from_global_contract_owner_key(&raw_key)
+                    .unwrap(),
+                account_id
+            );
+            assert_eq!(
+                trie_key_parsers::parse_code_hash_from_global_contract_key(&raw_key).unwrap(),
+                code_hash
+            );
+            assert_eq!(key.get_account_id(), Some(account_id));
+        }
+    }
+
+    #[test]
+    fn test_key_for_global_contract_publisher_consistency() {
+        let code_hash = CryptoHash::hash_bytes(b"code");
+        for account_id_str in OK_ACCOUNT_IDS {
+            let account_id = account_id_str.parse::<AccountId>().unwrap();
+            let key =
+                TrieKey::GlobalContractPublisher { code_hash, account_id: account_id.clone() };
+            let raw_key = key.to_vec();
+            assert_eq!(raw_key.len(), key.len());
+            assert!(raw_key.starts_with(
+                &trie_key_parsers::get_raw_prefix_for_global_contract_publishers(&code_hash)
+            ));
+            assert_eq!(
+                trie_key_parsers::parse_account_id_from_global_contract_publisher_key(&raw_key)
+                    .unwrap(),
+                account_id
+            );
+            assert_eq!(
+                trie_key_parsers::parse_code_hash_from_global_contract_key(&raw_key).unwrap(),
+                code_hash
+            );
+            // The publishers are replicated to every shard, so they don't belong to the account.
+            assert!(trie_key_parsers::parse_account_id_from_raw_key(&raw_key).unwrap().is_none());
+            assert_eq!(key.get_account_id(), None);
+        }
+    }
+
     #[test]
     fn test_account_id_from_trie_key() {
         for account_id_str in OK_ACCOUNT_IDS {
@@ -116,6 +116,21 @@ impl ValidatorMandates {
 
         Self { config, stake_per_mandate, mandates, partials }
     }
+
+    /// The amount of stake a whole mandate is worth.
+    pub fn stake_per_mandate(&self) -> Balance {
+        self.stake_per_mandate
+    }
+
+    /// The number of whole mandates held by all validators together.
+    pub fn num_mandates(&self) -> usize {
+        self.mandates.len()
+    }
+
+    /// The number of validators holding a partial mandate.
+    pub fn num_partials(&self) -> usize {
+        self.partials.len()
+    }
 }
 
 #[cfg(feature = "rand")]
@@ -8,6 +8,7 @@ use crate::block::{Approval, ApprovalInner, BlockHeader};
 use crate::challenge::ChallengeBody;
 use crate::hash::CryptoHash;
 use crate::network::{AnnounceAccount, PeerId};
+use crate::remote_signer::{RemoteChunkHeaderInner, RemoteSignRequest, RemoteValidatorSigner};
 use crate::sharding::ChunkHash;
 use crate::stateless_validation::chunk_endorsement::{
     ChunkEndorsementInner, ChunkEndorsementMetadata,
@@ -19,9 +20,27 @@ use crate::stateless_validation::contract_distribution::{
 use crate::stateless_validation::partial_witness::PartialEncodedStateWitnessInner;
 use crate::stateless_validation::state_witness::EncodedChunkStateWitness;
 use crate::telemetry::TelemetryInfo;
-use crate::types::{AccountId, BlockHeight, EpochId};
+use crate::types::{AccountId, BlockHeight, BlockHeightDelta, EpochId};
 use crate::utils::compression::CompressedData;
 
+/// Number of heights below the highest signed one within which an approval, block header, chunk
+/// header or chunk endorsement may still be signed. Both the node's signing ledger and the
+/// reference remote signer refuse anything further below.
+///
+/// Chunk state witnesses are validated in parallel, so endorsements for a shard are not
+/// necessarily signed in height order and a plain high watermark would refuse honest signatures.
+pub const SIGNING_WINDOW: BlockHeightDelta = 100;
+
+/// Error returned when a validator signer doesn't produce a signature. Only signers that hold the
+/// key outside of the node can fail, the caller must then not produce or send the message.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidatorSignerError {
+    #[error("signer refused to sign {kind}: {reason}")]
+    Refused { kind: &'static str, reason: String },
+    #[error("failed to get a signature for {kind} from signer: {error}")]
+    Unavailable { kind: &'static str, error: String },
+}
+
 /// Enum for validator signer, that holds validator id and key used for signing data.
 #[derive(Clone, Debug, PartialEq)]
 pub enum ValidatorSigner {
@@ -29,6 +48,8 @@ pub enum ValidatorSigner {
     Empty(EmptyValidatorSigner),
     /// Default validator signer that holds data in memory.
     InMemory(InMemoryValidatorSigner),
+    /// Validator signer that forwards sign requests to a separate process holding the key.
+    Remote(RemoteValidatorSigner),
 }
 
 /// Validator signer that is used to sign blocks and approvals.
@@ -38,6 +59,7 @@ impl ValidatorSigner {
         match self {
             ValidatorSigner::Empty(signer) => signer.validator_id(),
             ValidatorSigner::InMemory(signer) => signer.validator_id(),
+            ValidatorSigner::Remote(signer) => signer.validator_id(),
         }
     }
 
@@ -46,14 +68,27 @@ impl ValidatorSigner {
         match self {
             ValidatorSigner::Empty(signer) => signer.public_key(),
             ValidatorSigner::InMemory(signer) => signer.public_key(),
+            ValidatorSigner::Remote(signer) => signer.public_key(),
         }
     }
 
     /// Serializes telemetry info to JSON and signs it, returning JSON with "signature" field.
-    pub fn sign_telemetry(&self, info: &TelemetryInfo) -> serde_json::Value {
+    pub fn sign_telemetry(
+        &self,
+        info: &TelemetryInfo,
+    ) -> Result<serde_json::Value, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_telemetry(info),
-            ValidatorSigner::InMemory(signer) => signer.sign_telemetry(info),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_telemetry(info)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_telemetry(info)),
+            ValidatorSigner::Remote(signer) => {
+                let mut value =
+                    serde_json::to_value(info).expect("Telemetry must serialize to JSON");
+                let content =
+                    serde_json::to_string(&value).expect("Telemetry must serialize to JSON");
+                let signature = signer.sign(RemoteSignRequest::Telemetry { content })?;
+                value["signature"] = signature.to_string().into();
+                Ok(value)
+            }
         }
     }
 
@@ -63,54 +98,96 @@ impl ValidatorSigner {
         prev_hash: CryptoHash,
         inner_lite: &[u8],
         inner_rest: &[u8],
-    ) -> (CryptoHash, Signature) {
+    ) -> Result<(CryptoHash, Signature), ValidatorSignerError> {
         match self {
             ValidatorSigner::Empty(signer) => {
-                signer.sign_block_header_parts(prev_hash, inner_lite, inner_rest)
+                Ok(signer.sign_block_header_parts(prev_hash, inner_lite, inner_rest))
             }
             ValidatorSigner::InMemory(signer) => {
-                signer.sign_block_header_parts(prev_hash, inner_lite, inner_rest)
+                Ok(signer.sign_block_header_parts(prev_hash, inner_lite, inner_rest))
+            }
+            ValidatorSigner::Remote(signer) => {
+                let hash = BlockHeader::compute_hash(prev_hash, inner_lite, inner_rest);
+                let signature = signer.sign(RemoteSignRequest::BlockHeader {
+                    prev_hash,
+                    inner_lite: inner_lite.to_vec(),
+                    inner_rest: inner_rest.to_vec(),
+                })?;
+                Ok((hash, signature))
             }
         }
     }
 
-    /// Signs given inner of the chunk header.
-    pub fn sign_chunk_hash(&self, chunk_hash: &ChunkHash) -> Signature {
-        match self {
-            ValidatorSigner::Empty(signer) => signer.sign_chunk_hash(chunk_hash),
-            ValidatorSigner::InMemory(signer) => signer.sign_chunk_hash(chunk_hash),
-        }
+    /// Signs given inner of the chunk header, returning the chunk hash and the signature.
+    pub fn sign_chunk_header(
+        &self,
+        inner: RemoteChunkHeaderInner,
+    ) -> Result<(ChunkHash, Signature), ValidatorSignerError> {
+        let chunk_hash = inner.chunk_hash();
+        let signature = match self {
+            ValidatorSigner::Empty(signer) => signer.sign_chunk_hash(&chunk_hash),
+            ValidatorSigner::InMemory(signer) => signer.sign_chunk_hash(&chunk_hash),
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::ChunkHeader(Box::new(inner)))?
+            }
+        };
+        Ok((chunk_hash, signature))
     }
 
     /// Signs approval of given parent hash and reference hash.
-    pub fn sign_approval(&self, inner: &ApprovalInner, target_height: BlockHeight) -> Signature {
+    pub fn sign_approval(
+        &self,
+        inner: &ApprovalInner,
+        target_height: BlockHeight,
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_approval(inner, target_height),
-            ValidatorSigner::InMemory(signer) => signer.sign_approval(inner, target_height),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_approval(inner, target_height)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_approval(inner, target_height)),
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::Approval { inner: inner.clone(), target_height })
+            }
         }
     }
 
     /// Signs chunk endorsement to be sent to block producer.
-    pub fn sign_chunk_endorsement(&self, inner: &ChunkEndorsementInner) -> Signature {
+    pub fn sign_chunk_endorsement(
+        &self,
+        inner: &ChunkEndorsementInner,
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_chunk_endorsement(inner),
-            ValidatorSigner::InMemory(signer) => signer.sign_chunk_endorsement(inner),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_chunk_endorsement(inner)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_chunk_endorsement(inner)),
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::ChunkEndorsement(inner.clone()))
+            }
         }
     }
 
     /// Signs chunk endorsement metadata.
-    pub fn sign_chunk_endorsement_metadata(&self, inner: &ChunkEndorsementMetadata) -> Signature {
+    pub fn sign_chunk_endorsement_metadata(
+        &self,
+        inner: &ChunkEndorsementMetadata,
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_chunk_endorsement_metadata(inner),
-            ValidatorSigner::InMemory(signer) => signer.sign_chunk_endorsement_metadata(inner),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_chunk_endorsement_metadata(inner)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_chunk_endorsement_metadata(inner)),
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::ChunkEndorsementMetadata(inner.clone()))
+            }
         }
     }
 
     /// Signs chunk state witness to be sent to all validators.
-    pub fn sign_chunk_state_witness(&self, witness_bytes: &EncodedChunkStateWitness) -> Signature {
+    pub fn sign_chunk_state_witness(
+        &self,
+        witness_bytes: &EncodedChunkStateWitness,
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_chunk_state_witness(witness_bytes),
-            ValidatorSigner::InMemory(signer) => signer.sign_chunk_state_witness(witness_bytes),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_chunk_state_witness(witness_bytes)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_chunk_state_witness(witness_bytes)),
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::ChunkStateWitness(witness_bytes.clone()))
+            }
         }
     }
 
@@ -118,18 +195,33 @@ impl ValidatorSigner {
     pub fn sign_partial_encoded_state_witness(
         &self,
         part: &PartialEncodedStateWitnessInner,
-    ) -> Signature {
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_partial_encoded_state_witness(part),
-            ValidatorSigner::InMemory(signer) => signer.sign_partial_encoded_state_witness(part),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_partial_encoded_state_witness(part)),
+            ValidatorSigner::InMemory(signer) => {
+                Ok(signer.sign_partial_encoded_state_witness(part))
+            }
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::PartialEncodedStateWitness(part.clone()))
+            }
         }
     }
 
     /// Signs challenge body.
-    pub fn sign_challenge(&self, challenge_body: &ChallengeBody) -> (CryptoHash, Signature) {
+    pub fn sign_challenge(
+        &self,
+        challenge_body: &ChallengeBody,
+    ) -> Result<(CryptoHash, Signature), ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_challenge(challenge_body),
-            ValidatorSigner::InMemory(signer) => signer.sign_challenge(challenge_body),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_challenge(challenge_body)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_challenge(challenge_body)),
+            ValidatorSigner::Remote(signer) => {
+                let hash = CryptoHash::hash_borsh(challenge_body);
+                Ok((
+                    hash,
+                    signer.sign(RemoteSignRequest::Challenge(Box::new(challenge_body.clone())))?,
+                ))
+            }
         }
     }
 
@@ -139,50 +231,78 @@ impl ValidatorSigner {
         account_id: &AccountId,
         peer_id: &PeerId,
         epoch_id: &EpochId,
-    ) -> Signature {
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
             ValidatorSigner::Empty(signer) => {
-                signer.sign_account_announce(account_id, peer_id, epoch_id)
+                Ok(signer.sign_account_announce(account_id, peer_id, epoch_id))
             }
             ValidatorSigner::InMemory(signer) => {
-                signer.sign_account_announce(account_id, peer_id, epoch_id)
+                Ok(signer.sign_account_announce(account_id, peer_id, epoch_id))
             }
+            ValidatorSigner::Remote(signer) => signer.sign(RemoteSignRequest::AccountAnnounce {
+                account_id: account_id.clone(),
+                peer_id: peer_id.clone(),
+                epoch_id: *epoch_id,
+            }),
         }
     }
 
     /// Signs the inner contents of a ChunkContractAccesses message.
-    pub fn sign_chunk_contract_accesses(&self, inner: &ChunkContractAccessesInner) -> Signature {
+    pub fn sign_chunk_contract_accesses(
+        &self,
+        inner: &ChunkContractAccessesInner,
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_chunk_contract_accesses(inner),
-            ValidatorSigner::InMemory(signer) => signer.sign_chunk_contract_accesses(inner),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_chunk_contract_accesses(inner)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_chunk_contract_accesses(inner)),
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::ChunkContractAccesses(inner.clone()))
+            }
         }
     }
 
     pub fn sign_partial_encoded_contract_deploys(
         &self,
         inner: &PartialEncodedContractDeploysInner,
-    ) -> Signature {
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_partial_encoded_contract_deploys(inner),
+            ValidatorSigner::Empty(signer) => {
+                Ok(signer.sign_partial_encoded_contract_deploys(inner))
+            }
             ValidatorSigner::InMemory(signer) => {
-                signer.sign_partial_encoded_contract_deploys(inner)
+                Ok(signer.sign_partial_encoded_contract_deploys(inner))
+            }
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::PartialEncodedContractDeploys(inner.clone()))
             }
         }
     }
 
     /// Signs the inner contents of a ContractCodeRequest message.
-    pub fn sign_contract_code_request(&self, inner: &ContractCodeRequestInner) -> Signature {
+    pub fn sign_contract_code_request(
+        &self,
+        inner: &ContractCodeRequestInner,
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_contract_code_request(inner),
-            ValidatorSigner::InMemory(signer) => signer.sign_contract_code_request(inner),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_contract_code_request(inner)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_contract_code_request(inner)),
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::ContractCodeRequest(inner.clone()))
+            }
         }
     }
 
     /// Signs the inner contents of a ContractCodeResponse message.
-    pub fn sign_contract_code_response(&self, inner: &ContractCodeResponseInner) -> Signature {
+    pub fn sign_contract_code_response(
+        &self,
+        inner: &ContractCodeResponseInner,
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_contract_code_response(inner),
-            ValidatorSigner::InMemory(signer) => signer.sign_contract_code_response(inner),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_contract_code_response(inner)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_contract_code_response(inner)),
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::ContractCodeResponse(inner.clone()))
+            }
         }
     }
 
@@ -197,27 +317,34 @@ impl ValidatorSigner {
     /// used only for networking purposes and are not persisted on chain.
     /// Moving to proto serialization for stuff stored on chain would be way
     /// harder.
-    pub fn sign_account_key_payload(&self, proto_bytes: &[u8]) -> Signature {
+    pub fn sign_account_key_payload(
+        &self,
+        proto_bytes: &[u8],
+    ) -> Result<Signature, ValidatorSignerError> {
         match self {
-            ValidatorSigner::Empty(signer) => signer.sign_account_key_payload(proto_bytes),
-            ValidatorSigner::InMemory(signer) => signer.sign_account_key_payload(proto_bytes),
+            ValidatorSigner::Empty(signer) => Ok(signer.sign_account_key_payload(proto_bytes)),
+            ValidatorSigner::InMemory(signer) => Ok(signer.sign_account_key_payload(proto_bytes)),
+            ValidatorSigner::Remote(signer) => {
+                signer.sign(RemoteSignRequest::AccountKeyPayload(proto_bytes.to_vec()))
+            }
         }
     }
 
     pub fn compute_vrf_with_proof(
         &self,
         data: &[u8],
-    ) -> (near_crypto::vrf::Value, near_crypto::vrf::Proof) {
+    ) -> Result<(near_crypto::vrf::Value, near_crypto::vrf::Proof), ValidatorSignerError> {
         match self {
             ValidatorSigner::Empty(_) => unimplemented!(),
-            ValidatorSigner::InMemory(signer) => signer.compute_vrf_with_proof(data),
+            ValidatorSigner::InMemory(signer) => Ok(signer.compute_vrf_with_proof(data)),
+            ValidatorSigner::Remote(signer) => signer.compute_vrf_with_proof(data),
         }
     }
 
     /// Used by test infrastructure, only implement if make sense for testing otherwise raise `unimplemented`.
     pub fn write_to_file(&self, path: &Path) -> std::io::Result<()> {
         match self {
-            ValidatorSigner::Empty(_) => unimplemented!(),
+            ValidatorSigner::Empty(_) | ValidatorSigner::Remote(_) => unimplemented!(),
             ValidatorSigner::InMemory(signer) => signer.write_to_file(path),
         }
     }
@@ -235,6 +362,12 @@ impl From<InMemoryValidatorSigner> for ValidatorSigner {
     }
 }
 
+impl From<RemoteValidatorSigner> for ValidatorSigner {
+    fn from(signer: RemoteValidatorSigner) -> Self {
+        ValidatorSigner::Remote(signer)
+    }
+}
+
 /// Test-only signer that "signs" everything with 0s.
 /// Don't use in any production or code that requires signature verification.
 #[derive(smart_default::SmartDefault, Clone, Debug, PartialEq)]
@@ -14,7 +14,10 @@ use crate::errors::TxExecutionError;
 use crate::hash::{hash, CryptoHash};
 use crate::merkle::{combine_hash, MerklePath};
 use crate::network::PeerId;
-use crate::receipt::{ActionReceipt, DataReceipt, DataReceiver, Receipt, ReceiptEnum, ReceiptV1};
+use crate::receipt::{
+    ActionReceipt, DataReceipt, DataReceiver, GlobalContractChange,
+    GlobalContractDistributionReceipt, Receipt, ReceiptEnum, ReceiptV1,
+};
 use crate::serialize::dec_format;
 use crate::sharding::shard_chunk_header_inner::ShardChunkHeaderInnerV4;
 use crate::sharding::{
@@ -26,9 +29,10 @@ use crate::stateless_validation::chunk_endorsements_bitmap::ChunkEndorsementsBit
 use crate::transaction::NonrefundableStorageTransferAction;
 use crate::transaction::{
     Action, AddKeyAction, CreateAccountAction, DeleteAccountAction, DeleteKeyAction,
-    DeployContractAction, ExecutionMetadata, ExecutionOutcome, ExecutionOutcomeWithIdAndProof,
-    ExecutionStatus, FunctionCallAction, PartialExecutionOutcome, PartialExecutionStatus,
-    SignedTransaction, StakeAction, TransferAction,
+    DeployContractAction, DeployContractWithInitAction, DeployGlobalContractAction,
+    ExecutionMetadata, ExecutionOutcome, ExecutionOutcomeWithIdAndProof, ExecutionStatus,
+    FunctionCallAction, PartialExecutionOutcome, PartialExecutionStatus, SignedTransaction,
+    StakeAction, TransferAction, UseGlobalContractAction,
 };
 use crate::types::{
     AccountId, AccountWithPublicKey, Balance, BlockHeight, EpochHeight, EpochId, FunctionArgs, Gas,
@@ -48,7 +52,7 @@ use near_schema_checker_lib::ProtocolSchema;
 use near_time::Utc;
 use serde_with::base64::Base64;
 use serde_with::serde_as;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::ops::Range;
 use std::sync::Arc;
@@ -220,6 +224,10 @@ pub struct ViewStateResult {
 pub struct CallResult {
     pub result: Vec<u8>,
     pub logs: Vec<String>,
+    /// Gas burnt by each stack of Wasm functions in the folded stacks format, if requested
+    /// with `function_gas_profile`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function_gas_profile: Option<String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -313,6 +321,10 @@ pub enum QueryRequest {
         method_name: String,
         #[serde(rename = "args_base64")]
         args: FunctionArgs,
+        /// Attribute the burnt gas to the Wasm functions of the contract. The gas burnt is
+        /// slightly higher than without profiling.
+        #[serde(default, skip_serializing_if = "is_false")]
+        function_gas_profile: bool,
     },
 }
 
@@ -368,6 +380,9 @@ pub struct PeerInfoView {
     pub is_outbound_peer: bool,
     /// Connection nonce.
     pub nonce: u64,
+    /// Number of bytes waiting to be sent to the peer, by message class.
+    #[serde(default)]
+    pub send_queue_bytes: BTreeMap<String, u64>,
 }
 
 /// Information about a Producer: its account name, peer_id and a list of connected peers that
@@ -472,6 +487,54 @@ pub struct NetworkGraphView {
     pub next_hops: HashMap<PeerId, Vec<PeerId>>,
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct TopologyEdgeView {
+    pub peer0: PeerId,
+    pub peer1: PeerId,
+    pub nonce: u64,
+    /// Latest Ping/Pong round trip time over this edge, known only for the edges of this node.
+    pub latency_micros: Option<u64>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct TopologyAccountView {
+    pub account_id: AccountId,
+    pub peer_id: PeerId,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct TopologyProxyView {
+    pub peer_id: PeerId,
+    pub addr: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct TopologyTier1ProxiesView {
+    pub account_key: PublicKey,
+    pub peer_id: PeerId,
+    pub proxies: Vec<TopologyProxyView>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct PeerLatencyView {
+    pub peer_id: PeerId,
+    /// Round trip time of the latest Ping/Pong exchange with the peer.
+    pub latency_micros: u64,
+    pub measured_at: i64,
+}
+
+/// Snapshot of the network topology as seen by a single node.
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct NetworkTopologyView {
+    pub my_peer_id: PeerId,
+    pub edges: Vec<TopologyEdgeView>,
+    /// Known account -> peer mapping, based on the AnnounceAccount messages.
+    pub accounts: Vec<TopologyAccountView>,
+    pub tier1_proxies: Vec<TopologyTier1ProxiesView>,
+    /// Latencies measured by this node, including the peers which are not directly connected.
+    pub latencies: Vec<PeerLatencyView>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
 pub struct LabeledEdgeView {
     pub peer0: u32,
@@ -1170,6 +1233,22 @@ pub enum ActionView {
         delegate_action: DelegateAction,
         signature: Signature,
     },
+    DeployContractWithInit {
+        /// Hash of the deployed code when the view is created from an action.
+        #[serde_as(as = "Base64")]
+        code: Vec<u8>,
+        method_name: String,
+        args: FunctionArgs,
+        gas: Gas,
+    },
+    DeployGlobalContract {
+        /// Hash of the published code when the view is created from an action.
+        #[serde_as(as = "Base64")]
+        code: Vec<u8>,
+    },
+    UseGlobalContract {
+        code_hash: CryptoHash,
+    },
 }
 
 impl From<Action> for ActionView {
@@ -1206,6 +1285,19 @@ impl From<Action> for ActionView {
                 delegate_action: action.delegate_action,
                 signature: action.signature,
             },
+            Action::DeployContractWithInit(action) => ActionView::DeployContractWithInit {
+                code: hash(&action.code).as_ref().to_vec(),
+                method_name: action.method_name,
+                args: action.args.into(),
+                gas: action.gas,
+            },
+            Action::DeployGlobalContract(action) => {
+                let code = hash(&action.code).as_ref().to_vec();
+                ActionView::DeployGlobalContract { code }
+            }
+            Action::UseGlobalContract(action) => {
+                ActionView::UseGlobalContract { code_hash: action.code_hash }
+            }
         }
     }
 }
@@ -1247,6 +1339,20 @@ impl TryFrom<ActionView> for Action {
             ActionView::Delegate { delegate_action, signature } => {
                 Action::Delegate(Box::new(SignedDelegateAction { delegate_action, signature }))
             }
+            ActionView::DeployContractWithInit { code, method_name, args, gas } => {
+                Action::DeployContractWithInit(Box::new(DeployContractWithInitAction {
+                    code,
+                    method_name,
+                    args: args.into(),
+                    gas,
+                }))
+            }
+            ActionView::DeployGlobalContract { code } => {
+                Action::DeployGlobalContract(DeployGlobalContractAction { code })
+            }
+            ActionView::UseGlobalContract { code_hash } => {
+                Action::UseGlobalContract(Box::new(UseGlobalContractAction { code_hash }))
+            }
         })
     }
 }
@@ -1763,6 +1869,89 @@ pub struct FinalExecutionOutcomeWithReceiptView {
     pub receipts: Vec<ReceiptView>,
 }
 
+/// Step-level trace of a receipt execution, similar to the output of Ethereum's
+/// `debug_traceTransaction`. Only produced by the debugging tools replaying receipts.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptExecutionTraceView {
+    pub receipt_id: CryptoHash,
+    pub predecessor_id: AccountId,
+    pub receiver_id: AccountId,
+    /// Total gas burnt by the receipt.
+    pub gas_burnt: Gas,
+    pub failed: bool,
+    /// The function calls of the receipt in the order they were executed.
+    pub function_calls: Vec<FunctionCallTraceView>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCallTraceView {
+    /// Index of the `FunctionCall` action within the receipt.
+    pub action_index: u64,
+    pub method_name: String,
+    pub gas_burnt: Gas,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub steps: Vec<ExecutionTraceStepView>,
+}
+
+/// A host function call made by the contract.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionTraceStepView {
+    pub op: String,
+    pub args: Vec<ExecutionTraceArgView>,
+    /// Gas burnt by the function call before this step.
+    pub gas: Gas,
+    pub gas_cost: Gas,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<ExecutionTraceEventView>,
+}
+
+/// An argument of a host function call, with the pointers into the guest memory or registers
+/// replaced by the data read from them.
+#[serde_as]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionTraceArgView {
+    Value(u64),
+    Bytes(#[serde_as(as = "Base64")] Vec<u8>),
+    Balance(#[serde(with = "dec_format")] Balance),
+}
+
+#[serde_as]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExecutionTraceEventView {
+    StorageRead {
+        #[serde_as(as = "Base64")]
+        key: Vec<u8>,
+        #[serde_as(as = "Option<Base64>")]
+        value: Option<Vec<u8>>,
+    },
+    StorageWrite {
+        #[serde_as(as = "Base64")]
+        key: Vec<u8>,
+        #[serde_as(as = "Base64")]
+        value: Vec<u8>,
+        #[serde_as(as = "Option<Base64>")]
+        evicted: Option<Vec<u8>>,
+    },
+    StorageRemove {
+        #[serde_as(as = "Base64")]
+        key: Vec<u8>,
+        #[serde_as(as = "Option<Base64>")]
+        removed: Option<Vec<u8>>,
+    },
+    PromiseCreated {
+        promise_index: u64,
+        /// `None` for the joint promises created with `promise_and`.
+        receiver_id: Option<AccountId>,
+    },
+}
+
 pub mod validator_stake_view {
     pub use super::ValidatorStakeViewV1;
     use crate::types::validator_stake::ValidatorStake;
@@ -1904,6 +2093,11 @@ pub enum ReceiptEnumView {
         #[serde(default = "default_is_promise")]
         is_promise_resume: bool,
     },
+    GlobalContractDistribution {
+        target_shard: ShardId,
+        already_delivered_shards: Vec<ShardId>,
+        change: GlobalContractChange,
+    },
 }
 
 // Default value used when deserializing ReceiptEnumViews which are missing either the
@@ -1953,6 +2147,13 @@ impl From<Receipt> for ReceiptView {
                         is_promise_resume,
                     }
                 }
+                ReceiptEnum::GlobalContractDistribution(receipt) => {
+                    ReceiptEnumView::GlobalContractDistribution {
+                        target_shard: receipt.target_shard,
+                        already_delivered_shards: receipt.already_delivered_shards,
+                        change: receipt.change,
+                    }
+                }
             },
             priority,
         }
@@ -2010,6 +2211,15 @@ impl TryFrom<ReceiptView> for Receipt {
                         ReceiptEnum::Data(data_receipt)
                     }
                 }
+                ReceiptEnumView::GlobalContractDistribution {
+                    target_shard,
+                    already_delivered_shards,
+                    change,
+                } => ReceiptEnum::GlobalContractDistribution(GlobalContractDistributionReceipt {
+                    target_shard,
+                    already_delivered_shards,
+                    change,
+                }),
             },
             priority: receipt_view.priority,
         }))
@@ -2086,6 +2296,37 @@ fn num_blocks_is_zero(n: &NumBlocks) -> bool {
     n == &0
 }
 
+/// Performance of the validators of an epoch so far, with the kickouts and rewards they would get
+/// if the rest of the epoch went the same way.
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct EpochValidatorPerformanceView {
+    pub epoch_id: EpochId,
+    pub epoch_height: EpochHeight,
+    pub epoch_start_height: BlockHeight,
+    /// Height of the last block included in the stats.
+    pub block_height: BlockHeight,
+    pub epoch_length: BlockHeight,
+    pub validators: Vec<ValidatorPerformanceView>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ValidatorPerformanceView {
+    pub account_id: AccountId,
+    #[serde(with = "dec_format")]
+    pub stake: Balance,
+    pub num_produced_blocks: NumBlocks,
+    pub num_expected_blocks: NumBlocks,
+    pub num_produced_chunks: NumBlocks,
+    pub num_expected_chunks: NumBlocks,
+    pub num_produced_endorsements: NumBlocks,
+    pub num_expected_endorsements: NumBlocks,
+    /// Reason the validator would be kicked out for if the epoch ended now.
+    pub projected_kickout: Option<ValidatorKickoutReason>,
+    /// Reward the validator would get at the end of the epoch.
+    #[serde(with = "dec_format")]
+    pub projected_reward: Balance,
+}
+
 #[derive(
     BorshSerialize,
     BorshDeserialize,
@@ -2375,6 +2616,23 @@ impl From<StateChangeWithCause> for StateChangeWithCauseView {
 
 pub type StateChangesView = Vec<StateChangeWithCauseView>;
 
+/// Result of a transaction dry run, see `EXPERIMENTAL_simulate_tx`.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SimulatedTransactionView {
+    /// Block on top of which the transaction and its receipts were applied.
+    pub block_hash: CryptoHash,
+    pub block_height: BlockHeight,
+    #[serde(flatten)]
+    pub final_execution_outcome: FinalExecutionOutcomeView,
+    /// Gas burnt by the transaction and all its receipts.
+    pub gas_burnt: Gas,
+    /// Tokens burnt by the transaction and all its receipts.
+    #[serde(with = "dec_format")]
+    pub tokens_burnt: Balance,
+    /// Changes the transaction would make to the state, none of which were persisted.
+    pub state_changes: StateChangesView,
+}
+
 /// Maintenance windows view are a vector of maintenance window.
 pub type MaintenanceWindowsView = Vec<Range<BlockHeight>>;
 
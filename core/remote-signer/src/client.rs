@@ -0,0 +1,132 @@
+use crate::metrics;
+use crate::{encode_request, read_response, RemoteSignerAddress, RemoteSignerConfig};
+use near_primitives::remote_signer::{RemoteSignRequest, RemoteSignResponse, RemoteSignerClient};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+use std::sync::mpsc;
+use std::time::Duration;
+
+fn connect(address: &RemoteSignerAddress, timeout: Duration) -> std::io::Result<UnixStream> {
+    let RemoteSignerAddress::Unix(path) = address;
+    let stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    Ok(stream)
+}
+
+/// A request waiting to be sent by the connection thread.
+struct PendingRequest {
+    /// Encoded request, including its length prefix.
+    frame: Vec<u8>,
+    response: mpsc::SyncSender<std::io::Result<RemoteSignResponse>>,
+}
+
+/// Sends sign requests to a remote signer over a single connection.
+///
+/// The connection is owned by a dedicated thread, so that callers don't wait on each other's round
+/// trips: requests that arrive while a batch is in flight are written together as the next batch,
+/// and their responses are read in order. A caller waits for its own response for at most the
+/// configured timeout.
+///
+/// The connection is opened lazily and dropped on any error, so that a restarted signer is
+/// reconnected to on the next request.
+pub struct SocketRemoteSignerClient {
+    config: RemoteSignerConfig,
+    requests: mpsc::Sender<PendingRequest>,
+}
+
+impl std::fmt::Debug for SocketRemoteSignerClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SocketRemoteSignerClient").field("config", &self.config).finish()
+    }
+}
+
+impl SocketRemoteSignerClient {
+    /// Starts the connection thread. It stops once the client is dropped.
+    pub fn new(config: RemoteSignerConfig) -> Self {
+        let (requests, receiver) = mpsc::channel();
+        let thread_config = config.clone();
+        std::thread::Builder::new()
+            .name("remote-signer-client".to_string())
+            .spawn(move || run_connection(&thread_config, &receiver))
+            .expect("failed to start the remote signer client thread");
+        Self { config, requests }
+    }
+
+    fn send_and_wait(&self, request: &RemoteSignRequest) -> std::io::Result<RemoteSignResponse> {
+        let (response, receiver) = mpsc::sync_channel(1);
+        let frame = encode_request(request)?;
+        self.requests
+            .send(PendingRequest { frame, response })
+            .map_err(|_| std::io::Error::other("the remote signer client thread has stopped"))?;
+        match receiver.recv_timeout(self.config.timeout) {
+            Ok(result) => result,
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("no response from the remote signer in {:?}", self.config.timeout),
+            )),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                Err(std::io::Error::other("the remote signer client thread has stopped"))
+            }
+        }
+    }
+}
+
+/// Sends the pending requests in batches until all senders are dropped.
+fn run_connection(config: &RemoteSignerConfig, receiver: &mpsc::Receiver<PendingRequest>) {
+    let mut stream = None;
+    while let Ok(first) = receiver.recv() {
+        let mut batch = VecDeque::from([first]);
+        batch.extend(receiver.try_iter());
+        if let Err(err) = send_batch(&mut stream, config, &mut batch) {
+            tracing::warn!(target: "remote_signer", address = %config.address, ?err, "Remote sign requests failed, reconnecting on the next request");
+            stream = None;
+            for pending in batch {
+                let err = std::io::Error::new(err.kind(), err.to_string());
+                let _ = pending.response.send(Err(err));
+            }
+        }
+    }
+}
+
+/// Writes all requests of the batch, then answers them in order. On error, `batch` keeps the
+/// requests that were not answered.
+fn send_batch(
+    stream: &mut Option<UnixStream>,
+    config: &RemoteSignerConfig,
+    batch: &mut VecDeque<PendingRequest>,
+) -> std::io::Result<()> {
+    let stream = match stream {
+        Some(stream) => stream,
+        None => stream.insert(connect(&config.address, config.timeout)?),
+    };
+    let frames = batch.iter().map(|pending| pending.frame.as_slice()).collect::<Vec<_>>();
+    stream.write_all(&frames.concat())?;
+    stream.flush()?;
+    while !batch.is_empty() {
+        let response = read_response(stream)?;
+        let pending = batch.pop_front().unwrap();
+        // The caller may have given up waiting already.
+        let _ = pending.response.send(Ok(response));
+    }
+    Ok(())
+}
+
+impl RemoteSignerClient for SocketRemoteSignerClient {
+    fn send(&self, request: &RemoteSignRequest) -> std::io::Result<RemoteSignResponse> {
+        let kind = request.kind();
+        let _timer = metrics::REMOTE_SIGNER_REQUEST_TIME.with_label_values(&[kind]).start_timer();
+        let result = self.send_and_wait(request);
+        let outcome = match &result {
+            Ok(RemoteSignResponse::Refused(_)) => "refused",
+            Ok(_) => "signed",
+            Err(err) => {
+                tracing::warn!(target: "remote_signer", kind, address = %self.config.address, ?err, "Remote sign request failed");
+                "error"
+            }
+        };
+        metrics::REMOTE_SIGNER_REQUESTS_TOTAL.with_label_values(&[kind, outcome]).inc();
+        result
+    }
+}
@@ -0,0 +1,154 @@
+//! Signing with a validator key held by a separate process, over a Unix socket.
+//!
+//! The node connects to the signer with [`SocketRemoteSignerClient`] and wraps it in a
+//! [`near_primitives::remote_signer::RemoteValidatorSigner`]. Every request and response is a
+//! borsh-serialized [`RemoteSignRequest`] or [`RemoteSignResponse`], prefixed with its length as
+//! a little-endian `u32`. Requests on one connection are answered in order.
+//!
+//! The connection is neither authenticated nor encrypted, so only Unix sockets are supported: who
+//! may get messages signed is decided by the file system permissions of the socket.
+//!
+//! [`signer::RemoteSigner`] is a reference implementation of the signer side. It only signs the
+//! messages that pass its [`signer::HeightPolicy`].
+
+mod client;
+mod metrics;
+pub mod signer;
+
+pub use client::SocketRemoteSignerClient;
+
+use near_primitives::remote_signer::{RemoteSignRequest, RemoteSignResponse};
+use std::fmt;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Largest message accepted on the wire. Chunk state witnesses are the largest requests.
+const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Address of a remote signer, written as `unix:<path>`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum RemoteSignerAddress {
+    Unix(PathBuf),
+}
+
+impl FromStr for RemoteSignerAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("unix:") {
+            Some(path) => Ok(RemoteSignerAddress::Unix(PathBuf::from(path))),
+            None => Err(format!(
+                "unsupported remote signer address {s:?}, expected `unix:<path>`: connections \
+                to the signer are not authenticated, so it must listen on a Unix socket"
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for RemoteSignerAddress {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<RemoteSignerAddress> for String {
+    fn from(address: RemoteSignerAddress) -> Self {
+        address.to_string()
+    }
+}
+
+impl fmt::Display for RemoteSignerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteSignerAddress::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+fn default_timeout() -> Duration {
+    Duration::from_secs(1)
+}
+
+/// Configuration of the remote signer used by a validator node instead of a local key file.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RemoteSignerConfig {
+    pub address: RemoteSignerAddress,
+    /// Timeout for connecting to the signer, and for sending a request and reading its response.
+    /// Approvals and blocks signed later than the block production delay are useless anyway.
+    #[serde(default = "default_timeout")]
+    pub timeout: Duration,
+}
+
+pub fn write_request(stream: &mut impl Write, request: &RemoteSignRequest) -> std::io::Result<()> {
+    write_message(stream, &borsh::to_vec(request)?)
+}
+
+pub fn read_request(stream: &mut impl Read) -> std::io::Result<RemoteSignRequest> {
+    Ok(borsh::from_slice(&read_message(stream)?)?)
+}
+
+pub fn write_response(
+    stream: &mut impl Write,
+    response: &RemoteSignResponse,
+) -> std::io::Result<()> {
+    write_message(stream, &borsh::to_vec(response)?)
+}
+
+pub fn read_response(stream: &mut impl Read) -> std::io::Result<RemoteSignResponse> {
+    Ok(borsh::from_slice(&read_message(stream)?)?)
+}
+
+/// Serializes the request together with its length prefix, ready to be written to a stream.
+pub(crate) fn encode_request(request: &RemoteSignRequest) -> std::io::Result<Vec<u8>> {
+    encode_message(&borsh::to_vec(request)?)
+}
+
+fn encode_message(message: &[u8]) -> std::io::Result<Vec<u8>> {
+    if message.len() > MAX_MESSAGE_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("message of {} bytes exceeds the limit of {MAX_MESSAGE_SIZE}", message.len()),
+        ));
+    }
+    Ok([&(message.len() as u32).to_le_bytes(), message].concat())
+}
+
+fn write_message(stream: &mut impl Write, message: &[u8]) -> std::io::Result<()> {
+    stream.write_all(&encode_message(message)?)?;
+    stream.flush()
+}
+
+fn read_message(stream: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_MESSAGE_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("message of {len} bytes exceeds the limit of {MAX_MESSAGE_SIZE}"),
+        ));
+    }
+    let mut message = vec![0u8; len];
+    stream.read_exact(&mut message)?;
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RemoteSignerAddress;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_address() {
+        let address: RemoteSignerAddress = "unix:/run/near/signer.sock".parse().unwrap();
+        assert_eq!(address, RemoteSignerAddress::Unix(PathBuf::from("/run/near/signer.sock")));
+        assert_eq!(address.to_string(), "unix:/run/near/signer.sock");
+        // Connections are not authenticated, so the signer may not be exposed over TCP.
+        assert!("127.0.0.1:3031".parse::<RemoteSignerAddress>().is_err());
+    }
+}
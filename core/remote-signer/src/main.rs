@@ -0,0 +1,33 @@
+use anyhow::Context;
+use near_remote_signer::signer::{serve, RemoteSigner};
+use near_remote_signer::RemoteSignerAddress;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+#[derive(clap::Parser)]
+#[clap(
+    about = "Signs messages for a NEAR validator node with a key that the node has no access to"
+)]
+struct Cli {
+    /// Validator key file, in the format of `validator_key.json`.
+    #[clap(long)]
+    key_file: PathBuf,
+    /// Unix socket to listen on, as `unix:<path>`. Anyone allowed to connect to it can get
+    /// messages signed, so restrict the permissions of its directory accordingly.
+    #[clap(long)]
+    address: RemoteSignerAddress,
+    /// File in which the heights of the signed approvals, blocks and chunk endorsements are kept,
+    /// so that conflicting messages are refused across restarts.
+    #[clap(long, default_value = "remote_signer_policy.json")]
+    policy_file: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let env_filter = near_o11y::EnvFilterBuilder::from_env().finish().unwrap();
+    let _subscriber = near_o11y::default_subscriber(env_filter, &Default::default()).global();
+    let cli = <Cli as clap::Parser>::parse();
+    let key = near_crypto::InMemorySigner::from_file(&cli.key_file)
+        .with_context(|| format!("failed to read {}", cli.key_file.display()))?;
+    let signer = RemoteSigner::new(key, Some(cli.policy_file))?;
+    serve(Arc::new(Mutex::new(signer)), &cli.address)
+}
@@ -0,0 +1,24 @@
+use near_o11y::metrics::{
+    exponential_buckets, try_create_histogram_vec, try_create_int_counter_vec, HistogramVec,
+    IntCounterVec,
+};
+use std::sync::LazyLock;
+
+pub(crate) static REMOTE_SIGNER_REQUEST_TIME: LazyLock<HistogramVec> = LazyLock::new(|| {
+    try_create_histogram_vec(
+        "near_remote_signer_request_time",
+        "Time taken by the remote signer to answer a sign request, including reconnecting",
+        &["kind"],
+        Some(exponential_buckets(0.0001, 2.0, 16).unwrap()),
+    )
+    .unwrap()
+});
+
+pub(crate) static REMOTE_SIGNER_REQUESTS_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "near_remote_signer_requests_total",
+        "Number of requests sent to the remote signer, by kind and outcome: signed, refused or error",
+        &["kind", "outcome"],
+    )
+    .unwrap()
+});
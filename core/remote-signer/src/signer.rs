@@ -0,0 +1,379 @@
+//! Reference implementation of the signer side of the protocol.
+use crate::{read_request, write_response, RemoteSignerAddress};
+use anyhow::Context;
+use borsh::BorshDeserialize;
+use near_crypto::InMemorySigner;
+use near_primitives::block::ApprovalInner;
+use near_primitives::hash::CryptoHash;
+use near_primitives::remote_signer::{RemoteSignRequest, RemoteSignResponse};
+use near_primitives::stateless_validation::chunk_endorsement::ChunkEndorsementMetadata;
+use near_primitives::types::{BlockHeight, ShardId};
+use near_primitives::validator_signer::SIGNING_WINDOW;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// A message of one kind, and for chunk headers and endorsements of one shard, that was signed.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SignedPosition {
+    pub kind: String,
+    pub shard_id: Option<ShardId>,
+    pub height: BlockHeight,
+    /// Hash of the signed bytes, so that the very same message can be signed again.
+    pub data_hash: CryptoHash,
+}
+
+/// Refuses to sign an approval, block header, chunk header or chunk endorsement at a height (and
+/// shard) for which a different one was already signed, or more than [`SIGNING_WINDOW`] heights
+/// below the highest signed one.
+///
+/// Telemetry and account key payloads are signed as the raw bytes sent by the node, so they are
+/// refused when those bytes could be taken for one of the messages above. Full chunk state
+/// witnesses are never signed by a validator and are always refused. Other messages are always
+/// signed.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct HeightPolicy {
+    positions: Vec<SignedPosition>,
+}
+
+impl HeightPolicy {
+    /// Checks whether the bytes `data` of the request may be signed, and if so records them as
+    /// signed. Returns the reason of the refusal otherwise.
+    pub fn check_and_record(
+        &mut self,
+        request: &RemoteSignRequest,
+        data: &[u8],
+    ) -> Result<(), String> {
+        let Some((shard_id, height)) = request.signed_position() else {
+            return check_raw_payload(request, data);
+        };
+        let kind = request.kind();
+        let data_hash = CryptoHash::hash_bytes(data);
+        let same_slot =
+            |position: &SignedPosition| position.kind == kind && position.shard_id == shard_id;
+        let mut max_height = None;
+        for position in self.positions.iter().filter(|position| same_slot(position)) {
+            if position.height == height {
+                if position.data_hash != data_hash {
+                    return Err(format!(
+                        "a different {kind} was already signed at height {height}"
+                    ));
+                }
+                return Ok(());
+            }
+            max_height = max_height.max(Some(position.height));
+        }
+        if let Some(max_height) = max_height {
+            let min_height = max_height.saturating_sub(SIGNING_WINDOW);
+            if height < min_height {
+                return Err(format!(
+                    "{kind} at height {height} is below {min_height}, the lowest height still \
+                    tracked below the highest signed height {max_height}"
+                ));
+            }
+        }
+        let min_height = max_height.unwrap_or(height).max(height).saturating_sub(SIGNING_WINDOW);
+        self.positions.retain(|position| !same_slot(position) || position.height >= min_height);
+        self.positions.push(SignedPosition { kind: kind.to_string(), shard_id, height, data_hash });
+        Ok(())
+    }
+}
+
+/// Checks the requests whose signed bytes are chosen by the node rather than derived by the signer
+/// from a typed message, so that they can't be used to sign a message protected by the policy.
+fn check_raw_payload(request: &RemoteSignRequest, data: &[u8]) -> Result<(), String> {
+    match request {
+        RemoteSignRequest::ChunkStateWitness(_) => {
+            Err("chunk state witnesses are only signed in parts".to_string())
+        }
+        RemoteSignRequest::Telemetry { .. } | RemoteSignRequest::AccountKeyPayload(_) => {
+            match protected_message_kind(data) {
+                Some(kind) => Err(format!("{} payload could be taken for {kind}", request.kind())),
+                None => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the kind of the policy-protected message that `data` could be a signature payload of.
+fn protected_message_kind(data: &[u8]) -> Option<&'static str> {
+    if data.len() == CryptoHash::LENGTH {
+        // Block headers and chunk headers are signed as their hash.
+        return Some("a block or chunk header hash");
+    }
+    if <(ApprovalInner, BlockHeight)>::try_from_slice(data).is_ok() {
+        return Some("an approval");
+    }
+    if ChunkEndorsementMetadata::try_from_slice(data).is_ok() {
+        return Some("a chunk endorsement");
+    }
+    None
+}
+
+/// Signs requests with a validator key held in memory, subject to a [`HeightPolicy`].
+///
+/// When a policy file is given, the policy is loaded from it on start and saved to it before each
+/// signature is released, so that a restarted signer doesn't forget what it has signed.
+pub struct RemoteSigner {
+    signer: InMemorySigner,
+    policy: HeightPolicy,
+    policy_file: Option<PathBuf>,
+}
+
+impl RemoteSigner {
+    pub fn new(signer: InMemorySigner, policy_file: Option<PathBuf>) -> anyhow::Result<Self> {
+        let policy = match &policy_file {
+            Some(path) if path.exists() => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                serde_json::from_str(&content)
+                    .with_context(|| format!("failed to parse {}", path.display()))?
+            }
+            _ => HeightPolicy::default(),
+        };
+        Ok(Self { signer, policy, policy_file })
+    }
+
+    pub fn handle(&mut self, request: &RemoteSignRequest) -> RemoteSignResponse {
+        match request {
+            RemoteSignRequest::ValidatorKey => {
+                return RemoteSignResponse::ValidatorKey {
+                    account_id: self.signer.account_id.clone(),
+                    public_key: self.signer.public_key(),
+                };
+            }
+            RemoteSignRequest::Vrf { data } => {
+                let (value, proof) = self.signer.compute_vrf_with_proof(data);
+                return RemoteSignResponse::Vrf { value, proof };
+            }
+            _ => {}
+        }
+        let data = request.data_to_sign().expect("all other requests are signatures");
+        if let Err(reason) = self.policy.check_and_record(request, &data) {
+            tracing::warn!(target: "remote_signer", kind = request.kind(), %reason, "Refusing to sign");
+            return RemoteSignResponse::Refused(reason);
+        }
+        if let Err(err) = self.save_policy() {
+            tracing::error!(target: "remote_signer", ?err, "Failed to save the signing policy");
+            return RemoteSignResponse::Refused(format!(
+                "failed to save the signing policy: {err}"
+            ));
+        }
+        RemoteSignResponse::Signature(self.signer.sign(&data))
+    }
+
+    fn save_policy(&self) -> std::io::Result<()> {
+        let Some(path) = &self.policy_file else {
+            return Ok(());
+        };
+        // The policy must hit the disk before the signature is released, otherwise a crash could
+        // make us forget what we signed.  Sync the new file before renaming it over the old one
+        // and then the directory so that the rename itself is durable.
+        let tmp_path = path.with_extension("tmp");
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(&serde_json::to_vec(&self.policy)?)?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&tmp_path, path)?;
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => std::path::Path::new("."),
+        };
+        std::fs::File::open(dir)?.sync_all()
+    }
+}
+
+fn serve_connection(signer: &Mutex<RemoteSigner>, mut stream: impl Read + Write) {
+    loop {
+        let request = match read_request(&mut stream) {
+            Ok(request) => request,
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return,
+            Err(err) => {
+                tracing::warn!(target: "remote_signer", ?err, "Failed to read request");
+                return;
+            }
+        };
+        let response = signer.lock().unwrap().handle(&request);
+        if let Err(err) = write_response(&mut stream, &response) {
+            tracing::warn!(target: "remote_signer", ?err, "Failed to write response");
+            return;
+        }
+    }
+}
+
+/// Accepts connections on `address` and answers their requests, each connection on its own
+/// thread. Requests are signed one at a time across all connections.
+pub fn serve(
+    signer: Arc<Mutex<RemoteSigner>>,
+    address: &RemoteSignerAddress,
+) -> anyhow::Result<()> {
+    tracing::info!(target: "remote_signer", %address, "Listening for sign requests");
+    let RemoteSignerAddress::Unix(path) = address;
+    let listener =
+        UnixListener::bind(path).with_context(|| format!("failed to bind {}", path.display()))?;
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let signer = signer.clone();
+        std::thread::spawn(move || serve_connection(&signer, stream));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{serve, RemoteSigner};
+    use crate::{RemoteSignerAddress, RemoteSignerConfig, SocketRemoteSignerClient};
+    use near_crypto::{InMemorySigner, KeyType};
+    use near_primitives::block::{Approval, ApprovalInner};
+    use near_primitives::hash::CryptoHash;
+    use near_primitives::remote_signer::{
+        RemoteChunkHeaderInner, RemoteSignRequest, RemoteSignResponse, RemoteValidatorSigner,
+    };
+    use near_primitives::sharding::ShardChunkHeaderInnerV1;
+    use near_primitives::stateless_validation::state_witness::EncodedChunkStateWitness;
+    use near_primitives::types::ShardId;
+    use near_primitives::utils::compression::CompressedData;
+    use near_primitives::validator_signer::{
+        InMemoryValidatorSigner, ValidatorSigner, ValidatorSignerError, SIGNING_WINDOW,
+    };
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    fn test_signer() -> InMemorySigner {
+        InMemorySigner::from_seed("test0".parse().unwrap(), KeyType::ED25519, "test0")
+    }
+
+    fn approval(target_height: u64, parent_hash: CryptoHash) -> RemoteSignRequest {
+        RemoteSignRequest::Approval {
+            inner: ApprovalInner::Endorsement(parent_hash),
+            target_height,
+        }
+    }
+
+    fn chunk_header(height_created: u64, shard_id: u64, tx_root: CryptoHash) -> RemoteSignRequest {
+        RemoteSignRequest::ChunkHeader(Box::new(RemoteChunkHeaderInner::V2(
+            ShardChunkHeaderInnerV1 {
+                prev_block_hash: CryptoHash::default(),
+                prev_state_root: CryptoHash::default(),
+                prev_outcome_root: CryptoHash::default(),
+                encoded_merkle_root: CryptoHash::default(),
+                encoded_length: 0,
+                height_created,
+                shard_id: ShardId::new(shard_id),
+                prev_gas_used: 0,
+                gas_limit: 0,
+                prev_balance_burnt: 0,
+                prev_outgoing_receipts_root: CryptoHash::default(),
+                tx_root,
+                prev_validator_proposals: vec![],
+            },
+        )))
+    }
+
+    fn sign(signer: &mut RemoteSigner, request: RemoteSignRequest) -> bool {
+        matches!(signer.handle(&request), RemoteSignResponse::Signature(_))
+    }
+
+    #[test]
+    fn test_height_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy_file = dir.path().join("policy.json");
+        let mut signer = RemoteSigner::new(test_signer(), Some(policy_file.clone())).unwrap();
+        let parent = CryptoHash::hash_bytes(b"parent");
+        let fork = CryptoHash::hash_bytes(b"fork");
+
+        assert!(sign(&mut signer, approval(10, parent)));
+        // Signing the very same approval again is harmless.
+        assert!(sign(&mut signer, approval(10, parent)));
+        assert!(!sign(&mut signer, approval(10, fork)));
+        assert!(sign(&mut signer, approval(11, fork)));
+        // Heights below the highest signed one are fine as long as they are within the window.
+        assert!(sign(&mut signer, approval(9, parent)));
+        assert!(!sign(&mut signer, approval(9, fork)));
+        // Messages without a height are not restricted.
+        assert!(sign(&mut signer, RemoteSignRequest::AccountKeyPayload(vec![1, 2, 3])));
+
+        // Chunk headers are restricted per shard.
+        let tx_root = CryptoHash::hash_bytes(b"txs");
+        assert!(sign(&mut signer, chunk_header(10, 0, tx_root)));
+        assert!(!sign(&mut signer, chunk_header(10, 0, CryptoHash::default())));
+        assert!(sign(&mut signer, chunk_header(10, 1, CryptoHash::default())));
+        assert!(sign(&mut signer, chunk_header(9, 1, tx_root)));
+        assert!(sign(&mut signer, chunk_header(10 + SIGNING_WINDOW + 1, 1, tx_root)));
+        assert!(!sign(&mut signer, chunk_header(10, 1, tx_root)));
+        assert!(!sign(&mut signer, chunk_header(8, 1, tx_root)));
+        // Shard 0 is unaffected by the window of shard 1.
+        assert!(sign(&mut signer, chunk_header(8, 0, tx_root)));
+
+        // The policy survives a restart of the signer.
+        let mut signer = RemoteSigner::new(test_signer(), Some(policy_file)).unwrap();
+        assert!(!sign(&mut signer, approval(11, parent)));
+        assert!(sign(&mut signer, approval(12, parent)));
+    }
+
+    #[test]
+    fn test_raw_payload_cannot_bypass_policy() {
+        let mut signer = RemoteSigner::new(test_signer(), None).unwrap();
+        let parent = CryptoHash::hash_bytes(b"parent");
+        let fork = CryptoHash::hash_bytes(b"fork");
+        assert!(sign(&mut signer, approval(10, parent)));
+
+        // The bytes signed for a conflicting approval, a header hash or a state witness are not
+        // signed when sent as a raw payload.
+        let fork_approval = Approval::get_data_for_sig(&ApprovalInner::Endorsement(fork), 10);
+        assert!(!sign(&mut signer, RemoteSignRequest::AccountKeyPayload(fork_approval)));
+        let skip_approval = Approval::get_data_for_sig(&ApprovalInner::Skip(9), 10);
+        let content = String::from_utf8(skip_approval.clone()).unwrap();
+        assert!(!sign(&mut signer, RemoteSignRequest::Telemetry { content }));
+        assert!(!sign(&mut signer, RemoteSignRequest::AccountKeyPayload(skip_approval)));
+        let header_hash = CryptoHash::hash_bytes(b"header").as_bytes().to_vec();
+        assert!(!sign(&mut signer, RemoteSignRequest::AccountKeyPayload(header_hash)));
+        let witness = EncodedChunkStateWitness::from_boxed_slice(vec![1, 2, 3].into());
+        assert!(!sign(&mut signer, RemoteSignRequest::ChunkStateWitness(witness)));
+
+        let content = r#"{"agent":{"name":"near"}}"#.to_string();
+        assert!(sign(&mut signer, RemoteSignRequest::Telemetry { content }));
+    }
+
+    #[test]
+    fn test_sign_over_unix_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let address = RemoteSignerAddress::Unix(dir.path().join("signer.sock"));
+        let signer = Arc::new(Mutex::new(RemoteSigner::new(test_signer(), None).unwrap()));
+        let server_address = address.clone();
+        std::thread::spawn(move || serve(signer, &server_address).unwrap());
+
+        let config = RemoteSignerConfig { address, timeout: Duration::from_secs(5) };
+        // The server might not be listening yet.
+        let remote = (0..50)
+            .find_map(|_| {
+                let client = Arc::new(SocketRemoteSignerClient::new(config.clone()));
+                let remote = RemoteValidatorSigner::connect(client).ok();
+                if remote.is_none() {
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                remote
+            })
+            .expect("failed to connect to the remote signer");
+        let remote = ValidatorSigner::from(remote);
+        let local = ValidatorSigner::from(InMemoryValidatorSigner::from_signer(test_signer()));
+        assert_eq!(remote.validator_id(), local.validator_id());
+        assert_eq!(remote.public_key(), local.public_key());
+
+        let parent = CryptoHash::hash_bytes(b"parent");
+        let approval = Approval::new(parent, 9, 10, &remote).unwrap();
+        assert_eq!(approval, Approval::new(parent, 9, 10, &local).unwrap());
+        let (remote_value, _) = remote.compute_vrf_with_proof(b"data").unwrap();
+        let (local_value, _) = local.compute_vrf_with_proof(b"data").unwrap();
+        assert_eq!(remote_value.0, local_value.0);
+
+        // A conflicting approval is refused, so no approval is produced.
+        let fork = CryptoHash::hash_bytes(b"fork");
+        assert!(matches!(
+            Approval::new(fork, 9, 10, &remote),
+            Err(ValidatorSignerError::Refused { kind: "Approval", .. })
+        ));
+    }
+}
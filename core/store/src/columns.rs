@@ -293,6 +293,11 @@ pub enum DBCol {
     /// Witnesses with the lowest index are garbage collected first.
     /// u64 -> LatestWitnessesKey
     LatestWitnessesByIndex,
+    /// Sizes of the parts of the witnesses stored in `LatestChunkStateWitnesses`, which can be
+    /// listed without reading the witnesses themselves.
+    /// - *Rows*: `LatestWitnessesKey`
+    /// - *Column type*: `WitnessSizeBreakdown`
+    LatestWitnessesSizeBreakdown,
     /// A valid epoch sync proof that proves the transition from the genesis to some epoch,
     /// beyond which we keep all headers in this node. Nodes bootstrapped via Epoch Sync will
     /// have this column, which allows it to compute a more recent EpochSyncProof using block
@@ -306,6 +311,11 @@ pub enum DBCol {
     /// - *Rows*: `ShardUId`
     /// - *Column type*: `ShardUId`
     StateShardUIdMapping,
+    /// Messages signed by the validator keys of this node, used to refuse conflicting signatures.
+    /// Only the most recent heights are kept, see `near_chain::signing_ledger`.
+    /// - *Rows*: `SigningLedgerKey` (hash of the public key || kind || shard id || height)
+    /// - *Column type*: `CryptoHash` of the signed message
+    SigningLedger,
 }
 
 /// Defines different logical parts of a db key.
@@ -341,6 +351,7 @@ pub enum DBKeyType {
     ColumnId,
     LatestWitnessesKey,
     LatestWitnessIndex,
+    SigningLedgerKey,
 }
 
 impl DBCol {
@@ -478,6 +489,7 @@ impl DBCol {
             // LatestChunkStateWitnesses stores the last N observed witnesses, used only for debugging.
             DBCol::LatestChunkStateWitnesses => false,
             DBCol::LatestWitnessesByIndex => false,
+            DBCol::LatestWitnessesSizeBreakdown => false,
             // Deprecated.
             DBCol::_ReceiptIdToShardId => false,
             // This can be re-constructed from the Chunks column, so no need to store in Cold DB.
@@ -510,7 +522,8 @@ impl DBCol {
             | DBCol::FlatStateChanges
             | DBCol::FlatStateDeltaMetadata
             | DBCol::FlatStorageStatus
-            | DBCol::EpochSyncProof => false,
+            | DBCol::EpochSyncProof
+            | DBCol::SigningLedger => false,
         }
     }
 
@@ -582,8 +595,10 @@ impl DBCol {
             DBCol::StateTransitionData => &[DBKeyType::BlockHash, DBKeyType::ShardId],
             DBCol::LatestChunkStateWitnesses => &[DBKeyType::LatestWitnessesKey],
             DBCol::LatestWitnessesByIndex => &[DBKeyType::LatestWitnessIndex],
+            DBCol::LatestWitnessesSizeBreakdown => &[DBKeyType::LatestWitnessesKey],
             DBCol::EpochSyncProof => &[DBKeyType::Empty],
             DBCol::StateShardUIdMapping => &[DBKeyType::ShardUId],
+            DBCol::SigningLedger => &[DBKeyType::SigningLedgerKey],
         }
     }
 }
@@ -239,6 +239,14 @@ pub trait Database: Sync + Send {
     /// Atomically apply all operations in given batch at once.
     fn write(&self, batch: DBTransaction) -> io::Result<()>;
 
+    /// Like [`Self::write`] but returns only once the batch has been synced to
+    /// disk, so that it survives a crash of the machine.
+    ///
+    /// Databases which don't persist anything fall back to [`Self::write`].
+    fn write_sync(&self, batch: DBTransaction) -> io::Result<()> {
+        self.write(batch)
+    }
+
     /// Flush all in-memory data to disk.
     ///
     /// This is a no-op for in-memory databases.
@@ -114,6 +114,10 @@ impl Database for MixedDB {
         self.write_db.write(batch)
     }
 
+    fn write_sync(&self, batch: DBTransaction) -> io::Result<()> {
+        self.write_db.write_sync(batch)
+    }
+
     /// There is no need to flush a read-only DB.
     fn flush(&self) -> io::Result<()> {
         self.write_db.flush()
@@ -2,7 +2,8 @@ use crate::config::Mode;
 use crate::db::{refcount, DBIterator, DBOp, DBSlice, DBTransaction, Database, StatsValue};
 use crate::{metadata, metrics, DBCol, StoreConfig, StoreStatistics, Temperature};
 use ::rocksdb::{
-    BlockBasedOptions, Cache, ColumnFamily, Env, IteratorMode, Options, ReadOptions, WriteBatch, DB,
+    BlockBasedOptions, Cache, ColumnFamily, Env, IteratorMode, Options, ReadOptions, WriteBatch,
+    WriteOptions, DB,
 };
 use anyhow::Context;
 use itertools::Itertools;
@@ -402,6 +403,19 @@ impl Database for RocksDB {
         self.db.write(batch).map_err(io::Error::other)
     }
 
+    #[tracing::instrument(
+        target = "store::db::rocksdb",
+        level = "trace",
+        "RocksDB::write_sync",
+        skip_all
+    )]
+    fn write_sync(&self, transaction: DBTransaction) -> io::Result<()> {
+        let batch = self.build_write_batch(transaction)?;
+        let mut write_options = WriteOptions::default();
+        write_options.set_sync(true);
+        self.db.write_opt(batch, &write_options).map_err(io::Error::other)
+    }
+
     #[tracing::instrument(
         target = "store::db::rocksdb",
         level = "info",
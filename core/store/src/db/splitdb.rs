@@ -177,6 +177,10 @@ impl Database for SplitDB {
         self.hot.write(batch)
     }
 
+    fn write_sync(&self, batch: DBTransaction) -> io::Result<()> {
+        self.hot.write_sync(batch)
+    }
+
     fn flush(&self) -> io::Result<()> {
         let msg = "flush is not allowed - the split storage is read only.";
         log_assert_fail!("{}", msg);
@@ -123,9 +123,11 @@ fn genesis_state_from_genesis(
     info!(target: "store","distributing records to shards");
 
     genesis.for_each_record(|record: &StateRecord| {
-        let shard_id = state_record_to_shard_id(record, &shard_layout);
-        let account_id = state_record_to_account_id(record).clone();
-        shard_account_ids.get_mut(&shard_id).unwrap().insert(account_id);
+        // The records without an account are replicated to every shard by the applier.
+        if let Some(account_id) = state_record_to_account_id(record) {
+            let shard_id = account_id_to_shard_id(account_id, &shard_layout);
+            shard_account_ids.get_mut(&shard_id).unwrap().insert(account_id.clone());
+        }
         if let StateRecord::Account { account_id, .. } = record {
             if account_id == &genesis.config.protocol_treasury_account {
                 has_protocol_account = true;
@@ -175,7 +177,3 @@ fn genesis_state_from_genesis(
         })
         .collect()
 }
-
-fn state_record_to_shard_id(state_record: &StateRecord, shard_layout: &ShardLayout) -> ShardId {
-    account_id_to_shard_id(state_record_to_account_id(state_record), shard_layout)
-}
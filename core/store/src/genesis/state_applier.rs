@@ -3,14 +3,15 @@ use crate::flat::FlatStateChanges;
 use crate::trie::update::TrieUpdateResult;
 use crate::{
     get_account, has_received_data, set, set_access_key, set_account, set_code,
-    set_delayed_receipt, set_postponed_receipt, set_promise_yield_receipt, set_received_data,
-    ShardTries, TrieUpdate,
+    set_delayed_receipt, set_global_contract_owner, set_postponed_receipt,
+    set_promise_yield_receipt, set_received_data, ShardTries, TrieUpdate,
 };
 
 use near_chain_configs::Genesis;
 use near_crypto::PublicKey;
 use near_parameters::StorageUsageConfig;
 use near_primitives::account::{AccessKey, Account};
+use near_primitives::hash::hash;
 use near_primitives::receipt::{DelayedReceiptIndices, Receipt, ReceiptEnum, ReceivedData};
 use near_primitives::shard_layout::ShardUId;
 use near_primitives::state_record::{state_record_to_account_id, StateRecord};
@@ -62,6 +63,11 @@ impl<'a> StorageComputer<'a> {
             StateRecord::PostponedReceipt(_) => None,
             StateRecord::ReceivedData { .. } => None,
             StateRecord::DelayedReceipt(_) => None,
+            StateRecord::GlobalContractCode { .. } => None,
+            StateRecord::GlobalContractPublisher { .. } => None,
+            StateRecord::GlobalContractOwner { account_id, storage_usage, .. } => {
+                Some((account_id.clone(), *storage_usage))
+            }
         };
         if let Some((account_id, storage_usage)) = account_and_storage {
             *self.result.entry(account_id).or_default() += storage_usage;
@@ -180,8 +186,11 @@ impl GenesisStateApplier {
             "processing records…"
         );
         genesis.for_each_record(|record: &StateRecord| {
-            if !account_ids.contains(state_record_to_account_id(record)) {
-                return;
+            // The records without an account are replicated to every shard.
+            if let Some(account_id) = state_record_to_account_id(record) {
+                if !account_ids.contains(account_id) {
+                    return;
+                }
             }
             storage_computer.process_record(record);
             match record {
@@ -246,6 +255,30 @@ impl GenesisStateApplier {
                 StateRecord::DelayedReceipt(receipt) => storage.modify(|state_update| {
                     set_delayed_receipt(state_update, delayed_receipts_indices, &*receipt);
                 }),
+                StateRecord::GlobalContractCode { code } => storage.modify(|state_update| {
+                    let code_hash = hash(code);
+                    state_update.set(TrieKey::GlobalContractCode { code_hash }, code.clone());
+                }),
+                StateRecord::GlobalContractPublisher { code_hash, account_id } => storage
+                    .modify(|state_update| {
+                        state_update.set(
+                            TrieKey::GlobalContractPublisher {
+                                code_hash: *code_hash,
+                                account_id: account_id.clone(),
+                            },
+                            vec![],
+                        );
+                    }),
+                StateRecord::GlobalContractOwner { account_id, code_hash, storage_usage } => {
+                    storage.modify(|state_update| {
+                        set_global_contract_owner(
+                            state_update,
+                            account_id.clone(),
+                            *code_hash,
+                            *storage_usage,
+                        );
+                    })
+                }
             }
         });
 
@@ -315,7 +348,9 @@ impl GenesisStateApplier {
                         set_promise_yield_receipt(state_update, &receipt);
                     });
                 }
-                ReceiptEnum::Data(_) | ReceiptEnum::PromiseResume(_) => {
+                ReceiptEnum::Data(_)
+                | ReceiptEnum::PromiseResume(_)
+                | ReceiptEnum::GlobalContractDistribution(_) => {
                     panic!("Expected action receipt")
                 }
             }
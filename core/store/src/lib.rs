@@ -35,7 +35,7 @@ use near_primitives::receipt::{
 };
 pub use near_primitives::shard_layout::ShardUId;
 use near_primitives::trie_key::{trie_key_parsers, TrieKey};
-use near_primitives::types::{AccountId, BlockHeight, StateRoot};
+use near_primitives::types::{AccountId, BlockHeight, StateRoot, StorageUsage};
 use near_vm_runner::{CompiledContractInfo, ContractCode, ContractRuntimeCache};
 use std::fs::File;
 use std::path::Path;
@@ -750,6 +750,13 @@ impl StoreUpdate {
         }
         self.store.storage.write(self.transaction)
     }
+
+    /// Like [`Self::commit`] but returns only once the changes are synced to
+    /// disk.  Meant for the rare writes which must never be lost, e.g. the
+    /// record of what a validator has signed.
+    pub fn commit_sync(self) -> io::Result<()> {
+        self.store.storage.write_sync(self.transaction)
+    }
 }
 
 impl fmt::Debug for StoreUpdate {
@@ -1029,6 +1036,108 @@ pub fn set_code(state_update: &mut TrieUpdate, account_id: AccountId, code: &Con
     state_update.set(TrieKey::ContractCode { account_id }, code.code().to_vec());
 }
 
+pub fn remove_code(state_update: &mut TrieUpdate, account_id: AccountId) {
+    state_update.remove(TrieKey::ContractCode { account_id });
+}
+
+/// Records that the account publishes the code to the global contract registry, storing the
+/// code under its hash.
+pub fn publish_global_contract_code(
+    state_update: &mut TrieUpdate,
+    account_id: AccountId,
+    code: &ContractCode,
+) {
+    let code_hash = *code.hash();
+    state_update.set(TrieKey::GlobalContractCode { code_hash }, code.code().to_vec());
+    state_update.set(TrieKey::GlobalContractPublisher { code_hash, account_id }, vec![]);
+}
+
+/// Records that the account no longer publishes the code with the given hash to the global
+/// contract registry, removing the code if no other account publishes it.
+pub fn unpublish_global_contract_code(
+    state_update: &mut TrieUpdate,
+    account_id: AccountId,
+    code_hash: CryptoHash,
+) -> Result<(), StorageError> {
+    state_update.remove(TrieKey::GlobalContractPublisher { code_hash, account_id });
+    let lock = state_update.trie().lock_for_iter();
+    let has_publishers = state_update
+        .locked_iter(
+            &trie_key_parsers::get_raw_prefix_for_global_contract_publishers(&code_hash),
+            &lock,
+        )?
+        .next()
+        .transpose()?
+        .is_some();
+    drop(lock);
+    if !has_publishers {
+        state_update.remove(TrieKey::GlobalContractCode { code_hash });
+    }
+    Ok(())
+}
+
+pub fn has_global_contract_code(
+    trie: &dyn TrieAccess,
+    code_hash: CryptoHash,
+) -> Result<bool, StorageError> {
+    trie.contains_key(&TrieKey::GlobalContractCode { code_hash })
+}
+
+/// Returns the storage usage charged to the account for publishing the code with the given hash
+/// to the global contract registry, if the account publishes it.
+pub fn get_global_contract_owner(
+    trie: &dyn TrieAccess,
+    account_id: &AccountId,
+    code_hash: CryptoHash,
+) -> Result<Option<StorageUsage>, StorageError> {
+    get(trie, &TrieKey::GlobalContractOwner { account_id: account_id.clone(), code_hash })
+}
+
+pub fn set_global_contract_owner(
+    state_update: &mut TrieUpdate,
+    account_id: AccountId,
+    code_hash: CryptoHash,
+    storage_usage: StorageUsage,
+) {
+    set(state_update, TrieKey::GlobalContractOwner { account_id, code_hash }, &storage_usage);
+}
+
+/// Returns the hashes of the codes the account publishes to the global contract registry,
+/// together with the storage usage charged for each of them.
+pub fn get_global_contract_owned_codes(
+    state_update: &TrieUpdate,
+    account_id: &AccountId,
+) -> Result<Vec<(CryptoHash, StorageUsage)>, StorageError> {
+    let lock = state_update.trie().lock_for_iter();
+    let code_hashes = state_update
+        .locked_iter(
+            &trie_key_parsers::get_raw_prefix_for_global_contract_owners(account_id),
+            &lock,
+        )?
+        .map(|raw_key| {
+            trie_key_parsers::parse_code_hash_from_global_contract_key(&raw_key?).map_err(|_e| {
+                StorageError::StorageInconsistentState(
+                    "Can't parse code hash from raw key for GlobalContractOwner".to_string(),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    drop(lock);
+
+    code_hashes
+        .into_iter()
+        .map(|code_hash| {
+            let storage_usage = get_global_contract_owner(state_update, account_id, code_hash)?
+                .ok_or_else(|| {
+                    StorageError::StorageInconsistentState(
+                        "GlobalContractOwner should be in the state".to_string(),
+                    )
+                })?;
+            Ok((code_hash, storage_usage))
+        })
+        .collect()
+}
+
 /// Removes account, code and all access keys associated to it.
 pub fn remove_account(
     state_update: &mut TrieUpdate,
@@ -1076,6 +1185,12 @@ pub fn remove_account(
     for key in data_keys {
         state_update.remove(TrieKey::ContractData { account_id: account_id.clone(), key });
     }
+
+    // Removing the storage charged for the codes published to the global contract registry
+    for (code_hash, _) in get_global_contract_owned_codes(state_update, account_id)? {
+        state_update
+            .remove(TrieKey::GlobalContractOwner { account_id: account_id.clone(), code_hash });
+    }
     Ok(())
 }
 
@@ -1185,6 +1300,31 @@ pub fn get_code(
     trie.get(&key).map(|opt| opt.map(|code| ContractCode::new(code, code_hash)))
 }
 
+/// Get the contract WASM code with the given hash used by the account, either stored by the
+/// account itself or published to the global contract registry.
+pub fn get_account_code(
+    trie: &dyn TrieAccess,
+    account_id: &AccountId,
+    code_hash: CryptoHash,
+) -> Result<Option<ContractCode>, StorageError> {
+    if let Some(code) = get_code(trie, account_id, Some(code_hash))? {
+        return Ok(Some(code));
+    }
+    if code_hash == CryptoHash::default() {
+        return Ok(None);
+    }
+    get_global_contract_code(trie, code_hash)
+}
+
+/// Get the contract WASM code published to the global contract registry.
+pub fn get_global_contract_code(
+    trie: &dyn TrieAccess,
+    code_hash: CryptoHash,
+) -> Result<Option<ContractCode>, StorageError> {
+    let key = TrieKey::GlobalContractCode { code_hash };
+    trie.get(&key).map(|opt| opt.map(|code| ContractCode::new(code, Some(code_hash))))
+}
+
 #[cfg(test)]
 mod tests {
     use near_primitives::hash::CryptoHash;
@@ -70,6 +70,14 @@ pub struct DiskTrieIterator<'a> {
     /// the key also should be pruned. Otherwise it would be possible to bypass
     /// the pruning by seeking inside of the pruned sub-tree.
     prune_condition: Option<Box<dyn Fn(&Vec<u8>) -> bool>>,
+
+    /// Whether the nodes and values read by the iterator are counted by the
+    /// accounting cache of the trie.
+    use_accounting_cache: bool,
+
+    /// Whether only the keys are iterated over. The values are then neither
+    /// read nor recorded, and the items carry empty values.
+    keys_only: bool,
 }
 
 /// The TrieTiem is a tuple of (key, value) of the node.
@@ -90,6 +98,23 @@ impl<'a> DiskTrieIterator<'a> {
     pub(super) fn new(
         trie: &'a Trie,
         prune_condition: Option<Box<dyn Fn(&Vec<u8>) -> bool>>,
+    ) -> Result<Self, StorageError> {
+        Self::with_options(trie, prune_condition, true, false)
+    }
+
+    /// Create a new iterator over the keys only, whose reads are not counted
+    /// by the accounting cache of the trie, so that they don't affect the trie
+    /// node counts used for gas accounting. The nodes are still recorded if the
+    /// trie records storage proofs.
+    pub(super) fn new_keys_without_accounting(trie: &'a Trie) -> Result<Self, StorageError> {
+        Self::with_options(trie, None, false, true)
+    }
+
+    fn with_options(
+        trie: &'a Trie,
+        prune_condition: Option<Box<dyn Fn(&Vec<u8>) -> bool>>,
+        use_accounting_cache: bool,
+        keys_only: bool,
     ) -> Result<Self, StorageError> {
         let mut r = DiskTrieIterator {
             trie,
@@ -97,6 +122,8 @@ impl<'a> DiskTrieIterator<'a> {
             key_nibbles: Vec::with_capacity(64),
             visited_nodes: None,
             prune_condition,
+            use_accounting_cache,
+            keys_only,
         };
         r.descend_into_node(&trie.root)?;
         Ok(r)
@@ -107,6 +134,13 @@ impl<'a> DiskTrieIterator<'a> {
         self.seek_nibble_slice(NibbleSlice::new(key.as_ref()), true).map(drop)
     }
 
+    /// Position the iterator on the first element with key >= `key`. Unlike
+    /// [`Self::seek_prefix`], the iteration is not limited to the keys
+    /// starting with `key`.
+    pub fn seek<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StorageError> {
+        self.seek_nibble_slice(NibbleSlice::new(key.as_ref()), false).map(drop)
+    }
+
     /// Configures whether the iterator should remember all the nodes its
     /// visiting.
     ///
@@ -206,7 +240,8 @@ impl<'a> DiskTrieIterator<'a> {
     /// with [`Self::remember_visited_nodes`]), the node will be added to the
     /// list.
     fn descend_into_node(&mut self, hash: &CryptoHash) -> Result<(), StorageError> {
-        let (bytes, node) = self.trie.retrieve_node(hash)?;
+        let (bytes, node) =
+            self.trie.retrieve_node_with_accounting(hash, self.use_accounting_cache)?;
         if let Some(ref mut visited) = self.visited_nodes {
             visited.push(bytes.ok_or({
                 StorageError::MissingTrieValue(MissingTrieValueContext::TrieIterator, *hash)
@@ -416,9 +451,14 @@ impl<'a> Iterator for DiskTrieIterator<'a> {
                     Ok(_) => (),
                     Err(err) => return Some(Err(err)),
                 },
+                (IterStep::Value(_), true) if self.keys_only => {
+                    return Some(Ok((self.key(), Vec::new())));
+                }
                 (IterStep::Value(hash), true) => {
                     return Some(
-                        self.trie.retrieve_value(&hash).map(|value| (self.key(), value.to_vec())),
+                        self.trie
+                            .internal_retrieve_trie_node(&hash, self.use_accounting_cache, true)
+                            .map(|value| (self.key(), value.to_vec())),
                     )
                 }
             }
@@ -449,6 +489,13 @@ impl<'a> TrieIterator<'a> {
             TrieIterator::Memtrie(iter) => Ok(iter.seek_prefix(key)),
         }
     }
+
+    pub fn seek<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StorageError> {
+        match self {
+            TrieIterator::Disk(iter) => iter.seek(key),
+            TrieIterator::Memtrie(iter) => Ok(iter.seek(key)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -79,21 +79,43 @@ pub struct MemTrieIterator<'a, M: ArenaMemory> {
     trie: &'a Trie,
     trail: Vec<Crumb<'a, M>>,
     key_nibbles: Vec<u8>,
+    /// Whether only the keys are iterated over, see [`Self::keys_only`].
+    keys_only: bool,
 }
 
 impl<'a, M: ArenaMemory> MemTrieIterator<'a, M> {
     /// Create a new iterator.
     pub fn new(root: Option<MemTrieNodePtr<'a, M>>, trie: &'a Trie) -> Self {
-        let mut r = MemTrieIterator { root, trie, trail: Vec::new(), key_nibbles: Vec::new() };
+        let mut r = MemTrieIterator {
+            root,
+            trie,
+            trail: Vec::new(),
+            key_nibbles: Vec::new(),
+            keys_only: false,
+        };
         r.descend_into_node(root);
         r
     }
 
+    /// Iterate over the keys only. The values are then neither looked up nor
+    /// recorded, and the items carry empty values.
+    pub fn keys_only(mut self) -> Self {
+        self.keys_only = true;
+        self
+    }
+
     /// Position the iterator on the first element with key >= `key`.
     pub fn seek_prefix<K: AsRef<[u8]>>(&mut self, key: K) {
         self.seek_nibble_slice(NibbleSlice::new(key.as_ref()), true);
     }
 
+    /// Position the iterator on the first element with key >= `key`. Unlike
+    /// [`Self::seek_prefix`], the iteration is not limited to the keys
+    /// starting with `key`.
+    pub fn seek<K: AsRef<[u8]>>(&mut self, key: K) {
+        self.seek_nibble_slice(NibbleSlice::new(key.as_ref()), false);
+    }
+
     /// Returns the hash of the last node.
     pub(crate) fn seek_nibble_slice(
         &mut self,
@@ -270,6 +292,9 @@ impl<'a, M: ArenaMemory> Iterator for MemTrieIterator<'a, M> {
                 IterStep::Descend(ptr) => {
                     self.descend_into_node(Some(ptr));
                 }
+                IterStep::Value(_) if self.keys_only => {
+                    return Some(Ok((self.key(), Vec::new())));
+                }
                 IterStep::Value(value_ref) => {
                     let value = self.trie.deref_optimized(&value_ref);
                     if let Ok(value) = &value {
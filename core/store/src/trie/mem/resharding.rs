@@ -9,7 +9,9 @@ use super::updating::{
 };
 use itertools::Itertools;
 use near_primitives::errors::StorageError;
-use near_primitives::trie_key::col::COLUMNS_WITH_ACCOUNT_ID_IN_KEY;
+use near_primitives::trie_key::col::{
+    self, COLUMNS_WITH_ACCOUNT_ID_IN_KEY, EXTENDED_COLUMNS_WITH_ACCOUNT_ID_IN_KEY,
+};
 use near_primitives::types::{AccountId, StateRoot};
 use std::ops::Range;
 
@@ -49,6 +51,22 @@ fn boundary_account_to_intervals(
             }
         }
     }
+    for (col, _) in EXTENDED_COLUMNS_WITH_ACCOUNT_ID_IN_KEY {
+        let col_end = vec![col[0], col[1] + 1];
+        match retain_mode {
+            RetainMode::Left => {
+                intervals.push(col.to_vec()..[&col, boundary_account.as_bytes()].concat())
+            }
+            RetainMode::Right => {
+                intervals.push([&col, boundary_account.as_bytes()].concat()..col_end)
+            }
+        }
+    }
+    // The global contract registry is replicated to both children.
+    intervals.push(
+        col::GLOBAL_CONTRACT_CODE.to_vec()
+            ..vec![col::GLOBAL_CONTRACT_PUBLISHER[0], col::GLOBAL_CONTRACT_PUBLISHER[1] + 1],
+    );
     intervals
 }
 
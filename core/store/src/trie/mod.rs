@@ -770,29 +770,50 @@ impl Trie {
         self.storage.as_caching_storage()
     }
 
-    /// Request recording of the code for the given account.
-    pub fn request_code_recording(&self, account_id: AccountId) {
+    /// Request recording of the code with the given hash called on the given account.
+    ///
+    /// If the account does not store the code itself, the code is recorded from the global
+    /// contract registry instead.
+    pub fn request_code_recording(&self, account_id: AccountId, code_hash: CryptoHash) {
         let Some(recorder) = &self.recorder else {
             return;
         };
         {
             let mut r = recorder.borrow_mut();
-            if r.codes_to_record.contains(&account_id) {
+            if !r.codes_to_record.insert((account_id.clone(), code_hash)) {
                 return;
             }
-            r.codes_to_record.insert(account_id.clone());
         }
 
         // Get code length from ValueRef to update estimated upper bound for
         // recorded state.
         let key = TrieKey::ContractCode { account_id };
-        let value_ref = self.get_optimized_ref(&key.to_vec(), KeyLookupMode::FlatStorage);
-        if let Ok(Some(value_ref)) = value_ref {
+        let mut value_ref = self
+            .get_optimized_ref(&key.to_vec(), KeyLookupMode::FlatStorage)
+            .ok()
+            .flatten()
+            .map(OptimizedValueRef::into_value_ref);
+        if Self::is_global_contract_code(value_ref.as_ref(), code_hash) {
+            let key = TrieKey::GlobalContractCode { code_hash };
+            value_ref = self
+                .get_optimized_ref(&key.to_vec(), KeyLookupMode::FlatStorage)
+                .ok()
+                .flatten()
+                .map(OptimizedValueRef::into_value_ref);
+        }
+        if let Some(value_ref) = value_ref {
             let mut r = recorder.borrow_mut();
             r.record_code_len(value_ref.len());
         }
     }
 
+    /// Whether the code with the given hash comes from the global contract registry, given the
+    /// reference to the code stored for the account.
+    fn is_global_contract_code(account_code: Option<&ValueRef>, code_hash: CryptoHash) -> bool {
+        code_hash != CryptoHash::default()
+            && account_code.map_or(true, |value_ref| value_ref.hash != code_hash)
+    }
+
     #[cfg(feature = "test_features")]
     pub fn record_storage_garbage(&self, size_mbs: usize) -> bool {
         let Some(recorder) = &self.recorder else {
@@ -1251,7 +1272,17 @@ impl Trie {
         &self,
         hash: &CryptoHash,
     ) -> Result<(Option<std::sync::Arc<[u8]>>, TrieNodeWithSize), StorageError> {
-        match self.retrieve_raw_node(hash, true, true)? {
+        self.retrieve_node_with_accounting(hash, true)
+    }
+
+    /// Same as [`Self::retrieve_node`], but the node is counted by the
+    /// accounting cache only if `use_accounting_cache` is set.
+    fn retrieve_node_with_accounting(
+        &self,
+        hash: &CryptoHash,
+        use_accounting_cache: bool,
+    ) -> Result<(Option<std::sync::Arc<[u8]>>, TrieNodeWithSize), StorageError> {
+        match self.retrieve_raw_node(hash, use_accounting_cache, true)? {
             None => Ok((None, TrieNodeWithSize::empty())),
             Some((bytes, node)) => Ok((Some(bytes), TrieNodeWithSize::from_raw(node))),
         }
@@ -1627,9 +1658,18 @@ impl Trie {
         } else {
             HashSet::default()
         };
-        for account_id in codes_to_record {
-            let trie_key = TrieKey::ContractCode { account_id: account_id.clone() };
+        for (account_id, code_hash) in codes_to_record {
+            let trie_key = TrieKey::ContractCode { account_id };
+            let value_ref = self
+                .get_optimized_ref(&trie_key.to_vec(), KeyLookupMode::FlatStorage)
+                .ok()
+                .flatten()
+                .map(OptimizedValueRef::into_value_ref);
             let _ = self.get(&trie_key.to_vec());
+            if Self::is_global_contract_code(value_ref.as_ref(), code_hash) {
+                let trie_key = TrieKey::GlobalContractCode { code_hash };
+                let _ = self.get(&trie_key.to_vec());
+            }
         }
 
         match &self.memtries {
@@ -1759,6 +1799,20 @@ impl<'a> TrieWithReadLock<'a> {
             None => Ok(TrieIterator::Disk(DiskTrieIterator::new(self.trie, None)?)),
         }
     }
+
+    /// Same as [`Self::iter`], but iterates over the keys only, the items
+    /// carry empty values. The nodes read from disk are not counted by the
+    /// accounting cache, so that the trie node counts, and hence the gas
+    /// charged for subsequent reads, don't depend on whether memtries are
+    /// loaded. Memtrie iteration is never counted.
+    pub fn iter_keys_without_accounting(&self) -> Result<TrieIterator<'_>, StorageError> {
+        match &self.memtries {
+            Some(memtries) => Ok(TrieIterator::Memtrie(memtries.get_iter(self.trie)?.keys_only())),
+            None => {
+                Ok(TrieIterator::Disk(DiskTrieIterator::new_keys_without_accounting(self.trie)?))
+            }
+        }
+    }
 }
 
 impl TrieAccess for Trie {
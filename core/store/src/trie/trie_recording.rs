@@ -16,8 +16,8 @@ pub struct TrieRecorder {
     removal_counter: usize,
     /// Counts the total size of the contract codes read while recording.
     code_len_counter: usize,
-    /// Account IDs for which the code should be recorded.
-    pub codes_to_record: HashSet<AccountId>,
+    /// Account IDs and hashes of the called code for which the code should be recorded.
+    pub codes_to_record: HashSet<(AccountId, CryptoHash)>,
 }
 
 #[derive(Clone, Debug)]
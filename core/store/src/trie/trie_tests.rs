@@ -7,6 +7,7 @@ use near_primitives::challenge::PartialState;
 use near_primitives::errors::{MissingTrieValueContext, StorageError};
 use near_primitives::hash::{hash, CryptoHash};
 use near_primitives::shard_layout::ShardUId;
+use near_primitives::state_proof::{verify_trie_proof, StateProofError};
 use rand::seq::SliceRandom;
 use rand::Rng;
 use std::collections::{HashMap, HashSet};
@@ -140,6 +141,43 @@ fn test_reads_with_incomplete_storage() {
     }
 }
 
+/// Checks that the trie proofs recorded by `Trie` can be verified by the light client verifier in
+/// `near-primitives`, which decodes the trie nodes on its own.
+#[test]
+fn test_recorded_reads_verify_as_state_proofs() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..50 {
+        let tries = TestTriesBuilder::new().build();
+        let shard_uid = ShardUId { version: 1, shard_id: 0 };
+        let trie_changes = simplify_changes(&gen_changes(&mut rng, 20));
+        let state_root =
+            test_populate_trie(&tries, &Trie::EMPTY_ROOT, shard_uid, trie_changes.clone());
+        let values: HashMap<_, _> = trie_changes.into_iter().collect();
+
+        let lookups =
+            values.keys().cloned().chain(gen_changes(&mut rng, 5).into_iter().map(|c| c.0));
+        for key in lookups {
+            let trie = tries.get_trie_for_shard(shard_uid, state_root).recording_reads();
+            let value = trie.get(&key).unwrap();
+            assert_eq!(value, values.get(&key).cloned().flatten());
+            let PartialState::TrieValues(nodes) = trie.recorded_storage().unwrap().nodes;
+            verify_trie_proof(&state_root, &key, value.as_deref(), &nodes).unwrap();
+
+            let wrong_value = value.map_or_else(
+                || b"missing".to_vec(),
+                |mut value| {
+                    value.push(0);
+                    value
+                },
+            );
+            assert_eq!(
+                verify_trie_proof(&state_root, &key, Some(&wrong_value), &nodes),
+                Err(StateProofError::InvalidValue)
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod nodes_counter_tests {
     use super::*;
@@ -210,6 +210,29 @@ impl TrieUpdate {
         TrieUpdateIterator::new(self, key_prefix, Some(lock))
     }
 
+    /// Iterates over the keys with the given prefix which are not smaller than
+    /// `start`.
+    ///
+    /// Unlike [`Self::locked_iter`], the trie values are not read, and the trie
+    /// nodes read from disk are not counted by the accounting cache, so the
+    /// trie node counts don't depend on whether memtries are loaded. The nodes
+    /// are still recorded if the trie records storage proofs. The number of
+    /// keys gone over, see [`TrieUpdateIterator::visited_keys`], is meant to
+    /// pay for the iteration instead.
+    pub fn locked_iter_from<'a>(
+        &'a self,
+        key_prefix: &[u8],
+        start: &[u8],
+        lock: &'a TrieWithReadLock<'_>,
+    ) -> Result<TrieUpdateIterator<'a>, StorageError> {
+        TrieUpdateIterator::with_trie_iter(
+            self,
+            key_prefix,
+            start,
+            lock.iter_keys_without_accounting()?,
+        )
+    }
+
     pub fn get_root(&self) -> &StateRoot {
         self.trie.get_root()
     }
@@ -260,7 +283,7 @@ impl TrieUpdate {
     ) -> Result<(), StorageError> {
         if !ProtocolFeature::ExcludeContractCodeFromStateWitness.enabled(protocol_version) {
             // This causes trie lookup for the contract code to happen with side effects (charging gas and recording trie nodes).
-            self.trie.request_code_recording(account_id);
+            self.trie.request_code_recording(account_id, code_hash);
             return Ok(());
         }
 
@@ -270,30 +293,42 @@ impl TrieUpdate {
         if code_hash == CryptoHash::default() {
             return Ok(());
         }
-        let trie_key = TrieKey::ContractCode { account_id };
+        // Accounts using a code from the global contract registry do not store a copy of it.
+        let contract_exists = self
+            .contract_code_exists(TrieKey::ContractCode { account_id }, code_hash)?
+            || self.contract_code_exists(TrieKey::GlobalContractCode { code_hash }, code_hash)?;
+        if contract_exists {
+            self.contract_storage.record_call(code_hash);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the code with the given hash is stored under the key, without side effects
+    /// (not charging gas or recording trie nodes).
+    fn contract_code_exists(
+        &self,
+        trie_key: TrieKey,
+        code_hash: CryptoHash,
+    ) -> Result<bool, StorageError> {
         let contract_ref = self
             .trie
             .get_optimized_ref_no_side_effects(&trie_key.to_vec(), KeyLookupMode::FlatStorage)
             .or_else(|err| {
                 // If the value for the trie key is not found, we treat it as if the contract does not exist.
-                // In this case, we ignore the error and skip recording the contract call below.
+                // In this case, we ignore the error and skip recording the contract call.
                 if matches!(err, StorageError::MissingTrieValue(_, _)) {
                     Ok(None)
                 } else {
                     Err(err)
                 }
             })?;
-        let contract_exists: bool = match contract_ref {
+        Ok(match contract_ref {
             Some(OptimizedValueRef::Ref(value_ref)) => value_ref.hash == code_hash,
             Some(OptimizedValueRef::AvailableValue(ValueAccessToken { value })) => {
                 hash(value.as_slice()) == code_hash
             }
             None => false,
-        };
-        if contract_exists {
-            self.contract_storage.record_call(code_hash);
-        }
-        Ok(())
+        })
     }
 }
 
@@ -469,4 +504,45 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn trie_iter_from() {
+        let tries = TestTriesBuilder::new().build();
+        let mut trie_update = tries.new_trie_update(ShardUId::single_shard(), Trie::EMPTY_ROOT);
+        for key in [b"aaa".as_slice(), b"dog", b"dog1", b"dog3", b"dogz", b"xxx"] {
+            trie_update.set(test_key(key.to_vec()), b"puppy".to_vec());
+        }
+        trie_update
+            .commit(StateChangeCause::TransactionProcessing { tx_hash: CryptoHash::default() });
+        let trie_changes = trie_update.finalize().unwrap().trie_changes;
+        let mut store_update = tries.store_update();
+        let new_root = tries.apply_all(&trie_changes, ShardUId::single_shard(), &mut store_update);
+        store_update.commit().unwrap();
+
+        let mut trie_update = tries.new_trie_update(ShardUId::single_shard(), new_root);
+        trie_update.set(test_key(b"dog2".to_vec()), b"puppy".to_vec());
+        trie_update.remove(test_key(b"dog3".to_vec()));
+
+        // Returns the keys and the number of keys visited, including the removed `dog3`.
+        let iter_from = |start: &[u8]| -> (Vec<Vec<u8>>, u64) {
+            let lock = trie_update.trie().lock_for_iter();
+            let mut iter = trie_update
+                .locked_iter_from(
+                    &test_key(b"dog".to_vec()).to_vec(),
+                    &test_key(start.to_vec()).to_vec(),
+                    &lock,
+                )
+                .unwrap();
+            let keys = iter.by_ref().collect::<Result<_, _>>().unwrap();
+            (keys, iter.visited_keys())
+        };
+        let keys = |keys: &[&[u8]]| -> Vec<Vec<u8>> {
+            keys.iter().map(|key| test_key(key.to_vec()).to_vec()).collect()
+        };
+        assert_eq!(iter_from(b"a"), (keys(&[b"dog", b"dog1", b"dog2", b"dogz"]), 5));
+        assert_eq!(iter_from(b"dog0"), (keys(&[b"dog1", b"dog2", b"dogz"]), 4));
+        assert_eq!(iter_from(b"dog2"), (keys(&[b"dog2", b"dogz"]), 3));
+        assert_eq!(iter_from(b"dog3"), (keys(&[b"dogz"]), 2));
+        assert_eq!(iter_from(b"e"), (keys(&[]), 0));
+    }
 }
@@ -4,6 +4,7 @@ use std::ops::Bound;
 use crate::trie::{update::*, TrieWithReadLock};
 use crate::StorageError;
 
+use crate::trie::iterator::TrieItem;
 use crate::trie::TrieIterator;
 
 struct MergeIter<'a> {
@@ -35,7 +36,12 @@ impl<'a> Iterator for MergeIter<'a> {
     }
 }
 
-pub struct TrieUpdateIterator<'a>(Option<(Peekable<TrieIterator<'a>>, Peekable<MergeIter<'a>>)>);
+type TrieItemIter<'a> = Box<dyn Iterator<Item = Result<TrieItem, StorageError>> + 'a>;
+
+pub struct TrieUpdateIterator<'a> {
+    iterators: Option<(Peekable<TrieItemIter<'a>>, Peekable<MergeIter<'a>>)>,
+    visited_keys: u64,
+}
 
 impl<'a> TrieUpdateIterator<'a> {
     #![allow(clippy::new_ret_no_self)]
@@ -44,19 +50,47 @@ impl<'a> TrieUpdateIterator<'a> {
         prefix: &[u8],
         lock: Option<&'a TrieWithReadLock<'_>>,
     ) -> Result<Self, StorageError> {
-        let mut trie_iter = match lock {
+        let trie_iter = match lock {
             Some(lock) => lock.iter()?,
             None => TrieIterator::Disk(state_update.trie.disk_iter()?),
         };
-        trie_iter.seek_prefix(prefix)?;
+        Self::with_trie_iter(state_update, prefix, prefix, trie_iter)
+    }
 
+    /// Iterates over the keys with the given `prefix` which are not smaller
+    /// than `start`, reading the trie with `trie_iter`.
+    pub(crate) fn with_trie_iter(
+        state_update: &'a TrieUpdate,
+        prefix: &[u8],
+        start: &[u8],
+        mut trie_iter: TrieIterator<'a>,
+    ) -> Result<Self, StorageError> {
         let end_bound = make_prefix_range_end_bound(prefix);
+        let mut start = std::cmp::max(prefix, start);
+        if let Some(end_bound) = &end_bound {
+            // Nothing to iterate over if `start` is past the prefix range, but
+            // `BTreeMap::range` panics if the start is past the end.
+            start = std::cmp::min(start, end_bound.as_slice());
+        }
+        let trie_iter: TrieItemIter<'a> = if start == prefix {
+            trie_iter.seek_prefix(prefix)?;
+            Box::new(trie_iter)
+        } else {
+            // Seeking to `start` doesn't bound the iteration by the prefix.
+            trie_iter.seek(start)?;
+            let prefix = prefix.to_vec();
+            Box::new(trie_iter.take_while(move |item| match item {
+                Ok((key, _)) => key.starts_with(&prefix),
+                Err(_) => true,
+            }))
+        };
+
         let end_bound = if let Some(end_bound) = &end_bound {
             Bound::Excluded(end_bound.as_slice())
         } else {
             Bound::Unbounded
         };
-        let range = (Bound::Included(prefix), end_bound);
+        let range = (Bound::Included(start), end_bound);
 
         let committed_iter = state_update.committed.range::<[u8], _>(range).map(
             |(raw_key, changes_with_trie_key)| {
@@ -80,7 +114,16 @@ impl<'a> TrieUpdateIterator<'a> {
             right: (Box::new(prospective_iter) as Box<dyn Iterator<Item = _>>).peekable(),
         }
         .peekable();
-        Ok(TrieUpdateIterator(Some((trie_iter.peekable(), overlay_iter))))
+        Ok(TrieUpdateIterator {
+            iterators: Some((trie_iter.peekable(), overlay_iter)),
+            visited_keys: 0,
+        })
+    }
+
+    /// Number of distinct keys gone over so far. Besides the returned keys,
+    /// this includes the keys skipped because the pending changes remove them.
+    pub fn visited_keys(&self) -> u64 {
+        self.visited_keys
     }
 }
 
@@ -95,12 +138,12 @@ impl<'a> Iterator for TrieUpdateIterator<'a> {
             Both,
         }
         // Usually one iteration, unless need to skip None values in prospective / committed.
-        let iterators = self.0.as_mut()?;
+        let iterators = self.iterators.as_mut()?;
         loop {
             let res = match (iterators.0.peek(), iterators.1.peek()) {
                 (Some(Err(_)), _) => {
                     let err = iterators.0.next().unwrap().unwrap_err();
-                    self.0 = None;
+                    self.iterators = None;
                     return Some(Err(err));
                 }
 
@@ -114,10 +157,11 @@ impl<'a> Iterator for TrieUpdateIterator<'a> {
                 (Some(_), None) => Ordering::Trie,
                 (None, Some(_)) => Ordering::Overlay,
                 (None, None) => {
-                    self.0 = None;
+                    self.iterators = None;
                     return None;
                 }
             };
+            self.visited_keys += 1;
 
             // Check which element comes first and advance the corresponding
             // iterator only.  If both keys are equal, check if overlay doesn’t
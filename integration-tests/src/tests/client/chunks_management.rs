@@ -368,6 +368,7 @@ fn chunks_produced_and_distributed_chunk_distribution_network_disabled() {
     let config = ChunkDistributionNetworkConfig {
         enabled: false,
         uris: ChunkDistributionUris { set: String::new(), get: String::new() },
+        ..Default::default()
     };
     Test {
         validator_groups: 4,
@@ -391,6 +392,7 @@ fn chunks_produced_and_distributed_chunk_distribution_network_wrong_urls() {
             set: "http://www.fake-set-url.com".into(),
             get: "http://www.fake-get-url.com".into(),
         },
+        ..Default::default()
     };
     Test {
         validator_groups: 4,
@@ -412,6 +414,7 @@ fn chunks_produced_and_distributed_chunk_distribution_network_incorrect_get_retu
     let config = ChunkDistributionNetworkConfig {
         enabled: false,
         uris: ChunkDistributionUris { set: String::new(), get: "https://www.google.com".into() },
+        ..Default::default()
     };
     Test {
         validator_groups: 4,
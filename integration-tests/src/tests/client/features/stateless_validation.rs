@@ -12,9 +12,12 @@ use near_store::test_utils::create_test_store;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use std::collections::HashSet;
+use std::sync::Arc;
 
+use near_chain::stateless_validation::metrics::SHADOW_CHUNK_VALIDATION_TOTAL;
+use near_chain::stateless_validation::shadow_validation_report::ShadowValidationReport;
 use near_chain::{Chain, Provenance};
-use near_chain_configs::{Genesis, GenesisConfig, GenesisRecords};
+use near_chain_configs::{Genesis, GenesisConfig, GenesisRecords, ShadowChunkValidationConfig};
 use near_client::test_utils::{create_chunk_with_transactions, TestEnv};
 use near_crypto::{InMemorySigner, KeyType, SecretKey};
 use near_o11y::testonly::init_integration_logger;
@@ -390,6 +393,59 @@ fn test_chunk_state_witness_bad_shard_id() {
     assert!(error_message.contains("shard"));
 }
 
+/// Test that the shadow validation of every new chunk is appended to the report and counted in
+/// the metrics.
+#[test]
+fn test_shadow_validation_report() {
+    init_integration_logger();
+
+    let accounts = vec!["test0".parse().unwrap()];
+    let genesis = Genesis::test(accounts.clone(), 1);
+    let mut env = TestEnv::builder(&genesis.config)
+        .validators(accounts)
+        .nightshade_runtimes(&genesis)
+        .build();
+    let dir = tempfile::tempdir().unwrap();
+    let report_file = dir.path().join("shadow_validation.jsonl");
+    let client = &mut env.clients[0];
+    client.config.shadow_chunk_validation =
+        Some(ShadowChunkValidationConfig { report_file: Some(report_file.clone()) });
+    client.shadow_validation_report =
+        Some(Arc::new(ShadowValidationReport::open(&report_file, "test".to_string()).unwrap()));
+    let ok_validations = SHADOW_CHUNK_VALIDATION_TOTAL.with_label_values(&["0", "ok"]);
+    let ok_validations_before = ok_validations.get();
+
+    let upper_height = 6;
+    for height in 1..upper_height {
+        let block = env.clients[0].produce_block(height).unwrap().unwrap();
+        env.process_block(0, block, Provenance::PRODUCED);
+    }
+
+    // The validation itself runs in the background.
+    let num_chunks = upper_height as usize - 2;
+    let read_report = || {
+        std::fs::read_to_string(&report_file)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>()
+    };
+    let started = std::time::Instant::now();
+    while read_report().len() < num_chunks {
+        assert!(started.elapsed() < std::time::Duration::from_secs(30), "{:?}", read_report());
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    let report = read_report();
+    assert!(report.len() >= num_chunks);
+    for line in &report {
+        assert_eq!(line["status"], "ok", "{line}");
+        assert_eq!(line["shard_id"], 0);
+        assert_eq!(line["neard_version"], "test");
+        assert!(line["witness_size"].as_u64().unwrap() > 0);
+    }
+    assert!(ok_validations.get() - ok_validations_before >= report.len() as u64);
+}
+
 /// Test that processing chunks with invalid transactions does not lead to panics
 #[test]
 fn test_invalid_transactions() {
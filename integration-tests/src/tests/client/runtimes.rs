@@ -21,7 +21,7 @@ fn test_pending_approvals() {
     let mut env = TestEnv::builder(&genesis.config).nightshade_runtimes(&genesis).build();
     let signer = create_test_signer("test0");
     let parent_hash = hash(&[1]);
-    let approval = Approval::new(parent_hash, 0, 1, &signer);
+    let approval = Approval::new(parent_hash, 0, 1, &signer).unwrap();
     let peer_id = PeerId::random();
     let client_signer = env.clients[0].validator_signer.get();
     env.clients[0].collect_block_approval(
@@ -48,7 +48,7 @@ fn test_invalid_approvals() {
     let signer = create_test_signer("random");
     let parent_hash = hash(&[1]);
     // Approval not from a validator. Should be dropped
-    let approval = Approval::new(parent_hash, 1, 3, &signer);
+    let approval = Approval::new(parent_hash, 1, 3, &signer).unwrap();
     let peer_id = PeerId::random();
     let client_signer = env.clients[0].validator_signer.get();
     env.clients[0].collect_block_approval(
@@ -62,7 +62,7 @@ fn test_invalid_approvals() {
         InMemoryValidatorSigner::from_seed("test0".parse().unwrap(), KeyType::ED25519, "random")
             .into();
     let genesis_hash = *env.clients[0].chain.genesis().hash();
-    let approval = Approval::new(genesis_hash, 0, 1, &signer);
+    let approval = Approval::new(genesis_hash, 0, 1, &signer).unwrap();
     env.clients[0].collect_block_approval(
         &approval,
         ApprovalType::PeerApproval(peer_id),
@@ -73,6 +73,7 @@ pub fn add_blocks(
                     prev.header().height() + 1,
                     signer,
                 )
+                .unwrap()
                 .signature,
             ))],
             Ratio::from_integer(0),
@@ -86,7 +87,8 @@ pub fn add_blocks(
             block_merkle_tree.root(),
             clock.clone(),
             None,
-        );
+        )
+        .unwrap();
         block_merkle_tree.insert(*block.hash());
         let _ = client.do_send(
             BlockResponse {
@@ -123,12 +123,45 @@ fn test_view_call() {
         "run_test",
         &[],
         &mut logs,
+        None,
         &MockEpochInfoProvider::default(),
     );
 
     assert_eq!(result.unwrap(), (10i32).to_le_bytes());
 }
 
+#[test]
+fn test_view_call_with_function_gas_profile() {
+    let (viewer, root) = get_test_trie_viewer();
+
+    let mut logs = vec![];
+    let mut profile = String::new();
+    let view_state = ViewApplyState {
+        block_height: 1,
+        prev_block_hash: CryptoHash::default(),
+        block_hash: CryptoHash::default(),
+        shard_id: ShardUId::single_shard().shard_id(),
+        epoch_id: EpochId::default(),
+        epoch_height: 0,
+        block_timestamp: 1,
+        current_protocol_version: PROTOCOL_VERSION,
+        cache: None,
+    };
+    let result = viewer.call_function(
+        root,
+        view_state,
+        &"test.contract".parse().unwrap(),
+        "run_test",
+        &[],
+        &mut logs,
+        Some(&mut profile),
+        &MockEpochInfoProvider::default(),
+    );
+
+    assert_eq!(result.unwrap(), (10i32).to_le_bytes());
+    assert!(profile.lines().any(|line| line.starts_with("run_test ")), "{profile}");
+}
+
 #[test]
 fn test_view_call_try_changing_storage() {
     let (viewer, root) = get_test_trie_viewer();
@@ -152,6 +185,7 @@ fn test_view_call_try_changing_storage() {
         "run_test_with_storage_change",
         &[],
         &mut logs,
+        None,
         &MockEpochInfoProvider::default(),
     );
     let err = result.unwrap_err();
@@ -185,6 +219,7 @@ fn test_view_call_with_args() {
         "sum_with_input",
         &args,
         &mut logs,
+        None,
         &MockEpochInfoProvider::default(),
     );
     assert_eq!(view_call_result.unwrap(), 3u64.to_le_bytes().to_vec());
@@ -408,6 +443,7 @@ fn test_log_when_panic() {
             "panic_after_logging",
             &[],
             &mut logs,
+            None,
             &MockEpochInfoProvider::default(),
         )
         .unwrap_err();
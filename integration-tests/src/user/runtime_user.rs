@@ -322,6 +322,7 @@ impl User for RuntimeUser {
                 method_name,
                 args,
                 &mut result.logs,
+                None,
                 &self.epoch_info_provider,
             )
             .map_err(|err| err.to_string())?;
@@ -20,13 +20,13 @@ use near_chain_configs::{
     default_tx_routing_height_horizon, default_view_client_threads,
     default_view_client_throttle_period, get_initial_supply, ChunkDistributionNetworkConfig,
     ClientConfig, EpochSyncConfig, GCConfig, Genesis, GenesisConfig, GenesisValidationMode,
-    LogSummaryStyle, MutableConfigValue, MutableValidatorSigner, ReshardingConfig, StateSyncConfig,
-    BLOCK_PRODUCER_KICKOUT_THRESHOLD, CHUNK_PRODUCER_KICKOUT_THRESHOLD,
-    CHUNK_VALIDATOR_ONLY_KICKOUT_THRESHOLD, EXPECTED_EPOCH_LENGTH, FAST_EPOCH_LENGTH,
-    FISHERMEN_THRESHOLD, GAS_PRICE_ADJUSTMENT_RATE, GENESIS_CONFIG_FILENAME, INITIAL_GAS_LIMIT,
-    MAX_INFLATION_RATE, MIN_BLOCK_PRODUCTION_DELAY, MIN_GAS_PRICE, NEAR_BASE, NUM_BLOCKS_PER_YEAR,
-    NUM_BLOCK_PRODUCER_SEATS, PROTOCOL_REWARD_RATE, PROTOCOL_UPGRADE_STAKE_THRESHOLD,
-    TRANSACTION_VALIDITY_PERIOD,
+    LogSummaryStyle, MutableConfigValue, MutableValidatorSigner, ReshardingConfig,
+    ShadowChunkValidationConfig, StateSyncConfig, BLOCK_PRODUCER_KICKOUT_THRESHOLD,
+    CHUNK_PRODUCER_KICKOUT_THRESHOLD, CHUNK_VALIDATOR_ONLY_KICKOUT_THRESHOLD,
+    EXPECTED_EPOCH_LENGTH, FAST_EPOCH_LENGTH, FISHERMEN_THRESHOLD, GAS_PRICE_ADJUSTMENT_RATE,
+    GENESIS_CONFIG_FILENAME, INITIAL_GAS_LIMIT, MAX_INFLATION_RATE, MIN_BLOCK_PRODUCTION_DELAY,
+    MIN_GAS_PRICE, NEAR_BASE, NUM_BLOCKS_PER_YEAR, NUM_BLOCK_PRODUCER_SEATS, PROTOCOL_REWARD_RATE,
+    PROTOCOL_UPGRADE_STAKE_THRESHOLD, TRANSACTION_VALIDITY_PERIOD,
 };
 use near_config_utils::{DownloadConfigType, ValidationError, ValidationErrors};
 use near_crypto::{InMemorySigner, KeyFile, KeyType, PublicKey};
@@ -36,7 +36,9 @@ use near_jsonrpc::RpcConfig;
 use near_network::config::NetworkConfig;
 use near_network::tcp;
 use near_o11y::log_config::LogConfig;
+use near_parameters::RuntimeConfigStore;
 use near_primitives::hash::CryptoHash;
+use near_primitives::remote_signer::RemoteValidatorSigner;
 use near_primitives::shard_layout::ShardLayout;
 use near_primitives::test_utils::create_test_signer;
 use near_primitives::types::{
@@ -46,6 +48,7 @@ use near_primitives::types::{
 use near_primitives::utils::{from_timestamp, get_num_seats_per_shard};
 use near_primitives::validator_signer::{InMemoryValidatorSigner, ValidatorSigner};
 use near_primitives::version::PROTOCOL_VERSION;
+use near_remote_signer::{RemoteSignerConfig, SocketRemoteSignerClient};
 #[cfg(feature = "rosetta_rpc")]
 use near_rosetta_rpc::RosettaRpcConfig;
 use near_store::config::StateSnapshotType;
@@ -323,6 +326,19 @@ pub struct Config {
     /// which can cause extra load on the database. This option is not recommended for production use,
     /// as a large number of incoming witnesses could cause denial of service.
     pub save_latest_witnesses: bool,
+    /// Refuse to start a validator node when the signing ledger for its validator key is missing.
+    /// The ledger records what the node has signed, so that it cannot sign conflicting messages.
+    /// When moving a validator key to another host, move the ledger along with it using
+    /// `neard database signing-ledger export` and `neard database signing-ledger import`.
+    pub require_signing_ledger: bool,
+    /// Sign with a validator key held by a separate process instead of `validator_key_file`.
+    /// See the `near-remote-signer` crate for the protocol and a reference signer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_signer: Option<RemoteSignerConfig>,
+    /// Create and validate a state witness for every new chunk in the tracked shards, and report
+    /// the results. A relative `report_file` is resolved against the home directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_chunk_validation: Option<ShadowChunkValidationConfig>,
 }
 
 fn is_false(value: &bool) -> bool {
@@ -373,6 +389,9 @@ impl Default for Config {
             orphan_state_witness_max_size: default_orphan_state_witness_max_size(),
             max_loaded_contracts: 256,
             save_latest_witnesses: false,
+            require_signing_ledger: false,
+            remote_signer: None,
+            shadow_chunk_validation: None,
         }
     }
 }
@@ -563,7 +582,10 @@ impl NearConfig {
                 catchup_step_period: config.consensus.catchup_step_period,
                 chunk_request_retry_period: config.consensus.chunk_request_retry_period,
                 doosmslug_step_period: config.consensus.doomslug_step_period,
-                tracked_accounts: config.tracked_accounts,
+                tracked_accounts: MutableConfigValue::new(
+                    config.tracked_accounts,
+                    "tracked_accounts",
+                ),
                 tracked_shards: config.tracked_shards,
                 tracked_shadow_validator: config.tracked_shadow_validator,
                 tracked_shard_schedule: config.tracked_shard_schedule.unwrap_or(vec![]),
@@ -597,6 +619,8 @@ impl NearConfig {
                 orphan_state_witness_pool_size: config.orphan_state_witness_pool_size,
                 orphan_state_witness_max_size: config.orphan_state_witness_max_size,
                 save_latest_witnesses: config.save_latest_witnesses,
+                require_signing_ledger: config.require_signing_ledger,
+                shadow_chunk_validation: config.shadow_chunk_validation,
             },
             network_config: NetworkConfig::new(
                 config.network,
@@ -656,6 +680,18 @@ impl NightshadeRuntime {
         store: Store,
         config: &NearConfig,
         epoch_manager: Arc<EpochManagerHandle>,
+    ) -> std::io::Result<Arc<NightshadeRuntime>> {
+        Self::from_config_with_runtime_config_store(home_dir, store, config, epoch_manager, None)
+    }
+
+    /// Same as `from_config`, but with the given runtime configs instead of the ones of the
+    /// chain in the genesis config.
+    pub fn from_config_with_runtime_config_store(
+        home_dir: &Path,
+        store: Store,
+        config: &NearConfig,
+        epoch_manager: Arc<EpochManagerHandle>,
+        runtime_config_store: Option<RuntimeConfigStore>,
     ) -> std::io::Result<Arc<NightshadeRuntime>> {
         // TODO (#9989): directly use the new state snapshot config once the migration is done.
         let mut state_snapshot_type =
@@ -689,7 +725,7 @@ impl NightshadeRuntime {
             epoch_manager,
             config.client_config.trie_viewer_state_size_limit,
             config.client_config.max_gas_burnt_view,
-            None,
+            runtime_config_store,
             config.config.gc.gc_num_epochs_to_keep(),
             TrieConfig::from_store_config(&config.config.store),
             state_snapshot_config,
@@ -1412,6 +1448,21 @@ pub fn load_validator_key(validator_file: &Path) -> anyhow::Result<Option<Arc<Va
     }
 }
 
+/// Connects to the remote signer configured in `config`, if there is one.
+///
+/// Unlike the validator key file, the remote signer is not loaded by [`load_config`] nor on config
+/// reloads, since that would connect to it for every `neard` subcommand. Only `neard run` connects.
+pub fn connect_remote_signer(config: &Config) -> anyhow::Result<Option<Arc<ValidatorSigner>>> {
+    let Some(remote_signer) = &config.remote_signer else {
+        return Ok(None);
+    };
+    let client = Arc::new(SocketRemoteSignerClient::new(remote_signer.clone()));
+    let signer = RemoteValidatorSigner::connect(client).with_context(|| {
+        format!("Failed connecting to remote signer at {}", remote_signer.address)
+    })?;
+    Ok(Some(Arc::new(signer.into())))
+}
+
 pub fn load_config(
     dir: &Path,
     genesis_validation: GenesisValidationMode,
@@ -1419,14 +1470,26 @@ pub fn load_config(
     let mut validation_errors = ValidationErrors::new();
 
     // if config.json has file issues, the program will directly panic
-    let config = Config::from_file_skip_validation(&dir.join(CONFIG_FILENAME))?;
+    let mut config = Config::from_file_skip_validation(&dir.join(CONFIG_FILENAME))?;
+    if let Some(path) = &mut config.network.peer_records_file {
+        *path = dir.join(&path);
+    }
+    if let Some(path) = config.shadow_chunk_validation.as_mut().and_then(|c| c.report_file.as_mut())
+    {
+        *path = dir.join(&path);
+    }
     // do config.json validation later so that genesis_file, validator_file and genesis_file can be validated before program panic
     if let Err(e) = config.validate() {
         validation_errors.push_errors(e)
     };
 
+    // With a remote signer the validator signer is set by `connect_remote_signer` instead.
     let validator_file: PathBuf = dir.join(&config.validator_key_file);
-    let validator_signer = match load_validator_key(&validator_file) {
+    let validator_signer = match config.remote_signer {
+        Some(_) => Ok(None),
+        None => load_validator_key(&validator_file),
+    };
+    let validator_signer = match validator_signer {
         Ok(validator_signer) => validator_signer,
         Err(e) => {
             validation_errors.push_validator_key_file_error(e.to_string());
@@ -68,6 +68,7 @@ fn test_config_duration_all_std() {
                 },
                 ..Default::default()
             },
+            preferred_ip_family: Some(near_network::config::IpFamily::V4),
             ..Default::default()
         },
         ..Default::default()
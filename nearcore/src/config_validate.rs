@@ -1,4 +1,4 @@
-use near_chain_configs::{ExternalStorageLocation, SyncConfig};
+use near_chain_configs::{ChunkDistributionBackendConfig, ExternalStorageLocation, SyncConfig};
 use near_config_utils::{ValidationError, ValidationErrors};
 use std::collections::HashSet;
 use std::path::Path;
@@ -157,6 +157,18 @@ impl<'a> ConfigValidator<'a> {
             let error_message = format!("'config.tx_routing_height_horizon' can't be too high to avoid spamming the network. Keep it below 100. Got {tx_routing_height_horizon}.");
             self.validation_errors.push_config_semantics_error(error_message);
         }
+
+        if let Some(ChunkDistributionBackendConfig::MultiEndpoint { endpoints, .. }) = self
+            .config
+            .chunk_distribution_network
+            .as_ref()
+            .and_then(|config| config.backend.as_ref())
+        {
+            if endpoints.is_empty() {
+                let error_message = "'config.chunk_distribution_network.backend' of type multi_endpoint needs at least one endpoint.".to_string();
+                self.validation_errors.push_config_semantics_error(error_message);
+            }
+        }
     }
 
     fn result_with_full_error(&self) -> Result<(), ValidationError> {
@@ -171,6 +183,7 @@ impl<'a> ConfigValidator<'a> {
 
 #[cfg(test)]
 mod tests {
+    use near_chain_configs::ChunkDistributionNetworkConfig;
     use near_primitives::types::ShardId;
 
     use super::*;
@@ -252,4 +265,21 @@ mod tests {
         config.tx_routing_height_horizon = 1_000_000_000;
         validate_config(&config).unwrap();
     }
+
+    #[test]
+    #[should_panic(
+        expected = "\\nconfig.json semantic issue: 'config.chunk_distribution_network.backend' of type multi_endpoint needs at least one endpoint."
+    )]
+    fn test_chunk_distribution_multi_endpoint_empty() {
+        let mut config = Config::default();
+        config.chunk_distribution_network = Some(ChunkDistributionNetworkConfig {
+            enabled: true,
+            backend: Some(ChunkDistributionBackendConfig::MultiEndpoint {
+                endpoints: vec![],
+                hedge_delay: None,
+            }),
+            ..Default::default()
+        });
+        validate_config(&config).unwrap();
+    }
 }
@@ -1,6 +1,9 @@
 use crate::config::Config;
+use near_chain_configs::MutableConfigValue;
 use near_chain_configs::UpdateableClientConfig;
 use near_dyn_configs::{UpdateableConfigLoaderError, UpdateableConfigs};
+use near_network::config::UpdateableNetworkConfig;
+use near_network::MessagesLimitsConfig;
 use near_o11y::log_config::LogConfig;
 use near_primitives::validator_signer::ValidatorSigner;
 use serde::Deserialize;
@@ -32,17 +35,29 @@ pub fn read_updateable_configs(
         }
     };
     let updateable_client_config = config.as_ref().map(get_updateable_client_config);
+    let updateable_network_config = match config.as_ref().map(get_updateable_network_config) {
+        Some(Ok(network_config)) => Some(network_config),
+        Some(Err(err)) => {
+            errs.push(UpdateableConfigLoaderError::NetworkConfigError {
+                file: PathBuf::from(crate::config::CONFIG_FILENAME),
+                err,
+            });
+            None
+        }
+        None => None,
+    };
 
-    let validator_signer = if let Some(config) = config {
-        match read_validator_key(home_dir, &config) {
+    let validator_signer = match config {
+        // The remote signer is connected to once on start and kept across reloads.
+        Some(config) if config.remote_signer.is_some() => None,
+        Some(config) => match read_validator_key(home_dir, &config) {
             Ok(validator_key) => Some(validator_key),
             Err(err) => {
                 errs.push(err);
                 None
             }
-        }
-    } else {
-        None
+        },
+        None => None,
     };
 
     if errs.is_empty() {
@@ -50,6 +65,7 @@ pub fn read_updateable_configs(
         Ok(UpdateableConfigs {
             log_config,
             client_config: updateable_client_config,
+            network_config: updateable_network_config,
             validator_signer,
         })
     } else {
@@ -66,6 +82,40 @@ pub fn get_updateable_client_config(config: &Config) -> UpdateableClientConfig {
         expected_shutdown: config.expected_shutdown,
         resharding_config: config.resharding_config,
         produce_chunk_add_transactions_time_limit: config.produce_chunk_add_transactions_time_limit,
+        tracked_accounts: config.tracked_accounts.clone(),
+    }
+}
+
+pub fn get_updateable_network_config(config: &Config) -> anyhow::Result<UpdateableNetworkConfig> {
+    // All fields that can be updated while the node is running should be explicitly set in
+    // `UpdateableNetworkConfig::new`. Keep the list in-sync with `core/dyn-configs/README.md`.
+    let network_config =
+        UpdateableNetworkConfig::new(&config.network.experimental.network_config_overrides);
+    network_config.validate()?;
+    Ok(network_config)
+}
+
+/// Applies the updates of the network config, received while the node is running,
+/// to the values shared with the network layer. Returns when the updates channel is closed.
+pub async fn apply_network_config_updates(
+    mut rx_config_update: tokio::sync::broadcast::Receiver<
+        Result<UpdateableConfigs, Arc<UpdateableConfigLoaderError>>,
+    >,
+    received_messages_rate_limits: MutableConfigValue<MessagesLimitsConfig>,
+) {
+    use tokio::sync::broadcast::error::RecvError;
+    loop {
+        match rx_config_update.recv().await {
+            Ok(Ok(UpdateableConfigs { network_config: Some(network_config), .. })) => {
+                received_messages_rate_limits.update(network_config.received_messages_rate_limits);
+            }
+            // Errors are reported by the `ConfigUpdater` of the client.
+            Ok(_) => {}
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!(target: "neard", skipped, "Skipped network config updates");
+            }
+            Err(RecvError::Closed) => break,
+        }
     }
 }
 
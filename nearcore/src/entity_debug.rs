@@ -25,6 +25,7 @@ use near_primitives::state_sync::StateSyncDumpProgress;
 use near_primitives::stateless_validation::stored_chunk_state_transition_data::{
     StoredChunkStateTransitionData, StoredChunkStateTransitionDataV1,
     StoredChunkStateTransitionDataV2, StoredChunkStateTransitionDataV3,
+    StoredChunkStateTransitionDataV4,
 };
 use near_primitives::transaction::{ExecutionOutcomeWithProof, SignedTransaction};
 use near_primitives::types::chunk_extra::ChunkExtra;
@@ -336,6 +337,11 @@ impl EntityDebugHandlerImpl {
                             base_state,
                             receipts_hash,
                             ..
+                        })
+                        | StoredChunkStateTransitionData::V4(StoredChunkStateTransitionDataV4 {
+                            base_state,
+                            receipts_hash,
+                            ..
                         }) => (base_state, receipts_hash),
                     };
                     let mut serialized = EntityDataStruct::new();
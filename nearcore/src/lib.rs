@@ -18,6 +18,7 @@ use near_async::time::{self, Clock};
 use near_chain::rayon_spawner::RayonAsyncComputationSpawner;
 use near_chain::resharding::resharding_actor::ReshardingActor;
 pub use near_chain::runtime::NightshadeRuntime;
+use near_chain::signing_ledger::SigningLedger;
 use near_chain::state_snapshot_actor::{
     get_delete_snapshot_callback, get_make_snapshot_callback, SnapshotCallbacks, StateSnapshotActor,
 };
@@ -268,6 +269,11 @@ pub fn start_with_config_and_synchronization(
         Some(home_dir),
     );
 
+    if let Some(signer) = config.validator_signer.get() {
+        SigningLedger::new(storage.get_hot_store())
+            .check_on_startup(&signer.public_key(), config.client_config.require_signing_ledger)?;
+    }
+
     let shard_tracker =
         ShardTracker::new(TrackedConfig::from_config(&config.client_config), epoch_manager.clone());
     let runtime = NightshadeRuntime::from_config(
@@ -381,6 +387,7 @@ pub fn start_with_config_and_synchronization(
     let state_sync_runtime =
         Arc::new(tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap());
 
+    let rx_network_config_update = config_updater.as_ref().map(ConfigUpdater::subscribe);
     let StartClientResult { client_actor, client_arbiter_handle, resharding_handle } = start_client(
         Clock::real(),
         config.client_config.clone(),
@@ -434,6 +441,12 @@ pub fn start_with_config_and_synchronization(
     let cold_store = storage.get_cold_store();
 
     let mut rpc_servers = Vec::new();
+    if let Some(rx_network_config_update) = rx_network_config_update {
+        actix::spawn(dyn_config::apply_network_config_updates(
+            rx_network_config_update,
+            config.network_config.received_messages_rate_limits.clone(),
+        ));
+    }
     let network_actor = PeerManagerActor::spawn(
         time::Clock::real(),
         storage.into_inner(near_store::Temperature::Hot),
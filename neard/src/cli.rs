@@ -12,6 +12,7 @@ use near_fork_network::cli::ForkNetworkCommand;
 use near_jsonrpc_primitives::types::light_client::RpcLightClientExecutionProofResponse;
 use near_mirror::MirrorCommand;
 use near_network::tcp;
+use near_network::topology::TopologyFormat;
 use near_o11y::tracing_subscriber::EnvFilter;
 use near_o11y::{
     default_subscriber, default_subscriber_with_opentelemetry, BuildEnvFilterError,
@@ -21,6 +22,7 @@ use near_ping::PingCommand;
 use near_primitives::hash::CryptoHash;
 use near_primitives::merkle::compute_root_from_path;
 use near_primitives::types::{Gas, NumSeats, NumShards, ShardId};
+use near_primitives::views::NetworkTopologyView;
 use near_replay_archive_tool::ReplayArchiveCommand;
 use near_state_parts::cli::StatePartsCommand;
 use near_state_parts_dump_check::cli::StatePartsDumpCheckCommand;
@@ -28,6 +30,7 @@ use near_state_viewer::StateViewerSubCommand;
 use near_store::db::RocksDB;
 use near_store::Mode;
 use near_undo_block::cli::UndoBlockCommand;
+use near_validator_selection_tool::ValidatorSelectionCommand;
 use serde_json::Value;
 use std::fs::File;
 use std::io::BufReader;
@@ -147,6 +150,12 @@ impl NeardCmd {
             NeardSubCommand::ReplayArchive(cmd) => {
                 cmd.run(&home_dir, genesis_validation)?;
             }
+            NeardSubCommand::NetworkTopology(cmd) => {
+                cmd.run()?;
+            }
+            NeardSubCommand::SimulateValidatorSelection(cmd) => {
+                cmd.run(&home_dir, genesis_validation)?;
+            }
         };
         Ok(())
     }
@@ -253,6 +262,14 @@ pub(super) enum NeardSubCommand {
 
     /// Replays the blocks in the chain from an archival node.
     ReplayArchive(ReplayArchiveCommand),
+
+    /// Exports the network topology known to a running node (routing graph, account owners,
+    /// TIER1 proxies and measured latencies) as JSON, Graphviz DOT or GraphML.
+    NetworkTopology(NetworkTopologyCommand),
+
+    /// Runs recorded or hand-written stake proposals and kickouts through the validator
+    /// selection, to evaluate changes of the epoch config.
+    SimulateValidatorSelection(ValidatorSelectionCommand),
 }
 
 #[derive(clap::Parser)]
@@ -436,6 +453,11 @@ impl RunCmd {
         // Load configs from home.
         let mut near_config = nearcore::config::load_config(home_dir, genesis_validation)
             .unwrap_or_else(|e| panic!("Error loading config: {:#}", e));
+        if let Some(validator_signer) = nearcore::config::connect_remote_signer(&near_config.config)
+            .unwrap_or_else(|e| panic!("Error connecting to remote signer: {:#}", e))
+        {
+            near_config.validator_signer.update(Some(validator_signer));
+        }
 
         check_release_build(&near_config.client_config.chain_id);
 
@@ -787,6 +809,50 @@ impl ValidateConfigCommand {
     }
 }
 
+#[derive(clap::Parser)]
+pub(super) struct NetworkTopologyCommand {
+    /// Address of the JSON RPC server of the node. The node needs to have the debug RPC enabled.
+    #[clap(long, default_value = "http://127.0.0.1:3030")]
+    rpc_url: String,
+    /// Convert the topology previously exported in JSON format, instead of querying the node.
+    #[clap(long, conflicts_with = "rpc_url")]
+    input: Option<PathBuf>,
+    /// Output format: json, dot or graphml.
+    #[clap(long, default_value = "dot")]
+    format: TopologyFormat,
+    /// File to write the topology to. Defaults to stdout.
+    #[clap(long)]
+    output: Option<PathBuf>,
+}
+
+impl NetworkTopologyCommand {
+    pub(super) fn run(self) -> anyhow::Result<()> {
+        let json = match &self.input {
+            Some(input) => std::fs::read_to_string(input)
+                .with_context(|| format!("failed to read {}", input.display()))?,
+            None => {
+                let url = format!(
+                    "{}/debug/api/network_topology/json",
+                    self.rpc_url.trim_end_matches('/')
+                );
+                reqwest::blocking::get(&url)
+                    .and_then(|response| response.error_for_status())
+                    .and_then(|response| response.text())
+                    .with_context(|| format!("failed to fetch {url}"))?
+            }
+        };
+        let view: NetworkTopologyView =
+            serde_json::from_str(&json).context("failed to parse the network topology")?;
+        let rendered = near_network::topology::render(&view, self.format);
+        match &self.output {
+            Some(output) => std::fs::write(output, rendered)
+                .with_context(|| format!("failed to write {}", output.display()))?,
+            None => print!("{rendered}"),
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{CryptoHash, NeardCmd, NeardSubCommand, VerifyProofError, VerifyProofSubCommand};
@@ -0,0 +1,70 @@
+#!/usr/bin/env python3
+# Runs the near-light-client binary against a localnet.
+# Bootstraps the light client from an early final block, lets it follow the chain across a few
+# epochs, and checks that it verifies the proofs of a transaction and of its receipt.
+#
+# The near-light-client binary needs to be built first:
+#   cargo build -p near-light-client
+
+import json
+import os
+import pathlib
+import subprocess
+import sys
+import tempfile
+
+sys.path.append(str(pathlib.Path(__file__).resolve().parents[2] / 'lib'))
+
+from cluster import start_cluster, load_config
+from configured_logger import logger
+import transaction
+import utils
+
+EPOCH_LENGTH = 6
+
+config = load_config()
+nodes = start_cluster(
+    4, 0, 4, None,
+    [["epoch_length", EPOCH_LENGTH], ["block_producer_kickout_threshold", 40],
+     ["chunk_producer_kickout_threshold", 40]], {})
+
+state_file = os.path.join(tempfile.mkdtemp(), 'light_client_state.json')
+rpc_url = 'http://%s:%s' % nodes[0].rpc_addr()
+
+
+def light_client(*args):
+    cmd = (os.path.join(config['near_root'], 'near-light-client'), '--rpc-url',
+           rpc_url, '--state-file', state_file) + args
+    logger.info(' '.join(cmd))
+    output = subprocess.check_output(cmd, timeout=60)
+    return json.loads(output)
+
+
+utils.wait_for_blocks(nodes[0], target=5)
+trusted_block = nodes[0].get_final_block()['result']['header']
+head = light_client('init', '--trusted-block-hash', trusted_block['hash'])
+assert head['inner_lite']['height'] == trusted_block['height'], head
+
+block_hash = nodes[0].get_latest_block().hash_bytes
+tx = transaction.sign_payment_tx(nodes[0].signer_key, 'test1', 100, 1,
+                                 block_hash)
+result = nodes[0].send_tx_and_wait(tx, 15)
+assert 'error' not in result, result
+tx_hash = result['result']['transaction_outcome']['id']
+receipt_id = result['result']['receipts_outcome'][0]['id']
+
+# The light client head has to move past the blocks with the outcomes, across an epoch boundary.
+tx_height = nodes[0].get_latest_block().height
+utils.wait_for_blocks(nodes[0], target=tx_height + 3 * EPOCH_LENGTH)
+head = light_client('sync')
+assert head['inner_lite']['height'] > tx_height, head
+assert head['inner_lite']['epoch_id'] != trusted_block['epoch_id'], head
+
+outcome = light_client('verify-tx', '--tx-hash', tx_hash, '--sender-id',
+                       'test0')
+assert outcome['id'] == tx_hash, outcome
+outcome = light_client('verify-receipt', '--receipt-id', receipt_id,
+                       '--receiver-id', 'test1')
+assert outcome['id'] == receipt_id, outcome
+
+logger.info('light client verified the transaction and the receipt')
@@ -0,0 +1,238 @@
+//! Runs contract methods directly on top of `near-vm-runner`, with an in-memory contract state
+//! and a configurable execution context, so contracts can be unit tested without a node or a
+//! sandbox. Promises created by the contract are recorded rather than executed.
+
+#[cfg(test)]
+mod tests;
+
+use near_parameters::vm::Config;
+use near_parameters::{RuntimeConfigStore, RuntimeFeesConfig};
+use near_primitives_core::config::ViewConfig;
+use near_primitives_core::types::{AccountId, Balance, Gas, ProtocolVersion};
+use near_primitives_core::version::PROTOCOL_VERSION;
+use near_vm_runner::logic::mocks::mock_external::MockedExternal;
+use near_vm_runner::logic::ReturnData;
+use near_vm_runner::{ContractCode, MockContractRuntimeCache};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub use near_vm_runner::logic::errors::{FunctionCallError, VMRunnerError};
+pub use near_vm_runner::logic::mocks::mock_external::MockAction;
+pub use near_vm_runner::logic::types::PromiseResult;
+pub use near_vm_runner::logic::{VMContext, VMOutcome};
+
+/// A contract loaded together with mocked state and execution context.
+///
+/// Every call runs the method in a fresh VM instance, on top of the state left by the previous
+/// successful calls. The compiled contract is cached in memory, so only the first call pays for
+/// the compilation.
+pub struct ContractInstance {
+    config: Arc<Config>,
+    fees: Arc<RuntimeFeesConfig>,
+    cache: MockContractRuntimeCache,
+    ext: MockedExternal,
+    context: VMContext,
+}
+
+/// Result of a single method call.
+#[derive(Debug)]
+pub struct CallOutcome {
+    outcome: VMOutcome,
+    actions: Vec<MockAction>,
+}
+
+impl ContractInstance {
+    /// Loads the Wasm code with the runtime config of the latest protocol version.
+    pub fn new(wasm: impl Into<Vec<u8>>) -> Self {
+        Self::with_protocol_version(wasm, PROTOCOL_VERSION)
+    }
+
+    pub fn with_protocol_version(
+        wasm: impl Into<Vec<u8>>,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        let runtime_config = RuntimeConfigStore::new(None).get_config(protocol_version).clone();
+        let mut config = Config::clone(&runtime_config.wasm_config);
+        config.vm_kind = config.vm_kind.replace_with_wasmtime_if_unsupported();
+        let code = ContractCode::new(wasm.into(), None);
+        Self {
+            config: Arc::new(config),
+            fees: Arc::clone(&runtime_config.fees),
+            cache: MockContractRuntimeCache::default(),
+            ext: MockedExternal::with_code(code),
+            context: default_context(),
+        }
+    }
+
+    /// Context used for the subsequent calls. `input`, `view_config` and `promise_results` are
+    /// overwritten on every call.
+    pub fn context(&self) -> &VMContext {
+        &self.context
+    }
+
+    pub fn context_mut(&mut self) -> &mut VMContext {
+        &mut self.context
+    }
+
+    /// Sets the account executing the contract, and makes it the signer and the predecessor of
+    /// the calls as well.
+    pub fn set_account_id(&mut self, account_id: AccountId) {
+        self.context.signer_account_id = account_id.clone();
+        self.context.predecessor_account_id = account_id.clone();
+        self.context.current_account_id = account_id;
+    }
+
+    pub fn set_predecessor_id(&mut self, account_id: AccountId) {
+        self.context.predecessor_account_id = account_id;
+    }
+
+    pub fn set_attached_deposit(&mut self, deposit: Balance) {
+        self.context.attached_deposit = deposit;
+    }
+
+    pub fn set_prepaid_gas(&mut self, gas: Gas) {
+        self.context.prepaid_gas = gas;
+    }
+
+    /// Contract storage, keyed by the raw keys the contract uses.
+    pub fn state(&self) -> &HashMap<Vec<u8>, Vec<u8>> {
+        &self.ext.fake_trie
+    }
+
+    pub fn state_mut(&mut self) -> &mut HashMap<Vec<u8>, Vec<u8>> {
+        &mut self.ext.fake_trie
+    }
+
+    /// Stake returned by the `validator_stake` host function for each account.
+    pub fn validators_mut(&mut self) -> &mut HashMap<AccountId, Balance> {
+        &mut self.ext.validators
+    }
+
+    /// Calls a method as a function call action would.
+    ///
+    /// On success, the account balance and storage usage in the context are updated from the
+    /// outcome. If the method fails, all its state changes and created promises are reverted.
+    pub fn call(&mut self, method: &str, args: &[u8]) -> Result<CallOutcome, VMRunnerError> {
+        self.run(method, args, None, Vec::new())
+    }
+
+    /// Calls a method as a callback, with the results of the promises it depends on.
+    pub fn callback(
+        &mut self,
+        method: &str,
+        args: &[u8],
+        promise_results: Vec<PromiseResult>,
+    ) -> Result<CallOutcome, VMRunnerError> {
+        self.run(method, args, None, promise_results)
+    }
+
+    /// Calls a method as a view call would. State changing host functions fail with a
+    /// `ProhibitedInView` error.
+    pub fn view(&mut self, method: &str, args: &[u8]) -> Result<CallOutcome, VMRunnerError> {
+        let view_config = ViewConfig { max_gas_burnt: self.config.limit_config.max_gas_burnt };
+        self.run(method, args, Some(view_config), Vec::new())
+    }
+
+    fn run(
+        &mut self,
+        method: &str,
+        args: &[u8],
+        view_config: Option<ViewConfig>,
+        promise_results: Vec<PromiseResult>,
+    ) -> Result<CallOutcome, VMRunnerError> {
+        self.context.input = args.to_vec();
+        self.context.view_config = view_config;
+        self.context.promise_results = promise_results.into();
+        let state = self.ext.fake_trie.clone();
+        let actions_len = self.ext.action_log.len();
+
+        let gas_counter = self.context.make_gas_counter(&self.config);
+        let prepared = near_vm_runner::prepare(
+            &self.ext,
+            Arc::clone(&self.config),
+            Some(&self.cache),
+            gas_counter,
+            method,
+        );
+        let outcome =
+            near_vm_runner::run(prepared, &mut self.ext, &self.context, Arc::clone(&self.fees))?;
+
+        if outcome.aborted.is_some() {
+            self.ext.fake_trie = state;
+            self.ext.action_log.truncate(actions_len);
+            return Ok(CallOutcome { outcome, actions: Vec::new() });
+        }
+        self.context.account_balance = outcome.balance;
+        self.context.storage_usage = outcome.storage_usage;
+        let actions = self.ext.action_log[actions_len..].to_vec();
+        Ok(CallOutcome { outcome, actions })
+    }
+
+    /// Actions of all the promises created by the calls so far, in order.
+    pub fn actions(&self) -> &[MockAction] {
+        &self.ext.action_log
+    }
+
+    pub fn clear_actions(&mut self) {
+        self.ext.action_log.clear();
+    }
+}
+
+impl CallOutcome {
+    /// Value returned by the method, if it returned a value rather than a promise.
+    pub fn return_value(&self) -> Option<&[u8]> {
+        match &self.outcome.return_data {
+            ReturnData::Value(value) => Some(value),
+            ReturnData::ReceiptIndex(_) | ReturnData::None => None,
+        }
+    }
+
+    pub fn logs(&self) -> &[String] {
+        &self.outcome.logs
+    }
+
+    /// Error the method failed with, e.g. a contract panic or running out of gas.
+    pub fn error(&self) -> Option<&FunctionCallError> {
+        self.outcome.aborted.as_ref()
+    }
+
+    /// Actions of the promises created by this call.
+    pub fn actions(&self) -> &[MockAction] {
+        &self.actions
+    }
+
+    pub fn burnt_gas(&self) -> Gas {
+        self.outcome.burnt_gas
+    }
+
+    pub fn outcome(&self) -> &VMOutcome {
+        &self.outcome
+    }
+
+    pub fn into_outcome(self) -> VMOutcome {
+        self.outcome
+    }
+}
+
+fn default_context() -> VMContext {
+    let account_id: AccountId = "contract.near".parse().unwrap();
+    VMContext {
+        current_account_id: account_id.clone(),
+        signer_account_id: account_id.clone(),
+        signer_account_pk: vec![0; 33],
+        predecessor_account_id: account_id,
+        input: Vec::new(),
+        promise_results: Vec::new().into(),
+        block_height: 1,
+        block_timestamp: 0,
+        epoch_height: 1,
+        account_balance: 10u128.pow(24) * 100,
+        account_locked_balance: 0,
+        storage_usage: 0,
+        attached_deposit: 0,
+        prepaid_gas: 300 * 10u64.pow(12),
+        random_seed: vec![0; 32],
+        view_config: None,
+        output_data_receivers: Vec::new(),
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{ContractInstance, FunctionCallError, MockAction, PromiseResult};
+use near_vm_runner::logic::HostError;
+
+/// Encode array of `u64` to be passed as a smart contract argument.
+fn encode(xs: &[u64]) -> Vec<u8> {
+    xs.iter().flat_map(|it| it.to_le_bytes()).collect()
+}
+
+const PROMISE_CONTRACT: &str = r#"
+(module
+  (import "env" "promise_batch_create" (func $promise_batch_create (param i64 i64) (result i64)))
+  (import "env" "promise_batch_action_transfer" (func $transfer (param i64 i64)))
+  (import "env" "promise_results_count" (func $promise_results_count (result i64)))
+  (import "env" "value_return" (func $value_return (param i64 i64)))
+  (memory 1)
+  (data (i32.const 0) "bob.near")
+  (data (i32.const 16) "\01")
+  (func $send
+    (call $transfer (call $promise_batch_create (i64.const 8) (i64.const 0)) (i64.const 16)))
+  (func (export "send") (call $send))
+  (func (export "send_and_fail") (call $send) unreachable)
+  (func (export "results_count")
+    (i64.store (i32.const 32) (call $promise_results_count))
+    (call $value_return (i64.const 8) (i64.const 32))))
+"#;
+
+#[test]
+fn test_state() {
+    let mut contract = ContractInstance::new(near_test_contracts::rs_contract());
+    let outcome = contract.call("write_key_value", &encode(&[10, 20])).unwrap();
+    assert_eq!(outcome.error(), None);
+    assert_eq!(contract.state().get(&encode(&[10])), Some(&encode(&[20])));
+    assert!(contract.context().storage_usage > 0);
+
+    contract.state_mut().insert(encode(&[11]), encode(&[21]));
+    let outcome = contract.view("read_value", &encode(&[11])).unwrap();
+    assert_eq!(outcome.return_value(), Some(&encode(&[21])[..]));
+
+    let outcome = contract.view("write_key_value", &encode(&[12, 22])).unwrap();
+    assert_eq!(
+        outcome.error(),
+        Some(&FunctionCallError::HostError(HostError::ProhibitedInView {
+            method_name: "storage_write".to_string()
+        }))
+    );
+    assert_eq!(contract.state().len(), 2);
+}
+
+#[test]
+fn test_logs() {
+    let mut contract = ContractInstance::new(near_test_contracts::rs_contract());
+    let outcome = contract.call("log_something", &[]).unwrap();
+    assert_eq!(outcome.logs(), ["hello"]);
+    assert!(outcome.burnt_gas() > 0);
+
+    let outcome = contract.call("panic_after_logging", &[]).unwrap();
+    assert_eq!(outcome.logs(), ["hello"]);
+    assert!(outcome.error().is_some());
+}
+
+#[test]
+fn test_promises() {
+    let wasm = near_test_contracts::wat_contract(PROMISE_CONTRACT);
+    let mut contract = ContractInstance::new(wasm);
+    let outcome = contract.call("send", &[]).unwrap();
+    assert_eq!(outcome.error(), None);
+    let [MockAction::CreateReceipt { receiver_id, .. }, MockAction::Transfer { deposit: 1, .. }] =
+        outcome.actions()
+    else {
+        panic!("unexpected actions: {:?}", outcome.actions());
+    };
+    assert_eq!(receiver_id.as_str(), "bob.near");
+
+    // Promises of a failed call are dropped.
+    let outcome = contract.call("send_and_fail", &[]).unwrap();
+    assert!(outcome.error().is_some());
+    assert!(outcome.actions().is_empty());
+    assert_eq!(contract.actions().len(), 2);
+    contract.clear_actions();
+    assert!(contract.actions().is_empty());
+
+    let results = vec![PromiseResult::Successful(vec![]), PromiseResult::Failed];
+    let outcome = contract.callback("results_count", &[], results).unwrap();
+    assert_eq!(outcome.return_value(), Some(&encode(&[2])[..]));
+}
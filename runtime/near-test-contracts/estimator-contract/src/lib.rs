@@ -72,6 +72,33 @@ extern "C" {
         pub_key_len: u64,
         pub_key_ptr: u64,
     ) -> u64;
+    #[cfg(feature = "nightly")]
+    fn secp256k1_verify(
+        sig_len: u64,
+        sig_ptr: u64,
+        hash_len: u64,
+        hash_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64,
+    ) -> u64;
+    #[cfg(feature = "nightly")]
+    fn secp256r1_verify(
+        sig_len: u64,
+        sig_ptr: u64,
+        hash_len: u64,
+        hash_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64,
+    ) -> u64;
+    #[cfg(feature = "nightly")]
+    fn bip340_verify(
+        sig_len: u64,
+        sig_ptr: u64,
+        msg_len: u64,
+        msg_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64,
+    ) -> u64;
     // #####################
     // # Miscellaneous API #
     // #####################
@@ -188,6 +215,15 @@ extern "C" {
     fn storage_read(key_len: u64, key_ptr: u64, register_id: u64) -> u64;
     fn storage_remove(key_len: u64, key_ptr: u64, register_id: u64) -> u64;
     fn storage_has_key(key_len: u64, key_ptr: u64) -> u64;
+    #[cfg(feature = "nightly")]
+    fn storage_iter_prefix_v2(
+        prefix_len: u64,
+        prefix_ptr: u64,
+        start_len: u64,
+        start_ptr: u64,
+        limit: u64,
+        register_id: u64,
+    ) -> u64;
 }
 
 // Function that does not do anything at all.
@@ -586,6 +622,120 @@ pub unsafe fn ed25519_verify_16kib_64() {
     }
 }
 
+/// Function to measure `secp256k1_verify_base`. Also measures `base` and the cost of
+/// reading the inputs from memory, which is negligible compared to the
+/// signature verification.
+#[cfg(feature = "nightly")]
+#[unsafe(no_mangle)]
+pub unsafe fn secp256k1_verify_1k() {
+    // ECDSA signature over the hash below with a compressed public key.
+    let signature: [u8; 64] = [
+        118, 68, 131, 42, 10, 227, 223, 234, 154, 56, 177, 173, 205, 71, 37, 10, 212, 182, 114, 46,
+        124, 170, 203, 125, 235, 92, 132, 82, 35, 227, 109, 107, 55, 65, 3, 75, 156, 233, 165, 99,
+        216, 253, 151, 78, 146, 143, 60, 192, 141, 204, 75, 243, 48, 132, 244, 54, 171, 166, 113,
+        241, 164, 49, 132, 188,
+    ];
+    // sha256("kajdlfkjalkfjaklfjdkladjfkljadsk")
+    let message: [u8; 32] = [
+        36, 72, 216, 64, 44, 182, 11, 21, 148, 33, 237, 85, 251, 108, 177, 177, 167, 0, 160, 211,
+        67, 130, 227, 51, 198, 150, 205, 214, 78, 195, 111, 57,
+    ];
+    let public_key: [u8; 33] = [
+        3, 84, 13, 210, 105, 76, 231, 125, 142, 167, 148, 55, 223, 191, 210, 201, 104, 32, 242, 88,
+        35, 30, 8, 246, 132, 130, 96, 170, 206, 203, 49, 118, 97,
+    ];
+
+    for _ in 0..1_000 {
+        let result = secp256k1_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        );
+        // check that result was positive, as negative results could have exited
+        // early and do not reflect the full cost.
+        assert!(result == 1);
+    }
+}
+
+/// Function to measure `secp256r1_verify_base`. Also measures `base` and the cost of
+/// reading the inputs from memory, which is negligible compared to the
+/// signature verification.
+#[cfg(feature = "nightly")]
+#[unsafe(no_mangle)]
+pub unsafe fn secp256r1_verify_1k() {
+    // ECDSA signature over the hash below with a compressed public key.
+    let signature: [u8; 64] = [
+        143, 183, 171, 146, 223, 19, 110, 240, 50, 54, 233, 1, 45, 229, 53, 220, 5, 54, 34, 197,
+        37, 81, 245, 238, 32, 201, 85, 79, 48, 165, 192, 44, 118, 227, 131, 30, 136, 191, 143, 59,
+        2, 197, 97, 136, 60, 144, 237, 225, 119, 200, 45, 157, 172, 190, 197, 187, 175, 207, 16, 9,
+        87, 240, 173, 175,
+    ];
+    // sha256("kajdlfkjalkfjaklfjdkladjfkljadsk")
+    let message: [u8; 32] = [
+        36, 72, 216, 64, 44, 182, 11, 21, 148, 33, 237, 85, 251, 108, 177, 177, 167, 0, 160, 211,
+        67, 130, 227, 51, 198, 150, 205, 214, 78, 195, 111, 57,
+    ];
+    let public_key: [u8; 33] = [
+        3, 55, 64, 175, 67, 15, 133, 29, 179, 90, 191, 38, 43, 219, 134, 195, 171, 207, 134, 50,
+        113, 149, 134, 49, 74, 107, 240, 231, 155, 12, 191, 229, 51,
+    ];
+
+    for _ in 0..1_000 {
+        let result = secp256r1_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        );
+        // check that result was positive, as negative results could have exited
+        // early and do not reflect the full cost.
+        assert!(result == 1);
+    }
+}
+
+/// Function to measure `bip340_verify_base`. Also measures `base` and the cost of
+/// reading the inputs from memory, which is negligible compared to the
+/// signature verification.
+#[cfg(feature = "nightly")]
+#[unsafe(no_mangle)]
+pub unsafe fn bip340_verify_1k() {
+    // Test vector 1 from the BIP-340 specification.
+    let signature: [u8; 64] = [
+        104, 150, 189, 96, 238, 174, 41, 109, 180, 138, 34, 159, 247, 29, 254, 7, 27, 222, 65, 62,
+        109, 67, 249, 23, 220, 141, 207, 140, 120, 222, 51, 65, 137, 6, 209, 26, 201, 118, 171,
+        204, 178, 11, 9, 18, 146, 191, 244, 234, 137, 126, 252, 182, 57, 234, 135, 28, 250, 149,
+        246, 222, 51, 158, 75, 10,
+    ];
+    // 32 bytes message
+    let message: [u8; 32] = [
+        36, 63, 106, 136, 133, 163, 8, 211, 19, 25, 138, 46, 3, 112, 115, 68, 164, 9, 56, 34, 41,
+        159, 49, 208, 8, 46, 250, 152, 236, 78, 108, 137,
+    ];
+    let public_key: [u8; 32] = [
+        223, 241, 215, 127, 42, 103, 28, 95, 54, 24, 55, 38, 219, 35, 65, 190, 88, 254, 174, 29,
+        162, 222, 206, 216, 67, 36, 15, 123, 80, 43, 166, 89,
+    ];
+
+    for _ in 0..1_000 {
+        let result = bip340_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        );
+        // check that result was positive, as negative results could have exited
+        // early and do not reflect the full cost.
+        assert!(result == 1);
+    }
+}
+
 #[repr(C)]
 struct MultiexpElem([u8; 64], [u8; 32]);
 
@@ -1093,6 +1243,32 @@ storage_bench!(key, 10240, value, 10, 1000, storage_has_key_10kib_key_1k, {
     storage_has_key(10240, key.as_ptr() as _);
 });
 
+// Storage iteration.
+
+// Function to measure `storage_iter_v2_base`.
+// Requests an empty page from storage 1k times.
+#[cfg(feature = "nightly")]
+#[unsafe(no_mangle)]
+pub unsafe fn storage_iter_v2_empty_page_1k() {
+    for _ in 0..1000 {
+        storage_iter_prefix_v2(0, 0, 0, 0, 0, 0);
+    }
+}
+
+// Function to measure `storage_iter_v2_key` and `storage_iter_v2_byte`.
+// Iterates over the 1k keys written by one of the `storage_write_10b_key_*_1k`
+// functions, with one page per first key byte.
+#[cfg(feature = "nightly")]
+#[unsafe(no_mangle)]
+pub unsafe fn storage_iter_v2_1k_keys() {
+    let mut count = 0;
+    for first_byte in 0..=255u8 {
+        let prefix = [first_byte];
+        count += storage_iter_prefix_v2(1, prefix.as_ptr() as _, 0, 0, 100, 0);
+    }
+    assert!(count == 1000);
+}
+
 // Function to measure `promise_and_base`.
 #[unsafe(no_mangle)]
 pub unsafe fn promise_and_100k() {
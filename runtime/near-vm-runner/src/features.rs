@@ -23,16 +23,24 @@ use opts::*;
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) struct WasmFeatures {
     sign_extension: bool,
+    /// Enabled starting with `ContractPrepareVersion::V3`. Only the NearVM and Wasmtime
+    /// runtimes support it.
+    pub(crate) bulk_memory: bool,
+    /// Enabled starting with `ContractPrepareVersion::V3`. Only the NearVM and Wasmtime
+    /// runtimes support it. Preparation rejects functions with more than one result, so
+    /// this only enables the multi-value block types.
+    pub(crate) multi_value: bool,
 }
 
 impl From<crate::logic::ContractPrepareVersion> for WasmFeatures {
     fn from(version: crate::logic::ContractPrepareVersion) -> Self {
-        let sign_extension = match version {
-            crate::logic::ContractPrepareVersion::V0 => false,
-            crate::logic::ContractPrepareVersion::V1 => false,
-            crate::logic::ContractPrepareVersion::V2 => true,
+        let (sign_extension, bulk_memory, multi_value) = match version {
+            crate::logic::ContractPrepareVersion::V0 => (false, false, false),
+            crate::logic::ContractPrepareVersion::V1 => (false, false, false),
+            crate::logic::ContractPrepareVersion::V2 => (true, false, false),
+            crate::logic::ContractPrepareVersion::V3 => (true, true, true),
         };
-        WasmFeatures { sign_extension }
+        WasmFeatures { sign_extension, bulk_memory, multi_value }
     }
 }
 
@@ -44,10 +52,10 @@ impl From<WasmFeatures> for finite_wasm::wasmparser::WasmFeatures {
             mutable_global: true,
             sign_extension: f.sign_extension,
 
+            multi_value: f.multi_value,
+            bulk_memory: f.bulk_memory,
+
             reference_types: REFERENCE_TYPES,
-            // wasmer singlepass compiler requires multi_value return values to be disabled.
-            multi_value: MULTI_VALUE,
-            bulk_memory: BULK_MEMORY,
             simd: SIMD,
             threads: THREADS,
             tail_call: TAIL_CALL,
@@ -67,7 +75,7 @@ impl From<WasmFeatures> for finite_wasm::wasmparser::WasmFeatures {
 
 #[cfg(feature = "wasmparser")]
 impl From<WasmFeatures> for wasmparser::WasmFeatures {
-    fn from(_: WasmFeatures) -> Self {
+    fn from(f: WasmFeatures) -> Self {
         // /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\
         //
         // There are features that this version of wasmparser enables by default, but pwasm
@@ -85,9 +93,9 @@ impl From<WasmFeatures> for wasmparser::WasmFeatures {
             deterministic_only: false,
 
             module_linking: false, // old version of component model
+            multi_value: f.multi_value,
+            bulk_memory: f.bulk_memory,
             reference_types: REFERENCE_TYPES,
-            multi_value: MULTI_VALUE,
-            bulk_memory: BULK_MEMORY,
             simd: SIMD,
             threads: THREADS,
             tail_call: TAIL_CALL,
@@ -104,12 +112,12 @@ impl From<WasmFeatures> for near_vm_types::Features {
         Self {
             mutable_global: true,
             sign_extension: f.sign_extension,
+            bulk_memory: f.bulk_memory,
+            multi_value: f.multi_value,
 
             threads: THREADS,
             reference_types: REFERENCE_TYPES,
             simd: SIMD,
-            bulk_memory: BULK_MEMORY,
-            multi_value: MULTI_VALUE,
             tail_call: TAIL_CALL,
             multi_memory: MULTI_MEMORY,
             memory64: MEMORY64,
@@ -134,6 +142,10 @@ impl From<WasmFeatures> for wasmer_types::Features {
         // This is instead ensured by the fact that the V0 and V1 use pwasm utils in preparation
         // and it does not support these extensions.
         //
+        // Bulk memory and multi-value (`ContractPrepareVersion::V3`) are not supported by this
+        // runtime: neither the per-byte gas accounting for the bulk memory operations nor the
+        // multi-value block types have been implemented for it.
+        //
         // /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\ /!\
         Self {
             module_linking: false, // old version of component model
@@ -152,13 +164,13 @@ impl From<WasmFeatures> for wasmer_types::Features {
 
 #[cfg(feature = "wasmtime_vm")]
 impl From<WasmFeatures> for wasmtime::Config {
-    fn from(_: WasmFeatures) -> Self {
+    fn from(f: WasmFeatures) -> Self {
         let mut config = wasmtime::Config::default();
         config.wasm_threads(THREADS);
         config.wasm_reference_types(REFERENCE_TYPES);
         config.wasm_simd(SIMD);
-        config.wasm_bulk_memory(BULK_MEMORY);
-        config.wasm_multi_value(MULTI_VALUE);
+        config.wasm_bulk_memory(f.bulk_memory);
+        config.wasm_multi_value(f.multi_value);
         config.wasm_multi_memory(MULTI_MEMORY);
         config.wasm_memory64(MEMORY64);
         config
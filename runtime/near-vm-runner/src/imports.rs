@@ -91,6 +91,11 @@ imports! {
     @in internal: finite_wasm_gas<[gas: u64] -> []>,
     @in internal: finite_wasm_stack<[operand_size: u64, frame_size: u64] -> []>,
     @in internal: finite_wasm_unstack<[operand_size: u64, frame_size: u64] -> []>,
+    // ##########################
+    // # Function gas profiling #
+    // ##########################
+    #[function_gas_profiling] @in internal: profile_function_enter<[function_index: u32] -> []>,
+    #[function_gas_profiling] @in internal: profile_function_exit<[] -> []>,
     // #############
     // # Registers #
     // #############
@@ -131,6 +136,27 @@ imports! {
         pub_key_len: u64,
         pub_key_ptr: u64
     ] -> [u64]>,
+    #[ecdsa_schnorr_verify] secp256k1_verify<[sig_len: u64,
+        sig_ptr: u64,
+        hash_len: u64,
+        hash_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64
+    ] -> [u64]>,
+    #[ecdsa_schnorr_verify] secp256r1_verify<[sig_len: u64,
+        sig_ptr: u64,
+        hash_len: u64,
+        hash_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64
+    ] -> [u64]>,
+    #[ecdsa_schnorr_verify] bip340_verify<[sig_len: u64,
+        sig_ptr: u64,
+        msg_len: u64,
+        msg_ptr: u64,
+        pub_key_len: u64,
+        pub_key_ptr: u64
+    ] -> [u64]>,
     #[math_extension] ripemd160<[value_len: u64, value_ptr: u64, register_id: u64] -> []>,
     #[math_extension] ecrecover<[hash_len: u64, hash_ptr: u64, sign_len: u64, sig_ptr: u64, v: u64, malleability_flag: u64, register_id: u64] -> [u64]>,
     // #####################
@@ -261,6 +287,14 @@ imports! {
     storage_iter_prefix<[prefix_len: u64, prefix_ptr: u64] -> [u64]>,
     storage_iter_range<[start_len: u64, start_ptr: u64, end_len: u64, end_ptr: u64] -> [u64]>,
     storage_iter_next<[iterator_id: u64, key_register_id: u64, value_register_id: u64] -> [u64]>,
+    #[storage_iter_v2] storage_iter_prefix_v2<[
+        prefix_len: u64,
+        prefix_ptr: u64,
+        start_len: u64,
+        start_ptr: u64,
+        limit: u64,
+        register_id: u64
+    ] -> [u64]>,
     // Function for the injected gas counter. Automatically called by the gas meter.
     @as gas: gas_seen_from_wasm<[opcodes: u32] -> []>,
     // ###############
@@ -304,12 +338,34 @@ imports! {
     ##["test_features"] burn_gas<[gas: u64] -> []>,
 }
 
-pub(crate) use {call_with_name, for_each_available_import};
+/// Calls the host function on `VMLogic`, recording the call in the execution trace when
+/// `execution_tracing` is enabled.
+///
+/// The arguments are recorded along with their names, which is how the tracer tells the
+/// pointers and lengths it decodes from the scalar arguments.
+macro_rules! call_host_function {
+    ($logic:ident . $func:ident ( $( $arg:ident $( as $arg_type:ident )? ),* ) as $name:ident) => {
+        if $crate::imports::should_trace_host_function(stringify!($name)) && $logic.is_tracing() {
+            let args = [$( (stringify!($arg), u64::from($arg $( as $arg_type )?)) ),*];
+            $logic.trace_host_function(stringify!($name), &args, |logic| {
+                logic.$func( $( $arg $( as $arg_type )?, )* )
+            })
+        } else {
+            $logic.$func( $( $arg $( as $arg_type )?, )* )
+        }
+    };
+}
+
+pub(crate) use {call_host_function, call_with_name, for_each_available_import};
 
 pub(crate) const fn should_trace_host_function(host_function: &str) -> bool {
     match host_function {
         _ if str_eq(host_function, "gas") => false,
         _ if str_eq(host_function, "finite_wasm_gas") => false,
+        _ if str_eq(host_function, "finite_wasm_stack") => false,
+        _ if str_eq(host_function, "finite_wasm_unstack") => false,
+        _ if str_eq(host_function, "profile_function_enter") => false,
+        _ if str_eq(host_function, "profile_function_exit") => false,
         _ => true,
     }
 }
@@ -35,7 +35,7 @@ pub use cache::{
 #[cfg(feature = "metrics")]
 pub use metrics::{report_metrics, reset_metrics};
 pub use near_primitives_core::code::ContractCode;
-pub use profile::ProfileDataV3;
+pub use profile::{FunctionGasProfile, ProfileDataV3};
 pub use runner::{prepare, run, Contract, PreparedContract, VM};
 
 /// This is public for internal experimentation use only, and should otherwise be considered an
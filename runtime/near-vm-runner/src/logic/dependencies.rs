@@ -123,6 +123,16 @@ pub struct TrieNodesCount {
     pub mem_reads: u64,
 }
 
+/// Keys found by [`External::storage_iter_keys`].
+#[derive(Debug, PartialEq)]
+pub struct StorageIterKeys {
+    /// The keys found, in lexicographic order.
+    pub keys: Vec<Vec<u8>>,
+    /// Number of keys gone over to find them. Besides the returned keys, this includes the keys
+    /// of the trie skipped because they were removed by pending changes.
+    pub visited: u64,
+}
+
 impl TrieNodesCount {
     /// Used to determine the number of trie nodes charged during some operation.
     pub fn checked_sub(self, other: &Self) -> Option<Self> {
@@ -255,6 +265,39 @@ pub trait External {
     /// ```
     fn storage_has_key(&mut self, key: &[u8], mode: StorageGetMode) -> Result<bool>;
 
+    /// Returns up to `limit` keys of the storage trie associated with the current account which
+    /// start with `prefix` and are not smaller than `start`, in lexicographic order.
+    ///
+    /// Only the keys are read, and the trie nodes read while looking for them must not be counted
+    /// in [`External::get_trie_nodes_count`]. The iteration is paid for by the caller based on the
+    /// number of keys visited and the size of the keys returned.
+    ///
+    /// # Errors
+    ///
+    /// This function could return [`near_vm_runner::logic::VMError`].
+    ///
+    /// # Example
+    /// ```
+    /// # use near_vm_runner::logic::mocks::mock_external::MockedExternal;
+    /// # use near_vm_runner::logic::{External, StorageIterKeys};
+    ///
+    /// # let mut external = MockedExternal::new();
+    /// external.storage_set(b"key1", b"value1").unwrap();
+    /// external.storage_set(b"key2", b"value2").unwrap();
+    /// external.storage_set(b"key3", b"value3").unwrap();
+    /// external.storage_set(b"other", b"value").unwrap();
+    /// assert_eq!(
+    ///     external.storage_iter_keys(b"key", b"key2", 10),
+    ///     Ok(StorageIterKeys { keys: vec![b"key2".to_vec(), b"key3".to_vec()], visited: 2 })
+    /// );
+    /// assert_eq!(
+    ///     external.storage_iter_keys(b"key", b"", 1),
+    ///     Ok(StorageIterKeys { keys: vec![b"key1".to_vec()], visited: 1 })
+    /// );
+    /// ```
+    fn storage_iter_keys(&self, prefix: &[u8], start: &[u8], limit: u64)
+        -> Result<StorageIterKeys>;
+
     fn generate_data_id(&mut self) -> CryptoHash;
 
     /// Returns amount of touched trie nodes by storage operations
@@ -261,10 +261,20 @@ pub enum HostError {
     Ed25519VerifyInvalidInput {
         msg: String,
     },
+    /// Invalid input to secp256k1, secp256r1 or BIP-340 signature verification functions
+    /// (e.g. the hash has a wrong length).
+    SignatureVerifyInvalidInput {
+        msg: String,
+    },
     // Invalid input to bls12381 family of functions
     BLS12381InvalidInput {
         msg: String,
     },
+    /// The number of key-value pairs requested from `storage_iter_prefix_v2` exceeded the limit.
+    StoragePageSizeExceeded {
+        page_size: u64,
+        limit: u64,
+    },
     /// Yield payload length exceeds the maximum permitted.
     YieldPayloadLength {
         length: u64,
@@ -508,7 +518,15 @@ impl std::fmt::Display for HostError {
             Ed25519VerifyInvalidInput { msg } => {
                 write!(f, "ED25519 signature verification error: {}", msg)
             }
+            SignatureVerifyInvalidInput { msg } => {
+                write!(f, "signature verification error: {}", msg)
+            }
             BLS12381InvalidInput { msg } => write!(f, "BLS12-381 invalid input: {}", msg),
+            StoragePageSizeExceeded { page_size, limit } => write!(
+                f,
+                "The number of storage entries requested {} exceeds the limit {}",
+                page_size, limit
+            ),
             YieldPayloadLength { length, limit } => write!(
                 f,
                 "Yield resume payload is {length} bytes which exceeds the {limit} byte limit"
@@ -1,14 +1,16 @@
 use super::context::VMContext;
-use super::dependencies::{External, MemSlice, MemoryLike};
+use super::dependencies::{External, MemSlice, MemoryLike, StorageIterKeys};
 use super::errors::{FunctionCallError, InconsistentStateError};
 use super::gas_counter::GasCounter;
 use super::recorded_storage_counter::RecordedStorageCounter;
+use super::trace::{ExecutionTrace, ExecutionTracer, TraceArg, TraceEvent, TraceValue};
 use super::types::{PromiseIndex, PromiseResult, ReceiptIndex, ReturnData};
 use super::utils::split_method_names;
 use super::ValuePtr;
 use super::{HostError, VMLogicError};
 use crate::bls12381_impl;
-use crate::ProfileDataV3;
+use crate::profile::FunctionGasProfiler;
+use crate::{FunctionGasProfile, ProfileDataV3};
 use near_crypto::Secp256K1Signature;
 use near_parameters::vm::{Config, StorageGetMode};
 use near_parameters::{
@@ -20,9 +22,12 @@ use near_primitives_core::types::{
     AccountId, Balance, Compute, EpochHeight, Gas, GasWeight, StorageUsage,
 };
 use std::mem::size_of;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use ExtCosts::*;
 
+static SECP256K1: LazyLock<secp256k1::Secp256k1<secp256k1::VerifyOnly>> =
+    LazyLock::new(secp256k1::Secp256k1::verification_only);
+
 pub type Result<T, E = VMLogicError> = ::std::result::Result<T, E>;
 
 #[cfg(feature = "io_trace")]
@@ -50,6 +55,10 @@ pub struct ExecutionResultState {
     current_account_balance: Balance,
     /// Storage usage of the current account at the moment
     current_storage_usage: StorageUsage,
+    /// Attributes the burnt gas to the Wasm functions when `function_gas_profiling` is enabled.
+    function_gas_profiler: Option<FunctionGasProfiler>,
+    /// Records the host function calls when `execution_tracing` is enabled.
+    execution_tracer: Option<ExecutionTracer>,
 }
 
 impl ExecutionResultState {
@@ -65,6 +74,10 @@ impl ExecutionResultState {
             .checked_add(context.attached_deposit)
             .expect("current_account_balance overflowed");
         let current_storage_usage = context.storage_usage;
+        let function_gas_profiler = config
+            .function_gas_profiling
+            .then(|| FunctionGasProfiler::new(gas_counter.burnt_gas()));
+        let execution_tracer = config.execution_tracing.then(ExecutionTracer::default);
         Self {
             config,
             gas_counter,
@@ -73,6 +86,8 @@ impl ExecutionResultState {
             return_data: ReturnData::None,
             current_account_balance,
             current_storage_usage,
+            function_gas_profiler,
+            execution_tracer,
         }
     }
 
@@ -130,6 +145,9 @@ impl ExecutionResultState {
         let mut profile = self.gas_counter.profile_data();
         profile.compute_wasm_instruction_cost(burnt_gas);
         let compute_usage = profile.total_compute_usage(&self.config.ext_costs);
+        let function_gas_profile =
+            self.function_gas_profiler.map(|profiler| profiler.finish(burnt_gas));
+        let execution_trace = self.execution_tracer.map(ExecutionTracer::finish);
 
         VMOutcome {
             balance: self.current_account_balance,
@@ -140,6 +158,8 @@ impl ExecutionResultState {
             compute_usage,
             logs: self.logs,
             profile,
+            function_gas_profile,
+            execution_trace,
             aborted: None,
         }
     }
@@ -194,17 +214,22 @@ enum Promise {
 ///
 /// super::vmstate::get_memory_or_register has a whole lot of wordy arguments
 /// which are always the same when invoked inside of one of VMLogic method.
-/// This macro helps with that invocation.
+/// This macro helps with that invocation. The data read is also recorded in the execution trace
+/// when `execution_tracing` is enabled.
 macro_rules! get_memory_or_register {
-    ($logic:expr, $offset:expr, $len:expr) => {
-        super::vmstate::get_memory_or_register(
+    ($logic:expr, $offset:expr, $len:expr) => {{
+        let data = super::vmstate::get_memory_or_register(
             &mut $logic.result_state.gas_counter,
             &$logic.memory,
             &$logic.registers,
             $offset,
             $len,
-        )
-    };
+        );
+        if let (Ok(data), Some(tracer)) = (&data, &mut $logic.result_state.execution_tracer) {
+            tracer.input($offset, $len, TraceArg::Bytes(data.to_vec()));
+        }
+        data
+    }};
 }
 
 /// A wrapper for reading public key.
@@ -308,6 +333,58 @@ impl<'a> VMLogic<'a> {
         Ok(())
     }
 
+    // ##########################
+    // # Function gas profiling #
+    // ##########################
+
+    /// Called by the profiling instrumentation on entry to the Wasm function with the given
+    /// index. Only available when `function_gas_profiling` is enabled.
+    pub fn profile_function_enter(&mut self, function_index: u32) -> Result<()> {
+        let burnt_gas = self.result_state.gas_counter.burnt_gas();
+        if let Some(profiler) = &mut self.result_state.function_gas_profiler {
+            profiler.enter(function_index, burnt_gas);
+        }
+        Ok(())
+    }
+
+    /// Called by the profiling instrumentation on exit from the innermost Wasm function.
+    pub fn profile_function_exit(&mut self) -> Result<()> {
+        let burnt_gas = self.result_state.gas_counter.burnt_gas();
+        if let Some(profiler) = &mut self.result_state.function_gas_profiler {
+            profiler.exit(burnt_gas);
+        }
+        Ok(())
+    }
+
+    // #####################
+    // # Execution tracing #
+    // #####################
+
+    pub(crate) fn is_tracing(&self) -> bool {
+        self.result_state.execution_tracer.is_some()
+    }
+
+    /// Calls the host function and records it as a step of the execution trace, together with
+    /// the [`TraceEvent`]s it reported.
+    pub(crate) fn trace_host_function<T: TraceValue>(
+        &mut self,
+        host_function: &'static str,
+        args: &[(&'static str, u64)],
+        call: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let gas = self.result_state.gas_counter.burnt_gas();
+        let result = call(self);
+        let gas_cost = self.result_state.gas_counter.burnt_gas().saturating_sub(gas);
+        if let Some(tracer) = &mut self.result_state.execution_tracer {
+            let traced = match &result {
+                Ok(value) => Ok(value.trace_value()),
+                Err(err) => Err(err.to_string()),
+            };
+            tracer.step(host_function, args, gas, gas_cost, traced);
+        }
+        result
+    }
+
     // #################
     // # Registers API #
     // #################
@@ -526,6 +603,16 @@ impl<'a> VMLogic<'a> {
     /// Throws `NumberPromisesExceeded` if the total number of promises exceeded the limit.
     fn checked_push_promise(&mut self, promise: Promise) -> Result<PromiseIndex> {
         let new_promise_idx = self.promises.len() as PromiseIndex;
+        if let Some(tracer) = &mut self.result_state.execution_tracer {
+            let receiver_id = match &promise {
+                Promise::Receipt(receipt_idx) => Some(self.ext.get_receipt_receiver(*receipt_idx)),
+                Promise::NotReceipt(_) => None,
+            };
+            tracer.event(TraceEvent::PromiseCreated {
+                promise_index: new_promise_idx,
+                receiver_id: receiver_id.cloned(),
+            });
+        }
         self.promises.push(promise);
         if self.promises.len() as u64
             > self.config.limit_config.max_promises_per_function_call_action
@@ -544,6 +631,33 @@ impl<'a> VMLogic<'a> {
         Ok(PublicKeyBuffer::new(&get_memory_or_register!(self, ptr, len)?))
     }
 
+    /// Reads a balance from the guest memory, recording it in the execution trace if enabled.
+    fn read_balance(&mut self, ptr: u64) -> Result<Balance> {
+        let balance = self.memory.get_u128(&mut self.result_state.gas_counter, ptr)?;
+        if let Some(tracer) = &mut self.result_state.execution_tracer {
+            tracer.input(ptr, size_of::<Balance>() as u64, TraceArg::Balance(balance));
+        }
+        Ok(balance)
+    }
+
+    /// Reads an input of a signature verification host function, failing with
+    /// [`HostError::SignatureVerifyInvalidInput`] if its length is not one of `lengths`.
+    fn get_signature_verify_input(
+        &mut self,
+        ptr: u64,
+        len: u64,
+        name: &str,
+        lengths: &[usize],
+    ) -> Result<Vec<u8>> {
+        let vec = get_memory_or_register!(self, ptr, len)?;
+        if !lengths.contains(&vec.len()) {
+            return Err(VMLogicError::HostError(HostError::SignatureVerifyInvalidInput {
+                msg: format!("invalid {name} length"),
+            }));
+        }
+        Ok(vec.into_owned())
+    }
+
     // ###############
     // # Context API #
     // ###############
@@ -1697,6 +1811,172 @@ bls12381_p2_decompress_base + bls12381_p2_decompress_element * num_elements`
         }
     }
 
+    /// Verify a secp256k1 ECDSA signature given a 32-byte message hash and a public key.
+    ///
+    /// The signature is the 64-byte compact `r || s` encoding and the public key is
+    /// either a 33-byte compressed or a 65-byte uncompressed SEC1 encoding. Signatures
+    /// with a high `s` are accepted, so contracts that need to reject malleable
+    /// signatures have to check `s` themselves.
+    ///
+    /// Returns a bool indicating success (1) or failure (0) as a `u64`.
+    ///
+    /// # Errors
+    ///
+    /// * If the signature size is not equal to 64, the hash size is not equal to 32
+    ///   or the public key size is neither 33 nor 65, returns
+    ///   [HostError::SignatureVerifyInvalidInput].
+    /// * If any of the signature, hash or public key arguments are out of memory
+    ///   bounds, returns [`HostError::MemoryAccessViolation`]
+    ///
+    /// # Cost
+    ///
+    /// See [`VMLogic::ed25519_verify`] for the definition of `input_cost`.
+    ///
+    /// `input_cost(num_bytes_signature) + input_cost(num_bytes_hash) +
+    ///  input_cost(num_bytes_public_key) + secp256k1_verify_base`
+    pub fn secp256k1_verify(
+        &mut self,
+        signature_len: u64,
+        signature_ptr: u64,
+        hash_len: u64,
+        hash_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> Result<u64> {
+        self.result_state.gas_counter.pay_base(secp256k1_verify_base)?;
+
+        let signature =
+            self.get_signature_verify_input(signature_ptr, signature_len, "signature", &[64])?;
+        let hash = self.get_signature_verify_input(hash_ptr, hash_len, "hash", &[32])?;
+        let public_key = self.get_signature_verify_input(
+            public_key_ptr,
+            public_key_len,
+            "public key",
+            &[33, 65],
+        )?;
+
+        let Ok(mut signature) = secp256k1::ecdsa::Signature::from_compact(&signature) else {
+            return Ok(false as u64);
+        };
+        // libsecp256k1 only accepts signatures with a low `s`.
+        signature.normalize_s();
+        let Ok(public_key) = secp256k1::PublicKey::from_slice(&public_key) else {
+            return Ok(false as u64);
+        };
+        let message = secp256k1::Message::from_slice(&hash).expect("hash is 32 bytes long");
+        match SECP256K1.verify_ecdsa(&message, &signature, &public_key) {
+            Err(_) => Ok(false as u64),
+            Ok(()) => Ok(true as u64),
+        }
+    }
+
+    /// Verify a secp256r1 (NIST P-256) ECDSA signature given a 32-byte message hash
+    /// and a public key.
+    ///
+    /// The signature is the 64-byte `r || s` encoding and the public key is either a
+    /// 33-byte compressed or a 65-byte uncompressed SEC1 encoding. Signatures with a
+    /// high `s` are accepted.
+    ///
+    /// Returns a bool indicating success (1) or failure (0) as a `u64`.
+    ///
+    /// # Errors
+    ///
+    /// * If the signature size is not equal to 64, the hash size is not equal to 32
+    ///   or the public key size is neither 33 nor 65, returns
+    ///   [HostError::SignatureVerifyInvalidInput].
+    /// * If any of the signature, hash or public key arguments are out of memory
+    ///   bounds, returns [`HostError::MemoryAccessViolation`]
+    ///
+    /// # Cost
+    ///
+    /// See [`VMLogic::ed25519_verify`] for the definition of `input_cost`.
+    ///
+    /// `input_cost(num_bytes_signature) + input_cost(num_bytes_hash) +
+    ///  input_cost(num_bytes_public_key) + secp256r1_verify_base`
+    pub fn secp256r1_verify(
+        &mut self,
+        signature_len: u64,
+        signature_ptr: u64,
+        hash_len: u64,
+        hash_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> Result<u64> {
+        use p256::ecdsa::signature::hazmat::PrehashVerifier;
+
+        self.result_state.gas_counter.pay_base(secp256r1_verify_base)?;
+
+        let signature =
+            self.get_signature_verify_input(signature_ptr, signature_len, "signature", &[64])?;
+        let hash = self.get_signature_verify_input(hash_ptr, hash_len, "hash", &[32])?;
+        let public_key = self.get_signature_verify_input(
+            public_key_ptr,
+            public_key_len,
+            "public key",
+            &[33, 65],
+        )?;
+
+        let Ok(signature) = p256::ecdsa::Signature::from_slice(&signature) else {
+            return Ok(false as u64);
+        };
+        let Ok(public_key) = p256::ecdsa::VerifyingKey::from_sec1_bytes(&public_key) else {
+            return Ok(false as u64);
+        };
+        match public_key.verify_prehash(&hash, &signature) {
+            Err(_) => Ok(false as u64),
+            Ok(()) => Ok(true as u64),
+        }
+    }
+
+    /// Verify a BIP-340 Schnorr signature over secp256k1 given a 32-byte message and
+    /// an x-only public key.
+    ///
+    /// Returns a bool indicating success (1) or failure (0) as a `u64`.
+    ///
+    /// # Errors
+    ///
+    /// * If the signature size is not equal to 64, the message size is not equal to
+    ///   32 or the public key size is not equal to 32, returns
+    ///   [HostError::SignatureVerifyInvalidInput].
+    /// * If any of the signature, message or public key arguments are out of memory
+    ///   bounds, returns [`HostError::MemoryAccessViolation`]
+    ///
+    /// # Cost
+    ///
+    /// See [`VMLogic::ed25519_verify`] for the definition of `input_cost`.
+    ///
+    /// `input_cost(num_bytes_signature) + input_cost(num_bytes_message) +
+    ///  input_cost(num_bytes_public_key) + bip340_verify_base`
+    pub fn bip340_verify(
+        &mut self,
+        signature_len: u64,
+        signature_ptr: u64,
+        message_len: u64,
+        message_ptr: u64,
+        public_key_len: u64,
+        public_key_ptr: u64,
+    ) -> Result<u64> {
+        self.result_state.gas_counter.pay_base(bip340_verify_base)?;
+
+        let signature =
+            self.get_signature_verify_input(signature_ptr, signature_len, "signature", &[64])?;
+        let message =
+            self.get_signature_verify_input(message_ptr, message_len, "message", &[32])?;
+        let public_key =
+            self.get_signature_verify_input(public_key_ptr, public_key_len, "public key", &[32])?;
+
+        let signature =
+            secp256k1::schnorr::Signature::from_slice(&signature).expect("signature is 64 bytes");
+        let Ok(public_key) = secp256k1::XOnlyPublicKey::from_slice(&public_key) else {
+            return Ok(false as u64);
+        };
+        let message = secp256k1::Message::from_slice(&message).expect("message is 32 bytes long");
+        match SECP256K1.verify_schnorr(&signature, &message, &public_key) {
+            Err(_) => Ok(false as u64),
+            Ok(()) => Ok(true as u64),
+        }
+    }
+
     /// Consume gas. Counts both towards `burnt_gas` and `used_gas`.
     ///
     /// # Errors
@@ -2230,7 +2510,7 @@ bls12381_p2_decompress_base + bls12381_p2_decompress_element * num_elements`
             }
             .into());
         }
-        let amount = self.memory.get_u128(&mut self.result_state.gas_counter, amount_ptr)?;
+        let amount = self.read_balance(amount_ptr)?;
         let method_name = get_memory_or_register!(self, method_name_ptr, method_name_len)?;
         if method_name.is_empty() {
             return Err(HostError::EmptyMethodName.into());
@@ -2286,7 +2566,7 @@ bls12381_p2_decompress_base + bls12381_p2_decompress_element * num_elements`
             }
             .into());
         }
-        let amount = self.memory.get_u128(&mut self.result_state.gas_counter, amount_ptr)?;
+        let amount = self.read_balance(amount_ptr)?;
 
         let (receipt_idx, sir) = self.promise_idx_to_receipt_idx_with_sir(promise_idx)?;
         let receiver_id = self.ext.get_receipt_receiver(receipt_idx);
@@ -2346,7 +2626,7 @@ bls12381_p2_decompress_base + bls12381_p2_decompress_element * num_elements`
             }
             .into());
         }
-        let amount = self.memory.get_u128(&mut self.result_state.gas_counter, amount_ptr)?;
+        let amount = self.read_balance(amount_ptr)?;
         let public_key = self.get_public_key(public_key_ptr, public_key_len)?;
         let (receipt_idx, sir) = self.promise_idx_to_receipt_idx_with_sir(promise_idx)?;
         self.pay_action_base(ActionCosts::stake, sir)?;
@@ -2431,7 +2711,7 @@ bls12381_p2_decompress_base + bls12381_p2_decompress_element * num_elements`
             .into());
         }
         let public_key = self.get_public_key(public_key_ptr, public_key_len)?;
-        let allowance = self.memory.get_u128(&mut self.result_state.gas_counter, allowance_ptr)?;
+        let allowance = self.read_balance(allowance_ptr)?;
         let allowance = if allowance > 0 { Some(allowance) } else { None };
         let receiver_id = self.read_and_parse_account_id(receiver_id_ptr, receiver_id_len)?;
         let raw_method_names = get_memory_or_register!(self, method_names_ptr, method_names_len)?;
@@ -3085,6 +3365,13 @@ bls12381_p2_decompress_base + bls12381_p2_decompress_element * num_elements`
 
         self.result_state.gas_counter.add_trie_fees(&nodes_delta)?;
         self.ext.storage_set(&key, &value)?;
+        if let Some(tracer) = &mut self.result_state.execution_tracer {
+            tracer.event(TraceEvent::StorageWrite {
+                key: key.to_vec(),
+                value: value.to_vec(),
+                evicted: evicted.clone(),
+            });
+        }
         let storage_config = &self.fees_config.storage_usage_config;
         self.recorded_storage_counter.observe_size(self.ext.get_recorded_storage_size())?;
         match evicted {
@@ -3202,6 +3489,9 @@ bls12381_p2_decompress_base + bls12381_p2_decompress_element * num_elements`
             tn_mem_reads = nodes_delta.mem_reads,
         );
 
+        if let Some(tracer) = &mut self.result_state.execution_tracer {
+            tracer.event(TraceEvent::StorageRead { key: key.to_vec(), value: read.clone() });
+        }
         self.recorded_storage_counter.observe_size(self.ext.get_recorded_storage_size())?;
         match read {
             Some(value) => {
@@ -3264,6 +3554,9 @@ bls12381_p2_decompress_base + bls12381_p2_decompress_element * num_elements`
         )?;
 
         self.ext.storage_remove(&key)?;
+        if let Some(tracer) = &mut self.result_state.execution_tracer {
+            tracer.event(TraceEvent::StorageRemove { key: key.to_vec(), removed: removed.clone() });
+        }
         let nodes_delta = self
             .ext
             .get_trie_nodes_count()
@@ -3461,6 +3754,123 @@ bls12381_p2_decompress_base + bls12381_p2_decompress_element * num_elements`
         }))
     }
 
+    /// Reads a page of the key-value pairs whose keys start with the given prefix, in the
+    /// lexicographic order of the keys. Only the keys which are not smaller than `start` are
+    /// returned, so the next page can be read by passing the last returned key with a zero byte
+    /// appended as `start`.
+    /// * Writes the borsh-serialized `Vec<(Vec<u8>, Vec<u8>)>` of at most `limit` key-value pairs
+    ///   into `register_id`, even if there are none. Returns the number of pairs.
+    ///
+    /// Unlike with the deprecated iterators, no state is kept between the calls, so the storage
+    /// can be modified freely between reading two pages.
+    ///
+    /// # Errors
+    ///
+    /// * If `prefix_len + prefix_ptr` or `start_len + start_ptr` exceeds the memory container or
+    ///   points to an unused register it returns `MemoryAccessViolation`;
+    /// * If the length of the prefix or of `start` exceeds `max_length_storage_key` returns
+    ///   `KeyLengthExceeded`;
+    /// * If `limit` exceeds `max_storage_iter_page_size` returns `StoragePageSizeExceeded`;
+    /// * If the registers exceed the memory limit returns `MemoryAccessViolation`.
+    ///
+    /// # Cost
+    ///
+    /// `base + storage_iter_v2_base + storage_iter_v2_key * num_pairs
+    ///  + storage_iter_v2_byte * (num_key_bytes + num_value_bytes)
+    ///  + cost of reading the prefix and start + cost of reading the values
+    ///  + cost of writing the pairs into the register`.
+    pub fn storage_iter_prefix_v2(
+        &mut self,
+        prefix_len: u64,
+        prefix_ptr: u64,
+        start_len: u64,
+        start_ptr: u64,
+        limit: u64,
+        register_id: u64,
+    ) -> Result<u64> {
+        self.result_state.gas_counter.pay_base(base)?;
+        self.result_state.gas_counter.pay_base(storage_iter_v2_base)?;
+        let prefix = get_memory_or_register!(self, prefix_ptr, prefix_len)?;
+        let start = get_memory_or_register!(self, start_ptr, start_len)?;
+        let max_length_storage_key = self.config.limit_config.max_length_storage_key;
+        for key in [&prefix, &start] {
+            if key.len() as u64 > max_length_storage_key {
+                return Err(HostError::KeyLengthExceeded {
+                    length: key.len() as u64,
+                    limit: max_length_storage_key,
+                }
+                .into());
+            }
+        }
+        let max_page_size = self.config.limit_config.max_storage_iter_page_size;
+        if limit > max_page_size {
+            return Err(HostError::StoragePageSizeExceeded {
+                page_size: limit,
+                limit: max_page_size,
+            }
+            .into());
+        }
+
+        // The trie nodes visited by the iteration are not counted as touched, as that depends
+        // on whether the memtrie is loaded. They are paid for with the per key costs instead,
+        // including the keys skipped because they were removed earlier.
+        let StorageIterKeys { keys, visited } =
+            self.ext.storage_iter_keys(&prefix, &start, limit)?;
+        self.result_state.gas_counter.pay_per(storage_iter_v2_key, visited)?;
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys {
+            self.result_state.gas_counter.pay_per(storage_iter_v2_byte, key.len() as u64)?;
+            let nodes_before = self.ext.get_trie_nodes_count();
+            let read = self.ext.storage_get(&key, self.config.storage_get_mode);
+            let nodes_delta = self
+                .ext
+                .get_trie_nodes_count()
+                .checked_sub(&nodes_before)
+                .ok_or(InconsistentStateError::IntegerOverflow)?;
+            self.result_state.gas_counter.add_trie_fees(&nodes_delta)?;
+            // The keys come from the iteration over the same state, so they always have values.
+            let Some(read) = read? else { continue };
+            let read_len = read.len() as u64;
+            self.result_state.gas_counter.pay_per(storage_iter_v2_byte, read_len)?;
+            if read_len > INLINE_DISK_VALUE_THRESHOLD as u64 {
+                self.result_state.gas_counter.pay_base(storage_large_read_overhead_base)?;
+                self.result_state
+                    .gas_counter
+                    .pay_per(storage_large_read_overhead_byte, read_len)?;
+            }
+            let value = read.deref()?;
+
+            #[cfg(feature = "io_trace")]
+            tracing::trace!(
+                target = "io_tracer",
+                storage_op = "iter",
+                key = base64(&key),
+                size = value.len(),
+                tn_db_reads = nodes_delta.db_reads,
+                tn_mem_reads = nodes_delta.mem_reads,
+            );
+
+            if let Some(tracer) = &mut self.result_state.execution_tracer {
+                tracer.event(TraceEvent::StorageRead {
+                    key: key.clone(),
+                    value: Some(value.clone()),
+                });
+            }
+            entries.push((key, value));
+        }
+        self.recorded_storage_counter.observe_size(self.ext.get_recorded_storage_size())?;
+
+        let num_entries = entries.len() as u64;
+        let serialized = borsh::to_vec(&entries).unwrap();
+        self.registers.set(
+            &mut self.result_state.gas_counter,
+            &self.config.limit_config,
+            register_id,
+            serialized,
+        )?;
+        Ok(num_entries)
+    }
+
     /// Obtain a reference to the gas counter.
     ///
     /// This is meant for use in tests and implementation of VMs only. Implementations of host
@@ -3519,6 +3929,10 @@ pub struct VMOutcome {
     pub logs: Vec<String>,
     /// Data collected from making a contract call
     pub profile: ProfileDataV3,
+    /// Gas burnt by each Wasm function, if `function_gas_profiling` is enabled.
+    pub function_gas_profile: Option<FunctionGasProfile>,
+    /// Host function calls made by the contract, if `execution_tracing` is enabled.
+    pub execution_trace: Option<ExecutionTrace>,
     pub aborted: Option<FunctionCallError>,
 }
 
@@ -3551,6 +3965,8 @@ impl VMOutcome {
             compute_usage: 0,
             logs: Vec::new(),
             profile: ProfileDataV3::default(),
+            function_gas_profile: None,
+            execution_trace: None,
             aborted: Some(error),
         }
     }
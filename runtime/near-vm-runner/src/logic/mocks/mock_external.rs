@@ -1,4 +1,4 @@
-use crate::logic::dependencies::{Result, TrieNodesCount};
+use crate::logic::dependencies::{Result, StorageIterKeys, TrieNodesCount};
 use crate::logic::types::ReceiptIndex;
 use crate::logic::{External, StorageGetMode, ValuePtr};
 use crate::ContractCode;
@@ -149,6 +149,25 @@ impl External for MockedExternal {
         Ok(self.fake_trie.contains_key(key))
     }
 
+    fn storage_iter_keys(
+        &self,
+        prefix: &[u8],
+        start: &[u8],
+        limit: u64,
+    ) -> Result<StorageIterKeys> {
+        let mut keys: Vec<_> = self
+            .fake_trie
+            .keys()
+            .filter(|key| key.starts_with(prefix) && key.as_slice() >= start)
+            .cloned()
+            .collect();
+        keys.sort();
+        keys.truncate(limit.try_into().unwrap_or(usize::MAX));
+        // There are no pending changes to skip over.
+        let visited = keys.len() as u64;
+        Ok(StorageIterKeys { keys, visited })
+    }
+
     fn generate_data_id(&mut self) -> CryptoHash {
         // Generates some hash for the data ID to receive data. This hash should not be functionally
         // used in any mocked contexts.
@@ -10,15 +10,17 @@ pub mod recorded_storage_counter;
 pub mod test_utils;
 #[cfg(test)]
 mod tests;
+mod trace;
 pub mod types;
 mod utils;
 mod vmstate;
 
 pub use context::VMContext;
-pub use dependencies::{External, MemSlice, MemoryLike, TrieNodesCount, ValuePtr};
+pub use dependencies::{External, MemSlice, MemoryLike, StorageIterKeys, TrieNodesCount, ValuePtr};
 pub use errors::{HostError, VMLogicError};
 pub use gas_counter::{with_ext_cost_counter, GasCounter};
 pub use logic::{ExecutionResultState, VMLogic, VMOutcome};
 pub use near_parameters::vm::{Config, ContractPrepareVersion, LimitConfig, StorageGetMode};
 pub use near_primitives_core::types::ProtocolVersion;
+pub use trace::{ExecutionTrace, TraceArg, TraceEvent, TraceStep};
 pub use types::ReturnData;
@@ -1,5 +1,8 @@
+use crate::logic::tests::helpers::{assert_costs, reset_costs_counter};
 use crate::logic::tests::vm_logic_builder::VMLogicBuilder;
 use crate::logic::{HostError, VMLogicError};
+use near_parameters::ExtCosts;
+use std::collections::HashMap;
 
 #[test]
 fn test_iterator_deprecated() {
@@ -24,3 +27,99 @@ fn test_iterator_deprecated() {
         logic.storage_iter_next(0, 0, 1)
     );
 }
+
+fn iter_entries(entries: &[(&[u8], &[u8])]) -> Vec<u8> {
+    let entries: Vec<(Vec<u8>, Vec<u8>)> =
+        entries.iter().map(|(key, value)| (key.to_vec(), value.to_vec())).collect();
+    borsh::to_vec(&entries).unwrap()
+}
+
+// The v2 iterator costs are prohibitive before the protocol version that enables it, so
+// the tests below use free gas and only check the counted costs.
+#[test]
+fn test_iterator_v2_pagination() {
+    let mut logic_builder = VMLogicBuilder::free();
+    for key in [&b"aaa"[..], b"dog1", b"dog2", b"dog3", b"dogz", b"xxx"] {
+        logic_builder.ext.fake_trie.insert(key.to_vec(), [b"v_", key].concat());
+    }
+    let mut logic = logic_builder.build();
+    let prefix = logic.internal_mem_write(b"dog");
+    let empty = logic.internal_mem_write(b"");
+
+    assert_eq!(logic.storage_iter_prefix_v2(prefix.len, prefix.ptr, empty.len, 0, 2, 0), Ok(2));
+    logic.assert_read_register(&iter_entries(&[(b"dog1", b"v_dog1"), (b"dog2", b"v_dog2")]), 0);
+
+    // The start key is inclusive, so the next page starts right after the last returned key.
+    let start = logic.internal_mem_write(b"dog2\0");
+    assert_eq!(
+        logic.storage_iter_prefix_v2(prefix.len, prefix.ptr, start.len, start.ptr, 2, 0),
+        Ok(2)
+    );
+    logic.assert_read_register(&iter_entries(&[(b"dog3", b"v_dog3"), (b"dogz", b"v_dogz")]), 0);
+
+    let start = logic.internal_mem_write(b"dogz\0");
+    assert_eq!(
+        logic.storage_iter_prefix_v2(prefix.len, prefix.ptr, start.len, start.ptr, 2, 0),
+        Ok(0)
+    );
+    logic.assert_read_register(&iter_entries(&[]), 0);
+
+    // A start key before the prefix is the same as no start key at all.
+    let start = logic.internal_mem_write(b"b");
+    assert_eq!(
+        logic.storage_iter_prefix_v2(prefix.len, prefix.ptr, start.len, start.ptr, 1, 0),
+        Ok(1)
+    );
+    logic.assert_read_register(&iter_entries(&[(b"dog1", b"v_dog1")]), 0);
+}
+
+#[test]
+fn test_iterator_v2_costs() {
+    let mut logic_builder = VMLogicBuilder::free();
+    logic_builder.ext.fake_trie.insert(b"key1".to_vec(), b"value".to_vec());
+    logic_builder.ext.fake_trie.insert(b"key2".to_vec(), b"v".to_vec());
+    let mut logic = logic_builder.build();
+    let prefix = logic.internal_mem_write(b"key");
+    reset_costs_counter();
+
+    assert_eq!(logic.storage_iter_prefix_v2(prefix.len, prefix.ptr, 0, prefix.ptr, 10, 0), Ok(2));
+    assert_costs(HashMap::from([
+        (ExtCosts::base, 1),
+        (ExtCosts::read_memory_base, 2),
+        (ExtCosts::read_memory_byte, 3),
+        (ExtCosts::storage_iter_v2_base, 1),
+        (ExtCosts::storage_iter_v2_key, 2),
+        (ExtCosts::storage_iter_v2_byte, 14),
+        (ExtCosts::touching_trie_node, 0),
+        (ExtCosts::read_cached_trie_node, 0),
+        (ExtCosts::write_register_base, 1),
+        (ExtCosts::write_register_byte, 34),
+    ]));
+}
+
+#[test]
+fn test_iterator_v2_limits() {
+    let mut logic_builder = VMLogicBuilder::free();
+    let max_key_len = logic_builder.config.limit_config.max_length_storage_key;
+    let max_page_size = logic_builder.config.limit_config.max_storage_iter_page_size;
+    let mut logic = logic_builder.build();
+    let key = logic.internal_mem_write(&vec![b'a'; max_key_len as usize + 1]);
+
+    assert_eq!(
+        logic.storage_iter_prefix_v2(key.len, key.ptr, 0, key.ptr, 1, 0),
+        Err(HostError::KeyLengthExceeded { length: key.len, limit: max_key_len }.into())
+    );
+    assert_eq!(
+        logic.storage_iter_prefix_v2(0, key.ptr, key.len, key.ptr, 1, 0),
+        Err(HostError::KeyLengthExceeded { length: key.len, limit: max_key_len }.into())
+    );
+    assert_eq!(
+        logic.storage_iter_prefix_v2(0, key.ptr, 0, key.ptr, max_page_size + 1, 0),
+        Err(HostError::StoragePageSizeExceeded {
+            page_size: max_page_size + 1,
+            limit: max_page_size
+        }
+        .into())
+    );
+    assert_eq!(logic.storage_iter_prefix_v2(0, key.ptr, 0, key.ptr, max_page_size, 0), Ok(0));
+}
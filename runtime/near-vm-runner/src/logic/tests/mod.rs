@@ -9,6 +9,7 @@ mod logs;
 mod miscs;
 mod promises;
 mod registers;
+mod signature_verify;
 mod storage_read_write;
 mod storage_usage;
 mod view_method;
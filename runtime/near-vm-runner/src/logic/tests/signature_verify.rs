@@ -0,0 +1,249 @@
+use crate::logic::tests::helpers::*;
+use crate::logic::tests::vm_logic_builder::VMLogicBuilder;
+use crate::logic::HostError;
+use crate::logic::VMLogicError;
+use near_parameters::ExtCosts;
+use std::collections::HashMap;
+
+// sha256("kajdlfkjalkfjaklfjdkladjfkljadsk")
+const HASH: &str = "2448d8402cb60b159421ed55fb6cb1b1a700a0d34382e333c696cdd64ec36f39";
+
+const SECP256K1_SIGNATURE: &str =
+    "7644832a0ae3dfea9a38b1adcd47250ad4b6722e7caacb7deb5c845223e36d6b\
+    3741034b9ce9a563d8fd974e928f3cc08dcc4bf33084f436aba671f1a43184bc";
+// Same signature with `s` replaced by `n - s`.
+const SECP256K1_HIGH_S_SIGNATURE: &str =
+    "7644832a0ae3dfea9a38b1adcd47250ad4b6722e7caacb7deb5c845223e36d6b\
+    c8befcb463165a9c270268b16d70c33e2ce290f37ec3ac05142bec9b2c04bc85";
+const SECP256K1_COMPRESSED_PUBLIC_KEY: &str =
+    "03540dd2694ce77d8ea79437dfbfd2c96820f258231e08f6848260aacecb317661";
+const SECP256K1_UNCOMPRESSED_PUBLIC_KEY: &str =
+    "04540dd2694ce77d8ea79437dfbfd2c96820f258231e08f6848260aacecb317661\
+    c801dd053de6fcd1d258de2aaa28f8020b289dda56cd5169796873982eaaa027";
+
+const SECP256R1_SIGNATURE: &str =
+    "8fb7ab92df136ef03236e9012de535dc053622c52551f5ee20c9554f30a5c02c\
+    76e3831e88bf8f3b02c561883c90ede177c82d9dacbec5bbafcf100957f0adaf";
+// Same signature with `s` replaced by `n - s`.
+const SECP256R1_HIGH_S_SIGNATURE: &str =
+    "8fb7ab92df136ef03236e9012de535dc053622c52551f5ee20c9554f30a5c02c\
+    891c7ce0774070c5fd3a9e77c36f121e451ecd0ffa58d8c943eabab9a47277a2";
+const SECP256R1_COMPRESSED_PUBLIC_KEY: &str =
+    "033740af430f851db35abf262bdb86c3abcf8632719586314a6bf0e79b0cbfe533";
+const SECP256R1_UNCOMPRESSED_PUBLIC_KEY: &str =
+    "043740af430f851db35abf262bdb86c3abcf8632719586314a6bf0e79b0cbfe533\
+    c41001e1cdcc47587b376177105c36fcd570c19e549784e6d363855ef4b3cf1b";
+
+// Test vector 1 from https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+const BIP340_SIGNATURE: &str = "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de3341\
+    8906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a";
+const BIP340_MESSAGE: &str = "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89";
+const BIP340_PUBLIC_KEY: &str = "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659";
+// Test vector 5, the public key is not on the curve.
+const BIP340_INVALID_PUBLIC_KEY: &str =
+    "eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34";
+
+#[derive(Clone, Copy)]
+enum Verify {
+    Secp256k1,
+    Secp256r1,
+    Bip340,
+}
+
+impl Verify {
+    fn base_cost(self) -> ExtCosts {
+        match self {
+            Verify::Secp256k1 => ExtCosts::secp256k1_verify_base,
+            Verify::Secp256r1 => ExtCosts::secp256r1_verify_base,
+            Verify::Bip340 => ExtCosts::bip340_verify_base,
+        }
+    }
+}
+
+/// Flips the lowest bit of the last byte.
+fn tamper(hex: &str) -> Vec<u8> {
+    let mut bytes = hex::decode(hex).unwrap();
+    *bytes.last_mut().unwrap() ^= 1;
+    bytes
+}
+
+#[track_caller]
+fn check_verify(
+    verify: Verify,
+    signature: &[u8],
+    message: &[u8],
+    public_key: &[u8],
+    want: Result<u64, HostError>,
+) {
+    // The verification costs are prohibitive before the protocol version that enables them.
+    let mut logic_builder = VMLogicBuilder::free();
+    let mut logic = logic_builder.build();
+
+    let signature = logic.internal_mem_write(signature);
+    let message = logic.internal_mem_write(message);
+    let public_key = logic.internal_mem_write(public_key);
+    let args = (signature.len, signature.ptr, message.len, message.ptr);
+    let result = match verify {
+        Verify::Secp256k1 => {
+            logic.secp256k1_verify(args.0, args.1, args.2, args.3, public_key.len, public_key.ptr)
+        }
+        Verify::Secp256r1 => {
+            logic.secp256r1_verify(args.0, args.1, args.2, args.3, public_key.len, public_key.ptr)
+        }
+        Verify::Bip340 => {
+            logic.bip340_verify(args.0, args.1, args.2, args.3, public_key.len, public_key.ptr)
+        }
+    };
+
+    // Inputs are read in order and reading stops at the first one with an invalid length.
+    let read_inputs: &[u64] = if want.is_ok() || (signature.len == 64 && message.len == 32) {
+        &[signature.len, message.len, public_key.len]
+    } else if signature.len == 64 {
+        &[signature.len, message.len]
+    } else {
+        &[signature.len]
+    };
+    let want_costs = HashMap::from([
+        (ExtCosts::read_memory_base, read_inputs.len() as u64),
+        (ExtCosts::read_memory_byte, read_inputs.iter().sum()),
+        (verify.base_cost(), 1),
+    ]);
+
+    let want = want.map_err(VMLogicError::HostError);
+    assert_eq!(want, result);
+    assert_costs(want_costs);
+}
+
+fn invalid_input(msg: &str) -> Result<u64, HostError> {
+    Err(HostError::SignatureVerifyInvalidInput { msg: msg.to_string() })
+}
+
+#[track_caller]
+fn check_ecdsa_verify(
+    verify: Verify,
+    signature: &str,
+    high_s_signature: &str,
+    compressed_public_key: &str,
+    uncompressed_public_key: &str,
+) {
+    let signature = hex::decode(signature).unwrap();
+    let high_s_signature = hex::decode(high_s_signature).unwrap();
+    let hash = hex::decode(HASH).unwrap();
+    let compressed_public_key = hex::decode(compressed_public_key).unwrap();
+    let uncompressed_public_key = hex::decode(uncompressed_public_key).unwrap();
+
+    check_verify(verify, &signature, &hash, &compressed_public_key, Ok(1));
+    check_verify(verify, &signature, &hash, &uncompressed_public_key, Ok(1));
+    check_verify(verify, &high_s_signature, &hash, &compressed_public_key, Ok(1));
+
+    check_verify(verify, &signature, &tamper(HASH), &compressed_public_key, Ok(0));
+    check_verify(verify, &[0; 64], &hash, &compressed_public_key, Ok(0));
+    let mut bad_public_key = compressed_public_key.clone();
+    bad_public_key[0] = 0x05;
+    check_verify(verify, &signature, &hash, &bad_public_key, Ok(0));
+
+    check_verify(
+        verify,
+        &signature[..63],
+        &hash,
+        &compressed_public_key,
+        invalid_input("invalid signature length"),
+    );
+    check_verify(
+        verify,
+        &signature,
+        &hash[..31],
+        &compressed_public_key,
+        invalid_input("invalid hash length"),
+    );
+    check_verify(
+        verify,
+        &signature,
+        &hash,
+        &uncompressed_public_key[1..],
+        invalid_input("invalid public key length"),
+    );
+}
+
+#[test]
+fn test_secp256k1_verify() {
+    check_ecdsa_verify(
+        Verify::Secp256k1,
+        SECP256K1_SIGNATURE,
+        SECP256K1_HIGH_S_SIGNATURE,
+        SECP256K1_COMPRESSED_PUBLIC_KEY,
+        SECP256K1_UNCOMPRESSED_PUBLIC_KEY,
+    );
+}
+
+#[test]
+fn test_secp256r1_verify() {
+    check_ecdsa_verify(
+        Verify::Secp256r1,
+        SECP256R1_SIGNATURE,
+        SECP256R1_HIGH_S_SIGNATURE,
+        SECP256R1_COMPRESSED_PUBLIC_KEY,
+        SECP256R1_UNCOMPRESSED_PUBLIC_KEY,
+    );
+}
+
+#[test]
+fn test_secp256k1_and_secp256r1_signatures_are_not_interchangeable() {
+    let hash = hex::decode(HASH).unwrap();
+    check_verify(
+        Verify::Secp256k1,
+        &hex::decode(SECP256R1_SIGNATURE).unwrap(),
+        &hash,
+        &hex::decode(SECP256K1_COMPRESSED_PUBLIC_KEY).unwrap(),
+        Ok(0),
+    );
+    check_verify(
+        Verify::Secp256r1,
+        &hex::decode(SECP256K1_SIGNATURE).unwrap(),
+        &hash,
+        &hex::decode(SECP256R1_COMPRESSED_PUBLIC_KEY).unwrap(),
+        Ok(0),
+    );
+}
+
+#[test]
+fn test_bip340_verify() {
+    let signature = hex::decode(BIP340_SIGNATURE).unwrap();
+    let message = hex::decode(BIP340_MESSAGE).unwrap();
+    let public_key = hex::decode(BIP340_PUBLIC_KEY).unwrap();
+
+    check_verify(Verify::Bip340, &signature, &message, &public_key, Ok(1));
+
+    check_verify(Verify::Bip340, &tamper(BIP340_SIGNATURE), &message, &public_key, Ok(0));
+    check_verify(Verify::Bip340, &signature, &tamper(BIP340_MESSAGE), &public_key, Ok(0));
+    check_verify(
+        Verify::Bip340,
+        &signature,
+        &message,
+        &hex::decode(BIP340_INVALID_PUBLIC_KEY).unwrap(),
+        Ok(0),
+    );
+
+    check_verify(
+        Verify::Bip340,
+        &signature[..63],
+        &message,
+        &public_key,
+        invalid_input("invalid signature length"),
+    );
+    check_verify(
+        Verify::Bip340,
+        &signature,
+        &[message.as_slice(), &[0]].concat(),
+        &public_key,
+        invalid_input("invalid message length"),
+    );
+    let compressed_public_key = [&[0x02], public_key.as_slice()].concat();
+    check_verify(
+        Verify::Bip340,
+        &signature,
+        &message,
+        &compressed_public_key,
+        invalid_input("invalid public key length"),
+    );
+}
@@ -0,0 +1,159 @@
+//! Step-level tracing of the host function calls made by a contract.
+//!
+//! Tracing is enabled with the `execution_tracing` config flag, which is meant for debugging
+//! tools only and is never set when applying chunks.
+use near_primitives_core::types::{AccountId, Balance, Gas};
+
+/// Host function calls made by a contract execution, in the order they were made.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    pub steps: Vec<TraceStep>,
+}
+
+/// A single host function call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    pub host_function: &'static str,
+    /// Arguments the host function was called with. Pointers into the guest memory or
+    /// registers are replaced with the data the call read from them.
+    pub args: Vec<TraceArg>,
+    /// Gas burnt by the execution before the call.
+    pub gas: Gas,
+    /// Gas burnt by the call itself.
+    pub gas_cost: Gas,
+    /// The returned value, if any, or the error the call failed with.
+    pub result: Result<Option<u64>, String>,
+    /// State accesses and promises made by the call.
+    pub events: Vec<TraceEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceArg {
+    /// A scalar argument, such as a register id or a promise index, widened to `u64`.
+    Value(u64),
+    /// Data read through a pointer and length pair, such as a storage key.
+    Bytes(Vec<u8>),
+    /// A balance read through a pointer, such as the amount attached to a promise.
+    Balance(Balance),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceEvent {
+    StorageRead {
+        key: Vec<u8>,
+        value: Option<Vec<u8>>,
+    },
+    StorageWrite {
+        key: Vec<u8>,
+        value: Vec<u8>,
+        evicted: Option<Vec<u8>>,
+    },
+    StorageRemove {
+        key: Vec<u8>,
+        removed: Option<Vec<u8>>,
+    },
+    /// A promise backed by a new action receipt, or a joint promise created with `promise_and`
+    /// if `receiver_id` is `None`.
+    PromiseCreated {
+        promise_index: u64,
+        receiver_id: Option<AccountId>,
+    },
+}
+
+/// Host function return types that can be recorded in a [`TraceStep`].
+pub(crate) trait TraceValue {
+    fn trace_value(&self) -> Option<u64>;
+}
+
+impl TraceValue for () {
+    fn trace_value(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl TraceValue for u32 {
+    fn trace_value(&self) -> Option<u64> {
+        Some(u64::from(*self))
+    }
+}
+
+impl TraceValue for u64 {
+    fn trace_value(&self) -> Option<u64> {
+        Some(*self)
+    }
+}
+
+/// Data read by the host function call in progress, keyed by the pointer and length it was
+/// read through.
+struct TraceInput {
+    ptr: u64,
+    len: u64,
+    value: TraceArg,
+}
+
+/// Collects the [`TraceEvent`]s and inputs of the host function call in progress and the
+/// finished steps.
+#[derive(Default)]
+pub(crate) struct ExecutionTracer {
+    events: Vec<TraceEvent>,
+    inputs: Vec<TraceInput>,
+    trace: ExecutionTrace,
+}
+
+impl ExecutionTracer {
+    pub(crate) fn event(&mut self, event: TraceEvent) {
+        self.events.push(event);
+    }
+
+    /// Records the data read from the guest memory or a register, `len` is `u64::MAX` for the
+    /// latter just like in the host function arguments.
+    pub(crate) fn input(&mut self, ptr: u64, len: u64, value: TraceArg) {
+        self.inputs.push(TraceInput { ptr, len, value });
+    }
+
+    pub(crate) fn step(
+        &mut self,
+        host_function: &'static str,
+        args: &[(&'static str, u64)],
+        gas: Gas,
+        gas_cost: Gas,
+        result: Result<Option<u64>, String>,
+    ) {
+        let args = self.decode_args(args);
+        let events = std::mem::take(&mut self.events);
+        self.inputs.clear();
+        self.trace.steps.push(TraceStep { host_function, args, gas, gas_cost, result, events });
+    }
+
+    /// Replaces each `<name>_ptr`, `<name>_len` argument pair (or a lone `<name>_ptr`) with
+    /// the data the call read through it. Pointers the call did not read from, such as the
+    /// output ones or those the call failed before reaching, are kept as they are.
+    fn decode_args(&self, args: &[(&'static str, u64)]) -> Vec<TraceArg> {
+        let arg = |name: &str| args.iter().find(|(arg, _)| *arg == name).map(|(_, value)| *value);
+        let input = |base: &str| {
+            let ptr = arg(&format!("{base}_ptr"))?;
+            let len = arg(&format!("{base}_len"));
+            self.inputs
+                .iter()
+                .find(|input| input.ptr == ptr && len.map_or(true, |len| input.len == len))
+        };
+        let mut decoded = Vec::<&str>::new();
+        let mut result = Vec::with_capacity(args.len());
+        for &(name, value) in args {
+            let base = name.strip_suffix("_ptr").or_else(|| name.strip_suffix("_len"));
+            match base.and_then(|base| Some((base, input(base)?))) {
+                Some((base, _)) if decoded.contains(&base) => {}
+                Some((base, input)) => {
+                    decoded.push(base);
+                    result.push(input.value.clone());
+                }
+                None => result.push(TraceArg::Value(value)),
+            }
+        }
+        result
+    }
+
+    pub(crate) fn finish(self) -> ExecutionTrace {
+        self.trace
+    }
+}
@@ -521,6 +521,10 @@ impl near_vm_vm::Tunables for &NearVM {
         u64::from(self.config.regular_op_cost).saturating_mul((stack_size + 7) / 8)
     }
 
+    fn bulk_memory_byte_gas_cost(&self) -> u32 {
+        self.config.bulk_memory_byte_cost
+    }
+
     /// Instrumentation configuration: stack limiter config
     fn stack_limiter_cfg(&self) -> Box<dyn finite_wasm::max_stack::SizeConfig> {
         Box::new(MaxStackCfg)
@@ -772,7 +776,8 @@ impl<'e, 'l, 'lr> Resolver for NearVmImports<'e, 'l, 'lr> {
                             // lifetime and so it is safe to dereference the `env` pointer which is
                             // known to be derived from a valid `&'vmlogic mut VMLogic<'_>` in the
                             // first place.
-                            unsafe { (*env).$func( $( $arg_name, )* ) }
+                            let logic = unsafe { &mut *env };
+                            $crate::imports::call_host_function!(logic.$func( $( $arg_name ),* ) as $name)
                         }));
                         // We want to ensure that the only kind of error that host function calls
                         // return are VMLogicError. This is important because we later attempt to
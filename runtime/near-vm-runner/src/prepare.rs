@@ -4,9 +4,14 @@
 use crate::logic::errors::PrepareError;
 use near_parameters::vm::{Config, VMKind};
 
+mod bulk_memory;
+mod instrument;
 mod prepare_v0;
 mod prepare_v1;
 mod prepare_v2;
+mod profiling;
+
+pub use profiling::function_names;
 
 /// Loads the given module given in `original_code`, performs some checks on it and
 /// does some preprocessing.
@@ -25,10 +30,14 @@ pub fn prepare_contract(
     kind: VMKind,
 ) -> Result<Vec<u8>, PrepareError> {
     let prepare = config.limit_config.contract_prepare_version;
-    // NearVM => ContractPrepareVersion::V2
+    // NearVM => ContractPrepareVersion::V2 | ContractPrepareVersion::V3
     assert!(
-        (kind != VMKind::NearVm) || (prepare == crate::logic::ContractPrepareVersion::V2),
-        "NearVM only works with contract prepare version V2",
+        (kind != VMKind::NearVm)
+            || matches!(
+                prepare,
+                crate::logic::ContractPrepareVersion::V2 | crate::logic::ContractPrepareVersion::V3
+            ),
+        "NearVM only works with contract prepare version V2 or V3",
     );
     let features = crate::features::WasmFeatures::from(prepare);
     match prepare {
@@ -41,7 +50,7 @@ pub fn prepare_contract(
             prepare_v1::validate_contract(original_code, features, config)?;
             prepare_v1::prepare_contract(original_code, config)
         }
-        crate::logic::ContractPrepareVersion::V2 => {
+        crate::logic::ContractPrepareVersion::V2 | crate::logic::ContractPrepareVersion::V3 => {
             prepare_v2::prepare_contract(original_code, features, config, kind)
         }
     }
@@ -50,6 +59,7 @@ pub fn prepare_contract(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logic::ContractPrepareVersion;
     use crate::tests::{test_vm_config, with_vm_variants};
     use assert_matches::assert_matches;
 
@@ -174,4 +184,52 @@ mod tests {
             */
         })
     }
+
+    #[test]
+    fn bulk_memory_and_multi_value() {
+        let bulk_memory = r#"(module
+            (memory 1)
+            (func (export "main")
+                (memory.copy (i32.const 0) (i32.const 8) (i32.const 8))
+                (memory.fill (i32.const 0) (i32.const 42) (i32.const 8))))"#;
+        let multi_value_block = r#"(module
+            (func (export "main") (result i32)
+                (block (result i32 i32) (i32.const 1) (i32.const 2))
+                i32.add))"#;
+        let multi_value_func = r#"(module
+            (func (export "main") (result i32 i32) (i32.const 1) (i32.const 2)))"#;
+        let multi_value_import = r#"(module
+            (import "env" "f" (func (result i32 i32))))"#;
+        let multi_value_call_indirect = r#"(module
+            (type $t (func (result i32 i32)))
+            (table 1 funcref)
+            (func (export "main")
+                (call_indirect (type $t) (i32.const 0))
+                drop drop))"#;
+
+        let mut config = test_vm_config();
+        config.limit_config.contract_prepare_version = ContractPrepareVersion::V2;
+        with_vm_variants(&config, |kind| {
+            let r = parse_and_prepare_wat(&config, kind, bulk_memory);
+            assert_matches!(r, Err(PrepareError::Deserialization));
+            let r = parse_and_prepare_wat(&config, kind, multi_value_block);
+            assert_matches!(r, Err(PrepareError::Deserialization));
+        });
+
+        config.limit_config.contract_prepare_version = ContractPrepareVersion::V3;
+        with_vm_variants(&config, |kind| {
+            if !matches!(kind, VMKind::NearVm | VMKind::Wasmtime) {
+                return;
+            }
+            let r = parse_and_prepare_wat(&config, kind, bulk_memory);
+            assert_matches!(r, Ok(_));
+            let r = parse_and_prepare_wat(&config, kind, multi_value_block);
+            assert_matches!(r, Ok(_));
+            // Functions still may return at most one value.
+            for wat in [multi_value_func, multi_value_import, multi_value_call_indirect] {
+                let r = parse_and_prepare_wat(&config, kind, wat);
+                assert_matches!(r, Err(PrepareError::Deserialization));
+            }
+        });
+    }
 }
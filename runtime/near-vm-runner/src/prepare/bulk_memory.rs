@@ -0,0 +1,333 @@
+//! Instrumentation charging the per-byte gas of the bulk memory operations.
+//!
+//! The finite-wasm analysis charges a fixed cost for every instruction, so the runtimes relying
+//! on it need the length dependent part of `memory.copy`, `memory.fill` and `memory.init`
+//! injected separately. Before each of these operations the length operand is multiplied by
+//! `bulk_memory_byte_cost` and charged by calling `internal.finite_wasm_gas`, which is the very
+//! same host function the finite-wasm instrumentation calls. A function using these operations
+//! gets an extra `i32` local to hold the length. Modules not using them are left as is.
+//!
+//! The pass runs before the finite-wasm analysis, so that the stack-height accounting covers the
+//! added local and operands too.
+//!
+//! The gas hook is appended to the imported functions, so the indices of all the functions
+//! defined by the module are shifted by one.
+
+use super::instrument::{
+    shift_function_index, transform_element_section, transform_export_section, val_type,
+};
+use crate::logic::errors::PrepareError;
+use finite_wasm::wasmparser as wp;
+use wasm_encoder::{Encode, Instruction, Section, SectionId};
+
+const HOOK_MODULE: &str = "internal";
+const GAS_HOOK: &str = "finite_wasm_gas";
+const HOOK_COUNT: u32 = 1;
+
+struct InstrumentContext<'a> {
+    code: &'a [u8],
+    byte_cost: i64,
+    output_code: Vec<u8>,
+    /// Number of parameters of each of the types in the type section.
+    type_params: Vec<u32>,
+    /// Type index of each of the functions defined by the module.
+    function_types: Vec<u32>,
+    imported_functions: u32,
+    before_type_section: bool,
+    /// The code section being built and the number of its entries yet to be instrumented.
+    code_section: Vec<u8>,
+    remaining_functions: u32,
+    defined_functions: u32,
+}
+
+impl<'a> InstrumentContext<'a> {
+    fn new(code: &'a [u8], byte_cost: i64) -> Self {
+        Self {
+            code,
+            byte_cost,
+            output_code: Vec::with_capacity(code.len()),
+            type_params: Vec::new(),
+            function_types: Vec::new(),
+            imported_functions: 0,
+            before_type_section: true,
+            code_section: Vec::new(),
+            remaining_functions: 0,
+            defined_functions: 0,
+        }
+    }
+
+    fn run(&mut self) -> Result<Vec<u8>, PrepareError> {
+        let parser = wp::Parser::new(0);
+        for payload in parser.parse_all(self.code) {
+            let payload = payload.map_err(|_| PrepareError::Deserialization)?;
+            match payload {
+                wp::Payload::Version { range, .. } => self.copy(range)?,
+                wp::Payload::End(_) => {}
+                wp::Payload::TypeSection(reader) => self.transform_type_section(reader)?,
+                wp::Payload::ImportSection(reader) => self.transform_import_section(reader)?,
+                wp::Payload::FunctionSection(reader) => {
+                    for type_index in reader.clone() {
+                        let type_index = type_index.map_err(|_| PrepareError::Deserialization)?;
+                        self.function_types.push(type_index);
+                    }
+                    self.copy_section(SectionId::Function, reader.range())?;
+                }
+                wp::Payload::TableSection(reader) => {
+                    self.copy_section(SectionId::Table, reader.range())?
+                }
+                wp::Payload::GlobalSection(reader) => {
+                    self.copy_section(SectionId::Global, reader.range())?
+                }
+                wp::Payload::ExportSection(reader) => {
+                    let function_index = self.function_index_map();
+                    transform_export_section(reader, function_index, &mut self.output_code)?
+                }
+                wp::Payload::StartSection { func, .. } => {
+                    wasm_encoder::StartSection { function_index: self.function_index(func) }
+                        .append_to(&mut self.output_code);
+                }
+                wp::Payload::ElementSection(reader) => {
+                    let function_index = self.function_index_map();
+                    transform_element_section(
+                        self.code,
+                        reader.range(),
+                        function_index,
+                        &mut self.output_code,
+                    )?
+                }
+                wp::Payload::DataCountSection { range, .. } => {
+                    self.copy_section(SectionId::DataCount, range)?
+                }
+                wp::Payload::DataSection(reader) => {
+                    self.copy_section(SectionId::Data, reader.range())?
+                }
+                wp::Payload::CodeSectionStart { count, .. } => {
+                    self.remaining_functions = count;
+                    self.code_section.clear();
+                    count.encode(&mut self.code_section);
+                    self.finish_code_section();
+                }
+                wp::Payload::CodeSectionEntry(func) => {
+                    let body = self.instrument_function(&func)?;
+                    body.len().encode(&mut self.code_section);
+                    self.code_section.extend(body);
+                    self.remaining_functions = self.remaining_functions.saturating_sub(1);
+                    self.finish_code_section();
+                }
+                // The function indices in the name section would no longer be correct.
+                wp::Payload::CustomSection(_) => {}
+                _ => return Err(PrepareError::Deserialization),
+            }
+        }
+        Ok(std::mem::take(&mut self.output_code))
+    }
+
+    fn transform_type_section(
+        &mut self,
+        reader: wp::TypeSectionReader,
+    ) -> Result<(), PrepareError> {
+        self.before_type_section = false;
+        let mut section = wasm_encoder::TypeSection::new();
+        for ty in reader {
+            let ty = ty.map_err(|_| PrepareError::Deserialization)?;
+            #[allow(unreachable_patterns)]
+            let func_type = match ty {
+                wp::Type::Func(func_type) => func_type,
+                _ => return Err(PrepareError::Deserialization),
+            };
+            let params =
+                func_type.params().iter().map(|ty| val_type(*ty)).collect::<Result<Vec<_>, _>>()?;
+            let results = func_type
+                .results()
+                .iter()
+                .map(|ty| val_type(*ty))
+                .collect::<Result<Vec<_>, _>>()?;
+            let param_count =
+                u32::try_from(params.len()).map_err(|_| PrepareError::Deserialization)?;
+            self.type_params.push(param_count);
+            section.function(params, results);
+        }
+        section.function([wasm_encoder::ValType::I64], []);
+        section.append_to(&mut self.output_code);
+        Ok(())
+    }
+
+    fn transform_import_section(
+        &mut self,
+        reader: wp::ImportSectionReader,
+    ) -> Result<(), PrepareError> {
+        if self.before_type_section {
+            self.before_type_section = false;
+            let mut section = wasm_encoder::TypeSection::new();
+            section.function([wasm_encoder::ValType::I64], []);
+            section.append_to(&mut self.output_code);
+        }
+        let mut section = wasm_encoder::ImportSection::new();
+        for import in reader {
+            let import = import.map_err(|_| PrepareError::Deserialization)?;
+            let ty = match import.ty {
+                wp::TypeRef::Func(type_index) => {
+                    self.imported_functions += 1;
+                    wasm_encoder::EntityType::Function(type_index)
+                }
+                wp::TypeRef::Memory(memory) => {
+                    wasm_encoder::EntityType::Memory(wasm_encoder::MemoryType {
+                        minimum: memory.initial,
+                        maximum: memory.maximum,
+                        memory64: memory.memory64,
+                        shared: memory.shared,
+                    })
+                }
+                _ => return Err(PrepareError::Instantiate),
+            };
+            section.import(import.module, import.name, ty);
+        }
+        let hook_type =
+            u32::try_from(self.type_params.len()).map_err(|_| PrepareError::Deserialization)?;
+        section.import(HOOK_MODULE, GAS_HOOK, wasm_encoder::EntityType::Function(hook_type));
+        section.append_to(&mut self.output_code);
+        Ok(())
+    }
+
+    fn instrument_function(&mut self, func: &wp::FunctionBody) -> Result<Vec<u8>, PrepareError> {
+        let defined_index = self.defined_functions;
+        self.defined_functions += 1;
+        let params = usize::try_from(defined_index)
+            .ok()
+            .and_then(|idx| self.function_types.get(idx))
+            .and_then(|type_index| self.type_params.get(usize::try_from(*type_index).ok()?))
+            .copied()
+            .ok_or(PrepareError::Deserialization)?;
+        let gas_hook = self.imported_functions;
+
+        let mut locals = func.get_locals_reader().map_err(|_| PrepareError::Deserialization)?;
+        let local_groups = locals.get_count();
+        let local_groups_start = locals.original_position();
+        let mut length_local = params;
+        for _ in 0..local_groups {
+            let (count, _) = locals.read().map_err(|_| PrepareError::Deserialization)?;
+            length_local = length_local.checked_add(count).ok_or(PrepareError::TooManyLocals)?;
+        }
+        let mut operators =
+            func.get_operators_reader().map_err(|_| PrepareError::Deserialization)?;
+        let local_groups_end = operators.original_position();
+
+        let uses_bulk_memory = uses_bulk_memory(operators.clone())?;
+
+        let mut body = Vec::new();
+        if uses_bulk_memory {
+            // The local declarations with the length local appended.
+            (local_groups + 1).encode(&mut body);
+            body.extend(
+                self.code
+                    .get(local_groups_start..local_groups_end)
+                    .ok_or(PrepareError::Deserialization)?,
+            );
+            1_u32.encode(&mut body);
+            wasm_encoder::ValType::I32.encode(&mut body);
+        } else {
+            let locals = func.range().start..local_groups_end;
+            body.extend(self.code.get(locals).ok_or(PrepareError::Deserialization)?);
+        }
+        while !operators.eof() {
+            let (op, start) =
+                operators.read_with_offset().map_err(|_| PrepareError::Deserialization)?;
+            match op {
+                wp::Operator::Call { function_index } => {
+                    Instruction::Call(self.function_index(function_index)).encode(&mut body)
+                }
+                wp::Operator::RefFunc { function_index } => {
+                    Instruction::RefFunc(self.function_index(function_index)).encode(&mut body)
+                }
+                op => {
+                    if is_bulk_memory_operation(&op) {
+                        // The length is the topmost operand of all of these operations.
+                        Instruction::LocalTee(length_local).encode(&mut body);
+                        Instruction::LocalGet(length_local).encode(&mut body);
+                        Instruction::I64ExtendI32U.encode(&mut body);
+                        Instruction::I64Const(self.byte_cost).encode(&mut body);
+                        Instruction::I64Mul.encode(&mut body);
+                        Instruction::Call(gas_hook).encode(&mut body);
+                    }
+                    let end = operators.original_position();
+                    body.extend(self.code.get(start..end).ok_or(PrepareError::Deserialization)?);
+                }
+            }
+        }
+        Ok(body)
+    }
+
+    /// Emits the code section once all of its entries have been instrumented.
+    fn finish_code_section(&mut self) {
+        if self.remaining_functions == 0 {
+            SectionId::Code.encode(&mut self.output_code);
+            self.code_section.len().encode(&mut self.output_code);
+            self.output_code.append(&mut self.code_section);
+        }
+    }
+
+    /// The index of the given function after the gas hook has been imported.
+    fn function_index(&self, index: u32) -> u32 {
+        shift_function_index(index, self.imported_functions, HOOK_COUNT)
+    }
+
+    fn function_index_map(&self) -> impl Fn(u32) -> u32 {
+        let imported_functions = self.imported_functions;
+        move |index| shift_function_index(index, imported_functions, HOOK_COUNT)
+    }
+
+    fn copy_section(
+        &mut self,
+        id: SectionId,
+        range: std::ops::Range<usize>,
+    ) -> Result<(), PrepareError> {
+        id.encode(&mut self.output_code);
+        range.len().encode(&mut self.output_code);
+        self.copy(range)
+    }
+
+    fn copy(&mut self, range: std::ops::Range<usize>) -> Result<(), PrepareError> {
+        Ok(self.output_code.extend(self.code.get(range).ok_or(PrepareError::Deserialization)?))
+    }
+}
+
+/// The operations copying or filling a number of bytes given by their topmost operand.
+fn is_bulk_memory_operation(op: &wp::Operator) -> bool {
+    matches!(
+        op,
+        wp::Operator::MemoryCopy { .. }
+            | wp::Operator::MemoryFill { .. }
+            | wp::Operator::MemoryInit { .. }
+    )
+}
+
+fn uses_bulk_memory(operators: wp::OperatorsReader) -> Result<bool, PrepareError> {
+    for op in operators {
+        if is_bulk_memory_operation(&op.map_err(|_| PrepareError::Deserialization)?) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Injects the per-byte gas charges of the bulk memory operations into a module that went
+/// through the “early” preparation already.
+pub(crate) fn instrument(code: &[u8], byte_cost: u32) -> Result<Vec<u8>, PrepareError> {
+    let mut uses_any = false;
+    for payload in wp::Parser::new(0).parse_all(code) {
+        if let wp::Payload::CodeSectionEntry(func) =
+            payload.map_err(|_| PrepareError::Deserialization)?
+        {
+            let operators =
+                func.get_operators_reader().map_err(|_| PrepareError::Deserialization)?;
+            uses_any |= uses_bulk_memory(operators)?;
+        }
+    }
+    if !uses_any {
+        return Ok(code.to_vec());
+    }
+    InstrumentContext::new(code, i64::from(byte_cost)).run().map_err(|err| {
+        tracing::error!(?err, "bulk memory gas instrumentation failed");
+        PrepareError::Serialization
+    })
+}
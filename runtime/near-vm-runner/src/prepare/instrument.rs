@@ -0,0 +1,127 @@
+//! Helpers shared by the passes which instrument the Wasm code.
+//!
+//! The passes append imported functions, so all the references to the functions defined by the
+//! module have to be shifted accordingly, which is what most of the helpers here do.
+
+use crate::logic::errors::PrepareError;
+use finite_wasm::wasmparser as wp;
+use wasm_encoder::{Encode, Instruction, Section, SectionId};
+
+/// The index of a function after `added` functions have been appended to the
+/// `imported_functions` imported by the module.
+pub(super) fn shift_function_index(index: u32, imported_functions: u32, added: u32) -> u32 {
+    if index < imported_functions {
+        index
+    } else {
+        index + added
+    }
+}
+
+/// Rewrites the function indices of the exports, copying everything else as is.
+pub(super) fn transform_export_section(
+    reader: wp::ExportSectionReader,
+    function_index: impl Fn(u32) -> u32,
+    output: &mut Vec<u8>,
+) -> Result<(), PrepareError> {
+    let mut section = wasm_encoder::ExportSection::new();
+    for export in reader {
+        let export = export.map_err(|_| PrepareError::Deserialization)?;
+        let (kind, index) = match export.kind {
+            wp::ExternalKind::Func => {
+                (wasm_encoder::ExportKind::Func, function_index(export.index))
+            }
+            wp::ExternalKind::Table => (wasm_encoder::ExportKind::Table, export.index),
+            wp::ExternalKind::Memory => (wasm_encoder::ExportKind::Memory, export.index),
+            wp::ExternalKind::Global => (wasm_encoder::ExportKind::Global, export.index),
+            wp::ExternalKind::Tag => (wasm_encoder::ExportKind::Tag, export.index),
+        };
+        section.export(export.name, kind, index);
+    }
+    section.append_to(output);
+    Ok(())
+}
+
+/// Rewrites the function indices of the element segments, copying everything else as is.
+pub(super) fn transform_element_section(
+    code: &[u8],
+    range: std::ops::Range<usize>,
+    function_index: impl Fn(u32) -> u32,
+    output: &mut Vec<u8>,
+) -> Result<(), PrepareError> {
+    let data = code.get(range.clone()).ok_or(PrepareError::Deserialization)?;
+    let mut reader = wp::BinaryReader::new_with_offset(data, range.start);
+    let mut section = Vec::with_capacity(data.len());
+    let read_err = |_| PrepareError::Deserialization;
+    let count = reader.read_var_u32().map_err(read_err)?;
+    count.encode(&mut section);
+    for _ in 0..count {
+        let flags = reader.read_var_u32().map_err(read_err)?;
+        flags.encode(&mut section);
+        // See the binary format of the element segments in the bulk memory proposal.
+        let active = flags & 0b001 == 0;
+        let explicit_table = flags & 0b011 == 0b010;
+        if explicit_table {
+            reader.read_var_u32().map_err(read_err)?.encode(&mut section);
+        }
+        if active {
+            copy_const_expr(code, &mut reader, &function_index, &mut section)?;
+        }
+        // The element kind or the reference type byte.
+        if flags & 0b011 != 0 {
+            section.push(reader.read_u8().map_err(read_err)?);
+        }
+        let items = reader.read_var_u32().map_err(read_err)?;
+        items.encode(&mut section);
+        for _ in 0..items {
+            if flags & 0b100 == 0 {
+                let index = reader.read_var_u32().map_err(read_err)?;
+                function_index(index).encode(&mut section);
+            } else {
+                copy_const_expr(code, &mut reader, &function_index, &mut section)?;
+            }
+        }
+    }
+    if !reader.eof() {
+        return Err(PrepareError::Deserialization);
+    }
+    SectionId::Element.encode(output);
+    section.len().encode(output);
+    output.extend(section);
+    Ok(())
+}
+
+fn copy_const_expr(
+    code: &[u8],
+    reader: &mut wp::BinaryReader,
+    function_index: &impl Fn(u32) -> u32,
+    output: &mut Vec<u8>,
+) -> Result<(), PrepareError> {
+    loop {
+        let start = reader.original_position();
+        let op = reader.read_operator().map_err(|_| PrepareError::Deserialization)?;
+        match op {
+            wp::Operator::RefFunc { function_index: index } => {
+                Instruction::RefFunc(function_index(index)).encode(output)
+            }
+            op => {
+                let end = reader.original_position();
+                output.extend(code.get(start..end).ok_or(PrepareError::Deserialization)?);
+                if let wp::Operator::End = op {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn val_type(ty: wp::ValType) -> Result<wasm_encoder::ValType, PrepareError> {
+    Ok(match ty {
+        wp::ValType::I32 => wasm_encoder::ValType::I32,
+        wp::ValType::I64 => wasm_encoder::ValType::I64,
+        wp::ValType::F32 => wasm_encoder::ValType::F32,
+        wp::ValType::F64 => wasm_encoder::ValType::F64,
+        wp::ValType::V128 => wasm_encoder::ValType::V128,
+        // Reference types are not enabled.
+        wp::ValType::Ref(_) => return Err(PrepareError::Deserialization),
+    })
+}
@@ -12,6 +12,10 @@ struct PrepareContext<'a> {
     validator: wp::Validator,
     func_validator_allocations: wp::FuncValidatorAllocations,
     before_import_section: bool,
+    /// Whether the multi-value block types are allowed.
+    multi_value: bool,
+    /// Number of results of each of the types in the type section.
+    type_results: Vec<usize>,
 }
 
 impl<'a> PrepareContext<'a> {
@@ -28,6 +32,8 @@ impl<'a> PrepareContext<'a> {
             validator: wp::Validator::new_with_features(features.into()),
             func_validator_allocations: wp::FuncValidatorAllocations::default(),
             before_import_section: true,
+            multi_value: features.multi_value,
+            type_results: Vec::new(),
         }
     }
 
@@ -60,6 +66,9 @@ impl<'a> PrepareContext<'a> {
                     self.validator
                         .type_section(&reader)
                         .map_err(|_| PrepareError::Deserialization)?;
+                    if self.multi_value {
+                        self.record_type_results(&reader)?;
+                    }
                     self.copy_section(SectionId::Type, reader.range())?;
                 }
 
@@ -76,6 +85,10 @@ impl<'a> PrepareContext<'a> {
                     self.validator
                         .function_section(&reader)
                         .map_err(|_| PrepareError::Deserialization)?;
+                    for type_index in reader.clone() {
+                        let type_index = type_index.map_err(|_| PrepareError::Deserialization)?;
+                        self.check_function_type(type_index)?;
+                    }
                     self.copy_section(SectionId::Function, reader.range())?;
                 }
                 wp::Payload::TableSection(reader) => {
@@ -171,6 +184,9 @@ impl<'a> PrepareContext<'a> {
                     let mut func_validator = func_validator.into_validator(allocs);
                     func_validator.validate(&func).map_err(|_| PrepareError::Deserialization)?;
                     self.func_validator_allocations = func_validator.into_allocations();
+                    if self.multi_value {
+                        self.check_call_indirect_types(&func)?;
+                    }
                 }
                 wp::Payload::CustomSection(reader) => {
                     if !self.config.discard_custom_sections {
@@ -214,6 +230,7 @@ impl<'a> PrepareContext<'a> {
             let new_type = match import.ty {
                 wp::TypeRef::Func(id) => {
                     // TODO: validate imported function types here.
+                    self.check_function_type(id)?;
                     self.function_limit =
                         self.function_limit.checked_sub(1).ok_or(PrepareError::TooManyFunctions)?;
                     wasm_encoder::EntityType::Function(id)
@@ -231,6 +248,46 @@ impl<'a> PrepareContext<'a> {
         Ok(())
     }
 
+    fn record_type_results(&mut self, reader: &wp::TypeSectionReader) -> Result<(), PrepareError> {
+        for ty in reader.clone() {
+            let ty = ty.map_err(|_| PrepareError::Deserialization)?;
+            #[allow(unreachable_patterns)]
+            let results = match ty {
+                wp::Type::Func(func_type) => func_type.results().len(),
+                _ => return Err(PrepareError::Deserialization),
+            };
+            self.type_results.push(results);
+        }
+        Ok(())
+    }
+
+    /// Multi-value is only supported for the block types: functions (including the imported
+    /// ones) may not return more than one value.
+    fn check_function_type(&self, type_index: u32) -> Result<(), PrepareError> {
+        let results = usize::try_from(type_index)
+            .ok()
+            .and_then(|idx| self.type_results.get(idx))
+            .copied()
+            .unwrap_or(0);
+        if results > 1 {
+            tracing::trace!(type_index, "function returns multiple values");
+            return Err(PrepareError::Deserialization);
+        }
+        Ok(())
+    }
+
+    /// `call_indirect` may not refer to a type returning multiple values either.
+    fn check_call_indirect_types(&self, func: &wp::FunctionBody) -> Result<(), PrepareError> {
+        let reader = func.get_operators_reader().map_err(|_| PrepareError::Deserialization)?;
+        for op in reader {
+            let op = op.map_err(|_| PrepareError::Deserialization)?;
+            if let wp::Operator::CallIndirect { type_index, .. } = op {
+                self.check_function_type(type_index)?;
+            }
+        }
+        Ok(())
+    }
+
     fn ensure_import_section(&mut self) {
         if self.before_import_section {
             self.before_import_section = false;
@@ -272,12 +329,20 @@ pub(crate) fn prepare_contract(
     config: &Config,
     kind: VMKind,
 ) -> Result<Vec<u8>, PrepareError> {
-    let lightly_steamed = PrepareContext::new(original_code, features, config).run()?;
+    let mut lightly_steamed = PrepareContext::new(original_code, features, config).run()?;
+    if config.function_gas_profiling {
+        lightly_steamed = super::profiling::instrument(&lightly_steamed)?;
+    }
 
     if kind == VMKind::NearVm {
         // Built-in near-vm code instruments code for itself.
         return Ok(lightly_steamed);
     }
+    if features.bulk_memory {
+        // finite-wasm only charges a fixed cost for each instruction, add the per-byte part.
+        lightly_steamed =
+            super::bulk_memory::instrument(&lightly_steamed, config.bulk_memory_byte_cost)?;
+    }
 
     let res = finite_wasm::Analysis::new()
         .with_stack(Box::new(SimpleMaxStackCfg))
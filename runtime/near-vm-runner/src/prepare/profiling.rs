@@ -0,0 +1,320 @@
+//! Instrumentation attributing the gas burnt by a contract to its functions.
+//!
+//! Every function defined by the module calls `internal.profile_function_enter` with its index
+//! when entered and `internal.profile_function_exit` on every way out other than a trap. The
+//! original body is wrapped into a block, so that branching to the outermost label falls
+//! through to the exit hook just like reaching the end of the function does.
+//!
+//! The two hooks are appended to the imported functions, so the indices of all the functions
+//! defined by the module are shifted by two. The indices reported to the hooks are the
+//! original ones.
+
+use super::instrument::{
+    shift_function_index, transform_element_section, transform_export_section, val_type,
+};
+use crate::logic::errors::PrepareError;
+use finite_wasm::wasmparser as wp;
+use std::collections::HashMap;
+use wasm_encoder::{Encode, Instruction, Section, SectionId};
+
+const HOOK_MODULE: &str = "internal";
+const ENTER_HOOK: &str = "profile_function_enter";
+const EXIT_HOOK: &str = "profile_function_exit";
+const HOOK_COUNT: u32 = 2;
+
+struct InstrumentContext<'a> {
+    code: &'a [u8],
+    output_code: Vec<u8>,
+    /// Block type matching the results of each of the types in the type section.
+    block_types: Vec<wasm_encoder::BlockType>,
+    /// Type index of each of the functions defined by the module.
+    function_types: Vec<u32>,
+    imported_functions: u32,
+    before_type_section: bool,
+    /// The code section being built and the number of its entries yet to be instrumented.
+    code_section: Vec<u8>,
+    remaining_functions: u32,
+    defined_functions: u32,
+}
+
+impl<'a> InstrumentContext<'a> {
+    fn new(code: &'a [u8]) -> Self {
+        Self {
+            code,
+            output_code: Vec::with_capacity(code.len()),
+            block_types: Vec::new(),
+            function_types: Vec::new(),
+            imported_functions: 0,
+            before_type_section: true,
+            code_section: Vec::new(),
+            remaining_functions: 0,
+            defined_functions: 0,
+        }
+    }
+
+    fn run(&mut self) -> Result<Vec<u8>, PrepareError> {
+        let parser = wp::Parser::new(0);
+        for payload in parser.parse_all(self.code) {
+            let payload = payload.map_err(|_| PrepareError::Deserialization)?;
+            match payload {
+                wp::Payload::Version { range, .. } => self.copy(range)?,
+                wp::Payload::End(_) => {}
+                wp::Payload::TypeSection(reader) => self.transform_type_section(reader)?,
+                wp::Payload::ImportSection(reader) => self.transform_import_section(reader)?,
+                wp::Payload::FunctionSection(reader) => {
+                    for type_index in reader.clone() {
+                        let type_index = type_index.map_err(|_| PrepareError::Deserialization)?;
+                        self.function_types.push(type_index);
+                    }
+                    self.copy_section(SectionId::Function, reader.range())?;
+                }
+                wp::Payload::TableSection(reader) => {
+                    self.copy_section(SectionId::Table, reader.range())?
+                }
+                wp::Payload::GlobalSection(reader) => {
+                    self.copy_section(SectionId::Global, reader.range())?
+                }
+                wp::Payload::ExportSection(reader) => {
+                    let function_index = self.function_index_map();
+                    transform_export_section(reader, function_index, &mut self.output_code)?
+                }
+                wp::Payload::StartSection { func, .. } => {
+                    wasm_encoder::StartSection { function_index: self.function_index(func) }
+                        .append_to(&mut self.output_code);
+                }
+                wp::Payload::ElementSection(reader) => {
+                    let function_index = self.function_index_map();
+                    transform_element_section(
+                        self.code,
+                        reader.range(),
+                        function_index,
+                        &mut self.output_code,
+                    )?
+                }
+                wp::Payload::DataCountSection { range, .. } => {
+                    self.copy_section(SectionId::DataCount, range)?
+                }
+                wp::Payload::DataSection(reader) => {
+                    self.copy_section(SectionId::Data, reader.range())?
+                }
+                wp::Payload::CodeSectionStart { count, .. } => {
+                    self.remaining_functions = count;
+                    self.code_section.clear();
+                    count.encode(&mut self.code_section);
+                    self.finish_code_section();
+                }
+                wp::Payload::CodeSectionEntry(func) => {
+                    let body = self.instrument_function(&func)?;
+                    body.len().encode(&mut self.code_section);
+                    self.code_section.extend(body);
+                    self.remaining_functions = self.remaining_functions.saturating_sub(1);
+                    self.finish_code_section();
+                }
+                // The function indices in the name section would no longer be correct, and the
+                // names are looked up in the original code anyway.
+                wp::Payload::CustomSection(_) => {}
+                _ => return Err(PrepareError::Deserialization),
+            }
+        }
+        Ok(std::mem::take(&mut self.output_code))
+    }
+
+    fn transform_type_section(
+        &mut self,
+        reader: wp::TypeSectionReader,
+    ) -> Result<(), PrepareError> {
+        self.before_type_section = false;
+        let mut section = wasm_encoder::TypeSection::new();
+        for ty in reader {
+            let ty = ty.map_err(|_| PrepareError::Deserialization)?;
+            #[allow(unreachable_patterns)]
+            let func_type = match ty {
+                wp::Type::Func(func_type) => func_type,
+                _ => return Err(PrepareError::Deserialization),
+            };
+            let params =
+                func_type.params().iter().map(|ty| val_type(*ty)).collect::<Result<Vec<_>, _>>()?;
+            let results = func_type
+                .results()
+                .iter()
+                .map(|ty| val_type(*ty))
+                .collect::<Result<Vec<_>, _>>()?;
+            self.block_types.push(match results.as_slice() {
+                [] => wasm_encoder::BlockType::Empty,
+                [ty] => wasm_encoder::BlockType::Result(*ty),
+                // Rejected by the preparation already.
+                _ => return Err(PrepareError::Deserialization),
+            });
+            section.function(params, results);
+        }
+        self.add_hook_types(&mut section);
+        section.append_to(&mut self.output_code);
+        Ok(())
+    }
+
+    fn add_hook_types(&self, section: &mut wasm_encoder::TypeSection) {
+        section.function([wasm_encoder::ValType::I32], []);
+        section.function([], []);
+    }
+
+    fn transform_import_section(
+        &mut self,
+        reader: wp::ImportSectionReader,
+    ) -> Result<(), PrepareError> {
+        if self.before_type_section {
+            self.before_type_section = false;
+            let mut section = wasm_encoder::TypeSection::new();
+            self.add_hook_types(&mut section);
+            section.append_to(&mut self.output_code);
+        }
+        let mut section = wasm_encoder::ImportSection::new();
+        for import in reader {
+            let import = import.map_err(|_| PrepareError::Deserialization)?;
+            let ty = match import.ty {
+                wp::TypeRef::Func(type_index) => {
+                    self.imported_functions += 1;
+                    wasm_encoder::EntityType::Function(type_index)
+                }
+                wp::TypeRef::Memory(memory) => {
+                    wasm_encoder::EntityType::Memory(wasm_encoder::MemoryType {
+                        minimum: memory.initial,
+                        maximum: memory.maximum,
+                        memory64: memory.memory64,
+                        shared: memory.shared,
+                    })
+                }
+                _ => return Err(PrepareError::Instantiate),
+            };
+            section.import(import.module, import.name, ty);
+        }
+        let hook_type =
+            u32::try_from(self.block_types.len()).map_err(|_| PrepareError::Deserialization)?;
+        section.import(HOOK_MODULE, ENTER_HOOK, wasm_encoder::EntityType::Function(hook_type));
+        section.import(HOOK_MODULE, EXIT_HOOK, wasm_encoder::EntityType::Function(hook_type + 1));
+        section.append_to(&mut self.output_code);
+        Ok(())
+    }
+
+    fn instrument_function(&mut self, func: &wp::FunctionBody) -> Result<Vec<u8>, PrepareError> {
+        let defined_index = self.defined_functions;
+        self.defined_functions += 1;
+        let block_type = usize::try_from(defined_index)
+            .ok()
+            .and_then(|idx| self.function_types.get(idx))
+            .and_then(|type_index| self.block_types.get(usize::try_from(*type_index).ok()?))
+            .copied()
+            .ok_or(PrepareError::Deserialization)?;
+        let function_index = self.imported_functions + defined_index;
+        let enter_hook = self.imported_functions;
+        let exit_hook = self.imported_functions + 1;
+
+        let mut operators =
+            func.get_operators_reader().map_err(|_| PrepareError::Deserialization)?;
+        let mut body = Vec::new();
+        // The local declarations.
+        let locals = func.range().start..operators.original_position();
+        body.extend(self.code.get(locals).ok_or(PrepareError::Deserialization)?);
+        Instruction::I32Const(function_index as i32).encode(&mut body);
+        Instruction::Call(enter_hook).encode(&mut body);
+        Instruction::Block(block_type).encode(&mut body);
+        while !operators.eof() {
+            let (op, start) =
+                operators.read_with_offset().map_err(|_| PrepareError::Deserialization)?;
+            match op {
+                wp::Operator::Call { function_index } => {
+                    Instruction::Call(self.function_index(function_index)).encode(&mut body)
+                }
+                wp::Operator::RefFunc { function_index } => {
+                    Instruction::RefFunc(self.function_index(function_index)).encode(&mut body)
+                }
+                wp::Operator::Return => {
+                    Instruction::Call(exit_hook).encode(&mut body);
+                    Instruction::Return.encode(&mut body);
+                }
+                _ => {
+                    let end = operators.original_position();
+                    body.extend(self.code.get(start..end).ok_or(PrepareError::Deserialization)?);
+                }
+            }
+        }
+        // The final `end` of the original body closes the block now.
+        Instruction::Call(exit_hook).encode(&mut body);
+        Instruction::End.encode(&mut body);
+        Ok(body)
+    }
+
+    /// Emits the code section once all of its entries have been instrumented.
+    fn finish_code_section(&mut self) {
+        if self.remaining_functions == 0 {
+            SectionId::Code.encode(&mut self.output_code);
+            self.code_section.len().encode(&mut self.output_code);
+            self.output_code.append(&mut self.code_section);
+        }
+    }
+
+    /// The index of the given function after the hooks have been imported.
+    fn function_index(&self, index: u32) -> u32 {
+        shift_function_index(index, self.imported_functions, HOOK_COUNT)
+    }
+
+    fn function_index_map(&self) -> impl Fn(u32) -> u32 {
+        let imported_functions = self.imported_functions;
+        move |index| shift_function_index(index, imported_functions, HOOK_COUNT)
+    }
+
+    fn copy_section(
+        &mut self,
+        id: SectionId,
+        range: std::ops::Range<usize>,
+    ) -> Result<(), PrepareError> {
+        id.encode(&mut self.output_code);
+        range.len().encode(&mut self.output_code);
+        self.copy(range)
+    }
+
+    fn copy(&mut self, range: std::ops::Range<usize>) -> Result<(), PrepareError> {
+        Ok(self.output_code.extend(self.code.get(range).ok_or(PrepareError::Deserialization)?))
+    }
+}
+
+/// Injects the function gas profiling hooks into a module that went through the “early”
+/// preparation already.
+pub(crate) fn instrument(code: &[u8]) -> Result<Vec<u8>, PrepareError> {
+    InstrumentContext::new(code).run().map_err(|err| {
+        tracing::error!(?err, "function gas profiling instrumentation failed");
+        PrepareError::Serialization
+    })
+}
+
+/// Names of the functions defined or imported by the contract, indexed like in the profiles
+/// reported by the runtime.
+///
+/// Names from the `name` custom section take precedence over the export names. This is best
+/// effort: an invalid module simply yields fewer names.
+pub fn function_names(code: &[u8]) -> HashMap<u32, String> {
+    let mut names = HashMap::new();
+    for payload in wp::Parser::new(0).parse_all(code) {
+        let Ok(payload) = payload else { break };
+        match payload {
+            wp::Payload::ExportSection(reader) => {
+                for export in reader.into_iter().flatten() {
+                    if matches!(export.kind, wp::ExternalKind::Func) {
+                        names.entry(export.index).or_insert_with(|| export.name.to_string());
+                    }
+                }
+            }
+            wp::Payload::CustomSection(reader) if reader.name() == "name" => {
+                let subsections = wp::NameSectionReader::new(reader.data(), reader.data_offset());
+                for subsection in subsections.into_iter().flatten() {
+                    if let wp::Name::Function(map) = subsection {
+                        for naming in map.into_iter().flatten() {
+                            names.insert(naming.index, naming.name.to_string());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
@@ -2,6 +2,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use enum_map::{enum_map, Enum, EnumMap};
 use near_parameters::{ActionCosts, ExtCosts, ExtCostsConfig};
 use near_primitives_core::types::{Compute, Gas};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use strum::IntoEnumIterator;
 
@@ -231,6 +232,89 @@ impl fmt::Debug for ProfileDataV3 {
     }
 }
 
+/// Gas burnt by a contract call broken down by the stacks of Wasm functions that burnt it.
+///
+/// Only collected when [`Config::function_gas_profiling`] is enabled. Gas burnt while no Wasm
+/// function is executing, such as the contract loading cost, is not attributed.
+///
+/// [`Config::function_gas_profiling`]: near_parameters::vm::Config::function_gas_profiling
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FunctionGasProfile {
+    /// Exclusive gas burnt by the innermost function of each stack. Stacks are lists of Wasm
+    /// function indices, outermost function first.
+    pub stacks: BTreeMap<Vec<u32>, Gas>,
+}
+
+impl FunctionGasProfile {
+    /// Renders the profile in the folded stacks format understood by the flamegraph tools: one
+    /// `outer;inner gas` line per stack.
+    ///
+    /// Functions missing from `names` are rendered as `func[index]`.
+    pub fn to_folded(&self, names: &HashMap<u32, String>) -> String {
+        let mut folded = String::new();
+        for (stack, gas) in &self.stacks {
+            for (i, index) in stack.iter().enumerate() {
+                if i != 0 {
+                    folded.push(';');
+                }
+                match names.get(index) {
+                    Some(name) => folded.push_str(name),
+                    None => folded.push_str(&format!("func[{index}]")),
+                }
+            }
+            folded.push_str(&format!(" {gas}\n"));
+        }
+        folded
+    }
+}
+
+/// Builds a [`FunctionGasProfile`] from the function entry and exit events reported by the
+/// instrumented contract.
+pub(crate) struct FunctionGasProfiler {
+    stack: Vec<u32>,
+    /// Gas burnt at the time of the previous event.
+    last_burnt_gas: Gas,
+    profile: FunctionGasProfile,
+}
+
+impl FunctionGasProfiler {
+    pub(crate) fn new(burnt_gas: Gas) -> Self {
+        Self {
+            stack: Vec::new(),
+            last_burnt_gas: burnt_gas,
+            profile: FunctionGasProfile::default(),
+        }
+    }
+
+    /// Attributes the gas burnt since the previous event to the current stack.
+    fn attribute(&mut self, burnt_gas: Gas) {
+        let gas = burnt_gas.saturating_sub(self.last_burnt_gas);
+        self.last_burnt_gas = burnt_gas;
+        if gas == 0 || self.stack.is_empty() {
+            return;
+        }
+        let entry = self.profile.stacks.entry(self.stack.clone()).or_default();
+        *entry = entry.saturating_add(gas);
+    }
+
+    pub(crate) fn enter(&mut self, function_index: u32, burnt_gas: Gas) {
+        self.attribute(burnt_gas);
+        self.stack.push(function_index);
+    }
+
+    pub(crate) fn exit(&mut self, burnt_gas: Gas) {
+        self.attribute(burnt_gas);
+        self.stack.pop();
+    }
+
+    /// Attributes the remaining gas to the functions that were executing when the call
+    /// finished, which is only the case if it was aborted.
+    pub(crate) fn finish(mut self, burnt_gas: Gas) -> FunctionGasProfile {
+        self.attribute(burnt_gas);
+        self.profile
+    }
+}
+
 /// Tests for ProfileDataV3
 #[cfg(test)]
 mod test {
@@ -244,7 +328,7 @@ mod test {
         let pretty_debug_str = format!("{profile_data:#?}");
         expect_test::expect![[r#"
             ------------------------------
-            Action gas: 16120
+            Action gas: 17136
             ------ Host functions --------
             contract_loading_base -> 1 [0% host]
             contract_loading_bytes -> 2 [0% host]
@@ -281,11 +365,11 @@ mod test {
             storage_remove_base -> 33 [0% host]
             storage_remove_key_byte -> 34 [0% host]
             storage_remove_ret_value_byte -> 35 [0% host]
-            storage_has_key_base -> 36 [1% host]
-            storage_has_key_byte -> 37 [1% host]
-            storage_iter_create_prefix_base -> 38 [1% host]
-            storage_iter_create_prefix_byte -> 39 [1% host]
-            storage_iter_create_range_base -> 40 [1% host]
+            storage_has_key_base -> 36 [0% host]
+            storage_has_key_byte -> 37 [0% host]
+            storage_iter_create_prefix_base -> 38 [0% host]
+            storage_iter_create_prefix_byte -> 39 [0% host]
+            storage_iter_create_range_base -> 40 [0% host]
             storage_iter_create_from_byte -> 41 [1% host]
             storage_iter_create_to_byte -> 42 [1% host]
             storage_iter_next_base -> 43 [1% host]
@@ -317,19 +401,25 @@ mod test {
             bls12381_g1_multiexp_base -> 69 [1% host]
             bls12381_g1_multiexp_element -> 70 [1% host]
             bls12381_g2_multiexp_base -> 71 [1% host]
-            bls12381_g2_multiexp_element -> 72 [2% host]
-            bls12381_map_fp_to_g1_base -> 73 [2% host]
-            bls12381_map_fp_to_g1_element -> 74 [2% host]
-            bls12381_map_fp2_to_g2_base -> 75 [2% host]
-            bls12381_map_fp2_to_g2_element -> 76 [2% host]
-            bls12381_pairing_base -> 77 [2% host]
-            bls12381_pairing_element -> 78 [2% host]
-            bls12381_p1_decompress_base -> 79 [2% host]
-            bls12381_p1_decompress_element -> 80 [2% host]
-            bls12381_p2_decompress_base -> 81 [2% host]
+            bls12381_g2_multiexp_element -> 72 [1% host]
+            bls12381_map_fp_to_g1_base -> 73 [1% host]
+            bls12381_map_fp_to_g1_element -> 74 [1% host]
+            bls12381_map_fp2_to_g2_base -> 75 [1% host]
+            bls12381_map_fp2_to_g2_element -> 76 [1% host]
+            bls12381_pairing_base -> 77 [1% host]
+            bls12381_pairing_element -> 78 [1% host]
+            bls12381_p1_decompress_base -> 79 [1% host]
+            bls12381_p1_decompress_element -> 80 [1% host]
+            bls12381_p2_decompress_base -> 81 [1% host]
             bls12381_p2_decompress_element -> 82 [2% host]
             storage_large_read_overhead_base -> 83 [2% host]
             storage_large_read_overhead_byte -> 84 [2% host]
+            secp256k1_verify_base -> 85 [2% host]
+            secp256r1_verify_base -> 86 [2% host]
+            bip340_verify_base -> 87 [2% host]
+            storage_iter_v2_base -> 88 [2% host]
+            storage_iter_v2_key -> 89 [2% host]
+            storage_iter_v2_byte -> 90 [2% host]
             ------ Actions --------
             create_account -> 1000
             delete_account -> 1001
@@ -347,6 +437,7 @@ mod test {
             new_data_receipt_base -> 1013
             new_data_receipt_byte -> 1014
             delegate -> 1015
+            deploy_contract_with_init -> 1016
             ------------------------------
         "#]]
         .assert_eq(&pretty_debug_str)
@@ -462,6 +553,27 @@ mod test {
         assert_eq!(90, profile.wasm_gas);
     }
 
+    #[test]
+    fn test_function_gas_profiler() {
+        let mut profiler = FunctionGasProfiler::new(100);
+        profiler.enter(2, 150);
+        profiler.enter(3, 160);
+        profiler.exit(190);
+        profiler.enter(3, 200);
+        profiler.exit(205);
+        profiler.enter(1, 205);
+        // Aborted while executing `1`.
+        let profile = profiler.finish(300);
+
+        let names = HashMap::from([(2, "main".to_string()), (3, "helper".to_string())]);
+        expect_test::expect![[r#"
+            main 20
+            main;func[1] 95
+            main;helper 35
+        "#]]
+        .assert_eq(&profile.to_folded(&names));
+    }
+
     #[track_caller]
     fn manually_encode_profile_v2(
         action_profile: Vec<u64>,
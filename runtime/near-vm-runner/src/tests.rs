@@ -1,11 +1,14 @@
 mod cache;
 mod compile_errors;
+mod execution_tracing;
+mod function_profiling;
 mod fuzzers;
 mod regression_tests;
 mod rs_contract;
 mod runtime_errors;
 pub(crate) mod test_builder;
 mod ts_contract;
+mod wasm_proposals;
 mod wasm_validation;
 
 use crate::logic::VMContext;
@@ -46,8 +49,11 @@ pub(crate) fn with_vm_variants(
     run(VMKind::Wasmer2);
 
     #[cfg(all(feature = "near_vm", target_arch = "x86_64"))]
-    if cfg.limit_config.contract_prepare_version == near_parameters::vm::ContractPrepareVersion::V2
-    {
+    if matches!(
+        cfg.limit_config.contract_prepare_version,
+        near_parameters::vm::ContractPrepareVersion::V2
+            | near_parameters::vm::ContractPrepareVersion::V3
+    ) {
         run(VMKind::NearVm);
     }
 }
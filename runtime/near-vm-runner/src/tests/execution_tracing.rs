@@ -0,0 +1,70 @@
+use crate::logic::mocks::mock_external::MockedExternal;
+use crate::logic::{Config, TraceArg, TraceEvent, VMOutcome};
+use crate::runner::VMKindExt;
+use crate::tests::{create_context, test_vm_config, with_vm_variants};
+use crate::ContractCode;
+use near_parameters::vm::VMKind;
+use near_parameters::RuntimeFeesConfig;
+use std::sync::Arc;
+
+/// Writes `"k" => "v"` and reads it back into register 0.
+const CONTRACT: &str = r#"
+(module
+  (import "env" "storage_write" (func $storage_write (param i64 i64 i64 i64 i64) (result i64)))
+  (import "env" "storage_read" (func $storage_read (param i64 i64 i64) (result i64)))
+  (memory 1)
+  (data (i32.const 0) "kv")
+  (func (export "main")
+    (drop (call $storage_write (i64.const 1) (i64.const 0) (i64.const 1) (i64.const 1) (i64.const 1)))
+    (drop (call $storage_read (i64.const 1) (i64.const 0) (i64.const 0)))))
+"#;
+
+fn run(code: &[u8], config: Config, vm_kind: VMKind) -> VMOutcome {
+    let code = ContractCode::new(code.to_vec(), None);
+    let mut fake_external = MockedExternal::with_code(code);
+    let context = create_context(vec![]);
+    let fees = Arc::new(RuntimeFeesConfig::test());
+    let gas_counter = context.make_gas_counter(&config);
+    let runtime = vm_kind.runtime(Arc::new(config)).expect("runtime has not been compiled");
+    runtime
+        .prepare(&fake_external, None, gas_counter, "main")
+        .run(&mut fake_external, &context, fees)
+        .expect("execution failed")
+}
+
+#[test]
+fn test_execution_trace() {
+    let code = wat::parse_str(CONTRACT).unwrap();
+    let config = test_vm_config();
+    with_vm_variants(&config, |vm_kind: VMKind| {
+        let plain = run(&code, config.clone(), vm_kind);
+        assert_eq!(plain.execution_trace, None);
+
+        let traced = run(&code, Config { execution_tracing: true, ..config.clone() }, vm_kind);
+        assert_eq!(traced.aborted, None);
+        assert_eq!(traced.burnt_gas, plain.burnt_gas);
+
+        let trace = traced.execution_trace.expect("trace must be collected");
+        let steps = trace.steps.iter().map(|step| step.host_function).collect::<Vec<_>>();
+        assert_eq!(steps, ["storage_write", "storage_read"]);
+
+        let [write, read] = &trace.steps[..] else { unreachable!() };
+        assert_eq!(
+            write.args,
+            [TraceArg::Bytes(b"k".to_vec()), TraceArg::Bytes(b"v".to_vec()), TraceArg::Value(1)]
+        );
+        assert_eq!(write.result, Ok(Some(0)));
+        assert_eq!(
+            write.events,
+            [TraceEvent::StorageWrite { key: b"k".to_vec(), value: b"v".to_vec(), evicted: None }]
+        );
+        assert_eq!(read.args, [TraceArg::Bytes(b"k".to_vec()), TraceArg::Value(0)]);
+        assert_eq!(read.result, Ok(Some(1)));
+        assert_eq!(
+            read.events,
+            [TraceEvent::StorageRead { key: b"k".to_vec(), value: Some(b"v".to_vec()) }]
+        );
+        assert!(write.gas_cost > 0 && write.gas + write.gas_cost <= read.gas);
+        assert!(read.gas + read.gas_cost <= traced.burnt_gas);
+    });
+}
@@ -0,0 +1,79 @@
+use crate::logic::mocks::mock_external::MockedExternal;
+use crate::logic::types::ReturnData;
+use crate::logic::{Config, VMOutcome};
+use crate::prepare::function_names;
+use crate::runner::VMKindExt;
+use crate::tests::{create_context, test_vm_config, with_vm_variants};
+use crate::ContractCode;
+use near_parameters::vm::VMKind;
+use near_parameters::RuntimeFeesConfig;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// Returns the sum of the squares of 1..=10, leaving `$sum` with a branch to the function label
+/// and `$square` with a `return` when called indirectly with 0.
+const CONTRACT: &str = r#"
+(module
+  (type $unary (func (param i32) (result i32)))
+  (import "env" "value_return" (func $value_return (param i64 i64)))
+  (memory 1)
+  (table 1 funcref)
+  (elem (i32.const 0) $square)
+  (func $square (param i32) (result i32)
+    (if (i32.eqz (local.get 0)) (then (return (i32.const 0))))
+    (i32.mul (local.get 0) (local.get 0)))
+  (func $sum (param $n i32) (result i32)
+    (local $acc i32)
+    (block $done
+      (loop $l
+        (br_if $done (i32.eqz (local.get $n)))
+        (local.set $acc (i32.add (local.get $acc) (call $square (local.get $n))))
+        (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+        (br $l)))
+    (br 0 (local.get $acc)))
+  (func (export "main")
+    (drop (call_indirect (type $unary) (i32.const 0) (i32.const 0)))
+    (i32.store (i32.const 0) (call $sum (i32.const 10)))
+    (call $value_return (i64.const 4) (i64.const 0))))
+"#;
+
+fn run(code: &[u8], config: Config, vm_kind: VMKind) -> VMOutcome {
+    let code = ContractCode::new(code.to_vec(), None);
+    let mut fake_external = MockedExternal::with_code(code);
+    let context = create_context(vec![]);
+    let fees = Arc::new(RuntimeFeesConfig::test());
+    let gas_counter = context.make_gas_counter(&config);
+    let runtime = vm_kind.runtime(Arc::new(config)).expect("runtime has not been compiled");
+    runtime
+        .prepare(&fake_external, None, gas_counter, "main")
+        .run(&mut fake_external, &context, fees)
+        .expect("execution failed")
+}
+
+#[test]
+fn test_function_gas_profile() {
+    let code = wat::parse_str(CONTRACT).unwrap();
+    let config = test_vm_config();
+    with_vm_variants(&config, |vm_kind: VMKind| {
+        let plain = run(&code, config.clone(), vm_kind);
+        assert_eq!(plain.function_gas_profile, None);
+
+        let profiled =
+            run(&code, Config { function_gas_profiling: true, ..config.clone() }, vm_kind);
+        assert_eq!(profiled.aborted, None);
+        assert_eq!(profiled.return_data, ReturnData::Value(385i32.to_le_bytes().to_vec()));
+
+        let profile = profiled.function_gas_profile.expect("profile must be collected");
+        let attributed = profile.stacks.values().sum::<u64>();
+        assert!(0 < attributed && attributed <= profiled.burnt_gas);
+
+        let folded = profile.to_folded(&function_names(&code));
+        let stacks =
+            folded.lines().map(|line| line.rsplit_once(' ').unwrap().0).collect::<BTreeSet<_>>();
+        assert_eq!(
+            stacks,
+            BTreeSet::from(["main", "main;square", "main;sum", "main;sum;square"]),
+            "{folded}"
+        );
+    });
+}
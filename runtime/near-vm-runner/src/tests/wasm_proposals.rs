@@ -0,0 +1,137 @@
+use crate::logic::mocks::mock_external::MockedExternal;
+use crate::logic::types::ReturnData;
+use crate::logic::{Config, ContractPrepareVersion, VMOutcome};
+use crate::runner::VMKindExt;
+use crate::tests::{create_context, test_vm_config, with_vm_variants};
+use crate::ContractCode;
+use near_parameters::vm::VMKind;
+use near_parameters::RuntimeFeesConfig;
+use std::sync::Arc;
+
+/// Exercises the bulk memory operations and the multi-value blocks, loops and ifs.
+/// Returns 8 bytes filled by `memory.fill` and copied with `memory.copy`, followed by the
+/// little-endian `i32` computed with the multi-value blocks.
+const CONTRACT: &str = r#"
+(module
+  (import "env" "value_return" (func $value_return (param i64 i64)))
+  (memory 1)
+  (func (export "main")
+    (local $i i32) (local $acc i32)
+    (memory.fill (i32.const 0) (i32.const 42) (i32.const 16))
+    (memory.copy (i32.const 16) (i32.const 0) (i32.const 8))
+    ;; Sum 1..=10 with the loop parameters being the counter and the accumulator.
+    i32.const 1
+    i32.const 0
+    (loop $l (param i32 i32) (result i32 i32)
+      local.set $acc
+      local.set $i
+      (i32.add (local.get $i) (i32.const 1))
+      (i32.add (local.get $acc) (local.get $i))
+      (br_if $l (i32.lt_u (local.get $i) (i32.const 10))))
+    ;; [11, 55] -> [11, 110]
+    i32.const 1
+    (if (param i32 i32) (result i32 i32)
+      (then i32.const 2 i32.mul)
+      (else drop i32.const 0))
+    ;; [11, 110] -> [121]
+    (block (param i32 i32) (result i32) i32.add)
+    ;; [121] -> [121, 3, 2.5] -> [126]
+    (block (result i32 f64) i32.const 3 f64.const 2.5 br 0)
+    i32.trunc_f64_s
+    i32.add
+    i32.add
+    local.set $acc
+    (i32.store (i32.const 24) (local.get $acc))
+    (call $value_return (i64.const 12) (i64.const 16))))
+"#;
+
+fn v3_config() -> Config {
+    let mut config = test_vm_config();
+    config.limit_config.contract_prepare_version = ContractPrepareVersion::V3;
+    config
+}
+
+/// Uses the bulk memory operations in functions called directly and through a table, to cover
+/// the function index rewriting done along with the per-byte gas instrumentation.
+/// Returns the 4 bytes filled by `memory.fill` and copied with `memory.copy`.
+const CALLEES_CONTRACT: &str = r#"
+(module
+  (import "env" "value_return" (func $value_return (param i64 i64)))
+  (type $len (func (param i32)))
+  (memory 1)
+  (table 1 funcref)
+  (elem (i32.const 0) $fill)
+  (func $fill (param $len i32)
+    (memory.fill (i32.const 0) (i32.const 7) (local.get $len)))
+  (func $copy (param $len i32) (local $unused i64)
+    (memory.copy (i32.const 8) (i32.const 0) (local.get $len)))
+  (func (export "main")
+    (call_indirect (type $len) (i32.const 4) (i32.const 0))
+    (call $copy (i32.const 4))
+    (call $value_return (i64.const 4) (i64.const 8))))
+"#;
+
+fn run(config: Config, vm_kind: VMKind) -> VMOutcome {
+    run_contract(CONTRACT, config, vm_kind)
+}
+
+fn run_contract(wat: &str, config: Config, vm_kind: VMKind) -> VMOutcome {
+    let code = ContractCode::new(wat::parse_str(wat).unwrap(), None);
+    let mut fake_external = MockedExternal::with_code(code);
+    let context = create_context(vec![]);
+    let fees = Arc::new(RuntimeFeesConfig::test());
+    let gas_counter = context.make_gas_counter(&config);
+    let runtime = vm_kind.runtime(Arc::new(config)).expect("runtime has not been compiled");
+    runtime
+        .prepare(&fake_external, None, gas_counter, "main")
+        .run(&mut fake_external, &context, fees)
+        .expect("execution failed")
+}
+
+#[test]
+fn test_bulk_memory_and_multi_value() {
+    let config = v3_config();
+    with_vm_variants(&config, |vm_kind: VMKind| {
+        // Only these runtimes implement the proposals.
+        if !matches!(vm_kind, VMKind::NearVm | VMKind::Wasmtime) {
+            return;
+        }
+        let outcome = run(config.clone(), vm_kind);
+        assert_eq!(outcome.aborted, None);
+        let mut expected = vec![42; 8];
+        expected.extend_from_slice(&126i32.to_le_bytes());
+        assert_eq!(outcome.return_data, ReturnData::Value(expected));
+    });
+}
+
+#[test]
+fn test_bulk_memory_byte_cost() {
+    let config = v3_config();
+    with_vm_variants(&config, |vm_kind: VMKind| {
+        if !matches!(vm_kind, VMKind::NearVm | VMKind::Wasmtime) {
+            return;
+        }
+        let free = run(Config { bulk_memory_byte_cost: 0, ..config.clone() }, vm_kind);
+        let paid = run(Config { bulk_memory_byte_cost: 1000, ..config.clone() }, vm_kind);
+        // 16 bytes filled and 8 bytes copied.
+        assert_eq!(paid.burnt_gas - free.burnt_gas, 24 * 1000);
+    });
+}
+
+#[test]
+fn test_bulk_memory_byte_cost_in_callees() {
+    let config = v3_config();
+    with_vm_variants(&config, |vm_kind: VMKind| {
+        if !matches!(vm_kind, VMKind::NearVm | VMKind::Wasmtime) {
+            return;
+        }
+        let free_config = Config { bulk_memory_byte_cost: 0, ..config.clone() };
+        let free = run_contract(CALLEES_CONTRACT, free_config, vm_kind);
+        let paid_config = Config { bulk_memory_byte_cost: 1000, ..config.clone() };
+        let paid = run_contract(CALLEES_CONTRACT, paid_config, vm_kind);
+        assert_eq!(paid.aborted, None);
+        assert_eq!(paid.return_data, ReturnData::Value(vec![7; 4]));
+        // 4 bytes filled and 4 bytes copied.
+        assert_eq!(paid.burnt_gas - free.burnt_gas, 8 * 1000);
+    });
+}
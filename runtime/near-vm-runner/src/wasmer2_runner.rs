@@ -754,7 +754,8 @@ impl<'e, 'l, 'lr> Resolver for Wasmer2Imports<'e, 'l, 'lr> {
                         // lifetime and so it is safe to dereference the `env` pointer which is
                         // known to be derived from a valid `&'vmlogic mut VMLogic<'_>` in the
                         // first place.
-                        unsafe { (*env).$func( $( $arg_name, )* ) }
+                        let logic = unsafe { &mut *env };
+                        $crate::imports::call_host_function!(logic.$func( $( $arg_name ),* ) as $name)
                     }));
                     // We want to ensure that the only kind of error that host function calls
                     // return are VMLogicError. This is important because we later attempt to
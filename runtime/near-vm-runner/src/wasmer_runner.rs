@@ -586,7 +586,7 @@ pub(crate) fn build_imports(
                         tracing::trace_span!(target: "vm::host_function", stringify!($name)).entered()
                     });
                     let logic: &mut VMLogic<'_> = unsafe { &mut *(ctx.data as *mut VMLogic<'_>) };
-                    logic.$func( $( $arg_name, )* )
+                    $crate::imports::call_host_function!(logic.$func( $( $arg_name ),* ) as $name)
                 }
 
                 match stringify!($mod) {
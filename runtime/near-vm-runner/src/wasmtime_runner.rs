@@ -476,7 +476,7 @@ fn link<'a, 'b>(
                     crate::wasmtime_runner::CALLER.with(|runner_caller| *runner_caller.borrow_mut() = std::mem::transmute(caller));
                 }
                 let logic: &mut VMLogic<'_> = unsafe { &mut *(data as *mut VMLogic<'_>) };
-                match logic.$func( $( $arg_name as $arg_type, )* ) {
+                match $crate::imports::call_host_function!(logic.$func( $( $arg_name as $arg_type ),* ) as $name) {
                     Ok(result) => Ok(result as ($( $returns ),* ) ),
                     Err(err) => {
                         Err(ErrorContainer(std::sync::Mutex::new(Some(err))).into())
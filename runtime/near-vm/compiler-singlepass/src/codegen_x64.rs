@@ -98,6 +98,20 @@ pub(crate) struct FuncGen<'a> {
 
     /// Maximum size of the stack for this function
     stack_size: u32,
+
+    /// Number of the stack slots to reserve for passing the values of multi-value blocks.
+    multi_value_slot_count: usize,
+
+    /// Stack slots through which the parameters and results of the multi-value blocks are
+    /// passed, allocated by `emit_head`.
+    ///
+    /// Blocks taking parameters or returning more than one value cannot use RAX like the other
+    /// blocks do. Instead, the values are stored to these slots before jumping to the block
+    /// label, and loaded back after it.
+    multi_value_slots: Vec<Location>,
+
+    /// Gas cost per byte of the bulk memory operations.
+    bulk_memory_byte_gas_cost: u32,
 }
 
 struct SpecialLabelSet {
@@ -226,11 +240,26 @@ pub(crate) struct ControlFrame {
 
     pub(crate) loop_like: bool,
     pub(crate) if_else: IfElseState,
+    pub(crate) params: SmallVec<[WpType; 1]>,
     pub(crate) returns: SmallVec<[WpType; 1]>,
     pub(crate) value_stack_depth: usize,
     pub(crate) fp_stack_depth: usize,
 }
 
+impl ControlFrame {
+    /// Number of values passed through the multi-value slots when branching to this frame,
+    /// or `None` if the values are passed through RAX.
+    fn multi_value_arity(&self) -> Option<usize> {
+        if self.params.is_empty() && self.returns.len() <= 1 {
+            None
+        } else if self.loop_like {
+            Some(self.params.len())
+        } else {
+            Some(self.returns.len())
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub(crate) enum IfElseState {
     None,
@@ -371,6 +400,28 @@ impl<'a> FuncGen<'a> {
         self.machine.release_temp_gpr(cost_reg);
     }
 
+    /// Charges gas for a bulk memory operation processing `len` bytes, where `len` is the
+    /// location of the i32 length operand.
+    fn emit_bulk_memory_gas(&mut self, len: Location) {
+        if self.bulk_memory_byte_gas_cost == 0 {
+            return;
+        }
+        let cost_reg = self.machine.acquire_temp_gpr().unwrap();
+        let byte_cost_reg = self.machine.acquire_temp_gpr().unwrap();
+        // 32-bit moves zero-extend the length. The product of two 32-bit numbers cannot
+        // overflow 64 bits.
+        self.assembler.emit_mov(Size::S32, len, Location::GPR(cost_reg));
+        self.assembler.emit_mov(
+            Size::S64,
+            Location::Imm64(u64::from(self.bulk_memory_byte_gas_cost)),
+            Location::GPR(byte_cost_reg),
+        );
+        self.assembler.emit_imul(Size::S64, Location::GPR(byte_cost_reg), Location::GPR(cost_reg));
+        self.machine.release_temp_gpr(byte_cost_reg);
+        self.emit_gas(Location::GPR(cost_reg));
+        self.machine.release_temp_gpr(cost_reg);
+    }
+
     /// Emit a gas charge operation. The gas amount is stored in `cost_location`, which must be either an imm32 or a GPR
     // (this is because emit_add can only take up to an imm32)
     fn emit_gas(&mut self, cost_location: Location) {
@@ -1619,6 +1670,12 @@ impl<'a> FuncGen<'a> {
     pub(crate) fn emit_head(&mut self) -> Result<(), CodegenError> {
         // TODO: Patchpoint is not emitted for now, and ARM trampoline is not prepended.
 
+        if self.signature.results().len() > 1 {
+            return Err(CodegenError {
+                message: "multi-value function returns not yet implemented".to_string(),
+            });
+        }
+
         // Normal x86 entry prologue.
         self.assembler.emit_push(Size::S64, Location::GPR(GPR::RBP));
         self.assembler.emit_mov(Size::S64, Location::GPR(GPR::RSP), Location::GPR(GPR::RBP));
@@ -1654,6 +1711,8 @@ impl<'a> FuncGen<'a> {
             self.signature.params().len() as u32,
             self.calling_convention,
         );
+        self.multi_value_slots =
+            self.machine.acquire_fixed_stack_slots(self.assembler, self.multi_value_slot_count);
 
         self.assembler.emit_sub(Size::S64, Location::Imm32(32), Location::GPR(GPR::RSP)); // simulate "red zone" if not supported by the platform
 
@@ -1661,6 +1720,7 @@ impl<'a> FuncGen<'a> {
             br_label: self.assembler.get_label(),
             loop_like: false,
             if_else: IfElseState::None,
+            params: smallvec![],
             returns: self.signature.results().iter().map(|&x| type_to_wp_type(x)).collect(),
             value_stack_depth: 0,
             fp_stack_depth: 0,
@@ -1669,6 +1729,219 @@ impl<'a> FuncGen<'a> {
         Ok(())
     }
 
+    /// Pads with NOPs to the next 16-byte boundary, so that the loop header is aligned.
+    fn emit_loop_alignment(&mut self) {
+        // Here we don't use the dynasm `.align 16` attribute because it pads the alignment with single-byte nops
+        // which may lead to efficiency problems.
+        match self.assembler.get_offset().0 % 16 {
+            0 => {}
+            x => {
+                self.assembler.emit_nop_n(16 - x);
+            }
+        }
+        assert_eq!(self.assembler.get_offset().0 % 16, 0);
+    }
+
+    /// Parameter and result types of a block with the type `type_index`.
+    fn block_signature(&self, type_index: u32) -> (SmallVec<[WpType; 1]>, SmallVec<[WpType; 1]>) {
+        let signature = &self.module.signatures[SignatureIndex::new(type_index as usize)];
+        (
+            signature.params().iter().cloned().map(type_to_wp_type).collect(),
+            signature.results().iter().cloned().map(type_to_wp_type).collect(),
+        )
+    }
+
+    /// Depth of the value stack below the `count` topmost values.
+    fn value_stack_depth_below(&self, count: usize) -> Result<usize, CodegenError> {
+        self.value_stack.len().checked_sub(count).ok_or_else(|| CodegenError {
+            message: "multi-value: not enough values on the stack".to_string(),
+        })
+    }
+
+    /// Number of the `fp_stack` entries describing the values below `value_stack_depth`.
+    fn fp_stack_depth_at(&self, value_stack_depth: usize) -> usize {
+        self.fp_stack.iter().take_while(|fp| fp.depth < value_stack_depth).count()
+    }
+
+    /// Releases the values above `value_stack_depth`.
+    fn release_values_above(&mut self, value_stack_depth: usize) {
+        let released: &[Location] = &self.value_stack[value_stack_depth..];
+        self.machine.release_locations(self.assembler, released);
+        self.value_stack.truncate(value_stack_depth);
+        let fp_stack_depth = self.fp_stack_depth_at(value_stack_depth);
+        self.fp_stack.truncate(fp_stack_depth);
+    }
+
+    /// Stores the `count` topmost values to the multi-value slots, canonicalizing the floats
+    /// if needed. The value stack is left unchanged.
+    fn emit_multi_value_store(&mut self, count: usize) -> Result<(), CodegenError> {
+        let base = self.value_stack_depth_below(count)?;
+        for (i, depth) in (base..self.value_stack.len()).enumerate() {
+            let loc = self.value_stack[depth];
+            let slot = self.multi_value_slots[i];
+            let canonicalization = self
+                .fp_stack
+                .iter()
+                .rev()
+                .find(|fp| fp.depth == depth)
+                .and_then(|fp| fp.canonicalization);
+            match canonicalization {
+                Some(ty)
+                    if self.assembler.arch_supports_canonicalize_nan()
+                        && self.config.enable_nan_canonicalization =>
+                {
+                    self.canonicalize_nan(ty.to_size(), loc, slot);
+                }
+                _ => self.emit_relaxed_binop(Assembler::emit_mov, Size::S64, loc, slot),
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads the values of `types` from the multi-value slots and pushes them to the value
+    /// stack.
+    fn emit_multi_value_load(&mut self, types: &[WpType]) {
+        let locs = self.machine.acquire_locations(self.assembler, types, false);
+        for (i, (loc, ty)) in locs.into_iter().zip(types).enumerate() {
+            let slot = self.multi_value_slots[i];
+            self.emit_relaxed_binop(Assembler::emit_mov, Size::S64, slot, loc);
+            self.value_stack.push(loc);
+            if ty.is_float() {
+                // we already canonicalized when storing to the slot.
+                self.fp_stack.push(FloatValue::new(self.value_stack.len() - 1));
+            }
+        }
+    }
+
+    /// `block` with a function type. The parameters stay where they are on the value stack.
+    fn emit_multi_value_block(&mut self, type_index: u32) -> Result<(), CodegenError> {
+        let (params, returns) = self.block_signature(type_index);
+        let value_stack_depth = self.value_stack_depth_below(params.len())?;
+        let frame = ControlFrame {
+            br_label: self.assembler.get_label(),
+            loop_like: false,
+            if_else: IfElseState::None,
+            params,
+            returns,
+            value_stack_depth,
+            fp_stack_depth: self.fp_stack_depth_at(value_stack_depth),
+        };
+        self.control_stack.push(frame);
+        Ok(())
+    }
+
+    /// `loop` with a function type. The loop header is a branch target, so the parameters are
+    /// passed through the multi-value slots also when entering the loop.
+    fn emit_multi_value_loop(&mut self, type_index: u32) -> Result<(), CodegenError> {
+        let (params, returns) = self.block_signature(type_index);
+        let value_stack_depth = self.value_stack_depth_below(params.len())?;
+        self.emit_multi_value_store(params.len())?;
+        self.release_values_above(value_stack_depth);
+
+        self.emit_loop_alignment();
+        let br_label = self.assembler.get_label();
+        self.control_stack.push(ControlFrame {
+            br_label,
+            loop_like: true,
+            if_else: IfElseState::None,
+            params: params.clone(),
+            returns,
+            value_stack_depth,
+            fp_stack_depth: self.fp_stack.len(),
+        });
+        self.assembler.emit_label(br_label);
+        self.emit_multi_value_load(&params);
+        Ok(())
+    }
+
+    /// `if` with a function type. The parameters are stored to the multi-value slots, so that
+    /// both the `then` and the `else` arm can load them.
+    fn emit_multi_value_if(&mut self, type_index: u32) -> Result<(), CodegenError> {
+        let (params, returns) = self.block_signature(type_index);
+        let label_end = self.assembler.get_label();
+        let label_else = self.assembler.get_label();
+
+        let cond = self
+            .value_stack
+            .pop()
+            .ok_or_else(|| CodegenError { message: "If: value stack is empty".to_string() })?;
+        let value_stack_depth = self.value_stack_depth_below(params.len())?;
+        self.emit_multi_value_store(params.len())?;
+
+        // Releasing the stack slots may emit an `add rsp` clobbering the flags, so the condition
+        // is copied to a register and compared only after the release.
+        let tmp = self.machine.acquire_temp_gpr().unwrap();
+        self.assembler.emit_mov(Size::S32, cond, Location::GPR(tmp));
+        self.machine.release_locations(self.assembler, &[cond]);
+        self.release_values_above(value_stack_depth);
+        self.assembler.emit_cmp(Size::S32, Location::Imm32(0), Location::GPR(tmp));
+        self.machine.release_temp_gpr(tmp);
+
+        self.control_stack.push(ControlFrame {
+            br_label: label_end,
+            loop_like: false,
+            if_else: IfElseState::If(label_else),
+            params: params.clone(),
+            returns,
+            value_stack_depth,
+            fp_stack_depth: self.fp_stack.len(),
+        });
+        self.assembler.emit_jmp(Condition::Equal, label_else);
+        self.emit_multi_value_load(&params);
+        Ok(())
+    }
+
+    /// `else` of an `if` with the multi-value frame.
+    fn emit_multi_value_else(&mut self, was_unreachable: bool) -> Result<(), CodegenError> {
+        let frame = self.control_stack.last().unwrap();
+        let (returns, value_stack_depth) = (frame.returns.len(), frame.value_stack_depth);
+        if !was_unreachable {
+            self.emit_multi_value_store(returns)?;
+        }
+        self.release_values_above(value_stack_depth);
+
+        let frame = self.control_stack.last_mut().unwrap();
+        match frame.if_else {
+            IfElseState::If(label) => {
+                self.assembler.emit_jmp(Condition::None, frame.br_label);
+                self.assembler.emit_label(label);
+                frame.if_else = IfElseState::Else;
+            }
+            _ => {
+                return Err(CodegenError {
+                    message: "Else: frame.if_else unreachable code".to_string(),
+                })
+            }
+        }
+        // The `else` arm is entered straight from the `if`, with the parameters still in the
+        // multi-value slots.
+        let params = frame.params.clone();
+        self.emit_multi_value_load(&params);
+        Ok(())
+    }
+
+    /// `end` of a multi-value frame. All the paths reaching the end of the block leave the
+    /// results in the multi-value slots.
+    fn emit_multi_value_end(&mut self, was_unreachable: bool) -> Result<(), CodegenError> {
+        let returns = self.control_stack.last().unwrap().returns.len();
+        if !was_unreachable {
+            self.emit_multi_value_store(returns)?;
+        }
+        let frame = self.control_stack.pop().unwrap();
+        self.release_values_above(frame.value_stack_depth);
+
+        if !frame.loop_like {
+            self.assembler.emit_label(frame.br_label);
+        }
+        // For an `if` without `else`, the results are the same as the parameters, which are
+        // still in the multi-value slots.
+        if let IfElseState::If(label) = frame.if_else {
+            self.assembler.emit_label(label);
+        }
+        self.emit_multi_value_load(&frame.returns);
+        Ok(())
+    }
+
     /// Pushes the instruction to the address map, calculating the offset from a
     /// provided beginning address.
     fn mark_instruction_address_end(&mut self, begin: usize) {
@@ -1694,6 +1967,8 @@ impl<'a> FuncGen<'a> {
         gas_costs: &'a [u64],
         _gas_kinds: &'a [InstrumentationKind],
         stack_size: u64,
+        multi_value_slot_count: usize,
+        bulk_memory_byte_gas_cost: u32,
     ) -> Result<FuncGen<'a>, CodegenError> {
         let func_index = module.func_index(local_func_index);
         let sig_index = module.functions[func_index];
@@ -1734,6 +2009,9 @@ impl<'a> FuncGen<'a> {
             stack_size: u32::try_from(stack_size).map_err(|_| CodegenError {
                 message: "one function has a stack more than u32::MAX deep".to_string(),
             })?,
+            multi_value_slot_count,
+            multi_value_slots: vec![],
+            bulk_memory_byte_gas_cost,
         };
         for param in module.signatures[sig_index].params() {
             fg.feed_local(1, type_to_wp_type(*param));
@@ -4684,6 +4962,12 @@ impl<'a> FuncGen<'a> {
                     sig.params().iter().cloned().map(type_to_wp_type).collect();
                 let return_types: SmallVec<[WpType; 1]> =
                     sig.results().iter().cloned().map(type_to_wp_type).collect();
+                if return_types.len() > 1 {
+                    return Err(CodegenError {
+                        message: "CallIndirect: multi-value returns not yet implemented"
+                            .to_string(),
+                    });
+                }
 
                 let func_index = self.pop_value_released();
 
@@ -4857,6 +5141,9 @@ impl<'a> FuncGen<'a> {
                     }
                 }
             }
+            Operator::If { blockty: WpBlockType::FuncType(type_index) } => {
+                self.emit_multi_value_if(type_index)?;
+            }
             Operator::If { blockty } => {
                 let label_end = self.assembler.get_label();
                 let label_else = self.assembler.get_label();
@@ -4867,14 +5154,11 @@ impl<'a> FuncGen<'a> {
                     br_label: label_end,
                     loop_like: false,
                     if_else: IfElseState::If(label_else),
+                    params: smallvec![],
                     returns: match blockty {
                         WpBlockType::Empty => smallvec![],
                         WpBlockType::Type(inner_ty) => smallvec![inner_ty],
-                        WpBlockType::FuncType(_) => {
-                            return Err(CodegenError {
-                                message: "If: multi-value returns not yet implemented".to_string(),
-                            })
-                        }
+                        WpBlockType::FuncType(_) => unreachable!(),
                     },
                     value_stack_depth: self.value_stack.len(),
                     fp_stack_depth: self.fp_stack.len(),
@@ -4883,6 +5167,11 @@ impl<'a> FuncGen<'a> {
                 self.emit_relaxed_binop(Assembler::emit_cmp, Size::S32, Location::Imm32(0), cond);
                 self.assembler.emit_jmp(Condition::Equal, label_else);
             }
+            Operator::Else
+                if self.control_stack.last().and_then(|f| f.multi_value_arity()).is_some() =>
+            {
+                self.emit_multi_value_else(was_unreachable)?;
+            }
             Operator::Else => {
                 let frame = self.control_stack.last_mut().unwrap();
 
@@ -4999,37 +5288,30 @@ impl<'a> FuncGen<'a> {
                 }
                 self.assembler.emit_label(end_label);
             }
+            Operator::Block { blockty: WpBlockType::FuncType(type_index) } => {
+                self.emit_multi_value_block(type_index)?;
+            }
             Operator::Block { blockty } => {
                 let frame = ControlFrame {
                     br_label: self.assembler.get_label(),
                     loop_like: false,
                     if_else: IfElseState::None,
+                    params: smallvec![],
                     returns: match blockty {
                         WpBlockType::Empty => smallvec![],
                         WpBlockType::Type(inner_ty) => smallvec![inner_ty],
-                        WpBlockType::FuncType(_) => {
-                            return Err(CodegenError {
-                                message: "Block: multi-value returns not yet implemented"
-                                    .to_string(),
-                            })
-                        }
+                        WpBlockType::FuncType(_) => unreachable!(),
                     },
                     value_stack_depth: self.value_stack.len(),
                     fp_stack_depth: self.fp_stack.len(),
                 };
                 self.control_stack.push(frame);
             }
+            Operator::Loop { blockty: WpBlockType::FuncType(type_index) } => {
+                self.emit_multi_value_loop(type_index)?;
+            }
             Operator::Loop { blockty } => {
-                // Pad with NOPs to the next 16-byte boundary.
-                // Here we don't use the dynasm `.align 16` attribute because it pads the alignment with single-byte nops
-                // which may lead to efficiency problems.
-                match self.assembler.get_offset().0 % 16 {
-                    0 => {}
-                    x => {
-                        self.assembler.emit_nop_n(16 - x);
-                    }
-                }
-                assert_eq!(self.assembler.get_offset().0 % 16, 0);
+                self.emit_loop_alignment();
 
                 let br_label = self.assembler.get_label();
                 let _activate_offset = self.assembler.get_offset().0;
@@ -5038,15 +5320,11 @@ impl<'a> FuncGen<'a> {
                     br_label,
                     loop_like: true,
                     if_else: IfElseState::None,
+                    params: smallvec![],
                     returns: match blockty {
                         WpBlockType::Empty => smallvec![],
                         WpBlockType::Type(inner_ty) => smallvec![inner_ty],
-                        WpBlockType::FuncType(_) => {
-                            return Err(CodegenError {
-                                message: "Loop: multi-value returns not yet implemented"
-                                    .to_string(),
-                            })
-                        }
+                        WpBlockType::FuncType(_) => unreachable!(),
                     },
                     value_stack_depth: self.value_stack.len(),
                     fp_stack_depth: self.fp_stack.len(),
@@ -5085,6 +5363,7 @@ impl<'a> FuncGen<'a> {
                 self.assembler.emit_mov(Size::S64, Location::GPR(GPR::RAX), ret);
             }
             Operator::MemoryInit { data_index, mem } => {
+                self.emit_bulk_memory_gas(*self.value_stack.last().unwrap());
                 let len = self.value_stack.pop().unwrap();
                 let src = self.value_stack.pop().unwrap();
                 let dst = self.value_stack.pop().unwrap();
@@ -5135,6 +5414,7 @@ impl<'a> FuncGen<'a> {
             Operator::MemoryCopy { src_mem, dst_mem } => {
                 // ignore until we support multiple memories
                 let _dst = dst_mem;
+                self.emit_bulk_memory_gas(*self.value_stack.last().unwrap());
                 let len = self.value_stack.pop().unwrap();
                 let src_pos = self.value_stack.pop().unwrap();
                 let dst_pos = self.value_stack.pop().unwrap();
@@ -5169,6 +5449,7 @@ impl<'a> FuncGen<'a> {
                 self.machine.release_locations_only_stack(self.assembler, &[dst_pos, src_pos, len]);
             }
             Operator::MemoryFill { mem } => {
+                self.emit_bulk_memory_gas(*self.value_stack.last().unwrap());
                 let len = self.value_stack.pop().unwrap();
                 let val = self.value_stack.pop().unwrap();
                 let dst = self.value_stack.pop().unwrap();
@@ -5689,7 +5970,9 @@ impl<'a> FuncGen<'a> {
             Operator::Br { relative_depth } => {
                 let frame =
                     &self.control_stack[self.control_stack.len() - 1 - (relative_depth as usize)];
-                if !frame.loop_like && !frame.returns.is_empty() {
+                if let Some(arity) = frame.multi_value_arity() {
+                    self.emit_multi_value_store(arity)?;
+                } else if !frame.loop_like && !frame.returns.is_empty() {
                     if frame.returns.len() != 1 {
                         return Err(CodegenError {
                             message: "Br: incorrect frame.returns".to_string(),
@@ -5741,7 +6024,9 @@ impl<'a> FuncGen<'a> {
 
                 let frame =
                     &self.control_stack[self.control_stack.len() - 1 - (relative_depth as usize)];
-                if !frame.loop_like && !frame.returns.is_empty() {
+                if let Some(arity) = frame.multi_value_arity() {
+                    self.emit_multi_value_store(arity)?;
+                } else if !frame.loop_like && !frame.returns.is_empty() {
                     if frame.returns.len() != 1 {
                         return Err(CodegenError {
                             message: "BrIf: incorrect frame.returns".to_string(),
@@ -5820,7 +6105,9 @@ impl<'a> FuncGen<'a> {
                     table.push(label);
                     let frame =
                         &self.control_stack[self.control_stack.len() - 1 - (*target as usize)];
-                    if !frame.loop_like && !frame.returns.is_empty() {
+                    if let Some(arity) = frame.multi_value_arity() {
+                        self.emit_multi_value_store(arity)?;
+                    } else if !frame.loop_like && !frame.returns.is_empty() {
                         if frame.returns.len() != 1 {
                             return Err(CodegenError {
                                 message: format!(
@@ -5870,7 +6157,9 @@ impl<'a> FuncGen<'a> {
                 {
                     let frame = &self.control_stack
                         [self.control_stack.len() - 1 - (default_target as usize)];
-                    if !frame.loop_like && !frame.returns.is_empty() {
+                    if let Some(arity) = frame.multi_value_arity() {
+                        self.emit_multi_value_store(arity)?;
+                    } else if !frame.loop_like && !frame.returns.is_empty() {
                         if frame.returns.len() != 1 {
                             return Err(CodegenError {
                                 message: "BrTable: incorrect frame.returns".to_string(),
@@ -5927,6 +6216,11 @@ impl<'a> FuncGen<'a> {
                     }
                 }
             }
+            Operator::End
+                if self.control_stack.last().and_then(|f| f.multi_value_arity()).is_some() =>
+            {
+                self.emit_multi_value_end(was_unreachable)?;
+            }
             Operator::End => {
                 let frame = self.control_stack.pop().unwrap();
 
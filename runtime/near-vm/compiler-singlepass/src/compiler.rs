@@ -7,6 +7,7 @@ use crate::codegen_x64::{
     CodegenError, FuncGen,
 };
 use crate::config::Singlepass;
+use near_vm_compiler::wasmparser::{BlockType, Operator};
 use near_vm_compiler::{
     Architecture, CallingConvention, Compilation, CompileError, CompileModuleInfo,
     CompiledFunction, Compiler, CompilerConfig, CpuFeature, FunctionBody, FunctionBodyData,
@@ -14,7 +15,8 @@ use near_vm_compiler::{
 };
 use near_vm_types::entity::{EntityRef, PrimaryMap};
 use near_vm_types::{
-    FunctionIndex, FunctionType, LocalFunctionIndex, MemoryIndex, ModuleInfo, TableIndex,
+    FunctionIndex, FunctionType, LocalFunctionIndex, MemoryIndex, ModuleInfo, SignatureIndex,
+    TableIndex,
 };
 use near_vm_vm::{TrapCode, VMOffsets};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
@@ -61,9 +63,6 @@ impl Compiler for SinglepassCompiler {
         if !target.cpu_features().contains(CpuFeature::AVX) {
             return Err(CompileError::UnsupportedTarget("x86_64 without AVX".to_string()));
         }
-        if compile_info.features.multi_value {
-            return Err(CompileError::UnsupportedFeature("multivalue".to_string()));
-        }
         let calling_convention = match target.triple().default_calling_convention() {
             Ok(CallingConvention::WindowsFastcall) => CallingConvention::WindowsFastcall,
             Ok(CallingConvention::SystemV) => CallingConvention::SystemV,
@@ -114,9 +113,22 @@ impl Compiler for SinglepassCompiler {
                 tracing::trace_span!(target: "near_vm", "function", i = i.index()).in_scope(|| {
                     let reader =
                         near_vm_compiler::FunctionReader::new(input.module_offset, input.data);
-                    let stack_init_gas_cost = tunables
-                        .stack_init_gas_cost(instrumentation.function_frame_sizes[i.index()]);
-                    let stack_size = instrumentation.function_frame_sizes[i.index()]
+                    let multi_value_slot_count = if compile_info.features.multi_value {
+                        multi_value_slot_count(module, &reader)?
+                    } else {
+                        0
+                    };
+                    // The multi-value slots are a part of the function frame, allocated and
+                    // charged for together with the locals.
+                    let frame_size = instrumentation.function_frame_sizes[i.index()]
+                        .checked_add(8 * multi_value_slot_count as u64)
+                        .ok_or_else(|| {
+                            CompileError::Codegen(String::from(
+                                "got function with frame size going beyond u64::MAX",
+                            ))
+                        })?;
+                    let stack_init_gas_cost = tunables.stack_init_gas_cost(frame_size);
+                    let stack_size = frame_size
                         .checked_add(instrumentation.function_operand_stack_sizes[i.index()])
                         .ok_or_else(|| {
                             CompileError::Codegen(String::from(
@@ -137,6 +149,8 @@ impl Compiler for SinglepassCompiler {
                         &instrumentation.gas_costs[i.index()],
                         &instrumentation.gas_kinds[i.index()],
                         stack_size,
+                        multi_value_slot_count,
+                        tunables.bulk_memory_byte_gas_cost(),
                     )
                     .map_err(to_compile_error)?;
 
@@ -229,6 +243,30 @@ fn to_compile_error<T: ToCompileError>(x: T) -> CompileError {
     x.to_compile_error()
 }
 
+/// Number of the stack slots needed to pass the values of the multi-value blocks of a
+/// function: the largest number of parameters or results of its blocks with a function type.
+fn multi_value_slot_count(
+    module: &ModuleInfo,
+    reader: &near_vm_compiler::FunctionReader<'_>,
+) -> Result<usize, CompileError> {
+    let mut count = 0;
+    for op in reader.get_operators_reader()? {
+        match op? {
+            Operator::Block { blockty: BlockType::FuncType(type_index) }
+            | Operator::Loop { blockty: BlockType::FuncType(type_index) }
+            | Operator::If { blockty: BlockType::FuncType(type_index) } => {
+                let signature = module
+                    .signatures
+                    .get(SignatureIndex::new(type_index as usize))
+                    .ok_or_else(|| CompileError::Codegen(format!("unknown type {type_index}")))?;
+                count = count.max(signature.params().len()).max(signature.results().len());
+            }
+            _ => {}
+        }
+    }
+    Ok(count)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -517,6 +517,29 @@ impl Machine {
         }
     }
 
+    /// Allocates `n` stack slots that stay valid for the whole function body.
+    ///
+    /// Must be called right after `init_locals`, before any value is pushed on the stack. The
+    /// returned locations are never released: they are freed together with the locals by
+    /// `finalize_locals`.
+    pub(crate) fn acquire_fixed_stack_slots<E: Emitter>(
+        &mut self,
+        a: &mut E,
+        n: usize,
+    ) -> Vec<Location> {
+        let locs: Vec<Location> = (1..=n)
+            .map(|i| Location::Memory(GPR::RBP, -((self.stack_offset.0 + 8 * i) as i32)))
+            .collect();
+        if n != 0 {
+            self.decrease_rsp(a, 8 * n);
+        }
+        // Stack probe, see `init_locals`.
+        for loc in locs.iter().step_by(NATIVE_PAGE_SIZE / 8).skip(1) {
+            a.emit_mov(Size::S64, Location::Imm32(0), *loc);
+        }
+        locs
+    }
+
     pub(crate) fn finalize_locals<E: Emitter>(&mut self, a: &mut E) {
         // Unwind stack to the "save area".
         a.emit_lea(
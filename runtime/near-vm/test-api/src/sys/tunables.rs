@@ -150,6 +150,10 @@ impl Tunables for BaseTunables {
         (self.regular_op_cost / 8).saturating_mul(stack_size)
     }
 
+    fn bulk_memory_byte_gas_cost(&self) -> u32 {
+        0
+    }
+
     /// Instrumentation configuration: stack limiter config
     fn stack_limiter_cfg(&self) -> Box<dyn finite_wasm::max_stack::SizeConfig> {
         Box::new(SimpleMaxStackCfg)
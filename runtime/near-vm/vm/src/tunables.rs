@@ -59,6 +59,9 @@ pub trait Tunables: Sync {
 
     /// Cost for initializing a stack frame
     fn stack_init_gas_cost(&self, frame_size: u64) -> u64;
+
+    /// Cost per byte processed by a bulk memory operation
+    fn bulk_memory_byte_gas_cost(&self) -> u32;
 }
 
 #[doc(hidden)]
@@ -118,4 +121,8 @@ impl Tunables for TestTunables {
     fn stack_init_gas_cost(&self, _frame_size: u64) -> u64 {
         unimplemented!()
     }
+
+    fn bulk_memory_byte_gas_cost(&self) -> u32 {
+        unimplemented!()
+    }
 }
@@ -228,6 +228,14 @@ pub enum Cost {
     /// attempt to cause slow loads and stores. The total time spent in the
     /// runtime is divided by the number of executed instructions.
     WasmInstruction,
+    /// Estimates `wasm_config.bulk_memory_byte_cost` which is charged per byte
+    /// processed by the `memory.copy`, `memory.fill` and `memory.init` WASM
+    /// operations, on top of `regular_op_cost`.
+    ///
+    /// Estimation: Run a contract that copies and fills large memory ranges in
+    /// a loop, and the same contract processing zero bytes. The difference in
+    /// time is divided by the number of bytes processed.
+    WasmBulkMemoryByte,
 
     // # Reading and writing memory
     // The hosting runtime sometimes copies data between in and out of WASM
@@ -438,6 +446,24 @@ pub enum Cost {
     /// In the end, the cost should be low enough, compared to the base cost,
     /// that it does not matter all that much if we overestimate it a bit.
     Ed25519VerifyByte,
+    /// Estimates `secp256k1_verify_base`, which covers the full cost of the
+    /// host function `secp256k1_verify` to verify an ECDSA signature over a
+    /// 32-byte hash.
+    ///
+    /// Estimation: Use a fixed signature embedded in the test contract and
+    /// verify it `N` times in a loop and divide by `N`. The inputs have a fixed
+    /// size, so there is no per-byte cost to estimate.
+    Secp256k1VerifyBase,
+    /// Estimates `secp256r1_verify_base`, which covers the full cost of the
+    /// host function `secp256r1_verify` to verify a NIST P-256 ECDSA signature.
+    ///
+    /// Estimation: Same as `Secp256k1VerifyBase`.
+    Secp256r1VerifyBase,
+    /// Estimates `bip340_verify_base`, which covers the full cost of the host
+    /// function `bip340_verify` to verify a BIP-340 Schnorr signature.
+    ///
+    /// Estimation: Same as `Secp256k1VerifyBase`.
+    Bip340VerifyBase,
     // `storage_write` records a single key-value pair, initially in the
     // prospective changes in-memory hash map, and then once a full block has
     // been processed, in the on-disk trie. If there was already a value
@@ -541,6 +567,25 @@ pub enum Cost {
     StorageIterNextKeyByte,
     /// DEPRECATED: Was charged in `storage_iter_next`
     StorageIterNextValueByte,
+    /// Estimates `storage_iter_v2_base` which is charged once per call to
+    /// `storage_iter_prefix_v2`.
+    ///
+    /// Estimation: Contract call that requests N empty pages from a storage
+    /// with 1k small values and divide the cost by N.
+    StorageIterV2Base,
+    /// Estimates `storage_iter_v2_key` which is charged for each key returned
+    /// by `storage_iter_prefix_v2`.
+    ///
+    /// Estimation: Contract call that iterates over 1k small values with small
+    /// keys in pages of 3-4 entries, subtract the base cost of the calls and
+    /// divide by the number of keys.
+    StorageIterV2Key,
+    /// Estimates `storage_iter_v2_byte` which is charged for each byte of the
+    /// keys and values returned by `storage_iter_prefix_v2`.
+    ///
+    /// Estimation: Same as `StorageIterV2Key` but with big values (10kiB),
+    /// subtract the per key cost and divide by the total key and value bytes.
+    StorageIterV2Byte,
 
     /// Estimates `touching_trie_node` which is charged when smart contracts
     /// access storage either through `storage_has_key`, `storage_read`,
@@ -19,6 +19,9 @@ pub fn costs_to_runtime_config(cost_table: &CostTable) -> anyhow::Result<Runtime
     let regular_op_cost = cost_table
         .get(Cost::WasmInstruction)
         .with_context(|| format!("undefined cost: {}", Cost::WasmInstruction))?;
+    let bulk_memory_byte_cost = cost_table
+        .get(Cost::WasmBulkMemoryByte)
+        .with_context(|| format!("undefined cost: {}", Cost::WasmBulkMemoryByte))?;
 
     // Take latest VM limit config, because estimation doesn't affect it.
     // Note that if you run estimation against stable version, it doesn't catch updates of nightly
@@ -33,6 +36,7 @@ pub fn costs_to_runtime_config(cost_table: &CostTable) -> anyhow::Result<Runtime
             ext_costs: ext_costs_config(cost_table)?,
             grow_mem_cost: 1,
             regular_op_cost: u32::try_from(regular_op_cost).unwrap(),
+            bulk_memory_byte_cost: u32::try_from(bulk_memory_byte_cost).unwrap(),
             limit_config: vm_limit_config,
             ..*latest_runtime_config.wasm_config
         }),
@@ -72,6 +76,10 @@ fn runtime_fees_config(cost_table: &CostTable) -> anyhow::Result<RuntimeFeesConf
             ActionCosts::new_action_receipt => fee(Cost::ActionReceiptCreation)?,
             ActionCosts::new_data_receipt_base => fee(Cost::DataReceiptCreationBase)?,
             ActionCosts::new_data_receipt_byte => fee(Cost::DataReceiptCreationPerByte)?,
+            // Not estimated separately, the deploy and the function call are estimated already.
+            ActionCosts::deploy_contract_with_init => {
+                actual_fees_config.fee(ActionCosts::deploy_contract_with_init).clone()
+            }
         },
         ..RuntimeFeesConfig::clone(&actual_fees_config)
     };
@@ -128,6 +136,9 @@ fn estimation(cost: ExtCosts) -> Option<Cost> {
         ExtCosts::ecrecover_base => Cost::EcrecoverBase,
         ExtCosts::ed25519_verify_base => Cost::Ed25519VerifyBase,
         ExtCosts::ed25519_verify_byte => Cost::Ed25519VerifyByte,
+        ExtCosts::secp256k1_verify_base => Cost::Secp256k1VerifyBase,
+        ExtCosts::secp256r1_verify_base => Cost::Secp256r1VerifyBase,
+        ExtCosts::bip340_verify_base => Cost::Bip340VerifyBase,
         ExtCosts::log_base => Cost::LogBase,
         ExtCosts::log_byte => Cost::LogByte,
         ExtCosts::storage_write_base => Cost::StorageWriteBase,
@@ -142,6 +153,9 @@ fn estimation(cost: ExtCosts) -> Option<Cost> {
         ExtCosts::storage_remove_ret_value_byte => Cost::StorageRemoveRetValueByte,
         ExtCosts::storage_has_key_base => Cost::StorageHasKeyBase,
         ExtCosts::storage_has_key_byte => Cost::StorageHasKeyByte,
+        ExtCosts::storage_iter_v2_base => Cost::StorageIterV2Base,
+        ExtCosts::storage_iter_v2_key => Cost::StorageIterV2Key,
+        ExtCosts::storage_iter_v2_byte => Cost::StorageIterV2Byte,
         ExtCosts::touching_trie_node => Cost::TouchingTrieNode,
         ExtCosts::read_cached_trie_node => Cost::ReadCachedTrieNode,
         ExtCosts::promise_and_base => Cost::PromiseAndBase,
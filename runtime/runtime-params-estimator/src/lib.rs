@@ -82,6 +82,8 @@ pub mod config;
 mod function_call;
 mod gas_metering;
 mod trie;
+// Counts deterministic work done by fixed workloads, used as a gas cost regression suite.
+pub mod work_units;
 
 use crate::config::Config;
 pub use crate::cost::Cost;
@@ -213,6 +215,7 @@ static ALL_COSTS: &[(Cost, fn(&mut EstimatorContext) -> GasCost)] = &[
     (Cost::ActionDelegateExec, action_costs::delegate_exec),
     (Cost::HostFunctionCall, host_function_call),
     (Cost::WasmInstruction, wasm_instruction),
+    (Cost::WasmBulkMemoryByte, wasm_bulk_memory_byte),
     (Cost::DataReceiptCreationBase, data_receipt_creation_base),
     (Cost::DataReceiptCreationPerByte, data_receipt_creation_per_byte),
     (Cost::ReadMemoryBase, read_memory_base),
@@ -240,6 +243,12 @@ static ALL_COSTS: &[(Cost, fn(&mut EstimatorContext) -> GasCost)] = &[
     (Cost::EcrecoverBase, ecrecover_base),
     (Cost::Ed25519VerifyBase, ed25519_verify_base),
     (Cost::Ed25519VerifyByte, ed25519_verify_byte),
+    #[cfg(feature = "nightly")]
+    (Cost::Secp256k1VerifyBase, secp256k1_verify_base),
+    #[cfg(feature = "nightly")]
+    (Cost::Secp256r1VerifyBase, secp256r1_verify_base),
+    #[cfg(feature = "nightly")]
+    (Cost::Bip340VerifyBase, bip340_verify_base),
     (Cost::AltBn128G1MultiexpBase, alt_bn128g1_multiexp_base),
     (Cost::AltBn128G1MultiexpElement, alt_bn128g1_multiexp_element),
     (Cost::AltBn128G1SumBase, alt_bn128g1_sum_base),
@@ -258,6 +267,12 @@ static ALL_COSTS: &[(Cost, fn(&mut EstimatorContext) -> GasCost)] = &[
     (Cost::StorageRemoveBase, storage_remove_base),
     (Cost::StorageRemoveKeyByte, storage_remove_key_byte),
     (Cost::StorageRemoveRetValueByte, storage_remove_ret_value_byte),
+    #[cfg(feature = "nightly")]
+    (Cost::StorageIterV2Base, storage_iter_v2_base),
+    #[cfg(feature = "nightly")]
+    (Cost::StorageIterV2Key, storage_iter_v2_key),
+    #[cfg(feature = "nightly")]
+    (Cost::StorageIterV2Byte, storage_iter_v2_byte),
     (Cost::TouchingTrieNode, touching_trie_node),
     (Cost::ReadCachedTrieNode, read_cached_trie_node),
     (Cost::ApplyBlock, apply_block_cost),
@@ -941,6 +956,61 @@ fn wasm_instruction(ctx: &mut EstimatorContext) -> GasCost {
     per_instruction
 }
 
+fn wasm_bulk_memory_byte(ctx: &mut EstimatorContext) -> GasCost {
+    let vm_kind = ctx.config.vm_kind;
+
+    let n_iters = 10;
+    let n_loops = 1000;
+    let bytes_per_op = 64 * 1024;
+
+    let config_store = RuntimeConfigStore::new(None);
+    let mut config =
+        near_parameters::vm::Config::clone(&config_store.get_config(PROTOCOL_VERSION).wasm_config);
+    config.limit_config.contract_prepare_version = near_parameters::vm::ContractPrepareVersion::V3;
+    let config = Arc::new(config);
+    let fees = Arc::new(RuntimeFeesConfig::test());
+    let cache = MockContractRuntimeCache::default();
+
+    let measure = |len: u64| {
+        let code = wat::parse_str(format!(
+            r#"(module
+                (memory 2)
+                (func (export "main")
+                    (local $i i32)
+                    (loop $l
+                        (memory.copy (i32.const 0) (i32.const {bytes_per_op}) (i32.const {len}))
+                        (memory.fill (i32.const {bytes_per_op}) (local.get $i) (i32.const {len}))
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br_if $l (i32.lt_u (local.get $i) (i32.const {n_loops}))))))"#
+        ))
+        .unwrap();
+        let code = ContractCode::new(code, None);
+        let mut fake_external = MockedExternal::with_code(code.clone_for_tests());
+        let mut run = || {
+            let context = create_context(vec![]);
+            let gas_counter = context.make_gas_counter(&config);
+            let vm_result = vm_kind
+                .runtime(config.clone())
+                .unwrap()
+                .prepare(&fake_external, Some(&cache), gas_counter, "main")
+                .run(&mut fake_external, &context, Arc::clone(&fees))
+                .expect("fatal_error");
+            assert!(vm_result.aborted.is_none());
+        };
+        run(); // warmup
+        let start = GasCost::measure(ctx.config.metric);
+        for _ in 0..n_iters {
+            run();
+        }
+        start.elapsed()
+    };
+
+    let empty = measure(0);
+    let full = measure(bytes_per_op);
+    // Both `memory.copy` and `memory.fill` process `bytes_per_op` bytes per loop iteration.
+    (full - empty) / (n_iters * n_loops * 2 * bytes_per_op)
+}
+
 fn read_memory_base(ctx: &mut EstimatorContext) -> GasCost {
     fn_cost(ctx, "read_memory_10b_10k", ExtCosts::read_memory_base, 10_000)
 }
@@ -1058,6 +1128,21 @@ fn ed25519_verify_byte(ctx: &mut EstimatorContext) -> GasCost {
     byte - base / iteration_bytes
 }
 
+#[cfg(feature = "nightly")]
+fn secp256k1_verify_base(ctx: &mut EstimatorContext) -> GasCost {
+    fn_cost(ctx, "secp256k1_verify_1k", ExtCosts::secp256k1_verify_base, 1_000)
+}
+
+#[cfg(feature = "nightly")]
+fn secp256r1_verify_base(ctx: &mut EstimatorContext) -> GasCost {
+    fn_cost(ctx, "secp256r1_verify_1k", ExtCosts::secp256r1_verify_base, 1_000)
+}
+
+#[cfg(feature = "nightly")]
+fn bip340_verify_base(ctx: &mut EstimatorContext) -> GasCost {
+    fn_cost(ctx, "bip340_verify_1k", ExtCosts::bip340_verify_base, 1_000)
+}
+
 fn alt_bn128g1_multiexp_base(ctx: &mut EstimatorContext) -> GasCost {
     fn_cost(ctx, "alt_bn128_g1_multiexp_1_10", ExtCosts::alt_bn128_g1_multiexp_base, 10)
 }
@@ -1278,6 +1363,46 @@ fn storage_remove_ret_value_byte(ctx: &mut EstimatorContext) -> GasCost {
     )
 }
 
+#[cfg(feature = "nightly")]
+fn storage_iter_v2_base(ctx: &mut EstimatorContext) -> GasCost {
+    fn_cost_with_setup(
+        ctx,
+        "storage_write_10b_key_10b_value_1k",
+        "storage_iter_v2_empty_page_1k",
+        ExtCosts::storage_iter_v2_base,
+        1000,
+        0,
+    )
+}
+#[cfg(feature = "nightly")]
+fn storage_iter_v2_key(ctx: &mut EstimatorContext) -> GasCost {
+    let base = storage_iter_v2_base(ctx);
+    let key = fn_cost_with_setup(
+        ctx,
+        "storage_write_10b_key_10b_value_1k",
+        "storage_iter_v2_1k_keys",
+        ExtCosts::storage_iter_v2_key,
+        1000,
+        0,
+    );
+    // The contract makes one call per first key byte, 256 in total.
+    key.saturating_sub(&(base * 256 / 1000), &NonNegativeTolerance::PER_MILLE)
+}
+#[cfg(feature = "nightly")]
+fn storage_iter_v2_byte(ctx: &mut EstimatorContext) -> GasCost {
+    let key = storage_iter_v2_key(ctx);
+    let bytes_per_key = 10 + 10 * 1024;
+    let byte = fn_cost_with_setup(
+        ctx,
+        "storage_write_10b_key_10kib_value_1k",
+        "storage_iter_v2_1k_keys",
+        ExtCosts::storage_iter_v2_byte,
+        bytes_per_key * 1000,
+        0,
+    );
+    byte.saturating_sub(&(key / bytes_per_key), &NonNegativeTolerance::PER_MILLE)
+}
+
 fn touching_trie_node(ctx: &mut EstimatorContext) -> GasCost {
     // TTN write cost = TTN cost because we no longer charge it on reads since
     // flat storage for reads was introduced
@@ -113,6 +113,8 @@ struct CliArgs {
 #[derive(clap::Subcommand)]
 enum CliSubCmd {
     Replay(ReplayCmd),
+    /// Print the deterministic work units counted for each regression workload.
+    WorkUnits,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -122,6 +124,12 @@ fn main() -> anyhow::Result<()> {
     if let Some(cmd) = cli_args.sub_cmd {
         return match cmd {
             CliSubCmd::Replay(inner) => inner.run(&mut std::io::stdout()),
+            CliSubCmd::WorkUnits => {
+                for (name, work_units) in runtime_params_estimator::work_units::measure_all() {
+                    println!("{name}\n{work_units}");
+                }
+                Ok(())
+            }
         };
     }
 
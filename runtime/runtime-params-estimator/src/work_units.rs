@@ -0,0 +1,471 @@
+//! Deterministic work counting for gas cost regression testing.
+//!
+//! Time and QEMU based estimations are too noisy or too heavy to run on every
+//! change. Instead, the workloads here apply a fixed set of actions and host
+//! function calls on an in-memory trie and count work that does not depend on
+//! the machine: executed Wasm instructions, trie nodes read and written, bytes
+//! hashed by host functions and how much of each `ExtCosts` was charged.
+//!
+//! The tests compare the counts with the snapshots in `src/snapshots`, so a
+//! change that makes a workload do more work fails even when the workload is
+//! charged the same gas. After an intended change, update the snapshots with
+//! `cargo insta test -p runtime-params-estimator --review`.
+//!
+//! Stake and delegate actions are not covered, they need a real epoch manager
+//! and signed delegate actions respectively.
+
+use near_crypto::{KeyType, PublicKey};
+use near_parameters::{ExtCosts, RuntimeConfigStore};
+use near_primitives::account::{AccessKey, AccessKeyPermission, FunctionCallPermission};
+use near_primitives::bandwidth_scheduler::BlockBandwidthRequests;
+use near_primitives::congestion_info::{BlockCongestionInfo, ExtendedCongestionInfo};
+use near_primitives::hash::CryptoHash;
+use near_primitives::receipt::{ActionReceipt, Receipt, ReceiptEnum, ReceiptV0};
+use near_primitives::runtime::migration_data::{MigrationData, MigrationFlags};
+use near_primitives::test_utils::{account_new, MockEpochInfoProvider};
+use near_primitives::transaction::{
+    Action, AddKeyAction, CreateAccountAction, DeleteAccountAction, DeleteKeyAction,
+    DeployContractAction, ExecutionMetadata, ExecutionStatus, FunctionCallAction, TransferAction,
+};
+use near_primitives::types::{AccountId, Balance, Gas, StateChangeCause, StateRoot};
+use near_primitives::version::{ProtocolFeature, PROTOCOL_VERSION};
+use near_store::test_utils::TestTriesBuilder;
+use near_store::{set_account, set_code, ShardTries, ShardUId, Trie};
+use near_vm_runner::{ContractCode, MockContractRuntimeCache};
+use node_runtime::{ApplyState, ApplyStats};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+const ALICE: &str = "alice.near";
+const BOB: &str = "bob.near";
+const CAROL: &str = "carol.near";
+
+const INITIAL_BALANCE: Balance = 1_000_000 * NEAR;
+const NEAR: Balance = 10u128.pow(24);
+
+/// How many times each host function workload calls the host function.
+const HOST_CALLS: u64 = 10;
+/// Size of the input passed to the host functions and of the function call arguments.
+const INPUT_LEN: usize = 100;
+
+/// Deterministic work done by a workload.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WorkUnits {
+    /// Gas burnt by the workload. Kept next to the work to tell apart changes
+    /// of the work done from changes of the gas parameters.
+    pub gas_burnt: Gas,
+    /// Wasm instructions executed by contracts, as counted by gas metering.
+    pub wasm_instructions: u64,
+    /// Trie nodes read, either from the storage or the accounting cache.
+    pub trie_nodes_read: u64,
+    /// Trie nodes inserted when finalizing the state changes.
+    pub trie_nodes_written: u64,
+    /// Bytes passed to the hashing host functions.
+    pub bytes_hashed: u64,
+    /// How many times, or for how many bytes, each host function cost was charged.
+    pub ext_costs: BTreeMap<ExtCosts, u64>,
+}
+
+struct Workload {
+    name: &'static str,
+    predecessor_id: &'static str,
+    receiver_id: &'static str,
+    /// Actions applied and committed before the measured ones.
+    setup: Vec<Action>,
+    actions: Vec<Action>,
+}
+
+/// Measures the work done by each workload, in a fixed order.
+pub fn measure_all() -> Vec<(&'static str, WorkUnits)> {
+    workloads()
+        .into_iter()
+        .map(|workload| {
+            let mut testbed = WorkUnitsTestbed::new();
+            if !workload.setup.is_empty() {
+                testbed.apply(&workload, workload.setup.clone(), true);
+            }
+            (workload.name, testbed.apply(&workload, workload.actions.clone(), false))
+        })
+        .collect()
+}
+
+fn workloads() -> Vec<Workload> {
+    let workload = |name, predecessor_id, receiver_id, setup, actions| Workload {
+        name,
+        predecessor_id,
+        receiver_id,
+        setup,
+        actions,
+    };
+    let call = |method_name: &str| function_call(method_name, Vec::new());
+    let access_key = |permission| {
+        let public_key = PublicKey::from_seed(KeyType::ED25519, "work_units");
+        Action::AddKey(Box::new(AddKeyAction {
+            public_key,
+            access_key: AccessKey { nonce: 0, permission },
+        }))
+    };
+    let function_call_permission = AccessKeyPermission::FunctionCall(FunctionCallPermission {
+        allowance: None,
+        receiver_id: BOB.to_string(),
+        method_names: vec!["method".to_string()],
+    });
+    let transfer = Action::Transfer(TransferAction { deposit: NEAR });
+
+    vec![
+        workload("action_receipt_creation", ALICE, ALICE, vec![], vec![]),
+        workload(
+            "action_create_account",
+            ALICE,
+            "new.alice.near",
+            vec![],
+            vec![Action::CreateAccount(CreateAccountAction {}), transfer.clone()],
+        ),
+        workload("action_transfer", ALICE, BOB, vec![], vec![transfer]),
+        workload(
+            "action_add_full_access_key",
+            ALICE,
+            ALICE,
+            vec![],
+            vec![access_key(AccessKeyPermission::FullAccess)],
+        ),
+        workload(
+            "action_add_function_access_key",
+            ALICE,
+            ALICE,
+            vec![],
+            vec![access_key(function_call_permission)],
+        ),
+        workload(
+            "action_delete_key",
+            ALICE,
+            ALICE,
+            vec![access_key(AccessKeyPermission::FullAccess)],
+            vec![Action::DeleteKey(Box::new(DeleteKeyAction {
+                public_key: PublicKey::from_seed(KeyType::ED25519, "work_units"),
+            }))],
+        ),
+        workload(
+            "action_delete_account",
+            CAROL,
+            CAROL,
+            vec![],
+            vec![Action::DeleteAccount(DeleteAccountAction {
+                beneficiary_id: BOB.parse().unwrap(),
+            })],
+        ),
+        workload(
+            "action_deploy_contract",
+            BOB,
+            BOB,
+            vec![],
+            vec![Action::DeployContract(DeployContractAction {
+                code: near_test_contracts::trivial_contract().to_vec(),
+            })],
+        ),
+        workload(
+            "action_function_call",
+            ALICE,
+            ALICE,
+            vec![],
+            vec![function_call("noop", vec![0; INPUT_LEN])],
+        ),
+        workload("host_storage_write", ALICE, ALICE, vec![], vec![call("storage_write")]),
+        workload(
+            "host_storage_read",
+            ALICE,
+            ALICE,
+            vec![call("storage_write")],
+            vec![call("storage_read")],
+        ),
+        workload(
+            "host_storage_has_key",
+            ALICE,
+            ALICE,
+            vec![call("storage_write")],
+            vec![call("storage_has_key")],
+        ),
+        workload(
+            "host_storage_remove",
+            ALICE,
+            ALICE,
+            vec![call("storage_write")],
+            vec![call("storage_remove")],
+        ),
+        workload("host_registers", ALICE, ALICE, vec![], vec![call("registers")]),
+        workload("host_log_utf8", ALICE, ALICE, vec![], vec![call("log_utf8")]),
+        workload("host_log_utf16", ALICE, ALICE, vec![], vec![call("log_utf16")]),
+        workload("host_sha256", ALICE, ALICE, vec![], vec![call("sha256")]),
+        workload("host_keccak256", ALICE, ALICE, vec![], vec![call("keccak256")]),
+        workload("host_keccak512", ALICE, ALICE, vec![], vec![call("keccak512")]),
+        workload("host_ripemd160", ALICE, ALICE, vec![], vec![call("ripemd160")]),
+        workload("host_promise_transfer", ALICE, ALICE, vec![], vec![call("promise_transfer")]),
+    ]
+}
+
+fn function_call(method_name: &str, args: Vec<u8>) -> Action {
+    Action::FunctionCall(Box::new(FunctionCallAction {
+        method_name: method_name.to_string(),
+        args,
+        gas: 100 * 10u64.pow(12),
+        deposit: 0,
+    }))
+}
+
+/// Contract deployed on `ALICE`, each host function workload calls the
+/// host function `HOST_CALLS` times with an input of `INPUT_LEN` bytes.
+fn contract() -> Vec<u8> {
+    let host_function_loop = |name: &str, body: &str| {
+        format!(
+            r#"
+  (func (export "{name}") (local $i i64)
+    (loop $repeat
+      (i64.store (i32.const 512) (local.get $i))
+      {body}
+      (local.set $i (i64.add (local.get $i) (i64.const 1)))
+      (br_if $repeat (i64.lt_u (local.get $i) (i64.const {HOST_CALLS})))))"#
+        )
+    };
+    // Keys are the 8 bytes at offset 512, the loop counter.
+    let functions = [
+        host_function_loop(
+            "storage_write",
+            "(drop (call $storage_write (i64.const 8) (i64.const 512) (i64.const {len}) (i64.const 0) (i64.const 0)))",
+        ),
+        host_function_loop(
+            "storage_read",
+            "(drop (call $storage_read (i64.const 8) (i64.const 512) (i64.const 0)))",
+        ),
+        host_function_loop(
+            "storage_has_key",
+            "(drop (call $storage_has_key (i64.const 8) (i64.const 512)))",
+        ),
+        host_function_loop(
+            "storage_remove",
+            "(drop (call $storage_remove (i64.const 8) (i64.const 512) (i64.const 0)))",
+        ),
+        host_function_loop(
+            "registers",
+            "(call $write_register (i64.const 0) (i64.const {len}) (i64.const 0))
+      (call $read_register (i64.const 0) (i64.const 1024))",
+        ),
+        host_function_loop("log_utf8", "(call $log_utf8 (i64.const {len}) (i64.const 0))"),
+        host_function_loop("log_utf16", "(call $log_utf16 (i64.const {len}) (i64.const 0))"),
+        host_function_loop("sha256", "(call $sha256 (i64.const {len}) (i64.const 0) (i64.const 0))"),
+        host_function_loop(
+            "keccak256",
+            "(call $keccak256 (i64.const {len}) (i64.const 0) (i64.const 0))",
+        ),
+        host_function_loop(
+            "keccak512",
+            "(call $keccak512 (i64.const {len}) (i64.const 0) (i64.const 0))",
+        ),
+        host_function_loop(
+            "ripemd160",
+            "(call $ripemd160 (i64.const {len}) (i64.const 0) (i64.const 0))",
+        ),
+        host_function_loop(
+            "promise_transfer",
+            "(call $promise_batch_action_transfer
+        (call $promise_batch_create (i64.const 8) (i64.const 256))
+        (i64.const 272))",
+        ),
+    ]
+    .join("")
+    .replace("{len}", &INPUT_LEN.to_string());
+    let input = "a".repeat(INPUT_LEN);
+    let wat = format!(
+        r#"
+(module
+  (import "env" "storage_write" (func $storage_write (param i64 i64 i64 i64 i64) (result i64)))
+  (import "env" "storage_read" (func $storage_read (param i64 i64 i64) (result i64)))
+  (import "env" "storage_has_key" (func $storage_has_key (param i64 i64) (result i64)))
+  (import "env" "storage_remove" (func $storage_remove (param i64 i64 i64) (result i64)))
+  (import "env" "write_register" (func $write_register (param i64 i64 i64)))
+  (import "env" "read_register" (func $read_register (param i64 i64)))
+  (import "env" "log_utf8" (func $log_utf8 (param i64 i64)))
+  (import "env" "log_utf16" (func $log_utf16 (param i64 i64)))
+  (import "env" "sha256" (func $sha256 (param i64 i64 i64)))
+  (import "env" "keccak256" (func $keccak256 (param i64 i64 i64)))
+  (import "env" "keccak512" (func $keccak512 (param i64 i64 i64)))
+  (import "env" "ripemd160" (func $ripemd160 (param i64 i64 i64)))
+  (import "env" "promise_batch_create" (func $promise_batch_create (param i64 i64) (result i64)))
+  (import "env" "promise_batch_action_transfer" (func $promise_batch_action_transfer (param i64 i64)))
+  (memory 1)
+  ;; Valid as both UTF-8 and UTF-16.
+  (data (i32.const 0) "{input}")
+  (data (i32.const 256) "{BOB}")
+  ;; Deposit of the promises, a little endian u128.
+  (data (i32.const 272) "\01")
+  (func (export "noop"))
+  {functions})"#
+    );
+    wat::parse_str(wat).unwrap()
+}
+
+/// Single shard in-memory state with `ALICE`, `BOB` and `CAROL` accounts.
+struct WorkUnitsTestbed {
+    tries: ShardTries,
+    root: StateRoot,
+    apply_state: ApplyState,
+    epoch_info_provider: MockEpochInfoProvider,
+}
+
+impl WorkUnitsTestbed {
+    fn new() -> Self {
+        let tries = TestTriesBuilder::new().build();
+        let shard_uid = ShardUId::single_shard();
+        let mut state_update = tries.new_trie_update(shard_uid, Trie::EMPTY_ROOT);
+        let code = ContractCode::new(contract(), None);
+        for account_id in [ALICE, BOB, CAROL] {
+            let code_hash = if account_id == ALICE { *code.hash() } else { CryptoHash::default() };
+            let account = account_new(INITIAL_BALANCE, code_hash);
+            set_account(&mut state_update, account_id.parse().unwrap(), &account);
+        }
+        set_code(&mut state_update, ALICE.parse().unwrap(), &code);
+        state_update.commit(StateChangeCause::InitialState);
+        let trie_changes = state_update.finalize().unwrap().trie_changes;
+        let mut store_update = tries.store_update();
+        let root = tries.apply_all(&trie_changes, shard_uid, &mut store_update);
+        store_update.commit().unwrap();
+
+        Self {
+            tries,
+            root,
+            apply_state: Self::make_apply_state(),
+            epoch_info_provider: MockEpochInfoProvider::new([].into_iter()),
+        }
+    }
+
+    /// Uses the unmodified runtime config, so that the gas burnt, and thus
+    /// the executed Wasm instructions, are counted.
+    fn make_apply_state() -> ApplyState {
+        let runtime_config = RuntimeConfigStore::new(None).get_config(PROTOCOL_VERSION).clone();
+        let shard_id = ShardUId::single_shard().shard_id();
+        let congestion_info = if ProtocolFeature::CongestionControl.enabled(PROTOCOL_VERSION) {
+            [(shard_id, ExtendedCongestionInfo::default())].into()
+        } else {
+            Default::default()
+        };
+        ApplyState {
+            apply_reason: None,
+            block_height: 1,
+            prev_block_hash: Default::default(),
+            block_hash: Default::default(),
+            shard_id,
+            epoch_id: Default::default(),
+            epoch_height: 0,
+            gas_price: 100_000_000,
+            block_timestamp: 0,
+            gas_limit: None,
+            random_seed: Default::default(),
+            current_protocol_version: PROTOCOL_VERSION,
+            config: runtime_config,
+            cache: Some(Box::new(MockContractRuntimeCache::default())),
+            is_new_chunk: true,
+            migration_data: Arc::new(MigrationData::default()),
+            migration_flags: MigrationFlags::default(),
+            congestion_info: BlockCongestionInfo::new(congestion_info),
+            bandwidth_requests: BlockBandwidthRequests::empty(),
+        }
+    }
+
+    /// Applies the actions in a single action receipt of the workload and
+    /// counts the work done.
+    fn apply(&mut self, workload: &Workload, actions: Vec<Action>, commit: bool) -> WorkUnits {
+        let predecessor_id: AccountId = workload.predecessor_id.parse().unwrap();
+        let action_receipt = ActionReceipt {
+            signer_id: predecessor_id.clone(),
+            signer_public_key: PublicKey::from_seed(KeyType::ED25519, workload.predecessor_id),
+            gas_price: self.apply_state.gas_price,
+            output_data_receivers: vec![],
+            input_data_ids: vec![],
+            actions,
+        };
+        let receipt = Receipt::V0(ReceiptV0 {
+            predecessor_id,
+            receiver_id: workload.receiver_id.parse().unwrap(),
+            receipt_id: CryptoHash::default(),
+            receipt: ReceiptEnum::Action(action_receipt),
+        });
+
+        let shard_uid = ShardUId::single_shard();
+        let mut state_update = self.tries.new_trie_update(shard_uid, self.root);
+        node_runtime::with_ext_cost_counter(|cc| cc.clear());
+        let outcome = node_runtime::estimator::apply_action_receipt(
+            &mut state_update,
+            &self.apply_state,
+            &receipt,
+            &mut vec![],
+            &mut vec![],
+            &mut ApplyStats::default(),
+            &self.epoch_info_provider,
+        )
+        .expect("applying a workload should not fail");
+        match &outcome.outcome.status {
+            ExecutionStatus::Unknown => panic!("{}: receipt not applied", workload.name),
+            ExecutionStatus::Failure(err) => panic!("{}: failed apply, {err:?}", workload.name),
+            ExecutionStatus::SuccessValue(_) | ExecutionStatus::SuccessReceiptId(_) => (),
+        }
+        let mut ext_costs = BTreeMap::new();
+        node_runtime::with_ext_cost_counter(|cc| ext_costs.extend(cc.drain()));
+        let wasm_instructions = match &outcome.outcome.metadata {
+            ExecutionMetadata::V3(profile) => {
+                profile.get_wasm_cost()
+                    / u64::from(self.apply_state.config.wasm_config.regular_op_cost)
+            }
+            ExecutionMetadata::V1 | ExecutionMetadata::V2(_) => 0,
+        };
+
+        let result = state_update.finalize().unwrap();
+        let trie_nodes = result.trie.get_trie_nodes_count();
+        let trie_nodes_written = result.trie_changes.insertions().len() as u64;
+        if commit {
+            let mut store_update = self.tries.store_update();
+            self.root = self.tries.apply_all(&result.trie_changes, shard_uid, &mut store_update);
+            store_update.commit().unwrap();
+        }
+
+        let bytes_hashed =
+            [ExtCosts::sha256_byte, ExtCosts::keccak256_byte, ExtCosts::keccak512_byte]
+                .iter()
+                .map(|cost| ext_costs.get(cost).copied().unwrap_or_default())
+                .sum::<u64>()
+                + ext_costs.get(&ExtCosts::ripemd160_block).copied().unwrap_or_default() * 64;
+        WorkUnits {
+            gas_burnt: outcome.outcome.gas_burnt,
+            wasm_instructions,
+            trie_nodes_read: trie_nodes.db_reads + trie_nodes.mem_reads,
+            trie_nodes_written,
+            bytes_hashed,
+            ext_costs,
+        }
+    }
+}
+
+impl fmt::Display for WorkUnits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "gas_burnt: {}", self.gas_burnt)?;
+        writeln!(f, "wasm_instructions: {}", self.wasm_instructions)?;
+        writeln!(f, "trie_nodes_read: {}", self.trie_nodes_read)?;
+        writeln!(f, "trie_nodes_written: {}", self.trie_nodes_written)?;
+        writeln!(f, "bytes_hashed: {}", self.bytes_hashed)?;
+        writeln!(f, "ext_costs:")?;
+        for (cost, value) in &self.ext_costs {
+            writeln!(f, "  {cost}: {value}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /// Fails when the work done by any workload changes, see the module docs.
+    #[test]
+    fn test_work_units() {
+        for (name, work_units) in super::measure_all() {
+            insta::assert_snapshot!(name, work_units.to_string());
+        }
+    }
+}
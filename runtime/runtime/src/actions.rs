@@ -14,11 +14,11 @@ use near_primitives::config::ViewConfig;
 use near_primitives::errors::{ActionError, ActionErrorKind, InvalidAccessKeyError, RuntimeError};
 use near_primitives::hash::CryptoHash;
 use near_primitives::receipt::{
-    ActionReceipt, DataReceipt, Receipt, ReceiptEnum, ReceiptPriority, ReceiptV0,
+    ActionReceipt, DataReceipt, GlobalContractChange, Receipt, ReceiptEnum, ReceiptPriority,
+    ReceiptV0,
 };
 use near_primitives::transaction::{
-    Action, AddKeyAction, DeleteAccountAction, DeleteKeyAction, DeployContractAction,
-    FunctionCallAction, StakeAction,
+    Action, AddKeyAction, DeleteAccountAction, DeleteKeyAction, FunctionCallAction, StakeAction,
 };
 use near_primitives::types::validator_stake::ValidatorStake;
 use near_primitives::types::{
@@ -28,10 +28,13 @@ use near_primitives::utils::account_is_implicit;
 use near_primitives::version::{
     ProtocolFeature, ProtocolVersion, DELETE_KEY_STORAGE_USAGE_PROTOCOL_VERSION,
 };
+use near_primitives::views::FunctionCallTraceView;
 use near_primitives_core::account::id::AccountType;
 use near_store::{
-    enqueue_promise_yield_timeout, get_access_key, get_code, get_promise_yield_indices,
-    remove_access_key, remove_account, set_access_key, set_code, set_promise_yield_indices,
+    enqueue_promise_yield_timeout, get_access_key, get_code, get_global_contract_owned_codes,
+    get_global_contract_owner, get_promise_yield_indices, has_global_contract_code,
+    publish_global_contract_code, remove_access_key, remove_account, remove_code, set_access_key,
+    set_code, set_global_contract_owner, set_promise_yield_indices, unpublish_global_contract_code,
     StorageError, TrieUpdate,
 };
 use near_vm_runner::logic::errors::{
@@ -174,6 +177,7 @@ pub(crate) fn action_function_call(
     action_hash: &CryptoHash,
     code_hash: CryptoHash,
     config: &RuntimeConfig,
+    action_index: usize,
     is_last_action: bool,
     epoch_info_provider: &(dyn EpochInfoProvider),
     contract: Box<dyn PreparedContract>,
@@ -221,6 +225,16 @@ pub(crate) fn action_function_call(
         None,
     )?;
 
+    if let Some(trace) = outcome.execution_trace {
+        result.function_call_traces.push(FunctionCallTraceView {
+            action_index: action_index as u64,
+            method_name: function_call.method_name.clone(),
+            gas_burnt: outcome.burnt_gas,
+            error: outcome.aborted.as_ref().map(|err| err.to_string()),
+            steps: trace.steps.into_iter().map(crate::conversions::Convert::convert).collect(),
+        });
+    }
+
     match &outcome.aborted {
         None => {
             metrics::FUNCTION_CALL_PROCESSED.with_label_values(&["ok"]).inc();
@@ -614,12 +628,12 @@ pub(crate) fn action_deploy_contract(
     state_update: &mut TrieUpdate,
     account: &mut Account,
     account_id: &AccountId,
-    deploy_contract: &DeployContractAction,
+    code: &[u8],
     config: Arc<near_parameters::vm::Config>,
     cache: Option<&dyn ContractRuntimeCache>,
 ) -> Result<(), StorageError> {
     let _span = tracing::debug_span!(target: "runtime", "action_deploy_contract").entered();
-    let code = ContractCode::new(deploy_contract.code.clone(), None);
+    let code = ContractCode::new(code.to_vec(), None);
     let prev_code = get_code(state_update, account_id, Some(account.code_hash()))?;
     let prev_code_length = prev_code.map(|code| code.code().len() as u64).unwrap_or_default();
     account.set_storage_usage(account.storage_usage().saturating_sub(prev_code_length));
@@ -649,17 +663,143 @@ pub(crate) fn action_deploy_contract(
     Ok(())
 }
 
+/// Publishes the code to the global contract registry, which is replicated to every shard.
+///
+/// The account publishing the code pays for its storage for as long as the account exists. The
+/// code is removed from the registry once no account publishes it anymore. Publishing a code the
+/// account already publishes does nothing.
+pub(crate) fn action_deploy_global_contract(
+    state_update: &mut TrieUpdate,
+    apply_state: &ApplyState,
+    account: &mut Account,
+    account_id: &AccountId,
+    code: &[u8],
+    result: &mut ActionResult,
+    epoch_info_provider: &dyn EpochInfoProvider,
+) -> Result<(), RuntimeError> {
+    let _span = tracing::debug_span!(target: "runtime", "action_deploy_global_contract").entered();
+    let code = ContractCode::new(code.to_vec(), None);
+    let code_hash = *code.hash();
+    if get_global_contract_owner(state_update, account_id, code_hash)?.is_some() {
+        return Ok(());
+    }
+    let storage_config = &apply_state.config.fees.storage_usage_config;
+    let storage_usage = code.code().len() as u64
+        + code_hash.as_ref().len() as u64
+        + storage_config.num_extra_bytes_record;
+    account.set_storage_usage(account.storage_usage().checked_add(storage_usage).ok_or_else(
+        || {
+            StorageError::StorageInconsistentState(format!(
+                "Storage usage integer overflow for account {}",
+                account_id
+            ))
+        },
+    )?);
+    set_global_contract_owner(state_update, account_id.clone(), code_hash, storage_usage);
+    distribute_global_contract_change(
+        state_update,
+        apply_state,
+        account_id,
+        GlobalContractChange::Publish { code: code.code().to_vec() },
+        result,
+        epoch_info_provider,
+    )?;
+    // The compiled contract is cached under the hash of the code, so it is shared by all the
+    // accounts using the code.
+    precompile_contract(
+        &code,
+        Arc::clone(&apply_state.config.wasm_config),
+        apply_state.cache.as_deref(),
+    )
+    .ok();
+    state_update.contract_storage.record_deploy(code);
+    Ok(())
+}
+
+/// Points the account at a code from the global contract registry, removing the code the account
+/// stores itself, if any.
+pub(crate) fn action_use_global_contract(
+    state_update: &mut TrieUpdate,
+    account: &mut Account,
+    account_id: &AccountId,
+    code_hash: CryptoHash,
+    result: &mut ActionResult,
+) -> Result<(), StorageError> {
+    let _span = tracing::debug_span!(target: "runtime", "action_use_global_contract").entered();
+    if !has_global_contract_code(state_update, code_hash)? {
+        result.result = Err(ActionErrorKind::GlobalContractDoesNotExist { code_hash }.into());
+        return Ok(());
+    }
+    let prev_code = get_code(state_update, account_id, Some(account.code_hash()))?;
+    if let Some(prev_code) = prev_code {
+        let prev_code_length = prev_code.code().len() as u64;
+        account.set_storage_usage(account.storage_usage().saturating_sub(prev_code_length));
+        remove_code(state_update, account_id.clone());
+    }
+    account.set_code_hash(code_hash);
+    Ok(())
+}
+
+/// Applies the change of the global contract registry made by the account to the current shard
+/// and sends it to every other shard.
+fn distribute_global_contract_change(
+    state_update: &mut TrieUpdate,
+    apply_state: &ApplyState,
+    account_id: &AccountId,
+    change: GlobalContractChange,
+    result: &mut ActionResult,
+    epoch_info_provider: &dyn EpochInfoProvider,
+) -> Result<(), RuntimeError> {
+    apply_global_contract_change(state_update, account_id, &change)?;
+    let shard_layout = epoch_info_provider.shard_layout(&apply_state.epoch_id)?;
+    for shard_id in shard_layout.shard_ids() {
+        if shard_id == apply_state.shard_id {
+            continue;
+        }
+        result.new_receipts.push(Receipt::new_global_contract_distribution(
+            account_id.clone(),
+            shard_id,
+            vec![apply_state.shard_id],
+            change.clone(),
+        ));
+    }
+    Ok(())
+}
+
+/// Applies the change of the global contract registry made by the account to the current shard.
+/// Applying the same change more than once has no further effect.
+pub(crate) fn apply_global_contract_change(
+    state_update: &mut TrieUpdate,
+    account_id: &AccountId,
+    change: &GlobalContractChange,
+) -> Result<(), StorageError> {
+    match change {
+        GlobalContractChange::Publish { code } => {
+            let code = ContractCode::new(code.clone(), None);
+            publish_global_contract_code(state_update, account_id.clone(), &code);
+        }
+        GlobalContractChange::Unpublish { code_hash } => {
+            unpublish_global_contract_code(state_update, account_id.clone(), *code_hash)?;
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn action_delete_account(
     state_update: &mut TrieUpdate,
+    apply_state: &ApplyState,
     account: &mut Option<Account>,
     actor_id: &mut AccountId,
     receipt: &Receipt,
     result: &mut ActionResult,
     account_id: &AccountId,
     delete_account: &DeleteAccountAction,
-    current_protocol_version: ProtocolVersion,
-) -> Result<(), StorageError> {
-    if current_protocol_version >= ProtocolFeature::DeleteActionRestriction.protocol_version() {
+    epoch_info_provider: &dyn EpochInfoProvider,
+) -> Result<(), RuntimeError> {
+    let owned_global_contracts = get_global_contract_owned_codes(state_update, account_id)?;
+    if apply_state.current_protocol_version
+        >= ProtocolFeature::DeleteActionRestriction.protocol_version()
+    {
         let account = account.as_ref().unwrap();
         let mut account_storage_usage = account.storage_usage();
         let contract_code = get_code(state_update, account_id, Some(account.code_hash()))?;
@@ -669,6 +809,11 @@ pub(crate) fn action_delete_account(
             debug_assert!(account_storage_usage > code_len);
             account_storage_usage = account_storage_usage.saturating_sub(code_len);
         }
+        // The codes published to the global contract registry are released with the account,
+        // like its own code.
+        for (_, storage_usage) in &owned_global_contracts {
+            account_storage_usage = account_storage_usage.saturating_sub(*storage_usage);
+        }
         if account_storage_usage > Account::MAX_ACCOUNT_DELETION_STORAGE_USAGE {
             result.result = Err(ActionErrorKind::DeleteAccountWithLargeState {
                 account_id: account_id.clone(),
@@ -686,6 +831,16 @@ pub(crate) fn action_delete_account(
             ReceiptPriority::NoPriority,
         ));
     }
+    for (code_hash, _) in owned_global_contracts {
+        distribute_global_contract_change(
+            state_update,
+            apply_state,
+            account_id,
+            GlobalContractChange::Unpublish { code_hash },
+            result,
+            epoch_info_provider,
+        )?;
+    }
     remove_account(state_update, account_id)?;
     *actor_id = receipt.predecessor_id().clone();
     *account = None;
@@ -866,7 +1021,9 @@ fn receipt_required_gas(apply_state: &ApplyState, receipt: &Receipt) -> Result<G
 
             required_gas
         }
-        ReceiptEnum::Data(_) | ReceiptEnum::PromiseResume(_) => 0,
+        ReceiptEnum::Data(_)
+        | ReceiptEnum::PromiseResume(_)
+        | ReceiptEnum::GlobalContractDistribution(_) => 0,
     })
 }
 
@@ -992,7 +1149,13 @@ pub(crate) fn check_actor_permissions(
     account_id: &AccountId,
 ) -> Result<(), ActionError> {
     match action {
-        Action::DeployContract(_) | Action::Stake(_) | Action::AddKey(_) | Action::DeleteKey(_) => {
+        Action::DeployContract(_)
+        | Action::DeployContractWithInit(_)
+        | Action::DeployGlobalContract(_)
+        | Action::UseGlobalContract(_)
+        | Action::Stake(_)
+        | Action::AddKey(_)
+        | Action::DeleteKey(_) => {
             if actor_id != account_id {
                 return Err(ActionErrorKind::ActorNoPermission {
                     account_id: account_id.clone(),
@@ -1099,6 +1262,9 @@ pub(crate) fn check_account_existence(
             }
         }
         Action::DeployContract(_)
+        | Action::DeployContractWithInit(_)
+        | Action::DeployGlobalContract(_)
+        | Action::UseGlobalContract(_)
         | Action::FunctionCall(_)
         | Action::Stake(_)
         | Action::AddKey(_)
@@ -1167,7 +1333,8 @@ mod tests {
     use near_primitives::congestion_info::BlockCongestionInfo;
     use near_primitives::errors::InvalidAccessKeyError;
     use near_primitives::runtime::migration_data::MigrationFlags;
-    use near_primitives::transaction::CreateAccountAction;
+    use near_primitives::transaction::{CreateAccountAction, DeployContractAction};
+    use near_primitives::test_utils::MockEpochInfoProvider;
     use near_primitives::types::{EpochId, StateChangeCause};
     use near_primitives_core::version::PROTOCOL_VERSION;
     use near_store::set_account;
@@ -1288,15 +1455,19 @@ mod tests {
             0,
             ReceiptPriority::NoPriority,
         );
+        let mut apply_state = create_apply_state(0);
+        apply_state.current_protocol_version =
+            ProtocolFeature::DeleteActionRestriction.protocol_version();
         let res = action_delete_account(
             state_update,
+            &apply_state,
             &mut account,
             &mut actor_id,
             &receipt,
             &mut action_result,
             account_id,
             &DeleteAccountAction { beneficiary_id: "bob".parse().unwrap() },
-            ProtocolFeature::DeleteActionRestriction.protocol_version(),
+            &MockEpochInfoProvider::default(),
         );
         assert!(res.is_ok());
         action_result
@@ -1337,7 +1508,7 @@ mod tests {
             &mut state_update,
             &mut account,
             &account_id,
-            &deploy_action,
+            &deploy_action.code,
             Arc::clone(&apply_state.config.wasm_config),
             None,
         );
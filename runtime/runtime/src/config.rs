@@ -10,7 +10,9 @@ use num_traits::pow::Pow;
 // Just re-exporting RuntimeConfig for backwards compatibility.
 use near_parameters::{transfer_exec_fee, transfer_send_fee, ActionCosts, RuntimeConfig};
 pub use near_primitives::num_rational::Rational32;
-use near_primitives::transaction::{Action, DeployContractAction, Transaction};
+use near_primitives::transaction::{
+    Action, DeployContractAction, DeployGlobalContractAction, Transaction,
+};
 use near_primitives::types::{AccountId, Balance, Compute, Gas};
 
 /// Describes the cost of converting this transaction into a receipt.
@@ -150,6 +152,28 @@ pub fn total_send_fees(
                         &delegate_action.receiver_id,
                     )?
             }
+            DeployContractWithInit(deploy_action) => {
+                let code_bytes = deploy_action.code.len() as u64;
+                let call_bytes = deploy_action.method_name.as_bytes().len() as u64
+                    + deploy_action.args.len() as u64;
+                fees.fee(ActionCosts::deploy_contract_with_init).send_fee(sender_is_receiver)
+                    + fees.fee(ActionCosts::deploy_contract_base).send_fee(sender_is_receiver)
+                    + fees.fee(ActionCosts::deploy_contract_byte).send_fee(sender_is_receiver)
+                        * code_bytes
+                    + fees.fee(ActionCosts::function_call_base).send_fee(sender_is_receiver)
+                    + fees.fee(ActionCosts::function_call_byte).send_fee(sender_is_receiver)
+                        * call_bytes
+            }
+            DeployGlobalContract(DeployGlobalContractAction { code }) => {
+                let num_bytes = code.len() as u64;
+                fees.fee(ActionCosts::deploy_contract_base).send_fee(sender_is_receiver)
+                    + fees.fee(ActionCosts::deploy_contract_byte).send_fee(sender_is_receiver)
+                        * num_bytes
+            }
+            // No code is written, only the account is updated.
+            UseGlobalContract(_) => {
+                fees.fee(ActionCosts::deploy_contract_base).send_fee(sender_is_receiver)
+            }
         };
         result = safe_add_gas(result, delta)?;
     }
@@ -241,6 +265,22 @@ pub fn exec_fee(config: &RuntimeConfig, action: &Action, receiver_id: &AccountId
         DeleteKey(_) => fees.fee(ActionCosts::delete_key).exec_fee(),
         DeleteAccount(_) => fees.fee(ActionCosts::delete_account).exec_fee(),
         Delegate(_) => fees.fee(ActionCosts::delegate).exec_fee(),
+        DeployContractWithInit(deploy_action) => {
+            let code_bytes = deploy_action.code.len() as u64;
+            let call_bytes =
+                deploy_action.method_name.as_bytes().len() as u64 + deploy_action.args.len() as u64;
+            fees.fee(ActionCosts::deploy_contract_with_init).exec_fee()
+                + fees.fee(ActionCosts::deploy_contract_base).exec_fee()
+                + fees.fee(ActionCosts::deploy_contract_byte).exec_fee() * code_bytes
+                + fees.fee(ActionCosts::function_call_base).exec_fee()
+                + fees.fee(ActionCosts::function_call_byte).exec_fee() * call_bytes
+        }
+        DeployGlobalContract(DeployGlobalContractAction { code }) => {
+            let num_bytes = code.len() as u64;
+            fees.fee(ActionCosts::deploy_contract_base).exec_fee()
+                + fees.fee(ActionCosts::deploy_contract_byte).exec_fee() * num_bytes
+        }
+        UseGlobalContract(_) => fees.fee(ActionCosts::deploy_contract_base).exec_fee(),
     }
 }
 
@@ -243,8 +243,8 @@ impl ReceiptSinkV2<'_> {
         state_update: &mut TrieUpdate,
         epoch_info_provider: &dyn EpochInfoProvider,
     ) -> Result<(), RuntimeError> {
-        let shard = epoch_info_provider
-            .account_id_to_shard_id(receipt.receiver_id(), &apply_state.epoch_id)?;
+        let shard_layout = epoch_info_provider.shard_layout(&apply_state.epoch_id)?;
+        let shard = receipt.receiver_shard_id(&shard_layout);
 
         let size = compute_receipt_size(&receipt)?;
         let gas = compute_receipt_congestion_gas(&receipt, &apply_state.config)?;
@@ -410,6 +410,11 @@ pub(crate) fn compute_receipt_congestion_gas(
             // of it without expensive state lookups.
             Ok(0)
         }
+        ReceiptEnum::GlobalContractDistribution(_) => {
+            // Distributing a global contract doesn't execute anything, the cost of
+            // storing the code was burnt when it was published.
+            Ok(0)
+        }
     }
 }
 
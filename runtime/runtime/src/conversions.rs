@@ -94,3 +94,53 @@ mod profile_data_v3 {
         }
     }
 }
+
+mod execution_trace {
+    use near_primitives::views::{
+        ExecutionTraceArgView, ExecutionTraceEventView, ExecutionTraceStepView,
+    };
+    use near_vm_runner::logic::{TraceArg, TraceEvent, TraceStep};
+
+    impl super::Convert<TraceStep> for ExecutionTraceStepView {
+        fn convert(step: TraceStep) -> Self {
+            let (result, error) = match step.result {
+                Ok(value) => (value, None),
+                Err(err) => (None, Some(err)),
+            };
+            Self {
+                op: step.host_function.to_string(),
+                args: step.args.into_iter().map(super::Convert::convert).collect(),
+                gas: step.gas,
+                gas_cost: step.gas_cost,
+                result,
+                error,
+                events: step.events.into_iter().map(super::Convert::convert).collect(),
+            }
+        }
+    }
+
+    impl super::Convert<TraceArg> for ExecutionTraceArgView {
+        fn convert(arg: TraceArg) -> Self {
+            match arg {
+                TraceArg::Value(value) => Self::Value(value),
+                TraceArg::Bytes(bytes) => Self::Bytes(bytes),
+                TraceArg::Balance(balance) => Self::Balance(balance),
+            }
+        }
+    }
+
+    impl super::Convert<TraceEvent> for ExecutionTraceEventView {
+        fn convert(event: TraceEvent) -> Self {
+            match event {
+                TraceEvent::StorageRead { key, value } => Self::StorageRead { key, value },
+                TraceEvent::StorageWrite { key, value, evicted } => {
+                    Self::StorageWrite { key, value, evicted }
+                }
+                TraceEvent::StorageRemove { key, removed } => Self::StorageRemove { key, removed },
+                TraceEvent::PromiseCreated { promise_index, receiver_id } => {
+                    Self::PromiseCreated { promise_index, receiver_id }
+                }
+            }
+        }
+    }
+}
@@ -13,7 +13,7 @@ use near_store::contract::ContractStorage;
 use near_store::{has_promise_yield_receipt, KeyLookupMode, TrieUpdate, TrieUpdateValuePtr};
 use near_vm_runner::logic::errors::{AnyError, VMLogicError};
 use near_vm_runner::logic::types::ReceiptIndex;
-use near_vm_runner::logic::{External, StorageGetMode, ValuePtr};
+use near_vm_runner::logic::{External, StorageGetMode, StorageIterKeys, ValuePtr};
 use near_vm_runner::{Contract, ContractCode};
 use near_wallet_contract::wallet_contract;
 use std::sync::Arc;
@@ -159,6 +159,39 @@ impl<'a> External for RuntimeExt<'a> {
             .map_err(wrap_storage_error)
     }
 
+    fn storage_iter_keys(
+        &self,
+        prefix: &[u8],
+        start: &[u8],
+        limit: u64,
+    ) -> ExtResult<StorageIterKeys> {
+        let raw_prefix =
+            trie_key_parsers::get_raw_prefix_for_contract_data(&self.account_id, prefix);
+        let raw_start = trie_key_parsers::get_raw_prefix_for_contract_data(&self.account_id, start);
+        // Iterates over the memtrie if it is loaded. Either way the visited nodes are recorded
+        // for the state witness, but not counted as touched trie nodes.
+        let lock = self.trie_update.trie().lock_for_iter();
+        let mut iter = self
+            .trie_update
+            .locked_iter_from(&raw_prefix, &raw_start, &lock)
+            .map_err(wrap_storage_error)?;
+        let keys = iter
+            .by_ref()
+            .take(usize::try_from(limit).unwrap_or(usize::MAX))
+            .map(|raw_key| {
+                trie_key_parsers::parse_data_key_from_contract_data_key(&raw_key?, &self.account_id)
+                    .map_err(|_e| {
+                        StorageError::StorageInconsistentState(
+                            "Can't parse data key from raw key for ContractData".to_string(),
+                        )
+                    })
+                    .map(Vec::from)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(wrap_storage_error)?;
+        Ok(StorageIterKeys { keys, visited: iter.visited_keys() })
+    }
+
     fn storage_remove_subtree(&mut self, prefix: &[u8]) -> ExtResult<()> {
         let data_keys = self
             .trie_update
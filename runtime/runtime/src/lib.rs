@@ -30,13 +30,17 @@ use near_primitives::errors::{
 };
 use near_primitives::hash::CryptoHash;
 use near_primitives::receipt::{
-    ActionReceipt, DataReceipt, DelayedReceiptIndices, PromiseYieldIndices, PromiseYieldTimeout,
-    Receipt, ReceiptEnum, ReceiptOrStateStoredReceipt, ReceiptV0, ReceivedData,
+    ActionReceipt, DataReceipt, DelayedReceiptIndices, GlobalContractDistributionReceipt,
+    PromiseYieldIndices, PromiseYieldTimeout, Receipt, ReceiptEnum, ReceiptOrStateStoredReceipt,
+    ReceiptV0, ReceivedData,
 };
 use near_primitives::runtime::migration_data::{MigrationData, MigrationFlags};
 use near_primitives::sandbox::state_patch::SandboxStatePatch;
 use near_primitives::state_record::StateRecord;
 use near_primitives::stateless_validation::contract_distribution::ContractUpdates;
+use near_primitives::stateless_validation::witness_size_attribution::{
+    StorageProofContribution, StorageProofSizeAttribution, StorageProofSource,
+};
 #[cfg(feature = "protocol_feature_nonrefundable_transfer_nep491")]
 use near_primitives::transaction::NonrefundableStorageTransferAction;
 use near_primitives::transaction::{
@@ -54,6 +58,7 @@ use near_primitives::utils::{
     create_receipt_id_from_transaction,
 };
 use near_primitives::version::{ProtocolFeature, ProtocolVersion};
+use near_primitives::views::{FunctionCallTraceView, ReceiptExecutionTraceView};
 use near_primitives_core::apply::ApplyChunkReason;
 use near_store::trie::receipts_column_helper::DelayedReceiptQueue;
 use near_store::trie::update::TrieUpdateResult;
@@ -88,6 +93,7 @@ mod metrics;
 mod pipelining;
 mod prefetch;
 pub mod receipt_manager;
+pub mod simulation;
 pub mod state_viewer;
 #[cfg(test)]
 mod tests;
@@ -196,6 +202,8 @@ pub struct ApplyResult {
     pub processed_delayed_receipts: Vec<Receipt>,
     pub processed_yield_timeouts: Vec<PromiseYieldTimeout>,
     pub proof: Option<PartialStorage>,
+    /// Which transactions and receipts recorded the `proof`, present whenever the `proof` is.
+    pub storage_proof_size_attribution: Option<StorageProofSizeAttribution>,
     pub delayed_receipts_count: u64,
     pub metrics: Option<metrics::ApplyMetrics>,
     pub congestion_info: Option<CongestionInfo>,
@@ -204,6 +212,9 @@ pub struct ApplyResult {
     pub bandwidth_scheduler_state_hash: CryptoHash,
     /// Contracts accessed and deployed while applying the chunk.
     pub contract_updates: ContractUpdates,
+    /// Step-level traces of the executed action receipts. Only collected when
+    /// `execution_tracing` is enabled in the VM config.
+    pub receipt_traces: Vec<ReceiptExecutionTraceView>,
 }
 
 #[derive(Debug)]
@@ -217,6 +228,8 @@ pub struct ActionResult {
     pub new_receipts: Vec<Receipt>,
     pub validator_proposals: Vec<ValidatorStake>,
     pub profile: Box<ProfileDataV3>,
+    /// Traces of the executed function calls, if `execution_tracing` is enabled.
+    pub function_call_traces: Vec<FunctionCallTraceView>,
 }
 
 impl ActionResult {
@@ -238,6 +251,7 @@ impl ActionResult {
         self.profile.merge(&next_result.profile);
         self.result = next_result.result;
         self.logs.append(&mut next_result.logs);
+        self.function_call_traces.append(&mut next_result.function_call_traces);
         if let Ok(ReturnData::ReceiptIndex(ref mut receipt_index)) = self.result {
             // Shifting local receipt index to be global receipt index.
             *receipt_index += self.new_receipts.len() as u64;
@@ -265,6 +279,7 @@ impl Default for ActionResult {
             new_receipts: vec![],
             validator_proposals: vec![],
             profile: Default::default(),
+            function_call_traces: vec![],
         }
     }
 }
@@ -299,6 +314,8 @@ impl Runtime {
     /// `ExecutionOutcomeWithId` for the transaction.
     /// In case of an error, returns either `InvalidTxError` if the transaction verification failed
     /// or a `StorageError` wrapped into `RuntimeError`.
+    /// The signature is only checked if `verify_signature` is set, which is always the case when
+    /// applying chunks.
     #[instrument(target = "runtime", level = "debug", "process_transaction", skip_all, fields(
         tx_hash = %signed_transaction.get_hash(),
         gas_burnt = tracing::field::Empty,
@@ -309,6 +326,7 @@ impl Runtime {
         state_update: &mut TrieUpdate,
         apply_state: &ApplyState,
         signed_transaction: &SignedTransaction,
+        verify_signature: bool,
         stats: &mut ApplyStats,
     ) -> Result<(Receipt, ExecutionOutcomeWithId), InvalidTxError> {
         let span = tracing::Span::current();
@@ -319,7 +337,7 @@ impl Runtime {
             state_update,
             apply_state.gas_price,
             signed_transaction,
-            true,
+            verify_signature,
             Some(apply_state.block_height),
             apply_state.current_protocol_version,
         ) {
@@ -451,11 +469,69 @@ impl Runtime {
                     state_update,
                     account.as_mut().expect(EXPECT_ACCOUNT_EXISTS),
                     account_id,
-                    deploy_contract,
+                    &deploy_contract.code,
                     Arc::clone(&apply_state.config.wasm_config),
                     apply_state.cache.as_deref(),
                 )?;
             }
+            Action::DeployContractWithInit(deploy_contract) => {
+                let account = account.as_mut().expect(EXPECT_ACCOUNT_EXISTS);
+                action_deploy_contract(
+                    state_update,
+                    account,
+                    account_id,
+                    &deploy_contract.code,
+                    Arc::clone(&apply_state.config.wasm_config),
+                    apply_state.cache.as_deref(),
+                )?;
+                // The init method runs on the new code. If it fails, the whole receipt fails and
+                // the deployment is reverted together with the rest of its state changes.
+                let contract = preparation_pipeline.get_contract(
+                    receipt,
+                    account.code_hash(),
+                    action_index,
+                    None,
+                );
+                let is_last_action = action_index + 1 == actions.len();
+                action_function_call(
+                    state_update,
+                    apply_state,
+                    account,
+                    receipt,
+                    action_receipt,
+                    promise_results,
+                    &mut result,
+                    account_id,
+                    &deploy_contract.function_call(),
+                    action_hash,
+                    account.code_hash(),
+                    &apply_state.config,
+                    action_index,
+                    is_last_action,
+                    epoch_info_provider,
+                    contract,
+                )?;
+            }
+            Action::DeployGlobalContract(deploy_contract) => {
+                action_deploy_global_contract(
+                    state_update,
+                    apply_state,
+                    account.as_mut().expect(EXPECT_ACCOUNT_EXISTS),
+                    account_id,
+                    &deploy_contract.code,
+                    &mut result,
+                    epoch_info_provider,
+                )?;
+            }
+            Action::UseGlobalContract(use_contract) => {
+                action_use_global_contract(
+                    state_update,
+                    account.as_mut().expect(EXPECT_ACCOUNT_EXISTS),
+                    account_id,
+                    use_contract.code_hash,
+                    &mut result,
+                )?;
+            }
             Action::FunctionCall(function_call) => {
                 let account = account.as_mut().expect(EXPECT_ACCOUNT_EXISTS);
                 let contract = preparation_pipeline.get_contract(
@@ -478,6 +554,7 @@ impl Runtime {
                     action_hash,
                     account.code_hash(),
                     &apply_state.config,
+                    action_index,
                     is_last_action,
                     epoch_info_provider,
                     contract,
@@ -548,13 +625,14 @@ impl Runtime {
             Action::DeleteAccount(delete_account) => {
                 action_delete_account(
                     state_update,
+                    apply_state,
                     account,
                     actor_id,
                     receipt,
                     &mut result,
                     account_id,
                     delete_account,
-                    apply_state.current_protocol_version,
+                    epoch_info_provider,
                 )?;
             }
             Action::Delegate(signed_delegate_action) => {
@@ -582,6 +660,7 @@ impl Runtime {
         receipt_sink: &mut ReceiptSink,
         validator_proposals: &mut Vec<ValidatorStake>,
         stats: &mut ApplyStats,
+        receipt_traces: &mut Vec<ReceiptExecutionTraceView>,
         epoch_info_provider: &(dyn EpochInfoProvider),
     ) -> Result<ExecutionOutcomeWithId, RuntimeError> {
         let _span = tracing::debug_span!(
@@ -776,6 +855,16 @@ impl Runtime {
         // If the receipt is a refund, then we consider it free without burnt gas.
         let gas_burnt: Gas =
             if receipt.predecessor_id().is_system() { 0 } else { result.gas_burnt };
+        if apply_state.config.wasm_config.execution_tracing {
+            receipt_traces.push(ReceiptExecutionTraceView {
+                receipt_id: *receipt.receipt_id(),
+                predecessor_id: receipt.predecessor_id().clone(),
+                receiver_id: account_id.clone(),
+                gas_burnt,
+                failed: result.result.is_err(),
+                function_calls: std::mem::take(&mut result.function_call_traces),
+            });
+        }
         // `gas_deficit_amount` is strictly less than `gas_price * gas_burnt`.
         let mut tx_burnt_amount =
             safe_gas_to_balance(apply_state.gas_price, gas_burnt)? - gas_deficit_amount;
@@ -1007,6 +1096,7 @@ impl Runtime {
             epoch_info_provider,
             ref pipeline_manager,
             ref mut stats,
+            ref mut receipt_traces,
             ..
         } = *processing_state;
         let account_id = receipt.receiver_id();
@@ -1076,6 +1166,7 @@ impl Runtime {
                                 receipt_sink,
                                 validator_proposals,
                                 stats,
+                                receipt_traces,
                                 epoch_info_provider,
                             )
                             .map(Some);
@@ -1131,6 +1222,7 @@ impl Runtime {
                             receipt_sink,
                             validator_proposals,
                             stats,
+                            receipt_traces,
                             epoch_info_provider,
                         )
                         .map(Some);
@@ -1183,6 +1275,7 @@ impl Runtime {
                             receipt_sink,
                             validator_proposals,
                             stats,
+                            receipt_traces,
                             epoch_info_provider,
                         )
                         .map(Some);
@@ -1193,6 +1286,25 @@ impl Runtime {
                     return Ok(None);
                 }
             }
+            ReceiptEnum::GlobalContractDistribution(ref distribution_receipt) => {
+                // Received a change of the global contract registry made by the predecessor.
+                apply_global_contract_change(
+                    state_update,
+                    receipt.predecessor_id(),
+                    &distribution_receipt.change,
+                )?;
+                self.forward_global_contract_distribution(
+                    state_update,
+                    apply_state,
+                    receipt,
+                    distribution_receipt,
+                    receipt_sink,
+                    epoch_info_provider,
+                )?;
+                state_update
+                    .commit(StateChangeCause::ReceiptProcessing { receipt_hash: receipt.get_hash() });
+                return Ok(None);
+            }
         };
         // We didn't trigger execution, so we need to commit the state.
         state_update
@@ -1200,6 +1312,55 @@ impl Runtime {
         Ok(None)
     }
 
+    /// Forwards the change of the global contract registry to the other children of the target
+    /// shard, if it was split since the receipt was sent.
+    fn forward_global_contract_distribution(
+        &self,
+        state_update: &mut TrieUpdate,
+        apply_state: &ApplyState,
+        receipt: &Receipt,
+        distribution_receipt: &GlobalContractDistributionReceipt,
+        receipt_sink: &mut ReceiptSink,
+        epoch_info_provider: &dyn EpochInfoProvider,
+    ) -> Result<(), RuntimeError> {
+        if distribution_receipt.target_shard == apply_state.shard_id {
+            return Ok(());
+        }
+        let shard_layout = epoch_info_provider.shard_layout(&apply_state.epoch_id)?;
+        let Some(children) = shard_layout.get_children_shards_ids(distribution_receipt.target_shard)
+        else {
+            return Ok(());
+        };
+        let mut already_delivered_shards = distribution_receipt.already_delivered_shards.clone();
+        already_delivered_shards.push(apply_state.shard_id);
+        let target_shards: Vec<_> = children
+            .into_iter()
+            .filter(|shard_id| !already_delivered_shards.contains(shard_id))
+            .collect();
+        for (receipt_index, target_shard) in target_shards.into_iter().enumerate() {
+            let mut new_receipt = Receipt::new_global_contract_distribution(
+                receipt.predecessor_id().clone(),
+                target_shard,
+                already_delivered_shards.clone(),
+                distribution_receipt.change.clone(),
+            );
+            new_receipt.set_receipt_id(create_receipt_id_from_receipt_id(
+                apply_state.current_protocol_version,
+                receipt.receipt_id(),
+                &apply_state.prev_block_hash,
+                &apply_state.block_hash,
+                receipt_index,
+            ));
+            receipt_sink.forward_or_buffer_receipt(
+                new_receipt,
+                apply_state,
+                state_update,
+                epoch_info_provider,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Iterates over the validators in the current shard and updates their accounts to return stake
     /// and allocate rewards. Also updates protocol treasury account if it belongs to the current
     /// shard.
@@ -1566,10 +1727,14 @@ impl Runtime {
         let apply_state = &mut processing_state.apply_state;
         let state_update = &mut processing_state.state_update;
         for signed_transaction in processing_state.transactions {
+            let recorded_storage_size_before = state_update.trie().recorded_storage_size();
+            let storage_proof_size_upper_bound_before =
+                state_update.trie().recorded_storage_size_upper_bound();
             let (receipt, outcome_with_id) = self.process_transaction(
                 state_update,
                 apply_state,
                 signed_transaction,
+                true,
                 &mut processing_state.stats,
             )?;
             if receipt.receiver_id() == signed_transaction.transaction.signer_id() {
@@ -1582,6 +1747,14 @@ impl Runtime {
                     processing_state.epoch_info_provider,
                 )?;
             }
+            record_storage_proof_contribution(
+                &mut processing_state.storage_proof_contributions,
+                state_update,
+                StorageProofSource::Transaction(signed_transaction.get_hash()),
+                signed_transaction.transaction.signer_id(),
+                recorded_storage_size_before,
+                storage_proof_size_upper_bound_before,
+            );
             let compute = outcome_with_id.outcome.compute_usage;
             let compute = compute.expect("`process_transaction` must populate compute usage");
             total.add(outcome_with_id.outcome.gas_burnt, compute)?;
@@ -1639,6 +1812,14 @@ impl Runtime {
             .recorded_storage_size_upper_bound()
             .saturating_sub(storage_proof_size_upper_bound_before)
             as f64;
+        record_storage_proof_contribution(
+            &mut processing_state.storage_proof_contributions,
+            state_update,
+            StorageProofSource::Receipt(*receipt.receipt_id()),
+            receipt.receiver_id(),
+            recorded_storage_size_before,
+            storage_proof_size_upper_bound_before,
+        );
         let shard_id_str = processing_state.apply_state.shard_id.to_string();
         metrics::RECEIPT_RECORDED_SIZE
             .with_label_values(&[shard_id_str.as_str()])
@@ -2120,6 +2301,13 @@ impl Runtime {
             .observe(chunk_recorded_size_upper_bound / f64::max(1.0, chunk_recorded_size));
         metrics::report_recorded_column_sizes(&trie, &apply_state);
         let proof = trie.recorded_storage();
+        let storage_proof_size_attribution = proof.as_ref().map(|_| StorageProofSizeAttribution {
+            contributions: processing_state.storage_proof_contributions,
+            total_size: chunk_recorded_size as u64,
+            total_size_upper_bound: chunk_recorded_size_upper_bound as u64,
+            size_soft_limit: apply_state.config.witness_config.main_storage_proof_size_soft_limit
+                as u64,
+        });
         let processed_delayed_receipts = process_receipts_result.processed_delayed_receipts;
         let processed_yield_timeouts = promise_yield_result.processed_yield_timeouts;
         Ok(ApplyResult {
@@ -2133,6 +2321,7 @@ impl Runtime {
             processed_delayed_receipts,
             processed_yield_timeouts,
             proof,
+            storage_proof_size_attribution,
             delayed_receipts_count,
             metrics: Some(processing_state.metrics),
             congestion_info: own_congestion_info,
@@ -2142,6 +2331,7 @@ impl Runtime {
                 .map(|o| o.scheduler_state_hash)
                 .unwrap_or_default(),
             contract_updates,
+            receipt_traces: processing_state.receipt_traces,
         })
     }
 }
@@ -2224,6 +2414,32 @@ fn action_transfer_or_implicit_account_creation(
     })
 }
 
+/// Attributes the storage proof recorded since the given sizes to a transaction or receipt.
+/// Nothing is attributed when the trie isn't recording.
+fn record_storage_proof_contribution(
+    contributions: &mut Vec<StorageProofContribution>,
+    state_update: &TrieUpdate,
+    source: StorageProofSource,
+    account_id: &AccountId,
+    recorded_storage_size_before: usize,
+    storage_proof_size_upper_bound_before: usize,
+) {
+    let trie = state_update.trie();
+    let recorded_size = trie.recorded_storage_size().saturating_sub(recorded_storage_size_before);
+    let recorded_size_upper_bound = trie
+        .recorded_storage_size_upper_bound()
+        .saturating_sub(storage_proof_size_upper_bound_before);
+    if recorded_size_upper_bound == 0 {
+        return;
+    }
+    contributions.push(StorageProofContribution {
+        source,
+        account_id: account_id.clone(),
+        recorded_size: recorded_size as u64,
+        recorded_size_upper_bound: recorded_size_upper_bound as u64,
+    });
+}
+
 fn missing_chunk_apply_result(
     delayed_receipts: &DelayedReceiptQueueWrapper,
     processing_state: ApplyProcessingState,
@@ -2231,7 +2447,18 @@ fn missing_chunk_apply_result(
 ) -> Result<ApplyResult, RuntimeError> {
     let TrieUpdateResult { trie, trie_changes, state_changes, contract_updates } =
         processing_state.state_update.finalize()?;
+    let storage_proof_size_attribution = StorageProofSizeAttribution {
+        contributions: vec![],
+        total_size: trie.recorded_storage_size() as u64,
+        total_size_upper_bound: trie.recorded_storage_size_upper_bound() as u64,
+        size_soft_limit: processing_state
+            .apply_state
+            .config
+            .witness_config
+            .main_storage_proof_size_soft_limit as u64,
+    };
     let proof = trie.recorded_storage();
+    let storage_proof_size_attribution = proof.as_ref().map(|_| storage_proof_size_attribution);
 
     // For old chunks, copy the congestion info exactly as it came in,
     // potentially returning `None` even if the congestion control
@@ -2262,6 +2489,7 @@ fn missing_chunk_apply_result(
         processed_delayed_receipts: vec![],
         processed_yield_timeouts: vec![],
         proof,
+        storage_proof_size_attribution,
         delayed_receipts_count: delayed_receipts.len(),
         metrics: None,
         congestion_info,
@@ -2271,6 +2499,7 @@ fn missing_chunk_apply_result(
             .map(|o| o.scheduler_state_hash)
             .unwrap_or_default(),
         contract_updates,
+        receipt_traces: vec![],
     });
 }
 
@@ -2474,6 +2703,8 @@ impl<'a> ApplyProcessingState<'a> {
             total: self.total,
             stats: self.stats,
             outcomes: Vec::new(),
+            receipt_traces: Vec::new(),
+            storage_proof_contributions: Vec::new(),
             metrics: metrics::ApplyMetrics::default(),
             local_receipts: VecDeque::new(),
             incoming_receipts,
@@ -2494,6 +2725,8 @@ struct ApplyProcessingReceiptState<'a> {
     total: TotalResourceGuard,
     stats: ApplyStats,
     outcomes: Vec<ExecutionOutcomeWithId>,
+    receipt_traces: Vec<ReceiptExecutionTraceView>,
+    storage_proof_contributions: Vec<StorageProofContribution>,
     metrics: ApplyMetrics,
     local_receipts: VecDeque<Receipt>,
     incoming_receipts: &'a [Receipt],
@@ -2603,6 +2836,7 @@ fn schedule_contract_preparation<'b, R: MaybeRefReceipt>(
                     };
                     return handle_receipt(mgr, state_update, receiver, account_id, &yr);
                 }
+                ReceiptEnum::GlobalContractDistribution(_) => false,
             }
         }
         handle_receipt(pipeline_manager, state_update, &receiver, account_id, peek)
@@ -2660,6 +2894,7 @@ pub mod estimator {
             &mut receipt_sink,
             validator_proposals,
             stats,
+            &mut Vec::new(),
             epoch_info_provider,
         )
     }
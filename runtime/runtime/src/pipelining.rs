@@ -120,13 +120,17 @@ impl ReceiptPreparationPipeline {
         }
         let actions = match receipt.receipt() {
             ReceiptEnum::Action(a) | ReceiptEnum::PromiseYield(a) => &a.actions,
-            ReceiptEnum::Data(_) | ReceiptEnum::PromiseResume(_) => return false,
+            ReceiptEnum::Data(_)
+            | ReceiptEnum::PromiseResume(_)
+            | ReceiptEnum::GlobalContractDistribution(_) => return false,
         };
         let mut any_function_calls = false;
         for (action_index, action) in actions.iter().enumerate() {
             let account_id = account_id.clone();
             match action {
-                Action::DeployContract(_) => {
+                Action::DeployContract(_)
+                | Action::DeployContractWithInit(_)
+                | Action::UseGlobalContract(_) => {
                     // FIXME: instead of blocking these accounts, move the handling of
                     // deploy action into here, so that the necessary data dependencies can be
                     // established.
@@ -183,7 +187,8 @@ impl ReceiptPreparationPipeline {
                 // No need to handle this receipt as it only generates other new receipts.
                 Action::Delegate(_) => {}
                 // No handling for these.
-                Action::CreateAccount(_)
+                Action::DeployGlobalContract(_)
+                | Action::CreateAccount(_)
                 | Action::Transfer(_)
                 | Action::Stake(_)
                 | Action::AddKey(_)
@@ -217,17 +222,23 @@ impl ReceiptPreparationPipeline {
                 .actions
                 .get(action_index)
                 .expect("indexing receipt actions by an action_index failed!"),
-            ReceiptEnum::Data(_) | ReceiptEnum::PromiseResume(_) => {
+            ReceiptEnum::Data(_)
+            | ReceiptEnum::PromiseResume(_)
+            | ReceiptEnum::GlobalContractDistribution(_) => {
                 panic!("attempting to get_contract with a non-action receipt!?")
             }
         };
-        let Action::FunctionCall(function_call) = action else {
-            panic!("referenced receipt action is not a function call!");
+        let (gas, method_name) = match action {
+            Action::FunctionCall(function_call) => (function_call.gas, &function_call.method_name),
+            Action::DeployContractWithInit(deploy_contract) => {
+                (deploy_contract.gas, &deploy_contract.method_name)
+            }
+            _ => panic!("referenced receipt action is not a function call!"),
         };
         let key = PrepareTaskKey { receipt_id: receipt.get_hash(), action_index };
         let Some(task) = self.map.get(&key) else {
             let start = Instant::now();
-            let gas_counter = self.gas_counter(view_config.as_ref(), function_call.gas);
+            let gas_counter = self.gas_counter(view_config.as_ref(), gas);
             if !self.block_accounts.contains(account_id) {
                 tracing::debug!(
                     target: "runtime::pipelining",
@@ -244,7 +255,7 @@ impl ReceiptPreparationPipeline {
                 gas_counter,
                 code_hash,
                 &account_id,
-                &function_call.method_name,
+                method_name,
             );
             PIPELINING_ACTIONS_NOT_SUBMITTED.inc_by(1);
             PIPELINING_ACTIONS_MAIN_THREAD_WORKING_TIME.inc_by(start.elapsed().as_secs_f64());
@@ -264,9 +275,8 @@ impl ReceiptPreparationPipeline {
                         receipt=%receipt.get_hash(),
                         action_index
                     );
-                    let gas_counter = self.gas_counter(view_config.as_ref(), function_call.gas);
+                    let gas_counter = self.gas_counter(view_config.as_ref(), gas);
                     let cache = self.contract_cache.as_ref().map(|c| c.handle());
-                    let method_name = function_call.method_name.clone();
                     let contract = prepare_function_call(
                         &self.storage,
                         cache.as_deref(),
@@ -275,7 +285,7 @@ impl ReceiptPreparationPipeline {
                         gas_counter,
                         code_hash,
                         &account_id,
-                        &method_name,
+                        method_name,
                     );
                     PIPELINING_ACTIONS_PREPARED_IN_MAIN_THREAD.inc_by(1);
                     PIPELINING_ACTIONS_MAIN_THREAD_WORKING_TIME
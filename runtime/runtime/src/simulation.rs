@@ -0,0 +1,142 @@
+//! Dry runs of a transaction together with all the receipts it produces, used by the
+//! `EXPERIMENTAL_simulate_tx` RPC method.
+
+use crate::congestion_control::{DelayedReceiptQueueWrapper, ReceiptSink, ReceiptSinkV1};
+use crate::{ApplyProcessingReceiptState, ApplyProcessingState, ApplyState, Runtime};
+use near_primitives::errors::{InvalidTxError, RuntimeError};
+use near_primitives::transaction::{ExecutionOutcomeWithId, SignedTransaction};
+use near_primitives::types::{EpochInfoProvider, RawStateChangesWithTrieKey, ShardId};
+use near_store::trie::receipts_column_helper::DelayedReceiptQueue;
+use near_store::{get_access_key, set_access_key, Trie};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+/// Outcome of [`Runtime::simulate_transaction`].
+#[derive(Debug)]
+pub struct TransactionSimulation {
+    pub transaction_outcome: ExecutionOutcomeWithId,
+    /// Outcomes of the executed receipts, each one after the outcome of the receipt or the
+    /// transaction that created it.
+    pub receipt_outcomes: Vec<ExecutionOutcomeWithId>,
+    /// Changes to the state of all the shards touched by the transaction and its receipts.
+    pub state_changes: Vec<RawStateChangesWithTrieKey>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SimulationError {
+    #[error("transaction is invalid: {0:?}")]
+    InvalidTransaction(InvalidTxError),
+    #[error("state of shard {0} is not available")]
+    UnavailableShard(ShardId),
+    #[error(transparent)]
+    RuntimeError(#[from] RuntimeError),
+}
+
+impl Runtime {
+    /// Applies the transaction, and then every receipt it produces until none are left, on top
+    /// of the given tries of each shard. Nothing is persisted, the changes are returned instead.
+    ///
+    /// Receipts are executed one at a time as soon as they are produced, without any gas or
+    /// congestion limits, so the outcomes match the chain only as long as the touched state is
+    /// not modified by other transactions in the meantime. Receipts waiting for data that never
+    /// arrives, e.g. a yielded promise, are left unexecuted. `apply_state.shard_id` must be the
+    /// shard of the signer, it is only used for metrics.
+    ///
+    /// If `check_nonce` is unset, the access key nonce is overridden so that the transaction
+    /// nonce is the next one expected. The nonce must still be positive and below the upper
+    /// bound given by the block height.
+    pub fn simulate_transaction(
+        &self,
+        mut tries: HashMap<ShardId, Trie>,
+        apply_state: &ApplyState,
+        signed_transaction: &SignedTransaction,
+        verify_signature: bool,
+        check_nonce: bool,
+        epoch_info_provider: &(dyn EpochInfoProvider),
+    ) -> Result<TransactionSimulation, SimulationError> {
+        let mut shards = HashMap::new();
+        let transaction = &signed_transaction.transaction;
+        let shard_id = epoch_info_provider
+            .account_id_to_shard_id(transaction.signer_id(), &apply_state.epoch_id)
+            .map_err(RuntimeError::from)?;
+        let state =
+            processing_state(&mut shards, &mut tries, apply_state, epoch_info_provider, shard_id)?;
+        if !check_nonce {
+            let (signer_id, public_key) = (transaction.signer_id(), transaction.public_key());
+            if let Some(mut access_key) = get_access_key(&state.state_update, signer_id, public_key)
+                .map_err(RuntimeError::from)?
+            {
+                access_key.nonce = transaction.nonce().saturating_sub(1);
+                set_access_key(
+                    &mut state.state_update,
+                    signer_id.clone(),
+                    public_key.clone(),
+                    &access_key,
+                );
+            }
+        }
+        let (receipt, transaction_outcome) = self
+            .process_transaction(
+                &mut state.state_update,
+                apply_state,
+                signed_transaction,
+                verify_signature,
+                &mut state.stats,
+            )
+            .map_err(SimulationError::InvalidTransaction)?;
+
+        let mut receipt_outcomes = Vec::new();
+        let mut receipts = VecDeque::from([receipt]);
+        while let Some(receipt) = receipts.pop_front() {
+            let shard_layout = epoch_info_provider
+                .shard_layout(&apply_state.epoch_id)
+                .map_err(RuntimeError::from)?;
+            let shard_id = receipt.receiver_shard_id(&shard_layout);
+            let state = processing_state(
+                &mut shards,
+                &mut tries,
+                apply_state,
+                epoch_info_provider,
+                shard_id,
+            )?;
+            let mut outgoing_receipts = Vec::new();
+            let mut receipt_sink =
+                ReceiptSink::V1(ReceiptSinkV1 { outgoing_receipts: &mut outgoing_receipts });
+            if let Some(outcome) =
+                self.process_receipt(state, &receipt, &mut receipt_sink, &mut Vec::new())?
+            {
+                receipt_outcomes.push(outcome);
+            }
+            receipts.extend(outgoing_receipts);
+        }
+
+        let mut state_changes = Vec::new();
+        for (_, state) in shards {
+            let result = state.state_update.finalize().map_err(RuntimeError::from)?;
+            state_changes.extend(result.state_changes);
+        }
+        Ok(TransactionSimulation { transaction_outcome, receipt_outcomes, state_changes })
+    }
+}
+
+/// Returns the processing state of the shard, creating it from its trie on first use.
+fn processing_state<'a, 'b>(
+    shards: &'a mut HashMap<ShardId, ApplyProcessingReceiptState<'b>>,
+    tries: &mut HashMap<ShardId, Trie>,
+    apply_state: &'b ApplyState,
+    epoch_info_provider: &'b (dyn EpochInfoProvider),
+    shard_id: ShardId,
+) -> Result<&'a mut ApplyProcessingReceiptState<'b>, SimulationError> {
+    match shards.entry(shard_id) {
+        Entry::Occupied(entry) => Ok(entry.into_mut()),
+        Entry::Vacant(entry) => {
+            let trie =
+                tries.remove(&shard_id).ok_or(SimulationError::UnavailableShard(shard_id))?;
+            let state = ApplyProcessingState::new(apply_state, trie, epoch_info_provider, &[]);
+            let delayed_receipts =
+                DelayedReceiptQueue::load(&state.state_update).map_err(RuntimeError::from)?;
+            let delayed_receipts = DelayedReceiptQueueWrapper::new(delayed_receipts);
+            Ok(entry.insert(state.into_processing_receipt_state(&[], delayed_receipts)))
+        }
+    }
+}
@@ -4,7 +4,7 @@ use crate::pipelining::ReceiptPreparationPipeline;
 use crate::receipt_manager::ReceiptManager;
 use crate::ApplyState;
 use near_crypto::{KeyType, PublicKey};
-use near_parameters::RuntimeConfigStore;
+use near_parameters::{RuntimeConfig, RuntimeConfigStore};
 use near_primitives::account::{AccessKey, Account};
 use near_primitives::bandwidth_scheduler::BlockBandwidthRequests;
 use near_primitives::borsh::BorshDeserialize;
@@ -19,7 +19,7 @@ use near_primitives::types::{
 use near_primitives::version::PROTOCOL_VERSION;
 use near_primitives::views::{StateItem, ViewStateResult};
 use near_primitives_core::config::ViewConfig;
-use near_store::{get_access_key, get_account, get_code, TrieUpdate};
+use near_store::{get_access_key, get_account, get_account_code, get_code, TrieUpdate};
 use near_vm_runner::logic::{ProtocolVersion, ReturnData};
 use near_vm_runner::{ContractCode, ContractRuntimeCache};
 use std::{str, sync::Arc, time::Instant};
@@ -91,7 +91,7 @@ impl TrieViewer {
         account_id: &AccountId,
     ) -> Result<ContractCode, errors::ViewContractCodeError> {
         let account = self.view_account(state_update, account_id)?;
-        get_code(state_update, account_id, Some(account.code_hash()))?.ok_or_else(|| {
+        get_account_code(state_update, account_id, account.code_hash())?.ok_or_else(|| {
             errors::ViewContractCodeError::NoContractCode {
                 contract_account_id: account_id.clone(),
             }
@@ -188,6 +188,7 @@ impl TrieViewer {
         method_name: &str,
         args: &[u8],
         logs: &mut Vec<String>,
+        function_gas_profile: Option<&mut String>,
         epoch_info_provider: &(dyn EpochInfoProvider),
     ) -> Result<Vec<u8>, errors::CallFunctionError> {
         let now = Instant::now();
@@ -202,8 +203,16 @@ impl TrieViewer {
         let public_key = PublicKey::empty(KeyType::ED25519);
         let empty_hash = CryptoHash::default();
         let mut receipt_manager = ReceiptManager::default();
+        let code_hash = account.code_hash();
         let config_store = RuntimeConfigStore::new(None);
-        let config = config_store.get_config(PROTOCOL_VERSION);
+        let mut config = Arc::clone(config_store.get_config(PROTOCOL_VERSION));
+        if function_gas_profile.is_some() {
+            // The instrumented contract is compiled and cached separately, as the flag is part of
+            // the cache key.
+            let mut profiling_config = RuntimeConfig::clone(&config);
+            Arc::make_mut(&mut profiling_config.wasm_config).function_gas_profiling = true;
+            config = Arc::new(profiling_config);
+        }
         let apply_state = ApplyState {
             apply_reason: None,
             block_height: view_state.block_height,
@@ -218,7 +227,7 @@ impl TrieViewer {
             gas_limit: None,
             random_seed: root,
             current_protocol_version: view_state.current_protocol_version,
-            config: config.clone(),
+            config: Arc::clone(&config),
             cache: view_state.cache,
             is_new_chunk: false,
             migration_data: Arc::new(MigrationData::default()),
@@ -248,7 +257,7 @@ impl TrieViewer {
             priority: 0,
         });
         let pipeline = ReceiptPreparationPipeline::new(
-            Arc::clone(config),
+            Arc::clone(&config),
             apply_state.cache.as_ref().map(|v| v.handle()),
             apply_state.current_protocol_version,
             state_update.contract_storage.clone(),
@@ -277,7 +286,7 @@ impl TrieViewer {
             [].into(),
             &function_call,
             &empty_hash,
-            config,
+            &config,
             true,
             view_config,
         )
@@ -287,6 +296,17 @@ impl TrieViewer {
             (elapsed.as_secs() as f64 / 1_000.0) + f64::from(elapsed.subsec_nanos()) / 1_000_000.0;
         let time_str = format!("{:.*}ms", 2, time_ms);
 
+        if let (Some(output), Some(profile)) = (function_gas_profile, &outcome.function_gas_profile)
+        {
+            // Names are best effort, the profile is still useful with the function indices.
+            let names = get_account_code(&state_update, contract_id, code_hash)
+                .ok()
+                .flatten()
+                .map(|code| near_vm_runner::prepare::function_names(code.code()))
+                .unwrap_or_default();
+            *output = profile.to_folded(&names);
+        }
+
         if let Some(err) = outcome.aborted {
             logs.extend(outcome.logs);
             let message = format!("wasm execution failed with error: {:?}", err);
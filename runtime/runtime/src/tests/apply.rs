@@ -1,6 +1,7 @@
 use super::{to_yocto, GAS_PRICE};
 use crate::config::safe_add_gas;
 use crate::congestion_control::{compute_receipt_congestion_gas, compute_receipt_size};
+use crate::simulation::SimulationError;
 use crate::tests::{create_receipt_with_actions, set_sha256_cost, MAX_ATTACHED_GAS};
 use crate::total_prepaid_exec_fees;
 use crate::{ApplyResult, ApplyState, Runtime, ValidatorAccountsUpdate};
@@ -14,16 +15,22 @@ use near_primitives::bandwidth_scheduler::BlockBandwidthRequests;
 use near_primitives::congestion_info::{
     BlockCongestionInfo, CongestionControl, CongestionInfo, ExtendedCongestionInfo,
 };
-use near_primitives::errors::{ActionErrorKind, FunctionCallError, TxExecutionError};
+use near_primitives::errors::{
+    ActionErrorKind, FunctionCallError, InvalidTxError, TxExecutionError,
+};
 use near_primitives::hash::{hash, CryptoHash};
-use near_primitives::receipt::{ActionReceipt, Receipt, ReceiptEnum, ReceiptPriority, ReceiptV0};
+use near_primitives::receipt::{
+    ActionReceipt, GlobalContractChange, Receipt, ReceiptEnum, ReceiptPriority, ReceiptV0,
+};
 use near_primitives::runtime::migration_data::{MigrationData, MigrationFlags};
-use near_primitives::shard_layout::ShardUId;
+use near_primitives::shard_layout::{ShardLayout, ShardUId};
 use near_primitives::stateless_validation::contract_distribution::CodeHash;
 use near_primitives::test_utils::{account_new, MockEpochInfoProvider};
 use near_primitives::transaction::{
-    AddKeyAction, DeleteKeyAction, DeployContractAction, ExecutionOutcomeWithId, ExecutionStatus,
-    FunctionCallAction, SignedTransaction, TransferAction,
+    AddKeyAction, DeleteAccountAction, DeleteKeyAction, DeployContractAction,
+    DeployContractWithInitAction, DeployGlobalContractAction, ExecutionOutcomeWithId,
+    ExecutionStatus, FunctionCallAction, SignedTransaction, TransferAction,
+    UseGlobalContractAction,
 };
 use near_primitives::trie_key::TrieKey;
 use near_primitives::types::{
@@ -31,11 +38,12 @@ use near_primitives::types::{
 };
 use near_primitives::utils::create_receipt_id_from_transaction;
 use near_primitives::version::{ProtocolFeature, PROTOCOL_VERSION};
+use near_primitives::views::{ExecutionTraceArgView, ExecutionTraceEventView};
 use near_store::test_utils::TestTriesBuilder;
 use near_store::trie::receipts_column_helper::ShardsOutgoingReceiptBuffer;
 use near_store::{
-    get_account, set_access_key, set_account, MissingTrieValueContext, ShardTries, StorageError,
-    Trie,
+    get_account, get_global_contract_owner, has_global_contract_code, set_access_key, set_account,
+    MissingTrieValueContext, ShardTries, StorageError, Trie,
 };
 use near_vm_runner::{ContractCode, FilesystemContractRuntimeCache};
 use std::collections::{HashMap, HashSet};
@@ -1872,6 +1880,83 @@ fn test_deploy_and_call_in_same_receipt() {
     );
 }
 
+#[test]
+fn test_function_call_execution_trace() {
+    let (runtime, tries, root, mut apply_state, signers, epoch_info_provider) =
+        setup_runtime(vec![alice_account()], to_yocto(1_000_000), to_yocto(500_000), 1);
+
+    let mut config = RuntimeConfig::free();
+    Arc::make_mut(&mut config.wasm_config).execution_tracing = true;
+    apply_state.config = Arc::new(config);
+
+    let key = 42u64.to_le_bytes();
+    let value = 7u64.to_le_bytes();
+    let receipt = create_receipt_with_actions(
+        alice_account(),
+        signers[0].clone(),
+        vec![
+            Action::DeployContract(DeployContractAction {
+                code: near_test_contracts::rs_contract().to_vec(),
+            }),
+            Action::FunctionCall(Box::new(FunctionCallAction {
+                method_name: "write_key_value".to_string(),
+                args: [key, value].concat(),
+                gas: 1,
+                deposit: 0,
+            })),
+        ],
+    );
+    let receipt_id = *receipt.receipt_id();
+
+    let apply_result = runtime
+        .apply(
+            tries.get_trie_for_shard(ShardUId::single_shard(), root),
+            &None,
+            &apply_state,
+            &[receipt],
+            &[],
+            &epoch_info_provider,
+            Default::default(),
+        )
+        .unwrap();
+
+    let [trace] = &apply_result.receipt_traces[..] else {
+        panic!("expected a single receipt trace, got {:?}", apply_result.receipt_traces);
+    };
+    assert_eq!(trace.receipt_id, receipt_id);
+    assert_eq!(trace.receiver_id, alice_account());
+    assert!(!trace.failed);
+    let [call] = &trace.function_calls[..] else {
+        panic!("expected a single function call trace, got {:?}", trace.function_calls);
+    };
+    assert_eq!(call.action_index, 1);
+    assert_eq!(call.method_name, "write_key_value");
+    assert_eq!(call.error, None);
+
+    let step = |op: &str| call.steps.iter().find(|step| step.op == op).unwrap();
+    let write = step("storage_write");
+    assert_eq!(
+        write.args,
+        [
+            ExecutionTraceArgView::Bytes(key.to_vec()),
+            ExecutionTraceArgView::Bytes(value.to_vec()),
+            ExecutionTraceArgView::Value(1),
+        ]
+    );
+    assert_eq!(write.result, Some(0));
+    assert_eq!(
+        write.events,
+        [ExecutionTraceEventView::StorageWrite {
+            key: key.to_vec(),
+            value: value.to_vec(),
+            evicted: None
+        }]
+    );
+    // The contract returns the result of `storage_write` read back from its memory.
+    let value_return = step("value_return");
+    assert_eq!(value_return.args, [ExecutionTraceArgView::Bytes(0u64.to_le_bytes().to_vec())]);
+}
+
 // Tests the case in which deploy and call are contained in the same receipt and function call fails due to exceeding gas limit.
 #[test]
 fn test_deploy_and_call_in_same_receipt_with_failed_call() {
@@ -1921,6 +2006,279 @@ fn test_deploy_and_call_in_same_receipt_with_failed_call() {
     assert_eq!(apply_result.contract_updates.contract_deploy_hashes(), HashSet::new());
 }
 
+// Tests that the code deployed by a deploy with init action is kept only if the call succeeds.
+#[test]
+fn test_deploy_contract_with_init() {
+    if !ProtocolFeature::DeployContractWithInit.enabled(PROTOCOL_VERSION) {
+        return;
+    }
+    let contract_code = ContractCode::new(near_test_contracts::rs_contract().to_vec(), None);
+    for (method_name, expected_deploys) in [
+        ("ext_sha256", HashSet::from([CodeHash(*contract_code.hash())])),
+        ("missing_method", HashSet::new()),
+    ] {
+        let (runtime, tries, root, mut apply_state, signers, epoch_info_provider) = setup_runtime(
+            vec![alice_account(), bob_account()],
+            to_yocto(1_000_000),
+            to_yocto(500_000),
+            1,
+        );
+        apply_state.config = Arc::new(RuntimeConfig::free());
+        let initial_account = get_account(
+            &tries.get_trie_for_shard(ShardUId::single_shard(), root),
+            &alice_account(),
+        )
+        .unwrap()
+        .unwrap();
+
+        let receipt = create_receipt_with_actions(
+            alice_account(),
+            signers[0].clone(),
+            vec![Action::DeployContractWithInit(Box::new(DeployContractWithInitAction {
+                code: contract_code.code().to_vec(),
+                method_name: method_name.to_string(),
+                args: b"first".to_vec(),
+                gas: 1,
+            }))],
+        );
+
+        let apply_result = runtime
+            .apply(
+                tries.get_trie_for_shard(ShardUId::single_shard(), root).recording_reads(),
+                &None,
+                &apply_state,
+                &[receipt],
+                &[],
+                &epoch_info_provider,
+                Default::default(),
+            )
+            .unwrap();
+
+        let succeeded =
+            matches!(apply_result.outcomes[0].outcome.status, ExecutionStatus::SuccessValue(_));
+        assert_eq!(succeeded, !expected_deploys.is_empty(), "{method_name}");
+        assert_eq!(apply_result.contract_updates.contract_deploy_hashes(), expected_deploys);
+
+        // A failed initialization must revert the deployment along with the storage it took.
+        let root = commit_apply_result(&apply_result, &mut apply_state, &tries);
+        let account = get_account(
+            &tries.get_trie_for_shard(ShardUId::single_shard(), root),
+            &alice_account(),
+        )
+        .unwrap()
+        .unwrap();
+        if succeeded {
+            assert_eq!(account.code_hash(), *contract_code.hash());
+            assert!(account.storage_usage() > initial_account.storage_usage());
+        } else {
+            assert_eq!(account.code_hash(), initial_account.code_hash());
+            assert_eq!(account.storage_usage(), initial_account.storage_usage());
+        }
+    }
+}
+
+// Tests that code published once with a global deploy can be used and called by another account.
+#[test]
+fn test_deploy_and_use_global_contract() {
+    if !ProtocolFeature::GlobalContracts.enabled(PROTOCOL_VERSION) {
+        return;
+    }
+    let contract_code = ContractCode::new(near_test_contracts::rs_contract().to_vec(), None);
+    let code_hash = *contract_code.hash();
+    for (use_code_hash, expect_success) in [(code_hash, true), (hash(b"unknown"), false)] {
+        let (runtime, tries, root, mut apply_state, signers, epoch_info_provider) = setup_runtime(
+            vec![alice_account(), bob_account()],
+            to_yocto(1_000_000),
+            to_yocto(500_000),
+            1,
+        );
+        apply_state.config = Arc::new(RuntimeConfig::free());
+
+        let receipts = vec![
+            create_receipt_with_actions(
+                alice_account(),
+                signers[0].clone(),
+                vec![Action::DeployGlobalContract(DeployGlobalContractAction {
+                    code: contract_code.code().to_vec(),
+                })],
+            ),
+            create_receipt_with_actions(
+                bob_account(),
+                signers[1].clone(),
+                vec![
+                    Action::UseGlobalContract(Box::new(UseGlobalContractAction {
+                        code_hash: use_code_hash,
+                    })),
+                    Action::FunctionCall(Box::new(FunctionCallAction {
+                        method_name: "ext_sha256".to_string(),
+                        args: b"first".to_vec(),
+                        gas: 1,
+                        deposit: 0,
+                    })),
+                ],
+            ),
+        ];
+
+        let apply_result = runtime
+            .apply(
+                tries.get_trie_for_shard(ShardUId::single_shard(), root).recording_reads(),
+                &None,
+                &apply_state,
+                &receipts,
+                &[],
+                &epoch_info_provider,
+                Default::default(),
+            )
+            .unwrap();
+
+        assert_matches!(apply_result.outcomes[0].outcome.status, ExecutionStatus::SuccessValue(_));
+        if expect_success {
+            assert_matches!(
+                apply_result.outcomes[1].outcome.status,
+                ExecutionStatus::SuccessValue(_)
+            );
+        } else {
+            assert_matches!(
+                &apply_result.outcomes[1].outcome.status,
+                ExecutionStatus::Failure(TxExecutionError::ActionError(e))
+                    if e.kind == ActionErrorKind::GlobalContractDoesNotExist { code_hash: use_code_hash }
+            );
+        }
+        assert_eq!(
+            apply_result.contract_updates.contract_deploy_hashes(),
+            HashSet::from([CodeHash(code_hash)])
+        );
+    }
+}
+
+// Tests that a global deploy publishes the code on every shard and that deleting the account
+// publishing it removes the code and releases its storage.
+#[test]
+fn test_global_contract_distribution_and_removal() {
+    if !ProtocolFeature::GlobalContracts.enabled(PROTOCOL_VERSION) {
+        return;
+    }
+    let contract_code = ContractCode::new(near_test_contracts::rs_contract().to_vec(), None);
+    let code_hash = *contract_code.hash();
+    let shard_layout = ShardLayout::v2(
+        vec!["test1".parse().unwrap()],
+        vec![ShardId::new(0), ShardId::new(1)],
+        None,
+    );
+    let epoch_info_provider = MockEpochInfoProvider::default().with_shard_layout(shard_layout);
+    let (runtime, tries, mut root, mut apply_state, signers, _) =
+        setup_runtime(vec![alice_account(), bob_account()], to_yocto(1_000_000), 0, 10u64.pow(15));
+    apply_state.config = Arc::new(RuntimeConfig::free());
+    let shard_uid = ShardUId::single_shard();
+    let initial_storage_usage = {
+        let trie = tries.get_trie_for_shard(shard_uid, root);
+        get_account(&trie, &alice_account()).unwrap().unwrap().storage_usage()
+    };
+
+    let deploy = create_receipt_with_actions(
+        alice_account(),
+        signers[0].clone(),
+        vec![Action::DeployGlobalContract(DeployGlobalContractAction {
+            code: contract_code.code().to_vec(),
+        })],
+    );
+    let apply_result = runtime
+        .apply(
+            tries.get_trie_for_shard(shard_uid, root),
+            &None,
+            &apply_state,
+            &[deploy],
+            &[],
+            &epoch_info_provider,
+            Default::default(),
+        )
+        .unwrap();
+    assert_matches!(apply_result.outcomes[0].outcome.status, ExecutionStatus::SuccessValue(_));
+    root = commit_apply_result(&apply_result, &mut apply_state, &tries);
+
+    // The code is published on the current shard and sent to the other one.
+    let trie = tries.get_trie_for_shard(shard_uid, root);
+    assert!(has_global_contract_code(&trie, code_hash).unwrap());
+    let expected_storage_usage = contract_code.code().len() as u64
+        + code_hash.as_ref().len() as u64
+        + apply_state.config.fees.storage_usage_config.num_extra_bytes_record;
+    assert_eq!(
+        get_global_contract_owner(&trie, &alice_account(), code_hash).unwrap(),
+        Some(expected_storage_usage)
+    );
+    assert_eq!(
+        get_account(&trie, &alice_account()).unwrap().unwrap().storage_usage(),
+        initial_storage_usage + expected_storage_usage
+    );
+    let distributions: Vec<_> = apply_result
+        .outgoing_receipts
+        .iter()
+        .filter(|receipt| matches!(receipt.receipt(), ReceiptEnum::GlobalContractDistribution(_)))
+        .cloned()
+        .collect();
+    assert_eq!(distributions.len(), 1);
+    let distribution = distributions[0].clone();
+    assert_matches!(
+        distribution.receipt(),
+        ReceiptEnum::GlobalContractDistribution(r)
+            if r.target_shard == ShardId::new(1)
+                && r.already_delivered_shards == vec![ShardId::new(0)]
+                && matches!(&r.change, GlobalContractChange::Publish { code } if code == contract_code.code())
+    );
+
+    // Applying the distribution receipt on the other shard publishes the code there, without
+    // charging anyone for it.
+    let other_shard_uid = ShardUId::new(0, ShardId::new(1));
+    let (_, other_tries, other_root, mut other_apply_state, _, _) =
+        setup_runtime_for_shard(vec![], 0, 0, 10u64.pow(15), other_shard_uid);
+    other_apply_state.config = Arc::new(RuntimeConfig::free());
+    let other_apply_result = runtime
+        .apply(
+            other_tries.get_trie_for_shard(other_shard_uid, other_root),
+            &None,
+            &other_apply_state,
+            &[distribution],
+            &[],
+            &epoch_info_provider,
+            Default::default(),
+        )
+        .unwrap();
+    assert!(other_apply_result.outgoing_receipts.is_empty());
+    let other_root = commit_apply_result(&other_apply_result, &mut other_apply_state, &other_tries);
+    let other_trie = other_tries.get_trie_for_shard(other_shard_uid, other_root);
+    assert!(has_global_contract_code(&other_trie, code_hash).unwrap());
+    assert_eq!(get_global_contract_owner(&other_trie, &alice_account(), code_hash).unwrap(), None);
+
+    // Deleting the account removes the code from the current shard and from the other one.
+    let delete = create_receipt_with_actions(
+        alice_account(),
+        signers[0].clone(),
+        vec![Action::DeleteAccount(DeleteAccountAction { beneficiary_id: bob_account() })],
+    );
+    let apply_result = runtime
+        .apply(
+            tries.get_trie_for_shard(shard_uid, root),
+            &None,
+            &apply_state,
+            &[delete],
+            &[],
+            &epoch_info_provider,
+            Default::default(),
+        )
+        .unwrap();
+    assert_matches!(apply_result.outcomes[0].outcome.status, ExecutionStatus::SuccessValue(_));
+    root = commit_apply_result(&apply_result, &mut apply_state, &tries);
+    let trie = tries.get_trie_for_shard(shard_uid, root);
+    assert!(!has_global_contract_code(&trie, code_hash).unwrap());
+    assert_eq!(get_global_contract_owner(&trie, &alice_account(), code_hash).unwrap(), None);
+    assert!(apply_result.outgoing_receipts.iter().any(|receipt| matches!(
+        receipt.receipt(),
+        ReceiptEnum::GlobalContractDistribution(r)
+            if r.target_shard == ShardId::new(1)
+                && r.change == GlobalContractChange::Unpublish { code_hash }
+    )));
+}
+
 // Tests the case in which a function call is made to an account with no contract deployed.
 #[test]
 fn test_call_account_without_contract() {
@@ -2419,3 +2777,62 @@ fn test_deploy_and_call_local_receipts() {
         ActionErrorKind::FunctionCallError(FunctionCallError::MethodResolveError(_))
     );
 }
+
+#[test]
+fn test_simulate_transaction() {
+    let (runtime, tries, root, apply_state, signers, epoch_info_provider) =
+        setup_runtime(vec![alice_account(), bob_account()], to_yocto(1_000_000), 0, 10u64.pow(15));
+
+    let mut state_update = tries.new_trie_update(ShardUId::single_shard(), root);
+    let mut access_key = AccessKey::full_access();
+    access_key.nonce = 10;
+    set_access_key(&mut state_update, alice_account(), signers[0].public_key(), &access_key);
+    state_update.commit(StateChangeCause::InitialState);
+    let trie_changes = state_update.finalize().unwrap().trie_changes;
+    let mut store_update = tries.store_update();
+    let root = tries.apply_all(&trie_changes, ShardUId::single_shard(), &mut store_update);
+    store_update.commit().unwrap();
+
+    let tx = SignedTransaction::send_money(
+        5,
+        alice_account(),
+        bob_account(),
+        &*signers[0],
+        to_yocto(1),
+        CryptoHash::default(),
+    );
+    let simulate = |check_nonce| {
+        let tries = [(ShardId::new(0), tries.get_trie_for_shard(ShardUId::single_shard(), root))];
+        runtime.simulate_transaction(
+            tries.into(),
+            &apply_state,
+            &tx,
+            true,
+            check_nonce,
+            &epoch_info_provider,
+        )
+    };
+
+    assert_matches!(
+        simulate(true),
+        Err(SimulationError::InvalidTransaction(InvalidTxError::InvalidNonce {
+            tx_nonce: 5,
+            ak_nonce: 10
+        }))
+    );
+    let simulation = simulate(false).unwrap();
+    assert_eq!(simulation.transaction_outcome.id, tx.get_hash());
+    assert_matches!(
+        &simulation.receipt_outcomes[..],
+        [ExecutionOutcomeWithId { outcome, .. }, ..] if outcome.status == ExecutionStatus::SuccessValue(vec![])
+    );
+    assert!(simulation
+        .state_changes
+        .iter()
+        .any(|change| change.trie_key == TrieKey::Account { account_id: bob_account() }));
+
+    // Nothing is persisted by the simulation.
+    let state_update = tries.new_trie_update(ShardUId::single_shard(), root);
+    let bob = get_account(&state_update, &bob_account()).unwrap().unwrap();
+    assert_eq!(bob.amount(), to_yocto(1_000_000));
+}
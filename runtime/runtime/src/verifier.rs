@@ -9,10 +9,14 @@ use near_primitives::checked_feature;
 use near_primitives::errors::{
     ActionsValidationError, InvalidAccessKeyError, InvalidTxError, ReceiptValidationError,
 };
-use near_primitives::receipt::{ActionReceipt, DataReceipt, Receipt, ReceiptEnum};
+use near_primitives::receipt::{
+    ActionReceipt, DataReceipt, GlobalContractChange, GlobalContractDistributionReceipt, Receipt,
+    ReceiptEnum,
+};
 use near_primitives::transaction::DeleteAccountAction;
 use near_primitives::transaction::{
-    Action, AddKeyAction, DeployContractAction, FunctionCallAction, SignedTransaction, StakeAction,
+    Action, AddKeyAction, DeployContractAction, DeployContractWithInitAction,
+    DeployGlobalContractAction, FunctionCallAction, SignedTransaction, StakeAction,
 };
 use near_primitives::types::{AccountId, Balance};
 use near_primitives::types::{BlockHeight, StorageUsage};
@@ -322,9 +326,34 @@ pub(crate) fn validate_receipt(
         ReceiptEnum::Data(data_receipt) | ReceiptEnum::PromiseResume(data_receipt) => {
             validate_data_receipt(limit_config, data_receipt)
         }
+        ReceiptEnum::GlobalContractDistribution(distribution_receipt) => {
+            validate_global_contract_distribution_receipt(limit_config, distribution_receipt)
+        }
     }
 }
 
+/// Validates given global contract distribution receipt. Checks that published code
+/// does not exceed the contract size limit.
+fn validate_global_contract_distribution_receipt(
+    limit_config: &LimitConfig,
+    receipt: &GlobalContractDistributionReceipt,
+) -> Result<(), ReceiptValidationError> {
+    match &receipt.change {
+        GlobalContractChange::Publish { code } => {
+            if code.len() as u64 > limit_config.max_contract_size {
+                return Err(ReceiptValidationError::ActionsValidation(
+                    ActionsValidationError::ContractSizeExceeded {
+                        size: code.len() as u64,
+                        limit: limit_config.max_contract_size,
+                    },
+                ));
+            }
+        }
+        GlobalContractChange::Unpublish { .. } => {}
+    }
+    Ok(())
+}
+
 /// Validates given ActionReceipt. Checks validity of the number of input data dependencies and all actions.
 fn validate_action_receipt(
     limit_config: &LimitConfig,
@@ -431,6 +460,20 @@ pub fn validate_action(
         Action::DeleteKey(_) => Ok(()),
         Action::DeleteAccount(a) => validate_delete_action(a),
         Action::Delegate(a) => validate_delegate_action(limit_config, a, current_protocol_version),
+        Action::DeployContractWithInit(a) => {
+            check_feature_enabled(
+                ProtocolFeature::DeployContractWithInit,
+                current_protocol_version,
+            )?;
+            validate_deploy_contract_with_init_action(limit_config, a)
+        }
+        Action::DeployGlobalContract(a) => {
+            check_feature_enabled(ProtocolFeature::GlobalContracts, current_protocol_version)?;
+            validate_deploy_global_contract_action(limit_config, a)
+        }
+        Action::UseGlobalContract(_) => {
+            check_feature_enabled(ProtocolFeature::GlobalContracts, current_protocol_version)
+        }
     }
 }
 
@@ -459,6 +502,21 @@ fn validate_deploy_contract_action(
     Ok(())
 }
 
+/// Validates `DeployGlobalContractAction`. Checks that the given contract size doesn't exceed the limit.
+fn validate_deploy_global_contract_action(
+    limit_config: &LimitConfig,
+    action: &DeployGlobalContractAction,
+) -> Result<(), ActionsValidationError> {
+    if action.code.len() as u64 > limit_config.max_contract_size {
+        return Err(ActionsValidationError::ContractSizeExceeded {
+            size: action.code.len() as u64,
+            limit: limit_config.max_contract_size,
+        });
+    }
+
+    Ok(())
+}
+
 /// Validates `FunctionCallAction`. Checks that the method name length doesn't exceed the limit and
 /// the length of the arguments doesn't exceed the limit.
 fn validate_function_call_action(
@@ -486,6 +544,40 @@ fn validate_function_call_action(
     Ok(())
 }
 
+/// Validates `DeployContractWithInitAction`. Checks the contract size like for
+/// `DeployContractAction` and the method call like for `FunctionCallAction`.
+fn validate_deploy_contract_with_init_action(
+    limit_config: &LimitConfig,
+    action: &DeployContractWithInitAction,
+) -> Result<(), ActionsValidationError> {
+    if action.code.len() as u64 > limit_config.max_contract_size {
+        return Err(ActionsValidationError::ContractSizeExceeded {
+            size: action.code.len() as u64,
+            limit: limit_config.max_contract_size,
+        });
+    }
+
+    if action.gas == 0 {
+        return Err(ActionsValidationError::FunctionCallZeroAttachedGas);
+    }
+
+    if action.method_name.len() as u64 > limit_config.max_length_method_name {
+        return Err(ActionsValidationError::FunctionCallMethodNameLengthExceeded {
+            length: action.method_name.len() as u64,
+            limit: limit_config.max_length_method_name,
+        });
+    }
+
+    if action.args.len() as u64 > limit_config.max_arguments_length {
+        return Err(ActionsValidationError::FunctionCallArgumentsLengthExceeded {
+            length: action.args.len() as u64,
+            limit: limit_config.max_arguments_length,
+        });
+    }
+
+    Ok(())
+}
+
 /// Validates `StakeAction`. Checks that the `public_key` is a valid staking key.
 fn validate_stake_action(action: &StakeAction) -> Result<(), ActionsValidationError> {
     if !is_valid_staking_key(&action.public_key) {
@@ -555,7 +647,6 @@ fn validate_delete_action(action: &DeleteAccountAction) -> Result<(), ActionsVal
     Ok(())
 }
 
-#[cfg(feature = "protocol_feature_nonrefundable_transfer_nep491")]
 fn check_feature_enabled(
     feature: ProtocolFeature,
     current_protocol_version: ProtocolVersion,
@@ -591,6 +682,7 @@ mod tests {
     use near_primitives::test_utils::account_new;
     use near_primitives::transaction::{
         CreateAccountAction, DeleteAccountAction, DeleteKeyAction, StakeAction, TransferAction,
+        UseGlobalContractAction,
     };
     use near_primitives::types::{AccountId, Balance, MerkleHash, StateChangeCause};
     use near_primitives::version::PROTOCOL_VERSION;
@@ -1794,6 +1886,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_action_deploy_contract_with_init() {
+        let protocol_version = ProtocolFeature::DeployContractWithInit.protocol_version();
+        let action = |gas| {
+            Action::DeployContractWithInit(Box::new(DeployContractWithInitAction {
+                code: vec![1; 5],
+                method_name: "migrate".to_string(),
+                args: b"abc".to_vec(),
+                gas,
+            }))
+        };
+        validate_action(&test_limit_config(), &action(100), protocol_version)
+            .expect("valid action");
+        assert_eq!(
+            validate_action(&test_limit_config(), &action(0), protocol_version)
+                .expect_err("expected an error"),
+            ActionsValidationError::FunctionCallZeroAttachedGas,
+        );
+        assert_eq!(
+            validate_action(&test_limit_config(), &action(100), protocol_version - 1)
+                .expect_err("expected an error"),
+            ActionsValidationError::UnsupportedProtocolFeature {
+                protocol_feature: "DeployContractWithInit".to_string(),
+                version: protocol_version,
+            },
+        );
+    }
+
+    #[test]
+    fn test_validate_action_global_contracts() {
+        let protocol_version = ProtocolFeature::GlobalContracts.protocol_version();
+        let limit_config = test_limit_config();
+        let deploy = Action::DeployGlobalContract(DeployGlobalContractAction { code: vec![1; 5] });
+        let use_contract = Action::UseGlobalContract(Box::new(UseGlobalContractAction {
+            code_hash: CryptoHash::hash_bytes(&[1; 5]),
+        }));
+        for action in [&deploy, &use_contract] {
+            validate_action(&limit_config, action, protocol_version).expect("valid action");
+            assert_eq!(
+                validate_action(&limit_config, action, protocol_version - 1)
+                    .expect_err("expected an error"),
+                ActionsValidationError::UnsupportedProtocolFeature {
+                    protocol_feature: "GlobalContracts".to_string(),
+                    version: protocol_version,
+                },
+            );
+        }
+        let code_size = limit_config.max_contract_size + 1;
+        assert_eq!(
+            validate_action(
+                &limit_config,
+                &Action::DeployGlobalContract(DeployGlobalContractAction {
+                    code: vec![1; code_size as usize]
+                }),
+                protocol_version,
+            )
+            .expect_err("expected an error"),
+            ActionsValidationError::ContractSizeExceeded {
+                size: code_size,
+                limit: limit_config.max_contract_size
+            },
+        );
+    }
+
     #[test]
     fn test_validate_action_valid_transfer() {
         validate_action(
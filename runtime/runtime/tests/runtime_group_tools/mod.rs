@@ -79,7 +79,7 @@ impl StandaloneRuntime {
 
         let mut account_ids: HashSet<AccountId> = HashSet::new();
         genesis.for_each_record(|record: &StateRecord| {
-            account_ids.insert(state_record_to_account_id(record).clone());
+            account_ids.extend(state_record_to_account_id(record).cloned());
         });
         let writers = std::sync::atomic::AtomicUsize::new(0);
         let shard_uid =
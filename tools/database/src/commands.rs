@@ -10,6 +10,7 @@ use crate::make_snapshot::MakeSnapshotCommand;
 use crate::memtrie::LoadMemTrieCommand;
 use crate::resharding_v2::ReshardingV2Command;
 use crate::run_migrations::RunMigrationsCommand;
+use crate::signing_ledger::SigningLedgerCommand;
 use crate::state_perf::StatePerfCommand;
 use crate::write_to_db::WriteCryptoHashCommand;
 use clap::Parser;
@@ -64,6 +65,9 @@ enum SubCommand {
 
     /// Perform on demand resharding V2
     Resharding(ReshardingV2Command),
+
+    /// Export or import the ledger of messages signed by the validator key
+    SigningLedger(SigningLedgerCommand),
 }
 
 impl DatabaseCommand {
@@ -93,6 +97,7 @@ impl DatabaseCommand {
                 let near_config = load_config(home, genesis_validation);
                 cmd.run(near_config, home)
             }
+            SubCommand::SigningLedger(cmd) => cmd.run(home, genesis_validation),
         }
     }
 }
@@ -150,6 +150,7 @@ impl ReshardingV2Command {
             save_trie_changes: client_config.save_trie_changes,
             background_migration_threads: client_config.client_background_migration_threads,
             resharding_config: client_config.resharding_config,
+            save_latest_witnesses: client_config.save_latest_witnesses,
         };
         let chain = Chain::new(
             Clock::real(),
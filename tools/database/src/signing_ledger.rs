@@ -0,0 +1,74 @@
+use anyhow::Context;
+use near_chain::signing_ledger::{SigningLedger, SigningLedgerExport};
+use near_chain_configs::GenesisValidationMode;
+use near_store::NodeStorage;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Subcommand)]
+enum SigningLedgerSubCommand {
+    /// Writes the signing ledger of the validator key to a JSON file.
+    Export {
+        #[clap(long)]
+        output: PathBuf,
+    },
+    /// Merges a signing ledger exported on another host into this node's ledger. Fails if the
+    /// two ledgers contain conflicting signatures.
+    Import {
+        #[clap(long)]
+        input: PathBuf,
+    },
+}
+
+/// Moves the record of what the validator key has signed between hosts. Run `export` on the old
+/// host after stopping it, and `import` on the new host before starting it with the key.
+#[derive(clap::Args)]
+pub(crate) struct SigningLedgerCommand {
+    #[clap(subcommand)]
+    subcmd: SigningLedgerSubCommand,
+}
+
+impl SigningLedgerCommand {
+    pub(crate) fn run(
+        &self,
+        home_dir: &Path,
+        genesis_validation: GenesisValidationMode,
+    ) -> anyhow::Result<()> {
+        let near_config = nearcore::config::load_config(&home_dir, genesis_validation)?;
+        let signer = near_config
+            .validator_signer
+            .get()
+            .context("no validator key configured for the node")?;
+        let public_key = signer.public_key();
+        let opener = NodeStorage::opener(
+            home_dir,
+            near_config.config.archive,
+            &near_config.config.store,
+            near_config.config.cold_store.as_ref(),
+        );
+        let storage = opener.open()?;
+        let ledger = SigningLedger::new(storage.get_hot_store());
+
+        match &self.subcmd {
+            SigningLedgerSubCommand::Export { output } => {
+                let export = ledger.export(&public_key)?;
+                let file = std::fs::File::create(output)
+                    .with_context(|| format!("failed to create {}", output.display()))?;
+                serde_json::to_writer_pretty(file, &export)?;
+                println!(
+                    "Exported {} signed messages of {} to {}",
+                    export.messages.len(),
+                    public_key,
+                    output.display()
+                );
+            }
+            SigningLedgerSubCommand::Import { input } => {
+                let file = std::fs::File::open(input)
+                    .with_context(|| format!("failed to open {}", input.display()))?;
+                let export: SigningLedgerExport = serde_json::from_reader(file)?;
+                let imported = ledger.import(&public_key, &export)?;
+                println!("Imported {} signed messages of {}", imported, public_key);
+            }
+        }
+        Ok(())
+    }
+}
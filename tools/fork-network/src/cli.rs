@@ -525,6 +525,7 @@ impl ForkNetworkCommand {
         let mut contract_code_updated = 0;
         let mut postponed_receipts_updated = 0;
         let mut received_data_updated = 0;
+        let mut global_contracts_updated = 0;
         let mut fake_block_height = block_height + 1;
         for item in store.flat_store().iter(shard_uid) {
             let (key, value) = match item {
@@ -603,6 +604,31 @@ impl ForkNetworkCommand {
                         storage_mutator.set_delayed_receipt(index_delayed_receipt, &receipt)?;
                         index_delayed_receipt += 1;
                     }
+                    StateRecord::GlobalContractCode { .. } => {}
+                    StateRecord::GlobalContractPublisher { code_hash, account_id } => {
+                        // TODO(eth-implicit) Change back to is_implicit() when ETH-implicit accounts are supported.
+                        if account_id.get_account_type() == AccountType::NearImplicitAccount {
+                            let new_account_id = map_account(&account_id, None);
+                            storage_mutator
+                                .delete_global_contract_publisher(code_hash, account_id)?;
+                            storage_mutator
+                                .set_global_contract_publisher(code_hash, new_account_id)?;
+                            global_contracts_updated += 1;
+                        }
+                    }
+                    StateRecord::GlobalContractOwner { account_id, code_hash, storage_usage } => {
+                        // TODO(eth-implicit) Change back to is_implicit() when ETH-implicit accounts are supported.
+                        if account_id.get_account_type() == AccountType::NearImplicitAccount {
+                            let new_account_id = map_account(&account_id, None);
+                            storage_mutator.delete_global_contract_owner(account_id, code_hash)?;
+                            storage_mutator.set_global_contract_owner(
+                                new_account_id,
+                                code_hash,
+                                storage_usage,
+                            )?;
+                            global_contracts_updated += 1;
+                        }
+                    }
                 }
                 records_parsed += 1;
             } else {
@@ -619,7 +645,8 @@ impl ForkNetworkCommand {
                         + contract_code_updated
                         + postponed_receipts_updated
                         + index_delayed_receipt
-                        + received_data_updated,
+                        + received_data_updated
+                        + global_contracts_updated,
                 );
                 let state_root = storage_mutator.commit(&shard_uid, fake_block_height)?;
                 fake_block_height += 1;
@@ -639,7 +666,8 @@ impl ForkNetworkCommand {
                     + contract_code_updated
                     + postponed_receipts_updated
                     + index_delayed_receipt
-                    + received_data_updated,
+                    + received_data_updated
+                    + global_contracts_updated,
             );
             let state_root = storage_mutator.commit(&shard_uid, fake_block_height)?;
             fake_block_height += 1;
@@ -658,6 +686,7 @@ impl ForkNetworkCommand {
             postponed_receipts_updated,
             delayed_receipts_updated = index_delayed_receipt,
             received_data_updated,
+            global_contracts_updated,
             num_has_full_key = has_full_key.len(),
             "Pass 1 done"
         );
@@ -6,7 +6,7 @@ use near_primitives::hash::CryptoHash;
 use near_primitives::receipt::Receipt;
 use near_primitives::shard_layout::ShardUId;
 use near_primitives::trie_key::TrieKey;
-use near_primitives::types::{AccountId, StateRoot};
+use near_primitives::types::{AccountId, StateRoot, StorageUsage};
 use near_primitives::types::{StoreKey, StoreValue};
 use near_store::adapter::StoreUpdateAdapter;
 use near_store::{flat::FlatStateChanges, DBCol, ShardTries};
@@ -138,6 +138,42 @@ impl SingleShardStorageMutator {
         self.remove(TrieKey::DelayedReceipt { index })
     }
 
+    pub(crate) fn set_global_contract_publisher(
+        &mut self,
+        code_hash: CryptoHash,
+        account_id: AccountId,
+    ) -> anyhow::Result<()> {
+        self.set(TrieKey::GlobalContractPublisher { code_hash, account_id }, vec![])
+    }
+
+    pub(crate) fn delete_global_contract_publisher(
+        &mut self,
+        code_hash: CryptoHash,
+        account_id: AccountId,
+    ) -> anyhow::Result<()> {
+        self.remove(TrieKey::GlobalContractPublisher { code_hash, account_id })
+    }
+
+    pub(crate) fn set_global_contract_owner(
+        &mut self,
+        account_id: AccountId,
+        code_hash: CryptoHash,
+        storage_usage: StorageUsage,
+    ) -> anyhow::Result<()> {
+        self.set(
+            TrieKey::GlobalContractOwner { account_id, code_hash },
+            borsh::to_vec(&storage_usage)?,
+        )
+    }
+
+    pub(crate) fn delete_global_contract_owner(
+        &mut self,
+        account_id: AccountId,
+        code_hash: CryptoHash,
+    ) -> anyhow::Result<()> {
+        self.remove(TrieKey::GlobalContractOwner { account_id, code_hash })
+    }
+
     pub(crate) fn should_commit(&self, batch_size: u64) -> bool {
         self.updates.len() >= batch_size as usize
     }
@@ -246,6 +246,17 @@ pub(crate) fn map_records<P: AsRef<Path>>(
                 map_receipt(receipt, secret.as_ref(), &default_key);
                 records_seq.serialize_element(&r).unwrap();
             }
+            StateRecord::GlobalContractCode { .. } => {
+                records_seq.serialize_element(&r).unwrap();
+            }
+            StateRecord::GlobalContractPublisher { account_id, .. }
+            | StateRecord::GlobalContractOwner { account_id, .. } => {
+                // TODO(eth-implicit) Change back to is_implicit() when ETH-implicit accounts are supported.
+                if account_id.get_account_type() == AccountType::NearImplicitAccount {
+                    *account_id = crate::key_mapping::map_account(&account_id, secret.as_ref());
+                }
+                records_seq.serialize_element(&r).unwrap();
+            }
         };
     })?;
 
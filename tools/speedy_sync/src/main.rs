@@ -254,6 +254,7 @@ fn load_snapshot(load_cmd: LoadCmd) {
                 ReshardingConfig::default(),
                 "resharding_config",
             ),
+            save_latest_witnesses: false,
         },
         None,
         Arc::new(RayonAsyncComputationSpawner),
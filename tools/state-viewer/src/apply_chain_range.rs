@@ -172,7 +172,14 @@ fn apply_block_from_range(
             for tx in chunk.transactions() {
                 for action in tx.transaction.actions() {
                     has_contracts = has_contracts
-                        || matches!(action, Action::FunctionCall(_) | Action::DeployContract(_));
+                        || matches!(
+                            action,
+                            Action::FunctionCall(_)
+                                | Action::DeployContract(_)
+                                | Action::DeployContractWithInit(_)
+                                | Action::DeployGlobalContract(_)
+                                | Action::UseGlobalContract(_)
+                        );
                 }
             }
             if !has_contracts {
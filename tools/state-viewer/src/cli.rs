@@ -352,12 +352,16 @@ pub struct ApplyReceiptCmd {
     hash: String,
     #[clap(long, default_value = "trie")]
     storage: StorageSource,
+    /// Print the host function calls, storage accesses and promises made by the receipt as
+    /// JSON.
+    #[clap(long)]
+    trace: bool,
 }
 
 impl ApplyReceiptCmd {
     pub fn run(self, home_dir: &Path, near_config: NearConfig, store: Store) {
         let hash = CryptoHash::from_str(&self.hash).unwrap();
-        apply_receipt(home_dir, near_config, store, hash, self.storage).unwrap();
+        apply_receipt(home_dir, near_config, store, hash, self.storage, self.trace).unwrap();
     }
 }
 
@@ -24,6 +24,7 @@ use near_chain::types::{
 use near_chain::{Chain, ChainGenesis, ChainStore, ChainStoreAccess, ChainStoreUpdate, Error};
 use near_chain_configs::GenesisChangeConfig;
 use near_epoch_manager::{EpochManager, EpochManagerAdapter};
+use near_parameters::RuntimeConfigStore;
 use near_primitives::account::id::AccountId;
 use near_primitives::apply::ApplyChunkReason;
 use near_primitives::block::Block;
@@ -300,25 +301,41 @@ pub(crate) fn apply_receipt(
     store: Store,
     hash: CryptoHash,
     storage: StorageSource,
+    trace: bool,
 ) -> anyhow::Result<()> {
     let epoch_manager =
         EpochManager::new_arc_handle(store.clone(), &near_config.genesis.config, Some(home_dir));
-    let runtime = NightshadeRuntime::from_config(
+    let runtime_config_store = trace.then(|| {
+        RuntimeConfigStore::for_chain_id(&near_config.genesis.config.chain_id)
+            .with_execution_tracing()
+    });
+    let runtime = NightshadeRuntime::from_config_with_runtime_config_store(
         home_dir,
         store.clone(),
         &near_config,
         epoch_manager.clone(),
+        runtime_config_store,
     )
     .context("could not create the transaction runtime")?;
-    apply_chunk::apply_receipt(
+    let results = apply_chunk::apply_receipt(
         near_config.genesis.config.genesis_height,
         epoch_manager.as_ref(),
         runtime.as_ref(),
         store,
         hash,
         storage,
-    )
-    .map(|_| ())
+    )?;
+    if trace {
+        let receipt_trace = results
+            .iter()
+            .flat_map(|result| &result.receipt_traces)
+            .find(|receipt_trace| receipt_trace.receipt_id == hash)
+            .with_context(|| {
+                format!("receipt {hash} was applied, but it is not an action receipt")
+            })?;
+        println!("{}", serde_json::to_string_pretty(receipt_trace)?);
+    }
+    Ok(())
 }
 
 pub(crate) fn apply_tx(
@@ -1346,12 +1363,10 @@ fn get_state_stats_group_by<'a>(
                 let key_size = key.len() as u64;
                 let value_size = value.len() as u64;
                 let size = ByteSize::b(key_size + value_size);
-                let state_record = StateRecord::from_raw_key_value(key, value);
-                state_record.map(|state_record| StateStatsStateRecord {
-                    account_id: state_record_to_account_id(&state_record).clone(),
-                    state_record,
-                    size,
-                })
+                let state_record = StateRecord::from_raw_key_value(key, value)?;
+                // The records of the global contract registry don't belong to any account.
+                let account_id = state_record_to_account_id(&state_record)?.clone();
+                Some(StateStatsStateRecord { account_id, state_record, size })
             })
         })
         .into_iter();
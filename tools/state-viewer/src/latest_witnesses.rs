@@ -7,6 +7,9 @@ use near_chain::{Chain, ChainGenesis, ChainStore, DoomslugThresholdMode};
 use near_epoch_manager::shard_tracker::{ShardTracker, TrackedConfig};
 use near_epoch_manager::EpochManager;
 use near_primitives::stateless_validation::state_witness::ChunkStateWitness;
+use near_primitives::stateless_validation::witness_size_attribution::{
+    StorageProofSource, WitnessSizeBreakdown,
+};
 use near_primitives::types::EpochId;
 use near_store::Store;
 use near_time::Clock;
@@ -19,6 +22,9 @@ pub enum StateWitnessCmd {
     Dump(DumpWitnessesCmd),
     /// Validates given state witness.
     Validate(ValidateWitnessCmd),
+    /// Shows what the latest stored state witnesses consist of, and which receipts and accounts
+    /// recorded their storage proofs.
+    Sizes(WitnessSizesCmd),
 }
 
 impl StateWitnessCmd {
@@ -26,6 +32,7 @@ impl StateWitnessCmd {
         match self {
             StateWitnessCmd::Dump(cmd) => cmd.run(near_config, store),
             StateWitnessCmd::Validate(cmd) => cmd.run(home_dir, near_config, store),
+            StateWitnessCmd::Sizes(cmd) => cmd.run(near_config, store),
         }
     }
 }
@@ -98,6 +105,116 @@ impl DumpWitnessesCmd {
     }
 }
 
+#[derive(clap::Parser)]
+pub struct WitnessSizesCmd {
+    /// Select only witnesses with given block height.
+    #[arg(long)]
+    height: Option<u64>,
+    /// Select only witnesses for given shard id.
+    #[arg(long)]
+    shard_id: Option<u64>,
+    /// Select only witnesses for given epoch.
+    #[arg(long)]
+    epoch_id: Option<EpochId>,
+    /// Number of the largest accounts and receipts to show for each witness.
+    #[arg(long, default_value_t = 10)]
+    top: usize,
+    /// Print each breakdown as a line of JSON, with all the receipts.
+    #[arg(long)]
+    json: bool,
+}
+
+impl WitnessSizesCmd {
+    pub(crate) fn run(&self, near_config: NearConfig, store: Store) {
+        let chain_store = ChainStore::new(store, near_config.genesis.config.genesis_height, false);
+        let breakdowns = chain_store
+            .get_latest_witnesses_size_breakdown(self.height, self.shard_id, self.epoch_id)
+            .unwrap();
+        if !self.json {
+            println!("Found {} witnesses:", breakdowns.len());
+        }
+        for (key, breakdown) in breakdowns {
+            if self.json {
+                let line = serde_json::json!({
+                    "height": key.height,
+                    "shard_id": key.shard_id,
+                    "epoch_id": key.epoch_id,
+                    "breakdown": breakdown,
+                });
+                println!("{line}");
+                continue;
+            }
+            println!(
+                "height: {}, shard_id: {}, epoch_id: {:?}",
+                key.height, key.shard_id, key.epoch_id
+            );
+            self.print_breakdown(&breakdown);
+            println!();
+        }
+    }
+
+    fn print_breakdown(&self, breakdown: &WitnessSizeBreakdown) {
+        let size = |size: u64| bytesize::ByteSize::b(size).to_string();
+        println!("  witness:                          {}", size(breakdown.witness_size));
+        println!(
+            "  main state transition:            {}",
+            size(breakdown.main_state_transition_size)
+        );
+        println!(
+            "  source receipt proofs:            {}",
+            size(breakdown.source_receipt_proofs_size)
+        );
+        println!("  transactions:                     {}", size(breakdown.transactions_size));
+        println!(
+            "  implicit transitions:             {}",
+            size(breakdown.implicit_transitions_size)
+        );
+        println!("  new transactions:                 {}", size(breakdown.new_transactions_size));
+        println!(
+            "  new transactions validation state: {}",
+            size(breakdown.new_transactions_validation_state_size)
+        );
+        let Some(main_storage_proof) = &breakdown.main_storage_proof else {
+            println!(
+                "  storage proof attribution is only known for witnesses produced by this node"
+            );
+            return;
+        };
+        let summary = main_storage_proof.summary(self.top);
+        println!(
+            "  main storage proof: {}, upper bound {} of the soft limit {}, not attributed to receipts {}",
+            size(summary.total_size),
+            size(summary.total_size_upper_bound),
+            size(summary.size_soft_limit),
+            size(summary.unattributed_size),
+        );
+        println!("  largest accounts:");
+        for account in &summary.largest_accounts {
+            println!(
+                "    {:<64} {:>10} (upper bound {:>10}) in {} receipts and transactions",
+                account.account_id,
+                size(account.recorded_size),
+                size(account.recorded_size_upper_bound),
+                account.count,
+            );
+        }
+        println!("  largest receipts and transactions:");
+        for contribution in &summary.largest_contributions {
+            let source = match &contribution.source {
+                StorageProofSource::Transaction(hash) => format!("transaction {hash}"),
+                StorageProofSource::Receipt(receipt_id) => format!("receipt {receipt_id}"),
+            };
+            println!(
+                "    {:<56} {:<64} {:>10} (upper bound {:>10})",
+                source,
+                contribution.account_id,
+                size(contribution.recorded_size),
+                size(contribution.recorded_size_upper_bound),
+            );
+        }
+    }
+}
+
 #[derive(clap::Parser)]
 pub struct ValidateWitnessCmd {
     /// File with state witness saved as raw &[u8].
@@ -144,6 +261,7 @@ impl ValidateWitnessCmd {
                 epoch_manager.as_ref(),
                 runtime_adapter.as_ref(),
                 Some(processing_done_tracker),
+                None,
             )
             .unwrap();
         waiter.wait();
@@ -216,8 +216,10 @@ fn should_include_record(
     match account_allowlist {
         None => true,
         Some(allowlist) => {
-            let current_account_id = state_record_to_account_id(record);
-            allowlist.contains(current_account_id)
+            // The records of the global contract registry are kept, as they may be used by any
+            // of the selected accounts.
+            state_record_to_account_id(record)
+                .map_or(true, |current_account_id| allowlist.contains(current_account_id))
         }
     }
 }
@@ -0,0 +1,270 @@
+use crate::report::{format_near, SelectionDiff, SelectionReport};
+use crate::scenario::Scenario;
+use anyhow::Context;
+use borsh::BorshDeserialize;
+use near_chain_configs::GenesisValidationMode;
+use near_epoch_manager::{proposals_to_epoch_info, EpochManager};
+use near_primitives::epoch_info::EpochInfo;
+use near_primitives::epoch_manager::{EpochConfig, EpochConfigStore, AGGREGATOR_KEY};
+use near_primitives::types::{EpochHeight, EpochId, NumSeats, ProtocolVersion};
+use near_primitives::version::PROTOCOL_VERSION;
+use near_store::{DBCol, Mode, NodeStorage, Store};
+use nearcore::load_config;
+use std::path::{Path, PathBuf};
+
+/// Runs stake proposals and kickouts through the validator selection algorithm, and shows the
+/// seat price, the block and chunk producers and the chunk validator mandates of the resulting
+/// epoch.
+#[derive(clap::Parser)]
+pub struct ValidatorSelectionCommand {
+    #[clap(subcommand)]
+    input: SelectionInput,
+    #[clap(flatten)]
+    overrides: EpochConfigOverrides,
+    /// Number of heights at which chunk validators are assigned to shards, to show how evenly the
+    /// mandates are distributed.
+    #[clap(long, default_value_t = 100)]
+    sample_heights: u64,
+    /// Print the result as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(clap::Subcommand)]
+enum SelectionInput {
+    /// Selects the validators of an epoch stored in the DB again, from the proposals and kickouts
+    /// recorded two epochs before it, and compares the result with the stored epoch.
+    FromDb(FromDbCmd),
+    /// Selects the validators from a JSON scenario.
+    FromScenario(FromScenarioCmd),
+}
+
+#[derive(clap::Args)]
+struct FromDbCmd {
+    /// Epoch whose validators are selected.
+    #[clap(long, group("epoch"))]
+    epoch_id: Option<EpochId>,
+    #[clap(long, group("epoch"))]
+    epoch_height: Option<EpochHeight>,
+}
+
+#[derive(clap::Args)]
+struct FromScenarioCmd {
+    /// JSON file with the previous validators, the proposals and the kickouts.
+    path: PathBuf,
+}
+
+/// Changes of the epoch config that the selection is run with. The base config is the one of the
+/// chain for the protocol version of the selected epoch.
+#[derive(clap::Args)]
+struct EpochConfigOverrides {
+    /// Protocol version of the selected epoch, also used to pick the base epoch config.
+    #[clap(long)]
+    protocol_version: Option<ProtocolVersion>,
+    /// JSON file with an epoch config that replaces the base one. Other overrides are applied on
+    /// top of it.
+    #[clap(long)]
+    epoch_config: Option<PathBuf>,
+    #[clap(long)]
+    num_block_producer_seats: Option<NumSeats>,
+    #[clap(long)]
+    num_chunk_producer_seats: Option<NumSeats>,
+    #[clap(long)]
+    num_chunk_validator_seats: Option<NumSeats>,
+    #[clap(long)]
+    minimum_validators_per_shard: Option<NumSeats>,
+    #[clap(long)]
+    target_validator_mandates_per_shard: Option<NumSeats>,
+    #[clap(long)]
+    shuffle_shard_assignment_for_chunk_producers: Option<bool>,
+}
+
+impl EpochConfigOverrides {
+    /// Returns the config from `--epoch-config`, or else `base_config`, with the overrides.
+    fn apply(
+        &self,
+        base_config: impl FnOnce() -> anyhow::Result<EpochConfig>,
+    ) -> anyhow::Result<EpochConfig> {
+        let mut epoch_config = match &self.epoch_config {
+            Some(path) => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                serde_json::from_str(&content)
+                    .with_context(|| format!("failed to parse {}", path.display()))?
+            }
+            None => base_config()?,
+        };
+        let selection_config = &mut epoch_config.validator_selection_config;
+        if let Some(seats) = self.num_block_producer_seats {
+            epoch_config.num_block_producer_seats = seats;
+        }
+        if let Some(seats) = self.num_chunk_producer_seats {
+            selection_config.num_chunk_producer_seats = seats;
+        }
+        if let Some(seats) = self.num_chunk_validator_seats {
+            selection_config.num_chunk_validator_seats = seats;
+        }
+        if let Some(validators) = self.minimum_validators_per_shard {
+            selection_config.minimum_validators_per_shard = validators;
+        }
+        if let Some(shuffle) = self.shuffle_shard_assignment_for_chunk_producers {
+            selection_config.shuffle_shard_assignment_for_chunk_producers = shuffle;
+        }
+        if let Some(mandates) = self.target_validator_mandates_per_shard {
+            epoch_config.target_validator_mandates_per_shard = mandates;
+        }
+        Ok(epoch_config)
+    }
+}
+
+impl ValidatorSelectionCommand {
+    pub fn run(
+        self,
+        home_dir: &Path,
+        genesis_validation: GenesisValidationMode,
+    ) -> anyhow::Result<()> {
+        let (simulated, epoch_config, actual) = match &self.input {
+            SelectionInput::FromDb(cmd) => {
+                let (simulated, epoch_config, actual) =
+                    cmd.simulate(home_dir, genesis_validation, &self.overrides)?;
+                (simulated, epoch_config, Some(actual))
+            }
+            SelectionInput::FromScenario(cmd) => {
+                let (simulated, epoch_config) = cmd.simulate(&self.overrides)?;
+                (simulated, epoch_config, None)
+            }
+        };
+
+        let simulated = SelectionReport::new(&simulated, &epoch_config, self.sample_heights);
+        let actual = actual.map(|(actual, actual_config)| {
+            SelectionReport::new(&actual, &actual_config, self.sample_heights)
+        });
+        let diff = actual.as_ref().map(|actual| SelectionDiff::new(&simulated, actual));
+        if self.json {
+            let output = serde_json::json!({
+                "simulated": simulated,
+                "actual": actual,
+                "diff": diff,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            return Ok(());
+        }
+
+        simulated.print();
+        if let (Some(actual), Some(diff)) = (actual, diff) {
+            println!();
+            println!(
+                "Stored epoch: seat price {} NEAR, {} validators",
+                format_near(actual.seat_price),
+                actual.validators.len()
+            );
+            diff.print();
+        }
+        Ok(())
+    }
+}
+
+impl FromDbCmd {
+    /// Returns the simulated epoch info and its config, and the stored epoch info and its config.
+    fn simulate(
+        &self,
+        home_dir: &Path,
+        genesis_validation: GenesisValidationMode,
+        overrides: &EpochConfigOverrides,
+    ) -> anyhow::Result<(EpochInfo, EpochConfig, (EpochInfo, EpochConfig))> {
+        let near_config = load_config(home_dir, genesis_validation)
+            .unwrap_or_else(|e| panic!("Error loading config: {:#}", e));
+        let store_opener = NodeStorage::opener(
+            home_dir,
+            near_config.config.archive,
+            &near_config.config.store,
+            None,
+        );
+        let storage = store_opener.open_in_mode(Mode::ReadOnly)?;
+        let store = storage.get_hot_store();
+        let epoch_manager = EpochManager::new_arc_handle(
+            store.clone(),
+            &near_config.genesis.config,
+            Some(home_dir),
+        );
+        let epoch_manager = epoch_manager.read();
+
+        let epoch_id = match (self.epoch_id, self.epoch_height) {
+            (Some(epoch_id), _) => epoch_id,
+            (None, Some(epoch_height)) => find_epoch_by_height(&store, epoch_height)?,
+            (None, None) => anyhow::bail!("either --epoch-id or --epoch-height is required"),
+        };
+        // The epoch T+2 is identified by the hash of the last block of the epoch T, whose
+        // proposals and kickouts determine its validators.
+        let stored_epoch_info = epoch_manager.get_epoch_info(&epoch_id)?;
+        let last_block_info = epoch_manager
+            .get_block_info(&epoch_id.0)
+            .context("the proposals of the epoch are not recorded, is it one of the first two?")?;
+        let summary_epoch_id = *last_block_info.epoch_id();
+        let summary = epoch_manager.get_epoch_validator_info(&summary_epoch_id)?;
+        let prev_epoch_id = epoch_manager.get_next_epoch_id(&epoch_id.0)?;
+        let prev_epoch_info = epoch_manager.get_epoch_info(&prev_epoch_id)?;
+        let prev_epoch_config = epoch_manager.get_epoch_config(&prev_epoch_id)?;
+        let summary_protocol_version =
+            epoch_manager.get_epoch_info(&summary_epoch_id)?.protocol_version();
+
+        let protocol_version =
+            overrides.protocol_version.unwrap_or(summary.next_next_epoch_version);
+        let epoch_config = overrides
+            .apply(|| Ok(epoch_manager.get_config_for_protocol_version(protocol_version)?))?;
+        let has_same_shard_layout = prev_epoch_config.shard_layout == epoch_config.shard_layout;
+        // The seed and the rewards depend on the blocks of the epoch T, which aren't simulated, so
+        // they are taken from the stored epoch.
+        let simulated = proposals_to_epoch_info(
+            &epoch_config,
+            stored_epoch_info.rng_seed(),
+            &prev_epoch_info,
+            summary.all_proposals,
+            summary.validator_kickout,
+            stored_epoch_info.validator_reward().clone(),
+            stored_epoch_info.minted_amount(),
+            summary_protocol_version,
+            protocol_version,
+            has_same_shard_layout,
+        )?;
+        let stored_epoch_config = epoch_manager.get_epoch_config(&epoch_id)?;
+        Ok((simulated, epoch_config, (EpochInfo::clone(&stored_epoch_info), stored_epoch_config)))
+    }
+}
+
+impl FromScenarioCmd {
+    fn simulate(
+        &self,
+        overrides: &EpochConfigOverrides,
+    ) -> anyhow::Result<(EpochInfo, EpochConfig)> {
+        let content = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("failed to read {}", self.path.display()))?;
+        let scenario: Scenario = serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse {}", self.path.display()))?;
+        let protocol_version =
+            overrides.protocol_version.or(scenario.protocol_version).unwrap_or(PROTOCOL_VERSION);
+        let epoch_config = overrides.apply(|| {
+            let config_store = EpochConfigStore::for_chain_id(&scenario.chain_id, None)
+                .with_context(|| {
+                    format!("no epoch configs known for chain {}", scenario.chain_id)
+                })?;
+            Ok(EpochConfig::clone(config_store.get_config(protocol_version)))
+        })?;
+        let epoch_info = scenario.simulate(&epoch_config, protocol_version)?;
+        Ok((epoch_info, epoch_config))
+    }
+}
+
+fn find_epoch_by_height(store: &Store, epoch_height: EpochHeight) -> anyhow::Result<EpochId> {
+    for item in store.iter(DBCol::EpochInfo) {
+        let (key, value) = item?;
+        if key.as_ref() == AGGREGATOR_KEY {
+            continue;
+        }
+        let epoch_info = EpochInfo::try_from_slice(&value)?;
+        if epoch_info.epoch_height() == epoch_height {
+            return Ok(EpochId::try_from_slice(&key)?);
+        }
+    }
+    anyhow::bail!("no epoch with height {epoch_height} is stored")
+}
@@ -0,0 +1,4 @@
+pub mod cli;
+mod report;
+mod scenario;
+pub use cli::ValidatorSelectionCommand;
@@ -0,0 +1,296 @@
+use itertools::Itertools;
+use near_chain_configs::NEAR_BASE;
+use near_primitives::epoch_info::EpochInfo;
+use near_primitives::epoch_manager::EpochConfig;
+use near_primitives::serialize::dec_format;
+use near_primitives::types::{
+    AccountId, Balance, BlockHeight, EpochHeight, NumSeats, ProtocolVersion, ShardId,
+    ValidatorKickoutReason,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Role of one validator in the epoch.
+#[derive(serde::Serialize, Debug)]
+pub struct ValidatorReport {
+    pub account_id: AccountId,
+    #[serde(with = "dec_format")]
+    pub stake: Balance,
+    pub block_producer: bool,
+    /// Shards for which the validator produces chunks.
+    pub chunk_producer_shards: Vec<ShardId>,
+    /// Whole chunk validator mandates held by the validator.
+    pub mandates: u16,
+    #[serde(with = "dec_format")]
+    pub partial_mandate_weight: Balance,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct ShardReport {
+    pub shard_id: ShardId,
+    pub chunk_producers: Vec<AccountId>,
+    #[serde(with = "dec_format")]
+    pub chunk_producers_stake: Balance,
+    /// Fewest and most chunk validators assigned to the shard at the sampled heights.
+    pub min_chunk_validators: usize,
+    pub max_chunk_validators: usize,
+    /// Smallest and largest stake assigned to validate the shard at the sampled heights.
+    #[serde(with = "dec_format")]
+    pub min_chunk_validator_stake: Balance,
+    #[serde(with = "dec_format")]
+    pub max_chunk_validator_stake: Balance,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct MandatesReport {
+    pub target_mandates_per_shard: NumSeats,
+    #[serde(with = "dec_format")]
+    pub stake_per_mandate: Balance,
+    pub num_mandates: usize,
+    pub num_partials: usize,
+    /// Number of heights at which the chunk validators were assigned to shards.
+    pub sampled_heights: u64,
+}
+
+/// Outcome of the validator selection for one epoch.
+#[derive(serde::Serialize, Debug)]
+pub struct SelectionReport {
+    pub epoch_height: EpochHeight,
+    pub protocol_version: ProtocolVersion,
+    #[serde(with = "dec_format")]
+    pub seat_price: Balance,
+    #[serde(with = "dec_format")]
+    pub total_stake: Balance,
+    pub num_block_producers: usize,
+    pub mandates: MandatesReport,
+    pub shards: Vec<ShardReport>,
+    /// Ordered by stake, the largest first.
+    pub validators: Vec<ValidatorReport>,
+    pub kickouts: BTreeMap<AccountId, ValidatorKickoutReason>,
+}
+
+impl SelectionReport {
+    /// Summarizes `epoch_info`, sampling the chunk validator assignment at heights
+    /// `0..sample_heights`.
+    pub fn new(epoch_info: &EpochInfo, epoch_config: &EpochConfig, sample_heights: u64) -> Self {
+        let shard_layout = &epoch_config.shard_layout;
+        let validator_mandates = epoch_info.validator_mandates();
+        let stake_per_mandate = validator_mandates.stake_per_mandate();
+        let block_producers: HashSet<_> =
+            epoch_info.block_producers_settlement().iter().copied().collect();
+
+        let mut chunk_producer_shards = BTreeMap::<u64, Vec<ShardId>>::new();
+        for (shard_index, validator_ids) in
+            epoch_info.chunk_producers_settlement().iter().enumerate()
+        {
+            for validator_id in validator_ids {
+                chunk_producer_shards
+                    .entry(*validator_id)
+                    .or_default()
+                    .push(shard_layout.get_shard_id(shard_index));
+            }
+        }
+
+        // Mandates were introduced with stateless validation, before that there is nothing to
+        // sample.
+        let sample_heights = if stake_per_mandate > 0 { sample_heights } else { 0 };
+        let samples: Vec<_> = (0..sample_heights as BlockHeight)
+            .map(|height| epoch_info.sample_chunk_validators(height))
+            .collect();
+        let shards = epoch_info
+            .chunk_producers_settlement()
+            .iter()
+            .enumerate()
+            .map(|(shard_index, validator_ids)| {
+                let assignments = samples
+                    .iter()
+                    .map(|sample| sample.get(shard_index).map(Vec::as_slice).unwrap_or_default());
+                let num_validators = assignments.clone().map(|assignment| assignment.len());
+                let stakes = assignments
+                    .map(|assignment| assignment.iter().map(|(_, stake)| stake).sum::<Balance>());
+                ShardReport {
+                    shard_id: shard_layout.get_shard_id(shard_index),
+                    chunk_producers: validator_ids
+                        .iter()
+                        .map(|id| epoch_info.validator_account_id(*id).clone())
+                        .collect(),
+                    chunk_producers_stake: validator_ids
+                        .iter()
+                        .map(|id| epoch_info.validator_stake(*id))
+                        .sum(),
+                    min_chunk_validators: num_validators.clone().min().unwrap_or_default(),
+                    max_chunk_validators: num_validators.max().unwrap_or_default(),
+                    min_chunk_validator_stake: stakes.clone().min().unwrap_or_default(),
+                    max_chunk_validator_stake: stakes.max().unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let validators = epoch_info
+            .validators_iter()
+            .enumerate()
+            .map(|(validator_id, validator)| {
+                let validator_id = validator_id as u64;
+                let (mandates, partial_mandate_weight) = if stake_per_mandate > 0 {
+                    (
+                        validator.num_mandates(stake_per_mandate),
+                        validator.partial_mandate_weight(stake_per_mandate),
+                    )
+                } else {
+                    (0, 0)
+                };
+                ValidatorReport {
+                    stake: validator.stake(),
+                    account_id: validator.take_account_id(),
+                    block_producer: block_producers.contains(&validator_id),
+                    chunk_producer_shards: chunk_producer_shards
+                        .remove(&validator_id)
+                        .unwrap_or_default(),
+                    mandates,
+                    partial_mandate_weight,
+                }
+            })
+            .sorted_by(|a, b| b.stake.cmp(&a.stake).then_with(|| a.account_id.cmp(&b.account_id)))
+            .collect_vec();
+
+        Self {
+            epoch_height: epoch_info.epoch_height(),
+            protocol_version: epoch_info.protocol_version(),
+            seat_price: epoch_info.seat_price(),
+            total_stake: validators.iter().map(|v| v.stake).sum(),
+            num_block_producers: block_producers.len(),
+            mandates: MandatesReport {
+                target_mandates_per_shard: epoch_config.target_validator_mandates_per_shard,
+                stake_per_mandate,
+                num_mandates: validator_mandates.num_mandates(),
+                num_partials: validator_mandates.num_partials(),
+                sampled_heights: sample_heights,
+            },
+            shards,
+            validators,
+            kickouts: epoch_info
+                .validator_kickout()
+                .iter()
+                .map(|(account_id, reason)| (account_id.clone(), reason.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn print(&self) {
+        println!("Epoch height {}, protocol version {}", self.epoch_height, self.protocol_version);
+        println!("Seat price: {} NEAR", format_near(self.seat_price));
+        println!(
+            "Validators: {} with {} NEAR, of which {} block producers",
+            self.validators.len(),
+            format_near(self.total_stake),
+            self.num_block_producers
+        );
+        println!(
+            "Mandates: {} of {} NEAR each, {} partials, target {} per shard",
+            self.mandates.num_mandates,
+            format_near(self.mandates.stake_per_mandate),
+            self.mandates.num_partials,
+            self.mandates.target_mandates_per_shard
+        );
+
+        println!();
+        println!(
+            "{:>8} {:>15} {:>18} {:>16} {:>32}",
+            "SHARD", "CHUNK PRODUCERS", "STAKE", "VALIDATORS", "VALIDATOR STAKE"
+        );
+        for shard in &self.shards {
+            println!(
+                "{:>8} {:>15} {:>18} {:>16} {:>32}",
+                shard.shard_id,
+                shard.chunk_producers.len(),
+                format_near(shard.chunk_producers_stake),
+                format!("{}..{}", shard.min_chunk_validators, shard.max_chunk_validators),
+                format!(
+                    "{}..{}",
+                    format_near(shard.min_chunk_validator_stake),
+                    format_near(shard.max_chunk_validator_stake)
+                ),
+            );
+        }
+        if self.mandates.sampled_heights > 0 {
+            println!("Chunk validators are sampled at {} heights.", self.mandates.sampled_heights);
+        }
+
+        println!();
+        println!(
+            "{:<64} {:>18} {:>4} {:>12} {:>9} {:>18}",
+            "VALIDATOR", "STAKE", "BP", "CP SHARDS", "MANDATES", "PARTIAL"
+        );
+        for validator in &self.validators {
+            println!(
+                "{:<64} {:>18} {:>4} {:>12} {:>9} {:>18}",
+                validator.account_id,
+                format_near(validator.stake),
+                if validator.block_producer { "yes" } else { "" },
+                validator.chunk_producer_shards.iter().join(","),
+                validator.mandates,
+                format_near(validator.partial_mandate_weight),
+            );
+        }
+
+        if !self.kickouts.is_empty() {
+            println!();
+            println!("Kickouts:");
+            for (account_id, reason) in &self.kickouts {
+                println!("{account_id}: {reason:?}");
+            }
+        }
+    }
+}
+
+/// Differences between a simulated epoch and the one that is stored on chain.
+#[derive(serde::Serialize, Debug)]
+pub struct SelectionDiff {
+    /// Validators selected only in the simulation.
+    pub added_validators: Vec<AccountId>,
+    /// Validators selected only on chain.
+    pub removed_validators: Vec<AccountId>,
+    /// Validators selected in both, that produce chunks for different shards.
+    pub reassigned_chunk_producers: Vec<AccountId>,
+}
+
+impl SelectionDiff {
+    pub fn new(simulated: &SelectionReport, actual: &SelectionReport) -> Self {
+        let simulated_validators: BTreeMap<_, _> =
+            simulated.validators.iter().map(|v| (&v.account_id, v)).collect();
+        let actual_validators: BTreeMap<_, _> =
+            actual.validators.iter().map(|v| (&v.account_id, v)).collect();
+        let simulated_accounts: BTreeSet<_> = simulated_validators.keys().copied().collect();
+        let actual_accounts: BTreeSet<_> = actual_validators.keys().copied().collect();
+        Self {
+            added_validators: simulated_accounts
+                .difference(&actual_accounts)
+                .map(|&account_id| account_id.clone())
+                .collect(),
+            removed_validators: actual_accounts
+                .difference(&simulated_accounts)
+                .map(|&account_id| account_id.clone())
+                .collect(),
+            reassigned_chunk_producers: simulated_accounts
+                .intersection(&actual_accounts)
+                .filter(|account_id| {
+                    simulated_validators[*account_id].chunk_producer_shards
+                        != actual_validators[*account_id].chunk_producer_shards
+                })
+                .map(|&account_id| account_id.clone())
+                .collect(),
+        }
+    }
+
+    pub fn print(&self) {
+        println!("Added validators: {}", self.added_validators.iter().join(", "));
+        println!("Removed validators: {}", self.removed_validators.iter().join(", "));
+        println!(
+            "Chunk producers assigned to other shards: {}",
+            self.reassigned_chunk_producers.iter().join(", ")
+        );
+    }
+}
+
+pub(crate) fn format_near(balance: Balance) -> String {
+    format!("{:.3}", balance as f64 / NEAR_BASE as f64)
+}
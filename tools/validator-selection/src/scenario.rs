@@ -0,0 +1,140 @@
+use near_epoch_manager::proposals_to_epoch_info;
+use near_primitives::epoch_info::EpochInfo;
+use near_primitives::epoch_manager::EpochConfig;
+use near_primitives::hash::CryptoHash;
+use near_primitives::types::validator_stake::ValidatorStake;
+use near_primitives::types::{AccountId, AccountInfo, ProtocolVersion, ValidatorKickoutReason};
+use std::collections::HashMap;
+
+/// Inputs of the validator selection, written by hand instead of being recorded on chain.
+///
+/// ```json
+/// {
+///   "chain_id": "mainnet",
+///   "protocol_version": 73,
+///   "validators": [{"account_id": "a.near", "public_key": "ed25519:...", "amount": "1000"}],
+///   "proposals": [{"account_id": "b.near", "public_key": "ed25519:...", "amount": "500"}],
+///   "kickouts": {"a.near": "Unstaked"}
+/// }
+/// ```
+#[derive(serde::Deserialize, Debug)]
+pub struct Scenario {
+    /// Chain whose epoch configs are used, unless overridden.
+    #[serde(default = "default_chain_id")]
+    pub chain_id: String,
+    /// Protocol version of the selected epoch. Defaults to the current one.
+    pub protocol_version: Option<ProtocolVersion>,
+    /// Validators of the epoch preceding the selected one. They are selected in the same way as
+    /// the genesis validators.
+    pub validators: Vec<AccountInfo>,
+    /// Latest stake proposal of each account.
+    #[serde(default)]
+    pub proposals: Vec<AccountInfo>,
+    #[serde(default)]
+    pub kickouts: HashMap<AccountId, ValidatorKickoutReason>,
+    /// Seed of the shard assignment and the chunk validator sampling.
+    #[serde(default)]
+    pub rng_seed: CryptoHash,
+}
+
+fn default_chain_id() -> String {
+    near_primitives::chains::MAINNET.to_string()
+}
+
+fn to_validator_stakes(accounts: &[AccountInfo]) -> Vec<ValidatorStake> {
+    accounts
+        .iter()
+        .map(|account| {
+            ValidatorStake::new(
+                account.account_id.clone(),
+                account.public_key.clone(),
+                account.amount,
+            )
+        })
+        .collect()
+}
+
+impl Scenario {
+    /// Runs the selection of the validators of the epoch preceding the selected one, and then of
+    /// the selected epoch, both with `epoch_config`.
+    pub fn simulate(
+        &self,
+        epoch_config: &EpochConfig,
+        protocol_version: ProtocolVersion,
+    ) -> anyhow::Result<EpochInfo> {
+        let prev_epoch_info = proposals_to_epoch_info(
+            epoch_config,
+            [0; 32],
+            &EpochInfo::default(),
+            to_validator_stakes(&self.validators),
+            HashMap::new(),
+            HashMap::new(),
+            0,
+            protocol_version,
+            protocol_version,
+            false,
+        )?;
+        let epoch_info = proposals_to_epoch_info(
+            epoch_config,
+            self.rng_seed.0,
+            &prev_epoch_info,
+            to_validator_stakes(&self.proposals),
+            self.kickouts.clone(),
+            HashMap::new(),
+            0,
+            protocol_version,
+            protocol_version,
+            true,
+        )?;
+        Ok(epoch_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scenario;
+    use crate::report::SelectionReport;
+    use near_primitives::epoch_manager::EpochConfigStore;
+    use near_primitives::version::PROTOCOL_VERSION;
+
+    const NEAR: u128 = 10u128.pow(24);
+
+    fn account(account_id: &str, amount: u128) -> serde_json::Value {
+        serde_json::json!({
+            "account_id": account_id,
+            "public_key": "ed25519:11111111111111111111111111111111",
+            "amount": amount.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_simulate_scenario() {
+        let validators: Vec<_> = (0..20)
+            .map(|i| account(&format!("validator-{i}.near"), (i + 1) * 100_000 * NEAR))
+            .collect();
+        let scenario: Scenario = serde_json::from_value(serde_json::json!({
+            "validators": validators,
+            "proposals": [account("newcomer.near", 5_000_000 * NEAR)],
+            "kickouts": {"validator-0.near": "Unstaked"},
+        }))
+        .unwrap();
+        let config_store = EpochConfigStore::for_chain_id(&scenario.chain_id, None).unwrap();
+        let epoch_config = config_store.get_config(PROTOCOL_VERSION);
+
+        let epoch_info = scenario.simulate(epoch_config, PROTOCOL_VERSION).unwrap();
+        let report = SelectionReport::new(&epoch_info, epoch_config, 10);
+
+        assert_eq!(report.validators.len(), 20);
+        assert_eq!(report.validators[0].account_id.as_str(), "newcomer.near");
+        assert!(report.validators.iter().all(|v| v.account_id.as_str() != "validator-0.near"));
+        assert!(report.kickouts.keys().any(|account_id| account_id.as_str() == "validator-0.near"));
+        assert_eq!(report.shards.len(), epoch_config.shard_layout.shard_ids().count());
+        assert!(report.shards.iter().all(|shard| !shard.chunk_producers.is_empty()));
+        assert!(report.shards.iter().all(|shard| shard.min_chunk_validators > 0));
+
+        assert!(report.mandates.stake_per_mandate > 0);
+        let num_mandates: usize = report.validators.iter().map(|v| v.mandates as usize).sum();
+        assert_eq!(num_mandates, report.mandates.num_mandates);
+        assert_eq!(report.mandates.sampled_heights, 10);
+    }
+}